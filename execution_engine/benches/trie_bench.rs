@@ -1,6 +1,20 @@
-use criterion::{black_box, criterion_group, criterion_main, Bencher, Criterion};
+use std::{collections::HashMap, sync::Arc};
 
-use casper_execution_engine::storage::trie::{Pointer, PointerBlock, Trie};
+use criterion::{
+    black_box, criterion_group, criterion_main, BatchSize, Bencher, BenchmarkId, Criterion,
+};
+use lmdb::DatabaseFlags;
+use tempfile::tempdir;
+
+use casper_execution_engine::{
+    shared::newtypes::CorrelationId,
+    storage::{
+        global_state::lmdb::LmdbGlobalState,
+        transaction_source::lmdb::LmdbEnvironment,
+        trie::{Pointer, PointerBlock, Trie},
+        trie_store::lmdb::LmdbTrieStore,
+    },
+};
 use casper_hashing::Digest;
 use casper_types::{
     account::AccountHash,
@@ -8,6 +22,10 @@ use casper_types::{
     CLValue, Key, StoredValue,
 };
 
+const PUT_STORED_VALUES_MAX_DB_SIZE: usize = 1024 * 1024 * 1024;
+const PUT_STORED_VALUES_MAX_READERS: u32 = 8;
+const PUT_STORED_VALUES_PREFILLED_KEYS: u32 = 10_000;
+
 fn serialize_trie_leaf(b: &mut Bencher) {
     let leaf = Trie::Leaf {
         key: Key::Account(AccountHash::new([0; 32])),
@@ -60,6 +78,65 @@ fn deserialize_trie_node_pointer(b: &mut Bencher) {
     b.iter(|| Trie::<Key, StoredValue>::from_bytes(black_box(&node_bytes)));
 }
 
+fn account_values(range: std::ops::Range<u32>, value: i32) -> HashMap<Key, StoredValue> {
+    range
+        .map(|index| {
+            let mut bytes = [0; 32];
+            bytes[..4].copy_from_slice(&index.to_le_bytes());
+            (
+                Key::Account(AccountHash::new(bytes)),
+                StoredValue::CLValue(CLValue::from_t(value).unwrap()),
+            )
+        })
+        .collect()
+}
+
+fn put_stored_values(c: &mut Criterion) {
+    let temp_dir = tempdir().unwrap();
+    let environment = Arc::new(
+        LmdbEnvironment::new(
+            temp_dir.path(),
+            PUT_STORED_VALUES_MAX_DB_SIZE,
+            PUT_STORED_VALUES_MAX_READERS,
+            true,
+        )
+        .unwrap(),
+    );
+    let trie_store =
+        Arc::new(LmdbTrieStore::new(&environment, None, DatabaseFlags::empty()).unwrap());
+    let state = LmdbGlobalState::empty(environment, trie_store).unwrap();
+    let prestate_hash = state
+        .put_stored_values(
+            CorrelationId::new(),
+            state.empty_state_root_hash(),
+            account_values(0..PUT_STORED_VALUES_PREFILLED_KEYS, 0),
+        )
+        .unwrap();
+
+    let mut group = c.benchmark_group("put_stored_values");
+    for num_keys in [100_u32, 1_000, 5_000] {
+        // Half of the keys update existing entries, the other half are new.
+        let first_key = PUT_STORED_VALUES_PREFILLED_KEYS - num_keys / 2;
+        let stored_values = account_values(first_key..first_key + num_keys, 1);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(num_keys),
+            &stored_values,
+            |b, stored_values| {
+                b.iter_batched(
+                    || stored_values.clone(),
+                    |stored_values| {
+                        state
+                            .put_stored_values(CorrelationId::new(), prestate_hash, stored_values)
+                            .unwrap()
+                    },
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+    }
+    group.finish();
+}
+
 fn trie_bench(c: &mut Criterion) {
     c.bench_function("serialize_trie_leaf", serialize_trie_leaf);
     c.bench_function("deserialize_trie_leaf", deserialize_trie_leaf);
@@ -72,5 +149,5 @@ fn trie_bench(c: &mut Criterion) {
     );
 }
 
-criterion_group!(benches, trie_bench);
+criterion_group!(benches, trie_bench, put_stored_values);
 criterion_main!(benches);
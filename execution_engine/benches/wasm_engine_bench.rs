@@ -0,0 +1,136 @@
+use casper_wasm::{
+    builder,
+    elements::{BlockType, Instruction, Instructions, Local, Module, ValueType},
+};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+
+use casper_execution_engine::{
+    core::{
+        engine_state::EngineConfig,
+        execution::Error,
+        wasm_engine::{WasmEngineKind, WasmHost, WasmValue},
+    },
+    shared::{wasm_config::WasmConfig, wasm_prep},
+};
+use casper_types::ProtocolVersion;
+
+const LOOP_ITERATIONS: i32 = 100_000;
+
+/// The numbers of arithmetic operations per loop iteration to benchmark, as gas is charged by a
+/// host function call once per iteration, whatever its length.
+const OPERATIONS_PER_ITERATION: [usize; 2] = [1, 16];
+
+/// Builds a preprocessed module whose exported `call` function updates a value in memory in a loop
+/// of `LOOP_ITERATIONS`, each applying `operations` arithmetic operations to it.
+fn loop_module(operations: usize) -> Module {
+    let mut instructions = vec![
+        Instruction::I32Const(LOOP_ITERATIONS),
+        Instruction::SetLocal(0),
+        Instruction::Loop(BlockType::NoResult),
+        Instruction::I32Const(0),
+        Instruction::I32Const(0),
+        Instruction::I32Load(2, 0),
+    ];
+    for _ in 0..operations {
+        instructions.extend([
+            Instruction::I32Const(31),
+            Instruction::I32Mul,
+            Instruction::GetLocal(0),
+            Instruction::I32Add,
+        ]);
+    }
+    instructions.extend([
+        Instruction::I32Store(2, 0),
+        Instruction::GetLocal(0),
+        Instruction::I32Const(1),
+        Instruction::I32Sub,
+        Instruction::TeeLocal(0),
+        Instruction::BrIf(0),
+        Instruction::End,
+        Instruction::End,
+    ]);
+    let module = builder::module()
+        .memory()
+        .with_min(1)
+        .build()
+        .function()
+        .signature()
+        .build()
+        .body()
+        .with_locals(vec![Local::new(1, ValueType::I32)])
+        .with_instructions(Instructions::new(instructions))
+        .build()
+        .build()
+        .export()
+        .field("call")
+        .internal()
+        .func(0)
+        .build()
+        .build();
+    let bytes = casper_wasm::serialize(module).expect("should serialize");
+    wasm_prep::preprocess(WasmConfig::default(), &bytes).expect("should preprocess")
+}
+
+/// Accepts every host function call, which are only made to charge gas.
+struct GasHost;
+
+impl WasmHost for GasHost {
+    fn invoke_index(
+        &mut self,
+        _index: usize,
+        args: &[WasmValue],
+    ) -> Result<Option<WasmValue>, Error> {
+        black_box(args);
+        Ok(None)
+    }
+}
+
+fn compile(c: &mut Criterion) {
+    let module = loop_module(1);
+    let mut group = c.benchmark_group("compile");
+    for kind in WasmEngineKind::ALL {
+        let engine = kind.engine();
+        group.bench_function(format!("{:?}", kind), |b| {
+            b.iter_batched(
+                || module.clone(),
+                |module| engine.compile(module).expect("should compile"),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn invoke_loop(c: &mut Criterion) {
+    let engine_config = EngineConfig::default();
+    let mut group = c.benchmark_group("invoke_loop");
+    for operations in OPERATIONS_PER_ITERATION {
+        let module = loop_module(operations);
+        for kind in WasmEngineKind::ALL {
+            let compiled = kind
+                .engine()
+                .compile(module.clone())
+                .expect("should compile");
+            let id = BenchmarkId::new(format!("{:?}", kind), operations);
+            group.bench_function(id, |b| {
+                b.iter_batched(
+                    || {
+                        compiled
+                            .instantiate(ProtocolVersion::V1_0_0, &engine_config)
+                            .expect("should instantiate")
+                    },
+                    |(instance, _memory)| {
+                        instance
+                            .invoke_export("call", &mut GasHost)
+                            .expect("should succeed")
+                    },
+                    BatchSize::SmallInput,
+                )
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, compile, invoke_loop);
+criterion_main!(benches);
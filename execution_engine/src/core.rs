@@ -5,6 +5,7 @@ pub mod resolvers;
 pub mod runtime;
 pub mod runtime_context;
 pub mod tracking_copy;
+pub mod wasm_engine;
 
 pub use tracking_copy::{validate_balance_proof, validate_query_proof, ValidationError};
 
@@ -8,9 +8,12 @@ use std::collections::BTreeSet;
 use num_rational::Ratio;
 use num_traits::One;
 
-use casper_types::{account::AccountHash, PublicKey};
+use casper_types::{account::AccountHash, system::auction::SlashingOffence, PublicKey};
 
-use crate::shared::{system_config::SystemConfig, wasm_config::WasmConfig};
+use crate::{
+    core::wasm_engine::WasmEngineKind,
+    shared::{system_config::SystemConfig, wasm_config::WasmConfig},
+};
 
 pub use self::{fee_handling::FeeHandling, refund_handling::RefundHandling};
 
@@ -39,6 +42,8 @@ pub const DEFAULT_VESTING_SCHEDULE_LENGTH_MILLIS: u64 =
     VESTING_SCHEDULE_LENGTH_DAYS as u64 * DAY_MILLIS as u64;
 /// Default value for allowing auction bids.
 pub const DEFAULT_ALLOW_AUCTION_BIDS: bool = true;
+/// Default value for allowing delegations to be transferred between delegators.
+pub const DEFAULT_ALLOW_DELEGATION_TRANSFERS: bool = false;
 /// Default value for allowing unrestricted transfers.
 pub const DEFAULT_ALLOW_UNRESTRICTED_TRANSFERS: bool = true;
 /// Default gas cost refund ratio.
@@ -47,6 +52,18 @@ pub const DEFAULT_REFUND_HANDLING: RefundHandling = RefundHandling::Refund {
 };
 /// Default fee handling.
 pub const DEFAULT_FEE_HANDLING: FeeHandling = FeeHandling::PayToProposer;
+/// Default Wasm engine.
+pub const DEFAULT_WASM_ENGINE: WasmEngineKind = WasmEngineKind::Wasmi;
+/// Default fraction of stake slashed for any offence, i.e. slashing is disabled.
+pub const DEFAULT_SLASHING_FRACTION: Ratio<u64> = Ratio::new_raw(0, 1);
+/// Default maximum number of delegation slots a validator can reserve.
+pub const DEFAULT_MAX_RESERVED_DELEGATORS_PER_VALIDATOR: u32 = 1200;
+/// Default number of consecutive inactive eras after which a validator is jailed.
+pub const DEFAULT_INACTIVITY_JAIL_THRESHOLD: u32 = 1;
+/// Default number of eras a validator is jailed for on its first offence.
+pub const DEFAULT_JAIL_PERIOD_ERAS: u64 = 0;
+/// Default maximum number of eras a validator is jailed for.
+pub const DEFAULT_MAX_JAIL_PERIOD_ERAS: u64 = 0;
 
 /// The runtime configuration of the execution engine
 #[derive(Debug, Clone)]
@@ -64,6 +81,9 @@ pub struct EngineConfig {
     /// Vesting schedule period in milliseconds.
     vesting_schedule_period_millis: u64,
     max_delegators_per_validator: Option<u32>,
+    /// Maximum number of delegation slots a validator can reserve, whether or not the number of
+    /// delegators is limited by `max_delegators_per_validator`.
+    max_reserved_delegators_per_validator: u32,
     wasm_config: WasmConfig,
     system_config: SystemConfig,
     /// A private network specifies a list of administrative accounts.
@@ -81,6 +101,23 @@ pub struct EngineConfig {
     pub(crate) refund_handling: RefundHandling,
     /// Fee handling.
     pub(crate) fee_handling: FeeHandling,
+    /// The Wasm engine executing contracts.
+    wasm_engine: WasmEngineKind,
+    /// Fraction of stake slashed from an equivocating validator and its delegators.
+    equivocation_slashing_fraction: Ratio<u64>,
+    /// Fraction of stake slashed from an unavailable validator and its delegators.
+    unavailability_slashing_fraction: Ratio<u64>,
+    /// Number of consecutive eras a validator has to be inactive in before it is jailed.  Only eras
+    /// in which consensus reported the validator as inactive, i.e. without any participation,
+    /// count.
+    inactivity_jail_threshold: u32,
+    /// Number of eras a validator is jailed for on its first offence, doubling with every repeat
+    /// offence.
+    jail_period_eras: u64,
+    /// Maximum number of eras a validator is jailed for.
+    max_jail_period_eras: u64,
+    /// Allows delegators to transfer their delegations to other delegators without unbonding.
+    allow_delegation_transfers: bool,
 }
 
 impl Default for EngineConfig {
@@ -93,6 +130,7 @@ impl Default for EngineConfig {
             strict_argument_checking: DEFAULT_STRICT_ARGUMENT_CHECKING,
             vesting_schedule_period_millis: DEFAULT_VESTING_SCHEDULE_LENGTH_MILLIS,
             max_delegators_per_validator: None,
+            max_reserved_delegators_per_validator: DEFAULT_MAX_RESERVED_DELEGATORS_PER_VALIDATOR,
             wasm_config: WasmConfig::default(),
             system_config: SystemConfig::default(),
             administrative_accounts: Default::default(),
@@ -100,6 +138,13 @@ impl Default for EngineConfig {
             allow_unrestricted_transfers: DEFAULT_ALLOW_UNRESTRICTED_TRANSFERS,
             refund_handling: DEFAULT_REFUND_HANDLING,
             fee_handling: DEFAULT_FEE_HANDLING,
+            wasm_engine: DEFAULT_WASM_ENGINE,
+            equivocation_slashing_fraction: DEFAULT_SLASHING_FRACTION,
+            unavailability_slashing_fraction: DEFAULT_SLASHING_FRACTION,
+            inactivity_jail_threshold: DEFAULT_INACTIVITY_JAIL_THRESHOLD,
+            jail_period_eras: DEFAULT_JAIL_PERIOD_ERAS,
+            max_jail_period_eras: DEFAULT_MAX_JAIL_PERIOD_ERAS,
+            allow_delegation_transfers: DEFAULT_ALLOW_DELEGATION_TRANSFERS,
         }
     }
 }
@@ -133,6 +178,7 @@ impl EngineConfig {
             strict_argument_checking,
             vesting_schedule_period_millis,
             max_delegators_per_validator,
+            max_reserved_delegators_per_validator: DEFAULT_MAX_RESERVED_DELEGATORS_PER_VALIDATOR,
             wasm_config,
             system_config,
             administrative_accounts: Default::default(),
@@ -140,6 +186,13 @@ impl EngineConfig {
             allow_unrestricted_transfers: DEFAULT_ALLOW_UNRESTRICTED_TRANSFERS,
             refund_handling: DEFAULT_REFUND_HANDLING,
             fee_handling: DEFAULT_FEE_HANDLING,
+            wasm_engine: DEFAULT_WASM_ENGINE,
+            equivocation_slashing_fraction: DEFAULT_SLASHING_FRACTION,
+            unavailability_slashing_fraction: DEFAULT_SLASHING_FRACTION,
+            inactivity_jail_threshold: DEFAULT_INACTIVITY_JAIL_THRESHOLD,
+            jail_period_eras: DEFAULT_JAIL_PERIOD_ERAS,
+            max_jail_period_eras: DEFAULT_MAX_JAIL_PERIOD_ERAS,
+            allow_delegation_transfers: DEFAULT_ALLOW_DELEGATION_TRANSFERS,
         }
     }
 
@@ -183,6 +236,11 @@ impl EngineConfig {
         self.max_delegators_per_validator
     }
 
+    /// Returns the maximum number of delegation slots a validator can reserve.
+    pub fn max_reserved_delegators_per_validator(&self) -> u32 {
+        self.max_reserved_delegators_per_validator
+    }
+
     /// Returns the engine config's administrative accounts.
     pub fn administrative_accounts(&self) -> &BTreeSet<AccountHash> {
         &self.administrative_accounts
@@ -193,6 +251,11 @@ impl EngineConfig {
         self.allow_auction_bids
     }
 
+    /// Returns true if delegations can be transferred between delegators.
+    pub fn allow_delegation_transfers(&self) -> bool {
+        self.allow_delegation_transfers
+    }
+
     /// Returns true if unrestricted transfers are allowed.
     pub fn allow_unrestricted_transfers(&self) -> bool {
         self.allow_unrestricted_transfers
@@ -213,6 +276,35 @@ impl EngineConfig {
         self.fee_handling
     }
 
+    /// Returns the Wasm engine executing contracts.
+    pub fn wasm_engine(&self) -> WasmEngineKind {
+        self.wasm_engine
+    }
+
+    /// Returns the fraction of stake slashed for the given offence.
+    pub fn slashing_fraction(&self, offence: SlashingOffence) -> Ratio<u64> {
+        match offence {
+            SlashingOffence::Equivocation => self.equivocation_slashing_fraction,
+            SlashingOffence::Unavailability => self.unavailability_slashing_fraction,
+        }
+    }
+
+    /// Returns the number of consecutive eras a validator has to be inactive in before it is
+    /// jailed.
+    pub fn inactivity_jail_threshold(&self) -> u32 {
+        self.inactivity_jail_threshold
+    }
+
+    /// Returns the number of eras a validator is jailed for on its first offence.
+    pub fn jail_period_eras(&self) -> u64 {
+        self.jail_period_eras
+    }
+
+    /// Returns the maximum number of eras a validator is jailed for.
+    pub fn max_jail_period_eras(&self) -> u64 {
+        self.max_jail_period_eras
+    }
+
     /// Sets the `wasm_config.max_memory` to `new_value`.
     #[cfg(feature = "test-support")]
     pub fn set_max_memory(&mut self, new_value: u32) {
@@ -233,6 +325,7 @@ pub struct EngineConfigBuilder {
     strict_argument_checking: Option<bool>,
     vesting_schedule_period_millis: Option<u64>,
     max_delegators_per_validator: Option<u32>,
+    max_reserved_delegators_per_validator: Option<u32>,
     wasm_config: Option<WasmConfig>,
     system_config: Option<SystemConfig>,
     administrative_accounts: Option<BTreeSet<PublicKey>>,
@@ -240,6 +333,13 @@ pub struct EngineConfigBuilder {
     allow_unrestricted_transfers: Option<bool>,
     refund_handling: Option<RefundHandling>,
     fee_handling: Option<FeeHandling>,
+    wasm_engine: Option<WasmEngineKind>,
+    equivocation_slashing_fraction: Option<Ratio<u64>>,
+    unavailability_slashing_fraction: Option<Ratio<u64>>,
+    inactivity_jail_threshold: Option<u32>,
+    jail_period_eras: Option<u64>,
+    max_jail_period_eras: Option<u64>,
+    allow_delegation_transfers: Option<bool>,
 }
 
 impl EngineConfigBuilder {
@@ -287,6 +387,12 @@ impl EngineConfigBuilder {
         self
     }
 
+    /// Sets the max reserved delegators per validator config option.
+    pub fn with_max_reserved_delegators_per_validator(mut self, value: u32) -> Self {
+        self.max_reserved_delegators_per_validator = Some(value);
+        self
+    }
+
     /// Sets the wasm config options.
     pub fn with_wasm_config(mut self, wasm_config: WasmConfig) -> Self {
         self.wasm_config = Some(wasm_config);
@@ -354,6 +460,60 @@ impl EngineConfigBuilder {
         self
     }
 
+    /// Sets the Wasm engine executing contracts.
+    pub fn with_wasm_engine(mut self, wasm_engine: WasmEngineKind) -> Self {
+        self.wasm_engine = Some(wasm_engine);
+        self
+    }
+
+    /// Sets the fraction of stake slashed for an equivocation.
+    pub fn with_equivocation_slashing_fraction(mut self, fraction: Ratio<u64>) -> Self {
+        debug_assert!(
+            fraction <= Ratio::one(),
+            "slashing fraction should be in the range of [0, 1]"
+        );
+        self.equivocation_slashing_fraction = Some(fraction);
+        self
+    }
+
+    /// Sets the fraction of stake slashed for being unavailable.
+    pub fn with_unavailability_slashing_fraction(mut self, fraction: Ratio<u64>) -> Self {
+        debug_assert!(
+            fraction <= Ratio::one(),
+            "slashing fraction should be in the range of [0, 1]"
+        );
+        self.unavailability_slashing_fraction = Some(fraction);
+        self
+    }
+
+    /// Sets the number of consecutive eras a validator has to be inactive in before it is jailed.
+    pub fn with_inactivity_jail_threshold(mut self, threshold: u32) -> Self {
+        debug_assert!(
+            threshold > 0,
+            "inactivity jail threshold should be positive"
+        );
+        self.inactivity_jail_threshold = Some(threshold);
+        self
+    }
+
+    /// Sets the number of eras a validator is jailed for on its first offence.
+    pub fn with_jail_period_eras(mut self, jail_period_eras: u64) -> Self {
+        self.jail_period_eras = Some(jail_period_eras);
+        self
+    }
+
+    /// Sets the maximum number of eras a validator is jailed for.
+    pub fn with_max_jail_period_eras(mut self, max_jail_period_eras: u64) -> Self {
+        self.max_jail_period_eras = Some(max_jail_period_eras);
+        self
+    }
+
+    /// Sets the allow delegation transfers config option.
+    pub fn with_allow_delegation_transfers(mut self, allow_delegation_transfers: bool) -> Self {
+        self.allow_delegation_transfers = Some(allow_delegation_transfers);
+        self
+    }
+
     /// Builds a new [`EngineConfig`] object.
     pub fn build(self) -> EngineConfig {
         let max_query_depth = self.max_query_depth.unwrap_or(DEFAULT_MAX_QUERY_DEPTH);
@@ -383,6 +543,23 @@ impl EngineConfigBuilder {
             .unwrap_or(DEFAULT_ALLOW_UNRESTRICTED_TRANSFERS);
         let refund_handling = self.refund_handling.unwrap_or(DEFAULT_REFUND_HANDLING);
         let fee_handling = self.fee_handling.unwrap_or(DEFAULT_FEE_HANDLING);
+        let wasm_engine = self.wasm_engine.unwrap_or(DEFAULT_WASM_ENGINE);
+        let equivocation_slashing_fraction = self
+            .equivocation_slashing_fraction
+            .unwrap_or(DEFAULT_SLASHING_FRACTION);
+        let unavailability_slashing_fraction = self
+            .unavailability_slashing_fraction
+            .unwrap_or(DEFAULT_SLASHING_FRACTION);
+        let inactivity_jail_threshold = self
+            .inactivity_jail_threshold
+            .unwrap_or(DEFAULT_INACTIVITY_JAIL_THRESHOLD);
+        let jail_period_eras = self.jail_period_eras.unwrap_or(DEFAULT_JAIL_PERIOD_ERAS);
+        let max_jail_period_eras = self
+            .max_jail_period_eras
+            .unwrap_or(DEFAULT_MAX_JAIL_PERIOD_ERAS);
+        let allow_delegation_transfers = self
+            .allow_delegation_transfers
+            .unwrap_or(DEFAULT_ALLOW_DELEGATION_TRANSFERS);
 
         let strict_argument_checking = self
             .strict_argument_checking
@@ -391,6 +568,9 @@ impl EngineConfigBuilder {
             .vesting_schedule_period_millis
             .unwrap_or(DEFAULT_VESTING_SCHEDULE_LENGTH_MILLIS);
         let max_delegators_per_validator = self.max_delegators_per_validator;
+        let max_reserved_delegators_per_validator = self
+            .max_reserved_delegators_per_validator
+            .unwrap_or(DEFAULT_MAX_RESERVED_DELEGATORS_PER_VALIDATOR);
 
         EngineConfig {
             max_query_depth,
@@ -407,6 +587,14 @@ impl EngineConfigBuilder {
             strict_argument_checking,
             vesting_schedule_period_millis,
             max_delegators_per_validator,
+            max_reserved_delegators_per_validator,
+            wasm_engine,
+            equivocation_slashing_fraction,
+            unavailability_slashing_fraction,
+            inactivity_jail_threshold,
+            jail_period_eras,
+            max_jail_period_eras,
+            allow_delegation_transfers,
         }
     }
 }
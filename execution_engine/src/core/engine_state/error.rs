@@ -106,6 +106,9 @@ pub enum Error {
     /// Failed to retrieve accumulation purse from handle payment system contract.
     #[error("Failed to retrieve accumulation purse from the handle payment contract")]
     FailedToRetrieveAccumulationPurse,
+    /// A deploy passed an argument reserved for the system.
+    #[error("Reserved argument: {0}")]
+    ReservedArgument(String),
 }
 
 impl Error {
@@ -3,7 +3,8 @@
 use std::collections::VecDeque;
 
 use casper_types::{
-    bytesrepr::FromBytes, CLTyped, CLValue, Gas, Key, Motes, StoredValue, TransferAddr,
+    bytesrepr::FromBytes, CLTyped, CLValue, ContractEvent, Gas, Key, Motes, StoredValue,
+    TransferAddr,
 };
 
 use super::error;
@@ -49,6 +50,8 @@ pub enum ExecutionResult {
         cost: Gas,
         /// Journal of execution.
         execution_journal: ExecutionJournal,
+        /// The account or contract which paid for the deploy in place of its account, if any.
+        sponsor: Option<Key>,
     },
     /// Execution was finished successfully
     Success {
@@ -58,6 +61,10 @@ pub enum ExecutionResult {
         cost: Gas,
         /// Journal of execution.
         execution_journal: ExecutionJournal,
+        /// Events emitted by contracts.
+        events: Vec<ContractEvent>,
+        /// The account or contract which paid for the deploy in place of its account, if any.
+        sponsor: Option<Key>,
     },
 }
 
@@ -67,6 +74,8 @@ impl Default for ExecutionResult {
             execution_journal: Default::default(),
             transfers: Default::default(),
             cost: Default::default(),
+            events: Default::default(),
+            sponsor: None,
         }
     }
 }
@@ -94,6 +103,7 @@ impl ExecutionResult {
             transfers: Vec::default(),
             cost: Gas::default(),
             execution_journal: Default::default(),
+            sponsor: None,
         }
     }
 
@@ -144,6 +154,16 @@ impl ExecutionResult {
         }
     }
 
+    /// Returns list of events emitted by contracts.
+    ///
+    /// Events are only recorded for a successful execution, so this is empty for a failure.
+    pub fn events(&self) -> &[ContractEvent] {
+        match self {
+            ExecutionResult::Failure { .. } => &[],
+            ExecutionResult::Success { events, .. } => events,
+        }
+    }
+
     /// The journal of transforms regardless of variant.
     pub fn execution_journal(&self) -> &ExecutionJournal {
         match self {
@@ -166,21 +186,27 @@ impl ExecutionResult {
                 error,
                 transfers,
                 execution_journal,
+                sponsor,
                 ..
             } => ExecutionResult::Failure {
                 error,
                 transfers,
                 cost,
                 execution_journal,
+                sponsor,
             },
             ExecutionResult::Success {
                 transfers,
                 execution_journal,
+                events,
+                sponsor,
                 ..
             } => ExecutionResult::Success {
                 transfers,
                 cost,
                 execution_journal,
+                events,
+                sponsor,
             },
         }
     }
@@ -195,21 +221,27 @@ impl ExecutionResult {
                 error,
                 cost,
                 execution_journal,
+                sponsor,
                 ..
             } => ExecutionResult::Failure {
                 error,
                 transfers,
                 cost,
                 execution_journal,
+                sponsor,
             },
             ExecutionResult::Success {
                 cost,
                 execution_journal,
+                events,
+                sponsor,
                 ..
             } => ExecutionResult::Success {
                 transfers,
                 cost,
                 execution_journal,
+                events,
+                sponsor,
             },
         }
     }
@@ -225,24 +257,52 @@ impl ExecutionResult {
                 transfers,
                 cost,
                 execution_journal: _,
+                sponsor,
             } => ExecutionResult::Failure {
                 error,
                 transfers,
                 cost,
                 execution_journal,
+                sponsor,
             },
             ExecutionResult::Success {
                 transfers,
                 cost,
                 execution_journal: _,
+                events,
+                sponsor,
             } => ExecutionResult::Success {
                 transfers,
                 cost,
                 execution_journal,
+                events,
+                sponsor,
             },
         }
     }
 
+    /// Returns the account or contract which paid for the deploy in place of its account, if any.
+    pub fn sponsor(&self) -> Option<&Key> {
+        match self {
+            ExecutionResult::Failure { sponsor, .. } | ExecutionResult::Success { sponsor, .. } => {
+                sponsor.as_ref()
+            }
+        }
+    }
+
+    /// Returns a new execution result with an updated sponsor.
+    ///
+    /// This method preserves the [`ExecutionResult`] variant and updates the `sponsor` field
+    /// only.
+    pub fn with_sponsor(mut self, new_sponsor: Option<Key>) -> Self {
+        match &mut self {
+            ExecutionResult::Failure { sponsor, .. } | ExecutionResult::Success { sponsor, .. } => {
+                *sponsor = new_sponsor
+            }
+        }
+        self
+    }
+
     /// Returns error value, if possible.
     ///
     /// Returns a reference to a wrapped [`error::Error`] instance if the object is a failure
@@ -328,6 +388,7 @@ impl ExecutionResult {
             execution_journal,
             transfers,
             cost: gas_cost,
+            sponsor: None,
         })
     }
 
@@ -349,21 +410,27 @@ impl From<&ExecutionResult> for casper_types::ExecutionResult {
                 transfers,
                 cost,
                 execution_journal,
+                events,
+                sponsor,
             } => casper_types::ExecutionResult::Success {
                 effect: execution_journal.into(),
                 transfers: transfers.clone(),
                 cost: cost.value(),
+                events: events.clone(),
+                sponsor: *sponsor,
             },
             ExecutionResult::Failure {
                 error,
                 transfers,
                 cost,
                 execution_journal,
+                sponsor,
             } => casper_types::ExecutionResult::Failure {
                 effect: execution_journal.into(),
                 transfers: transfers.clone(),
                 cost: cost.value(),
                 error_message: error.to_string(),
+                sponsor: *sponsor,
             },
         }
     }
@@ -376,21 +443,27 @@ impl From<ExecutionResult> for casper_types::ExecutionResult {
                 transfers,
                 cost,
                 execution_journal,
+                events,
+                sponsor,
             } => casper_types::ExecutionResult::Success {
                 effect: execution_journal.into(),
                 transfers,
                 cost: cost.value(),
+                events,
+                sponsor,
             },
             ExecutionResult::Failure {
                 error,
                 transfers,
                 cost,
                 execution_journal,
+                sponsor,
             } => casper_types::ExecutionResult::Failure {
                 effect: execution_journal.into(),
                 transfers,
                 cost: cost.value(),
                 error_message: error.to_string(),
+                sponsor,
             },
         }
     }
@@ -486,11 +559,13 @@ impl ExecutionResultBuilder {
         let mut transfers = self.transfers();
         let cost = self.total_cost();
 
-        let mut journal = match self.payment_execution_result {
+        let (mut journal, mut events) = match self.payment_execution_result {
             Some(result @ ExecutionResult::Failure { .. }) => return Ok(result),
             Some(ExecutionResult::Success {
-                execution_journal, ..
-            }) => execution_journal,
+                execution_journal,
+                events,
+                ..
+            }) => (execution_journal, events),
             None => return Err(ExecutionResultBuilderError::MissingPaymentExecutionResult),
         };
 
@@ -500,15 +575,19 @@ impl ExecutionResultBuilder {
             Some(ExecutionResult::Failure {
                 error: session_error,
                 transfers: session_transfers,
-                execution_journal: _,
-                cost: _,
+                ..
             }) => {
                 error = Some(session_error);
                 transfers = session_transfers;
             }
             Some(ExecutionResult::Success {
-                execution_journal, ..
-            }) => journal.extend(execution_journal),
+                execution_journal,
+                events: session_events,
+                ..
+            }) => {
+                journal.extend(execution_journal);
+                events.extend(session_events);
+            }
             None => return Err(ExecutionResultBuilderError::MissingSessionExecutionResult),
         };
 
@@ -530,12 +609,15 @@ impl ExecutionResultBuilder {
                 transfers,
                 cost,
                 execution_journal: journal,
+                events,
+                sponsor: None,
             }),
             Some(error) => Ok(ExecutionResult::Failure {
                 error,
                 transfers,
                 cost,
                 execution_journal: journal,
+                sponsor: None,
             }),
         }
     }
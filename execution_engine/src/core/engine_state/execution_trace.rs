@@ -0,0 +1,467 @@
+//! Support for tracing and stepping through an execution.
+use std::{cell::RefCell, collections::BTreeSet, fmt::Debug, rc::Rc};
+
+use serde::{Deserialize, Serialize};
+
+use casper_types::{system::CallStackElement, Key, StoredValue};
+
+use crate::core::execution::Error;
+pub use crate::core::wasm_engine::WasmValue;
+
+/// An event in the execution of a deploy.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum TraceEvent {
+    /// A host function is about to be called.
+    HostCall {
+        /// The name of the host function, as imported by Wasm.
+        name: String,
+        /// The arguments passed by Wasm.
+        args: Vec<WasmValue>,
+    },
+    /// A host function returned.
+    HostReturn {
+        /// The name of the host function, as imported by Wasm.
+        name: String,
+        /// The value returned to Wasm, or the error with which the host function failed.
+        result: Result<Option<WasmValue>, String>,
+    },
+    /// A stored contract or stored session was called, pushing `frame` onto the runtime stack.
+    EnterFrame {
+        /// The frame pushed onto the runtime stack.
+        frame: CallStackElement,
+        /// The name of the called entry point.
+        entry_point: String,
+    },
+    /// The call which pushed the innermost frame onto the runtime stack returned.
+    ExitFrame,
+    /// A named key of the current context was looked up.
+    NamedKeyRead {
+        /// The name of the named key.
+        name: String,
+        /// The key under the name, if any.
+        key: Option<Key>,
+    },
+    /// A named key was put into the current context.
+    NamedKeyWrite {
+        /// The name of the named key.
+        name: String,
+        /// The key put under the name.
+        key: Key,
+    },
+    /// A named key was removed from the current context.
+    NamedKeyRemove {
+        /// The name of the named key.
+        name: String,
+    },
+    /// A value was read from global state.
+    Read {
+        /// The key read.
+        key: Key,
+        /// The value under the key, if any.
+        value: Option<StoredValue>,
+    },
+    /// A value was written to global state.
+    Write {
+        /// The key written.
+        key: Key,
+        /// The value written.
+        value: StoredValue,
+    },
+    /// A value was added to the value in global state.
+    Add {
+        /// The key added to.
+        key: Key,
+        /// The value added.
+        value: StoredValue,
+    },
+}
+
+impl TraceEvent {
+    pub(crate) fn host_call(name: &str, args: &[WasmValue]) -> Self {
+        TraceEvent::HostCall {
+            name: name.to_string(),
+            args: args.to_vec(),
+        }
+    }
+
+    pub(crate) fn host_return(name: &str, result: &Result<Option<WasmValue>, Error>) -> Self {
+        TraceEvent::HostReturn {
+            name: name.to_string(),
+            result: result.clone().map_err(|error| error.to_string()),
+        }
+    }
+
+    /// Returns the name of the host function about to be called, if this is a
+    /// [`TraceEvent::HostCall`].
+    pub fn host_call_name(&self) -> Option<&str> {
+        match self {
+            TraceEvent::HostCall { name, .. } => Some(name),
+            _ => None,
+        }
+    }
+}
+
+/// What execution should do after a hook has been called.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum HookAction {
+    /// Carry on executing.
+    Continue,
+    /// Stop executing.
+    ///
+    /// Only honored for [`TraceEvent::HostCall`], in which case the host function is not called
+    /// and execution fails with [`crate::core::execution::Error::Breakpoint`].
+    Break,
+}
+
+/// A hook called by the engine at each event in the execution of a deploy.
+///
+/// Calls to the `gas` host function, through which the metering code injected into Wasm charges
+/// for opcodes, are not reported.
+pub trait ExecutionHook {
+    /// Called on each event, in execution order.
+    fn on_event(&mut self, event: &TraceEvent) -> HookAction;
+}
+
+/// The events of an execution, in execution order.
+#[derive(Clone, Default, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ExecutionTrace {
+    /// The events.
+    pub events: Vec<TraceEvent>,
+}
+
+impl ExecutionTrace {
+    /// Feeds the recorded events of the trace to `hook` in order, as the engine did while
+    /// executing, stopping if `hook` breaks on a host call.
+    ///
+    /// Nothing is executed again: `hook` only sees the events, so its actions can't change them.
+    /// To execute the deploys again, pass a [`TraceReplayer`] to the engine instead.
+    ///
+    /// Returns the index of the event at which `hook` broke, if it did.
+    pub fn feed(&self, hook: &mut dyn ExecutionHook) -> Option<usize> {
+        self.events.iter().position(|event| {
+            hook.on_event(event) == HookAction::Break && event.host_call_name().is_some()
+        })
+    }
+}
+
+/// The first event at which a re-execution replayed by a [`TraceReplayer`] departed from the
+/// recorded trace.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ReplayDivergence {
+    /// The index of the event in the recorded trace.
+    pub index: usize,
+    /// The recorded event, or `None` if the re-execution emitted more events than were recorded.
+    pub expected: Option<TraceEvent>,
+    /// The event emitted by the re-execution, or `None` if it emitted fewer events than were
+    /// recorded.
+    pub actual: Option<TraceEvent>,
+}
+
+/// An [`ExecutionHook`] which checks that the re-execution of the deploys a trace was recorded
+/// from emits the recorded events, passing them on to another hook.
+///
+/// Execution is deterministic, so re-executing the same request against the same parent state
+/// with the same engine config produces the same events and effects as the recorded execution,
+/// which [`TraceReplayer::divergence`] confirms. The hook passed on to sees the execution as it
+/// happens again, so it can break on host calls: since every replay stops at the same point, a
+/// later replay with a breakpoint handler which continues past earlier hits steps through the
+/// execution.
+pub struct TraceReplayer {
+    recorded: ExecutionTrace,
+    position: usize,
+    divergence: Option<ReplayDivergence>,
+    hook: Option<Rc<RefCell<dyn ExecutionHook>>>,
+}
+
+impl TraceReplayer {
+    /// Returns a replayer checking a re-execution against `recorded`.
+    pub fn new(recorded: ExecutionTrace) -> Self {
+        TraceReplayer {
+            recorded,
+            position: 0,
+            divergence: None,
+            hook: None,
+        }
+    }
+
+    /// Passes the events of the re-execution on to `hook`, whose actions are honored.
+    pub fn with_hook(mut self, hook: Rc<RefCell<dyn ExecutionHook>>) -> Self {
+        self.hook = Some(hook);
+        self
+    }
+
+    /// Returns the number of events of the re-execution so far.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the first event at which the re-execution departed from the recorded trace, if it
+    /// did.
+    ///
+    /// Once the re-execution is over, a re-execution which stopped short of the recorded events
+    /// departs from it at the first event it didn't emit.
+    pub fn divergence(&self) -> Option<ReplayDivergence> {
+        self.divergence.clone().or_else(|| {
+            self.recorded
+                .events
+                .get(self.position)
+                .map(|expected| ReplayDivergence {
+                    index: self.position,
+                    expected: Some(expected.clone()),
+                    actual: None,
+                })
+        })
+    }
+}
+
+impl ExecutionHook for TraceReplayer {
+    fn on_event(&mut self, event: &TraceEvent) -> HookAction {
+        let expected = self.recorded.events.get(self.position);
+        if self.divergence.is_none() && expected != Some(event) {
+            self.divergence = Some(ReplayDivergence {
+                index: self.position,
+                expected: expected.cloned(),
+                actual: Some(event.clone()),
+            });
+        }
+        self.position += 1;
+        match &self.hook {
+            Some(hook) => hook.borrow_mut().on_event(event),
+            None => HookAction::Continue,
+        }
+    }
+}
+
+impl Debug for TraceReplayer {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter
+            .debug_struct("TraceReplayer")
+            .field("recorded", &self.recorded)
+            .field("position", &self.position)
+            .field("divergence", &self.divergence)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Callback for a breakpoint, given the host call hit and the trace up to and including it.
+type BreakpointHandler = Box<dyn FnMut(&TraceEvent, &ExecutionTrace) -> HookAction>;
+
+/// An [`ExecutionHook`] which records an [`ExecutionTrace`] and breaks on calls to chosen host
+/// functions.
+#[derive(Default)]
+pub struct ExecutionTracer {
+    trace: ExecutionTrace,
+    breakpoints: BTreeSet<String>,
+    on_breakpoint: Option<BreakpointHandler>,
+}
+
+impl ExecutionTracer {
+    /// Returns a tracer without breakpoints.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a breakpoint on calls to the host function imported by Wasm as `name`, e.g.
+    /// `casper_write`.
+    pub fn with_breakpoint(mut self, name: &str) -> Self {
+        self.breakpoints.insert(name.to_string());
+        self
+    }
+
+    /// Sets the callback called when a breakpoint is hit, which decides whether execution carries
+    /// on.
+    ///
+    /// Without a callback, execution stops at the first breakpoint hit.
+    pub fn on_breakpoint(
+        mut self,
+        on_breakpoint: impl FnMut(&TraceEvent, &ExecutionTrace) -> HookAction + 'static,
+    ) -> Self {
+        self.on_breakpoint = Some(Box::new(on_breakpoint));
+        self
+    }
+
+    /// Returns the trace recorded so far.
+    pub fn trace(&self) -> &ExecutionTrace {
+        &self.trace
+    }
+
+    /// Returns the trace recorded so far, leaving the tracer with an empty one.
+    pub fn take_trace(&mut self) -> ExecutionTrace {
+        std::mem::take(&mut self.trace)
+    }
+}
+
+impl ExecutionHook for ExecutionTracer {
+    fn on_event(&mut self, event: &TraceEvent) -> HookAction {
+        self.trace.events.push(event.clone());
+        match event.host_call_name() {
+            Some(name) if self.breakpoints.contains(name) => match self.on_breakpoint.as_mut() {
+                Some(on_breakpoint) => on_breakpoint(event, &self.trace),
+                None => HookAction::Break,
+            },
+            _ => HookAction::Continue,
+        }
+    }
+}
+
+impl Debug for ExecutionTracer {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter
+            .debug_struct("ExecutionTracer")
+            .field("trace", &self.trace)
+            .field("breakpoints", &self.breakpoints)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Sends [`TraceEvent::ExitFrame`] to a hook when dropped.
+pub(crate) struct ExecutionTraceFrame(Option<Rc<RefCell<dyn ExecutionHook>>>);
+
+impl ExecutionTraceFrame {
+    /// Sends `enter` to `hook`, if there is a hook.
+    pub(crate) fn enter(
+        hook: Option<&Rc<RefCell<dyn ExecutionHook>>>,
+        enter: impl FnOnce() -> TraceEvent,
+    ) -> Self {
+        if let Some(hook) = hook {
+            hook.borrow_mut().on_event(&enter());
+        }
+        ExecutionTraceFrame(hook.cloned())
+    }
+}
+
+impl Drop for ExecutionTraceFrame {
+    fn drop(&mut self) {
+        if let Some(hook) = &self.0 {
+            hook.borrow_mut().on_event(&TraceEvent::ExitFrame);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{CLValue, URef};
+
+    use super::*;
+
+    fn host_call(name: &str) -> TraceEvent {
+        TraceEvent::host_call(name, &[WasmValue::I32(1), WasmValue::I64(2)])
+    }
+
+    fn trace() -> ExecutionTrace {
+        let key = Key::URef(URef::default());
+        ExecutionTrace {
+            events: vec![
+                host_call("casper_new_uref"),
+                TraceEvent::host_return("casper_new_uref", &Ok(None)),
+                host_call("casper_write"),
+                TraceEvent::Write {
+                    key,
+                    value: StoredValue::CLValue(CLValue::from_t(1u64).unwrap()),
+                },
+                TraceEvent::host_return("casper_write", &Ok(Some(WasmValue::I32(0)))),
+                host_call("casper_read_value"),
+                TraceEvent::Read {
+                    key,
+                    value: Some(StoredValue::CLValue(CLValue::from_t(1u64).unwrap())),
+                },
+                TraceEvent::host_return("casper_read_value", &Ok(Some(WasmValue::I32(0)))),
+            ],
+        }
+    }
+
+    #[test]
+    fn should_break_on_breakpoint() {
+        let mut tracer = ExecutionTracer::new().with_breakpoint("casper_write");
+        assert_eq!(trace().feed(&mut tracer), Some(2));
+        assert_eq!(tracer.trace().events, trace().events[..3]);
+    }
+
+    #[test]
+    fn should_continue_if_breakpoint_handler_continues() {
+        let hits = Rc::new(RefCell::new(Vec::new()));
+        let mut tracer = {
+            let hits = Rc::clone(&hits);
+            ExecutionTracer::new()
+                .with_breakpoint("casper_write")
+                .with_breakpoint("casper_read_value")
+                .on_breakpoint(move |event, trace| {
+                    hits.borrow_mut().push(trace.events.len());
+                    assert_eq!(trace.events.last(), Some(event));
+                    HookAction::Continue
+                })
+        };
+        assert_eq!(trace().feed(&mut tracer), None);
+        assert_eq!(*hits.borrow(), vec![3, 6]);
+        assert_eq!(tracer.take_trace(), trace());
+        assert!(tracer.trace().events.is_empty());
+    }
+
+    #[test]
+    fn should_replay_identical_events_and_pass_them_on() {
+        let tracer = Rc::new(RefCell::new(
+            ExecutionTracer::new().with_breakpoint("casper_write"),
+        ));
+        let mut replayer = TraceReplayer::new(trace()).with_hook(tracer.clone());
+        assert_eq!(trace().feed(&mut replayer), Some(2));
+        assert_eq!(replayer.position(), 3);
+        assert_eq!(tracer.borrow().trace().events, trace().events[..3]);
+
+        let mut replayer = TraceReplayer::new(trace());
+        assert_eq!(trace().feed(&mut replayer), None);
+        assert_eq!(replayer.divergence(), None);
+    }
+
+    #[test]
+    fn should_report_first_divergence() {
+        let mut diverging = trace();
+        diverging.events[5] = host_call("casper_write");
+        diverging.events.truncate(7);
+        let mut replayer = TraceReplayer::new(trace());
+        diverging.feed(&mut replayer);
+        assert_eq!(
+            replayer.divergence(),
+            Some(ReplayDivergence {
+                index: 5,
+                expected: Some(host_call("casper_read_value")),
+                actual: Some(host_call("casper_write")),
+            })
+        );
+
+        let mut replayer = TraceReplayer::new(trace());
+        ExecutionTrace {
+            events: trace().events[..4].to_vec(),
+        }
+        .feed(&mut replayer);
+        assert_eq!(
+            replayer.divergence(),
+            Some(ReplayDivergence {
+                index: 4,
+                expected: Some(trace().events[4].clone()),
+                actual: None,
+            })
+        );
+
+        let mut replayer = TraceReplayer::new(ExecutionTrace::default());
+        trace().feed(&mut replayer);
+        assert_eq!(
+            replayer.divergence(),
+            Some(ReplayDivergence {
+                index: 0,
+                expected: None,
+                actual: Some(host_call("casper_new_uref")),
+            })
+        );
+    }
+
+    #[test]
+    fn should_roundtrip_through_json() {
+        let trace = trace();
+        let json = serde_json::to_string(&trace).unwrap();
+        assert_eq!(
+            serde_json::from_str::<ExecutionTrace>(&json).unwrap(),
+            trace
+        );
+    }
+}
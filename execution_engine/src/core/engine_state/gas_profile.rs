@@ -0,0 +1,541 @@
+//! Support for profiling the gas charged by an execution.
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    convert::TryFrom,
+    fmt::Write,
+    rc::Rc,
+    sync::Arc,
+};
+
+use casper_wasm::elements::{External, Instruction, Module};
+use casper_wasm_utils::rules::Rules;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use casper_types::{Gas, Phase};
+
+use crate::shared::opcode_costs::OpcodeCosts;
+
+/// Name of the host function through which injected metering code charges for opcodes.
+const GAS_FUNCTION_NAME: &str = "gas";
+/// Leaf frame under which gas which couldn't be attributed to anything more specific is recorded.
+const OTHER_FRAME: &str = "other";
+/// Leaf frame under which storage costs are recorded.
+const STORAGE_FRAME: &str = "storage";
+/// Leaf frame under which the fixed costs of system contract entry points are recorded.
+const SYSTEM_CONTRACT_CALL_FRAME: &str = "system_contract_call";
+/// Opcode class under which metered gas not matching any metered block of the module is recorded.
+const UNATTRIBUTED_OPCODE_CLASS: &str = "unattributed";
+
+/// A breakdown of the gas charged while executing a deploy.
+///
+/// Opcode gas is charged by the metering code injected into a module once per metered block, so
+/// it is split between opcode classes in proportion to the costs of the opcodes in the blocks of
+/// the module metered by the same amount.
+///
+/// Gas charged by a system contract called directly by the engine, e.g. to finalize payment, is
+/// included under the phase in which it was charged, even though it isn't part of the deploy's
+/// cost.
+#[derive(Clone, Default, PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GasProfile {
+    /// The total gas charged.
+    pub total: u64,
+    /// Gas charged for calling each host function, excluding storage and nested calls.
+    pub host_functions: BTreeMap<String, u64>,
+    /// Gas charged for executing each class of Wasm opcode.
+    pub opcodes: BTreeMap<String, u64>,
+    /// Gas charged for storing values in global state.
+    pub storage: u64,
+    /// Gas charged for calling system contract entry points.
+    pub system_contract_calls: u64,
+    /// Gas charged while each contract entry point, or the phase's own code, was executing,
+    /// excluding nested contract calls.
+    pub entry_points: BTreeMap<String, u64>,
+    /// Gas charged under each stack of frames, keyed by the frames joined with `;`.
+    pub stacks: BTreeMap<String, u64>,
+}
+
+impl GasProfile {
+    /// Returns the profile as folded stacks, one `frame;frame;...;frame gas` line per stack, as
+    /// consumed by flamegraph tools.
+    pub fn to_folded_stacks(&self) -> String {
+        let mut folded_stacks = String::new();
+        for (stack, gas) in &self.stacks {
+            let _ = writeln!(folded_stacks, "{} {}", stack, gas);
+        }
+        folded_stacks
+    }
+}
+
+/// The kind of cost for which gas is charged.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) enum GasCharge {
+    /// Gas charged by metering code for the opcodes of a metered block.
+    Opcodes,
+    /// Gas charged for storing values in global state.
+    Storage,
+    /// Gas charged for calling a system contract entry point.
+    SystemContractCall,
+    /// Gas which has already been recorded by the runtime which originally charged it.
+    AlreadyProfiled,
+    /// Any other gas, such as the cost of a host function.
+    Other,
+}
+
+/// The per-class opcode costs of the metered blocks of a module, keyed by the amount of gas the
+/// metering code charges for the block.
+#[derive(Default, Debug)]
+struct OpcodeMix {
+    blocks: HashMap<u32, BTreeMap<&'static str, u64>>,
+}
+
+impl OpcodeMix {
+    fn new(module: &Module, opcode_costs: &OpcodeCosts) -> Self {
+        let mut blocks: HashMap<u32, BTreeMap<&'static str, u64>> = HashMap::new();
+        let gas_function_index = match Self::gas_function_index(module) {
+            Some(index) => index,
+            None => return OpcodeMix { blocks },
+        };
+        let bodies = module
+            .code_section()
+            .map(|code_section| code_section.bodies())
+            .unwrap_or_default();
+
+        for body in bodies {
+            let instructions = body.code().elements();
+            let mut current_block: Option<(u32, BTreeMap<&'static str, u64>)> = None;
+            let mut index = 0;
+            while index < instructions.len() {
+                if let (Instruction::I32Const(amount), Some(Instruction::Call(function))) =
+                    (&instructions[index], instructions.get(index + 1))
+                {
+                    if *function == gas_function_index {
+                        if let Some((amount, costs)) = current_block.take() {
+                            Self::add_block(&mut blocks, amount, costs);
+                        }
+                        current_block = Some((*amount as u32, BTreeMap::new()));
+                        index += 2;
+                        continue;
+                    }
+                }
+
+                // Metering doesn't charge for the opcodes which only delimit blocks.
+                let instruction = &instructions[index];
+                if !matches!(instruction, Instruction::End | Instruction::Else) {
+                    if let (Some((_, costs)), Some(class), Some(cost)) = (
+                        current_block.as_mut(),
+                        OpcodeCosts::instruction_class(instruction),
+                        opcode_costs.instruction_cost(instruction),
+                    ) {
+                        *costs.entry(class).or_default() += u64::from(cost);
+                    }
+                }
+                index += 1;
+            }
+            if let Some((amount, costs)) = current_block {
+                Self::add_block(&mut blocks, amount, costs);
+            }
+        }
+
+        OpcodeMix { blocks }
+    }
+
+    fn gas_function_index(module: &Module) -> Option<u32> {
+        module
+            .import_section()?
+            .entries()
+            .iter()
+            .filter(|entry| matches!(entry.external(), External::Function(_)))
+            .position(|entry| entry.field() == GAS_FUNCTION_NAME)
+            .and_then(|index| u32::try_from(index).ok())
+    }
+
+    fn add_block(
+        blocks: &mut HashMap<u32, BTreeMap<&'static str, u64>>,
+        amount: u32,
+        costs: BTreeMap<&'static str, u64>,
+    ) {
+        let block_costs = blocks.entry(amount).or_default();
+        for (class, cost) in costs {
+            *block_costs.entry(class).or_default() += cost;
+        }
+    }
+
+    /// Splits `amount` between opcode classes in proportion to the costs of the blocks metered by
+    /// that amount.
+    fn split(&self, amount: u64) -> Vec<(&'static str, u64)> {
+        let costs = match u32::try_from(amount)
+            .ok()
+            .and_then(|amount| self.blocks.get(&amount))
+        {
+            Some(costs) => costs,
+            None => return vec![(UNATTRIBUTED_OPCODE_CLASS, amount)],
+        };
+        let total_cost: u128 = costs.values().map(|cost| u128::from(*cost)).sum();
+        if total_cost == 0 {
+            return vec![(UNATTRIBUTED_OPCODE_CLASS, amount)];
+        }
+
+        let mut shares: Vec<(&'static str, u64)> = costs
+            .iter()
+            .map(|(class, cost)| {
+                let share = u128::from(amount) * u128::from(*cost) / total_cost;
+                (*class, share as u64)
+            })
+            .collect();
+        // Give the rounding remainder to the most expensive class.
+        let remainder = amount - shares.iter().map(|(_, share)| share).sum::<u64>();
+        if let Some(largest) = shares.iter_mut().max_by_key(|(_, share)| *share) {
+            largest.1 += remainder;
+        }
+        shares
+    }
+}
+
+#[derive(Debug)]
+enum Frame {
+    Contract {
+        name: String,
+        opcode_mix: Option<Rc<OpcodeMix>>,
+    },
+    HostFunction(&'static str),
+    Suspended,
+}
+
+/// Records the gas charged by the runtimes of an execution into a [`GasProfile`].
+#[derive(Debug)]
+pub(crate) struct GasProfiler {
+    opcode_costs: OpcodeCosts,
+    frames: Vec<Frame>,
+    root_opcode_mix: Option<Rc<OpcodeMix>>,
+    opcode_mixes: Vec<(Arc<Module>, Rc<OpcodeMix>)>,
+    profile: GasProfile,
+}
+
+impl GasProfiler {
+    /// Creates a profiler attributing opcode gas according to `opcode_costs`.
+    pub(crate) fn new(opcode_costs: OpcodeCosts) -> Self {
+        GasProfiler {
+            opcode_costs,
+            frames: Vec::new(),
+            root_opcode_mix: None,
+            opcode_mixes: Vec::new(),
+            profile: GasProfile::default(),
+        }
+    }
+
+    /// Returns the profile recorded so far, leaving the profiler empty.
+    pub(crate) fn take_profile(&mut self) -> GasProfile {
+        self.root_opcode_mix = None;
+        std::mem::take(&mut self.profile)
+    }
+
+    /// Pushes a frame for a call to `entry_point_name` of the contract named `contract_name`.
+    pub(crate) fn enter_contract(&mut self, contract_name: &str, entry_point_name: &str) {
+        let name = format!("{}::{}", contract_name, entry_point_name).replace(';', "_");
+        self.frames.push(Frame::Contract {
+            name,
+            opcode_mix: None,
+        });
+    }
+
+    /// Pushes a frame for a call to the host function named `name`.
+    pub(crate) fn enter_host_function(&mut self, name: &'static str) {
+        self.frames.push(Frame::HostFunction(name));
+    }
+
+    /// Pushes a frame under which no gas is recorded, for gas which is charged and then discarded.
+    pub(crate) fn suspend(&mut self) {
+        self.frames.push(Frame::Suspended);
+    }
+
+    /// Pops the innermost frame.
+    pub(crate) fn exit(&mut self) {
+        self.frames.pop();
+    }
+
+    /// Sets the module executed by the innermost contract frame, or by the phase if there's none.
+    pub(crate) fn set_module(&mut self, module: &Arc<Module>) {
+        let opcode_mix = match self
+            .opcode_mixes
+            .iter()
+            .find(|(cached, _)| Arc::ptr_eq(cached, module))
+        {
+            Some((_, opcode_mix)) => Rc::clone(opcode_mix),
+            None => {
+                let opcode_mix = Rc::new(OpcodeMix::new(module, &self.opcode_costs));
+                self.opcode_mixes
+                    .push((Arc::clone(module), Rc::clone(&opcode_mix)));
+                opcode_mix
+            }
+        };
+        match self.frames.iter_mut().rev().find_map(|frame| match frame {
+            Frame::Contract { opcode_mix, .. } => Some(opcode_mix),
+            _ => None,
+        }) {
+            Some(frame_opcode_mix) => *frame_opcode_mix = Some(opcode_mix),
+            None => self.root_opcode_mix = Some(opcode_mix),
+        }
+    }
+
+    /// Records `amount` of gas of kind `charge`, charged during `phase`.
+    pub(crate) fn record(&mut self, phase: Phase, charge: GasCharge, amount: Gas) {
+        let amount = u64::try_from(amount.value()).unwrap_or(u64::MAX);
+        if amount == 0
+            || charge == GasCharge::AlreadyProfiled
+            || self
+                .frames
+                .iter()
+                .any(|frame| matches!(frame, Frame::Suspended))
+        {
+            return;
+        }
+
+        let phase_name = match phase {
+            Phase::System => "system",
+            Phase::Payment => "payment",
+            Phase::Session => "session",
+            Phase::FinalizePayment => "finalize_payment",
+        };
+        let mut stack = String::from(phase_name);
+        let mut entry_point = phase_name;
+        let mut opcode_mix = self.root_opcode_mix.as_ref();
+        let mut host_function = None;
+        for frame in &self.frames {
+            match frame {
+                Frame::Contract {
+                    name,
+                    opcode_mix: frame_opcode_mix,
+                } => {
+                    stack.push(';');
+                    stack.push_str(name);
+                    entry_point = name;
+                    opcode_mix = frame_opcode_mix.as_ref();
+                    host_function = None;
+                }
+                Frame::HostFunction(name) => {
+                    stack.push_str(";host:");
+                    stack.push_str(name);
+                    host_function = Some(*name);
+                }
+                Frame::Suspended => {}
+            }
+        }
+
+        let profile = &mut self.profile;
+        profile.total = profile.total.saturating_add(amount);
+        *profile
+            .entry_points
+            .entry(entry_point.to_string())
+            .or_default() += amount;
+
+        let mut add_stack = |leaf: Option<&str>, gas: u64| {
+            let key = match leaf {
+                Some(leaf) => format!("{};{}", stack, leaf),
+                None => stack.clone(),
+            };
+            *profile.stacks.entry(key).or_default() += gas;
+        };
+        match charge {
+            GasCharge::Opcodes => {
+                let shares = match opcode_mix {
+                    Some(opcode_mix) => opcode_mix.split(amount),
+                    None => vec![(UNATTRIBUTED_OPCODE_CLASS, amount)],
+                };
+                for (class, gas) in shares {
+                    add_stack(Some(&format!("wasm:{}", class)), gas);
+                    *profile.opcodes.entry(class.to_string()).or_default() += gas;
+                }
+            }
+            GasCharge::Storage => {
+                add_stack(Some(STORAGE_FRAME), amount);
+                profile.storage += amount;
+            }
+            GasCharge::SystemContractCall => {
+                add_stack(Some(SYSTEM_CONTRACT_CALL_FRAME), amount);
+                profile.system_contract_calls += amount;
+            }
+            GasCharge::Other => match host_function {
+                Some(name) => {
+                    add_stack(None, amount);
+                    *profile.host_functions.entry(name.to_string()).or_default() += amount;
+                }
+                None => add_stack(Some(OTHER_FRAME), amount),
+            },
+            GasCharge::AlreadyProfiled => {}
+        }
+    }
+}
+
+/// A frame pushed onto a [`GasProfiler`], which is popped again when this is dropped.
+pub(crate) struct GasProfilerScope(Option<Rc<RefCell<GasProfiler>>>);
+
+impl GasProfilerScope {
+    /// Pushes a frame onto `profiler` via `enter`, if there is a profiler.
+    pub(crate) fn enter(
+        profiler: Option<&Rc<RefCell<GasProfiler>>>,
+        enter: impl FnOnce(&mut GasProfiler),
+    ) -> Self {
+        if let Some(profiler) = profiler {
+            enter(&mut profiler.borrow_mut());
+        }
+        GasProfilerScope(profiler.cloned())
+    }
+}
+
+impl Drop for GasProfilerScope {
+    fn drop(&mut self) {
+        if let Some(profiler) = &self.0 {
+            profiler.borrow_mut().exit();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_wasm::{
+        builder,
+        elements::{Instructions, ValueType},
+    };
+
+    use super::*;
+
+    /// Builds a module importing `gas` whose single function is one metered block of `add`s and
+    /// `const`s, as the metering code would instrument it.
+    fn metered_module(opcode_costs: &OpcodeCosts) -> (Module, u32) {
+        let block = vec![
+            Instruction::I32Const(1),
+            Instruction::I32Const(2),
+            Instruction::I32Add,
+            Instruction::Drop,
+        ];
+        let amount: u32 = block
+            .iter()
+            .map(|instruction| opcode_costs.instruction_cost(instruction).unwrap())
+            .sum();
+        let mut instructions = vec![Instruction::I32Const(amount as i32), Instruction::Call(0)];
+        instructions.extend(block);
+        instructions.push(Instruction::End);
+
+        let module = builder::module()
+            .import()
+            .module("env")
+            .field(GAS_FUNCTION_NAME)
+            .external()
+            .func(0)
+            .build()
+            .function()
+            .signature()
+            .build()
+            .body()
+            .with_instructions(Instructions::new(instructions))
+            .build()
+            .build()
+            .function()
+            .signature()
+            .with_param(ValueType::I32)
+            .build()
+            .body()
+            .build()
+            .build()
+            .build();
+        (module, amount)
+    }
+
+    #[test]
+    fn should_split_metered_gas_between_opcode_classes() {
+        let opcode_costs = OpcodeCosts::default();
+        let (module, amount) = metered_module(&opcode_costs);
+        let mut profiler = GasProfiler::new(opcode_costs);
+        profiler.set_module(&Arc::new(module));
+        profiler.record(Phase::Session, GasCharge::Opcodes, Gas::from(amount));
+
+        let profile = profiler.take_profile();
+        assert_eq!(profile.total, u64::from(amount));
+        assert_eq!(
+            profile.opcodes.get("const").copied(),
+            Some(u64::from(2 * opcode_costs.op_const))
+        );
+        assert_eq!(
+            profile.opcodes.get("add").copied(),
+            Some(u64::from(opcode_costs.add))
+        );
+        assert_eq!(
+            profile.opcodes.get("control_flow").copied(),
+            Some(u64::from(opcode_costs.control_flow.drop))
+        );
+        assert_eq!(
+            profile.stacks.get("session;wasm:add").copied(),
+            Some(u64::from(opcode_costs.add))
+        );
+    }
+
+    #[test]
+    fn should_attribute_gas_to_frames() {
+        let mut profiler = GasProfiler::new(OpcodeCosts::default());
+        profiler.record(Phase::Session, GasCharge::Opcodes, Gas::from(7_u64));
+        profiler.enter_host_function("casper_call_contract");
+        profiler.record(Phase::Session, GasCharge::Other, Gas::from(100_u64));
+        profiler.enter_contract("contract-00", "counter_inc");
+        profiler.enter_host_function("casper_write");
+        profiler.record(Phase::Session, GasCharge::Other, Gas::from(10_u64));
+        profiler.record(Phase::Session, GasCharge::Storage, Gas::from(20_u64));
+        profiler.exit();
+        profiler.suspend();
+        profiler.record(Phase::Session, GasCharge::Other, Gas::from(1_000_u64));
+        profiler.exit();
+        profiler.record(
+            Phase::Session,
+            GasCharge::AlreadyProfiled,
+            Gas::from(1_000_u64),
+        );
+        profiler.exit();
+        profiler.exit();
+
+        let profile = profiler.take_profile();
+        assert_eq!(profile.total, 137);
+        assert_eq!(profile.storage, 20);
+        assert_eq!(profile.opcodes.get(UNATTRIBUTED_OPCODE_CLASS), Some(&7));
+        assert_eq!(
+            profile.host_functions.get("casper_call_contract"),
+            Some(&100)
+        );
+        assert_eq!(profile.host_functions.get("casper_write"), Some(&10));
+        assert_eq!(profile.entry_points.get("session"), Some(&107));
+        assert_eq!(
+            profile.entry_points.get("contract-00::counter_inc"),
+            Some(&30)
+        );
+        assert_eq!(
+            profile.to_folded_stacks(),
+            "session;host:casper_call_contract 100\n\
+             session;host:casper_call_contract;contract-00::counter_inc;host:casper_write 10\n\
+             session;host:casper_call_contract;contract-00::counter_inc;host:casper_write;storage 20\n\
+             session;wasm:unattributed 7\n"
+        );
+    }
+
+    #[test]
+    fn should_roundtrip_through_json() {
+        let mut profiler = GasProfiler::new(OpcodeCosts::default());
+        profiler.enter_contract("mint", "transfer");
+        profiler.record(
+            Phase::Payment,
+            GasCharge::SystemContractCall,
+            Gas::from(5_u64),
+        );
+        profiler.exit();
+        let profile = profiler.take_profile();
+
+        let json = serde_json::to_string(&profile).unwrap();
+        assert_eq!(serde_json::from_str::<GasProfile>(&json).unwrap(), profile);
+        assert_eq!(
+            profile
+                .stacks
+                .get("payment;mint::transfer;system_contract_call"),
+            Some(&5)
+        );
+    }
+}
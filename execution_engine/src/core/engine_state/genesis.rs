@@ -19,7 +19,7 @@ use casper_types::{
     system::{
         auction::{
             self, Bid, Bids, DelegationRate, Delegator, SeigniorageRecipient,
-            SeigniorageRecipients, SeigniorageRecipientsSnapshot, AUCTION_DELAY_KEY,
+            SeigniorageRecipients, SeigniorageRecipientsSnapshot, VestingCurve, AUCTION_DELAY_KEY,
             DELEGATION_RATE_DENOMINATOR, ERA_END_TIMESTAMP_MILLIS_KEY, ERA_ID_KEY,
             INITIAL_ERA_END_TIMESTAMP_MILLIS, INITIAL_ERA_ID, LOCKED_FUNDS_PERIOD_KEY,
             SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY, UNBONDING_DELAY_KEY, VALIDATOR_SLOTS_KEY,
@@ -100,12 +100,14 @@ enum GenesisAccountTag {
 }
 
 /// Represents details about genesis account's validator status.
-#[derive(DataSize, Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(DataSize, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct GenesisValidator {
     /// Stake of a genesis validator.
     bonded_amount: Motes,
     /// Delegation rate in the range of 0-100.
     delegation_rate: DelegationRate,
+    /// Custom curve along which the stake is released after the locked funds period.
+    vesting_curve: Option<VestingCurve>,
 }
 
 impl ToBytes for GenesisValidator {
@@ -113,11 +115,14 @@ impl ToBytes for GenesisValidator {
         let mut buffer = bytesrepr::allocate_buffer(self)?;
         buffer.extend(self.bonded_amount.to_bytes()?);
         buffer.extend(self.delegation_rate.to_bytes()?);
+        buffer.extend(self.vesting_curve.to_bytes()?);
         Ok(buffer)
     }
 
     fn serialized_length(&self) -> usize {
-        self.bonded_amount.serialized_length() + self.delegation_rate.serialized_length()
+        self.bonded_amount.serialized_length()
+            + self.delegation_rate.serialized_length()
+            + self.vesting_curve.serialized_length()
     }
 }
 
@@ -125,9 +130,11 @@ impl FromBytes for GenesisValidator {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
         let (bonded_amount, remainder) = FromBytes::from_bytes(bytes)?;
         let (delegation_rate, remainder) = FromBytes::from_bytes(remainder)?;
+        let (vesting_curve, remainder) = FromBytes::from_bytes(remainder)?;
         let genesis_validator = GenesisValidator {
             bonded_amount,
             delegation_rate,
+            vesting_curve,
         };
         Ok((genesis_validator, remainder))
     }
@@ -139,9 +146,22 @@ impl GenesisValidator {
         Self {
             bonded_amount,
             delegation_rate,
+            vesting_curve: None,
         }
     }
 
+    /// Sets a custom curve along which the stake is released after the locked funds period,
+    /// instead of the configured vesting schedule period.
+    pub fn with_vesting_curve(mut self, vesting_curve: Option<VestingCurve>) -> Self {
+        self.vesting_curve = vesting_curve;
+        self
+    }
+
+    /// Returns the custom vesting curve of a genesis validator, if any.
+    pub fn vesting_curve(&self) -> Option<&VestingCurve> {
+        self.vesting_curve.as_ref()
+    }
+
     /// Returns the bonded amount of a genesis validator.
     pub fn bonded_amount(&self) -> Motes {
         self.bonded_amount
@@ -245,6 +265,9 @@ pub enum GenesisAccount {
         balance: Motes,
         /// Delegated amount for given `validator_public_key`.
         delegated_amount: Motes,
+        /// Custom curve along which the delegated amount is released after the locked funds
+        /// period.
+        vesting_curve: Option<VestingCurve>,
     },
     /// An administrative account in the genesis process.
     ///
@@ -283,12 +306,31 @@ impl GenesisAccount {
         delegator_public_key: PublicKey,
         balance: Motes,
         delegated_amount: Motes,
+    ) -> Self {
+        Self::delegator_with_vesting_curve(
+            validator_public_key,
+            delegator_public_key,
+            balance,
+            delegated_amount,
+            None,
+        )
+    }
+
+    /// Create a delegator account variant whose delegated amount is released along a custom
+    /// vesting curve.
+    pub fn delegator_with_vesting_curve(
+        validator_public_key: PublicKey,
+        delegator_public_key: PublicKey,
+        balance: Motes,
+        delegated_amount: Motes,
+        vesting_curve: Option<VestingCurve>,
     ) -> Self {
         Self::Delegator {
             validator_public_key,
             delegator_public_key,
             balance,
             delegated_amount,
+            vesting_curve,
         }
     }
 
@@ -419,18 +461,29 @@ impl GenesisAccount {
     }
 
     /// Details about the genesis delegator.
-    pub fn as_delegator(&self) -> Option<(&PublicKey, &PublicKey, &Motes, &Motes)> {
+    #[allow(clippy::type_complexity)]
+    pub fn as_delegator(
+        &self,
+    ) -> Option<(
+        &PublicKey,
+        &PublicKey,
+        &Motes,
+        &Motes,
+        Option<&VestingCurve>,
+    )> {
         match self {
             GenesisAccount::Delegator {
                 validator_public_key,
                 delegator_public_key,
                 balance,
                 delegated_amount,
+                vesting_curve,
             } => Some((
                 validator_public_key,
                 delegator_public_key,
                 balance,
                 delegated_amount,
+                vesting_curve.as_ref(),
             )),
             _ => None,
         }
@@ -481,12 +534,14 @@ impl ToBytes for GenesisAccount {
                 delegator_public_key,
                 balance,
                 delegated_amount,
+                vesting_curve,
             } => {
                 buffer.push(GenesisAccountTag::Delegator as u8);
                 buffer.extend(validator_public_key.to_bytes()?);
                 buffer.extend(delegator_public_key.to_bytes()?);
                 buffer.extend(balance.value().to_bytes()?);
                 buffer.extend(delegated_amount.value().to_bytes()?);
+                buffer.extend(vesting_curve.to_bytes()?);
             }
             GenesisAccount::Administrator(administrator_account) => {
                 buffer.push(GenesisAccountTag::Administrator as u8);
@@ -514,11 +569,13 @@ impl ToBytes for GenesisAccount {
                 delegator_public_key,
                 balance,
                 delegated_amount,
+                vesting_curve,
             } => {
                 validator_public_key.serialized_length()
                     + delegator_public_key.serialized_length()
                     + balance.value().serialized_length()
                     + delegated_amount.value().serialized_length()
+                    + vesting_curve.serialized_length()
                     + TAG_LENGTH
             }
             GenesisAccount::Administrator(administrator_account) => {
@@ -548,11 +605,13 @@ impl FromBytes for GenesisAccount {
                 let (delegator_public_key, remainder) = FromBytes::from_bytes(remainder)?;
                 let (balance, remainder) = FromBytes::from_bytes(remainder)?;
                 let (delegated_amount_value, remainder) = FromBytes::from_bytes(remainder)?;
-                let genesis_account = GenesisAccount::delegator(
+                let (vesting_curve, remainder) = FromBytes::from_bytes(remainder)?;
+                let genesis_account = GenesisAccount::delegator_with_vesting_curve(
                     validator_public_key,
                     delegator_public_key,
                     balance,
                     Motes::new(delegated_amount_value),
+                    vesting_curve,
                 );
                 Ok((genesis_account, remainder))
             }
@@ -717,7 +776,15 @@ impl ExecConfig {
     /// Returns all bonded genesis delegators.
     pub fn get_bonded_delegators(
         &self,
-    ) -> impl Iterator<Item = (&PublicKey, &PublicKey, &Motes, &Motes)> {
+    ) -> impl Iterator<
+        Item = (
+            &PublicKey,
+            &PublicKey,
+            &Motes,
+            &Motes,
+            Option<&VestingCurve>,
+        ),
+    > {
         self.accounts
             .iter()
             .filter_map(|genesis_account| genesis_account.as_delegator())
@@ -990,6 +1057,11 @@ pub enum GenesisError {
         /// Delegator's public key.
         public_key: PublicKey,
     },
+    /// Invalid vesting curve in a genesis account.
+    InvalidVestingCurve {
+        /// Validator's or delegator's public key.
+        public_key: PublicKey,
+    },
     /// Failed to create system registry.
     FailedToCreateSystemRegistry,
     /// Missing system contract hash.
@@ -1198,7 +1270,7 @@ where
         let genesis_delegators: Vec<_> = self.exec_config.get_bonded_delegators().collect();
 
         // Make sure all delegators have corresponding genesis validator entries
-        for (validator_public_key, delegator_public_key, _, delegated_amount) in
+        for (validator_public_key, delegator_public_key, _, delegated_amount, vesting_curve) in
             genesis_delegators.iter()
         {
             if delegated_amount.is_zero() {
@@ -1208,6 +1280,13 @@ where
                 .into());
             }
 
+            if vesting_curve.map_or(false, |vesting_curve| !vesting_curve.is_valid()) {
+                return Err(GenesisError::InvalidVestingCurve {
+                    public_key: (*delegator_public_key).clone(),
+                }
+                .into());
+            }
+
             let orphan_condition = genesis_validators.iter().find(|genesis_validator| {
                 genesis_validator.public_key() == (*validator_public_key).clone()
             });
@@ -1244,6 +1323,13 @@ where
                 }
                 debug_assert_ne!(public_key, PublicKey::System);
 
+                let vesting_curve = genesis_validator
+                    .validator()
+                    .and_then(GenesisValidator::vesting_curve);
+                if vesting_curve.map_or(false, |vesting_curve| !vesting_curve.is_valid()) {
+                    return Err(GenesisError::InvalidVestingCurve { public_key }.into());
+                }
+
                 total_staked_amount += staked_amount;
 
                 let purse_uref = self.create_purse(staked_amount)?;
@@ -1257,6 +1343,11 @@ where
                         delegation_rate,
                         release_timestamp_millis,
                     );
+                    if let (Some(vesting_curve), Some(vesting_schedule)) =
+                        (vesting_curve, bid.vesting_schedule_mut())
+                    {
+                        vesting_schedule.set_vesting_curve(vesting_curve.clone());
+                    }
 
                     // Set up delegator entries attached to genesis validators
                     for (
@@ -1264,19 +1355,25 @@ where
                         delegator_public_key,
                         _delegator_balance,
                         delegator_delegated_amount,
+                        delegator_vesting_curve,
                     ) in genesis_delegators.iter()
                     {
                         if (*validator_public_key).clone() == public_key.clone() {
                             let purse_uref =
                                 self.create_purse(delegator_delegated_amount.value())?;
 
-                            let delegator = Delegator::locked(
+                            let mut delegator = Delegator::locked(
                                 (*delegator_public_key).clone(),
                                 delegator_delegated_amount.value(),
                                 purse_uref,
                                 (*validator_public_key).clone(),
                                 release_timestamp_millis,
                             );
+                            if let (Some(vesting_curve), Some(vesting_schedule)) =
+                                (*delegator_vesting_curve, delegator.vesting_schedule_mut())
+                            {
+                                vesting_schedule.set_vesting_curve(vesting_curve.clone());
+                            }
 
                             if bid
                                 .delegators_mut()
@@ -10,12 +10,15 @@ pub mod executable_deploy_item;
 pub mod execute_request;
 pub mod execution_effect;
 pub mod execution_result;
+pub mod execution_trace;
+pub mod gas_profile;
 pub mod genesis;
 pub mod get_bids;
 pub mod op;
 mod prune;
 pub mod query;
 pub mod run_genesis_request;
+pub mod state_diff;
 pub mod step;
 pub mod system_contract_registry;
 mod transfer;
@@ -26,6 +29,7 @@ use std::{
     collections::{BTreeMap, BTreeSet},
     convert::TryFrom,
     rc::Rc,
+    sync::Arc,
 };
 
 use num::Zero;
@@ -41,12 +45,11 @@ use casper_types::{
     system::{
         auction::{
             EraValidators, ARG_ERA_END_TIMESTAMP_MILLIS, ARG_EVICTED_VALIDATORS,
-            ARG_REWARD_FACTORS, ARG_VALIDATOR_PUBLIC_KEYS, AUCTION_DELAY_KEY,
-            LOCKED_FUNDS_PERIOD_KEY, SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY, UNBONDING_DELAY_KEY,
-            VALIDATOR_SLOTS_KEY,
+            ARG_REWARD_FACTORS, ARG_SLASHES, AUCTION_DELAY_KEY, LOCKED_FUNDS_PERIOD_KEY,
+            SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY, UNBONDING_DELAY_KEY, VALIDATOR_SLOTS_KEY,
         },
         handle_payment::{self, ACCUMULATION_PURSE_KEY},
-        mint::{self, ROUND_SEIGNIORAGE_RATE_KEY},
+        mint::{self, ScheduledTransferResult, ROUND_SEIGNIORAGE_RATE_KEY},
         AUCTION, HANDLE_PAYMENT, MINT, STANDARD_PAYMENT,
     },
     AccessRights, ApiError, BlockTime, CLValue, ContractHash, DeployHash, DeployInfo, Gas, Key,
@@ -68,11 +71,17 @@ pub use self::{
     execute_request::ExecuteRequest,
     execution::Error as ExecError,
     execution_result::{ExecutionResult, ForcedTransferResult},
+    execution_trace::{
+        ExecutionHook, ExecutionTrace, ExecutionTracer, HookAction, ReplayDivergence, TraceEvent,
+        TraceReplayer,
+    },
+    gas_profile::GasProfile,
     genesis::{ExecConfig, GenesisAccount, GenesisConfig, GenesisSuccess},
     get_bids::{GetBidsRequest, GetBidsResult},
     prune::{PruneConfig, PruneResult},
-    query::{QueryRequest, QueryResult},
+    query::{MultiQueryRequest, MultiQueryResult, QueryRequest, QueryResult},
     run_genesis_request::RunGenesisRequest,
+    state_diff::{StateDiffRequest, StateDiffResult},
     step::{RewardItem, SlashItem, StepError, StepRequest, StepSuccess},
     system_contract_registry::SystemContractRegistry,
     transfer::{TransferArgs, TransferRuntimeArgsBuilder, TransferTargetMode},
@@ -91,14 +100,21 @@ use crate::{
         runtime::RuntimeStack,
         tracking_copy::{TrackingCopy, TrackingCopyExt},
     },
-    shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
+    shared::{
+        additive_map::AdditiveMap,
+        module_cache::{ModuleCache, ModuleCacheStats},
+        newtypes::CorrelationId,
+        transform::Transform,
+    },
     storage::{
         global_state::{
-            lmdb::LmdbGlobalState, scratch::ScratchGlobalState, CommitProvider, StateProvider,
-            StateReader,
+            lmdb::LmdbGlobalState,
+            read_recording::{ReadRecordingState, ReadSet},
+            scratch::ScratchGlobalState,
+            CommitProvider, StateProvider, StateReader,
         },
         trie::{merkle_proof::TrieMerkleProof, TrieRaw},
-        trie_store::operations::DeleteResult,
+        trie_store::operations::{DeleteResult, TrieDiff},
     },
     system::auction,
 };
@@ -125,6 +141,7 @@ pub const WASMLESS_TRANSFER_FIXED_GAS_PRICE: u64 = 1;
 pub struct EngineState<S> {
     config: EngineConfig,
     state: S,
+    module_cache: Arc<ModuleCache>,
 }
 
 impl EngineState<ScratchGlobalState> {
@@ -153,6 +170,7 @@ impl EngineState<LmdbGlobalState> {
         EngineState {
             config: self.config.clone(),
             state: self.state.create_scratch(),
+            module_cache: Arc::clone(&self.module_cache),
         }
     }
 
@@ -176,7 +194,11 @@ where
 {
     /// Creates new engine state.
     pub fn new(state: S, config: EngineConfig) -> EngineState<S> {
-        EngineState { config, state }
+        EngineState {
+            config,
+            state,
+            module_cache: Arc::new(ModuleCache::default()),
+        }
     }
 
     /// Returns engine config.
@@ -184,6 +206,11 @@ where
         &self.config
     }
 
+    /// Returns the hit and miss counts of the cache of stored contracts' Wasm modules.
+    pub fn module_cache_stats(&self) -> ModuleCacheStats {
+        self.module_cache.stats()
+    }
+
     /// Updates current engine config with a new instance.
     pub fn update_config(&mut self, new_config: EngineConfig) {
         self.config = new_config
@@ -504,6 +531,44 @@ where
         }
     }
 
+    /// Computes the differences between two global state roots.
+    ///
+    /// Both tries are walked in parallel, skipping subtries which are identical under both roots,
+    /// and each changed key is passed to `visitor` together with its old and new values as soon as
+    /// it is found.
+    pub fn diff_state_roots<F>(
+        &self,
+        correlation_id: CorrelationId,
+        state_diff_request: StateDiffRequest,
+        mut visitor: F,
+    ) -> Result<StateDiffResult, Error>
+    where
+        F: FnMut(TrieDiff<Key, StoredValue>),
+    {
+        let old_state_hash = state_diff_request.old_state_hash();
+        let new_state_hash = state_diff_request.new_state_hash();
+        for state_hash in [old_state_hash, new_state_hash] {
+            if self.tracking_copy(state_hash)?.is_none() {
+                return Ok(StateDiffResult::RootNotFound(state_hash));
+            }
+        }
+
+        let mut changed_keys = 0;
+        self.state
+            .diff(
+                correlation_id,
+                old_state_hash,
+                new_state_hash,
+                &mut |trie_diff| {
+                    changed_keys += 1;
+                    visitor(trie_diff);
+                },
+            )
+            .map_err(|err| Error::Exec(err.into()))?;
+
+        Ok(StateDiffResult::Success { changed_keys })
+    }
+
     /// Creates a new tracking copy instance.
     pub fn tracking_copy(&self, hash: Digest) -> Result<Option<TrackingCopy<S::Reader>>, Error> {
         match self.state.checkout(hash).map_err(Into::into)? {
@@ -540,6 +605,61 @@ where
             .into())
     }
 
+    /// Executes a query for several keys at once.
+    ///
+    /// Unlike [`EngineState::run_query`], the keys are read directly without following any path,
+    /// and a single Merkle proof covering all of the values is returned.
+    pub fn run_query_multi(
+        &self,
+        correlation_id: CorrelationId,
+        query_request: MultiQueryRequest,
+    ) -> Result<MultiQueryResult, Error> {
+        let reader = match self.state.checkout(query_request.state_hash()) {
+            Ok(Some(reader)) => reader,
+            Ok(None) => return Ok(MultiQueryResult::RootNotFound),
+            Err(error) => return Err(Error::Exec(error.into())),
+        };
+
+        let keys: Vec<Key> = query_request
+            .keys()
+            .iter()
+            .map(|key| key.normalize())
+            .collect();
+        let proof = match reader
+            .read_with_multi_proof(correlation_id, &keys)
+            .map_err(|error| Error::Exec(error.into()))?
+        {
+            Some(proof) => proof,
+            None => {
+                // Find the first missing key to report it.
+                for key in &keys {
+                    if reader
+                        .read(correlation_id, key)
+                        .map_err(|error| Error::Exec(error.into()))?
+                        .is_none()
+                    {
+                        return Ok(MultiQueryResult::ValueNotFound(format!(
+                            "Failed to find {}",
+                            key
+                        )));
+                    }
+                }
+                return Ok(MultiQueryResult::ValueNotFound(
+                    "Failed to find one of the keys".to_string(),
+                ));
+            }
+        };
+
+        let values = keys
+            .iter()
+            .filter_map(|key| proof.value(key).cloned())
+            .collect();
+        Ok(MultiQueryResult::Success {
+            values,
+            proof: Box::new(proof),
+        })
+    }
+
     /// Runs a deploy execution request.
     ///
     /// For each deploy stored in the request it will execute it.
@@ -550,18 +670,59 @@ where
     pub fn run_execute(
         &self,
         correlation_id: CorrelationId,
-        mut exec_request: ExecuteRequest,
+        exec_request: ExecuteRequest,
+    ) -> Result<ExecutionResults, Error> {
+        let executor = Executor::new(self.config().clone(), Arc::clone(&self.module_cache));
+        self.execute_deploys(correlation_id, exec_request, &executor)
+            .map(|(results, _)| results)
+    }
+
+    /// Runs a deploy execution request as [`run_execute`](Self::run_execute) does, additionally
+    /// returning a [`GasProfile`] of the gas charged by each deploy, in the same order as the
+    /// results.
+    ///
+    /// Profiling doesn't change the results of the execution.
+    pub fn run_execute_with_gas_profiles(
+        &self,
+        correlation_id: CorrelationId,
+        exec_request: ExecuteRequest,
+    ) -> Result<(ExecutionResults, Vec<GasProfile>), Error> {
+        let executor = Executor::new(self.config().clone(), Arc::clone(&self.module_cache))
+            .with_gas_profiling();
+        self.execute_deploys(correlation_id, exec_request, &executor)
+    }
+
+    /// Runs a deploy execution request as [`run_execute`](Self::run_execute) does, calling
+    /// `execution_hook` at each event of the execution of each deploy, in execution order.
+    ///
+    /// The results of the execution don't depend on the hook, unless it breaks on a host call.
+    pub fn run_execute_with_hook(
+        &self,
+        correlation_id: CorrelationId,
+        exec_request: ExecuteRequest,
+        execution_hook: Rc<RefCell<dyn ExecutionHook>>,
     ) -> Result<ExecutionResults, Error> {
-        let executor = Executor::new(self.config().clone());
+        let executor = Executor::new(self.config().clone(), Arc::clone(&self.module_cache))
+            .with_execution_hook(execution_hook);
+        self.execute_deploys(correlation_id, exec_request, &executor)
+            .map(|(results, _)| results)
+    }
 
+    fn execute_deploys(
+        &self,
+        correlation_id: CorrelationId,
+        mut exec_request: ExecuteRequest,
+        executor: &Executor,
+    ) -> Result<(ExecutionResults, Vec<GasProfile>), Error> {
         let deploys = exec_request.take_deploys();
         let mut results = ExecutionResults::with_capacity(deploys.len());
+        let mut gas_profiles = Vec::new();
 
         for deploy_item in deploys {
             let result = match deploy_item.session {
                 ExecutableDeployItem::Transfer { .. } => self.transfer(
                     correlation_id,
-                    &executor,
+                    executor,
                     exec_request.protocol_version,
                     exec_request.parent_state_hash,
                     BlockTime::new(exec_request.block_time),
@@ -570,7 +731,7 @@ where
                 ),
                 _ => self.deploy(
                     correlation_id,
-                    &executor,
+                    executor,
                     exec_request.protocol_version,
                     exec_request.parent_state_hash,
                     BlockTime::new(exec_request.block_time),
@@ -584,9 +745,51 @@ where
                     return Err(error);
                 }
             };
+            gas_profiles.extend(executor.take_gas_profile());
         }
 
-        Ok(results)
+        Ok((results, gas_profiles))
+    }
+
+    /// Runs a deploy execution request as [`run_execute`](Self::run_execute) does, additionally
+    /// returning every value read from global state by the tracking copies of the execution.
+    ///
+    /// Executing the request against a different pre-state produces the same results as long as
+    /// the returned [`ReadSet`] is current in that state, see
+    /// [`is_read_set_current`](Self::is_read_set_current).
+    pub fn run_execute_recording_reads(
+        &self,
+        correlation_id: CorrelationId,
+        exec_request: ExecuteRequest,
+    ) -> Result<(ExecutionResults, ReadSet), Error> {
+        let read_set = Rc::new(RefCell::new(ReadSet::default()));
+        let recording_engine_state = EngineState {
+            config: self.config.clone(),
+            state: ReadRecordingState::new(&self.state, Rc::clone(&read_set)),
+            module_cache: Arc::clone(&self.module_cache),
+        };
+        let results = recording_engine_state.run_execute(correlation_id, exec_request)?;
+        let read_set = read_set.take();
+        Ok((results, read_set))
+    }
+
+    /// Returns `true` if everything in `read_set` is unchanged in the state under `state_hash`,
+    /// given that only the values under `written_keys` can have changed since it was recorded.
+    pub fn is_read_set_current(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Digest,
+        read_set: &ReadSet,
+        written_keys: &BTreeSet<Key>,
+    ) -> Result<bool, Error> {
+        let reader = match self.state.checkout(state_hash).map_err(Into::into)? {
+            Some(reader) => reader,
+            None => return Err(Error::RootNotFound(state_hash)),
+        };
+        let is_current = read_set
+            .is_current(correlation_id, &reader, written_keys)
+            .map_err(Into::<execution::Error>::into)?;
+        Ok(is_current)
     }
 
     fn get_authorized_account(
@@ -626,6 +829,52 @@ where
         Ok(account)
     }
 
+    /// Splits the keys authorizing a sponsored deploy between the deploy's account and its
+    /// sponsoring account.
+    ///
+    /// Each account is authorized only by the keys associated with it, and every key must be
+    /// associated with at least one of the two accounts.  Administrator keys authorize both.
+    fn split_authorization_keys(
+        &self,
+        correlation_id: CorrelationId,
+        account_hash: AccountHash,
+        sponsor_account_hash: AccountHash,
+        authorization_keys: &BTreeSet<AccountHash>,
+        tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
+    ) -> Result<(BTreeSet<AccountHash>, BTreeSet<AccountHash>), Error> {
+        let read_account = |account_hash| {
+            tracking_copy
+                .borrow_mut()
+                .get_account(correlation_id, account_hash)
+                .map_err(|_| Error::Authorization)
+        };
+        let account = read_account(account_hash)?;
+        let sponsor_account = read_account(sponsor_account_hash)?;
+
+        let admin_set = self.config().administrative_accounts();
+
+        let mut account_keys = BTreeSet::new();
+        let mut sponsor_keys = BTreeSet::new();
+        for authorization_key in authorization_keys {
+            let is_admin = admin_set.contains(authorization_key);
+            let is_account_key = account.associated_keys().contains_key(authorization_key);
+            let is_sponsor_key = sponsor_account
+                .associated_keys()
+                .contains_key(authorization_key);
+            if !is_admin && !is_account_key && !is_sponsor_key {
+                return Err(Error::Authorization);
+            }
+            if is_admin || is_account_key {
+                account_keys.insert(*authorization_key);
+            }
+            if is_admin || is_sponsor_key {
+                sponsor_keys.insert(*authorization_key);
+            }
+        }
+
+        Ok((account_keys, sponsor_keys))
+    }
+
     /// Get the balance of a passed purse referenced by its [`URef`].
     pub fn get_purse_balance(
         &self,
@@ -1210,6 +1459,59 @@ where
 
         let authorization_keys = deploy_item.authorization_keys;
 
+        let payment = deploy_item.payment;
+        let session = deploy_item.session;
+        let deploy_hash = deploy_item.deploy_hash;
+
+        // A deploy using standard payment can name an account or a contract paying for it in place
+        // of its account through the `sponsor` payment argument.
+        let maybe_sponsor = match payment.args().get(handle_payment::ARG_SPONSOR) {
+            Some(cl_value) if payment.is_standard_payment(Phase::Payment) => {
+                match cl_value.clone().into_t::<Key>() {
+                    Ok(sponsor @ Key::Account(_)) | Ok(sponsor @ Key::Hash(_)) => Some(sponsor),
+                    Ok(_) => {
+                        return Ok(ExecutionResult::precondition_failure(
+                            Error::InvalidKeyVariant,
+                        ))
+                    }
+                    Err(error) => {
+                        return Ok(ExecutionResult::precondition_failure(
+                            ExecError::CLValue(error).into(),
+                        ))
+                    }
+                }
+            }
+            Some(_) | None => None,
+        };
+
+        // A sponsoring contract is told which account it pays for through the `account` argument
+        // of its `approve_payment` entry point, so the deploy must not pass one of its own.
+        if let Some(Key::Hash(_)) = maybe_sponsor {
+            if payment.args().get(handle_payment::ARG_ACCOUNT).is_some() {
+                return Ok(ExecutionResult::precondition_failure(
+                    Error::ReservedArgument(handle_payment::ARG_ACCOUNT.to_string()),
+                ));
+            }
+        }
+
+        // A sponsoring account approves the payment by co-signing the deploy, and then pays for it
+        // from its main purse exactly as the deploy's account otherwise would.  A sponsoring
+        // contract approves the payment from its `approve_payment` entry point, but the deploy's
+        // account remains liable for the penalty of a failed payment.
+        let (account_authorization_keys, sponsor_authorization_keys) = match maybe_sponsor {
+            Some(Key::Account(sponsor_account_hash)) => match self.split_authorization_keys(
+                correlation_id,
+                deploy_item.address,
+                sponsor_account_hash,
+                &authorization_keys,
+                Rc::clone(&tracking_copy),
+            ) {
+                Ok((account_keys, sponsor_keys)) => (account_keys, Some(sponsor_keys)),
+                Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
+            },
+            Some(_) | None => (authorization_keys.clone(), None),
+        };
+
         // Get account from tracking copy
         // validation_spec_3: account validity
         let account = {
@@ -1217,7 +1519,7 @@ where
             match self.get_authorized_account(
                 correlation_id,
                 account_hash,
-                &authorization_keys,
+                &account_authorization_keys,
                 Rc::clone(&tracking_copy),
             ) {
                 Ok(account) => account,
@@ -1225,6 +1527,21 @@ where
             }
         };
 
+        let sponsor_account = match (maybe_sponsor, sponsor_authorization_keys) {
+            (Some(Key::Account(sponsor_account_hash)), Some(sponsor_keys)) => {
+                match self.get_authorized_account(
+                    correlation_id,
+                    sponsor_account_hash,
+                    &sponsor_keys,
+                    Rc::clone(&tracking_copy),
+                ) {
+                    Ok(sponsor_account) => Some(sponsor_account),
+                    Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
+                }
+            }
+            _ => None,
+        };
+
         // Finalization is executed by system account (currently genesis account)
         // payment_code_spec_5: system executes finalization
         let system_account = match tracking_copy
@@ -1235,12 +1552,11 @@ where
             Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
         };
 
-        let payment = deploy_item.payment;
-        let session = deploy_item.session;
-        let deploy_hash = deploy_item.deploy_hash;
-
         let session_args = session.args().clone();
 
+        let payer = sponsor_account.as_ref().unwrap_or(&account);
+        let payment_error_sponsor = sponsor_account.as_ref().and(maybe_sponsor);
+
         // Create session code `A` from provided session bytes
         // validation_spec_1: valid wasm bytes
         // we do this upfront as there is no reason to continue if session logic is invalid
@@ -1261,7 +1577,7 @@ where
         // Get account main purse balance key
         // validation_spec_5: account main purse minimum balance
         let account_main_purse_balance_key: Key = {
-            let account_key = Key::URef(account.main_purse());
+            let account_key = Key::URef(payer.main_purse());
             match tracking_copy
                 .borrow_mut()
                 .get_purse_balance_key(correlation_id, account_key)
@@ -1386,18 +1702,61 @@ where
             );
 
             // payment_code_spec_2: execute payment code
-            let payment_access_rights = account.extract_access_rights();
+            let payment_access_rights = payer.extract_access_rights();
 
-            let mut payment_named_keys = account.named_keys().clone();
+            let mut payment_named_keys = payer.named_keys().clone();
 
-            let payment_args = payment.args().clone();
+            let mut payment_args = payment.args().clone();
 
-            if payment.is_standard_payment(phase) {
+            if let Some(Key::Hash(sponsor_contract_hash)) = maybe_sponsor {
+                if let Err(error) =
+                    payment_args.insert(handle_payment::ARG_ACCOUNT, account.account_hash())
+                {
+                    return Ok(ExecutionResult::precondition_failure(
+                        ExecError::CLValue(error).into(),
+                    ));
+                }
+                let approve_payment = ExecutableDeployItem::StoredContractByHash {
+                    hash: ContractHash::new(sponsor_contract_hash),
+                    entry_point: handle_payment::ENTRY_POINT_APPROVE_PAYMENT.to_string(),
+                    args: payment_args.clone(),
+                };
+                let payment_execution_kind = match ExecutionKind::new(
+                    Rc::clone(&tracking_copy),
+                    account.named_keys(),
+                    approve_payment,
+                    correlation_id,
+                    &protocol_version,
+                    phase,
+                ) {
+                    Ok(execution_kind) => execution_kind,
+                    Err(error) => {
+                        return Ok(ExecutionResult::precondition_failure(error));
+                    }
+                };
+                executor.exec(
+                    payment_execution_kind,
+                    payment_args,
+                    &account,
+                    &mut payment_named_keys,
+                    payment_access_rights,
+                    authorization_keys.clone(),
+                    blocktime,
+                    deploy_hash,
+                    payment_gas_limit,
+                    protocol_version,
+                    correlation_id,
+                    Rc::clone(&tracking_copy),
+                    phase,
+                    payment_stack,
+                    Some(ContractHash::new(sponsor_contract_hash)),
+                )
+            } else if payment.is_standard_payment(phase) {
                 // Todo potentially could be moved to Executor::Exec
                 executor.exec_standard_payment(
                     payment_args,
-                    Key::Account(account.account_hash()),
-                    &account,
+                    Key::Account(payer.account_hash()),
+                    payer,
                     &mut payment_named_keys,
                     payment_access_rights,
                     authorization_keys.clone(),
@@ -1439,6 +1798,7 @@ where
                     Rc::clone(&tracking_copy),
                     phase,
                     payment_stack,
+                    None,
                 )
             }
         };
@@ -1459,7 +1819,9 @@ where
                 account_main_purse_balance_key,
                 rewards_target_purse_balance_key,
             ) {
-                Ok(execution_result) => return Ok(execution_result),
+                Ok(execution_result) => {
+                    return Ok(execution_result.with_sponsor(payment_error_sponsor))
+                }
                 Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
             }
         }
@@ -1510,7 +1872,9 @@ where
                 account_main_purse_balance_key,
                 rewards_target_purse_balance_key,
             ) {
-                Ok(execution_result) => return Ok(execution_result),
+                Ok(execution_result) => {
+                    return Ok(execution_result.with_sponsor(payment_error_sponsor))
+                }
                 Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
             }
         };
@@ -1564,6 +1928,7 @@ where
                 Rc::clone(&session_tracking_copy),
                 Phase::Session,
                 session_stack,
+                None,
             )
         };
         log_execution_result("session result", &session_result);
@@ -1576,7 +1941,7 @@ where
                 deploy_hash,
                 transfers,
                 account.account_hash(),
-                account.main_purse(),
+                payer.main_purse(),
                 cost,
             );
             session_tracking_copy.borrow_mut().write(
@@ -1605,7 +1970,9 @@ where
                 account_main_purse_balance_key,
                 rewards_target_purse_balance_key,
             ) {
-                Ok(execution_result) => return Ok(execution_result),
+                Ok(execution_result) => {
+                    return Ok(execution_result.with_sponsor(payment_error_sponsor))
+                }
                 Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
             }
         }
@@ -1645,7 +2012,7 @@ where
 
                 let maybe_runtime_args = RuntimeArgs::try_new(|args| {
                     args.insert(handle_payment::ARG_AMOUNT, finalize_cost_motes.value())?;
-                    args.insert(handle_payment::ARG_ACCOUNT, account.account_hash())?;
+                    args.insert(handle_payment::ARG_ACCOUNT, payer.account_hash())?;
                     args.insert(handle_payment::ARG_TARGET, rewards_target_purse)?;
                     Ok(())
                 });
@@ -1712,7 +2079,8 @@ where
         // We panic here to indicate that the builder was not used properly.
         let ret = execution_result_builder
             .build()
-            .expect("ExecutionResultBuilder not initialized properly");
+            .expect("ExecutionResultBuilder not initialized properly")
+            .with_sponsor(maybe_sponsor);
 
         // NOTE: payment_code_spec_5_a is enforced in execution_result_builder.build()
         // payment_code_spec_6: return properly combined set of transforms and
@@ -1932,7 +2300,7 @@ where
             Ok(Some(tracking_copy)) => Rc::new(RefCell::new(tracking_copy)),
         };
 
-        let executor = Executor::new(self.config().clone());
+        let executor = Executor::new(self.config().clone(), Arc::clone(&self.module_cache));
 
         let virtual_system_account = {
             let purse = URef::new(Default::default(), AccessRights::READ_ADD_WRITE);
@@ -2016,13 +2384,13 @@ where
             return Err(StepError::DistributeError(exec_error));
         }
 
-        let slashed_validators: Vec<PublicKey> = step_request.slashed_validators();
+        let slashes = step_request.slashes();
 
-        if !slashed_validators.is_empty() {
+        if !slashes.is_empty() {
             let slash_args = {
                 let mut runtime_args = RuntimeArgs::new();
                 runtime_args
-                    .insert(ARG_VALIDATOR_PUBLIC_KEYS, slashed_validators)
+                    .insert(ARG_SLASHES, slashes)
                     .map_err(|e| Error::Exec(e.into()))?;
                 runtime_args
             };
@@ -2051,6 +2419,17 @@ where
             }
         }
 
+        let scheduled_transfers = self.run_scheduled_transfers(
+            &executor,
+            &step_request,
+            &virtual_system_account,
+            authorization_keys.clone(),
+            deploy_hash,
+            gas_limit,
+            correlation_id,
+            Rc::clone(&tracking_copy),
+        )?;
+
         let run_auction_args = RuntimeArgs::try_new(|args| {
             args.insert(
                 ARG_ERA_END_TIMESTAMP_MILLIS,
@@ -2105,9 +2484,88 @@ where
         Ok(StepSuccess {
             post_state_hash,
             execution_journal,
+            scheduled_transfers,
         })
     }
 
+    /// Runs the scheduled transfers due in the era started by `step_request`.  The mint is only
+    /// called once a transfer was ever scheduled.
+    #[allow(clippy::too_many_arguments)]
+    fn run_scheduled_transfers(
+        &self,
+        executor: &Executor,
+        step_request: &StepRequest,
+        virtual_system_account: &Account,
+        authorization_keys: BTreeSet<AccountHash>,
+        deploy_hash: DeployHash,
+        gas_limit: Gas,
+        correlation_id: CorrelationId,
+        tracking_copy: Rc<RefCell<TrackingCopy<S::Reader>>>,
+    ) -> Result<Vec<ScheduledTransferResult>, StepError> {
+        let mint_hash = self
+            .get_system_mint_hash(correlation_id, step_request.pre_state_hash)
+            .map_err(StepError::GetContractError)?;
+        let mint = tracking_copy
+            .borrow_mut()
+            .get_contract(correlation_id, mint_hash)
+            .map_err(|error| StepError::GetContractError(error.into()))?;
+        if !mint
+            .named_keys()
+            .contains_key(mint::SCHEDULED_TRANSFERS_KEY)
+        {
+            return Ok(Vec::new());
+        }
+
+        // The addresses of the transfers recorded by the mint are derived from the deploy hash, so
+        // the payouts get their own to not collide with the transfers recorded by `run_auction`.
+        let deploy_hash = {
+            let mut bytes = deploy_hash.as_bytes().to_vec();
+            bytes.extend(mint::METHOD_RUN_SCHEDULED_TRANSFERS.as_bytes());
+            DeployHash::new(Digest::hash(&bytes).value())
+        };
+
+        let args = RuntimeArgs::try_new(|args| {
+            args.insert(mint::ARG_ERA_ID, step_request.next_era_id)?;
+            args.insert(
+                mint::ARG_ERA_END_TIMESTAMP_MILLIS,
+                step_request.era_end_timestamp_millis,
+            )?;
+            Ok(())
+        })?;
+
+        let stack = self.get_new_system_call_stack();
+        let (maybe_result, execution_result): (
+            Option<Result<Vec<ScheduledTransferResult>, mint::Error>>,
+            ExecutionResult,
+        ) = executor.call_system_contract(
+            DirectSystemContractCall::RunScheduledTransfers,
+            args,
+            virtual_system_account,
+            authorization_keys,
+            BlockTime::new(step_request.era_end_timestamp_millis),
+            deploy_hash,
+            gas_limit,
+            step_request.protocol_version,
+            correlation_id,
+            tracking_copy,
+            Phase::Session,
+            stack,
+            // Scheduled transfers are paid out of escrow purses funded when they were scheduled.
+            U512::zero(),
+        );
+
+        if let Some(exec_error) = execution_result.take_error() {
+            return Err(StepError::ScheduledTransfersError(exec_error));
+        }
+        match maybe_result {
+            Some(Ok(scheduled_transfers)) => Ok(scheduled_transfers),
+            Some(Err(mint_error)) => Err(StepError::ScheduledTransfersError(Error::Exec(
+                execution::Error::Revert(mint_error.into()),
+            ))),
+            None => Ok(Vec::new()),
+        }
+    }
+
     /// Gets the balance of a given public key.
     pub fn get_balance(
         &self,
@@ -2282,11 +2740,14 @@ fn log_execution_result(preamble: &'static str, result: &ExecutionResult) {
             transfers,
             cost,
             execution_journal,
+            events,
+            ..
         } => {
             debug!(
                 %cost,
                 transfer_count=%transfers.len(),
                 journal_entries=%execution_journal.len(),
+                event_count=%events.len(),
                 "{}: execution success",
                 preamble
             );
@@ -2296,6 +2757,7 @@ fn log_execution_result(preamble: &'static str, result: &ExecutionResult) {
             transfers,
             cost,
             execution_journal,
+            ..
         } => {
             debug!(
                 %error,
@@ -2316,6 +2778,7 @@ fn should_charge_for_errors_in_wasm(execution_result: &ExecutionResult) -> bool
             transfers: _,
             cost: _,
             execution_journal: _,
+            sponsor: _,
         } => match error {
             Error::Exec(err) => match err {
                 ExecError::WasmPreprocessing(_) | ExecError::UnsupportedWasmStart => true,
@@ -2365,7 +2828,8 @@ fn should_charge_for_errors_in_wasm(execution_result: &ExecutionResult) -> bool
                 | ExecError::ValueTooLarge
                 | ExecError::MissingRuntimeStack
                 | ExecError::DisabledContract(_)
-                | ExecError::DisabledUnrestrictedTransfers => false,
+                | ExecError::DisabledUnrestrictedTransfers
+                | ExecError::Breakpoint(_) => false,
             },
             Error::WasmPreprocessing(_) => true,
             Error::WasmSerialization(_) => true,
@@ -2394,7 +2858,8 @@ fn should_charge_for_errors_in_wasm(execution_result: &ExecutionResult) -> bool
             | Error::FailedToRetrieveUnbondingDelay
             | Error::FailedToRetrieveEraId
             | Error::MissingTrieNodeChildren(_)
-            | Error::FailedToRetrieveAccumulationPurse => false,
+            | Error::FailedToRetrieveAccumulationPurse
+            | Error::ReservedArgument(_) => false,
         },
         ExecutionResult::Success { .. } => false,
     }
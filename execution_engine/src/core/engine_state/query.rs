@@ -3,7 +3,8 @@ use casper_hashing::Digest;
 use casper_types::{Key, StoredValue};
 
 use crate::{
-    core::tracking_copy::TrackingCopyQueryResult, storage::trie::merkle_proof::TrieMerkleProof,
+    core::tracking_copy::TrackingCopyQueryResult,
+    storage::trie::merkle_proof::{TrieMerkleMultiProof, TrieMerkleProof},
 };
 
 /// Result of a global state query request.
@@ -63,6 +64,46 @@ impl QueryRequest {
     }
 }
 
+/// Result of a global state query for several keys at once.
+#[derive(Debug)]
+pub enum MultiQueryResult {
+    /// Invalid state root hash.
+    RootNotFound,
+    /// Value not found.
+    ValueNotFound(String),
+    /// Successful query.
+    Success {
+        /// Stored values under the requested keys, in the order of the request.
+        values: Vec<StoredValue>,
+        /// A single Merkle proof covering all of the values.
+        proof: Box<TrieMerkleMultiProof<Key, StoredValue>>,
+    },
+}
+
+/// Request for a global state query for several keys at once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiQueryRequest {
+    state_hash: Digest,
+    keys: Vec<Key>,
+}
+
+impl MultiQueryRequest {
+    /// Creates new request object.
+    pub fn new(state_hash: Digest, keys: Vec<Key>) -> Self {
+        MultiQueryRequest { state_hash, keys }
+    }
+
+    /// Returns state root hash.
+    pub fn state_hash(&self) -> Digest {
+        self.state_hash
+    }
+
+    /// Returns the keys.
+    pub fn keys(&self) -> &[Key] {
+        &self.keys
+    }
+}
+
 impl From<TrackingCopyQueryResult> for QueryResult {
     fn from(tracking_copy_query_result: TrackingCopyQueryResult) -> Self {
         match tracking_copy_query_result {
@@ -0,0 +1,41 @@
+//! Support for computing the differences between two global state roots.
+use casper_hashing::Digest;
+
+/// Represents a request to compute the differences between two global state roots.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateDiffRequest {
+    old_state_hash: Digest,
+    new_state_hash: Digest,
+}
+
+impl StateDiffRequest {
+    /// Creates new request.
+    pub fn new(old_state_hash: Digest, new_state_hash: Digest) -> Self {
+        StateDiffRequest {
+            old_state_hash,
+            new_state_hash,
+        }
+    }
+
+    /// Returns the state root hash to compare from.
+    pub fn old_state_hash(&self) -> Digest {
+        self.old_state_hash
+    }
+
+    /// Returns the state root hash to compare to.
+    pub fn new_state_hash(&self) -> Digest {
+        self.new_state_hash
+    }
+}
+
+/// Represents a result of a `diff_state_roots` request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateDiffResult {
+    /// The given state root hash was not found.
+    RootNotFound(Digest),
+    /// All differences were passed to the visitor.
+    Success {
+        /// Number of keys which differ between the two roots.
+        changed_keys: usize,
+    },
+}
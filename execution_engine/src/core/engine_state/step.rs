@@ -5,7 +5,13 @@
 use std::{collections::BTreeMap, vec::Vec};
 
 use casper_hashing::Digest;
-use casper_types::{bytesrepr, CLValueError, EraId, ProtocolVersion, PublicKey};
+use num_rational::Ratio;
+
+use casper_types::{
+    bytesrepr,
+    system::{auction::SlashingOffence, mint::ScheduledTransferResult},
+    CLValueError, EraId, ProtocolVersion, PublicKey,
+};
 
 use crate::{
     core::{engine_state::Error, execution, runtime::stack::RuntimeStackOverflow},
@@ -17,12 +23,20 @@ use crate::{
 pub struct SlashItem {
     /// The public key of the validator that will be slashed.
     pub validator_id: PublicKey,
+    /// The offence the validator is slashed for.
+    pub offence: SlashingOffence,
+    /// Fraction of the validator's and its delegators' stakes that will be burned.
+    pub fraction: Ratio<u64>,
 }
 
 impl SlashItem {
     /// Creates a new slash item.
-    pub fn new(validator_id: PublicKey) -> Self {
-        Self { validator_id }
+    pub fn new(validator_id: PublicKey, offence: SlashingOffence, fraction: Ratio<u64>) -> Self {
+        Self {
+            validator_id,
+            offence,
+            fraction,
+        }
     }
 }
 
@@ -117,6 +131,29 @@ impl StepRequest {
             .collect()
     }
 
+    /// Returns the offence and fraction to slash for each slashed validator.
+    ///
+    /// Items with a zero fraction are skipped.  If a validator is listed more than once, the item
+    /// with the largest fraction is used.
+    pub fn slashes(&self) -> BTreeMap<PublicKey, (SlashingOffence, Ratio<u64>)> {
+        let mut ret: BTreeMap<PublicKey, (SlashingOffence, Ratio<u64>)> = BTreeMap::new();
+        for slash_item in &self.slash_items {
+            if slash_item.fraction == Ratio::from_integer(0) {
+                continue;
+            }
+            match ret.get(&slash_item.validator_id) {
+                Some((_, fraction)) if *fraction >= slash_item.fraction => {}
+                _ => {
+                    ret.insert(
+                        slash_item.validator_id.clone(),
+                        (slash_item.offence, slash_item.fraction),
+                    );
+                }
+            }
+        }
+        ret
+    }
+
     /// Returns all reward factors.
     pub fn reward_factors(&self) -> Result<BTreeMap<PublicKey, u64>, bytesrepr::Error> {
         let mut ret = BTreeMap::new();
@@ -154,6 +191,9 @@ pub enum StepError {
     /// Error executing a distribute accumulated fees operation.
     #[error("Distribute accumulated fees error: {0}")]
     DistributeAccumulatedFeesError(Error),
+    /// Error running the scheduled transfers.
+    #[error("Scheduled transfers error: {0}")]
+    ScheduledTransfersError(Error),
     /// Invalid protocol version.
     #[error("Invalid protocol version: {0}")]
     InvalidProtocolVersion(ProtocolVersion),
@@ -196,4 +236,6 @@ pub struct StepSuccess {
     pub post_state_hash: Digest,
     /// Effects of executing a step request.
     pub execution_journal: ExecutionJournal,
+    /// Outcomes of the scheduled transfers run by the step.
+    pub scheduled_transfers: Vec<ScheduledTransferResult>,
 }
@@ -176,6 +176,9 @@ pub enum Error {
     /// Failed to transfer tokens on a private chain.
     #[error("Failed to transfer with unrestricted transfers disabled")]
     DisabledUnrestrictedTransfers,
+    /// An execution hook broke on a call to a host function.
+    #[error("Breakpoint hit on host function {0}")]
+    Breakpoint(String),
 }
 
 impl From<wasm_prep::PreprocessingError> for Error {
@@ -214,6 +217,8 @@ impl From<casper_wasmi::Error> for Error {
     }
 }
 
+impl wasmi::core::HostError for Error {}
+
 impl From<storage::error::Error> for Error {
     fn from(e: storage::error::Error) -> Self {
         Error::Storage(e)
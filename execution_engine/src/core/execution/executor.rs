@@ -1,26 +1,29 @@
-use std::{cell::RefCell, collections::BTreeSet, rc::Rc};
+use std::{cell::RefCell, collections::BTreeSet, rc::Rc, sync::Arc};
 
 use casper_types::{
     account::{Account, AccountHash},
     bytesrepr::FromBytes,
     contracts::NamedKeys,
     system::{auction, handle_payment, mint, AUCTION, HANDLE_PAYMENT, MINT},
-    BlockTime, CLTyped, ContextAccessRights, DeployHash, EntryPointType, Gas, Key, Phase,
-    ProtocolVersion, RuntimeArgs, StoredValue, U512,
+    BlockTime, CLTyped, ContextAccessRights, ContractHash, DeployHash, EntryPointType, Gas, Key,
+    Phase, ProtocolVersion, RuntimeArgs, StoredValue, U512,
 };
 
 use crate::{
     core::{
         engine_state::{
-            executable_deploy_item::ExecutionKind, execution_result::ExecutionResult, EngineConfig,
-            ExecError,
+            executable_deploy_item::ExecutionKind,
+            execution_result::ExecutionResult,
+            execution_trace::ExecutionHook,
+            gas_profile::{GasProfile, GasProfiler},
+            EngineConfig, ExecError,
         },
         execution::{address_generator::AddressGenerator, Error},
         runtime::{Runtime, RuntimeStack},
         runtime_context::RuntimeContext,
         tracking_copy::{TrackingCopy, TrackingCopyExt},
     },
-    shared::newtypes::CorrelationId,
+    shared::{module_cache::ModuleCache, newtypes::CorrelationId},
     storage::global_state::StateReader,
 };
 
@@ -35,18 +38,52 @@ fn try_get_amount(runtime_args: &RuntimeArgs) -> Result<U512, ExecError> {
 /// Executor object deals with execution of WASM modules.
 pub struct Executor {
     config: EngineConfig,
+    module_cache: Arc<ModuleCache>,
+    gas_profiler: Option<Rc<RefCell<GasProfiler>>>,
+    execution_hook: Option<Rc<RefCell<dyn ExecutionHook>>>,
 }
 
 impl Executor {
     /// Creates new executor object.
-    pub fn new(config: EngineConfig) -> Self {
-        Executor { config }
+    pub fn new(config: EngineConfig, module_cache: Arc<ModuleCache>) -> Self {
+        Executor {
+            config,
+            module_cache,
+            gas_profiler: None,
+            execution_hook: None,
+        }
+    }
+
+    /// Enables profiling of the gas charged by the executions run by this executor.
+    pub(crate) fn with_gas_profiling(mut self) -> Self {
+        let gas_profiler = GasProfiler::new(self.config.wasm_config().opcode_costs());
+        self.gas_profiler = Some(Rc::new(RefCell::new(gas_profiler)));
+        self
+    }
+
+    /// Returns the profile of the gas charged since it was last taken, if gas is being profiled.
+    pub(crate) fn take_gas_profile(&self) -> Option<GasProfile> {
+        self.gas_profiler
+            .as_ref()
+            .map(|gas_profiler| gas_profiler.borrow_mut().take_profile())
+    }
+
+    /// Sets the hook called at each event of the executions run by this executor.
+    pub(crate) fn with_execution_hook(
+        mut self,
+        execution_hook: Rc<RefCell<dyn ExecutionHook>>,
+    ) -> Self {
+        self.execution_hook = Some(execution_hook);
+        self
     }
 
     /// Executes a WASM module.
     ///
     /// This method checks if a given contract hash is a system contract, and then short circuits to
     /// a specific native implementation of it. Otherwise, a supplied WASM module is executed.
+    ///
+    /// `payment_sponsor` is the contract called to approve the payment of a sponsored deploy, the
+    /// only one then allowed to pay for it via the handle payment's `sponsor_payment`.
     #[allow(clippy::too_many_arguments)]
     pub(crate) fn exec<R>(
         &self,
@@ -64,6 +101,7 @@ impl Executor {
         tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
         phase: Phase,
         stack: RuntimeStack,
+        payment_sponsor: Option<ContractHash>,
     ) -> ExecutionResult
     where
         R: StateReader<Key, StoredValue>,
@@ -81,7 +119,7 @@ impl Executor {
             Rc::new(RefCell::new(generator))
         };
 
-        let context = self.create_runtime_context(
+        let mut context = self.create_runtime_context(
             EntryPointType::Session,
             args.clone(),
             named_keys,
@@ -99,8 +137,12 @@ impl Executor {
             phase,
             spending_limit,
         );
+        if let Some(payment_sponsor) = payment_sponsor {
+            context.set_payment_sponsor(payment_sponsor);
+        }
 
-        let mut runtime = Runtime::new(self.config.clone(), context);
+        let mut runtime =
+            Runtime::new(self.config.clone(), Arc::clone(&self.module_cache), context);
 
         let result = match execution_kind {
             ExecutionKind::Module(module_bytes) => {
@@ -122,12 +164,15 @@ impl Executor {
                 execution_journal: runtime.context().execution_journal(),
                 transfers: runtime.context().transfers().to_owned(),
                 cost: runtime.context().gas_counter(),
+                events: runtime.context().events().to_owned(),
+                sponsor: None,
             },
             Err(error) => ExecutionResult::Failure {
                 error: error.into(),
                 execution_journal: runtime.context().execution_journal(),
                 transfers: runtime.context().transfers().to_owned(),
                 cost: runtime.context().gas_counter(),
+                sponsor: None,
             },
         }
     }
@@ -190,19 +235,26 @@ impl Executor {
 
         // Standard payment is executed in the calling account's context; the stack already
         // captures that.
-        let mut runtime = Runtime::new(self.config.clone(), runtime_context);
+        let mut runtime = Runtime::new(
+            self.config.clone(),
+            Arc::clone(&self.module_cache),
+            runtime_context,
+        );
 
         match runtime.call_host_standard_payment(stack) {
             Ok(()) => ExecutionResult::Success {
                 execution_journal: runtime.context().execution_journal(),
                 transfers: runtime.context().transfers().to_owned(),
                 cost: runtime.context().gas_counter(),
+                events: runtime.context().events().to_owned(),
+                sponsor: None,
             },
             Err(error) => ExecutionResult::Failure {
                 execution_journal,
                 error: error.into(),
                 transfers: runtime.context().transfers().to_owned(),
                 cost: runtime.context().gas_counter(),
+                sponsor: None,
             },
         }
     }
@@ -259,7 +311,9 @@ impl Executor {
                     .expect("should have auction hash");
                 *auction_hash
             }
-            DirectSystemContractCall::CreatePurse | DirectSystemContractCall::Transfer => {
+            DirectSystemContractCall::CreatePurse
+            | DirectSystemContractCall::Transfer
+            | DirectSystemContractCall::RunScheduledTransfers => {
                 let mint_hash = system_contract_registry
                     .get(MINT)
                     .expect("should have mint hash");
@@ -306,7 +360,11 @@ impl Executor {
             remaining_spending_limit,
         );
 
-        let mut runtime = Runtime::new(self.config.clone(), runtime_context);
+        let mut runtime = Runtime::new(
+            self.config.clone(),
+            Arc::clone(&self.module_cache),
+            runtime_context,
+        );
 
         // DO NOT alter this logic to call a system contract directly (such as via mint_internal,
         // etc). Doing so would bypass necessary context based security checks in some use cases. It
@@ -322,6 +380,8 @@ impl Executor {
                     execution_journal: runtime.context().execution_journal(),
                     transfers: runtime.context().transfers().to_owned(),
                     cost: runtime.context().gas_counter(),
+                    events: runtime.context().events().to_owned(),
+                    sponsor: None,
                 }
                 .take_with_ret(ret),
                 Err(error) => ExecutionResult::Failure {
@@ -329,6 +389,7 @@ impl Executor {
                     error: Error::CLValue(error).into(),
                     transfers: runtime.context().transfers().to_owned(),
                     cost: runtime.context().gas_counter(),
+                    sponsor: None,
                 }
                 .take_without_ret(),
             },
@@ -337,6 +398,7 @@ impl Executor {
                 error: error.into(),
                 transfers: runtime.context().transfers().to_owned(),
                 cost: runtime.context().gas_counter(),
+                sponsor: None,
             }
             .take_without_ret(),
         }
@@ -370,7 +432,7 @@ impl Executor {
         let gas_counter = Gas::default();
         let transfers = Vec::default();
 
-        RuntimeContext::new(
+        let mut runtime_context = RuntimeContext::new(
             tracking_copy,
             entry_point_type,
             named_keys,
@@ -390,7 +452,14 @@ impl Executor {
             self.config.clone(),
             transfers,
             remaining_spending_limit,
-        )
+        );
+        if let Some(gas_profiler) = &self.gas_profiler {
+            runtime_context.set_gas_profiler(Rc::clone(gas_profiler));
+        }
+        if let Some(execution_hook) = &self.execution_hook {
+            runtime_context.set_execution_hook(Rc::clone(execution_hook));
+        }
+        runtime_context
     }
 }
 
@@ -412,6 +481,8 @@ pub(crate) enum DirectSystemContractCall {
     GetPaymentPurse,
     /// Calls handle payment's `distribute_accumulated_fees` entry point.
     DistributeAccumulatedFees,
+    /// Calls mint's `run_scheduled_transfers` entry point.
+    RunScheduledTransfers,
 }
 
 impl DirectSystemContractCall {
@@ -427,6 +498,7 @@ impl DirectSystemContractCall {
             DirectSystemContractCall::DistributeAccumulatedFees => {
                 handle_payment::METHOD_DISTRIBUTE_ACCUMULATED_FEES
             }
+            DirectSystemContractCall::RunScheduledTransfers => mint::METHOD_RUN_SCHEDULED_TRANSFERS,
         }
     }
 }
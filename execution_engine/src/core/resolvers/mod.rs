@@ -4,6 +4,8 @@ pub mod memory_resolver;
 pub(crate) mod v1_function_index;
 mod v1_resolver;
 
+pub(crate) use v1_resolver::RuntimeModuleImportResolver;
+
 use casper_wasmi::ModuleImportResolver;
 
 use casper_types::ProtocolVersion;
@@ -12,22 +14,32 @@ use self::error::ResolverError;
 use super::engine_state::EngineConfig;
 use crate::core::resolvers::memory_resolver::MemoryResolver;
 
-/// Creates a module resolver for given protocol version.
+/// Checks that host functions can be resolved for given protocol version.
 ///
 /// * `protocol_version` Version of the protocol. Can't be lower than 1.
-pub(crate) fn create_module_resolver(
+pub(crate) fn check_protocol_version(
     protocol_version: ProtocolVersion,
-    engine_config: &EngineConfig,
-) -> Result<impl ModuleImportResolver + MemoryResolver, ResolverError> {
+) -> Result<(), ResolverError> {
     // TODO: revisit how protocol_version check here is meant to combine with upgrade
     if protocol_version >= ProtocolVersion::V1_0_0 {
-        return Ok(v1_resolver::RuntimeModuleImportResolver::new(
-            engine_config.wasm_config().max_memory,
-        ));
+        return Ok(());
     }
     Err(ResolverError::UnknownProtocolVersion(protocol_version))
 }
 
+/// Creates a module resolver for given protocol version.
+///
+/// * `protocol_version` Version of the protocol. Can't be lower than 1.
+pub(crate) fn create_module_resolver(
+    protocol_version: ProtocolVersion,
+    engine_config: &EngineConfig,
+) -> Result<impl ModuleImportResolver + MemoryResolver, ResolverError> {
+    check_protocol_version(protocol_version)?;
+    Ok(RuntimeModuleImportResolver::new(
+        engine_config.wasm_config().max_memory,
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use casper_types::ProtocolVersion;
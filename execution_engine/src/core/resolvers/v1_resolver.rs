@@ -21,229 +21,256 @@ impl RuntimeModuleImportResolver {
             max_memory,
         }
     }
-}
 
-impl MemoryResolver for RuntimeModuleImportResolver {
-    fn memory_ref(&self) -> Result<MemoryRef, ResolverError> {
-        self.memory
-            .borrow()
-            .as_ref()
-            .map(Clone::clone)
-            .ok_or(ResolverError::NoImportedMemory)
+    /// Checks that a memory with the given `initial` and `maximum` pages can be imported, for
+    /// engines other than `casper-wasmi` to resolve memory imports the same way.
+    pub(crate) fn check_memory_limits(
+        initial: u32,
+        maximum: Option<u32>,
+        max_memory: u32,
+    ) -> Result<(), String> {
+        // Any memory entry in the wasm file without max specified is changed into an entry with
+        // hardcoded max value. This way `maximum` below is never unspecified, but for safety
+        // reasons we'll still default it.
+        let maximum = maximum.unwrap_or(max_memory);
+        // Checks if wasm's memory entry has too much initial memory or non-default max memory
+        // pages exceeds the limit.
+        if initial > maximum || maximum > max_memory {
+            return Err("Module requested too much memory".into());
+        }
+        Ok(())
     }
-}
 
-impl ModuleImportResolver for RuntimeModuleImportResolver {
-    fn resolve_func(
-        &self,
+    /// Returns the signature and index of the host function imported as `field_name`, for engines
+    /// other than `casper-wasmi` to resolve function imports the same way.
+    pub(crate) fn host_function(
         field_name: &str,
-        _signature: &Signature,
-    ) -> Result<FuncRef, InterpreterError> {
-        let func_ref = match field_name {
-            "casper_read_value" => FuncInstance::alloc_host(
+    ) -> Result<(Signature, FunctionIndex), InterpreterError> {
+        let host_function = match field_name {
+            "casper_read_value" => (
                 Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::ReadFuncIndex.into(),
+                FunctionIndex::ReadFuncIndex,
             ),
-            "casper_load_named_keys" => FuncInstance::alloc_host(
+            "casper_load_named_keys" => (
                 Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
-                FunctionIndex::LoadNamedKeysFuncIndex.into(),
+                FunctionIndex::LoadNamedKeysFuncIndex,
             ),
-            "casper_write" => FuncInstance::alloc_host(
+            "casper_write" => (
                 Signature::new(&[ValueType::I32; 4][..], None),
-                FunctionIndex::WriteFuncIndex.into(),
+                FunctionIndex::WriteFuncIndex,
             ),
-            "casper_add" => FuncInstance::alloc_host(
+            "casper_add" => (
                 Signature::new(&[ValueType::I32; 4][..], None),
-                FunctionIndex::AddFuncIndex.into(),
+                FunctionIndex::AddFuncIndex,
             ),
-            "casper_new_uref" => FuncInstance::alloc_host(
+            "casper_new_uref" => (
                 Signature::new(&[ValueType::I32; 3][..], None),
-                FunctionIndex::NewFuncIndex.into(),
+                FunctionIndex::NewFuncIndex,
             ),
-            "casper_ret" => FuncInstance::alloc_host(
+            "casper_ret" => (
                 Signature::new(&[ValueType::I32; 2][..], None),
-                FunctionIndex::RetFuncIndex.into(),
+                FunctionIndex::RetFuncIndex,
             ),
-            "casper_get_key" => FuncInstance::alloc_host(
+            "casper_get_key" => (
                 Signature::new(&[ValueType::I32; 5][..], Some(ValueType::I32)),
-                FunctionIndex::GetKeyFuncIndex.into(),
+                FunctionIndex::GetKeyFuncIndex,
             ),
-            "casper_has_key" => FuncInstance::alloc_host(
+            "casper_has_key" => (
                 Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
-                FunctionIndex::HasKeyFuncIndex.into(),
+                FunctionIndex::HasKeyFuncIndex,
             ),
-            "casper_put_key" => FuncInstance::alloc_host(
+            "casper_put_key" => (
                 Signature::new(&[ValueType::I32; 4][..], None),
-                FunctionIndex::PutKeyFuncIndex.into(),
+                FunctionIndex::PutKeyFuncIndex,
             ),
-            "gas" => FuncInstance::alloc_host(
+            "gas" => (
                 Signature::new(&[ValueType::I32; 1][..], None),
-                FunctionIndex::GasFuncIndex.into(),
+                FunctionIndex::GasFuncIndex,
             ),
-            "casper_is_valid_uref" => FuncInstance::alloc_host(
+            "casper_is_valid_uref" => (
                 Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
-                FunctionIndex::IsValidURefFnIndex.into(),
+                FunctionIndex::IsValidURefFnIndex,
             ),
-            "casper_revert" => FuncInstance::alloc_host(
+            "casper_revert" => (
                 Signature::new(&[ValueType::I32; 1][..], None),
-                FunctionIndex::RevertFuncIndex.into(),
+                FunctionIndex::RevertFuncIndex,
             ),
-            "casper_add_associated_key" => FuncInstance::alloc_host(
+            "casper_add_associated_key" => (
                 Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::AddAssociatedKeyFuncIndex.into(),
+                FunctionIndex::AddAssociatedKeyFuncIndex,
             ),
-            "casper_remove_associated_key" => FuncInstance::alloc_host(
+            "casper_remove_associated_key" => (
                 Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
-                FunctionIndex::RemoveAssociatedKeyFuncIndex.into(),
+                FunctionIndex::RemoveAssociatedKeyFuncIndex,
             ),
-            "casper_update_associated_key" => FuncInstance::alloc_host(
+            "casper_update_associated_key" => (
                 Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::UpdateAssociatedKeyFuncIndex.into(),
+                FunctionIndex::UpdateAssociatedKeyFuncIndex,
             ),
-            "casper_set_action_threshold" => FuncInstance::alloc_host(
+            "casper_set_action_threshold" => (
                 Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
-                FunctionIndex::SetActionThresholdFuncIndex.into(),
+                FunctionIndex::SetActionThresholdFuncIndex,
             ),
-            "casper_remove_key" => FuncInstance::alloc_host(
+            "casper_remove_key" => (
                 Signature::new(&[ValueType::I32; 2][..], None),
-                FunctionIndex::RemoveKeyFuncIndex.into(),
+                FunctionIndex::RemoveKeyFuncIndex,
             ),
-            "casper_get_caller" => FuncInstance::alloc_host(
+            "casper_get_caller" => (
                 Signature::new(&[ValueType::I32; 1][..], Some(ValueType::I32)),
-                FunctionIndex::GetCallerIndex.into(),
+                FunctionIndex::GetCallerIndex,
             ),
-            "casper_get_blocktime" => FuncInstance::alloc_host(
+            "casper_get_blocktime" => (
                 Signature::new(&[ValueType::I32; 1][..], None),
-                FunctionIndex::GetBlocktimeIndex.into(),
+                FunctionIndex::GetBlocktimeIndex,
             ),
-            "casper_create_purse" => FuncInstance::alloc_host(
+            "casper_create_purse" => (
                 Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
-                FunctionIndex::CreatePurseIndex.into(),
+                FunctionIndex::CreatePurseIndex,
             ),
-            "casper_transfer_to_account" => FuncInstance::alloc_host(
+            "casper_transfer_to_account" => (
                 Signature::new(&[ValueType::I32; 7][..], Some(ValueType::I32)),
-                FunctionIndex::TransferToAccountIndex.into(),
+                FunctionIndex::TransferToAccountIndex,
             ),
-            "casper_transfer_from_purse_to_account" => FuncInstance::alloc_host(
+            "casper_transfer_from_purse_to_account" => (
                 Signature::new(&[ValueType::I32; 9][..], Some(ValueType::I32)),
-                FunctionIndex::TransferFromPurseToAccountIndex.into(),
+                FunctionIndex::TransferFromPurseToAccountIndex,
             ),
-            "casper_transfer_from_purse_to_purse" => FuncInstance::alloc_host(
+            "casper_transfer_from_purse_to_purse" => (
                 Signature::new(&[ValueType::I32; 8][..], Some(ValueType::I32)),
-                FunctionIndex::TransferFromPurseToPurseIndex.into(),
+                FunctionIndex::TransferFromPurseToPurseIndex,
             ),
-            "casper_get_balance" => FuncInstance::alloc_host(
+            "casper_get_balance" => (
                 Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::GetBalanceIndex.into(),
+                FunctionIndex::GetBalanceIndex,
             ),
-            "casper_get_phase" => FuncInstance::alloc_host(
+            "casper_get_phase" => (
                 Signature::new(&[ValueType::I32; 1][..], None),
-                FunctionIndex::GetPhaseIndex.into(),
+                FunctionIndex::GetPhaseIndex,
             ),
-            "casper_get_system_contract" => FuncInstance::alloc_host(
+            "casper_get_system_contract" => (
                 Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::GetSystemContractIndex.into(),
+                FunctionIndex::GetSystemContractIndex,
             ),
-            "casper_get_main_purse" => FuncInstance::alloc_host(
+            "casper_get_main_purse" => (
                 Signature::new(&[ValueType::I32; 1][..], None),
-                FunctionIndex::GetMainPurseIndex.into(),
+                FunctionIndex::GetMainPurseIndex,
             ),
-            "casper_read_host_buffer" => FuncInstance::alloc_host(
+            "casper_read_host_buffer" => (
                 Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::ReadHostBufferIndex.into(),
+                FunctionIndex::ReadHostBufferIndex,
             ),
-            "casper_create_contract_package_at_hash" => FuncInstance::alloc_host(
+            "casper_create_contract_package_at_hash" => (
                 Signature::new(&[ValueType::I32; 3][..], None),
-                FunctionIndex::CreateContractPackageAtHash.into(),
+                FunctionIndex::CreateContractPackageAtHash,
             ),
-            "casper_create_contract_user_group" => FuncInstance::alloc_host(
+            "casper_create_contract_user_group" => (
                 Signature::new(&[ValueType::I32; 8][..], Some(ValueType::I32)),
-                FunctionIndex::CreateContractUserGroup.into(),
+                FunctionIndex::CreateContractUserGroup,
             ),
-            "casper_add_contract_version" => FuncInstance::alloc_host(
+            "casper_add_contract_version" => (
                 Signature::new(&[ValueType::I32; 10][..], Some(ValueType::I32)),
-                FunctionIndex::AddContractVersion.into(),
+                FunctionIndex::AddContractVersion,
             ),
-            "casper_disable_contract_version" => FuncInstance::alloc_host(
+            "casper_disable_contract_version" => (
                 Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
-                FunctionIndex::DisableContractVersion.into(),
+                FunctionIndex::DisableContractVersion,
             ),
-            "casper_call_contract" => FuncInstance::alloc_host(
+            "casper_call_contract" => (
                 Signature::new(&[ValueType::I32; 7][..], Some(ValueType::I32)),
-                FunctionIndex::CallContractFuncIndex.into(),
+                FunctionIndex::CallContractFuncIndex,
             ),
-            "casper_call_versioned_contract" => FuncInstance::alloc_host(
+            "casper_call_versioned_contract" => (
                 Signature::new(&[ValueType::I32; 9][..], Some(ValueType::I32)),
-                FunctionIndex::CallVersionedContract.into(),
+                FunctionIndex::CallVersionedContract,
             ),
-            "casper_get_named_arg_size" => FuncInstance::alloc_host(
+            "casper_get_named_arg_size" => (
                 Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::GetRuntimeArgsizeIndex.into(),
+                FunctionIndex::GetRuntimeArgsizeIndex,
             ),
-            "casper_get_named_arg" => FuncInstance::alloc_host(
+            "casper_get_named_arg" => (
                 Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
-                FunctionIndex::GetRuntimeArgIndex.into(),
+                FunctionIndex::GetRuntimeArgIndex,
             ),
-            "casper_remove_contract_user_group" => FuncInstance::alloc_host(
+            "casper_remove_contract_user_group" => (
                 Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
-                FunctionIndex::RemoveContractUserGroupIndex.into(),
+                FunctionIndex::RemoveContractUserGroupIndex,
             ),
-            "casper_provision_contract_user_group_uref" => FuncInstance::alloc_host(
+            "casper_provision_contract_user_group_uref" => (
                 Signature::new(&[ValueType::I32; 5][..], Some(ValueType::I32)),
-                FunctionIndex::ExtendContractUserGroupURefsIndex.into(),
+                FunctionIndex::ExtendContractUserGroupURefsIndex,
             ),
-            "casper_remove_contract_user_group_urefs" => FuncInstance::alloc_host(
+            "casper_remove_contract_user_group_urefs" => (
                 Signature::new(&[ValueType::I32; 6][..], Some(ValueType::I32)),
-                FunctionIndex::RemoveContractUserGroupURefsIndex.into(),
+                FunctionIndex::RemoveContractUserGroupURefsIndex,
             ),
-            "casper_blake2b" => FuncInstance::alloc_host(
+            "casper_blake2b" => (
                 Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
-                FunctionIndex::Blake2b.into(),
+                FunctionIndex::Blake2b,
             ),
-            "casper_record_transfer" => FuncInstance::alloc_host(
+            "casper_record_transfer" => (
                 Signature::new(&[ValueType::I32; 10][..], Some(ValueType::I32)),
-                FunctionIndex::RecordTransfer.into(),
+                FunctionIndex::RecordTransfer,
             ),
-            "casper_record_era_info" => FuncInstance::alloc_host(
+            "casper_record_era_info" => (
                 Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
-                FunctionIndex::RecordEraInfo.into(),
+                FunctionIndex::RecordEraInfo,
             ),
-            "casper_load_call_stack" => FuncInstance::alloc_host(
+            "casper_load_call_stack" => (
                 Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
-                FunctionIndex::LoadCallStack.into(),
+                FunctionIndex::LoadCallStack,
             ),
             #[cfg(feature = "test-support")]
-            "casper_print" => FuncInstance::alloc_host(
+            "casper_print" => (
                 Signature::new(&[ValueType::I32; 2][..], None),
-                FunctionIndex::PrintIndex.into(),
+                FunctionIndex::PrintIndex,
             ),
-            "casper_dictionary_get" => FuncInstance::alloc_host(
+            "casper_dictionary_get" => (
                 Signature::new(&[ValueType::I32; 5][..], Some(ValueType::I32)),
-                FunctionIndex::DictionaryGetFuncIndex.into(),
+                FunctionIndex::DictionaryGetFuncIndex,
             ),
-            "casper_dictionary_read" => FuncInstance::alloc_host(
+            "casper_dictionary_read" => (
                 Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::DictionaryReadFuncIndex.into(),
+                FunctionIndex::DictionaryReadFuncIndex,
             ),
-            "casper_dictionary_put" => FuncInstance::alloc_host(
+            "casper_dictionary_put" => (
                 Signature::new(&[ValueType::I32; 6][..], Some(ValueType::I32)),
-                FunctionIndex::DictionaryPutFuncIndex.into(),
+                FunctionIndex::DictionaryPutFuncIndex,
             ),
-            "casper_new_dictionary" => FuncInstance::alloc_host(
+            "casper_new_dictionary" => (
                 Signature::new(&[ValueType::I32; 1][..], Some(ValueType::I32)),
-                FunctionIndex::NewDictionaryFuncIndex.into(),
+                FunctionIndex::NewDictionaryFuncIndex,
             ),
-            "casper_load_authorization_keys" => FuncInstance::alloc_host(
+            "casper_load_authorization_keys" => (
                 Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
-                FunctionIndex::LoadAuthorizationKeys.into(),
+                FunctionIndex::LoadAuthorizationKeys,
             ),
-            "casper_random_bytes" => FuncInstance::alloc_host(
+            "casper_random_bytes" => (
                 Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
-                FunctionIndex::RandomBytes.into(),
+                FunctionIndex::RandomBytes,
+            ),
+            "casper_enable_contract_version" => (
+                Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
+                FunctionIndex::EnableContractVersion,
+            ),
+            "casper_emit_event" => (
+                Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
+                FunctionIndex::EmitEvent,
+            ),
+            "casper_verify_signature" => (
+                Signature::new(&[ValueType::I32; 6][..], Some(ValueType::I32)),
+                FunctionIndex::VerifySignature,
+            ),
+            "casper_sha256" => (
+                Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
+                FunctionIndex::Sha256,
             ),
-            "casper_enable_contract_version" => FuncInstance::alloc_host(
+            "casper_keccak256" => (
                 Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
-                FunctionIndex::EnableContractVersion.into(),
+                FunctionIndex::Keccak256,
+            ),
+            "casper_approve_allowance" => (
+                Signature::new(&[ValueType::I32; 8][..], Some(ValueType::I32)),
+                FunctionIndex::ApproveAllowance,
             ),
             _ => {
                 return Err(InterpreterError::Function(format!(
@@ -252,7 +279,28 @@ impl ModuleImportResolver for RuntimeModuleImportResolver {
                 )));
             }
         };
-        Ok(func_ref)
+        Ok(host_function)
+    }
+}
+
+impl MemoryResolver for RuntimeModuleImportResolver {
+    fn memory_ref(&self) -> Result<MemoryRef, ResolverError> {
+        self.memory
+            .borrow()
+            .as_ref()
+            .map(Clone::clone)
+            .ok_or(ResolverError::NoImportedMemory)
+    }
+}
+
+impl ModuleImportResolver for RuntimeModuleImportResolver {
+    fn resolve_func(
+        &self,
+        field_name: &str,
+        _signature: &Signature,
+    ) -> Result<FuncRef, InterpreterError> {
+        let (signature, index) = Self::host_function(field_name)?;
+        Ok(FuncInstance::alloc_host(signature, index.into()))
     }
 
     fn resolve_memory(
@@ -270,17 +318,12 @@ impl ModuleImportResolver for RuntimeModuleImportResolver {
                     ))
                 }
                 memory_ref @ None => {
-                    // Any memory entry in the wasm file without max specified is changed into an
-                    // entry with hardcoded max value. This way `maximum` below is never
-                    // unspecified, but for safety reasons we'll still default it.
-                    let descriptor_max = descriptor.maximum().unwrap_or(self.max_memory);
-                    // Checks if wasm's memory entry has too much initial memory or non-default max
-                    // memory pages exceeds the limit.
-                    if descriptor.initial() > descriptor_max || descriptor_max > self.max_memory {
-                        return Err(InterpreterError::Instantiation(
-                            "Module requested too much memory".into(),
-                        ));
-                    }
+                    Self::check_memory_limits(
+                        descriptor.initial(),
+                        descriptor.maximum(),
+                        self.max_memory,
+                    )
+                    .map_err(InterpreterError::Instantiation)?;
                     // Note: each "page" is 64 KiB
                     let mem = MemoryInstance::alloc(
                         Pages(descriptor.initial() as usize),
@@ -106,7 +106,24 @@ where
             .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))
     }
 
-    fn record_era_info(&mut self, _era_id: EraId, era_summary: EraInfo) -> Result<(), Error> {
+    fn read_era_info(&mut self) -> Result<Option<EraInfo>, Error> {
+        match self.context.read_gs(&Key::EraSummary) {
+            Ok(Some(StoredValue::EraInfo(era_info))) => Ok(Some(era_info)),
+            Ok(Some(_)) => Err(Error::Storage),
+            Ok(None) => Ok(None),
+            Err(execution::Error::BytesRepr(_)) => Err(Error::Serialization),
+            // NOTE: This extra condition is needed to correctly propagate GasLimit to the user. See
+            // also [`Runtime::reverter`] and [`to_auction_error`]
+            Err(execution::Error::GasLimit) => Err(Error::GasLimit),
+            Err(_) => Err(Error::Storage),
+        }
+    }
+
+    fn record_era_info(
+        &mut self,
+        _era_id: Option<EraId>,
+        era_summary: EraInfo,
+    ) -> Result<(), Error> {
         Runtime::record_era_summary(self, era_summary)
             .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::RecordEraInfo))
     }
@@ -144,6 +161,10 @@ where
     fn allow_auction_bids(&self) -> bool {
         self.config.allow_auction_bids()
     }
+
+    fn allow_delegation_transfers(&self) -> bool {
+        self.config.allow_delegation_transfers()
+    }
 }
 
 impl<'a, R> MintProvider for Runtime<'a, R>
@@ -305,6 +326,18 @@ where
         // exception here.
         Ok(Runtime::context(self).account().main_purse())
     }
+
+    fn read_main_purse(&mut self, account_hash: &AccountHash) -> Result<Option<URef>, Error> {
+        let maybe_value = self
+            .context
+            .read_gs_direct(&Key::Account(*account_hash))
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))?;
+        match maybe_value {
+            Some(StoredValue::Account(account)) => Ok(Some(account.main_purse())),
+            Some(_) => Err(Error::CLValue),
+            None => Ok(None),
+        }
+    }
 }
 
 impl<'a, R> Auction for Runtime<'a, R>
@@ -1,6 +1,6 @@
 use std::{collections::BTreeSet, convert::TryFrom};
 
-use casper_wasmi::{Externals, RuntimeArgs, RuntimeValue, Trap};
+use casper_wasmi::{RuntimeArgs, RuntimeValue, Trap};
 
 use casper_types::{
     account::AccountHash,
@@ -9,18 +9,25 @@ use casper_types::{
     contracts::{ContractPackageStatus, EntryPoints, NamedKeys},
     crypto,
     system::auction::EraInfo,
-    ApiError, ContractHash, ContractPackageHash, ContractVersion, EraId, Gas, Group, Key,
-    StoredValue, URef, U512, UREF_SERIALIZED_LENGTH,
+    ApiError, CLValue, ContractHash, ContractPackageHash, ContractVersion, EraId, Gas, Group, Key,
+    PublicKey, Signature, StoredValue, URef, U512, UREF_SERIALIZED_LENGTH,
 };
 
 use super::{args::Args, Error, Runtime};
 use crate::{
-    core::resolvers::v1_function_index::FunctionIndex,
+    core::{
+        engine_state::{
+            execution_trace::{HookAction, TraceEvent},
+            gas_profile::GasCharge,
+        },
+        resolvers::v1_function_index::FunctionIndex,
+        wasm_engine::{WasmHost, WasmValue},
+    },
     shared::host_function_costs::{Cost, HostFunction},
     storage::global_state::StateReader,
 };
 
-impl<'a, R> Externals for Runtime<'a, R>
+impl<'a, R> WasmHost for Runtime<'a, R>
 where
     R: StateReader<Key, StoredValue>,
     R::Error: Into<Error>,
@@ -28,10 +35,62 @@ where
     fn invoke_index(
         &mut self,
         index: usize,
-        args: RuntimeArgs,
-    ) -> Result<Option<RuntimeValue>, Trap> {
+        args: &[WasmValue],
+    ) -> Result<Option<WasmValue>, Error> {
         let func = FunctionIndex::try_from(index).expect("unknown function index");
 
+        // `gas` is called by the metering code for every metered block, so it is neither traced
+        // nor profiled as a host function; the gas charged through it is recorded against the
+        // opcodes it meters instead.
+        if func == FunctionIndex::GasFuncIndex {
+            return self.call_host_function(func, args);
+        }
+
+        let name = func.host_function_name();
+        let _gas_profiler_frame = self
+            .context
+            .enter_gas_profiler_frame(|profiler| profiler.enter_host_function(name));
+        if self
+            .context
+            .on_execution_event(|| TraceEvent::host_call(name, args))
+            == HookAction::Break
+        {
+            return Err(Error::Breakpoint(name.to_string()));
+        }
+
+        let result = self.call_host_function(func, args);
+        self.context
+            .on_execution_event(|| TraceEvent::host_return(name, &result));
+        result
+    }
+}
+
+impl<'a, R> Runtime<'a, R>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<Error>,
+{
+    /// Calls the host function `func`, converting to and from the values and traps of
+    /// `casper-wasmi`, which host functions are implemented against.
+    ///
+    /// A trap which isn't an [`Error`] becomes an [`Error::Interpreter`] with the same message as
+    /// `casper-wasmi` would give it, since the message ends up in the execution result.
+    fn call_host_function(
+        &mut self,
+        func: FunctionIndex,
+        args: &[WasmValue],
+    ) -> Result<Option<WasmValue>, Error> {
+        let args: Vec<RuntimeValue> = args.iter().copied().map(RuntimeValue::from).collect();
+        self.invoke_host_function(func, RuntimeArgs::from(args.as_slice()))
+            .map(|value| value.map(WasmValue::from))
+            .map_err(|trap| Error::from(casper_wasmi::Error::Trap(trap)))
+    }
+
+    fn invoke_host_function(
+        &mut self,
+        func: FunctionIndex,
+        args: RuntimeArgs,
+    ) -> Result<Option<RuntimeValue>, Trap> {
         let host_function_costs = self.config.wasm_config().take_host_function_costs();
 
         match func {
@@ -189,7 +248,7 @@ where
                 let (gas_arg,): (u32,) = Args::parse(args)?;
                 // Gas is special cased internal host function and for accounting purposes it isn't
                 // represented in protocol data.
-                self.gas(Gas::new(gas_arg.into()))?;
+                self.gas(GasCharge::Opcodes, Gas::new(gas_arg.into()))?;
                 Ok(None)
             }
 
@@ -289,7 +348,7 @@ where
                     let purse_bytes = purse.into_bytes().map_err(Error::BytesRepr)?;
                     self.try_get_memory()?
                         .set(dest_ptr, &purse_bytes)
-                        .map_err(|e| Error::Interpreter(e.into()))?;
+                        .map_err(Error::Interpreter)?;
                     Ok(())
                 };
 
@@ -337,7 +396,7 @@ where
                         let result_value_bytes = result_value.to_le_bytes();
                         self.try_get_memory()?
                             .set(result_ptr, &result_value_bytes)
-                            .map_err(|error| Error::Interpreter(error.into()))?;
+                            .map_err(Error::Interpreter)?;
                         Ok(())
                     }
                     Err(api_error) => Err(api_error),
@@ -407,7 +466,7 @@ where
                         let result_value_bytes = result_value.to_le_bytes();
                         self.try_get_memory()?
                             .set(result_ptr, &result_value_bytes)
-                            .map_err(|error| Error::Interpreter(error.into()))?;
+                            .map_err(Error::Interpreter)?;
                         Ok(())
                     }
                     Err(api_error) => Err(api_error),
@@ -472,6 +531,63 @@ where
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
 
+            FunctionIndex::ApproveAllowance => {
+                // args(0) = pointer to array of bytes in Wasm memory of a purse
+                // args(1) = length of array of bytes in Wasm memory of a purse
+                // args(2) = pointer to array of bytes in Wasm memory of a spender key
+                // args(3) = length of array of bytes in Wasm memory of a spender key
+                // args(4) = pointer to array of bytes in Wasm memory of an amount
+                // args(5) = length of array of bytes in Wasm memory of an amount
+                // args(6) = pointer to array of bytes in Wasm memory of an expiry block time
+                // args(7) = length of array of bytes in Wasm memory of an expiry block time
+                let (
+                    purse_ptr,
+                    purse_size,
+                    spender_ptr,
+                    spender_size,
+                    amount_ptr,
+                    amount_size,
+                    expires_at_ptr,
+                    expires_at_size,
+                ) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    &host_function_costs.approve_allowance,
+                    [
+                        purse_ptr,
+                        purse_size,
+                        spender_ptr,
+                        spender_size,
+                        amount_ptr,
+                        amount_size,
+                        expires_at_ptr,
+                        expires_at_size,
+                    ],
+                )?;
+
+                let purse: URef = {
+                    let bytes = self.bytes_from_mem(purse_ptr, purse_size as usize)?;
+                    bytesrepr::deserialize_from_slice(bytes).map_err(Error::BytesRepr)?
+                };
+
+                let spender: Key = {
+                    let bytes = self.bytes_from_mem(spender_ptr, spender_size as usize)?;
+                    bytesrepr::deserialize_from_slice(bytes).map_err(Error::BytesRepr)?
+                };
+
+                let amount: U512 = {
+                    let bytes = self.bytes_from_mem(amount_ptr, amount_size as usize)?;
+                    bytesrepr::deserialize_from_slice(bytes).map_err(Error::BytesRepr)?
+                };
+
+                let expires_at: Option<u64> = {
+                    let bytes = self.bytes_from_mem(expires_at_ptr, expires_at_size as usize)?;
+                    bytesrepr::deserialize_from_slice(bytes).map_err(Error::BytesRepr)?
+                };
+
+                let ret = self.mint_approve_allowance(purse, spender, amount, expires_at)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
             FunctionIndex::GetBalanceIndex => {
                 // args(0) = pointer to purse input
                 // args(1) = length of purse
@@ -898,24 +1014,10 @@ where
                     &host_function_costs.blake2b,
                     [in_ptr, in_size, out_ptr, out_size],
                 )?;
-                let digest =
-                    self.checked_memory_slice(in_ptr as usize, in_size as usize, |input| {
-                        crypto::blake2b(input)
-                    })?;
-
-                let result = if digest.len() != out_size as usize {
-                    Err(ApiError::BufferTooSmall)
-                } else {
-                    Ok(())
-                };
-                if result.is_err() {
-                    return Ok(Some(RuntimeValue::I32(api_error::i32_from(result))));
-                }
-
-                self.try_get_memory()?
-                    .set(out_ptr, &digest)
-                    .map_err(|error| Error::Interpreter(error.into()))?;
-                Ok(Some(RuntimeValue::I32(0)))
+                let result = self.hash_into_mem(in_ptr, in_size, out_ptr, out_size, |input| {
+                    crypto::blake2b(input)
+                })?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(result))))
             }
 
             FunctionIndex::RecordTransfer => {
@@ -1073,7 +1175,7 @@ where
 
                 self.try_get_memory()?
                     .set(out_ptr, &random_bytes)
-                    .map_err(|error| Error::Interpreter(error.into()))?;
+                    .map_err(Error::Interpreter)?;
 
                 Ok(Some(RuntimeValue::I32(0)))
             }
@@ -1100,6 +1202,79 @@ where
 
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(result))))
             }
+            FunctionIndex::EmitEvent => {
+                // args(0) = pointer to the serialized topic in wasm memory
+                // args(1) = size of the serialized topic in wasm memory
+                // args(2) = pointer to the serialized value in wasm memory
+                // args(3) = size of the serialized value in wasm memory
+                let (topic_ptr, topic_size, value_ptr, value_size) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    &host_function_costs.emit_event,
+                    [topic_ptr, topic_size, value_ptr, value_size],
+                )?;
+                let topic: String = self.t_from_mem(topic_ptr, topic_size)?;
+                let value: CLValue = self.t_from_mem(value_ptr, value_size)?;
+                self.context.emit_event(topic, value);
+                Ok(Some(RuntimeValue::I32(0)))
+            }
+            FunctionIndex::VerifySignature => {
+                // args(0) = pointer to the message in wasm memory
+                // args(1) = size of the message in wasm memory
+                // args(2) = pointer to the serialized signature in wasm memory
+                // args(3) = size of the serialized signature in wasm memory
+                // args(4) = pointer to the serialized public key in wasm memory
+                // args(5) = size of the serialized public key in wasm memory
+                let (
+                    message_ptr,
+                    message_size,
+                    signature_ptr,
+                    signature_size,
+                    public_key_ptr,
+                    public_key_size,
+                ) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    &host_function_costs.verify_signature,
+                    [
+                        message_ptr,
+                        message_size,
+                        signature_ptr,
+                        signature_size,
+                        public_key_ptr,
+                        public_key_size,
+                    ],
+                )?;
+                let signature: Signature = self.t_from_mem(signature_ptr, signature_size)?;
+                let public_key: PublicKey = self.t_from_mem(public_key_ptr, public_key_size)?;
+                let result = self.checked_memory_slice(
+                    message_ptr as usize,
+                    message_size as usize,
+                    |message| crypto::verify(message, &signature, &public_key),
+                )?;
+                let result = result.map_err(|_| ApiError::InvalidSignature);
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(result))))
+            }
+            FunctionIndex::Sha256 => {
+                let (in_ptr, in_size, out_ptr, out_size) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    &host_function_costs.sha256,
+                    [in_ptr, in_size, out_ptr, out_size],
+                )?;
+                let result = self.hash_into_mem(in_ptr, in_size, out_ptr, out_size, |input| {
+                    crypto::sha256(input)
+                })?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(result))))
+            }
+            FunctionIndex::Keccak256 => {
+                let (in_ptr, in_size, out_ptr, out_size) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    &host_function_costs.keccak256,
+                    [in_ptr, in_size, out_ptr, out_size],
+                )?;
+                let result = self.hash_into_mem(in_ptr, in_size, out_ptr, out_size, |input| {
+                    crypto::keccak256(input)
+                })?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(result))))
+            }
         }
     }
 }
@@ -1,8 +1,9 @@
 use std::collections::BTreeSet;
 
 use casper_types::{
-    account::AccountHash, system::handle_payment::Error, BlockTime, CLValue, Key, Phase,
-    StoredValue, TransferredTo, URef, U512,
+    account::AccountHash,
+    system::{handle_payment::Error, CallStackElement},
+    BlockTime, CLValue, Key, Phase, StoredValue, TransferredTo, URef, U512,
 };
 
 use crate::{
@@ -123,6 +124,16 @@ where
         self.context.get_caller()
     }
 
+    fn is_payment_sponsor_caller(&self) -> bool {
+        match (self.context.payment_sponsor(), self.get_immediate_caller()) {
+            (
+                Some(payment_sponsor),
+                Some(CallStackElement::StoredContract { contract_hash, .. }),
+            ) => *contract_hash == payment_sponsor,
+            _ => false,
+        }
+    }
+
     fn refund_handling(&self) -> &RefundHandling {
         self.config.refund_handling()
     }
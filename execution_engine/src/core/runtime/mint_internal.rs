@@ -2,7 +2,7 @@ use casper_types::{
     account::AccountHash,
     bytesrepr::{FromBytes, ToBytes},
     system::{mint::Error, CallStackElement},
-    CLTyped, CLValue, Key, Phase, StoredValue, URef, U512,
+    BlockTime, CLTyped, CLValue, Key, Phase, StoredValue, URef, U512,
 };
 
 use super::Runtime;
@@ -44,6 +44,10 @@ where
         self.context.phase()
     }
 
+    fn get_blocktime(&self) -> BlockTime {
+        self.context.get_blocktime()
+    }
+
     fn put_key(&mut self, name: &str, key: Key) -> Result<(), Error> {
         self.context
             .put_key(name.to_string(), key)
@@ -149,6 +153,12 @@ where
         }
     }
 
+    fn balance_exists(&mut self, uref: URef) -> Result<bool, Error> {
+        self.context
+            .key_exists_direct(&Key::Balance(uref.addr()))
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))
+    }
+
     fn write_balance(&mut self, uref: URef, balance: U512) -> Result<(), Error> {
         let cl_value = CLValue::from_t(balance).map_err(|_| Error::CLValue)?;
         self.context
@@ -162,6 +172,38 @@ where
             .metered_add_gs_unsafe(Key::Balance(uref.addr()), StoredValue::CLValue(cl_value))
             .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))
     }
+
+    fn read_dictionary<T: CLTyped + FromBytes>(
+        &mut self,
+        seed_uref: URef,
+        dictionary_item_key: &str,
+    ) -> Result<Option<T>, Error> {
+        let maybe_value = self
+            .context
+            .dictionary_get(seed_uref, dictionary_item_key)
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))?;
+        match maybe_value {
+            Some(cl_value) => {
+                let value = CLValue::into_t(cl_value).map_err(|_| Error::CLValue)?;
+                Ok(Some(value))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn write_dictionary<T: CLTyped + ToBytes>(
+        &mut self,
+        seed_uref: URef,
+        dictionary_item_key: &str,
+        value: T,
+    ) -> Result<(), Error> {
+        let cl_value = CLValue::from_t(value).map_err(|_| Error::CLValue)?;
+        self.context
+            .dictionary_put(seed_uref, dictionary_item_key, cl_value)
+            .map_err(|exec_error| {
+                <Option<Error>>::from(exec_error).unwrap_or(Error::WriteDictionary)
+            })
+    }
 }
 
 impl<'a, R> SystemProvider for Runtime<'a, R>
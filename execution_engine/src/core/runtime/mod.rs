@@ -14,15 +14,14 @@ use std::{
     collections::{BTreeMap, BTreeSet},
     convert::{TryFrom, TryInto},
     iter::FromIterator,
+    sync::Arc,
 };
 
 use casper_wasm::elements::Module;
-use casper_wasmi::{MemoryRef, Trap, TrapCode};
+use casper_wasmi::{Trap, TrapCode};
+use num_rational::Ratio;
 use tracing::error;
 
-#[cfg(feature = "test-support")]
-use casper_wasmi::RuntimeValue;
-
 use casper_types::{
     account::{
         Account, AccountHash, ActionType, AddKeyFailure, RemoveKeyFailure, SetThresholdFailure,
@@ -36,26 +35,28 @@ use casper_types::{
     },
     system::{
         self,
-        auction::{self, EraInfo},
+        auction::{self, EraInfo, SlashingOffence},
         handle_payment, mint, standard_payment, CallStackElement, SystemContractType, AUCTION,
         HANDLE_PAYMENT, MINT, STANDARD_PAYMENT,
     },
     AccessRights, ApiError, CLTyped, CLValue, ContextAccessRights, ContractHash,
-    ContractPackageHash, ContractVersionKey, ContractWasm, DeployHash, EntryPointType, Gas,
+    ContractPackageHash, ContractVersionKey, ContractWasm, DeployHash, EntryPointType, EraId, Gas,
     GrantedAccess, Key, NamedArg, Parameter, Phase, PublicKey, RuntimeArgs, StoredValue, Transfer,
-    TransferResult, TransferredTo, URef, DICTIONARY_ITEM_KEY_MAX_LENGTH, U512,
+    TransferResult, TransferredTo, URef, URefAddr, DICTIONARY_ITEM_KEY_MAX_LENGTH, U512,
 };
 
 use crate::{
     core::{
-        engine_state::EngineConfig,
+        engine_state::{execution_trace::TraceEvent, gas_profile::GasCharge, EngineConfig},
         execution::{self, Error},
         runtime::host_function_flag::HostFunctionFlag,
         runtime_context::{self, RuntimeContext},
         tracking_copy::TrackingCopyExt,
+        wasm_engine::WasmMemory,
     },
     shared::{
         host_function_costs::{Cost, HostFunction},
+        module_cache::ModuleCache,
         wasm_prep::{self, PreprocessingError},
     },
     storage::global_state::StateReader,
@@ -79,8 +80,9 @@ enum CallContractIdentifier {
 /// Represents the runtime properties of a WASM execution.
 pub struct Runtime<'a, R> {
     config: EngineConfig,
-    memory: Option<MemoryRef>,
-    module: Option<Module>,
+    module_cache: Arc<ModuleCache>,
+    memory: Option<Box<dyn WasmMemory>>,
+    module: Option<Arc<Module>>,
     host_buffer: Option<CLValue>,
     context: RuntimeContext<'a, R>,
     stack: Option<RuntimeStack>,
@@ -93,9 +95,14 @@ where
     R::Error: Into<Error>,
 {
     /// Creates a new runtime instance.
-    pub(crate) fn new(config: EngineConfig, context: RuntimeContext<'a, R>) -> Self {
+    pub(crate) fn new(
+        config: EngineConfig,
+        module_cache: Arc<ModuleCache>,
+        context: RuntimeContext<'a, R>,
+    ) -> Self {
         Runtime {
             config,
+            module_cache,
             memory: None,
             module: None,
             host_buffer: None,
@@ -105,17 +112,19 @@ where
         }
     }
 
-    /// Creates a new runtime instance by cloning the config, and host function flag from `self`.
+    /// Creates a new runtime instance by cloning the config, module cache, and host function flag
+    /// from `self`.
     fn new_invocation_runtime(
         &self,
         context: RuntimeContext<'a, R>,
-        module: Module,
-        memory: MemoryRef,
+        module: Arc<Module>,
+        memory: Box<dyn WasmMemory>,
         stack: RuntimeStack,
     ) -> Self {
         Self::check_preconditions(&stack);
         Runtime {
             config: self.config.clone(),
+            module_cache: Arc::clone(&self.module_cache),
             memory: Some(memory),
             module: Some(module),
             host_buffer: None,
@@ -134,6 +143,7 @@ where
         Self::check_preconditions(&stack);
         Runtime {
             config: self.config.clone(),
+            module_cache: Arc::clone(&self.module_cache),
             memory: None,
             module: None,
             host_buffer: None,
@@ -162,8 +172,8 @@ where
         &self.context
     }
 
-    fn gas(&mut self, amount: Gas) -> Result<(), Error> {
-        self.context.charge_gas(amount)
+    fn gas(&mut self, charge: GasCharge, amount: Gas) -> Result<(), Error> {
+        self.context.charge_gas(charge, amount)
     }
 
     /// Returns current gas counter.
@@ -202,33 +212,39 @@ where
         size: usize,
         func: impl FnOnce(&[u8]) -> Ret,
     ) -> Result<Ret, Error> {
-        // This is mostly copied from a private function `MemoryInstance::checked_memory_region`
-        // that calls a user defined function with a validated slice of memory. This allows
-        // usage patterns that does not involve copying data onto heap first i.e. deserialize
-        // values without copying data first, etc.
-        // NOTE: Depending on the VM backend used in future, this may change, as not all VMs may
-        // support direct memory access.
+        // Calls a user defined function with a validated slice of memory. This allows usage
+        // patterns that does not involve copying data onto heap first i.e. deserialize values
+        // without copying data first, etc.
+        let mut func = Some(func);
+        let mut ret = None;
         self.try_get_memory()?
-            .with_direct_access(|buffer| {
-                let end = offset.checked_add(size).ok_or_else(|| {
-                    casper_wasmi::Error::Memory(format!(
-                        "trying to access memory block of size {} from offset {}",
-                        size, offset
-                    ))
-                })?;
-
-                if end > buffer.len() {
-                    return Err(casper_wasmi::Error::Memory(format!(
-                        "trying to access region [{}..{}] in memory [0..{}]",
-                        offset,
-                        end,
-                        buffer.len(),
-                    )));
-                }
-
-                Ok(func(&buffer[offset..end]))
+            .with_slice(offset, size, &mut |buffer| {
+                ret = func.take().map(|func| func(buffer));
             })
-            .map_err(Into::into)
+            .map_err(Error::Interpreter)?;
+        Ok(ret.expect("should have called func"))
+    }
+
+    /// Hashes the `in_size` bytes at `in_ptr` in Wasm memory and writes the digest to `out_ptr`.
+    ///
+    /// Returns `ApiError::BufferTooSmall` without writing anything if `out_size` is not the length
+    /// of the digest.
+    fn hash_into_mem<const N: usize>(
+        &self,
+        in_ptr: u32,
+        in_size: u32,
+        out_ptr: u32,
+        out_size: u32,
+        hash: impl FnOnce(&[u8]) -> [u8; N],
+    ) -> Result<Result<(), ApiError>, Error> {
+        let digest = self.checked_memory_slice(in_ptr as usize, in_size as usize, hash)?;
+        if digest.len() != out_size as usize {
+            return Ok(Err(ApiError::BufferTooSmall));
+        }
+        self.try_get_memory()?
+            .set(out_ptr, &digest)
+            .map_err(Error::Interpreter)?;
+        Ok(Ok(()))
     }
 
     /// Returns bytes from the WASM memory instance.
@@ -296,7 +312,13 @@ where
         let name = self.string_from_mem(name_ptr, name_size)?;
 
         // Get a key and serialize it
-        let key = match self.context.named_keys_get(&name) {
+        let maybe_key = self.context.named_keys_get(&name);
+        self.context
+            .on_execution_event(|| TraceEvent::NamedKeyRead {
+                name: name.clone(),
+                key: maybe_key.copied(),
+            });
+        let key = match maybe_key {
             Some(key) => key,
             None => return Ok(Err(ApiError::MissingKey)),
         };
@@ -313,7 +335,7 @@ where
 
         // Set serialized Key bytes into the output buffer
         if let Err(error) = self.try_get_memory()?.set(output_ptr, &key_bytes) {
-            return Err(Error::Interpreter(error.into()).into());
+            return Err(Error::Interpreter(error).into());
         }
 
         // SAFETY: For all practical purposes following conversion is assumed to be safe
@@ -323,7 +345,7 @@ where
             .expect("Keys should not serialize to many bytes");
         let size_bytes = bytes_size.to_le_bytes(); // Wasm is little-endian
         if let Err(error) = self.try_get_memory()?.set(bytes_written_ptr, &size_bytes) {
-            return Err(Error::Interpreter(error.into()).into());
+            return Err(Error::Interpreter(error).into());
         }
 
         Ok(Ok(()))
@@ -331,7 +353,13 @@ where
 
     fn has_key(&mut self, name_ptr: u32, name_size: u32) -> Result<i32, Trap> {
         let name = self.string_from_mem(name_ptr, name_size)?;
-        if self.context.named_keys_contains_key(&name) {
+        let maybe_key = self.context.named_keys_get(&name).copied();
+        self.context
+            .on_execution_event(|| TraceEvent::NamedKeyRead {
+                name,
+                key: maybe_key,
+            });
+        if maybe_key.is_some() {
             Ok(0)
         } else {
             Ok(1)
@@ -347,12 +375,17 @@ where
     ) -> Result<(), Trap> {
         let name = self.string_from_mem(name_ptr, name_size)?;
         let key = self.key_from_mem(key_ptr, key_size)?;
-        self.context.put_key(name, key).map_err(Into::into)
+        self.context.put_key(name.clone(), key)?;
+        self.context
+            .on_execution_event(|| TraceEvent::NamedKeyWrite { name, key });
+        Ok(())
     }
 
     fn remove_key(&mut self, name_ptr: u32, name_size: u32) -> Result<(), Trap> {
         let name = self.string_from_mem(name_ptr, name_size)?;
         self.context.remove_key(&name)?;
+        self.context
+            .on_execution_event(|| TraceEvent::NamedKeyRemove { name });
         Ok(())
     }
 
@@ -362,7 +395,7 @@ where
         let purse_bytes = purse.into_bytes().map_err(Error::BytesRepr)?;
         self.try_get_memory()?
             .set(dest_ptr, &purse_bytes)
-            .map_err(|e| Error::Interpreter(e.into()).into())
+            .map_err(|e| Error::Interpreter(e).into())
     }
 
     /// Writes caller (deploy) account public key to dest_ptr in the Wasm
@@ -383,7 +416,7 @@ where
         // Write output
         let output_size_bytes = value_size.to_le_bytes(); // Wasm is little-endian
         if let Err(error) = self.try_get_memory()?.set(output_size, &output_size_bytes) {
-            return Err(Error::Interpreter(error.into()).into());
+            return Err(Error::Interpreter(error).into());
         }
         Ok(Ok(()))
     }
@@ -412,7 +445,7 @@ where
         let bytes = phase.into_bytes().map_err(Error::BytesRepr)?;
         self.try_get_memory()?
             .set(dest_ptr, &bytes)
-            .map_err(|e| Error::Interpreter(e.into()).into())
+            .map_err(|e| Error::Interpreter(e).into())
     }
 
     /// Writes current blocktime to dest_ptr in Wasm memory.
@@ -424,7 +457,7 @@ where
             .map_err(Error::BytesRepr)?;
         self.try_get_memory()?
             .set(dest_ptr, &blocktime)
-            .map_err(|e| Error::Interpreter(e.into()).into())
+            .map_err(|e| Error::Interpreter(e).into())
     }
 
     /// Load the uref known by the given name into the Wasm memory
@@ -453,7 +486,7 @@ where
             .try_get_memory()?
             .set(call_stack_len_ptr, &call_stack_len_bytes)
         {
-            return Err(Error::Interpreter(error.into()).into());
+            return Err(Error::Interpreter(error).into());
         }
 
         if call_stack_len == 0 {
@@ -478,7 +511,7 @@ where
             .try_get_memory()?
             .set(result_size_ptr, &call_stack_cl_value_bytes_len_bytes)
         {
-            return Err(Error::Interpreter(error.into()).into());
+            return Err(Error::Interpreter(error).into());
         }
 
         Ok(Ok(()))
@@ -576,6 +609,17 @@ where
             .map_err(|_| Error::Revert(ApiError::InvalidArgument))
     }
 
+    /// Returns the value of an argument of type `Option<T>`, treating a missing argument as `None`.
+    fn get_optional_named_argument<T: FromBytes + CLTyped>(
+        args: &RuntimeArgs,
+        name: &str,
+    ) -> Result<Option<T>, Error> {
+        if args.get(name).is_none() {
+            return Ok(None);
+        }
+        Self::get_named_argument(args, name)
+    }
+
     fn reverter<T: Into<ApiError>>(error: T) -> Error {
         let api_error: ApiError = error.into();
         // NOTE: This is special casing needed to keep the native system contracts propagate
@@ -700,6 +744,76 @@ where
                     mint_runtime.mint_into_existing_purse(existing_purse, amount);
                 CLValue::from_t(result).map_err(Self::reverter)
             })(),
+            // Type: `fn approve_allowance(purse: URef, spender: Key, amount: U512, expires_at:
+            // Option<u64>) -> Result<(), Error>`
+            mint::METHOD_APPROVE_ALLOWANCE => (|| {
+                mint_runtime.charge_system_contract_call(mint_costs.approve_allowance)?;
+
+                let purse: URef = Self::get_named_argument(runtime_args, mint::ARG_PURSE)?;
+                let spender: Key = Self::get_named_argument(runtime_args, mint::ARG_SPENDER)?;
+                let amount: U512 = Self::get_named_argument(runtime_args, mint::ARG_AMOUNT)?;
+                let expires_at: Option<u64> =
+                    Self::get_named_argument(runtime_args, mint::ARG_EXPIRES_AT)?;
+
+                let result: Result<(), mint::Error> =
+                    mint_runtime.approve_allowance(purse, spender, amount, expires_at);
+                CLValue::from_t(result).map_err(Self::reverter)
+            })(),
+            // Type: `fn transfer_from_allowance(source: URefAddr, target: URef, amount: U512, id:
+            // Option<u64>) -> Result<(), Error>`
+            mint::METHOD_TRANSFER_FROM_ALLOWANCE => (|| {
+                mint_runtime.charge_system_contract_call(mint_costs.transfer_from_allowance)?;
+
+                let source: URefAddr = Self::get_named_argument(runtime_args, mint::ARG_SOURCE)?;
+                let target: URef = Self::get_named_argument(runtime_args, mint::ARG_TARGET)?;
+                let amount: U512 = Self::get_named_argument(runtime_args, mint::ARG_AMOUNT)?;
+                let id: Option<u64> = Self::get_named_argument(runtime_args, mint::ARG_ID)?;
+
+                let result: Result<(), mint::Error> =
+                    mint_runtime.transfer_from_allowance(source, target, amount, id);
+                CLValue::from_t(result).map_err(Self::reverter)
+            })(),
+            // Type: `fn schedule_transfer(source: URef, target: AccountHash, amount: U512, schedule:
+            // TransferSchedule, runs: u32, id: Option<u64>) -> Result<u64, Error>`
+            mint::METHOD_SCHEDULE_TRANSFER => (|| {
+                mint_runtime.charge_system_contract_call(mint_costs.schedule_transfer)?;
+
+                let source: URef = Self::get_named_argument(runtime_args, mint::ARG_SOURCE)?;
+                let target: AccountHash = Self::get_named_argument(runtime_args, mint::ARG_TARGET)?;
+                let amount: U512 = Self::get_named_argument(runtime_args, mint::ARG_AMOUNT)?;
+                let schedule: mint::TransferSchedule =
+                    Self::get_named_argument(runtime_args, mint::ARG_SCHEDULE)?;
+                let runs: u32 = Self::get_named_argument(runtime_args, mint::ARG_RUNS)?;
+                let id: Option<u64> = Self::get_named_argument(runtime_args, mint::ARG_ID)?;
+
+                let result: Result<u64, mint::Error> =
+                    mint_runtime.schedule_transfer(source, target, amount, schedule, runs, id);
+                CLValue::from_t(result).map_err(Self::reverter)
+            })(),
+            // Type: `fn cancel_scheduled_transfer(schedule_id: u64) -> Result<(), Error>`
+            mint::METHOD_CANCEL_SCHEDULED_TRANSFER => (|| {
+                mint_runtime.charge_system_contract_call(mint_costs.cancel_scheduled_transfer)?;
+
+                let schedule_id: u64 =
+                    Self::get_named_argument(runtime_args, mint::ARG_SCHEDULE_ID)?;
+
+                let result: Result<(), mint::Error> =
+                    mint_runtime.cancel_scheduled_transfer(schedule_id);
+                CLValue::from_t(result).map_err(Self::reverter)
+            })(),
+            // Type: `fn run_scheduled_transfers(era_id: EraId, era_end_timestamp_millis: u64) ->
+            // Result<Vec<ScheduledTransferResult>, Error>`
+            mint::METHOD_RUN_SCHEDULED_TRANSFERS => (|| {
+                mint_runtime.charge_system_contract_call(mint_costs.run_scheduled_transfers)?;
+
+                let era_id: EraId = Self::get_named_argument(runtime_args, mint::ARG_ERA_ID)?;
+                let era_end_timestamp_millis: u64 =
+                    Self::get_named_argument(runtime_args, mint::ARG_ERA_END_TIMESTAMP_MILLIS)?;
+
+                let result: Result<Vec<mint::ScheduledTransferResult>, mint::Error> =
+                    mint_runtime.run_scheduled_transfers(era_id, era_end_timestamp_millis);
+                CLValue::from_t(result).map_err(Self::reverter)
+            })(),
 
             _ => CLValue::from_t(()).map_err(Self::reverter),
         };
@@ -707,10 +821,13 @@ where
         // Charge just for the amount that particular entry point cost - using gas cost from the
         // isolated runtime might have a recursive costs whenever system contract calls other system
         // contract.
-        self.gas(match mint_runtime.gas_counter().checked_sub(gas_counter) {
-            None => gas_counter,
-            Some(new_gas) => new_gas,
-        })?;
+        self.gas(
+            GasCharge::AlreadyProfiled,
+            match mint_runtime.gas_counter().checked_sub(gas_counter) {
+                None => gas_counter,
+                Some(new_gas) => new_gas,
+            },
+        )?;
 
         // Result still contains a result, but the entrypoints logic does not exit early on errors.
         let ret = result?;
@@ -796,6 +913,18 @@ where
                     .map_err(Self::reverter)?;
                 CLValue::from_t(()).map_err(Self::reverter)
             })(),
+            handle_payment::METHOD_SPONSOR_PAYMENT => (|| {
+                runtime.charge_system_contract_call(handle_payment_costs.sponsor_payment)?;
+
+                let purse: URef =
+                    Self::get_named_argument(runtime_args, handle_payment::ARG_PURSE)?;
+                let amount: U512 =
+                    Self::get_named_argument(runtime_args, handle_payment::ARG_AMOUNT)?;
+                runtime
+                    .sponsor_payment(purse, amount)
+                    .map_err(Self::reverter)?;
+                CLValue::from_t(()).map_err(Self::reverter)
+            })(),
             handle_payment::METHOD_DISTRIBUTE_ACCUMULATED_FEES => (|| {
                 runtime.charge_system_contract_call(handle_payment_costs.finalize_payment)?;
                 runtime
@@ -807,10 +936,13 @@ where
             _ => CLValue::from_t(()).map_err(Self::reverter),
         };
 
-        self.gas(match runtime.gas_counter().checked_sub(gas_counter) {
-            None => gas_counter,
-            Some(new_gas) => new_gas,
-        })?;
+        self.gas(
+            GasCharge::AlreadyProfiled,
+            match runtime.gas_counter().checked_sub(gas_counter) {
+                None => gas_counter,
+                Some(new_gas) => new_gas,
+            },
+        )?;
 
         let ret = result?;
         let urefs = utils::extract_urefs(&ret)?;
@@ -830,7 +962,13 @@ where
         let gas_counter = self.gas_counter();
         let amount: U512 =
             Self::get_named_argument(self.context.args(), standard_payment::ARG_AMOUNT)?;
-        let result = self.pay(amount).map_err(Self::reverter);
+        let result = {
+            // The gas charged by standard payment is discarded, so it isn't profiled either.
+            let _gas_profiler_frame = self
+                .context
+                .enter_gas_profiler_frame(|profiler| profiler.suspend());
+            self.pay(amount).map_err(Self::reverter)
+        };
         self.set_gas_counter(gas_counter);
         result
     }
@@ -883,9 +1021,23 @@ where
                 let delegation_rate =
                     Self::get_named_argument(runtime_args, auction::ARG_DELEGATION_RATE)?;
                 let amount = Self::get_named_argument(runtime_args, auction::ARG_AMOUNT)?;
+                let minimum_delegation_amount = Self::get_optional_named_argument(
+                    runtime_args,
+                    auction::ARG_MINIMUM_DELEGATION_AMOUNT,
+                )?;
+                let maximum_delegation_amount = Self::get_optional_named_argument(
+                    runtime_args,
+                    auction::ARG_MAXIMUM_DELEGATION_AMOUNT,
+                )?;
 
                 let result = runtime
-                    .add_bid(account_hash, delegation_rate, amount)
+                    .add_bid(
+                        account_hash,
+                        delegation_rate,
+                        amount,
+                        minimum_delegation_amount,
+                        maximum_delegation_amount,
+                    )
                     .map_err(Self::reverter)?;
 
                 CLValue::from_t(result).map_err(Self::reverter)
@@ -964,6 +1116,32 @@ where
                 CLValue::from_t(result).map_err(Self::reverter)
             })(),
 
+            auction::METHOD_TRANSFER_DELEGATION => (|| {
+                runtime.charge_system_contract_call(auction_costs.transfer_delegation)?;
+
+                let delegator = Self::get_named_argument(runtime_args, auction::ARG_DELEGATOR)?;
+                let validator = Self::get_named_argument(runtime_args, auction::ARG_VALIDATOR)?;
+                let amount = Self::get_named_argument(runtime_args, auction::ARG_AMOUNT)?;
+                let new_delegator =
+                    Self::get_named_argument(runtime_args, auction::ARG_NEW_DELEGATOR)?;
+
+                let max_delegators_per_validator = self.config.max_delegators_per_validator();
+                let minimum_delegation_amount = self.config.minimum_delegation_amount();
+
+                let result = runtime
+                    .transfer_delegation(
+                        delegator,
+                        validator,
+                        amount,
+                        new_delegator,
+                        max_delegators_per_validator,
+                        minimum_delegation_amount,
+                    )
+                    .map_err(Self::reverter)?;
+
+                CLValue::from_t(result).map_err(Self::reverter)
+            })(),
+
             auction::METHOD_RUN_AUCTION => (|| {
                 runtime.charge_system_contract_call(auction_costs.run_auction)?;
 
@@ -979,21 +1157,41 @@ where
                         era_end_timestamp_millis,
                         evicted_validators,
                         max_delegators_per_validator,
+                        self.config.inactivity_jail_threshold(),
+                        self.config.jail_period_eras(),
+                        self.config.max_jail_period_eras(),
                     )
                     .map_err(Self::reverter)?;
 
                 CLValue::from_t(()).map_err(Self::reverter)
             })(),
 
-            // Type: `fn slash(validator_account_hashes: &[AccountHash]) -> Result<(), Error>`
+            // Type: `fn slash(slashes: BTreeMap<PublicKey, (SlashingOffence, Ratio<u64>)>) ->
+            // Result<(), Error>`
             auction::METHOD_SLASH => (|| {
                 runtime.charge_system_contract_call(auction_costs.slash)?;
 
-                let validator_public_keys =
-                    Self::get_named_argument(runtime_args, auction::ARG_VALIDATOR_PUBLIC_KEYS)?;
-                runtime
-                    .slash(validator_public_keys)
-                    .map_err(Self::reverter)?;
+                let slashes =
+                    match Self::get_optional_named_argument(runtime_args, auction::ARG_SLASHES)? {
+                        Some(slashes) => slashes,
+                        // Callers passing only the validators' public keys slash their entire stake.
+                        None => {
+                            let validator_public_keys: Vec<PublicKey> = Self::get_named_argument(
+                                runtime_args,
+                                auction::ARG_VALIDATOR_PUBLIC_KEYS,
+                            )?;
+                            validator_public_keys
+                                .into_iter()
+                                .map(|validator_public_key| {
+                                    (
+                                        validator_public_key,
+                                        (SlashingOffence::Equivocation, Ratio::from_integer(1)),
+                                    )
+                                })
+                                .collect()
+                        }
+                    };
+                runtime.slash(slashes).map_err(Self::reverter)?;
                 CLValue::from_t(()).map_err(Self::reverter)
             })(),
 
@@ -1028,14 +1226,81 @@ where
                 CLValue::from_t(()).map_err(Self::reverter)
             })(),
 
+            auction::METHOD_UNJAIL => (|| {
+                runtime.charge_system_contract_call(auction_costs.unjail)?;
+
+                let validator_public_key: PublicKey =
+                    Self::get_named_argument(runtime_args, auction::ARG_VALIDATOR_PUBLIC_KEY)?;
+
+                runtime
+                    .unjail(validator_public_key)
+                    .map_err(Self::reverter)?;
+
+                CLValue::from_t(()).map_err(Self::reverter)
+            })(),
+
+            auction::METHOD_ADD_RESERVATIONS => (|| {
+                runtime.charge_system_contract_call(auction_costs.add_reservations)?;
+
+                let validator = Self::get_named_argument(runtime_args, auction::ARG_VALIDATOR)?;
+                let delegators = Self::get_named_argument(runtime_args, auction::ARG_DELEGATORS)?;
+
+                let max_delegators_per_validator = self.config.max_delegators_per_validator();
+                let max_reserved_delegators_per_validator =
+                    self.config.max_reserved_delegators_per_validator();
+
+                runtime
+                    .add_reservations(
+                        validator,
+                        delegators,
+                        max_delegators_per_validator,
+                        max_reserved_delegators_per_validator,
+                    )
+                    .map_err(Self::reverter)?;
+
+                CLValue::from_t(()).map_err(Self::reverter)
+            })(),
+
+            auction::METHOD_CANCEL_RESERVATIONS => (|| {
+                runtime.charge_system_contract_call(auction_costs.cancel_reservations)?;
+
+                let validator = Self::get_named_argument(runtime_args, auction::ARG_VALIDATOR)?;
+                let delegators = Self::get_named_argument(runtime_args, auction::ARG_DELEGATORS)?;
+
+                runtime
+                    .cancel_reservations(validator, delegators)
+                    .map_err(Self::reverter)?;
+
+                CLValue::from_t(()).map_err(Self::reverter)
+            })(),
+
+            auction::METHOD_SET_REWARD_DESTINATION => (|| {
+                runtime.charge_system_contract_call(auction_costs.set_reward_destination)?;
+
+                let validator = Self::get_named_argument(runtime_args, auction::ARG_VALIDATOR)?;
+                let delegator =
+                    Self::get_optional_named_argument(runtime_args, auction::ARG_DELEGATOR)?;
+                let reward_destination =
+                    Self::get_named_argument(runtime_args, auction::ARG_REWARD_DESTINATION)?;
+
+                runtime
+                    .set_reward_destination(validator, delegator, reward_destination)
+                    .map_err(Self::reverter)?;
+
+                CLValue::from_t(()).map_err(Self::reverter)
+            })(),
+
             _ => CLValue::from_t(()).map_err(Self::reverter),
         };
 
         // Charge for the gas spent during execution in an isolated runtime.
-        self.gas(match runtime.gas_counter().checked_sub(gas_counter) {
-            None => gas_counter,
-            Some(new_gas) => new_gas,
-        })?;
+        self.gas(
+            GasCharge::AlreadyProfiled,
+            match runtime.gas_counter().checked_sub(gas_counter) {
+                None => gas_counter,
+                Some(new_gas) => new_gas,
+            },
+        )?;
 
         // Result still contains a result, but the entrypoints logic does not exit early on errors.
         let ret = result?;
@@ -1070,8 +1335,14 @@ where
         let protocol_version = self.context.protocol_version();
         let engine_config = self.config.clone();
         let module = wasm_prep::preprocess(*engine_config.wasm_config(), module_bytes)?;
-        let (instance, memory) =
-            utils::instance_and_memory(module.clone(), protocol_version, &engine_config)?;
+        let (instance, memory) = engine_config
+            .wasm_engine()
+            .engine()
+            .compile(module.clone())?
+            .instantiate(protocol_version, &engine_config)?;
+        let module = Arc::new(module);
+        self.context
+            .with_gas_profiler(|profiler| profiler.set_module(&module));
         self.memory = Some(memory);
         self.module = Some(module);
         self.stack = Some(stack);
@@ -1081,36 +1352,20 @@ where
             AccessRights::WRITE,
         )?);
 
-        let result = instance.invoke_export(DEFAULT_ENTRY_POINT_NAME, &[], self);
-
-        let error = match result {
-            Err(error) => error,
+        match instance.invoke_export(DEFAULT_ENTRY_POINT_NAME, self) {
             // If `Ok` and the `host_buffer` is `None`, the contract's execution succeeded but did
             // not explicitly call `runtime::ret()`.  Treat as though the execution
             // returned the unit type `()` as per Rust functions which don't specify a
             // return value.
-            Ok(_) => {
-                return Ok(self.take_host_buffer().unwrap_or(CLValue::from_t(())?));
-            }
-        };
-
-        #[cfg(feature = "test-support")]
-        dump_runtime_stack_info(instance, engine_config.wasm_config().max_stack_height);
-
-        if let Some(host_error) = error.as_host_error() {
+            Ok(()) => Ok(self.take_host_buffer().unwrap_or(CLValue::from_t(())?)),
             // If the "error" was in fact a trap caused by calling `ret` then
             // this is normal operation and we should return the value captured
             // in the Runtime result field.
-            let downcasted_error = host_error.downcast_ref::<Error>();
-            match downcasted_error {
-                Some(Error::Ret(ref _ret_urefs)) => {
-                    return self.take_host_buffer().ok_or(Error::ExpectedReturnValue);
-                }
-                Some(error) => return Err(error.clone()),
-                None => return Err(Error::Interpreter(host_error.to_string())),
+            Err(Error::Ret(_ret_urefs)) => {
+                self.take_host_buffer().ok_or(Error::ExpectedReturnValue)
             }
+            Err(error) => Err(error),
         }
-        Err(Error::Interpreter(error.into()))
     }
 
     /// Calls contract living under a `key`, with supplied `args`.
@@ -1164,15 +1419,15 @@ where
         }
     }
 
-    fn try_get_memory(&self) -> Result<&MemoryRef, Error> {
-        self.memory.as_ref().ok_or(Error::WasmPreprocessing(
+    fn try_get_memory(&self) -> Result<&dyn WasmMemory, Error> {
+        self.memory.as_deref().ok_or(Error::WasmPreprocessing(
             PreprocessingError::MissingMemorySection,
         ))
     }
 
     fn try_get_module(&self) -> Result<&Module, Error> {
         self.module
-            .as_ref()
+            .as_deref()
             .ok_or(Error::WasmPreprocessing(PreprocessingError::MissingModule))
     }
 
@@ -1370,6 +1625,26 @@ where
 
         access_rights.extend(&extended_access_rights);
 
+        let _gas_profiler_frame = self.context.enter_gas_profiler_frame(|profiler| {
+            let contract_name = if self.is_mint(context_key) {
+                MINT.to_string()
+            } else if self.is_handle_payment(context_key) {
+                HANDLE_PAYMENT.to_string()
+            } else if self.is_auction(context_key) {
+                AUCTION.to_string()
+            } else {
+                contract_hash.to_formatted_string()
+            };
+            profiler.enter_contract(&contract_name, entry_point.name())
+        });
+        let _execution_trace_frame = stack.current_frame().map(|frame| {
+            self.context
+                .enter_execution_trace_frame(|| TraceEvent::EnterFrame {
+                    frame: frame.clone(),
+                    entry_point: entry_point.name().to_string(),
+                })
+        });
+
         if self.is_mint(context_key) {
             return self.call_host_mint(entry_point.name(), &context_args, access_rights, stack);
         } else if self.is_handle_payment(context_key) {
@@ -1383,17 +1658,26 @@ where
             return self.call_host_auction(entry_point.name(), &context_args, access_rights, stack);
         }
 
-        let module: Module = {
+        let prepared_module = {
             let wasm_key = contract.contract_wasm_key();
 
+            // The contract Wasm is always read from global state, even if its module is cached, so
+            // that the read is recorded identically regardless of the state of the cache.
             let contract_wasm: ContractWasm = match self.context.read_gs(&wasm_key)? {
                 Some(StoredValue::ContractWasm(contract_wasm)) => contract_wasm,
                 Some(_) => return Err(Error::InvalidContractWasm(contract.contract_wasm_hash())),
                 None => return Err(Error::KeyNotFound(context_key)),
             };
 
-            casper_wasm::deserialize_buffer(contract_wasm.bytes())?
+            self.module_cache.get_or_prepare(
+                contract.contract_wasm_hash(),
+                *self.config.wasm_config(),
+                self.config.wasm_engine(),
+                contract_wasm.bytes(),
+            )?
         };
+        self.context
+            .with_gas_profiler(|profiler| profiler.set_module(prepared_module.module()));
 
         let context = self.context.new_from_self(
             context_key,
@@ -1403,11 +1687,13 @@ where
             context_args,
         );
         let protocol_version = self.context.protocol_version();
-        let (instance, memory) =
-            utils::instance_and_memory(module.clone(), protocol_version, &self.config)?;
+        let (instance, memory) = prepared_module
+            .compiled()
+            .instantiate(protocol_version, &self.config)?;
+        let module = Arc::clone(prepared_module.module());
         let runtime = &mut Runtime::new_invocation_runtime(self, context, module, memory, stack);
 
-        let result = instance.invoke_export(entry_point.name(), &[], runtime);
+        let result = instance.invoke_export(entry_point.name(), runtime);
 
         // The `runtime`'s context was initialized with our counter from before the call and any gas
         // charged by the sub-call was added to its counter - so let's copy the correct value of the
@@ -1418,13 +1704,16 @@ where
             let transfers = self.context.transfers_mut();
             *transfers = runtime.context.transfers().to_owned();
         }
+        {
+            let events = self.context.events_mut();
+            *events = runtime.context.events().to_owned();
+        }
 
-        let error = match result {
-            Err(error) => error,
+        match result {
             // If `Ok` and the `host_buffer` is `None`, the contract's execution succeeded but did
             // not explicitly call `runtime::ret()`.  Treat as though the execution returned the
             // unit type `()` as per Rust functions which don't specify a return value.
-            Ok(_) => {
+            Ok(()) => {
                 if self.context.entry_point_type() == EntryPointType::Session
                     && runtime.context.entry_point_type() == EntryPointType::Session
                 {
@@ -1434,42 +1723,30 @@ where
                 }
                 self.context
                     .set_remaining_spending_limit(runtime.context.remaining_spending_limit());
-                return Ok(runtime.take_host_buffer().unwrap_or(CLValue::from_t(())?));
+                Ok(runtime.take_host_buffer().unwrap_or(CLValue::from_t(())?))
             }
-        };
-
-        #[cfg(feature = "test-support")]
-        dump_runtime_stack_info(instance, self.config.wasm_config().max_stack_height);
-
-        if let Some(host_error) = error.as_host_error() {
             // If the "error" was in fact a trap caused by calling `ret` then this is normal
             // operation and we should return the value captured in the Runtime result field.
-            let downcasted_error = host_error.downcast_ref::<Error>();
-            match downcasted_error {
-                Some(Error::Ret(ref ret_urefs)) => {
-                    // Insert extra urefs returned from call.
-                    // Those returned URef's are guaranteed to be valid as they were already
-                    // validated in the `ret` call inside context we ret from.
-                    self.context.access_rights_extend(ret_urefs);
-
-                    if self.context.entry_point_type() == EntryPointType::Session
-                        && runtime.context.entry_point_type() == EntryPointType::Session
-                    {
-                        // Overwrites parent's named keys with child's new named keys but only when
-                        // running session code.
-                        *self.context.named_keys_mut() = runtime.context.named_keys().clone();
-                    }
+            Err(Error::Ret(ret_urefs)) => {
+                // Insert extra urefs returned from call.
+                // Those returned URef's are guaranteed to be valid as they were already
+                // validated in the `ret` call inside context we ret from.
+                self.context.access_rights_extend(&ret_urefs);
 
-                    // Stored contracts are expected to always call a `ret` function, otherwise it's
-                    // an error.
-                    return runtime.take_host_buffer().ok_or(Error::ExpectedReturnValue);
+                if self.context.entry_point_type() == EntryPointType::Session
+                    && runtime.context.entry_point_type() == EntryPointType::Session
+                {
+                    // Overwrites parent's named keys with child's new named keys but only when
+                    // running session code.
+                    *self.context.named_keys_mut() = runtime.context.named_keys().clone();
                 }
-                Some(error) => return Err(error.clone()),
-                None => return Err(Error::Interpreter(host_error.to_string())),
+
+                // Stored contracts are expected to always call a `ret` function, otherwise it's
+                // an error.
+                runtime.take_host_buffer().ok_or(Error::ExpectedReturnValue)
             }
+            Err(error) => Err(error),
         }
-
-        Err(Error::Interpreter(error.into()))
     }
 
     fn call_contract_host_buffer(
@@ -1540,7 +1817,7 @@ where
             .try_get_memory()?
             .set(result_size_ptr, &result_size_bytes)
         {
-            return Err(Error::Interpreter(error.into()));
+            return Err(Error::Interpreter(error));
         }
 
         Ok(Ok(()))
@@ -1566,7 +1843,7 @@ where
             .try_get_memory()?
             .set(total_keys_ptr, &total_keys_bytes)
         {
-            return Err(Error::Interpreter(error.into()).into());
+            return Err(Error::Interpreter(error).into());
         }
 
         if total_keys == 0 {
@@ -1588,7 +1865,7 @@ where
 
         let length_bytes = length.to_le_bytes();
         if let Err(error) = self.try_get_memory()?.set(result_size_ptr, &length_bytes) {
-            return Err(Error::Interpreter(error.into()).into());
+            return Err(Error::Interpreter(error).into());
         }
 
         Ok(Ok(()))
@@ -1684,7 +1961,7 @@ where
             .try_get_memory()?
             .set(output_size_ptr, &output_size_bytes)
         {
-            return Err(Error::Interpreter(error.into()));
+            return Err(Error::Interpreter(error));
         }
 
         // Write updated package to the global state
@@ -1771,7 +2048,7 @@ where
 
             // Set serialized Key bytes into the output buffer
             if let Err(error) = self.try_get_memory()?.set(output_ptr, &key_bytes) {
-                return Err(Error::Interpreter(error.into()));
+                return Err(Error::Interpreter(error));
             }
 
             // SAFETY: For all practical purposes following conversion is assumed to be safe
@@ -1781,13 +2058,13 @@ where
                 .expect("Serialized value should fit within the limit");
             let size_bytes = bytes_size.to_le_bytes(); // Wasm is little-endian
             if let Err(error) = self.try_get_memory()?.set(bytes_written_ptr, &size_bytes) {
-                return Err(Error::Interpreter(error.into()));
+                return Err(Error::Interpreter(error));
             }
 
             let version_value: u32 = insert_contract_result.contract_version();
             let version_bytes = version_value.to_le_bytes();
             if let Err(error) = self.try_get_memory()?.set(version_ptr, &version_bytes) {
-                return Err(Error::Interpreter(error.into()));
+                return Err(Error::Interpreter(error));
             }
         }
 
@@ -1851,7 +2128,7 @@ where
     fn function_address(&mut self, hash_bytes: [u8; 32], dest_ptr: u32) -> Result<(), Trap> {
         self.try_get_memory()?
             .set(dest_ptr, &hash_bytes)
-            .map_err(|e| Error::Interpreter(e.into()).into())
+            .map_err(|e| Error::Interpreter(e).into())
     }
 
     /// Generates new unforgable reference and adds it to the context's
@@ -1861,7 +2138,7 @@ where
         let uref = self.context.new_uref(StoredValue::CLValue(cl_value))?;
         self.try_get_memory()?
             .set(uref_ptr, &uref.into_bytes().map_err(Error::BytesRepr)?)
-            .map_err(|e| Error::Interpreter(e.into()).into())
+            .map_err(|e| Error::Interpreter(e).into())
     }
 
     /// Writes `value` under `key` in GlobalState.
@@ -1975,7 +2252,7 @@ where
 
         let value_bytes = value_size.to_le_bytes(); // Wasm is little-endian
         if let Err(error) = self.try_get_memory()?.set(output_size_ptr, &value_bytes) {
-            return Err(Error::Interpreter(error.into()).into());
+            return Err(Error::Interpreter(error).into());
         }
 
         Ok(Ok(()))
@@ -2431,6 +2708,37 @@ where
         }
     }
 
+    /// Grants `spender` an allowance to transfer up to `amount` out of `purse` until `expires_at`.
+    fn mint_approve_allowance(
+        &mut self,
+        purse: URef,
+        spender: Key,
+        amount: U512,
+        expires_at: Option<u64>,
+    ) -> Result<Result<(), mint::Error>, Error> {
+        self.context.validate_uref(&purse)?;
+        let mint_contract_hash = self.get_mint_contract()?;
+
+        let args_values = {
+            let mut runtime_args = RuntimeArgs::new();
+            runtime_args.insert(mint::ARG_PURSE, purse)?;
+            runtime_args.insert(mint::ARG_SPENDER, spender)?;
+            runtime_args.insert(mint::ARG_AMOUNT, amount)?;
+            runtime_args.insert(mint::ARG_EXPIRES_AT, expires_at)?;
+            runtime_args
+        };
+
+        let gas_counter = self.gas_counter();
+        let call_result = self.call_contract(
+            mint_contract_hash,
+            mint::METHOD_APPROVE_ALLOWANCE,
+            args_values,
+        );
+        self.set_gas_counter(gas_counter);
+
+        Ok(call_result?.into_t()?)
+    }
+
     fn get_balance(&mut self, purse: URef) -> Result<Option<U512>, Error> {
         let maybe_value = self.context.read_gs_direct(&Key::Balance(purse.addr()))?;
         match maybe_value {
@@ -2482,7 +2790,7 @@ where
             .try_get_memory()?
             .set(output_size_ptr, &balance_size_bytes)
         {
-            return Err(Error::Interpreter(error.into()));
+            return Err(Error::Interpreter(error));
         }
 
         Ok(Ok(()))
@@ -2505,7 +2813,7 @@ where
 
         match self.try_get_memory()?.set(dest_ptr, contract_hash.as_ref()) {
             Ok(_) => Ok(Ok(())),
-            Err(error) => Err(Error::Interpreter(error.into()).into()),
+            Err(error) => Err(Error::Interpreter(error).into()),
         }
     }
 
@@ -2552,7 +2860,7 @@ where
         // as whole.
         let sliced_buf = &serialized_value[..cmp::min(dest_size, serialized_value.len())];
         if let Err(error) = self.try_get_memory()?.set(dest_ptr, sliced_buf) {
-            return Err(Error::Interpreter(error.into()));
+            return Err(Error::Interpreter(error));
         }
 
         // Never panics because we check that `serialized_value.len()` fits in `u32`.
@@ -2566,7 +2874,7 @@ where
             .try_get_memory()?
             .set(bytes_written_ptr, &bytes_written_data)
         {
-            return Err(Error::Interpreter(error.into()));
+            return Err(Error::Interpreter(error));
         }
 
         Ok(Ok(()))
@@ -2605,7 +2913,7 @@ where
         let arg_size_bytes = arg_size.to_le_bytes(); // Wasm is little-endian
 
         if let Err(e) = self.try_get_memory()?.set(size_ptr, &arg_size_bytes) {
-            return Err(Error::Interpreter(e.into()).into());
+            return Err(Error::Interpreter(e).into());
         }
 
         Ok(Ok(()))
@@ -2634,7 +2942,7 @@ where
             .try_get_memory()?
             .set(output_ptr, &arg.inner_bytes()[..output_size])
         {
-            return Err(Error::Interpreter(error.into()).into());
+            return Err(Error::Interpreter(error).into());
         }
 
         Ok(Ok(()))
@@ -2756,7 +3064,7 @@ where
             .try_get_memory()?
             .set(output_size_ptr, &output_size_bytes)
         {
-            return Err(Error::Interpreter(error.into()));
+            return Err(Error::Interpreter(error));
         }
 
         // Write updated package to the global state
@@ -2819,7 +3127,7 @@ where
         T: AsRef<[Cost]> + Copy,
     {
         let cost = host_function.calculate_gas_cost(weights);
-        self.gas(cost)?;
+        self.gas(GasCharge::Other, cost)?;
         Ok(())
     }
 
@@ -2846,7 +3154,7 @@ where
             .try_get_memory()?
             .set(output_size_ptr, &output_size_bytes)
         {
-            return Err(Error::Interpreter(error.into()));
+            return Err(Error::Interpreter(error));
         }
 
         Ok(Ok(()))
@@ -2895,7 +3203,7 @@ where
 
         let value_bytes = value_size.to_le_bytes(); // Wasm is little-endian
         if let Err(error) = self.try_get_memory()?.set(output_size_ptr, &value_bytes) {
-            return Err(Error::Interpreter(error.into()).into());
+            return Err(Error::Interpreter(error).into());
         }
 
         Ok(Ok(()))
@@ -2930,7 +3238,7 @@ where
 
         let value_bytes = value_size.to_le_bytes(); // Wasm is little-endian
         if let Err(error) = self.try_get_memory()?.set(output_size_ptr, &value_bytes) {
-            return Err(Error::Interpreter(error.into()).into());
+            return Err(Error::Interpreter(error).into());
         }
 
         Ok(Ok(()))
@@ -3015,7 +3323,7 @@ where
         };
         let total_keys_bytes = total_keys.to_le_bytes();
         if let Err(error) = self.try_get_memory()?.set(len_ptr, &total_keys_bytes) {
-            return Err(Error::Interpreter(error.into()).into());
+            return Err(Error::Interpreter(error).into());
         }
 
         if total_keys == 0 {
@@ -3035,25 +3343,9 @@ where
 
         let length_bytes = length.to_le_bytes();
         if let Err(error) = self.try_get_memory()?.set(result_size_ptr, &length_bytes) {
-            return Err(Error::Interpreter(error.into()).into());
+            return Err(Error::Interpreter(error).into());
         }
 
         Ok(Ok(()))
     }
 }
-
-#[cfg(feature = "test-support")]
-fn dump_runtime_stack_info(instance: casper_wasmi::ModuleRef, max_stack_height: u32) {
-    let globals = instance.globals();
-    let Some(current_runtime_call_stack_height) = globals.last() else {
-        return;
-    };
-
-    if let RuntimeValue::I32(current_runtime_call_stack_height) =
-        current_runtime_call_stack_height.get()
-    {
-        if current_runtime_call_stack_height > max_stack_height as i32 {
-            eprintln!("runtime stack overflow, current={current_runtime_call_stack_height}, max={max_stack_height}");
-        }
-    };
-}
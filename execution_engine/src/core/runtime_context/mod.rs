@@ -17,15 +17,21 @@ use casper_types::{
     bytesrepr::ToBytes,
     contracts::NamedKeys,
     system::auction::EraInfo,
-    AccessRights, BlockTime, CLType, CLValue, ContextAccessRights, Contract, ContractHash,
-    ContractPackage, ContractPackageHash, DeployHash, DeployInfo, EntryPointAccess, EntryPointType,
-    Gas, GrantedAccess, Key, KeyTag, Phase, ProtocolVersion, PublicKey, RuntimeArgs, StoredValue,
-    Transfer, TransferAddr, URef, URefAddr, DICTIONARY_ITEM_KEY_MAX_LENGTH, KEY_HASH_LENGTH, U512,
+    AccessRights, BlockTime, CLType, CLValue, ContextAccessRights, Contract, ContractEvent,
+    ContractHash, ContractPackage, ContractPackageHash, DeployHash, DeployInfo, EntryPointAccess,
+    EntryPointType, Gas, GrantedAccess, Key, KeyTag, Phase, ProtocolVersion, PublicKey,
+    RuntimeArgs, StoredValue, Transfer, TransferAddr, URef, URefAddr,
+    DICTIONARY_ITEM_KEY_MAX_LENGTH, KEY_HASH_LENGTH, U512,
 };
 
 use crate::{
     core::{
-        engine_state::{execution_effect::ExecutionEffect, EngineConfig, SystemContractRegistry},
+        engine_state::{
+            execution_effect::ExecutionEffect,
+            execution_trace::{ExecutionHook, ExecutionTraceFrame, HookAction, TraceEvent},
+            gas_profile::{GasCharge, GasProfiler, GasProfilerScope},
+            EngineConfig, SystemContractRegistry,
+        },
         execution::{AddressGenerator, Error},
         runtime_context::dictionary::DictionaryValue,
         tracking_copy::{AddResult, TrackingCopy, TrackingCopyExt},
@@ -103,7 +109,12 @@ pub struct RuntimeContext<'a, R> {
     engine_config: EngineConfig,
     entry_point_type: EntryPointType,
     transfers: Vec<TransferAddr>,
+    events: Vec<ContractEvent>,
     remaining_spending_limit: U512,
+    gas_profiler: Option<Rc<RefCell<GasProfiler>>>,
+    execution_hook: Option<Rc<RefCell<dyn ExecutionHook>>>,
+    // Contract called by the engine to approve the payment of a sponsored deploy
+    payment_sponsor: Option<ContractHash>,
 }
 
 impl<'a, R> RuntimeContext<'a, R>
@@ -155,7 +166,11 @@ where
             phase,
             engine_config,
             transfers,
+            events: Vec::new(),
             remaining_spending_limit,
+            gas_profiler: None,
+            execution_hook: None,
+            payment_sponsor: None,
         }
     }
 
@@ -183,7 +198,11 @@ where
         let phase = self.phase;
         let engine_config = self.engine_config.clone();
         let transfers = self.transfers.clone();
+        let events = self.events.clone();
         let remaining_spending_limit = self.remaining_spending_limit();
+        let gas_profiler = self.gas_profiler.clone();
+        let execution_hook = self.execution_hook.clone();
+        let payment_sponsor = self.payment_sponsor;
 
         RuntimeContext {
             tracking_copy,
@@ -204,10 +223,70 @@ where
             phase,
             engine_config,
             transfers,
+            events,
             remaining_spending_limit,
+            gas_profiler,
+            execution_hook,
+            payment_sponsor,
         }
     }
 
+    /// Sets the profiler recording the gas charged through this context and those created from it.
+    pub(crate) fn set_gas_profiler(&mut self, gas_profiler: Rc<RefCell<GasProfiler>>) {
+        self.gas_profiler = Some(gas_profiler);
+    }
+
+    /// Calls `f` with the gas profiler, if gas is being profiled.
+    pub(crate) fn with_gas_profiler(&self, f: impl FnOnce(&mut GasProfiler)) {
+        if let Some(gas_profiler) = &self.gas_profiler {
+            f(&mut gas_profiler.borrow_mut());
+        }
+    }
+
+    /// Pushes a frame onto the gas profiler via `enter`, if gas is being profiled, returning a
+    /// scope which pops it again when dropped.
+    pub(crate) fn enter_gas_profiler_frame(
+        &self,
+        enter: impl FnOnce(&mut GasProfiler),
+    ) -> GasProfilerScope {
+        GasProfilerScope::enter(self.gas_profiler.as_ref(), enter)
+    }
+
+    /// Sets the hook called at each event of the execution through this context and those created
+    /// from it.
+    pub(crate) fn set_execution_hook(&mut self, execution_hook: Rc<RefCell<dyn ExecutionHook>>) {
+        self.execution_hook = Some(execution_hook);
+    }
+
+    /// Calls the execution hook with the event built by `event`, if there is a hook.
+    pub(crate) fn on_execution_event(&self, event: impl FnOnce() -> TraceEvent) -> HookAction {
+        match &self.execution_hook {
+            Some(execution_hook) => execution_hook.borrow_mut().on_event(&event()),
+            None => HookAction::Continue,
+        }
+    }
+
+    /// Calls the execution hook with the event built by `enter`, if there is a hook, returning a
+    /// frame which reports [`TraceEvent::ExitFrame`] when dropped.
+    pub(crate) fn enter_execution_trace_frame(
+        &self,
+        enter: impl FnOnce() -> TraceEvent,
+    ) -> ExecutionTraceFrame {
+        ExecutionTraceFrame::enter(self.execution_hook.as_ref(), enter)
+    }
+
+    /// Sets the contract the engine calls to approve the payment of a sponsored deploy through this
+    /// context and those created from it.
+    pub(crate) fn set_payment_sponsor(&mut self, payment_sponsor: ContractHash) {
+        self.payment_sponsor = Some(payment_sponsor);
+    }
+
+    /// Returns the contract the engine called to approve the payment of a sponsored deploy, if
+    /// any.
+    pub(crate) fn payment_sponsor(&self) -> Option<ContractHash> {
+        self.payment_sponsor
+    }
+
     /// Returns all authorization keys for this deploy.
     pub fn authorization_keys(&self) -> &BTreeSet<AccountHash> {
         &self.authorization_keys
@@ -509,9 +588,19 @@ where
 
         let stored_value = match maybe_stored_value {
             Some(stored_value) => dictionary::handle_stored_value(*key, stored_value)?,
-            None => return Ok(None),
+            None => {
+                self.on_execution_event(|| TraceEvent::Read {
+                    key: *key,
+                    value: None,
+                });
+                return Ok(None);
+            }
         };
 
+        self.on_execution_event(|| TraceEvent::Read {
+            key: *key,
+            value: Some(stored_value.clone()),
+        });
         Ok(Some(stored_value))
     }
 
@@ -528,6 +617,14 @@ where
             .map_err(Into::into)
     }
 
+    /// Returns whether a value is stored under `key`, without regard to the value itself.
+    pub fn key_exists_direct(&mut self, key: &Key) -> Result<bool, Error> {
+        self.tracking_copy
+            .borrow_mut()
+            .exists(self.correlation_id, key)
+            .map_err(Into::into)
+    }
+
     /// This method is a wrapper over `read_gs` in the sense that it extracts the type held by a
     /// `StoredValue` stored in the global state in a type safe manner.
     ///
@@ -655,6 +752,27 @@ where
         &mut self.transfers
     }
 
+    /// Returns list of events emitted by contracts.
+    pub fn events(&self) -> &Vec<ContractEvent> {
+        &self.events
+    }
+
+    /// Returns mutable list of events emitted by contracts.
+    pub fn events_mut(&mut self) -> &mut Vec<ContractEvent> {
+        &mut self.events
+    }
+
+    /// Records an event emitted under the given topic by the account or contract in whose context
+    /// we are executing.
+    pub fn emit_event(&mut self, topic: String, value: CLValue) {
+        let event = ContractEvent {
+            emitter: self.base_key.to_formatted_string(),
+            topic,
+            value,
+        };
+        self.events.push(event);
+    }
+
     fn validate_cl_value(&self, cl_value: &CLValue) -> Result<(), Error> {
         match cl_value.cl_type() {
             CLType::Bool
@@ -844,11 +962,13 @@ where
     /// Returns [`Error::GasLimit`] if gas limit exceeded and `()` if not.
     /// Intuition about the return value sense is to answer the question 'are we
     /// allowed to continue?'
-    pub(crate) fn charge_gas(&mut self, amount: Gas) -> Result<(), Error> {
+    ///
+    /// If gas is being profiled, the amount charged is recorded as a `charge`.
+    pub(crate) fn charge_gas(&mut self, charge: GasCharge, amount: Gas) -> Result<(), Error> {
         let prev = self.gas_counter();
         let gas_limit = self.gas_limit();
         // gas charge overflow protection
-        match prev.checked_add(amount) {
+        let result = match prev.checked_add(amount) {
             None => {
                 self.set_gas_counter(gas_limit);
                 Err(Error::GasLimit)
@@ -861,7 +981,14 @@ where
                 self.set_gas_counter(val);
                 Ok(())
             }
+        };
+        if let Some(gas_profiler) = &self.gas_profiler {
+            let charged = self.gas_counter().checked_sub(prev).unwrap_or_default();
+            gas_profiler
+                .borrow_mut()
+                .record(self.phase, charge, charged);
         }
+        result
     }
 
     /// Checks if we are calling a system contract.
@@ -885,7 +1012,7 @@ where
 
         let gas_cost = storage_costs.calculate_gas_cost(bytes_count);
 
-        self.charge_gas(gas_cost)
+        self.charge_gas(GasCharge::Storage, gas_cost)
     }
 
     /// Charges gas for using a host system contract's entrypoint.
@@ -900,7 +1027,7 @@ where
             return Ok(());
         }
         let amount: Gas = call_cost.into();
-        self.charge_gas(amount)
+        self.charge_gas(GasCharge::SystemContractCall, amount)
     }
 
     /// Writes data to global state with a measurement.
@@ -918,9 +1045,12 @@ where
         let bytes_count = stored_value.serialized_length();
         self.charge_gas_storage(bytes_count)?;
 
-        self.tracking_copy
-            .borrow_mut()
-            .write(key.into(), stored_value);
+        let key = key.into();
+        self.on_execution_event(|| TraceEvent::Write {
+            key,
+            value: stored_value.clone(),
+        });
+        self.tracking_copy.borrow_mut().write(key, stored_value);
         Ok(())
     }
 
@@ -949,6 +1079,10 @@ where
         let value_bytes_count = value.serialized_length();
         self.charge_gas_storage(value_bytes_count)?;
 
+        self.on_execution_event(|| TraceEvent::Add {
+            key,
+            value: value.clone(),
+        });
         match self
             .tracking_copy
             .borrow_mut()
@@ -1208,9 +1342,17 @@ where
 
         if let Some(stored_value) = maybe_stored_value {
             let stored_value = dictionary::handle_stored_value(dictionary_key, stored_value)?;
+            self.on_execution_event(|| TraceEvent::Read {
+                key: dictionary_key,
+                value: Some(stored_value.clone()),
+            });
             let cl_value = CLValue::try_from(stored_value).map_err(Error::TypeMismatch)?;
             Ok(Some(cl_value))
         } else {
+            self.on_execution_event(|| TraceEvent::Read {
+                key: dictionary_key,
+                value: None,
+            });
             Ok(None)
         }
     }
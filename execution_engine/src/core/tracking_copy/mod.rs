@@ -32,7 +32,10 @@ use crate::{
         newtypes::CorrelationId,
         transform::{self, Transform},
     },
-    storage::{global_state::StateReader, trie::merkle_proof::TrieMerkleProof},
+    storage::{
+        global_state::StateReader,
+        trie::merkle_proof::{TrieMerkleMultiProof, TrieMerkleProof},
+    },
 };
 
 /// Result of a query on a `TrackingCopy`.
@@ -225,6 +228,9 @@ pub struct TrackingCopy<R> {
     reader: R,
     cache: TrackingCopyCache<HeapSize>,
     journal: ExecutionJournal,
+    // Keys whose values were only read from `reader` to add to them or to check that they exist,
+    // and whose cached values haven't been read since.
+    unobserved: BTreeSet<Key>,
 }
 
 /// Result of executing an "add" operation on a value in the state.
@@ -263,6 +269,7 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
              * be fraction of wasm memory
              * limit? */
             journal: Default::default(),
+            unobserved: BTreeSet::new(),
         }
     }
 
@@ -293,8 +300,14 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         key: &Key,
     ) -> Result<Option<StoredValue>, R::Error> {
         if let Some(value) = self.cache.get(key) {
-            return Ok(Some(value.to_owned()));
+            let value = value.to_owned();
+            if self.unobserved.remove(key) {
+                // The cached value depends on the one in the underlying state, so read it as such.
+                self.reader.read(correlation_id, key)?;
+            }
+            return Ok(Some(value));
         }
+        let _ = self.unobserved.remove(key);
         if let Some(value) = self.reader.read(correlation_id, key)? {
             self.cache.insert_read(*key, value.to_owned());
             Ok(Some(value))
@@ -303,6 +316,24 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         }
     }
 
+    /// Gets the value under `key` in order to add to it or to check that it exists.
+    fn get_for_add(
+        &mut self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<StoredValue>, R::Error> {
+        if let Some(value) = self.cache.get(key) {
+            return Ok(Some(value.to_owned()));
+        }
+        if let Some(value) = self.reader.read_for_add(correlation_id, key)? {
+            self.cache.insert_read(*key, value.to_owned());
+            let _ = self.unobserved.insert(*key);
+            Ok(Some(value))
+        } else {
+            Ok(None)
+        }
+    }
+
     /// Gets the set of keys in the state whose tag is `key_tag`.
     pub fn get_keys(
         &mut self,
@@ -342,10 +373,22 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         }
     }
 
+    /// Returns whether a value is stored under `key`, without regard to the value itself.
+    pub fn exists(&mut self, correlation_id: CorrelationId, key: &Key) -> Result<bool, R::Error> {
+        let normalized_key = key.normalize();
+        if self.get_for_add(correlation_id, &normalized_key)?.is_some() {
+            self.journal.push((normalized_key, Transform::Identity));
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
     /// Writes `value` under `key`. Note that the write is only cached, and the global state itself
     /// remains unmodified.
     pub fn write(&mut self, key: Key, value: StoredValue) {
         let normalized_key = key.normalize();
+        let _ = self.unobserved.remove(&normalized_key);
         self.cache.insert_write(normalized_key, value.clone());
         self.journal.push((normalized_key, Transform::Write(value)));
     }
@@ -361,7 +404,7 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         value: StoredValue,
     ) -> Result<AddResult, R::Error> {
         let normalized_key = key.normalize();
-        let current_value = match self.get(correlation_id, &normalized_key)? {
+        let current_value = match self.get_for_add(correlation_id, &normalized_key)? {
             None => return Ok(AddResult::KeyNotFound(normalized_key)),
             Some(current_value) => current_value,
         };
@@ -575,6 +618,9 @@ impl<R: StateReader<Key, StoredValue>> StateReader<Key, StoredValue> for &Tracki
         key: &Key,
     ) -> Result<Option<StoredValue>, Self::Error> {
         if let Some(value) = self.cache.muts_cached.get(key) {
+            if self.unobserved.contains(key) {
+                self.reader.read(correlation_id, key)?;
+            }
             return Ok(Some(value.to_owned()));
         }
         if let Some(value) = self.reader.read(correlation_id, key)? {
@@ -584,6 +630,17 @@ impl<R: StateReader<Key, StoredValue>> StateReader<Key, StoredValue> for &Tracki
         }
     }
 
+    fn read_for_add(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<StoredValue>, Self::Error> {
+        if let Some(value) = self.cache.muts_cached.get(key) {
+            return Ok(Some(value.to_owned()));
+        }
+        self.reader.read_for_add(correlation_id, key)
+    }
+
     fn read_with_proof(
         &self,
         correlation_id: CorrelationId,
@@ -592,6 +649,14 @@ impl<R: StateReader<Key, StoredValue>> StateReader<Key, StoredValue> for &Tracki
         self.reader.read_with_proof(correlation_id, key)
     }
 
+    fn read_with_multi_proof(
+        &self,
+        correlation_id: CorrelationId,
+        keys: &[Key],
+    ) -> Result<Option<TrieMerkleMultiProof<Key, StoredValue>>, Self::Error> {
+        self.reader.read_with_multi_proof(correlation_id, keys)
+    }
+
     fn keys_with_prefix(
         &self,
         correlation_id: CorrelationId,
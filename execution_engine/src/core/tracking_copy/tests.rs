@@ -22,7 +22,7 @@ use crate::{
     shared::{execution_journal::ExecutionJournal, newtypes::CorrelationId, transform::Transform},
     storage::{
         global_state::{in_memory::InMemoryGlobalState, StateProvider, StateReader},
-        trie::merkle_proof::TrieMerkleProof,
+        trie::merkle_proof::{TrieMerkleMultiProof, TrieMerkleProof},
     },
 };
 
@@ -71,6 +71,14 @@ impl StateReader<Key, StoredValue> for CountingDb {
         Ok(None)
     }
 
+    fn read_with_multi_proof(
+        &self,
+        _correlation_id: CorrelationId,
+        _keys: &[Key],
+    ) -> Result<Option<TrieMerkleMultiProof<Key, StoredValue>>, Self::Error> {
+        Ok(None)
+    }
+
     fn keys_with_prefix(
         &self,
         _correlation_id: CorrelationId,
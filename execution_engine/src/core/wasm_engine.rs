@@ -0,0 +1,548 @@
+//! The interface between the execution engine and the Wasm interpreter executing contracts.
+//!
+//! Contract Wasm is preprocessed, with gas metering and stack height limiting injected, before it
+//! is handed to a [`WasmEngine`], so every engine charges exactly the same gas for the same
+//! execution.  Engines only need to interpret the preprocessed module faithfully and call back
+//! into the host for imported functions.
+mod upstream_wasmi;
+mod wasmi;
+
+use std::ops::{Deref, Range};
+
+use casper_wasm::elements::Module;
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    ProtocolVersion,
+};
+
+use crate::core::{engine_state::EngineConfig, execution::Error};
+
+/// The Wasm engines able to execute contracts.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, DataSize)]
+#[serde(rename_all = "snake_case")]
+pub enum WasmEngineKind {
+    /// The `casper-wasmi` interpreter.
+    #[default]
+    Wasmi,
+    /// The upstream `wasmi` interpreter, version 0.31, which compiles modules to bytecode before
+    /// executing them and is faster than `casper-wasmi`, forked from its version 0.13.
+    UpstreamWasmi,
+}
+
+impl WasmEngineKind {
+    /// All the available engines.
+    pub const ALL: [WasmEngineKind; 2] = [WasmEngineKind::Wasmi, WasmEngineKind::UpstreamWasmi];
+
+    /// Returns the engine.
+    pub fn engine(self) -> &'static dyn WasmEngine {
+        match self {
+            WasmEngineKind::Wasmi => &wasmi::WasmiEngine,
+            WasmEngineKind::UpstreamWasmi => &upstream_wasmi::UpstreamWasmiEngine,
+        }
+    }
+}
+
+const WASMI_TAG: u8 = 0;
+const UPSTREAM_WASMI_TAG: u8 = 1;
+
+impl ToBytes for WasmEngineKind {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let tag = match self {
+            WasmEngineKind::Wasmi => WASMI_TAG,
+            WasmEngineKind::UpstreamWasmi => UPSTREAM_WASMI_TAG,
+        };
+        Ok(vec![tag])
+    }
+
+    fn serialized_length(&self) -> usize {
+        1
+    }
+}
+
+impl FromBytes for WasmEngineKind {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        let kind = match tag {
+            WASMI_TAG => WasmEngineKind::Wasmi,
+            UPSTREAM_WASMI_TAG => WasmEngineKind::UpstreamWasmi,
+            _ => return Err(bytesrepr::Error::Formatting),
+        };
+        Ok((kind, remainder))
+    }
+}
+
+/// A value passed to or returned from a host function.
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum WasmValue {
+    /// A 32-bit integer.
+    I32(i32),
+    /// A 64-bit integer.
+    I64(i64),
+    /// The bits of a 32-bit float.
+    F32(u32),
+    /// The bits of a 64-bit float.
+    F64(u64),
+}
+
+/// The most parameters taken by any host function.
+const MAX_HOST_FUNCTION_PARAMS: usize = 10;
+
+/// The arguments of a host function call, collected without allocating.
+#[derive(Copy, Clone, Debug)]
+struct HostArgs {
+    values: [WasmValue; MAX_HOST_FUNCTION_PARAMS],
+    len: usize,
+}
+
+impl HostArgs {
+    /// Collects `args`, failing if there are more than any host function takes.
+    fn new(args: impl IntoIterator<Item = WasmValue>) -> Result<Self, Error> {
+        let mut host_args = HostArgs {
+            values: [WasmValue::I32(0); MAX_HOST_FUNCTION_PARAMS],
+            len: 0,
+        };
+        for arg in args {
+            let value = host_args.values.get_mut(host_args.len).ok_or_else(|| {
+                Error::Interpreter(format!(
+                    "host functions take at most {} arguments",
+                    MAX_HOST_FUNCTION_PARAMS
+                ))
+            })?;
+            *value = arg;
+            host_args.len += 1;
+        }
+        Ok(host_args)
+    }
+}
+
+impl Deref for HostArgs {
+    type Target = [WasmValue];
+
+    fn deref(&self) -> &[WasmValue] {
+        &self.values[..self.len]
+    }
+}
+
+/// Returns the range of the `size` bytes at `offset` in a memory of `len` bytes, failing with the
+/// same messages as `casper-wasmi` if they are out of bounds.
+fn memory_region(offset: usize, size: usize, len: usize) -> Result<Range<usize>, String> {
+    let end = offset.checked_add(size).ok_or_else(|| {
+        format!(
+            "trying to access memory block of size {} from offset {}",
+            size, offset
+        )
+    })?;
+
+    if end > len {
+        return Err(format!(
+            "trying to access region [{}..{}] in memory [0..{}]",
+            offset, end, len,
+        ));
+    }
+
+    Ok(offset..end)
+}
+
+/// An instance of a [`CompiledModule`] along with its linear memory.
+pub type InstanceAndMemory = (Box<dyn WasmInstance>, Box<dyn WasmMemory>);
+
+/// A Wasm engine, compiling preprocessed modules for execution.
+pub trait WasmEngine: Send + Sync {
+    /// Compiles `module`, which has already been preprocessed.
+    fn compile(&self, module: Module) -> Result<Box<dyn CompiledModule>, Error>;
+}
+
+/// A module compiled by a [`WasmEngine`], which can be instantiated any number of times.
+pub trait CompiledModule: Send + Sync {
+    /// Instantiates the module, resolving its imports against the host functions available under
+    /// `protocol_version`.
+    ///
+    /// Fails with [`Error::UnsupportedWasmStart`] if the module has a start function.
+    fn instantiate(
+        &self,
+        protocol_version: ProtocolVersion,
+        engine_config: &EngineConfig,
+    ) -> Result<InstanceAndMemory, Error>;
+}
+
+/// An instance of a [`CompiledModule`].
+pub trait WasmInstance {
+    /// Calls the exported function `name`, which takes no arguments and returns nothing, with
+    /// imported functions calling back into `host`.
+    ///
+    /// A host function failing with an [`Error`] fails the call with that error, including
+    /// [`Error::Ret`].  Any other trap fails it with [`Error::Interpreter`].
+    fn invoke_export(&self, name: &str, host: &mut dyn WasmHost) -> Result<(), Error>;
+}
+
+/// The host functions imported by an instance.
+pub trait WasmHost {
+    /// Calls the host function at `index`, a
+    /// [`FunctionIndex`](crate::core::resolvers::v1_function_index::FunctionIndex).
+    ///
+    /// An engine fails the call to the exported function with the returned error, if any.
+    fn invoke_index(
+        &mut self,
+        index: usize,
+        args: &[WasmValue],
+    ) -> Result<Option<WasmValue>, Error>;
+}
+
+/// The linear memory of a [`WasmInstance`].
+pub trait WasmMemory {
+    /// Calls `func` with the `size` bytes of memory at `offset`, without copying them.
+    ///
+    /// Fails without calling `func` if the bytes are out of bounds.
+    fn with_slice(
+        &self,
+        offset: usize,
+        size: usize,
+        func: &mut dyn FnMut(&[u8]),
+    ) -> Result<(), String>;
+
+    /// Copies `bytes` into memory at `offset`.
+    fn set(&self, offset: u32, bytes: &[u8]) -> Result<(), String>;
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_wasm::{
+        builder,
+        elements::{External, ImportEntry, Instruction, Instructions, ValueType},
+    };
+
+    use casper_types::ApiError;
+
+    use super::*;
+    use crate::core::resolvers::v1_function_index::FunctionIndex;
+
+    const REVERT_CODE: i32 = 7;
+
+    /// The exported functions of [`module`] which trap.
+    const TRAPPING_EXPORTS: [&str; 3] = ["trap", "divide_by_zero", "load_out_of_bounds"];
+
+    /// Builds a module importing memory and `casper_revert`, which its exported `call` function
+    /// calls with `REVERT_CODE`, and exporting the [`TRAPPING_EXPORTS`].
+    fn module() -> Module {
+        builder::module()
+            .import()
+            .module("env")
+            .field("memory")
+            .external()
+            .memory(1, Some(1))
+            .build()
+            .import()
+            .module("env")
+            .field("casper_revert")
+            .external()
+            .func(0)
+            .build()
+            .function()
+            .signature()
+            .with_param(ValueType::I32)
+            .build()
+            .body()
+            .with_instructions(Instructions::new(vec![
+                Instruction::GetLocal(0),
+                Instruction::Call(0),
+                Instruction::End,
+            ]))
+            .build()
+            .build()
+            .function()
+            .signature()
+            .build()
+            .body()
+            .with_instructions(Instructions::new(vec![
+                Instruction::I32Const(REVERT_CODE),
+                Instruction::Call(1),
+                Instruction::End,
+            ]))
+            .build()
+            .build()
+            .function()
+            .signature()
+            .build()
+            .body()
+            .with_instructions(Instructions::new(vec![
+                Instruction::Unreachable,
+                Instruction::End,
+            ]))
+            .build()
+            .build()
+            .function()
+            .signature()
+            .build()
+            .body()
+            .with_instructions(Instructions::new(vec![
+                Instruction::I32Const(1),
+                Instruction::I32Const(0),
+                Instruction::I32DivS,
+                Instruction::Drop,
+                Instruction::End,
+            ]))
+            .build()
+            .build()
+            .function()
+            .signature()
+            .build()
+            .body()
+            .with_instructions(Instructions::new(vec![
+                Instruction::I32Const(64 * 1024),
+                Instruction::I32Load(2, 0),
+                Instruction::Drop,
+                Instruction::End,
+            ]))
+            .build()
+            .build()
+            .export()
+            .field("call")
+            .internal()
+            .func(2)
+            .build()
+            .export()
+            .field("trap")
+            .internal()
+            .func(3)
+            .build()
+            .export()
+            .field("divide_by_zero")
+            .internal()
+            .func(4)
+            .build()
+            .export()
+            .field("load_out_of_bounds")
+            .internal()
+            .func(5)
+            .build()
+            .build()
+    }
+
+    fn instantiate(kind: WasmEngineKind) -> InstanceAndMemory {
+        kind.engine()
+            .compile(module())
+            .expect("should compile")
+            .instantiate(ProtocolVersion::V1_0_0, &EngineConfig::default())
+            .expect("should instantiate")
+    }
+
+    /// Records host function calls, failing each with `error` if set.
+    #[derive(Default)]
+    struct RecordingHost {
+        calls: Vec<(usize, Vec<WasmValue>)>,
+        error: Option<Error>,
+    }
+
+    impl WasmHost for RecordingHost {
+        fn invoke_index(
+            &mut self,
+            index: usize,
+            args: &[WasmValue],
+        ) -> Result<Option<WasmValue>, Error> {
+            self.calls.push((index, args.to_vec()));
+            match self.error.clone() {
+                Some(error) => Err(error),
+                None => Ok(None),
+            }
+        }
+    }
+
+    #[test]
+    fn bytesrepr_roundtrip() {
+        for kind in WasmEngineKind::ALL {
+            bytesrepr::test_serialization_roundtrip(&kind);
+        }
+    }
+
+    #[test]
+    fn should_call_host_functions() {
+        for kind in WasmEngineKind::ALL {
+            let (instance, _memory) = instantiate(kind);
+            let mut host = RecordingHost::default();
+            instance
+                .invoke_export("call", &mut host)
+                .unwrap_or_else(|error| panic!("{:?} should succeed: {}", kind, error));
+            assert_eq!(
+                host.calls,
+                vec![(
+                    FunctionIndex::RevertFuncIndex as usize,
+                    vec![WasmValue::I32(REVERT_CODE)]
+                )],
+                "{:?}",
+                kind
+            );
+        }
+    }
+
+    #[test]
+    fn should_fail_with_host_error() {
+        for kind in WasmEngineKind::ALL {
+            let (instance, _memory) = instantiate(kind);
+            let mut host = RecordingHost {
+                error: Some(Error::Revert(ApiError::User(REVERT_CODE as u16))),
+                ..Default::default()
+            };
+            let error = instance.invoke_export("call", &mut host).unwrap_err();
+            assert!(
+                matches!(error, Error::Revert(ApiError::User(code)) if code == REVERT_CODE as u16),
+                "{:?}: {:?}",
+                kind,
+                error
+            );
+
+            let error = instance
+                .invoke_export("missing", &mut RecordingHost::default())
+                .unwrap_err();
+            assert!(
+                matches!(error, Error::Interpreter(_)),
+                "{:?}: {:?}",
+                kind,
+                error
+            );
+        }
+    }
+
+    #[test]
+    fn should_fail_identically_with_traps() {
+        for name in TRAPPING_EXPORTS {
+            let errors: Vec<String> = WasmEngineKind::ALL
+                .iter()
+                .map(|kind| {
+                    let (instance, _memory) = instantiate(*kind);
+                    match instance.invoke_export(name, &mut RecordingHost::default()) {
+                        Err(Error::Interpreter(message)) => message,
+                        result => panic!("{:?} should trap in {}: {:?}", kind, name, result),
+                    }
+                })
+                .collect();
+            assert!(errors.len() >= 2);
+            assert!(
+                errors.iter().all(|error| *error == errors[0]),
+                "{}: {:?}",
+                name,
+                errors
+            );
+        }
+    }
+
+    #[test]
+    fn should_fail_identically_to_call_missing_exports() {
+        let errors: Vec<String> = WasmEngineKind::ALL
+            .iter()
+            .map(|kind| {
+                let (instance, _memory) = instantiate(*kind);
+                match instance.invoke_export("missing", &mut RecordingHost::default()) {
+                    Err(Error::Interpreter(message)) => message,
+                    result => panic!("{:?} should fail: {:?}", kind, result),
+                }
+            })
+            .collect();
+        assert!(
+            errors.iter().all(|error| *error == errors[0]),
+            "{:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn should_fail_identically_to_import_unknown_host_functions() {
+        let mut module = module();
+        let import_section = module.import_section_mut().expect("should have imports");
+        import_section.entries_mut()[1] = ImportEntry::new(
+            "env".to_string(),
+            "casper_unknown".to_string(),
+            External::Function(0),
+        );
+        let errors: Vec<String> = WasmEngineKind::ALL
+            .iter()
+            .map(|kind| {
+                let compiled = kind
+                    .engine()
+                    .compile(module.clone())
+                    .expect("should compile");
+                match compiled.instantiate(ProtocolVersion::V1_0_0, &EngineConfig::default()) {
+                    Err(Error::Interpreter(message)) => message,
+                    result => panic!("{:?} should fail: {:?}", kind, result.err()),
+                }
+            })
+            .collect();
+        assert!(
+            errors.iter().all(|error| *error == errors[0]),
+            "{:?}",
+            errors
+        );
+    }
+
+    /// Copies the argument of every host function call into memory at `offset`.
+    struct MemoryHost {
+        memory: Box<dyn WasmMemory>,
+        offset: u32,
+    }
+
+    impl WasmHost for MemoryHost {
+        fn invoke_index(
+            &mut self,
+            _index: usize,
+            args: &[WasmValue],
+        ) -> Result<Option<WasmValue>, Error> {
+            if let [WasmValue::I32(value)] = args {
+                self.memory
+                    .set(self.offset, &value.to_le_bytes())
+                    .map_err(Error::Interpreter)?;
+            }
+            Ok(None)
+        }
+    }
+
+    #[test]
+    fn should_access_memory_during_host_calls() {
+        for kind in WasmEngineKind::ALL {
+            let (instance, memory) = instantiate(kind);
+            let mut host = MemoryHost { memory, offset: 8 };
+            instance
+                .invoke_export("call", &mut host)
+                .unwrap_or_else(|error| panic!("{:?} should succeed: {}", kind, error));
+
+            let mut bytes = Vec::new();
+            host.memory
+                .with_slice(8, 4, &mut |slice| bytes.extend_from_slice(slice))
+                .expect("should read");
+            assert_eq!(bytes, REVERT_CODE.to_le_bytes(), "{:?}", kind);
+
+            host.offset = 64 * 1024;
+            let error = instance.invoke_export("call", &mut host).unwrap_err();
+            assert!(
+                matches!(error, Error::Interpreter(_)),
+                "{:?}: {:?}",
+                kind,
+                error
+            );
+        }
+    }
+
+    #[test]
+    fn should_access_memory_within_bounds() {
+        for kind in WasmEngineKind::ALL {
+            let (_instance, memory) = instantiate(kind);
+            memory.set(10, &[1, 2, 3]).expect("should set");
+
+            let mut bytes = Vec::new();
+            memory
+                .with_slice(11, 2, &mut |slice| bytes.extend_from_slice(slice))
+                .expect("should read");
+            assert_eq!(bytes, vec![2, 3], "{:?}", kind);
+
+            let page_size = 64 * 1024;
+            assert!(memory.set(page_size - 1, &[1, 2]).is_err(), "{:?}", kind);
+            assert!(memory
+                .with_slice(page_size as usize - 1, 2, &mut |_| panic!("out of bounds"))
+                .is_err());
+            assert!(memory
+                .with_slice(usize::MAX, 2, &mut |_| panic!("overflowed"))
+                .is_err());
+        }
+    }
+}
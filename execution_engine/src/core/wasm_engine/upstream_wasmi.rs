@@ -0,0 +1,499 @@
+//! The [`WasmEngine`] backed by the upstream `wasmi` interpreter.
+//!
+//! `casper-wasmi` is a fork of `wasmi` 0.13, which executes the Wasm instructions directly.  Since
+//! 0.30, `wasmi` first translates modules into its own register-friendly bytecode, which executes
+//! considerably faster.  It is configured to accept only the Wasm features `casper-wasmi` accepts,
+//! and whenever it rejects a module or fails to look up an export, the same operation is repeated
+//! on `casper-wasmi` to fail with the same error, as error messages end up in execution results.
+use std::{
+    cell::{Cell, RefCell},
+    mem,
+    ptr::NonNull,
+    rc::Rc,
+};
+
+use casper_wasm::elements::Module;
+use once_cell::sync::Lazy;
+use wasmi::{
+    core::{Trap, TrapCode, ValueType, F32, F64},
+    errors::{InstantiationError, LinkerError},
+    Caller, Config, Engine, Extern, ExternType, FuncType, Instance, Linker, Memory, MemoryType,
+    StackLimits, Store, Value,
+};
+
+use casper_types::ProtocolVersion;
+
+use super::{
+    memory_region, CompiledModule, HostArgs, InstanceAndMemory, WasmEngine, WasmEngineKind,
+    WasmHost, WasmInstance, WasmMemory, WasmValue,
+};
+use crate::core::{
+    engine_state::EngineConfig,
+    execution::Error,
+    resolvers::{self, error::ResolverError, RuntimeModuleImportResolver},
+};
+
+/// The value stack is as large as `casper-wasmi`'s, which is given in bytes rather than values.
+const MAX_VALUE_STACK_HEIGHT: usize = casper_wasmi::DEFAULT_VALUE_STACK_LIMIT / 8;
+
+/// The engine shared by all modules, as modules can only be instantiated in stores of the engine
+/// which compiled them.
+static ENGINE: Lazy<Engine> = Lazy::new(|| {
+    let stack_limits = StackLimits::new(
+        1024,
+        MAX_VALUE_STACK_HEIGHT,
+        casper_wasmi::DEFAULT_CALL_STACK_LIMIT,
+    )
+    .expect("initial value stack height should not exceed its maximum");
+    let mut config = Config::default();
+    config
+        .set_stack_limits(stack_limits)
+        .wasm_sign_extension(false)
+        .wasm_saturating_float_to_int(false)
+        .wasm_multi_value(false)
+        .wasm_bulk_memory(false)
+        .wasm_reference_types(false)
+        .wasm_tail_call(false)
+        .wasm_extended_const(false);
+    Engine::new(&config)
+});
+
+/// The upstream `wasmi` interpreter.
+pub(super) struct UpstreamWasmiEngine;
+
+impl WasmEngine for UpstreamWasmiEngine {
+    fn compile(&self, module: Module) -> Result<Box<dyn CompiledModule>, Error> {
+        // `wasmi` parses modules with `wasmparser` rather than `casper-wasm`, so the module is
+        // handed over in its binary format.
+        let bytes = casper_wasm::serialize(module.clone()).map_err(Error::ParityWasm)?;
+        match wasmi::Module::new(&ENGINE, &bytes[..]) {
+            Ok(compiled) => Ok(Box::new(UpstreamWasmiModule { module, compiled })),
+            Err(error) => Err(WasmEngineKind::Wasmi
+                .engine()
+                .compile(module)
+                .err()
+                .unwrap_or_else(|| Error::Interpreter(error.to_string()))),
+        }
+    }
+}
+
+struct UpstreamWasmiModule {
+    /// The preprocessed module, kept to reproduce errors on `casper-wasmi`.
+    module: Module,
+    compiled: wasmi::Module,
+}
+
+impl CompiledModule for UpstreamWasmiModule {
+    fn instantiate(
+        &self,
+        protocol_version: ProtocolVersion,
+        engine_config: &EngineConfig,
+    ) -> Result<InstanceAndMemory, Error> {
+        resolvers::check_protocol_version(protocol_version)?;
+        let mut store = Store::new(&ENGINE, HostData::default());
+        let instance = self
+            .link(&mut store, engine_config.wasm_config().max_memory)
+            .and_then(|linker| {
+                linker
+                    .instantiate(&mut store, &self.compiled)
+                    .map_err(|error| error.to_string())
+            });
+        let instance = match instance {
+            Ok(instance) => instance,
+            Err(message) => {
+                return Err(self.canonical_error(protocol_version, engine_config, message))
+            }
+        };
+        // We currently don't support running a "start" section.
+        let instance = match instance.ensure_no_start(&mut store) {
+            Ok(instance) => instance,
+            Err(InstantiationError::FoundStartFn { .. }) => {
+                return Err(Error::UnsupportedWasmStart)
+            }
+            Err(error) => {
+                return Err(self.canonical_error(
+                    protocol_version,
+                    engine_config,
+                    error.to_string(),
+                ))
+            }
+        };
+        let memory = store.data().memory.ok_or(ResolverError::NoImportedMemory)?;
+        let host_call_memory = Rc::clone(&store.data().host_call_memory);
+        let store = Rc::new(RefCell::new(store));
+        let instance = UpstreamWasmiInstance {
+            store: Rc::clone(&store),
+            instance,
+            module: self.module.clone(),
+            protocol_version,
+            engine_config: engine_config.clone(),
+        };
+        let memory = UpstreamWasmiMemory {
+            store,
+            memory,
+            host_call_memory,
+        };
+        Ok((Box::new(instance), Box::new(memory)))
+    }
+}
+
+impl UpstreamWasmiModule {
+    /// Defines the imports of the module the same way as [`RuntimeModuleImportResolver`] resolves
+    /// them for `casper-wasmi`, storing the imported memory in `store`, or fails with a message if
+    /// they can't be.
+    fn link(
+        &self,
+        store: &mut Store<HostData>,
+        max_memory: u32,
+    ) -> Result<Linker<HostData>, String> {
+        let mut linker = Linker::new(&ENGINE);
+        for import in self.compiled.imports() {
+            if import.module() != "env" {
+                return Err(format!("Module {} not found", import.module()));
+            }
+            match import.ty() {
+                ExternType::Func(func_type) => {
+                    if linker.get(&*store, "env", import.name()).is_some() {
+                        continue;
+                    }
+                    let (signature, index) =
+                        RuntimeModuleImportResolver::host_function(import.name())
+                            .map_err(String::from)?;
+                    let host_type = FuncType::new(
+                        signature.params().iter().map(value_type),
+                        signature.return_type().as_ref().map(value_type),
+                    );
+                    if *func_type != host_type {
+                        return Err(format!("Unexpected signature of {}", import.name()));
+                    }
+                    define_host_function(&mut linker, import.name(), index.into(), &signature)
+                        .map_err(|error| error.to_string())?;
+                }
+                ExternType::Memory(memory_type) => {
+                    if import.name() != "memory" {
+                        return Err("Memory imported under unknown name".to_owned());
+                    }
+                    if store.data().memory.is_some() {
+                        return Err("Memory is already instantiated".to_owned());
+                    }
+                    let initial = u32::from(memory_type.initial_pages());
+                    let maximum = memory_type.maximum_pages().map(u32::from);
+                    RuntimeModuleImportResolver::check_memory_limits(initial, maximum, max_memory)?;
+                    let memory = MemoryType::new(initial, maximum)
+                        .and_then(|memory_type| Memory::new(&mut *store, memory_type))
+                        .map_err(|error| error.to_string())?;
+                    linker
+                        .define("env", "memory", memory)
+                        .map_err(|error| error.to_string())?;
+                    store.data_mut().memory = Some(memory);
+                }
+                ExternType::Global(_) | ExternType::Table(_) => {
+                    return Err(format!("Export {} not found", import.name()));
+                }
+            }
+        }
+        Ok(linker)
+    }
+
+    /// Returns the error `casper-wasmi` fails to instantiate the module with, or `message` if it
+    /// unexpectedly succeeds.
+    fn canonical_error(
+        &self,
+        protocol_version: ProtocolVersion,
+        engine_config: &EngineConfig,
+        message: String,
+    ) -> Error {
+        WasmEngineKind::Wasmi
+            .engine()
+            .compile(self.module.clone())
+            .and_then(|compiled| compiled.instantiate(protocol_version, engine_config))
+            .err()
+            .unwrap_or(Error::Interpreter(message))
+    }
+}
+
+fn value_type(value_type: &casper_wasmi::ValueType) -> ValueType {
+    match value_type {
+        casper_wasmi::ValueType::I32 => ValueType::I32,
+        casper_wasmi::ValueType::I64 => ValueType::I64,
+        casper_wasmi::ValueType::F32 => ValueType::F32,
+        casper_wasmi::ValueType::F64 => ValueType::F64,
+    }
+}
+
+/// The data held by the store of an instance.
+#[derive(Default)]
+struct HostData {
+    /// The host of the export being called, if any.
+    host: Option<NonNull<dyn WasmHost>>,
+    /// The imported memory.
+    memory: Option<Memory>,
+    /// The contents of the imported memory while a host function is being called.
+    host_call_memory: Rc<Cell<Option<NonNull<[u8]>>>>,
+}
+
+/// Defines a host function named `name` on `linker`, taking `i32` `$arg`s and returning an `i32`
+/// if `returns` is set, which calls the host function at `index`.
+macro_rules! wrap_host_function {
+    ($linker:expr, $name:expr, $index:expr, $returns:expr $(, $arg:ident)*) => {
+        if $returns {
+            $linker.func_wrap("env", $name, move |caller: Caller<HostData>, $($arg: i32),*| {
+                match call_host(caller, $index, &[$(WasmValue::I32($arg)),*])? {
+                    Some(WasmValue::I32(value)) => Ok(value),
+                    _ => Err(Trap::from(TrapCode::BadSignature)),
+                }
+            })
+        } else {
+            $linker.func_wrap("env", $name, move |caller: Caller<HostData>, $($arg: i32),*| {
+                match call_host(caller, $index, &[$(WasmValue::I32($arg)),*])? {
+                    None => Ok(()),
+                    Some(_) => Err(Trap::from(TrapCode::BadSignature)),
+                }
+            })
+        }
+    };
+}
+
+/// Defines the host function at `index` with `signature` as `name` on `linker`.
+///
+/// Host functions taking and returning `i32`s only, which all of them currently do, are defined
+/// with static types, as calling dynamically typed functions allocates.
+fn define_host_function(
+    linker: &mut Linker<HostData>,
+    name: &str,
+    index: usize,
+    signature: &casper_wasmi::Signature,
+) -> Result<(), LinkerError> {
+    let i32_only = signature
+        .params()
+        .iter()
+        .chain(signature.return_type().as_ref())
+        .all(|value_type| *value_type == casper_wasmi::ValueType::I32);
+    let returns = signature.return_type().is_some();
+    match signature.params().len() {
+        _ if !i32_only => {}
+        0 => return wrap_host_function!(linker, name, index, returns).map(drop),
+        1 => return wrap_host_function!(linker, name, index, returns, a).map(drop),
+        2 => return wrap_host_function!(linker, name, index, returns, a, b).map(drop),
+        3 => return wrap_host_function!(linker, name, index, returns, a, b, c).map(drop),
+        4 => return wrap_host_function!(linker, name, index, returns, a, b, c, d).map(drop),
+        5 => return wrap_host_function!(linker, name, index, returns, a, b, c, d, e).map(drop),
+        6 => return wrap_host_function!(linker, name, index, returns, a, b, c, d, e, f).map(drop),
+        7 => {
+            return wrap_host_function!(linker, name, index, returns, a, b, c, d, e, f, g).map(drop)
+        }
+        8 => {
+            return wrap_host_function!(linker, name, index, returns, a, b, c, d, e, f, g, h)
+                .map(drop)
+        }
+        9 => {
+            return wrap_host_function!(linker, name, index, returns, a, b, c, d, e, f, g, h, i)
+                .map(drop)
+        }
+        10 => {
+            return wrap_host_function!(linker, name, index, returns, a, b, c, d, e, f, g, h, i, j)
+                .map(drop)
+        }
+        _ => {}
+    }
+    let func_type = FuncType::new(
+        signature.params().iter().map(value_type),
+        signature.return_type().as_ref().map(value_type),
+    );
+    linker.func_new("env", name, func_type, move |caller, args, results| {
+        let args = HostArgs::new(args.iter().map(WasmValue::from))?;
+        if let (Some(value), Some(result)) = (call_host(caller, index, &args)?, results.first_mut())
+        {
+            *result = value.into();
+        }
+        Ok(())
+    })?;
+    Ok(())
+}
+
+/// Calls the host function at `index` on the host of the export being called.
+fn call_host(
+    mut caller: Caller<HostData>,
+    index: usize,
+    args: &[WasmValue],
+) -> Result<Option<WasmValue>, Trap> {
+    let mut host = caller
+        .data()
+        .host
+        .expect("host functions should only be called while calling an export");
+    // While the host function is called, the store is mutably borrowed by `caller`, so the memory
+    // is made available to `UpstreamWasmiMemory` through a pointer to its contents instead.
+    if let Some(memory) = caller.data().memory {
+        let contents = NonNull::from(memory.data_mut(&mut caller));
+        caller.data().host_call_memory.set(Some(contents));
+    }
+    // SAFETY: `host` was set from a mutable reference by `UpstreamWasmiInstance::invoke_export`,
+    // which doesn't use the reference until the call to the export returns.
+    let result = unsafe { host.as_mut() }.invoke_index(index, args);
+    caller.data().host_call_memory.set(None);
+    Ok(result?)
+}
+
+struct UpstreamWasmiInstance {
+    store: Rc<RefCell<Store<HostData>>>,
+    instance: Instance,
+    module: Module,
+    protocol_version: ProtocolVersion,
+    engine_config: EngineConfig,
+}
+
+impl WasmInstance for UpstreamWasmiInstance {
+    fn invoke_export(&self, name: &str, host: &mut dyn WasmHost) -> Result<(), Error> {
+        let mut store = self.store.borrow_mut();
+        let func = match self
+            .instance
+            .get_export(&*store, name)
+            .and_then(Extern::into_func)
+        {
+            Some(func) => func,
+            None => return Err(self.canonical_invoke_error(name)),
+        };
+        // Exports are called without arguments, and `casper-wasmi` discards their result.
+        let func_type = func.ty(&*store);
+        if !func_type.params().is_empty() || func_type.results().len() > 1 {
+            return Err(self.canonical_invoke_error(name));
+        }
+        let mut outputs = [Value::I32(0)];
+        let outputs = &mut outputs[..func_type.results().len()];
+
+        // SAFETY: only the lifetime of the reference is erased, and the pointer is only
+        // dereferenced by `call_host` during the call below, after which it is cleared.
+        let host = unsafe {
+            mem::transmute::<NonNull<dyn WasmHost + '_>, NonNull<dyn WasmHost + 'static>>(
+                NonNull::from(host),
+            )
+        };
+        store.data_mut().host = Some(host);
+        let result = func.call(&mut *store, &[], outputs);
+        store.data_mut().host = None;
+        result.map_err(interpreter_error)
+    }
+}
+
+impl UpstreamWasmiInstance {
+    /// Returns the error `casper-wasmi` fails to look up the export `name` with, which it does
+    /// before calling any host function.
+    fn canonical_invoke_error(&self, name: &str) -> Error {
+        let instance = WasmEngineKind::Wasmi
+            .engine()
+            .compile(self.module.clone())
+            .and_then(|compiled| compiled.instantiate(self.protocol_version, &self.engine_config));
+        let error = match instance {
+            Ok((instance, _memory)) => instance.invoke_export(name, &mut NoHost).err(),
+            Err(error) => Some(error),
+        };
+        error.unwrap_or_else(|| Error::Interpreter(format!("Module doesn't have export {}", name)))
+    }
+}
+
+/// A [`WasmHost`] failing every host function call.
+struct NoHost;
+
+impl WasmHost for NoHost {
+    fn invoke_index(
+        &mut self,
+        index: usize,
+        _args: &[WasmValue],
+    ) -> Result<Option<WasmValue>, Error> {
+        Err(Error::Interpreter(format!(
+            "unexpected call to host function {}",
+            index
+        )))
+    }
+}
+
+/// Converts an error of `wasmi` to the error `casper-wasmi` fails with in the same case.
+fn interpreter_error(error: wasmi::Error) -> Error {
+    let trap = match &error {
+        wasmi::Error::Trap(trap) => trap,
+        _ => return Error::Interpreter(error.to_string()),
+    };
+    if let Some(error) = trap.downcast_ref::<Error>() {
+        return error.clone();
+    }
+    let trap_code = match trap.trap_code() {
+        Some(TrapCode::UnreachableCodeReached) => casper_wasmi::TrapCode::Unreachable,
+        Some(TrapCode::MemoryOutOfBounds) => casper_wasmi::TrapCode::MemoryAccessOutOfBounds,
+        Some(TrapCode::TableOutOfBounds) => casper_wasmi::TrapCode::TableAccessOutOfBounds,
+        Some(TrapCode::IndirectCallToNull) => casper_wasmi::TrapCode::ElemUninitialized,
+        Some(TrapCode::IntegerDivisionByZero) => casper_wasmi::TrapCode::DivisionByZero,
+        Some(TrapCode::IntegerOverflow) => casper_wasmi::TrapCode::IntegerOverflow,
+        Some(TrapCode::BadConversionToInteger) => casper_wasmi::TrapCode::InvalidConversionToInt,
+        Some(TrapCode::StackOverflow) => casper_wasmi::TrapCode::StackOverflow,
+        Some(TrapCode::BadSignature) => casper_wasmi::TrapCode::UnexpectedSignature,
+        Some(TrapCode::OutOfFuel) | Some(TrapCode::GrowthOperationLimited) | None => {
+            return Error::Interpreter(error.to_string())
+        }
+    };
+    Error::Interpreter(casper_wasmi::Error::Trap(casper_wasmi::Trap::Code(trap_code)).into())
+}
+
+impl From<&Value> for WasmValue {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::I32(value) => WasmValue::I32(*value),
+            Value::I64(value) => WasmValue::I64(*value),
+            Value::F32(value) => WasmValue::F32(value.to_bits()),
+            Value::F64(value) => WasmValue::F64(value.to_bits()),
+            // Reference types are disabled, so host functions never take references.
+            Value::FuncRef(_) | Value::ExternRef(_) => unreachable!("reference types are disabled"),
+        }
+    }
+}
+
+impl From<WasmValue> for Value {
+    fn from(value: WasmValue) -> Self {
+        match value {
+            WasmValue::I32(value) => Value::I32(value),
+            WasmValue::I64(value) => Value::I64(value),
+            WasmValue::F32(bits) => Value::F32(F32::from_bits(bits)),
+            WasmValue::F64(bits) => Value::F64(F64::from_bits(bits)),
+        }
+    }
+}
+
+struct UpstreamWasmiMemory {
+    store: Rc<RefCell<Store<HostData>>>,
+    memory: Memory,
+    host_call_memory: Rc<Cell<Option<NonNull<[u8]>>>>,
+}
+
+impl UpstreamWasmiMemory {
+    /// Calls `func` with the contents of the memory.
+    fn with_contents<R>(&self, func: impl FnOnce(&mut [u8]) -> R) -> R {
+        match self.host_call_memory.get() {
+            // SAFETY: the pointer is only set by `call_host` while the host function it calls has
+            // exclusive access to the memory, which can't grow during the call.
+            Some(mut contents) => func(unsafe { contents.as_mut() }),
+            None => func(self.memory.data_mut(&mut *self.store.borrow_mut())),
+        }
+    }
+}
+
+impl WasmMemory for UpstreamWasmiMemory {
+    fn with_slice(
+        &self,
+        offset: usize,
+        size: usize,
+        func: &mut dyn FnMut(&[u8]),
+    ) -> Result<(), String> {
+        self.with_contents(|contents| {
+            let region = memory_region(offset, size, contents.len())?;
+            func(&contents[region]);
+            Ok(())
+        })
+    }
+
+    fn set(&self, offset: u32, bytes: &[u8]) -> Result<(), String> {
+        self.with_contents(|contents| {
+            let region = memory_region(offset as usize, bytes.len(), contents.len())?;
+            contents[region].copy_from_slice(bytes);
+            Ok(())
+        })
+    }
+}
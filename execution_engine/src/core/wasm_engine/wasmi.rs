@@ -0,0 +1,166 @@
+//! The [`WasmEngine`] backed by the `casper-wasmi` interpreter.
+use casper_wasm::elements::Module;
+use casper_wasmi::{
+    nan_preserving_float::{F32, F64},
+    Externals, ImportsBuilder, MemoryRef, ModuleInstance, ModuleRef, RuntimeArgs, RuntimeValue,
+    Trap,
+};
+
+use casper_types::ProtocolVersion;
+
+use super::{
+    memory_region, CompiledModule, HostArgs, InstanceAndMemory, WasmEngine, WasmHost, WasmInstance,
+    WasmMemory, WasmValue,
+};
+use crate::core::{
+    engine_state::EngineConfig,
+    execution::Error,
+    resolvers::{self, memory_resolver::MemoryResolver},
+};
+
+/// The `casper-wasmi` interpreter.
+pub(super) struct WasmiEngine;
+
+impl WasmEngine for WasmiEngine {
+    fn compile(&self, module: Module) -> Result<Box<dyn CompiledModule>, Error> {
+        let module = casper_wasmi::Module::from_casper_wasm_module(module)?;
+        Ok(Box::new(WasmiModule(module)))
+    }
+}
+
+struct WasmiModule(casper_wasmi::Module);
+
+impl CompiledModule for WasmiModule {
+    fn instantiate(
+        &self,
+        protocol_version: ProtocolVersion,
+        engine_config: &EngineConfig,
+    ) -> Result<InstanceAndMemory, Error> {
+        // This ensures that a memory instance is properly resolved into a pre-allocated memory
+        // area, and a host function resolver is attached to the module.
+        let resolver = resolvers::create_module_resolver(protocol_version, engine_config)?;
+        let mut imports = ImportsBuilder::new();
+        imports.push_resolver("env", &resolver);
+        let not_started_module = ModuleInstance::new(&self.0, &imports)?;
+        // We currently don't support running a "start" section.
+        if not_started_module.has_start() {
+            return Err(Error::UnsupportedWasmStart);
+        }
+        let instance = WasmiInstance {
+            instance: not_started_module.not_started_instance().clone(),
+            max_stack_height: engine_config.wasm_config().max_stack_height,
+        };
+        let memory = WasmiMemory(resolver.memory_ref()?);
+        Ok((Box::new(instance), Box::new(memory)))
+    }
+}
+
+struct WasmiInstance {
+    instance: ModuleRef,
+    #[cfg_attr(not(feature = "test-support"), allow(dead_code))]
+    max_stack_height: u32,
+}
+
+impl WasmInstance for WasmiInstance {
+    fn invoke_export(&self, name: &str, host: &mut dyn WasmHost) -> Result<(), Error> {
+        let error = match self
+            .instance
+            .invoke_export(name, &[], &mut WasmiExternals(host))
+        {
+            Ok(_) => return Ok(()),
+            Err(error) => error,
+        };
+
+        #[cfg(feature = "test-support")]
+        self.dump_runtime_stack_info();
+
+        if let Some(host_error) = error.as_host_error() {
+            return match host_error.downcast_ref::<Error>() {
+                Some(error) => Err(error.clone()),
+                None => Err(Error::Interpreter(host_error.to_string())),
+            };
+        }
+        Err(Error::Interpreter(error.into()))
+    }
+}
+
+impl WasmiInstance {
+    #[cfg(feature = "test-support")]
+    fn dump_runtime_stack_info(&self) {
+        let globals = self.instance.globals();
+        let Some(current_runtime_call_stack_height) = globals.last() else {
+            return;
+        };
+
+        if let RuntimeValue::I32(current_runtime_call_stack_height) =
+            current_runtime_call_stack_height.get()
+        {
+            if current_runtime_call_stack_height > self.max_stack_height as i32 {
+                eprintln!(
+                    "runtime stack overflow, current={current_runtime_call_stack_height}, max={}",
+                    self.max_stack_height
+                );
+            }
+        };
+    }
+}
+
+/// Adapts a [`WasmHost`] to the host function interface of `casper-wasmi`.
+struct WasmiExternals<'a>(&'a mut dyn WasmHost);
+
+impl<'a> Externals for WasmiExternals<'a> {
+    fn invoke_index(
+        &mut self,
+        index: usize,
+        args: RuntimeArgs,
+    ) -> Result<Option<RuntimeValue>, Trap> {
+        let args = HostArgs::new(args.as_ref().iter().copied().map(WasmValue::from))?;
+        self.0
+            .invoke_index(index, &args)
+            .map(|value| value.map(RuntimeValue::from))
+            .map_err(Trap::from)
+    }
+}
+
+impl From<RuntimeValue> for WasmValue {
+    fn from(value: RuntimeValue) -> Self {
+        match value {
+            RuntimeValue::I32(value) => WasmValue::I32(value),
+            RuntimeValue::I64(value) => WasmValue::I64(value),
+            RuntimeValue::F32(value) => WasmValue::F32(value.to_bits()),
+            RuntimeValue::F64(value) => WasmValue::F64(value.to_bits()),
+        }
+    }
+}
+
+impl From<WasmValue> for RuntimeValue {
+    fn from(value: WasmValue) -> Self {
+        match value {
+            WasmValue::I32(value) => RuntimeValue::I32(value),
+            WasmValue::I64(value) => RuntimeValue::I64(value),
+            WasmValue::F32(bits) => RuntimeValue::F32(F32::from_bits(bits)),
+            WasmValue::F64(bits) => RuntimeValue::F64(F64::from_bits(bits)),
+        }
+    }
+}
+
+struct WasmiMemory(MemoryRef);
+
+impl WasmMemory for WasmiMemory {
+    fn with_slice(
+        &self,
+        offset: usize,
+        size: usize,
+        func: &mut dyn FnMut(&[u8]),
+    ) -> Result<(), String> {
+        self.0.with_direct_access(|buffer| {
+            let region = memory_region(offset, size, buffer.len())?;
+            func(&buffer[region]);
+            Ok(())
+        })
+    }
+
+    fn set(&self, offset: u32, bytes: &[u8]) -> Result<(), String> {
+        self.0.set(offset, bytes).map_err(Into::into)
+    }
+}
@@ -78,11 +78,20 @@ const DEFAULT_DICTIONARY_PUT_KEY_BYTES_SIZE_WEIGHT: u32 = 1_800;
 const DEFAULT_DICTIONARY_PUT_VALUE_SIZE_WEIGHT: u32 = 520;
 const DEFAULT_BLAKE2B_COST: u32 = 1_200_000;
 
+const DEFAULT_EMIT_EVENT_COST: u32 = 14_000;
+const DEFAULT_EMIT_EVENT_TOPIC_SIZE_WEIGHT: u32 = 980;
+const DEFAULT_EMIT_EVENT_VALUE_SIZE_WEIGHT: u32 = 980;
+
+const DEFAULT_VERIFY_SIGNATURE_COST: u32 = 1_300_000;
+const DEFAULT_SHA256_COST: u32 = 1_200_000;
+const DEFAULT_KECCAK256_COST: u32 = 1_200_000;
+const DEFAULT_APPROVE_ALLOWANCE_COST: u32 = 82_000_000;
+
 /// Representation of a host function cost.
 ///
 /// The total gas cost is equal to `cost` + sum of each argument weight multiplied by the byte size
 /// of the data.
-#[derive(Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Debug, DataSize)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize, Debug, DataSize)]
 #[serde(deny_unknown_fields)]
 pub struct HostFunction<T> {
     /// How much the user is charged for calling the host function.
@@ -194,7 +203,7 @@ where
 }
 
 /// Definition of a host function cost table.
-#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug, DataSize)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug, DataSize)]
 #[serde(deny_unknown_fields)]
 pub struct HostFunctionCosts {
     /// Cost of calling the `read_value` host function.
@@ -287,6 +296,16 @@ pub struct HostFunctionCosts {
     pub random_bytes: HostFunction<[Cost; 2]>,
     /// Cost of calling the `enable_contract_version` host function.
     pub enable_contract_version: HostFunction<[Cost; 4]>,
+    /// Cost of calling the `emit_event` host function.
+    pub emit_event: HostFunction<[Cost; 4]>,
+    /// Cost of calling the `verify_signature` host function.
+    pub verify_signature: HostFunction<[Cost; 6]>,
+    /// Cost of calling the `sha256` host function.
+    pub sha256: HostFunction<[Cost; 4]>,
+    /// Cost of calling the `keccak256` host function.
+    pub keccak256: HostFunction<[Cost; 4]>,
+    /// Cost of calling the `approve_allowance` host function.
+    pub approve_allowance: HostFunction<[Cost; 8]>,
 }
 
 impl Default for HostFunctionCosts {
@@ -452,6 +471,35 @@ impl Default for HostFunctionCosts {
             ),
             random_bytes: HostFunction::default(),
             enable_contract_version: HostFunction::default(),
+            emit_event: HostFunction::new(
+                DEFAULT_EMIT_EVENT_COST,
+                [
+                    NOT_USED,
+                    DEFAULT_EMIT_EVENT_TOPIC_SIZE_WEIGHT,
+                    NOT_USED,
+                    DEFAULT_EMIT_EVENT_VALUE_SIZE_WEIGHT,
+                ],
+            ),
+            verify_signature: HostFunction::new(
+                DEFAULT_VERIFY_SIGNATURE_COST,
+                [
+                    NOT_USED,
+                    DEFAULT_ARG_CHARGE,
+                    NOT_USED,
+                    NOT_USED,
+                    NOT_USED,
+                    NOT_USED,
+                ],
+            ),
+            sha256: HostFunction::new(
+                DEFAULT_SHA256_COST,
+                [NOT_USED, DEFAULT_ARG_CHARGE, NOT_USED, NOT_USED],
+            ),
+            keccak256: HostFunction::new(
+                DEFAULT_KECCAK256_COST,
+                [NOT_USED, DEFAULT_ARG_CHARGE, NOT_USED, NOT_USED],
+            ),
+            approve_allowance: HostFunction::fixed(DEFAULT_APPROVE_ALLOWANCE_COST),
         }
     }
 }
@@ -503,6 +551,11 @@ impl ToBytes for HostFunctionCosts {
         ret.append(&mut self.blake2b.to_bytes()?);
         ret.append(&mut self.random_bytes.to_bytes()?);
         ret.append(&mut self.enable_contract_version.to_bytes()?);
+        ret.append(&mut self.emit_event.to_bytes()?);
+        ret.append(&mut self.verify_signature.to_bytes()?);
+        ret.append(&mut self.sha256.to_bytes()?);
+        ret.append(&mut self.keccak256.to_bytes()?);
+        ret.append(&mut self.approve_allowance.to_bytes()?);
         Ok(ret)
     }
 
@@ -551,6 +604,11 @@ impl ToBytes for HostFunctionCosts {
             + self.blake2b.serialized_length()
             + self.random_bytes.serialized_length()
             + self.enable_contract_version.serialized_length()
+            + self.emit_event.serialized_length()
+            + self.verify_signature.serialized_length()
+            + self.sha256.serialized_length()
+            + self.keccak256.serialized_length()
+            + self.approve_allowance.serialized_length()
     }
 }
 
@@ -600,6 +658,11 @@ impl FromBytes for HostFunctionCosts {
         let (blake2b, rem) = FromBytes::from_bytes(rem)?;
         let (random_bytes, rem) = FromBytes::from_bytes(rem)?;
         let (enable_contract_version, rem) = FromBytes::from_bytes(rem)?;
+        let (emit_event, rem) = FromBytes::from_bytes(rem)?;
+        let (verify_signature, rem) = FromBytes::from_bytes(rem)?;
+        let (sha256, rem) = FromBytes::from_bytes(rem)?;
+        let (keccak256, rem) = FromBytes::from_bytes(rem)?;
+        let (approve_allowance, rem) = FromBytes::from_bytes(rem)?;
         Ok((
             HostFunctionCosts {
                 read_value,
@@ -646,6 +709,11 @@ impl FromBytes for HostFunctionCosts {
                 blake2b,
                 random_bytes,
                 enable_contract_version,
+                emit_event,
+                verify_signature,
+                sha256,
+                keccak256,
+                approve_allowance,
             },
             rem,
         ))
@@ -699,6 +767,11 @@ impl Distribution<HostFunctionCosts> for Standard {
             blake2b: rng.gen(),
             random_bytes: rng.gen(),
             enable_contract_version: rng.gen(),
+            emit_event: rng.gen(),
+            verify_signature: rng.gen(),
+            sha256: rng.gen(),
+            keccak256: rng.gen(),
+            approve_allowance: rng.gen(),
         }
     }
 }
@@ -760,6 +833,11 @@ pub mod gens {
             blake2b in host_function_cost_arb(),
             random_bytes in host_function_cost_arb(),
             enable_contract_version in host_function_cost_arb(),
+            emit_event in host_function_cost_arb(),
+            verify_signature in host_function_cost_arb(),
+            sha256 in host_function_cost_arb(),
+            keccak256 in host_function_cost_arb(),
+            approve_allowance in host_function_cost_arb(),
         ) -> HostFunctionCosts {
             HostFunctionCosts {
                 read_value,
@@ -806,6 +884,11 @@ pub mod gens {
                 blake2b,
                 random_bytes,
                 enable_contract_version,
+                emit_event,
+                verify_signature,
+                sha256,
+                keccak256,
+                approve_allowance,
             }
         }
     }
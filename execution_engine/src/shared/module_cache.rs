@@ -0,0 +1,321 @@
+//! A bounded cache of Wasm modules which are ready to be instantiated.
+use std::{
+    fmt::{self, Debug, Formatter},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+};
+
+use casper_wasm::elements::Module;
+use linked_hash_map::LinkedHashMap;
+
+use casper_hashing::Digest;
+use casper_types::ContractWasmHash;
+
+use super::wasm_config::WasmConfig;
+use crate::core::{
+    execution,
+    wasm_engine::{CompiledModule, WasmEngineKind},
+};
+
+/// The default maximum number of modules held by a [`ModuleCache`].
+pub const DEFAULT_MODULE_CACHE_CAPACITY: usize = 256;
+
+type Entries = LinkedHashMap<(ContractWasmHash, WasmConfig, WasmEngineKind), Arc<PreparedModule>>;
+
+/// A stored contract's Wasm module, deserialized and compiled.
+pub struct PreparedModule {
+    /// Digest of the serialized module this was prepared from.
+    digest: Digest,
+    module: Arc<Module>,
+    compiled: Box<dyn CompiledModule>,
+}
+
+impl PreparedModule {
+    /// Deserializes the module held in `wasm_bytes` and compiles it with `wasm_engine`.
+    pub fn new(wasm_bytes: &[u8], wasm_engine: WasmEngineKind) -> Result<Self, execution::Error> {
+        let module: Module = casper_wasm::deserialize_buffer(wasm_bytes)?;
+        let compiled = wasm_engine.engine().compile(module.clone())?;
+        Ok(PreparedModule {
+            digest: Digest::hash(wasm_bytes),
+            module: Arc::new(module),
+            compiled,
+        })
+    }
+
+    /// Returns the deserialized module.
+    pub fn module(&self) -> &Arc<Module> {
+        &self.module
+    }
+
+    /// Returns the compiled module, ready to be instantiated.
+    pub fn compiled(&self) -> &dyn CompiledModule {
+        &*self.compiled
+    }
+}
+
+/// Counters describing the effectiveness of a [`ModuleCache`].
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub struct ModuleCacheStats {
+    /// Number of lookups served from the cache.
+    pub hits: u64,
+    /// Number of lookups which required the module to be prepared.
+    pub misses: u64,
+    /// Number of modules currently held.
+    pub entries: usize,
+}
+
+/// A least-recently-used cache of [`PreparedModule`]s keyed by the hash of the contract Wasm, the
+/// [`WasmConfig`] in force when it was prepared and the [`WasmEngineKind`] which compiled it.
+///
+/// Lookups also compare a digest of the Wasm bytes, so an entry is never served for a contract
+/// Wasm hash whose bytes differ from those the entry was prepared from.
+pub struct ModuleCache {
+    capacity: usize,
+    entries: Mutex<Entries>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ModuleCache {
+    /// Creates an empty cache holding at most `capacity` modules.
+    pub fn new(capacity: usize) -> Self {
+        ModuleCache {
+            capacity,
+            entries: Mutex::new(LinkedHashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the cached module for `wasm_hash` under `wasm_config` compiled by `wasm_engine`, or
+    /// prepares it from `wasm_bytes` and caches it, evicting the least recently used module if full.
+    pub fn get_or_prepare(
+        &self,
+        wasm_hash: ContractWasmHash,
+        wasm_config: WasmConfig,
+        wasm_engine: WasmEngineKind,
+        wasm_bytes: &[u8],
+    ) -> Result<Arc<PreparedModule>, execution::Error> {
+        let key = (wasm_hash, wasm_config, wasm_engine);
+        let digest = Digest::hash(wasm_bytes);
+
+        if let Some(prepared) = self.lock().get_refresh(&key) {
+            if prepared.digest == digest {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(Arc::clone(prepared));
+            }
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+
+        // Prepare outside the lock so that other executions aren't blocked on compilation.
+        let prepared = Arc::new(PreparedModule::new(wasm_bytes, wasm_engine)?);
+        if self.capacity > 0 {
+            let mut entries = self.lock();
+            entries.insert(key, Arc::clone(&prepared));
+            while entries.len() > self.capacity {
+                entries.pop_front();
+            }
+        }
+        Ok(prepared)
+    }
+
+    /// Returns the hit and miss counts since creation, and the current number of entries.
+    pub fn stats(&self) -> ModuleCacheStats {
+        ModuleCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.lock().len(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<Entries> {
+        // The map is never left in an inconsistent state, so a poisoned lock is safe to reuse.
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Debug for ModuleCache {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("ModuleCache")
+            .field("capacity", &self.capacity)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+impl Default for ModuleCache {
+    fn default() -> Self {
+        ModuleCache::new(DEFAULT_MODULE_CACHE_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_wasm::{
+        builder,
+        elements::{Instructions, Module},
+    };
+
+    use super::*;
+
+    fn wasm_bytes(function_count: usize) -> Vec<u8> {
+        let mut module_builder = builder::module();
+        for _ in 0..function_count {
+            module_builder = module_builder
+                .function()
+                .signature()
+                .build()
+                .body()
+                .with_instructions(Instructions::empty())
+                .build()
+                .build();
+        }
+        let module: Module = module_builder.build();
+        casper_wasm::serialize(module).expect("should serialize")
+    }
+
+    fn wasm_hash(byte: u8) -> ContractWasmHash {
+        ContractWasmHash::new([byte; 32])
+    }
+
+    #[test]
+    fn should_hit_on_repeated_lookup() {
+        let cache = ModuleCache::new(2);
+        let bytes = wasm_bytes(1);
+        let first = cache
+            .get_or_prepare(
+                wasm_hash(1),
+                WasmConfig::default(),
+                WasmEngineKind::default(),
+                &bytes,
+            )
+            .unwrap();
+        let second = cache
+            .get_or_prepare(
+                wasm_hash(1),
+                WasmConfig::default(),
+                WasmEngineKind::default(),
+                &bytes,
+            )
+            .unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(
+            cache.stats(),
+            ModuleCacheStats {
+                hits: 1,
+                misses: 1,
+                entries: 1
+            }
+        );
+    }
+
+    #[test]
+    fn should_miss_on_different_wasm_config() {
+        let cache = ModuleCache::new(2);
+        let bytes = wasm_bytes(1);
+        let mut other_config = WasmConfig::default();
+        other_config.max_memory += 1;
+        cache
+            .get_or_prepare(
+                wasm_hash(1),
+                WasmConfig::default(),
+                WasmEngineKind::default(),
+                &bytes,
+            )
+            .unwrap();
+        cache
+            .get_or_prepare(
+                wasm_hash(1),
+                other_config,
+                WasmEngineKind::default(),
+                &bytes,
+            )
+            .unwrap();
+        let stats = cache.stats();
+        assert_eq!(stats.misses, 2);
+        assert_eq!(stats.entries, 2);
+    }
+
+    #[test]
+    fn should_not_serve_entry_for_different_bytes() {
+        let cache = ModuleCache::new(2);
+        cache
+            .get_or_prepare(
+                wasm_hash(1),
+                WasmConfig::default(),
+                WasmEngineKind::default(),
+                &wasm_bytes(1),
+            )
+            .unwrap();
+        let prepared = cache
+            .get_or_prepare(
+                wasm_hash(1),
+                WasmConfig::default(),
+                WasmEngineKind::default(),
+                &wasm_bytes(2),
+            )
+            .unwrap();
+        assert_eq!(prepared.module().functions_space(), 2);
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 0);
+        assert_eq!(stats.misses, 2);
+        assert_eq!(stats.entries, 1);
+    }
+
+    #[test]
+    fn should_evict_least_recently_used() {
+        let cache = ModuleCache::new(2);
+        let bytes = wasm_bytes(1);
+        for byte in [1, 2, 1, 3] {
+            cache
+                .get_or_prepare(
+                    wasm_hash(byte),
+                    WasmConfig::default(),
+                    WasmEngineKind::default(),
+                    &bytes,
+                )
+                .unwrap();
+        }
+        assert_eq!(cache.stats().entries, 2);
+
+        // Module 1 was used more recently than module 2, so module 2 was evicted.
+        cache
+            .get_or_prepare(
+                wasm_hash(1),
+                WasmConfig::default(),
+                WasmEngineKind::default(),
+                &bytes,
+            )
+            .unwrap();
+        cache
+            .get_or_prepare(
+                wasm_hash(2),
+                WasmConfig::default(),
+                WasmEngineKind::default(),
+                &bytes,
+            )
+            .unwrap();
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 2);
+        assert_eq!(stats.misses, 4);
+    }
+
+    #[test]
+    fn should_not_cache_invalid_wasm() {
+        let cache = ModuleCache::new(2);
+        assert!(cache
+            .get_or_prepare(
+                wasm_hash(1),
+                WasmConfig::default(),
+                WasmEngineKind::default(),
+                &[0xff, 0x00]
+            )
+            .is_err());
+        assert_eq!(cache.stats().entries, 0);
+    }
+}
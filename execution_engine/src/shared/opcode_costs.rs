@@ -77,7 +77,7 @@ pub const DEFAULT_CONTROL_FLOW_BR_TABLE_MULTIPLIER: u32 = 100;
 /// cost + (len(br_table.targets) * size_multiplier)
 /// ```
 // This is done to encourage users to avoid writing code with very long `br_table`s.
-#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug, DataSize)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug, DataSize)]
 #[serde(deny_unknown_fields)]
 pub struct BrTableCost {
     /// Fixed cost charge for `br_table` opcode.
@@ -144,7 +144,7 @@ impl FromBytes for BrTableCost {
 }
 
 /// Definition of a cost table for a Wasm control flow opcodes.
-#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug, DataSize)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug, DataSize)]
 #[serde(deny_unknown_fields)]
 pub struct ControlFlowCosts {
     /// Cost for `block` opcode.
@@ -325,7 +325,7 @@ impl Distribution<ControlFlowCosts> for Standard {
 /// Definition of a cost table for Wasm opcodes.
 ///
 /// This is taken (partially) from parity-ethereum.
-#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug, DataSize)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug, DataSize)]
 #[serde(deny_unknown_fields)]
 pub struct OpcodeCosts {
     /// Bit operations multiplier.
@@ -575,6 +575,74 @@ impl Rules for OpcodeCosts {
     }
 }
 
+/// Names of the classes of opcode priced by [`OpcodeCosts`], indexed by the cost assigned to each
+/// class in [`OPCODE_CLASS_PROBE`].
+const OPCODE_CLASSES: [&str; 16] = [
+    "bit",
+    "add",
+    "mul",
+    "div",
+    "load",
+    "store",
+    "const",
+    "local",
+    "global",
+    "integer_comparison",
+    "conversion",
+    "unreachable",
+    "nop",
+    "current_memory",
+    "grow_memory",
+    "control_flow",
+];
+
+/// Opcode costs which price every opcode at the index of its class in [`OPCODE_CLASSES`], so that
+/// the class of an opcode is always derived from the same table as its cost.
+const OPCODE_CLASS_PROBE: OpcodeCosts = OpcodeCosts {
+    bit: 0,
+    add: 1,
+    mul: 2,
+    div: 3,
+    load: 4,
+    store: 5,
+    op_const: 6,
+    local: 7,
+    global: 8,
+    integer_comparison: 9,
+    conversion: 10,
+    unreachable: 11,
+    nop: 12,
+    current_memory: 13,
+    grow_memory: 14,
+    control_flow: ControlFlowCosts {
+        block: 15,
+        op_loop: 15,
+        op_if: 15,
+        op_else: 15,
+        end: 15,
+        br: 15,
+        br_if: 15,
+        op_return: 15,
+        call: 15,
+        call_indirect: 15,
+        drop: 15,
+        select: 15,
+        br_table: BrTableCost {
+            cost: 15,
+            size_multiplier: 0,
+        },
+    },
+};
+
+impl OpcodeCosts {
+    /// Returns the name of the class of opcode whose cost is charged for `instruction`, or `None`
+    /// if the instruction is unsupported.
+    pub(crate) fn instruction_class(instruction: &Instruction) -> Option<&'static str> {
+        let index = OPCODE_CLASS_PROBE.instruction_cost(instruction)?;
+        OPCODE_CLASSES.get(index as usize).copied()
+    }
+}
+
 impl Default for OpcodeCosts {
     fn default() -> Self {
         OpcodeCosts {
@@ -839,11 +907,37 @@ pub mod gens {
 
 #[cfg(test)]
 mod tests {
+    use casper_wasm::elements::{BrTableData, Instruction};
     use proptest::proptest;
 
     use casper_types::bytesrepr;
 
-    use super::gens;
+    use super::{gens, OpcodeCosts};
+
+    #[test]
+    fn should_classify_instructions() {
+        let br_table = Instruction::BrTable(Box::new(BrTableData {
+            table: vec![0; 3].into_boxed_slice(),
+            default: 0,
+        }));
+        assert_eq!(
+            OpcodeCosts::instruction_class(&Instruction::I32Add),
+            Some("add")
+        );
+        assert_eq!(
+            OpcodeCosts::instruction_class(&Instruction::GetLocal(0)),
+            Some("local")
+        );
+        assert_eq!(
+            OpcodeCosts::instruction_class(&br_table),
+            Some("control_flow")
+        );
+        assert_eq!(
+            OpcodeCosts::instruction_class(&Instruction::GrowMemory(0)),
+            Some("grow_memory")
+        );
+        assert_eq!(OpcodeCosts::instruction_class(&Instruction::F32Add), None);
+    }
 
     proptest! {
         #[test]
@@ -12,7 +12,7 @@ use casper_types::{
 pub const DEFAULT_GAS_PER_BYTE_COST: u32 = 1_117_587;
 
 /// Represents a cost table for storage costs.
-#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug, DataSize)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug, DataSize)]
 #[serde(deny_unknown_fields)]
 pub struct StorageCosts {
     /// Gas charged per byte stored in the global state.
@@ -32,6 +32,16 @@ pub const DEFAULT_WITHDRAW_VALIDATOR_REWARD_COST: u32 = 10_000;
 pub const DEFAULT_READ_ERA_ID_COST: u32 = 10_000;
 /// Default cost of the `activate_bid` auction entry point.
 pub const DEFAULT_ACTIVATE_BID_COST: u32 = 10_000;
+/// Default cost of the `add_reservations` auction entry point.
+pub const DEFAULT_ADD_RESERVATIONS_COST: u32 = 10_000;
+/// Default cost of the `cancel_reservations` auction entry point.
+pub const DEFAULT_CANCEL_RESERVATIONS_COST: u32 = 10_000;
+/// Default cost of the `unjail` auction entry point.
+pub const DEFAULT_UNJAIL_COST: u32 = 10_000;
+/// Default cost of the `transfer_delegation` auction entry point.
+pub const DEFAULT_TRANSFER_DELEGATION_COST: u32 = 10_000;
+/// Default cost of the `set_reward_destination` auction entry point.
+pub const DEFAULT_SET_REWARD_DESTINATION_COST: u32 = 10_000;
 
 /// Description of the costs of calling auction entrypoints.
 #[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug, DataSize)]
@@ -65,6 +75,16 @@ pub struct AuctionCosts {
     pub activate_bid: u32,
     /// Cost of calling the `redelegate` entry point.
     pub redelegate: u32,
+    /// Cost of calling the `add_reservations` entry point.
+    pub add_reservations: u32,
+    /// Cost of calling the `cancel_reservations` entry point.
+    pub cancel_reservations: u32,
+    /// Cost of calling the `unjail` entry point.
+    pub unjail: u32,
+    /// Cost of calling the `transfer_delegation` entry point.
+    pub transfer_delegation: u32,
+    /// Cost of calling the `set_reward_destination` entry point.
+    pub set_reward_destination: u32,
 }
 
 impl Default for AuctionCosts {
@@ -84,6 +104,11 @@ impl Default for AuctionCosts {
             read_era_id: DEFAULT_READ_ERA_ID_COST,
             activate_bid: DEFAULT_ACTIVATE_BID_COST,
             redelegate: DEFAULT_REDELEGATE_COST,
+            add_reservations: DEFAULT_ADD_RESERVATIONS_COST,
+            cancel_reservations: DEFAULT_CANCEL_RESERVATIONS_COST,
+            unjail: DEFAULT_UNJAIL_COST,
+            transfer_delegation: DEFAULT_TRANSFER_DELEGATION_COST,
+            set_reward_destination: DEFAULT_SET_REWARD_DESTINATION_COST,
         }
     }
 }
@@ -107,6 +132,11 @@ impl ToBytes for AuctionCosts {
             read_era_id,
             activate_bid,
             redelegate,
+            add_reservations,
+            cancel_reservations,
+            unjail,
+            transfer_delegation,
+            set_reward_destination,
         } = self;
 
         ret.append(&mut get_era_validators.to_bytes()?);
@@ -123,6 +153,11 @@ impl ToBytes for AuctionCosts {
         ret.append(&mut read_era_id.to_bytes()?);
         ret.append(&mut activate_bid.to_bytes()?);
         ret.append(&mut redelegate.to_bytes()?);
+        ret.append(&mut add_reservations.to_bytes()?);
+        ret.append(&mut cancel_reservations.to_bytes()?);
+        ret.append(&mut unjail.to_bytes()?);
+        ret.append(&mut transfer_delegation.to_bytes()?);
+        ret.append(&mut set_reward_destination.to_bytes()?);
 
         Ok(ret)
     }
@@ -143,6 +178,11 @@ impl ToBytes for AuctionCosts {
             read_era_id,
             activate_bid,
             redelegate,
+            add_reservations,
+            cancel_reservations,
+            unjail,
+            transfer_delegation,
+            set_reward_destination,
         } = self;
 
         get_era_validators.serialized_length()
@@ -159,6 +199,11 @@ impl ToBytes for AuctionCosts {
             + read_era_id.serialized_length()
             + activate_bid.serialized_length()
             + redelegate.serialized_length()
+            + add_reservations.serialized_length()
+            + cancel_reservations.serialized_length()
+            + unjail.serialized_length()
+            + transfer_delegation.serialized_length()
+            + set_reward_destination.serialized_length()
     }
 }
 
@@ -178,6 +223,11 @@ impl FromBytes for AuctionCosts {
         let (read_era_id, rem) = FromBytes::from_bytes(rem)?;
         let (activate_bid, rem) = FromBytes::from_bytes(rem)?;
         let (redelegate, rem) = FromBytes::from_bytes(rem)?;
+        let (add_reservations, rem) = FromBytes::from_bytes(rem)?;
+        let (cancel_reservations, rem) = FromBytes::from_bytes(rem)?;
+        let (unjail, rem) = FromBytes::from_bytes(rem)?;
+        let (transfer_delegation, rem) = FromBytes::from_bytes(rem)?;
+        let (set_reward_destination, rem) = FromBytes::from_bytes(rem)?;
         Ok((
             Self {
                 get_era_validators,
@@ -194,6 +244,11 @@ impl FromBytes for AuctionCosts {
                 read_era_id,
                 activate_bid,
                 redelegate,
+                add_reservations,
+                cancel_reservations,
+                unjail,
+                transfer_delegation,
+                set_reward_destination,
             },
             rem,
         ))
@@ -217,6 +272,11 @@ impl Distribution<AuctionCosts> for Standard {
             read_era_id: rng.gen(),
             activate_bid: rng.gen(),
             redelegate: rng.gen(),
+            add_reservations: rng.gen(),
+            cancel_reservations: rng.gen(),
+            unjail: rng.gen(),
+            transfer_delegation: rng.gen(),
+            set_reward_destination: rng.gen(),
         }
     }
 }
@@ -244,6 +304,11 @@ pub mod gens {
             read_era_id in num::u32::ANY,
             activate_bid in num::u32::ANY,
             redelegate in num::u32::ANY,
+            add_reservations in num::u32::ANY,
+            cancel_reservations in num::u32::ANY,
+            unjail in num::u32::ANY,
+            transfer_delegation in num::u32::ANY,
+            set_reward_destination in num::u32::ANY,
         ) -> AuctionCosts {
             AuctionCosts {
                 get_era_validators,
@@ -260,6 +325,11 @@ pub mod gens {
                 read_era_id,
                 activate_bid,
                 redelegate,
+                add_reservations,
+                cancel_reservations,
+                unjail,
+                transfer_delegation,
+                set_reward_destination,
             }
         }
     }
@@ -12,6 +12,8 @@ pub const DEFAULT_SET_REFUND_PURSE_COST: u32 = 10_000;
 pub const DEFAULT_GET_REFUND_PURSE_COST: u32 = 10_000;
 /// Default cost of the `finalize_payment` `handle_payment` entry point.
 pub const DEFAULT_FINALIZE_PAYMENT_COST: u32 = 10_000;
+/// Default cost of the `sponsor_payment` `handle_payment` entry point.
+pub const DEFAULT_SPONSOR_PAYMENT_COST: u32 = 10_000;
 
 /// Description of the costs of calling `handle_payment` entrypoints.
 #[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug, DataSize)]
@@ -25,6 +27,8 @@ pub struct HandlePaymentCosts {
     pub get_refund_purse: u32,
     /// Cost of calling the `finalize_payment` entry point.
     pub finalize_payment: u32,
+    /// Cost of calling the `sponsor_payment` entry point.
+    pub sponsor_payment: u32,
 }
 
 impl Default for HandlePaymentCosts {
@@ -34,6 +38,7 @@ impl Default for HandlePaymentCosts {
             set_refund_purse: DEFAULT_SET_REFUND_PURSE_COST,
             get_refund_purse: DEFAULT_GET_REFUND_PURSE_COST,
             finalize_payment: DEFAULT_FINALIZE_PAYMENT_COST,
+            sponsor_payment: DEFAULT_SPONSOR_PAYMENT_COST,
         }
     }
 }
@@ -46,6 +51,7 @@ impl ToBytes for HandlePaymentCosts {
         ret.append(&mut self.set_refund_purse.to_bytes()?);
         ret.append(&mut self.get_refund_purse.to_bytes()?);
         ret.append(&mut self.finalize_payment.to_bytes()?);
+        ret.append(&mut self.sponsor_payment.to_bytes()?);
 
         Ok(ret)
     }
@@ -55,6 +61,7 @@ impl ToBytes for HandlePaymentCosts {
             + self.set_refund_purse.serialized_length()
             + self.get_refund_purse.serialized_length()
             + self.finalize_payment.serialized_length()
+            + self.sponsor_payment.serialized_length()
     }
 }
 
@@ -64,6 +71,7 @@ impl FromBytes for HandlePaymentCosts {
         let (set_refund_purse, rem) = FromBytes::from_bytes(rem)?;
         let (get_refund_purse, rem) = FromBytes::from_bytes(rem)?;
         let (finalize_payment, rem) = FromBytes::from_bytes(rem)?;
+        let (sponsor_payment, rem) = FromBytes::from_bytes(rem)?;
 
         Ok((
             Self {
@@ -71,6 +79,7 @@ impl FromBytes for HandlePaymentCosts {
                 set_refund_purse,
                 get_refund_purse,
                 finalize_payment,
+                sponsor_payment,
             },
             rem,
         ))
@@ -84,6 +93,7 @@ impl Distribution<HandlePaymentCosts> for Standard {
             set_refund_purse: rng.gen(),
             get_refund_purse: rng.gen(),
             finalize_payment: rng.gen(),
+            sponsor_payment: rng.gen(),
         }
     }
 }
@@ -101,12 +111,14 @@ pub mod gens {
             set_refund_purse in num::u32::ANY,
             get_refund_purse in num::u32::ANY,
             finalize_payment in num::u32::ANY,
+            sponsor_payment in num::u32::ANY,
         ) -> HandlePaymentCosts {
             HandlePaymentCosts {
                 get_payment_purse,
                 set_refund_purse,
                 get_refund_purse,
                 finalize_payment,
+                sponsor_payment,
             }
         }
     }
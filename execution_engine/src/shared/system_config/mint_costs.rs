@@ -18,6 +18,16 @@ pub const DEFAULT_TRANSFER_COST: u32 = 10_000;
 pub const DEFAULT_READ_BASE_ROUND_REWARD_COST: u32 = 10_000;
 /// Default cost of the `mint_into_existing_purse` mint entry point.
 pub const DEFAULT_MINT_INTO_EXISTING_PURSE_COST: u32 = 2_500_000_000;
+/// Default cost of the `approve_allowance` mint entry point.
+pub const DEFAULT_APPROVE_ALLOWANCE_COST: u32 = 10_000;
+/// Default cost of the `transfer_from_allowance` mint entry point.
+pub const DEFAULT_TRANSFER_FROM_ALLOWANCE_COST: u32 = 10_000;
+/// Default cost of the `schedule_transfer` mint entry point.
+pub const DEFAULT_SCHEDULE_TRANSFER_COST: u32 = 2_500_000_000;
+/// Default cost of the `cancel_scheduled_transfer` mint entry point.
+pub const DEFAULT_CANCEL_SCHEDULED_TRANSFER_COST: u32 = 10_000;
+/// Default cost of the `run_scheduled_transfers` mint entry point.
+pub const DEFAULT_RUN_SCHEDULED_TRANSFERS_COST: u32 = 10_000;
 
 /// Description of the costs of calling mint entry points.
 #[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug, DataSize)]
@@ -37,6 +47,16 @@ pub struct MintCosts {
     pub read_base_round_reward: u32,
     /// Cost of calling the `mint_into_existing_purse` entry point.
     pub mint_into_existing_purse: u32,
+    /// Cost of calling the `approve_allowance` entry point.
+    pub approve_allowance: u32,
+    /// Cost of calling the `transfer_from_allowance` entry point.
+    pub transfer_from_allowance: u32,
+    /// Cost of calling the `schedule_transfer` entry point.
+    pub schedule_transfer: u32,
+    /// Cost of calling the `cancel_scheduled_transfer` entry point.
+    pub cancel_scheduled_transfer: u32,
+    /// Cost of calling the `run_scheduled_transfers` entry point.
+    pub run_scheduled_transfers: u32,
 }
 
 impl Default for MintCosts {
@@ -49,6 +69,11 @@ impl Default for MintCosts {
             transfer: DEFAULT_TRANSFER_COST,
             read_base_round_reward: DEFAULT_READ_BASE_ROUND_REWARD_COST,
             mint_into_existing_purse: DEFAULT_MINT_INTO_EXISTING_PURSE_COST,
+            approve_allowance: DEFAULT_APPROVE_ALLOWANCE_COST,
+            transfer_from_allowance: DEFAULT_TRANSFER_FROM_ALLOWANCE_COST,
+            schedule_transfer: DEFAULT_SCHEDULE_TRANSFER_COST,
+            cancel_scheduled_transfer: DEFAULT_CANCEL_SCHEDULED_TRANSFER_COST,
+            run_scheduled_transfers: DEFAULT_RUN_SCHEDULED_TRANSFERS_COST,
         }
     }
 }
@@ -65,6 +90,11 @@ impl ToBytes for MintCosts {
             transfer,
             read_base_round_reward,
             mint_into_existing_purse,
+            approve_allowance,
+            transfer_from_allowance,
+            schedule_transfer,
+            cancel_scheduled_transfer,
+            run_scheduled_transfers,
         } = self;
 
         ret.append(&mut mint.to_bytes()?);
@@ -74,6 +104,11 @@ impl ToBytes for MintCosts {
         ret.append(&mut transfer.to_bytes()?);
         ret.append(&mut read_base_round_reward.to_bytes()?);
         ret.append(&mut mint_into_existing_purse.to_bytes()?);
+        ret.append(&mut approve_allowance.to_bytes()?);
+        ret.append(&mut transfer_from_allowance.to_bytes()?);
+        ret.append(&mut schedule_transfer.to_bytes()?);
+        ret.append(&mut cancel_scheduled_transfer.to_bytes()?);
+        ret.append(&mut run_scheduled_transfers.to_bytes()?);
 
         Ok(ret)
     }
@@ -87,6 +122,11 @@ impl ToBytes for MintCosts {
             transfer,
             read_base_round_reward,
             mint_into_existing_purse,
+            approve_allowance,
+            transfer_from_allowance,
+            schedule_transfer,
+            cancel_scheduled_transfer,
+            run_scheduled_transfers,
         } = self;
 
         mint.serialized_length()
@@ -96,6 +136,11 @@ impl ToBytes for MintCosts {
             + transfer.serialized_length()
             + read_base_round_reward.serialized_length()
             + mint_into_existing_purse.serialized_length()
+            + approve_allowance.serialized_length()
+            + transfer_from_allowance.serialized_length()
+            + schedule_transfer.serialized_length()
+            + cancel_scheduled_transfer.serialized_length()
+            + run_scheduled_transfers.serialized_length()
     }
 }
 
@@ -108,6 +153,11 @@ impl FromBytes for MintCosts {
         let (transfer, rem) = FromBytes::from_bytes(rem)?;
         let (read_base_round_reward, rem) = FromBytes::from_bytes(rem)?;
         let (mint_into_existing_purse, rem) = FromBytes::from_bytes(rem)?;
+        let (approve_allowance, rem) = FromBytes::from_bytes(rem)?;
+        let (transfer_from_allowance, rem) = FromBytes::from_bytes(rem)?;
+        let (schedule_transfer, rem) = FromBytes::from_bytes(rem)?;
+        let (cancel_scheduled_transfer, rem) = FromBytes::from_bytes(rem)?;
+        let (run_scheduled_transfers, rem) = FromBytes::from_bytes(rem)?;
 
         Ok((
             Self {
@@ -118,6 +168,11 @@ impl FromBytes for MintCosts {
                 transfer,
                 read_base_round_reward,
                 mint_into_existing_purse,
+                approve_allowance,
+                transfer_from_allowance,
+                schedule_transfer,
+                cancel_scheduled_transfer,
+                run_scheduled_transfers,
             },
             rem,
         ))
@@ -134,6 +189,11 @@ impl Distribution<MintCosts> for Standard {
             transfer: rng.gen(),
             read_base_round_reward: rng.gen(),
             mint_into_existing_purse: rng.gen(),
+            approve_allowance: rng.gen(),
+            transfer_from_allowance: rng.gen(),
+            schedule_transfer: rng.gen(),
+            cancel_scheduled_transfer: rng.gen(),
+            run_scheduled_transfers: rng.gen(),
         }
     }
 }
@@ -154,6 +214,11 @@ pub mod gens {
             transfer in num::u32::ANY,
             read_base_round_reward in num::u32::ANY,
             mint_into_existing_purse in num::u32::ANY,
+            approve_allowance in num::u32::ANY,
+            transfer_from_allowance in num::u32::ANY,
+            schedule_transfer in num::u32::ANY,
+            cancel_scheduled_transfer in num::u32::ANY,
+            run_scheduled_transfers in num::u32::ANY,
         ) -> MintCosts {
             MintCosts {
                 mint,
@@ -163,6 +228,11 @@ pub mod gens {
                 transfer,
                 read_base_round_reward,
                 mint_into_existing_purse,
+                approve_allowance,
+                transfer_from_allowance,
+                schedule_transfer,
+                cancel_scheduled_transfer,
+                run_scheduled_transfers,
             }
         }
     }
@@ -3,6 +3,7 @@ use std::sync;
 use lmdb as lmdb_external;
 use thiserror::Error;
 
+use casper_hashing::Digest;
 use casper_types::bytesrepr;
 
 use crate::storage::{error::in_memory, global_state::CommitError};
@@ -26,6 +27,10 @@ pub enum Error {
     /// Error committing to execution engine.
     #[error(transparent)]
     CommitError(#[from] CommitError),
+
+    /// A trie node referenced by its parent is missing from the store.
+    #[error("Trie node not found: {0:?}")]
+    TrieNotFound(Digest),
 }
 
 impl casper_wasmi::HostError for Error {}
@@ -16,13 +16,16 @@ use crate::{
             Transaction, TransactionSource,
         },
         trie::{
-            merkle_proof::TrieMerkleProof, operations::create_hashed_empty_trie, Trie, TrieRaw,
+            merkle_proof::{TrieMerkleMultiProof, TrieMerkleProof},
+            operations::create_hashed_empty_trie,
+            Trie, TrieRaw,
         },
         trie_store::{
             in_memory::InMemoryTrieStore,
             operations::{
-                self, delete, keys_with_prefix, missing_children, put_trie, read, read_with_proof,
-                DeleteResult, ReadResult, WriteResult,
+                self, delete, diff, keys_with_prefix, missing_children, put_trie, read,
+                read_with_multi_proof, read_with_proof, DeleteResult, ReadResult, TrieDiff,
+                WriteResult,
             },
         },
     },
@@ -174,6 +177,33 @@ impl StateReader<Key, StoredValue> for InMemoryGlobalStateView {
         Ok(ret)
     }
 
+    fn read_with_multi_proof(
+        &self,
+        correlation_id: CorrelationId,
+        keys: &[Key],
+    ) -> Result<Option<TrieMerkleMultiProof<Key, StoredValue>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match read_with_multi_proof::<
+            Key,
+            StoredValue,
+            InMemoryReadTransaction,
+            InMemoryTrieStore,
+            Self::Error,
+        >(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            keys,
+        )? {
+            ReadResult::Found(value) => Some(value),
+            ReadResult::NotFound => None,
+            ReadResult::RootNotFound => panic!("InMemoryGlobalState has invalid root"),
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
+
     fn keys_with_prefix(
         &self,
         correlation_id: CorrelationId,
@@ -308,6 +338,28 @@ impl StateProvider for InMemoryGlobalState {
         txn.commit()?;
         Ok(DeleteResult::Deleted(root))
     }
+
+    fn diff(
+        &self,
+        correlation_id: CorrelationId,
+        old_root: Digest,
+        new_root: Digest,
+        visitor: &mut dyn FnMut(TrieDiff<Key, StoredValue>),
+    ) -> Result<(), Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let diff_iter = diff::<Key, StoredValue, InMemoryReadTransaction, InMemoryTrieStore>(
+            correlation_id,
+            &txn,
+            self.trie_store.deref(),
+            &old_root,
+            &new_root,
+        );
+        for result in diff_iter {
+            visitor(result?);
+        }
+        txn.commit()?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -14,13 +14,15 @@ use crate::{
         store::Store,
         transaction_source::{lmdb::LmdbEnvironment, Transaction, TransactionSource},
         trie::{
-            merkle_proof::TrieMerkleProof, operations::create_hashed_empty_trie, Trie, TrieRaw,
+            merkle_proof::{TrieMerkleMultiProof, TrieMerkleProof},
+            operations::create_hashed_empty_trie,
+            Trie, TrieRaw,
         },
         trie_store::{
             lmdb::{LmdbTrieStore, ScratchTrieStore},
             operations::{
-                delete, keys_with_prefix, missing_children, put_trie, read, read_with_proof,
-                DeleteResult, ReadResult,
+                delete, diff, keys_with_prefix, missing_children, put_trie, read,
+                read_with_multi_proof, read_with_proof, DeleteResult, ReadResult, TrieDiff,
             },
         },
     },
@@ -185,6 +187,33 @@ impl StateReader<Key, StoredValue> for LmdbGlobalStateView {
         Ok(ret)
     }
 
+    fn read_with_multi_proof(
+        &self,
+        correlation_id: CorrelationId,
+        keys: &[Key],
+    ) -> Result<Option<TrieMerkleMultiProof<Key, StoredValue>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match read_with_multi_proof::<
+            Key,
+            StoredValue,
+            lmdb::RoTransaction,
+            LmdbTrieStore,
+            Self::Error,
+        >(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            keys,
+        )? {
+            ReadResult::Found(value) => Some(value),
+            ReadResult::NotFound => None,
+            ReadResult::RootNotFound => panic!("LmdbGlobalState has invalid root"),
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
+
     fn keys_with_prefix(
         &self,
         correlation_id: CorrelationId,
@@ -325,6 +354,28 @@ impl StateProvider for LmdbGlobalState {
         scratch_trie_store.write_root_to_db(state_root_hash)?;
         Ok(DeleteResult::Deleted(state_root_hash))
     }
+
+    fn diff(
+        &self,
+        correlation_id: CorrelationId,
+        old_root: Digest,
+        new_root: Digest,
+        visitor: &mut dyn FnMut(TrieDiff<Key, StoredValue>),
+    ) -> Result<(), Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let diff_iter = diff::<Key, StoredValue, lmdb::RoTransaction, LmdbTrieStore>(
+            correlation_id,
+            &txn,
+            self.trie_store.deref(),
+            &old_root,
+            &new_root,
+        );
+        for result in diff_iter {
+            visitor(result?);
+        }
+        txn.commit()?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
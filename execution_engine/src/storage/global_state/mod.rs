@@ -6,6 +6,9 @@ pub mod in_memory;
 /// Lmdb implementation of global state.
 pub mod lmdb;
 
+/// Global state wrapper which records the keys read through it.
+pub mod read_recording;
+
 /// Lmdb implementation of global state with cache.
 pub mod scratch;
 
@@ -23,16 +26,19 @@ use crate::{
         transform::{self, Transform},
     },
     storage::{
-        transaction_source::{Transaction, TransactionSource},
-        trie::{merkle_proof::TrieMerkleProof, Trie, TrieRaw},
+        transaction_source::{Readable, Transaction, TransactionSource, Writable},
+        trie::{
+            merkle_proof::{TrieMerkleMultiProof, TrieMerkleProof},
+            Trie, TrieRaw,
+        },
         trie_store::{
-            operations::{read, write, ReadResult, WriteResult},
+            operations::{batch_write, read, ReadResult, WriteResult},
             TrieStore,
         },
     },
 };
 
-use super::trie_store::operations::DeleteResult;
+use super::trie_store::operations::{DeleteResult, TrieDiff};
 
 /// A trait expressing the reading of state. This trait is used to abstract the underlying store.
 pub trait StateReader<K, V> {
@@ -42,6 +48,16 @@ pub trait StateReader<K, V> {
     /// Returns the state value from the corresponding key
     fn read(&self, correlation_id: CorrelationId, key: &K) -> Result<Option<V>, Self::Error>;
 
+    /// Returns the state value from the corresponding key, read only to add to it or to check that
+    /// it exists, so that nothing depends on the value beyond its existence and type.
+    fn read_for_add(
+        &self,
+        correlation_id: CorrelationId,
+        key: &K,
+    ) -> Result<Option<V>, Self::Error> {
+        self.read(correlation_id, key)
+    }
+
     /// Returns the merkle proof of the state value from the corresponding key
     fn read_with_proof(
         &self,
@@ -49,6 +65,14 @@ pub trait StateReader<K, V> {
         key: &K,
     ) -> Result<Option<TrieMerkleProof<K, V>>, Self::Error>;
 
+    /// Returns a single merkle proof of the state values under all of the given keys, or `None` if
+    /// any of the keys is not present.
+    fn read_with_multi_proof(
+        &self,
+        correlation_id: CorrelationId,
+        keys: &[K],
+    ) -> Result<Option<TrieMerkleMultiProof<K, V>>, Self::Error>;
+
     /// Returns the keys in the trie matching `prefix`.
     fn keys_with_prefix(
         &self,
@@ -130,6 +154,16 @@ pub trait StateProvider {
         root: Digest,
         keys_to_delete: &[Key],
     ) -> Result<DeleteResult, Self::Error>;
+
+    /// Passes every difference between the tries under `old_root` and `new_root` to `visitor`, in
+    /// ascending order of serialized keys.
+    fn diff(
+        &self,
+        correlation_id: CorrelationId,
+        old_root: Digest,
+        new_root: Digest,
+        visitor: &mut dyn FnMut(TrieDiff<Key, StoredValue>),
+    ) -> Result<(), Self::Error>;
 }
 
 /// Write multiple key/stored value pairs to the store in a single rw transaction.
@@ -147,29 +181,51 @@ where
     E: From<R::Error> + From<S::Error> + From<bytesrepr::Error> + From<CommitError>,
 {
     let mut txn = environment.create_read_write_txn()?;
-    let mut state_root = prestate_hash;
-    let maybe_root: Option<Trie<Key, StoredValue>> = store.get(&txn, &state_root)?;
+    let maybe_root: Option<Trie<Key, StoredValue>> = store.get(&txn, &prestate_hash)?;
     if maybe_root.is_none() {
         return Err(CommitError::RootNotFound(prestate_hash).into());
     };
-    for (key, value) in stored_values.iter() {
-        let write_result =
-            write::<_, _, _, _, E>(correlation_id, &mut txn, store, &state_root, key, value)?;
-        match write_result {
-            WriteResult::Written(root_hash) => {
-                state_root = root_hash;
-            }
-            WriteResult::AlreadyExists => (),
-            WriteResult::RootNotFound => {
-                error!(?state_root, ?key, ?value, "Error writing new value");
-                return Err(CommitError::WriteRootNotFound(state_root).into());
-            }
-        }
-    }
+    let state_root = write_batch::<_, _, E>(
+        correlation_id,
+        &mut txn,
+        store,
+        prestate_hash,
+        stored_values.iter(),
+    )?;
     txn.commit()?;
     Ok(state_root)
 }
 
+/// Writes all `stored_values` under `prestate_hash` in a single batch, returning the new root.
+fn write_batch<'a, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &mut T,
+    store: &S,
+    prestate_hash: Digest,
+    stored_values: impl IntoIterator<Item = (&'a Key, &'a StoredValue)>,
+) -> Result<Digest, E>
+where
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<Key, StoredValue>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<bytesrepr::Error> + From<CommitError>,
+{
+    match batch_write::<_, _, _, _, E, _>(
+        correlation_id,
+        txn,
+        store,
+        &prestate_hash,
+        stored_values,
+    )? {
+        WriteResult::Written(root_hash) => Ok(root_hash),
+        WriteResult::AlreadyExists => Ok(prestate_hash),
+        WriteResult::RootNotFound => {
+            error!(?prestate_hash, "Error writing new values");
+            Err(CommitError::WriteRootNotFound(prestate_hash).into())
+        }
+    }
+}
+
 /// Commit `effects` to the store.
 pub fn commit<'a, R, S, H, E>(
     environment: &'a R,
@@ -186,7 +242,7 @@ where
     H: BuildHasher,
 {
     let mut txn = environment.create_read_write_txn()?;
-    let mut state_root = prestate_hash;
+    let state_root = prestate_hash;
 
     let maybe_root: Option<Trie<Key, StoredValue>> = store.get(&txn, &state_root)?;
 
@@ -194,6 +250,9 @@ where
         return Err(CommitError::RootNotFound(prestate_hash).into());
     };
 
+    // Every key occurs once in `effects`, so all values can be read under the prestate root and
+    // then written in a single batch.
+    let mut stored_values = Vec::new();
     for (key, transform) in effects.into_iter() {
         let read_result = read::<_, _, _, _, E>(correlation_id, &txn, store, &state_root, &key)?;
 
@@ -231,21 +290,17 @@ where
             }
         };
 
-        let write_result =
-            write::<_, _, _, _, E>(correlation_id, &mut txn, store, &state_root, &key, &value)?;
-
-        match write_result {
-            WriteResult::Written(root_hash) => {
-                state_root = root_hash;
-            }
-            WriteResult::AlreadyExists => (),
-            WriteResult::RootNotFound => {
-                error!(?state_root, ?key, ?value, "Error writing new value");
-                return Err(CommitError::WriteRootNotFound(state_root).into());
-            }
-        }
+        stored_values.push((key, value));
     }
 
+    let state_root = write_batch::<_, _, E>(
+        correlation_id,
+        &mut txn,
+        store,
+        state_root,
+        stored_values.iter().map(|(key, value)| (key, value)),
+    )?;
+
     txn.commit()?;
 
     Ok(state_root)
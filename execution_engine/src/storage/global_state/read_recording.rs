@@ -0,0 +1,425 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    mem,
+    rc::Rc,
+};
+
+use casper_hashing::Digest;
+use casper_types::{bytesrepr::ToBytes, Key, StoredValue};
+
+use crate::{
+    shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
+    storage::{
+        global_state::{CommitProvider, StateProvider, StateReader},
+        trie::{
+            merkle_proof::{TrieMerkleMultiProof, TrieMerkleProof},
+            TrieRaw,
+        },
+        trie_store::operations::{DeleteResult, TrieDiff},
+    },
+};
+
+/// The values and key prefixes read from global state by the tracking copies of an execution.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct ReadSet {
+    values: BTreeMap<Key, Option<StoredValue>>,
+    added_to: BTreeMap<Key, Option<StoredValue>>,
+    prefixes: BTreeSet<Vec<u8>>,
+}
+
+impl ReadSet {
+    /// Returns the values which were read, by key.
+    pub fn values(&self) -> &BTreeMap<Key, Option<StoredValue>> {
+        &self.values
+    }
+
+    /// Returns the values which were only read to add to them or to check that they exist, by key.
+    pub fn added_to(&self) -> &BTreeMap<Key, Option<StoredValue>> {
+        &self.added_to
+    }
+
+    /// Returns the serialized key prefixes under which all keys were listed.
+    pub fn prefixes(&self) -> &BTreeSet<Vec<u8>> {
+        &self.prefixes
+    }
+
+    /// Returns `true` if everything read is unchanged in the state read by `reader`, given that
+    /// only the values under `written_keys` can have changed since.
+    ///
+    /// Values only read to add to them need only still exist with the same type, as additions
+    /// commute.  Listing the keys under a prefix is treated as changed by any write under it.
+    pub fn is_current<R>(
+        &self,
+        correlation_id: CorrelationId,
+        reader: &R,
+        written_keys: &BTreeSet<Key>,
+    ) -> Result<bool, R::Error>
+    where
+        R: StateReader<Key, StoredValue>,
+    {
+        for key in written_keys {
+            if let Some(value) = self.values.get(key) {
+                if reader.read(correlation_id, key)? != *value {
+                    return Ok(false);
+                }
+            } else if let Some(value) = self.added_to.get(key) {
+                let current_value = reader.read(correlation_id, key)?;
+                let same_type = match (value, &current_value) {
+                    (Some(value), Some(current_value)) => have_same_type(value, current_value),
+                    (None, None) => true,
+                    _ => false,
+                };
+                if !same_type {
+                    return Ok(false);
+                }
+            }
+        }
+
+        if self.prefixes.is_empty() {
+            return Ok(true);
+        }
+        Ok(!written_keys.iter().any(|key| match key.to_bytes() {
+            Ok(key_bytes) => self
+                .prefixes
+                .iter()
+                .any(|prefix| key_bytes.starts_with(prefix)),
+            // Treat a key we can't serialize as conflicting, which is always safe.
+            Err(_) => true,
+        }))
+    }
+
+    fn record_value(&mut self, key: &Key, value: &Option<StoredValue>) {
+        let _ = self.added_to.remove(key);
+        let _ = self.values.entry(*key).or_insert_with(|| value.clone());
+    }
+
+    fn record_added_to(&mut self, key: &Key, value: &Option<StoredValue>) {
+        if !self.values.contains_key(key) {
+            let _ = self.added_to.entry(*key).or_insert_with(|| value.clone());
+        }
+    }
+}
+
+/// Returns `true` if adding to `left` and `right` is valid for the same transforms.
+fn have_same_type(left: &StoredValue, right: &StoredValue) -> bool {
+    match (left, right) {
+        (StoredValue::CLValue(left), StoredValue::CLValue(right)) => {
+            left.cl_type() == right.cl_type()
+        }
+        _ => mem::discriminant(left) == mem::discriminant(right),
+    }
+}
+
+/// A view of global state which records every value read through it into a shared [`ReadSet`].
+pub struct ReadRecordingView<R> {
+    reader: R,
+    read_set: Rc<RefCell<ReadSet>>,
+}
+
+impl<R> StateReader<Key, StoredValue> for ReadRecordingView<R>
+where
+    R: StateReader<Key, StoredValue>,
+{
+    type Error = R::Error;
+
+    fn read(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<StoredValue>, Self::Error> {
+        let value = self.reader.read(correlation_id, key)?;
+        self.read_set.borrow_mut().record_value(key, &value);
+        Ok(value)
+    }
+
+    fn read_for_add(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<StoredValue>, Self::Error> {
+        let value = self.reader.read_for_add(correlation_id, key)?;
+        self.read_set.borrow_mut().record_added_to(key, &value);
+        Ok(value)
+    }
+
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, StoredValue>>, Self::Error> {
+        let proof = self.reader.read_with_proof(correlation_id, key)?;
+        let value = proof.as_ref().map(|proof| proof.value().clone());
+        self.read_set.borrow_mut().record_value(key, &value);
+        Ok(proof)
+    }
+
+    fn read_with_multi_proof(
+        &self,
+        correlation_id: CorrelationId,
+        keys: &[Key],
+    ) -> Result<Option<TrieMerkleMultiProof<Key, StoredValue>>, Self::Error> {
+        // Without a proof, which of the keys are missing isn't known, so read them individually.
+        let multi_proof = self.reader.read_with_multi_proof(correlation_id, keys)?;
+        for key in keys {
+            let value = match &multi_proof {
+                Some(multi_proof) => multi_proof.value(key).cloned(),
+                None => self.reader.read(correlation_id, key)?,
+            };
+            self.read_set.borrow_mut().record_value(key, &value);
+        }
+        Ok(multi_proof)
+    }
+
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+    ) -> Result<Vec<Key>, Self::Error> {
+        self.read_set.borrow_mut().prefixes.insert(prefix.to_vec());
+        self.reader.keys_with_prefix(correlation_id, prefix)
+    }
+}
+
+/// Global state which delegates to an underlying state, recording the keys read through every view
+/// checked out from it.
+pub struct ReadRecordingState<'a, S> {
+    state: &'a S,
+    read_set: Rc<RefCell<ReadSet>>,
+}
+
+impl<'a, S> ReadRecordingState<'a, S> {
+    /// Creates a state delegating to `state` which records reads into `read_set`.
+    pub fn new(state: &'a S, read_set: Rc<RefCell<ReadSet>>) -> Self {
+        ReadRecordingState { state, read_set }
+    }
+}
+
+impl<'a, S> StateProvider for ReadRecordingState<'a, S>
+where
+    S: StateProvider,
+{
+    type Error = S::Error;
+
+    type Reader = ReadRecordingView<S::Reader>;
+
+    fn checkout(&self, state_hash: Digest) -> Result<Option<Self::Reader>, Self::Error> {
+        Ok(self
+            .state
+            .checkout(state_hash)?
+            .map(|reader| ReadRecordingView {
+                reader,
+                read_set: Rc::clone(&self.read_set),
+            }))
+    }
+
+    fn empty_root(&self) -> Digest {
+        self.state.empty_root()
+    }
+
+    fn get_trie_full(
+        &self,
+        correlation_id: CorrelationId,
+        trie_key: &Digest,
+    ) -> Result<Option<TrieRaw>, Self::Error> {
+        self.state.get_trie_full(correlation_id, trie_key)
+    }
+
+    fn put_trie(&self, correlation_id: CorrelationId, trie: &[u8]) -> Result<Digest, Self::Error> {
+        self.state.put_trie(correlation_id, trie)
+    }
+
+    fn missing_children(
+        &self,
+        correlation_id: CorrelationId,
+        trie_raw: &[u8],
+    ) -> Result<Vec<Digest>, Self::Error> {
+        self.state.missing_children(correlation_id, trie_raw)
+    }
+
+    fn delete_keys(
+        &self,
+        correlation_id: CorrelationId,
+        root: Digest,
+        keys_to_delete: &[Key],
+    ) -> Result<DeleteResult, Self::Error> {
+        self.state.delete_keys(correlation_id, root, keys_to_delete)
+    }
+
+    fn diff(
+        &self,
+        correlation_id: CorrelationId,
+        old_root: Digest,
+        new_root: Digest,
+        visitor: &mut dyn FnMut(TrieDiff<Key, StoredValue>),
+    ) -> Result<(), Self::Error> {
+        self.state.diff(correlation_id, old_root, new_root, visitor)
+    }
+}
+
+impl<'a, S> CommitProvider for ReadRecordingState<'a, S>
+where
+    S: CommitProvider,
+{
+    fn commit(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Digest,
+        effects: AdditiveMap<Key, Transform>,
+    ) -> Result<Digest, Self::Error> {
+        self.state.commit(correlation_id, state_hash, effects)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{account::AccountHash, AccessRights, CLValue, KeyTag, URef, U512};
+
+    use super::*;
+    use crate::{
+        core::tracking_copy::TrackingCopy, storage::global_state::in_memory::InMemoryGlobalState,
+    };
+
+    fn cl_value<T: casper_types::CLTyped + ToBytes>(value: T) -> StoredValue {
+        StoredValue::CLValue(CLValue::from_t(value).unwrap())
+    }
+
+    #[test]
+    fn should_record_reads_through_checked_out_views() {
+        let account_key = Key::Account(AccountHash::new([1; 32]));
+        let uref_key = Key::URef(URef::default());
+        let value = cl_value(1_u64);
+        let (state, root_hash) =
+            InMemoryGlobalState::from_pairs(CorrelationId::new(), &[(uref_key, value.clone())])
+                .unwrap();
+
+        let read_set = Rc::new(RefCell::new(ReadSet::default()));
+        let recording_state = ReadRecordingState::new(&state, Rc::clone(&read_set));
+        let view = recording_state.checkout(root_hash).unwrap().unwrap();
+        assert!(view
+            .read(CorrelationId::new(), &uref_key)
+            .unwrap()
+            .is_some());
+        assert!(view
+            .read(CorrelationId::new(), &account_key)
+            .unwrap()
+            .is_none());
+        let _ = view
+            .keys_with_prefix(CorrelationId::new(), &[KeyTag::URef as u8])
+            .unwrap();
+
+        let read_set = read_set.borrow();
+        assert_eq!(
+            read_set.values(),
+            &[(account_key, None), (uref_key, Some(value))]
+                .into_iter()
+                .collect()
+        );
+        assert!(read_set.added_to().is_empty());
+        assert_eq!(
+            read_set.prefixes(),
+            &[vec![KeyTag::URef as u8]].into_iter().collect()
+        );
+    }
+
+    #[test]
+    fn should_observe_values_read_after_adding_to_them() {
+        let balance_key = Key::Balance([1; 32]);
+        let balance = cl_value(U512::from(10));
+        let (state, root_hash) = InMemoryGlobalState::from_pairs(
+            CorrelationId::new(),
+            &[(balance_key, balance.clone())],
+        )
+        .unwrap();
+
+        let read_set = Rc::new(RefCell::new(ReadSet::default()));
+        let recording_state = ReadRecordingState::new(&state, Rc::clone(&read_set));
+        let view = recording_state.checkout(root_hash).unwrap().unwrap();
+        let mut tracking_copy = TrackingCopy::new(view);
+
+        assert!(tracking_copy
+            .exists(CorrelationId::new(), &balance_key)
+            .unwrap());
+        let _ = tracking_copy
+            .add(CorrelationId::new(), balance_key, cl_value(U512::from(5)))
+            .unwrap();
+        assert!(read_set.borrow().values().is_empty());
+        assert_eq!(
+            read_set.borrow().added_to(),
+            &[(balance_key, Some(balance.clone()))].into_iter().collect()
+        );
+
+        // Reading the sum makes the result depend on the value read from global state.
+        assert_eq!(
+            tracking_copy
+                .read(CorrelationId::new(), &balance_key)
+                .unwrap(),
+            Some(cl_value(U512::from(15)))
+        );
+        assert_eq!(
+            read_set.borrow().values(),
+            &[(balance_key, Some(balance))].into_iter().collect()
+        );
+        assert!(read_set.borrow().added_to().is_empty());
+    }
+
+    #[test]
+    fn should_validate_reads_by_value() {
+        let read_key = Key::URef(URef::new([1; 32], AccessRights::NONE));
+        let balance_key = Key::Balance([2; 32]);
+        let other_key = Key::Hash([3; 32]);
+        let written_keys: BTreeSet<Key> = [read_key, balance_key].into_iter().collect();
+        let is_current = |read_set: &ReadSet, pairs: &[(Key, StoredValue)]| {
+            let (state, root_hash) =
+                InMemoryGlobalState::from_pairs(CorrelationId::new(), pairs).unwrap();
+            let reader = state.checkout(root_hash).unwrap().unwrap();
+            read_set
+                .is_current(CorrelationId::new(), &reader, &written_keys)
+                .unwrap()
+        };
+
+        let mut read_set = ReadSet::default();
+        read_set.record_value(&read_key, &Some(cl_value(1_u64)));
+        read_set.record_added_to(&balance_key, &Some(cl_value(U512::from(10))));
+
+        // Rewriting a value read with the same value, or adding to one only added to, is fine.
+        assert!(is_current(
+            &read_set,
+            &[
+                (read_key, cl_value(1_u64)),
+                (balance_key, cl_value(U512::from(20)))
+            ]
+        ));
+        assert!(!is_current(
+            &read_set,
+            &[
+                (read_key, cl_value(2_u64)),
+                (balance_key, cl_value(U512::from(10)))
+            ]
+        ));
+        assert!(!is_current(
+            &read_set,
+            &[(read_key, cl_value(1_u64)), (balance_key, cl_value(10_u64))]
+        ));
+        assert!(!is_current(&read_set, &[(read_key, cl_value(1_u64))]));
+
+        // Writing under a listed prefix invalidates the listing.
+        read_set.prefixes.insert(vec![KeyTag::Balance as u8]);
+        assert!(!is_current(
+            &read_set,
+            &[
+                (read_key, cl_value(1_u64)),
+                (balance_key, cl_value(U512::from(10)))
+            ]
+        ));
+        read_set.prefixes = [vec![KeyTag::Hash as u8]].into_iter().collect();
+        assert!(is_current(
+            &read_set,
+            &[
+                (read_key, cl_value(1_u64)),
+                (balance_key, cl_value(U512::from(10))),
+                (other_key, cl_value(1_u64))
+            ]
+        ));
+    }
+}
@@ -17,12 +17,15 @@ use crate::{
         global_state::{CommitError, CommitProvider, StateProvider, StateReader},
         store::Store,
         transaction_source::{lmdb::LmdbEnvironment, Transaction, TransactionSource},
-        trie::{merkle_proof::TrieMerkleProof, Trie, TrieRaw},
+        trie::{
+            merkle_proof::{TrieMerkleMultiProof, TrieMerkleProof},
+            Trie, TrieRaw,
+        },
         trie_store::{
             lmdb::LmdbTrieStore,
             operations::{
-                delete, keys_with_prefix, missing_children, put_trie, read, read_with_proof,
-                DeleteResult, ReadResult,
+                delete, diff, keys_with_prefix, missing_children, put_trie, read,
+                read_with_multi_proof, read_with_proof, DeleteResult, ReadResult, TrieDiff,
             },
         },
     },
@@ -167,6 +170,33 @@ impl StateReader<Key, StoredValue> for ScratchGlobalStateView {
         Ok(ret)
     }
 
+    fn read_with_multi_proof(
+        &self,
+        correlation_id: CorrelationId,
+        keys: &[Key],
+    ) -> Result<Option<TrieMerkleMultiProof<Key, StoredValue>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match read_with_multi_proof::<
+            Key,
+            StoredValue,
+            lmdb::RoTransaction,
+            LmdbTrieStore,
+            Self::Error,
+        >(
+            correlation_id,
+            &txn,
+            self.trie_store.deref(),
+            &self.root_hash,
+            keys,
+        )? {
+            ReadResult::Found(value) => Some(value),
+            ReadResult::NotFound => None,
+            ReadResult::RootNotFound => panic!("LmdbWithCacheGlobalState has invalid root"),
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
+
     fn keys_with_prefix(
         &self,
         correlation_id: CorrelationId,
@@ -353,6 +383,30 @@ impl StateProvider for ScratchGlobalState {
         txn.commit()?;
         Ok(DeleteResult::Deleted(state_root_hash))
     }
+
+    /// Compares tries already written to LMDB; values held only in the scratch cache are not part
+    /// of either root and so are not reported.
+    fn diff(
+        &self,
+        correlation_id: CorrelationId,
+        old_root: Digest,
+        new_root: Digest,
+        visitor: &mut dyn FnMut(TrieDiff<Key, StoredValue>),
+    ) -> Result<(), Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let diff_iter = diff::<Key, StoredValue, lmdb::RoTransaction, LmdbTrieStore>(
+            correlation_id,
+            &txn,
+            self.trie_store.deref(),
+            &old_root,
+            &new_root,
+        );
+        for result in diff_iter {
+            visitor(result?);
+        }
+        txn.commit()?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -1,6 +1,7 @@
-use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 use casper_hashing::Digest;
 use casper_types::bytesrepr::{self, Bytes, FromBytes, ToBytes};
@@ -235,6 +236,157 @@ where
     }
 }
 
+/// Error conditions of a [`TrieMerkleMultiProof`] verification.
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum TrieMerkleMultiProofError {
+    /// (De)serialization error while hashing a trie.
+    #[error("{0}")]
+    BytesRepr(bytesrepr::Error),
+    /// The proof doesn't contain the trie under the expected state root hash.
+    #[error("The proof doesn't contain the state root {0:?}.")]
+    RootNotFound(Digest),
+    /// The proof contains the same trie more than once.
+    #[error("The proof contains the trie {0:?} more than once.")]
+    DuplicateTrie(Digest),
+    /// The proof contains a trie which is not reachable from the state root.
+    #[error("The proof contains the trie {0:?} which is not reachable from the state root.")]
+    UnreachableTrie(Digest),
+}
+
+impl From<bytesrepr::Error> for TrieMerkleMultiProofError {
+    fn from(error: bytesrepr::Error) -> Self {
+        TrieMerkleMultiProofError::BytesRepr(error)
+    }
+}
+
+/// A proof that several entries are present in the Merkle trie under a single state root.
+///
+/// Rather than one [`TrieMerkleProof`] per entry, the proof holds every trie node lying on the
+/// paths from the root to the proven leaves, each of them only once, so that nodes shared by
+/// several paths (usually the ones close to the root) are not repeated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrieMerkleMultiProof<K, V> {
+    tries: Vec<Trie<K, V>>,
+}
+
+impl<K, V> TrieMerkleMultiProof<K, V> {
+    /// Constructor for [`TrieMerkleMultiProof`]
+    pub fn new(tries: Vec<Trie<K, V>>) -> Self {
+        TrieMerkleMultiProof { tries }
+    }
+
+    /// Getter for the tries in [`TrieMerkleMultiProof`]
+    pub fn tries(&self) -> &[Trie<K, V>] {
+        &self.tries
+    }
+
+    /// Returns the value under `key` in the proof, if any.
+    ///
+    /// The value can only be trusted once the proof has been checked with
+    /// [`TrieMerkleMultiProof::verify`].
+    pub fn value(&self, key: &K) -> Option<&V>
+    where
+        K: Eq,
+    {
+        self.tries.iter().find_map(|trie| match trie {
+            Trie::Leaf {
+                key: leaf_key,
+                value,
+            } if leaf_key == key => Some(value),
+            _ => None,
+        })
+    }
+}
+
+impl<K, V> TrieMerkleMultiProof<K, V>
+where
+    K: ToBytes,
+    V: ToBytes,
+{
+    /// Verifies the proof against a state root hash, without any access to the global state.
+    ///
+    /// Every trie in the proof has to be reachable from `state_root_hash` through the pointers of
+    /// the other tries in the proof, and no trie may be included twice.  Since a pointer commits to
+    /// the hash of its target, this guarantees that all the included leaves are present in the
+    /// global state under `state_root_hash`.
+    ///
+    /// Returns the proven entries, in the order of their serialized keys.
+    pub fn verify(
+        &self,
+        state_root_hash: &Digest,
+    ) -> Result<Vec<(&K, &V)>, TrieMerkleMultiProofError> {
+        let mut tries_by_hash = HashMap::with_capacity(self.tries.len());
+        for trie in &self.tries {
+            let trie_hash = trie.trie_hash()?;
+            if tries_by_hash.insert(trie_hash, trie).is_some() {
+                return Err(TrieMerkleMultiProofError::DuplicateTrie(trie_hash));
+            }
+        }
+        if !tries_by_hash.contains_key(state_root_hash) {
+            return Err(TrieMerkleMultiProofError::RootNotFound(*state_root_hash));
+        }
+
+        let mut entries = Vec::new();
+        let mut reached = HashSet::with_capacity(self.tries.len());
+        let mut to_visit = vec![*state_root_hash];
+        while let Some(trie_hash) = to_visit.pop() {
+            let trie = match tries_by_hash.get(&trie_hash) {
+                Some(trie) => trie,
+                // Not included in the proof; the subtrie under it is not being proven.
+                None => continue,
+            };
+            reached.insert(trie_hash);
+            match trie {
+                Trie::Leaf { key, value } => entries.push((key, value)),
+                Trie::Node { pointer_block } => {
+                    // Visit the children in reverse so that the leaves are found in order.
+                    let children: Vec<_> = pointer_block.as_indexed_pointers().collect();
+                    to_visit.extend(
+                        children
+                            .into_iter()
+                            .rev()
+                            .map(|(_, pointer)| pointer.into_hash()),
+                    );
+                }
+                Trie::Extension { pointer, .. } => to_visit.push(pointer.into_hash()),
+            }
+        }
+
+        if let Some(unreachable) = tries_by_hash
+            .keys()
+            .find(|trie_hash| !reached.contains(*trie_hash))
+        {
+            return Err(TrieMerkleMultiProofError::UnreachableTrie(*unreachable));
+        }
+        Ok(entries)
+    }
+}
+
+impl<K, V> ToBytes for TrieMerkleMultiProof<K, V>
+where
+    K: ToBytes,
+    V: ToBytes,
+{
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        self.tries.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.tries.serialized_length()
+    }
+}
+
+impl<K, V> FromBytes for TrieMerkleMultiProof<K, V>
+where
+    K: FromBytes,
+    V: FromBytes,
+{
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tries, rem): (Vec<Trie<K, V>>, &[u8]) = FromBytes::from_bytes(bytes)?;
+        Ok((TrieMerkleMultiProof { tries }, rem))
+    }
+}
+
 #[cfg(test)]
 mod gens {
     use proptest::{collection::vec, prelude::*};
@@ -245,14 +397,15 @@ mod gens {
     };
 
     use crate::storage::trie::{
-        gens::trie_pointer_arb,
-        merkle_proof::{TrieMerkleProof, TrieMerkleProofStep},
+        gens::{trie_extension_arb, trie_leaf_arb, trie_node_arb, trie_pointer_arb},
+        merkle_proof::{TrieMerkleMultiProof, TrieMerkleProof, TrieMerkleProofStep},
         RADIX,
     };
 
     const POINTERS_SIZE: usize = RADIX / 8;
     const AFFIX_SIZE: usize = 6;
     const STEPS_SIZE: usize = 6;
+    const TRIES_SIZE: usize = 6;
 
     pub fn trie_merkle_proof_step_arb() -> impl Strategy<Value = TrieMerkleProofStep> {
         prop_oneof![
@@ -284,6 +437,15 @@ mod gens {
                 TrieMerkleProof::new(key, value, proof_steps.into())
             })
     }
+
+    pub fn trie_merkle_multi_proof_arb(
+    ) -> impl Strategy<Value = TrieMerkleMultiProof<Key, StoredValue>> {
+        vec(
+            prop_oneof![trie_leaf_arb(), trie_extension_arb(), trie_node_arb()],
+            TRIES_SIZE,
+        )
+        .prop_map(TrieMerkleMultiProof::new)
+    }
 }
 
 #[cfg(test)]
@@ -308,5 +470,12 @@ mod tests {
         ) {
             bytesrepr::test_serialization_roundtrip(&proof)
         }
+
+        #[test]
+        fn trie_merkle_multi_proof_serialization_is_correct(
+            proof in gens::trie_merkle_multi_proof_arb()
+        ) {
+            bytesrepr::test_serialization_roundtrip(&proof)
+        }
     }
 }
@@ -1,9 +1,13 @@
 #[cfg(test)]
 mod tests;
 
-#[cfg(test)]
-use std::collections::HashSet;
-use std::{borrow::Cow, cmp, collections::VecDeque, convert::TryInto, mem};
+use std::{
+    borrow::Cow,
+    cmp,
+    collections::{BTreeMap, HashSet, VecDeque},
+    convert::TryInto,
+    mem,
+};
 
 use either::Either;
 use num_traits::FromPrimitive;
@@ -15,10 +19,11 @@ use casper_types::bytesrepr::{self, Bytes, FromBytes, ToBytes};
 use crate::{
     shared::newtypes::CorrelationId,
     storage::{
+        error,
         transaction_source::{Readable, Writable},
         trie::{
             self,
-            merkle_proof::{TrieMerkleProof, TrieMerkleProofStep},
+            merkle_proof::{TrieMerkleMultiProof, TrieMerkleProof, TrieMerkleProofStep},
             Parents, Pointer, PointerBlock, Trie, TrieTag, RADIX, USIZE_EXCEEDS_U8,
         },
         trie_store::TrieStore,
@@ -234,6 +239,86 @@ where
     }
 }
 
+/// Same as [`read_with_proof`], except that several keys are read at once and a single
+/// [`TrieMerkleMultiProof`] covering all of them is returned.
+///
+/// Returns [`ReadResult::NotFound`] if any of the keys is not present under the given root.
+pub fn read_with_multi_proof<K, V, T, S, E>(
+    _correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    root: &Digest,
+    keys: &[K],
+) -> Result<ReadResult<TrieMerkleMultiProof<K, V>>, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<bytesrepr::Error>,
+{
+    let root_trie: Trie<K, V> = match store.get(txn, root)? {
+        Some(root_trie) => root_trie,
+        None => return Ok(ReadResult::RootNotFound),
+    };
+
+    let mut included = HashSet::new();
+    let mut tries = Vec::new();
+    for key in keys {
+        let path: Vec<u8> = key.to_bytes()?;
+        let mut depth: usize = 0;
+        let mut current_hash = *root;
+        let mut current = root_trie.clone();
+        loop {
+            if included.insert(current_hash) {
+                tries.push(current.clone());
+            }
+            let next_pointer = match current {
+                Trie::Leaf { key: leaf_key, .. } => {
+                    if *key != leaf_key {
+                        return Ok(ReadResult::NotFound);
+                    }
+                    break;
+                }
+                Trie::Node { pointer_block } => {
+                    let index: usize = {
+                        assert!(depth < path.len(), "depth must be < {}", path.len());
+                        path[depth].into()
+                    };
+                    match pointer_block[index] {
+                        Some(pointer) => {
+                            depth += 1;
+                            pointer
+                        }
+                        None => return Ok(ReadResult::NotFound),
+                    }
+                }
+                Trie::Extension { affix, pointer } => {
+                    let sub_path = &path[depth..depth + affix.len()];
+                    if sub_path != affix.as_slice() {
+                        return Ok(ReadResult::NotFound);
+                    }
+                    depth += affix.len();
+                    pointer
+                }
+            };
+            current_hash = next_pointer.into_hash();
+            current = match store.get(txn, &current_hash)? {
+                Some(next) => next,
+                None => {
+                    warn!(
+                        "No trie value at key: {:?} (reading from path: {:?})",
+                        current_hash, path
+                    );
+                    return Ok(ReadResult::NotFound);
+                }
+            };
+        }
+    }
+    Ok(ReadResult::Found(TrieMerkleMultiProof::new(tries)))
+}
+
 /// Given a serialized trie, find any children that are referenced but not present in the database.
 pub fn missing_children<K, V, T, S, E>(
     _correlation_id: CorrelationId,
@@ -997,6 +1082,339 @@ where
     }
 }
 
+/// A single entry of a batch write, paired with its path through the trie.
+#[derive(Clone)]
+struct BatchEntry<'a, K, V> {
+    path: Vec<u8>,
+    kind: BatchEntryKind<'a, K, V>,
+}
+
+#[derive(Clone)]
+enum BatchEntryKind<'a, K, V> {
+    /// A leaf which has to be written.
+    Write { key: &'a K, value: &'a V },
+    /// A leaf already present in the store, which only has to be relinked.
+    Existing(Pointer),
+}
+
+/// Splits `entries`, sorted by path, into runs sharing the same path byte at `depth`.
+fn group_by_index<'e, 'a, K, V>(
+    entries: &'e [BatchEntry<'a, K, V>],
+    depth: usize,
+) -> Vec<(u8, &'e [BatchEntry<'a, K, V>])> {
+    let mut groups = Vec::new();
+    let mut start = 0;
+    while start < entries.len() {
+        let index = entries[start].path[depth];
+        let len = entries[start..]
+            .iter()
+            .take_while(|entry| entry.path[depth] == index)
+            .count();
+        groups.push((index, &entries[start..start + len]));
+        start += len;
+    }
+    groups
+}
+
+fn common_prefix_len(ls: &[u8], rs: &[u8]) -> usize {
+    ls.iter().zip(rs.iter()).take_while(|(l, r)| l == r).count()
+}
+
+fn put_batch_trie<K, V, T, S, E>(txn: &mut T, store: &S, trie: &Trie<K, V>) -> Result<Digest, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq,
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<bytesrepr::Error>,
+{
+    let hash = trie.trie_hash()?;
+    store.put(txn, &hash, trie)?;
+    Ok(hash)
+}
+
+/// Builds a fresh subtrie holding `entries`, which must be non-empty, sorted by path and share the
+/// first `depth` bytes of their paths.
+fn batch_write_new<K, V, T, S, E>(
+    txn: &mut T,
+    store: &S,
+    depth: usize,
+    entries: &[BatchEntry<K, V>],
+) -> Result<Pointer, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq,
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<bytesrepr::Error>,
+{
+    if let [entry] = entries {
+        return match entry.kind {
+            BatchEntryKind::Existing(pointer) => Ok(pointer),
+            BatchEntryKind::Write { key, value } => {
+                let leaf = Trie::leaf(key.to_owned(), value.to_owned());
+                let hash = put_batch_trie::<_, _, _, _, E>(txn, store, &leaf)?;
+                Ok(Pointer::LeafPointer(hash))
+            }
+        };
+    }
+    // As the entries are sorted, the prefix shared by the first and the last one is shared by all.
+    let first_path = &entries[0].path[depth..];
+    let last_path = &entries[entries.len() - 1].path[depth..];
+    let affix_len = common_prefix_len(first_path, last_path);
+    let branch_depth = depth + affix_len;
+
+    let mut indexed_pointers = Vec::new();
+    for (index, group) in group_by_index(entries, branch_depth) {
+        let pointer = batch_write_new::<_, _, _, _, E>(txn, store, branch_depth + 1, group)?;
+        indexed_pointers.push((index, pointer));
+    }
+    let node = Trie::node(&indexed_pointers);
+    let mut pointer = Pointer::NodePointer(put_batch_trie::<_, _, _, _, E>(txn, store, &node)?);
+    if affix_len > 0 {
+        let extension = Trie::extension(first_path[..affix_len].to_vec(), pointer);
+        pointer = Pointer::NodePointer(put_batch_trie::<_, _, _, _, E>(txn, store, &extension)?);
+    }
+    Ok(pointer)
+}
+
+/// Writes `entries`, sorted by path and sharing the first `depth` bytes of their paths, into the
+/// subtrie `existing` points to.  Returns the pointer to the rewritten subtrie, or `None` if it is
+/// unchanged.
+fn batch_write_subtrie<K, V, T, S, E>(
+    txn: &mut T,
+    store: &S,
+    existing: Option<Pointer>,
+    depth: usize,
+    entries: &[BatchEntry<K, V>],
+) -> Result<Option<Pointer>, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq,
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<bytesrepr::Error>,
+{
+    if entries.is_empty() {
+        return Ok(None);
+    }
+    let existing = match existing {
+        Some(existing) => existing,
+        None => return batch_write_new::<_, _, _, _, E>(txn, store, depth, entries).map(Some),
+    };
+    let trie = match store.get(txn, existing.hash())? {
+        Some(trie) => trie,
+        None => panic!("No trie value at key: {:?}", existing.hash()),
+    };
+    match trie {
+        Trie::Leaf {
+            key: existing_key,
+            value: existing_value,
+        } => {
+            // Merge the existing leaf into the entries, relinking rather than rewriting it if it
+            // is left untouched.
+            let mut merged = entries.to_vec();
+            let existing_path = existing_key.to_bytes()?;
+            match merged.binary_search_by(|entry| entry.path.cmp(&existing_path)) {
+                Ok(position) => {
+                    if let BatchEntryKind::Write { value, .. } = merged[position].kind {
+                        if *value == existing_value {
+                            merged[position].kind = BatchEntryKind::Existing(existing);
+                        }
+                    }
+                }
+                Err(position) => merged.insert(
+                    position,
+                    BatchEntry {
+                        path: existing_path,
+                        kind: BatchEntryKind::Existing(existing),
+                    },
+                ),
+            }
+            if let [BatchEntry {
+                kind: BatchEntryKind::Existing(_),
+                ..
+            }] = merged.as_slice()
+            {
+                return Ok(None);
+            }
+            batch_write_new::<_, _, _, _, E>(txn, store, depth, &merged).map(Some)
+        }
+        Trie::Node { mut pointer_block } => {
+            let mut changed = false;
+            for (index, group) in group_by_index(entries, depth) {
+                let index: usize = index.into();
+                if let Some(pointer) = batch_write_subtrie::<_, _, _, _, E>(
+                    txn,
+                    store,
+                    pointer_block[index],
+                    depth + 1,
+                    group,
+                )? {
+                    pointer_block[index] = Some(pointer);
+                    changed = true;
+                }
+            }
+            if !changed {
+                return Ok(None);
+            }
+            let node = Trie::Node { pointer_block };
+            let hash = put_batch_trie::<_, _, _, _, E>(txn, store, &node)?;
+            Ok(Some(Pointer::NodePointer(hash)))
+        }
+        Trie::Extension { affix, pointer } => {
+            batch_write_extension::<_, _, _, _, E>(txn, store, &affix, pointer, depth, entries)
+        }
+    }
+}
+
+/// Writes `entries` into the subtrie rooted at an extension with the given `affix` and `pointer`,
+/// splitting the extension where the entries diverge from it.  Returns the pointer to the
+/// rewritten subtrie, or `None` if it is unchanged.
+fn batch_write_extension<K, V, T, S, E>(
+    txn: &mut T,
+    store: &S,
+    affix: &[u8],
+    pointer: Pointer,
+    depth: usize,
+    entries: &[BatchEntry<K, V>],
+) -> Result<Option<Pointer>, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq,
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<bytesrepr::Error>,
+{
+    let matched_len = entries
+        .iter()
+        .map(|entry| common_prefix_len(&entry.path[depth..], affix))
+        .min()
+        .unwrap_or(affix.len());
+
+    // All entries lie below the extension, which is kept and only repointed.
+    if matched_len == affix.len() {
+        let maybe_pointer = batch_write_subtrie::<_, _, _, _, E>(
+            txn,
+            store,
+            Some(pointer),
+            depth + affix.len(),
+            entries,
+        )?;
+        return match maybe_pointer {
+            Some(new_pointer) => {
+                let extension = Trie::extension(affix.to_vec(), new_pointer);
+                let hash = put_batch_trie::<_, _, _, _, E>(txn, store, &extension)?;
+                Ok(Some(Pointer::NodePointer(hash)))
+            }
+            None => Ok(None),
+        };
+    }
+
+    // Otherwise a new node is needed where the first entry diverges from the affix.  What remains
+    // of the affix below that node becomes a child extension, which is only written once it has
+    // taken in all entries which go through it.
+    let branch_depth = depth + matched_len;
+    let existing_index = affix[matched_len];
+    let child_affix = &affix[matched_len + 1..];
+    let mut existing_group: &[BatchEntry<K, V>] = &[];
+    let mut indexed_pointers = Vec::new();
+    for (index, group) in group_by_index(entries, branch_depth) {
+        if index == existing_index {
+            existing_group = group;
+        } else {
+            let new_pointer =
+                batch_write_new::<_, _, _, _, E>(txn, store, branch_depth + 1, group)?;
+            indexed_pointers.push((index, new_pointer));
+        }
+    }
+    let existing_pointer = if child_affix.is_empty() {
+        batch_write_subtrie::<_, _, _, _, E>(
+            txn,
+            store,
+            Some(pointer),
+            branch_depth + 1,
+            existing_group,
+        )?
+        .unwrap_or(pointer)
+    } else {
+        match batch_write_extension::<_, _, _, _, E>(
+            txn,
+            store,
+            child_affix,
+            pointer,
+            branch_depth + 1,
+            existing_group,
+        )? {
+            Some(new_pointer) => new_pointer,
+            None => {
+                let extension = Trie::extension(child_affix.to_vec(), pointer);
+                Pointer::NodePointer(put_batch_trie::<_, _, _, _, E>(txn, store, &extension)?)
+            }
+        }
+    };
+    indexed_pointers.push((existing_index, existing_pointer));
+
+    let node = Trie::node(&indexed_pointers);
+    let mut new_pointer = Pointer::NodePointer(put_batch_trie::<_, _, _, _, E>(txn, store, &node)?);
+    if matched_len > 0 {
+        let extension = Trie::extension(affix[..matched_len].to_vec(), new_pointer);
+        new_pointer =
+            Pointer::NodePointer(put_batch_trie::<_, _, _, _, E>(txn, store, &extension)?);
+    }
+    Ok(Some(new_pointer))
+}
+
+/// Writes several key/value pairs under `root` at once.
+///
+/// Unlike repeated calls to [`write`], which rewrite the whole path from the root for every key,
+/// the pairs are sorted by their path through the trie so that paths sharing a prefix are
+/// descended together, and every trie element touched by the batch is written to the store
+/// exactly once.  The resulting root is the same as if the pairs had been written one by one.  If
+/// a key occurs more than once, the last value given for it is written.
+pub fn batch_write<'a, K, V, T, S, E, I>(
+    _correlation_id: CorrelationId,
+    txn: &mut T,
+    store: &S,
+    root: &Digest,
+    pairs: I,
+) -> Result<WriteResult, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug + 'a,
+    V: ToBytes + FromBytes + Clone + Eq + 'a,
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<bytesrepr::Error>,
+    I: IntoIterator<Item = (&'a K, &'a V)>,
+{
+    if store.get(txn, root)?.is_none() {
+        return Ok(WriteResult::RootNotFound);
+    }
+    let mut sorted = BTreeMap::new();
+    for (key, value) in pairs {
+        sorted.insert(key.to_bytes()?, BatchEntryKind::Write { key, value });
+    }
+    let entries: Vec<_> = sorted
+        .into_iter()
+        .map(|(path, kind)| BatchEntry { path, kind })
+        .collect();
+    match batch_write_subtrie::<_, _, _, _, E>(
+        txn,
+        store,
+        Some(Pointer::NodePointer(*root)),
+        0,
+        &entries,
+    )? {
+        Some(new_root) => Ok(WriteResult::Written(*new_root.hash())),
+        None => Ok(WriteResult::AlreadyExists),
+    }
+}
+
 /// Puts a trie pointer block, extension node or leaf into the trie.
 pub fn put_trie<K, V, T, S, E>(
     _correlation_id: CorrelationId,
@@ -1220,6 +1638,301 @@ where
     keys_with_prefix(correlation_id, txn, store, root, &[])
 }
 
+/// A single difference between two tries, as produced by [`diff`].
+///
+/// At least one of `old_value` and `new_value` is present: an absent old value means the key was
+/// added, an absent new value means the key was removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrieDiff<K, V> {
+    key: K,
+    old_value: Option<V>,
+    new_value: Option<V>,
+}
+
+impl<K, V> TrieDiff<K, V> {
+    /// Constructs a new `TrieDiff`.
+    pub fn new(key: K, old_value: Option<V>, new_value: Option<V>) -> Self {
+        TrieDiff {
+            key,
+            old_value,
+            new_value,
+        }
+    }
+
+    /// Returns the key which differs between the two tries.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Returns the value under the key in the old trie, if any.
+    pub fn old_value(&self) -> Option<&V> {
+        self.old_value.as_ref()
+    }
+
+    /// Returns the value under the key in the new trie, if any.
+    pub fn new_value(&self) -> Option<&V> {
+        self.new_value.as_ref()
+    }
+
+    /// Consumes `self`, returning the key, the old value and the new value.
+    pub fn into_parts(self) -> (K, Option<V>, Option<V>) {
+        (self.key, self.old_value, self.new_value)
+    }
+}
+
+/// A reference to a subtrie visited by the [`DiffIterator`].
+///
+/// Two references which compare equal are guaranteed to describe identical subtries, which lets
+/// the iterator skip them without loading any of their nodes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DiffSubtrie {
+    /// There is no subtrie at this position.
+    Empty,
+    /// A trie stored under the given hash.
+    Stored(Digest),
+    /// The remainder of an extension node's affix whose leading bytes were already consumed.
+    PartialExtension { affix: Vec<u8>, pointer: Pointer },
+}
+
+/// A loaded [`DiffSubtrie`], split into its children by the next byte of the path.
+enum LoadedSubtrie<K, V> {
+    Empty,
+    Leaf { key: K, value: V },
+    Branch(Vec<(u8, DiffSubtrie)>),
+}
+
+impl DiffSubtrie {
+    fn from_affix(affix: &[u8], pointer: Pointer) -> (u8, Self) {
+        let subtrie = if affix.len() == 1 {
+            DiffSubtrie::Stored(pointer.into_hash())
+        } else {
+            DiffSubtrie::PartialExtension {
+                affix: affix[1..].to_vec(),
+                pointer,
+            }
+        };
+        (affix[0], subtrie)
+    }
+
+    fn load<K, V, T, S>(self, txn: &T, store: &S) -> Result<LoadedSubtrie<K, V>, error::Error>
+    where
+        K: FromBytes,
+        V: FromBytes,
+        T: Readable<Handle = S::Handle>,
+        S: TrieStore<K, V>,
+        S::Error: From<T::Error>,
+        error::Error: From<S::Error>,
+    {
+        let trie_hash = match self {
+            DiffSubtrie::Empty => return Ok(LoadedSubtrie::Empty),
+            DiffSubtrie::PartialExtension { affix, pointer } => {
+                return Ok(LoadedSubtrie::Branch(vec![DiffSubtrie::from_affix(
+                    &affix, pointer,
+                )]));
+            }
+            DiffSubtrie::Stored(trie_hash) => trie_hash,
+        };
+        let loaded = match store.get(txn, &trie_hash)? {
+            None => return Err(error::Error::TrieNotFound(trie_hash)),
+            Some(Trie::Leaf { key, value }) => LoadedSubtrie::Leaf { key, value },
+            Some(Trie::Node { pointer_block }) => LoadedSubtrie::Branch(
+                pointer_block
+                    .as_indexed_pointers()
+                    .map(|(index, pointer)| (index, DiffSubtrie::Stored(pointer.into_hash())))
+                    .collect(),
+            ),
+            Some(Trie::Extension { affix, pointer }) => {
+                LoadedSubtrie::Branch(vec![DiffSubtrie::from_affix(&affix, pointer)])
+            }
+        };
+        Ok(loaded)
+    }
+}
+
+/// An iterator over the differences between two tries, in ascending order of serialized keys.
+pub struct DiffIterator<'a, 'b, K, V, T, S: TrieStore<K, V>> {
+    /// Pairs of subtries (old, new) still to be compared, the next one on top.
+    pending: Vec<(DiffSubtrie, DiffSubtrie)>,
+    /// Differences already found but not yet returned.
+    ready: VecDeque<TrieDiff<K, V>>,
+    store: &'a S,
+    txn: &'b T,
+    failed: bool,
+}
+
+impl<'a, 'b, K, V, T, S> DiffIterator<'a, 'b, K, V, T, S>
+where
+    K: ToBytes + FromBytes + Eq,
+    V: ToBytes + FromBytes + Eq,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    error::Error: From<S::Error>,
+{
+    /// Compares the next pair of pending subtries, queueing up either their children or the
+    /// differences found between them.
+    fn compare_next(&mut self) -> Result<(), error::Error> {
+        let (old, new) = match self.pending.pop() {
+            Some(pair) => pair,
+            None => return Ok(()),
+        };
+        if old == new {
+            return Ok(());
+        }
+        match (
+            old.load(self.txn, self.store)?,
+            new.load(self.txn, self.store)?,
+        ) {
+            (LoadedSubtrie::Branch(old_children), LoadedSubtrie::Branch(new_children)) => {
+                let mut old_children = old_children.into_iter().peekable();
+                let mut new_children = new_children.into_iter().peekable();
+                let mut pairs = Vec::new();
+                loop {
+                    let pair = match (old_children.peek(), new_children.peek()) {
+                        (None, None) => break,
+                        (Some((old_index, _)), Some((new_index, _))) if old_index == new_index => {
+                            let (_, old_child) = old_children.next().unwrap();
+                            let (_, new_child) = new_children.next().unwrap();
+                            (old_child, new_child)
+                        }
+                        (Some((old_index, _)), Some((new_index, _))) if old_index < new_index => {
+                            (old_children.next().unwrap().1, DiffSubtrie::Empty)
+                        }
+                        (Some(_), None) => (old_children.next().unwrap().1, DiffSubtrie::Empty),
+                        (_, Some(_)) => (DiffSubtrie::Empty, new_children.next().unwrap().1),
+                    };
+                    pairs.push(pair);
+                }
+                // Push in reverse so that the lowest index is compared first.
+                self.pending.extend(pairs.into_iter().rev());
+            }
+            (old, new) => {
+                // At least one side is empty or a single leaf, so the whole subtrie can be
+                // compared key by key.
+                let old_leaves = self.collect_leaves(old)?;
+                let mut new_leaves = self.collect_leaves(new)?.into_iter().peekable();
+                for (old_path, old_key, old_value) in old_leaves {
+                    while let Some((new_path, _, _)) = new_leaves.peek() {
+                        if *new_path >= old_path {
+                            break;
+                        }
+                        let (_, new_key, new_value) = new_leaves.next().unwrap();
+                        self.ready
+                            .push_back(TrieDiff::new(new_key, None, Some(new_value)));
+                    }
+                    match new_leaves.peek() {
+                        Some((new_path, _, _)) if *new_path == old_path => {
+                            let (_, _, new_value) = new_leaves.next().unwrap();
+                            if new_value != old_value {
+                                self.ready.push_back(TrieDiff::new(
+                                    old_key,
+                                    Some(old_value),
+                                    Some(new_value),
+                                ));
+                            }
+                        }
+                        _ => self
+                            .ready
+                            .push_back(TrieDiff::new(old_key, Some(old_value), None)),
+                    }
+                }
+                for (_, new_key, new_value) in new_leaves {
+                    self.ready
+                        .push_back(TrieDiff::new(new_key, None, Some(new_value)));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns all the leaves of the given subtrie, sorted by their serialized keys.
+    fn collect_leaves(
+        &self,
+        subtrie: LoadedSubtrie<K, V>,
+    ) -> Result<Vec<(Vec<u8>, K, V)>, error::Error> {
+        let mut leaves = Vec::new();
+        let mut to_visit = vec![subtrie];
+        while let Some(subtrie) = to_visit.pop() {
+            match subtrie {
+                LoadedSubtrie::Empty => (),
+                LoadedSubtrie::Leaf { key, value } => {
+                    let path = key.to_bytes()?;
+                    leaves.push((path, key, value));
+                }
+                LoadedSubtrie::Branch(children) => {
+                    for (_, child) in children.into_iter().rev() {
+                        to_visit.push(child.load(self.txn, self.store)?);
+                    }
+                }
+            }
+        }
+        Ok(leaves)
+    }
+}
+
+impl<'a, 'b, K, V, T, S> Iterator for DiffIterator<'a, 'b, K, V, T, S>
+where
+    K: ToBytes + FromBytes + Eq,
+    V: ToBytes + FromBytes + Eq,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    error::Error: From<S::Error>,
+{
+    type Item = Result<TrieDiff<K, V>, error::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        loop {
+            if let Some(trie_diff) = self.ready.pop_front() {
+                return Some(Ok(trie_diff));
+            }
+            if self.pending.is_empty() {
+                return None;
+            }
+            if let Err(error) = self.compare_next() {
+                self.failed = true;
+                return Some(Err(error));
+            }
+        }
+    }
+}
+
+/// Returns the iterator over the differences between the tries under `old_root` and `new_root`.
+///
+/// Both tries are walked in parallel and subtries with identical hashes are skipped without being
+/// read, so the cost is proportional to the size of the difference rather than the size of the
+/// tries.  A missing trie node (including either of the roots) is reported as
+/// [`error::Error::TrieNotFound`].
+pub fn diff<'a, 'b, K, V, T, S>(
+    _correlation_id: CorrelationId,
+    txn: &'b T,
+    store: &'a S,
+    old_root: &Digest,
+    new_root: &Digest,
+) -> DiffIterator<'a, 'b, K, V, T, S>
+where
+    K: ToBytes + FromBytes + Eq,
+    V: ToBytes + FromBytes + Eq,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    error::Error: From<S::Error>,
+{
+    DiffIterator {
+        pending: vec![(
+            DiffSubtrie::Stored(*old_root),
+            DiffSubtrie::Stored(*new_root),
+        )],
+        ready: VecDeque::new(),
+        store,
+        txn,
+        failed: false,
+    }
+}
+
 #[cfg(test)]
 pub fn check_integrity<K, V, T, S, E>(
     _correlation_id: CorrelationId,
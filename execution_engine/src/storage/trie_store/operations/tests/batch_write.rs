@@ -0,0 +1,204 @@
+use proptest::{
+    array,
+    collection::vec,
+    prelude::{any, prop, proptest, Strategy},
+};
+
+use super::*;
+use crate::storage::trie_store::operations::batch_write;
+
+fn leaf_pairs(leaves: &[TestTrie]) -> Vec<(TestKey, TestValue)> {
+    leaves
+        .iter()
+        .map(|leaf| match leaf {
+            Trie::Leaf { key, value } => (*key, *value),
+            _ => panic!("leaves should contain only leaves"),
+        })
+        .collect()
+}
+
+fn batch_write_pairs<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    root: &Digest,
+    pairs: &[(TestKey, TestValue)],
+) -> Result<WriteResult, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<bytesrepr::Error>,
+{
+    let mut txn = environment.create_read_write_txn()?;
+    let result = batch_write::<_, _, _, _, E, _>(
+        CorrelationId::new(),
+        &mut txn,
+        store,
+        root,
+        pairs.iter().map(|(key, value)| (key, value)),
+    )?;
+    if let WriteResult::Written(new_root) = result {
+        operations::check_integrity::<_, _, _, _, E>(
+            CorrelationId::new(),
+            &txn,
+            store,
+            vec![new_root],
+        )?;
+    }
+    txn.commit()?;
+    Ok(result)
+}
+
+/// Writes `pairs` under `root` both one by one and in a single batch, and checks that both yield
+/// the same root under which all pairs can be read back.
+fn batch_write_matches_sequential_writes<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    root: &Digest,
+    pairs: &[(TestKey, TestValue)],
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<bytesrepr::Error>,
+{
+    let expected_root =
+        write_pairs::<_, _, _, _, E>(CorrelationId::new(), environment, store, root, pairs)?
+            .last()
+            .copied()
+            .unwrap_or(*root);
+
+    let actual_root = match batch_write_pairs::<_, _, E>(environment, store, root, pairs)? {
+        WriteResult::Written(new_root) => new_root,
+        WriteResult::AlreadyExists => *root,
+        WriteResult::RootNotFound => panic!("batch_write given an invalid root"),
+    };
+    assert_eq!(actual_root, expected_root);
+
+    // Later pairs override earlier ones with the same key.
+    let mut expected_pairs: BTreeMap<TestKey, TestValue> = BTreeMap::new();
+    expected_pairs.extend(pairs.iter().copied());
+    let txn = environment.create_read_txn()?;
+    for (key, value) in expected_pairs {
+        let read_result =
+            read::<_, _, _, _, E>(CorrelationId::new(), &txn, store, &actual_root, &key)?;
+        assert_eq!(read_result, ReadResult::Found(value));
+    }
+    txn.commit()?;
+    Ok(())
+}
+
+fn all_test_pairs() -> Vec<(TestKey, TestValue)> {
+    let mut pairs = leaf_pairs(&TEST_LEAVES_UPDATED);
+    pairs.extend(leaf_pairs(&TEST_LEAVES_NON_COLLIDING));
+    pairs.extend(leaf_pairs(&TEST_LEAVES_ADJACENTS));
+    pairs
+}
+
+#[test]
+fn lmdb_batch_writes_to_partial_tries_match_sequential_writes() {
+    for generator in &TEST_TRIE_GENERATORS {
+        let (root_hash, tries) = generator().unwrap();
+        let context = LmdbTestContext::new(&tries).unwrap();
+        batch_write_matches_sequential_writes::<_, _, error::Error>(
+            &context.environment,
+            &context.store,
+            &root_hash,
+            &all_test_pairs(),
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn in_memory_batch_writes_to_partial_tries_match_sequential_writes() {
+    for generator in &TEST_TRIE_GENERATORS {
+        let (root_hash, tries) = generator().unwrap();
+        let context = InMemoryTestContext::new(&tries).unwrap();
+        batch_write_matches_sequential_writes::<_, _, in_memory::Error>(
+            &context.environment,
+            &context.store,
+            &root_hash,
+            &all_test_pairs(),
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn in_memory_batch_write_of_present_leaves_should_not_change_root() {
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[TEST_TRIE_GENERATORS_LENGTH - 1]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+    let result = batch_write_pairs::<_, _, in_memory::Error>(
+        &context.environment,
+        &context.store,
+        &root_hash,
+        &leaf_pairs(&TEST_LEAVES),
+    )
+    .unwrap();
+    assert_eq!(result, WriteResult::AlreadyExists);
+}
+
+#[test]
+fn in_memory_batch_write_to_missing_root_should_fail() {
+    let (_, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+    let result = batch_write_pairs::<_, _, in_memory::Error>(
+        &context.environment,
+        &context.store,
+        &Digest::hash([1u8; 8]),
+        &leaf_pairs(&TEST_LEAVES),
+    )
+    .unwrap();
+    assert_eq!(result, WriteResult::RootNotFound);
+}
+
+/// Keys drawn from a small alphabet, so that they share long prefixes and collide often.
+fn colliding_test_key_arb() -> impl Strategy<Value = TestKey> {
+    array::uniform7(0u8..3).prop_map(TestKey)
+}
+
+fn test_value_arb() -> impl Strategy<Value = TestValue> {
+    array::uniform6(any::<u8>()).prop_map(TestValue)
+}
+
+fn test_pairs_arb() -> impl Strategy<Value = Vec<(TestKey, TestValue)>> {
+    vec(
+        prop::strategy::Union::new(vec![
+            colliding_test_key_arb().boxed(),
+            array::uniform7(any::<u8>()).prop_map(TestKey).boxed(),
+        ])
+        .prop_flat_map(|key| test_value_arb().prop_map(move |value| (key, value))),
+        0..50,
+    )
+}
+
+proptest! {
+    #[test]
+    fn prop_in_memory_batch_write_matches_sequential_writes(
+        initial in test_pairs_arb(),
+        batch in test_pairs_arb(),
+    ) {
+        let (root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+        let context = InMemoryTestContext::new(&tries).unwrap();
+        let root_hash = write_pairs::<_, _, _, _, in_memory::Error>(
+            CorrelationId::new(),
+            &context.environment,
+            &context.store,
+            &root_hash,
+            &initial,
+        )
+        .unwrap()
+        .last()
+        .copied()
+        .unwrap_or(root_hash);
+        batch_write_matches_sequential_writes::<_, _, in_memory::Error>(
+            &context.environment,
+            &context.store,
+            &root_hash,
+            &batch,
+        )
+        .unwrap();
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::BTreeMap;
+
+use super::*;
+use crate::storage::trie_store::operations::TrieDiff;
+
+fn leaves_to_pairs(leaves: &[TestTrie]) -> Vec<(TestKey, TestValue)> {
+    leaves
+        .iter()
+        .map(|leaf| match leaf {
+            Trie::Leaf { key, value } => (*key, *value),
+            _ => panic!("leaves should contain only leaves"),
+        })
+        .collect()
+}
+
+fn expected_diff(
+    old_pairs: &[(TestKey, TestValue)],
+    new_pairs: &[(TestKey, TestValue)],
+) -> Vec<TrieDiff<TestKey, TestValue>> {
+    let old: BTreeMap<_, _> = old_pairs.iter().copied().collect();
+    let new: BTreeMap<_, _> = new_pairs.iter().copied().collect();
+    let mut keys: Vec<TestKey> = old.keys().chain(new.keys()).copied().collect();
+    keys.sort();
+    keys.dedup();
+    keys.into_iter()
+        .filter_map(|key| {
+            let old_value = old.get(&key).copied();
+            let new_value = new.get(&key).copied();
+            (old_value != new_value).then(|| TrieDiff::new(key, old_value, new_value))
+        })
+        .collect()
+}
+
+fn diff_roots<'a, R, S>(
+    environment: &'a R,
+    store: &S,
+    old_root: &Digest,
+    new_root: &Digest,
+) -> Result<Vec<TrieDiff<TestKey, TestValue>>, error::Error>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    error::Error: From<R::Error> + From<S::Error>,
+{
+    let txn = environment.create_read_txn()?;
+    let diffs = operations::diff::<TestKey, TestValue, _, _>(
+        CorrelationId::new(),
+        &txn,
+        store,
+        old_root,
+        new_root,
+    )
+    .collect::<Result<Vec<_>, _>>()?;
+    txn.commit()?;
+    Ok(diffs)
+}
+
+/// Writes `old_leaves` on top of an empty trie and then `new_leaves` on top of the result, checking
+/// that the diff between the two roots (in both directions) matches the expected one.
+fn diff_between_writes_had_expected_results<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    empty_root: &Digest,
+    old_leaves: &[TestTrie],
+    new_leaves: &[TestTrie],
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<bytesrepr::Error>,
+    error::Error: From<R::Error> + From<S::Error>,
+{
+    let correlation_id = CorrelationId::new();
+    let old_pairs = leaves_to_pairs(old_leaves);
+    let old_root =
+        write_pairs::<_, _, _, _, E>(correlation_id, environment, store, empty_root, &old_pairs)?
+            .last()
+            .copied()
+            .unwrap_or(*empty_root);
+
+    let new_root = write_pairs::<_, _, _, _, E>(
+        correlation_id,
+        environment,
+        store,
+        &old_root,
+        &leaves_to_pairs(new_leaves),
+    )?
+    .last()
+    .copied()
+    .unwrap_or(old_root);
+
+    let mut new_state: BTreeMap<_, _> = old_pairs.iter().copied().collect();
+    new_state.extend(leaves_to_pairs(new_leaves));
+    let new_pairs: Vec<_> = new_state.into_iter().collect();
+
+    assert_eq!(
+        diff_roots(environment, store, &old_root, &new_root).unwrap(),
+        expected_diff(&old_pairs, &new_pairs)
+    );
+    assert_eq!(
+        diff_roots(environment, store, &new_root, &old_root).unwrap(),
+        expected_diff(&new_pairs, &old_pairs)
+    );
+    assert!(diff_roots(environment, store, &new_root, &new_root)
+        .unwrap()
+        .is_empty());
+    Ok(())
+}
+
+fn diff_cases() -> [(&'static [TestTrie], &'static [TestTrie]); 6] {
+    [
+        (&[], &TEST_LEAVES),
+        (&TEST_LEAVES, &TEST_LEAVES_UPDATED),
+        (&TEST_LEAVES, &TEST_LEAVES_ADJACENTS),
+        (&TEST_LEAVES_ADJACENTS, &TEST_LEAVES_NON_COLLIDING),
+        (&TEST_LEAVES[..2], &TEST_LEAVES_UPDATED[1..4]),
+        (&TEST_LEAVES[..1], &TEST_LEAVES_ADJACENTS[..1]),
+    ]
+}
+
+#[test]
+fn lmdb_diff_between_writes_had_expected_results() {
+    for (old_leaves, new_leaves) in diff_cases() {
+        let (root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+        let context = LmdbTestContext::new(&tries).unwrap();
+        diff_between_writes_had_expected_results::<_, _, error::Error>(
+            &context.environment,
+            &context.store,
+            &root_hash,
+            old_leaves,
+            new_leaves,
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn in_memory_diff_between_writes_had_expected_results() {
+    for (old_leaves, new_leaves) in diff_cases() {
+        let (root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+        let context = InMemoryTestContext::new(&tries).unwrap();
+        diff_between_writes_had_expected_results::<_, _, in_memory::Error>(
+            &context.environment,
+            &context.store,
+            &root_hash,
+            old_leaves,
+            new_leaves,
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn diff_with_missing_root_is_an_error() {
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[2]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+    let missing_root = Digest::hash(b"missing");
+    assert_eq!(
+        diff_roots(
+            &context.environment,
+            &context.store,
+            &root_hash,
+            &missing_root
+        ),
+        Err(error::Error::TrieNotFound(missing_root))
+    );
+}
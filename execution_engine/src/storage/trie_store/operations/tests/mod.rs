@@ -1,6 +1,9 @@
+mod batch_write;
 mod delete;
+mod diff;
 mod ee_699;
 mod keys;
+mod multi_proof;
 mod proptests;
 mod read;
 mod scan;
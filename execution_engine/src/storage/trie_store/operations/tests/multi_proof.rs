@@ -0,0 +1,138 @@
+use super::*;
+use crate::storage::{
+    trie::merkle_proof::{TrieMerkleMultiProof, TrieMerkleMultiProofError},
+    trie_store::operations::read_with_multi_proof,
+};
+
+fn leaf_keys(leaves: &[TestTrie]) -> Vec<TestKey> {
+    leaves
+        .iter()
+        .map(|leaf| match leaf {
+            Trie::Leaf { key, .. } => *key,
+            _ => panic!("leaves should contain only leaves"),
+        })
+        .collect()
+}
+
+fn read_multi_proof<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    root: &Digest,
+    keys: &[TestKey],
+) -> Result<ReadResult<TrieMerkleMultiProof<TestKey, TestValue>>, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<bytesrepr::Error>,
+{
+    let txn = environment.create_read_txn()?;
+    let result =
+        read_with_multi_proof::<_, _, _, _, E>(CorrelationId::new(), &txn, store, root, keys)?;
+    txn.commit()?;
+    Ok(result)
+}
+
+/// Checks that a multi-proof over all leaves present under `root` verifies and yields their values,
+/// and that asking for an absent key yields no proof at all.
+fn multi_proofs_had_expected_results<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    root: &Digest,
+    present: &[TestTrie],
+    absent: &[TestTrie],
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<bytesrepr::Error>,
+{
+    let keys = leaf_keys(present);
+    let proof = match read_multi_proof::<_, _, E>(environment, store, root, &keys)? {
+        ReadResult::Found(proof) => proof,
+        other => panic!("expected a proof, got {:?}", other),
+    };
+
+    let mut proven = proof.verify(root).expect("proof should verify");
+    proven.sort_by_key(|(key, _)| **key);
+    let mut expected: Vec<_> = present
+        .iter()
+        .filter_map(|leaf| match leaf {
+            Trie::Leaf { key, value } => Some((key, value)),
+            _ => None,
+        })
+        .collect();
+    expected.sort_by_key(|(key, _)| **key);
+    assert_eq!(proven, expected);
+
+    for leaf in present {
+        if let Trie::Leaf { key, value } = leaf {
+            assert_eq!(proof.value(key), Some(value));
+        }
+    }
+
+    let other_root = Digest::hash(b"other root");
+    assert_eq!(
+        proof.verify(&other_root),
+        Err(TrieMerkleMultiProofError::RootNotFound(other_root))
+    );
+
+    for leaf in absent {
+        let mut keys = keys.clone();
+        keys.extend(leaf_keys(&[leaf.clone()]));
+        assert!(matches!(
+            read_multi_proof::<_, _, E>(environment, store, root, &keys)?,
+            ReadResult::NotFound
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn lmdb_multi_proofs_from_n_leaf_partial_trie_had_expected_results() {
+    for (num_leaves, generator) in TEST_TRIE_GENERATORS.iter().enumerate().skip(1) {
+        let (root_hash, tries) = generator().unwrap();
+        let context = LmdbTestContext::new(&tries).unwrap();
+        let (present, absent) = TEST_LEAVES.split_at(num_leaves);
+        multi_proofs_had_expected_results::<_, _, error::Error>(
+            &context.environment,
+            &context.store,
+            &root_hash,
+            present,
+            absent,
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn in_memory_multi_proofs_from_n_leaf_partial_trie_had_expected_results() {
+    for (num_leaves, generator) in TEST_TRIE_GENERATORS.iter().enumerate().skip(1) {
+        let (root_hash, tries) = generator().unwrap();
+        let context = InMemoryTestContext::new(&tries).unwrap();
+        let (present, absent) = TEST_LEAVES.split_at(num_leaves);
+        multi_proofs_had_expected_results::<_, _, in_memory::Error>(
+            &context.environment,
+            &context.store,
+            &root_hash,
+            present,
+            absent,
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn multi_proof_with_missing_root_is_root_not_found() {
+    let (_, tries) = TEST_TRIE_GENERATORS[2]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+    let result = read_multi_proof::<_, _, in_memory::Error>(
+        &context.environment,
+        &context.store,
+        &Digest::hash(b"missing"),
+        &leaf_keys(&TEST_LEAVES[..1]),
+    )
+    .unwrap();
+    assert!(matches!(result, ReadResult::RootNotFound));
+}
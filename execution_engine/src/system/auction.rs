@@ -9,8 +9,9 @@ use num_traits::{CheckedMul, CheckedSub};
 use casper_types::{
     account::AccountHash,
     system::auction::{
-        Bid, DelegationRate, EraInfo, EraValidators, Error, SeigniorageAllocation,
-        SeigniorageRecipients, ValidatorWeights, BLOCK_REWARD, DELEGATION_RATE_DENOMINATOR,
+        Bid, DelegationRate, EraInfo, EraValidators, Error, RewardDestination,
+        SeigniorageAllocation, SeigniorageRecipients, SlashedStake, SlashingOffence,
+        UnbondingPurse, ValidatorWeights, BLOCK_REWARD, DELEGATION_RATE_DENOMINATOR,
     },
     ApiError, EraId, PublicKey, U512,
 };
@@ -57,12 +58,19 @@ pub trait Auction:
     /// Validators cannot create a bid with 0 amount, and the delegation rate can't exceed
     /// [`DELEGATION_RATE_DENOMINATOR`].
     ///
+    /// `minimum_delegation_amount` and `maximum_delegation_amount` optionally limit the amount of
+    /// a single delegation and the total amount a single delegator can delegate to the validator,
+    /// on top of the chain-wide limits.  A limit which is `None` is left unchanged for an existing
+    /// bid.  The minimum can't exceed the maximum.
+    ///
     /// Returns a [`U512`] value indicating total amount of tokens staked for given `public_key`.
     fn add_bid(
         &mut self,
         public_key: PublicKey,
         delegation_rate: DelegationRate,
         amount: U512,
+        minimum_delegation_amount: Option<u64>,
+        maximum_delegation_amount: Option<u64>,
     ) -> Result<U512, ApiError> {
         if !self.allow_auction_bids() {
             // Validation set rotation might be disabled on some private chains and we should not
@@ -80,6 +88,14 @@ pub trait Auction:
             return Err(Error::DelegationRateTooLarge.into());
         }
 
+        if let (Some(minimum), Some(maximum)) =
+            (minimum_delegation_amount, maximum_delegation_amount)
+        {
+            if minimum > maximum {
+                return Err(Error::InvalidDelegationAmountLimits.into());
+            }
+        }
+
         if !self.is_allowed_session_caller(&provided_account_hash) {
             return Err(Error::InvalidContext.into());
         }
@@ -91,6 +107,10 @@ pub trait Auction:
         // Update bids or stakes
         let updated_amount = match self.read_bid(&account_hash)? {
             Some(mut bid) => {
+                bid.with_delegation_amount_limits(
+                    minimum_delegation_amount,
+                    maximum_delegation_amount,
+                )?;
                 if bid.inactive() {
                     bid.activate();
                 }
@@ -130,7 +150,11 @@ pub trait Auction:
                     // unapproved spending limit error.
                     ApiError::from(mint_error)
                 })?;
-                let bid = Bid::unlocked(public_key, bonding_purse, amount, delegation_rate);
+                let mut bid = Bid::unlocked(public_key, bonding_purse, amount, delegation_rate);
+                bid.with_delegation_amount_limits(
+                    minimum_delegation_amount,
+                    maximum_delegation_amount,
+                )?;
                 self.write_bid(account_hash, bid)?;
                 amount
             }
@@ -205,6 +229,12 @@ pub trait Auction:
     /// Adds a new delegator to delegators or increases its current stake. If the target validator
     /// is missing, the function call returns an error and does nothing.
     ///
+    /// If the validator has reserved its delegation slots, only delegators with a reservation can
+    /// delegate.  Reservations count toward `max_delegators_per_validator`.
+    ///
+    /// The amount must respect both the chain-wide minimum delegation amount and the delegation
+    /// amount limits set by the validator in its bid.
+    ///
     /// The function transfers motes from the source purse to the delegator's bonding purse.
     ///
     /// This entry point returns the number of tokens currently delegated to a given validator.
@@ -238,10 +268,15 @@ pub trait Auction:
 
         let bid = detail::read_bid_for_validator(self, validator_account_hash)?;
 
-        let delegator_already_exists = bid.delegators().contains_key(&delegator_public_key);
+        if !bid.accepts_delegator(&delegator_public_key) {
+            return Err(Error::DelegatorNotReserved.into());
+        }
+
+        let delegator_already_exists = bid.delegators().contains_key(&delegator_public_key)
+            || bid.reserved_delegators().contains(&delegator_public_key);
 
         if let Some(max_delegators_per_validator) = max_delegators_per_validator {
-            if bid.delegators().len() >= max_delegators_per_validator as usize
+            if bid.occupied_delegator_slots() >= max_delegators_per_validator as usize
                 && !delegator_already_exists
             {
                 return Err(Error::ExceededDelegatorSizeLimit.into());
@@ -252,6 +287,8 @@ pub trait Auction:
             return Err(Error::DelegationAmountTooSmall.into());
         }
 
+        bid.check_delegation_amount(&delegator_public_key, amount)?;
+
         detail::handle_delegation(
             self,
             bid,
@@ -311,6 +348,7 @@ pub trait Auction:
             }
             None => return Err(Error::DelegatorNotFound),
         };
+        bid.prune_delegator_reward_destinations();
 
         self.write_bid(validator_account_hash, bid)?;
 
@@ -325,6 +363,10 @@ pub trait Auction:
     /// The arguments are the delegator's key, the validator's key, the amount,
     /// and the new validator's key.
     ///
+    /// The amount is checked against the delegation amount limits and reservations of the new
+    /// validator both now and when the redelegation is processed.  If the redelegation is not
+    /// allowed at that point, the amount is unbonded instead.
+    ///
     /// Returns the remaining bid amount if the new validator is inactive.
     fn redelegate(
         &mut self,
@@ -345,6 +387,13 @@ pub trait Auction:
             return Err(Error::DelegationAmountTooSmall);
         }
 
+        if let Some(new_validator_bid) = self.read_bid(&new_validator.to_account_hash())? {
+            if !new_validator_bid.accepts_delegator(&delegator_public_key) {
+                return Err(Error::DelegatorNotReserved);
+            }
+            new_validator_bid.check_delegation_amount(&delegator_public_key, amount)?;
+        }
+
         let validator_account_hash = AccountHash::from(&validator_public_key);
 
         let mut bid = match self.read_bid(&validator_account_hash)? {
@@ -374,48 +423,184 @@ pub trait Auction:
             }
             None => return Err(Error::DelegatorNotFound),
         };
+        bid.prune_delegator_reward_destinations();
 
         self.write_bid(validator_account_hash, bid)?;
 
         Ok(new_amount)
     }
 
-    /// Slashes each validator.
+    /// Transfers specified amount of motes of a delegation to a given validator to another
+    /// delegator, without unbonding.  The motes are moved from the delegator's bonding purse to
+    /// the new delegator's one, and the delegator's entry is removed from the delegators map if
+    /// the remaining amount is 0.
+    ///
+    /// The arguments are the delegator's key, the validator's key, the amount, and the new
+    /// delegator's key.  The new delegator has to be accepted by the validator's reservations,
+    /// delegation amount limits and delegator cap, as if it delegated the amount itself.
+    ///
+    /// Returns the remaining bid amount of the delegator.
+    fn transfer_delegation(
+        &mut self,
+        delegator_public_key: PublicKey,
+        validator_public_key: PublicKey,
+        amount: U512,
+        new_delegator_public_key: PublicKey,
+        max_delegators_per_validator: Option<u32>,
+        minimum_delegation_amount: u64,
+    ) -> Result<U512, ApiError> {
+        if !self.allow_delegation_transfers() {
+            return Err(Error::DelegationTransfersDisabled.into());
+        }
+
+        let provided_account_hash =
+            AccountHash::from_public_key(&delegator_public_key, |x| self.blake2b(x));
+
+        if !self.is_allowed_session_caller(&provided_account_hash) {
+            return Err(Error::InvalidContext.into());
+        }
+
+        if new_delegator_public_key == delegator_public_key {
+            return Err(Error::DelegationTransferToSelf.into());
+        }
+
+        if amount.is_zero() || amount < U512::from(minimum_delegation_amount) {
+            return Err(Error::DelegationAmountTooSmall.into());
+        }
+
+        let validator_account_hash = AccountHash::from(&validator_public_key);
+
+        let mut bid = detail::read_bid_for_validator(self, validator_account_hash)?;
+
+        if !bid.accepts_delegator(&new_delegator_public_key) {
+            return Err(Error::DelegatorNotReserved.into());
+        }
+
+        bid.check_delegation_amount(&new_delegator_public_key, amount)?;
+
+        let era_end_timestamp_millis = detail::get_era_end_timestamp_millis(self)?;
+        let delegators = bid.delegators_mut();
+        let (source, remaining_amount) = match delegators.get_mut(&delegator_public_key) {
+            Some(delegator) => {
+                let remaining_amount =
+                    delegator.decrease_stake(amount, era_end_timestamp_millis)?;
+                (*delegator.bonding_purse(), remaining_amount)
+            }
+            None => return Err(Error::DelegatorNotFound.into()),
+        };
+        if remaining_amount.is_zero() {
+            delegators.remove(&delegator_public_key);
+        }
+        bid.prune_delegator_reward_destinations();
+
+        let new_delegator_already_exists = bid.delegators().contains_key(&new_delegator_public_key)
+            || bid
+                .reserved_delegators()
+                .contains(&new_delegator_public_key);
+
+        if let Some(max_delegators_per_validator) = max_delegators_per_validator {
+            if bid.occupied_delegator_slots() >= max_delegators_per_validator as usize
+                && !new_delegator_already_exists
+            {
+                return Err(Error::ExceededDelegatorSizeLimit.into());
+            }
+        }
+
+        detail::handle_delegation(
+            self,
+            bid,
+            new_delegator_public_key,
+            validator_public_key,
+            source,
+            amount,
+        )?;
+
+        Ok(remaining_amount)
+    }
+
+    /// Slashes each validator by burning the given fraction of its stake, its delegators'
+    /// stakes and all unbonding purses created against it.  A bid left without stake is
+    /// deactivated.
+    ///
+    /// The burned amounts are added to the slashes recorded in the current era info.
     ///
     /// This can be only invoked through a system call.
-    fn slash(&mut self, validator_public_keys: Vec<PublicKey>) -> Result<(), Error> {
+    fn slash(
+        &mut self,
+        slashes: BTreeMap<PublicKey, (SlashingOffence, Ratio<u64>)>,
+    ) -> Result<(), Error> {
         if self.get_caller() != PublicKey::System.to_account_hash() {
             return Err(Error::InvalidCaller);
         }
 
         let mut burned_amount: U512 = U512::zero();
+        let mut slashed_stakes = Vec::new();
 
-        for validator_public_key in validator_public_keys {
-            // Burn stake, deactivate
+        for (validator_public_key, (offence, fraction)) in slashes {
             let validator_account_hash = AccountHash::from(&validator_public_key);
+            let mut validator_amount = U512::zero();
+            let mut delegators_amount = U512::zero();
+
+            // Burn a fraction of the validator's and its delegators' stakes
             if let Some(mut bid) = self.read_bid(&validator_account_hash)? {
-                burned_amount += *bid.staked_amount();
-                *bid.staked_amount_mut() = U512::zero();
-                bid.deactivate();
-                // Reset delegator stakes when deactivating validator bid.
+                let slashed = detail::slashed_amount(*bid.staked_amount(), fraction)?;
+                *bid.staked_amount_mut() -= slashed;
+                validator_amount += slashed;
+                if bid.staked_amount().is_zero() {
+                    bid.deactivate();
+                }
                 for delegator in bid.delegators_mut().values_mut() {
-                    *delegator.staked_amount_mut() = U512::zero();
+                    let slashed = detail::slashed_amount(*delegator.staked_amount(), fraction)?;
+                    *delegator.staked_amount_mut() -= slashed;
+                    delegators_amount += slashed;
                 }
                 self.write_bid(validator_account_hash, bid)?;
             };
 
-            let validator_account_hash = AccountHash::from(&validator_public_key);
-            // Update unbonding entries for given validator
+            // Burn the same fraction of unbonding entries for given validator
             let unbonding_purses = self.read_unbond(&validator_account_hash)?;
             if !unbonding_purses.is_empty() {
-                burned_amount += unbonding_purses
-                    .into_iter()
-                    .map(|unbonding_purse| *unbonding_purse.amount())
-                    .sum();
-                self.write_unbond(validator_account_hash, Vec::new())?;
+                let mut remaining_purses = Vec::with_capacity(unbonding_purses.len());
+                for unbonding_purse in unbonding_purses {
+                    let slashed = detail::slashed_amount(*unbonding_purse.amount(), fraction)?;
+                    if unbonding_purse.is_validator() {
+                        validator_amount += slashed;
+                    } else {
+                        delegators_amount += slashed;
+                    }
+                    let remaining_amount = *unbonding_purse.amount() - slashed;
+                    if !remaining_amount.is_zero() {
+                        remaining_purses.push(UnbondingPurse::new(
+                            *unbonding_purse.bonding_purse(),
+                            unbonding_purse.validator_public_key().clone(),
+                            unbonding_purse.unbonder_public_key().clone(),
+                            unbonding_purse.era_of_creation(),
+                            remaining_amount,
+                            unbonding_purse.new_validator().clone(),
+                        ));
+                    }
+                }
+                self.write_unbond(validator_account_hash, remaining_purses)?;
+            }
+
+            let slashed_stake = SlashedStake::new(
+                validator_public_key,
+                offence,
+                validator_amount,
+                delegators_amount,
+            );
+            if !slashed_stake.total_amount().is_zero() {
+                burned_amount += slashed_stake.total_amount();
+                slashed_stakes.push(slashed_stake);
             }
         }
 
+        if !slashed_stakes.is_empty() {
+            let mut era_info = self.read_era_info()?.unwrap_or_default();
+            era_info.slashes_mut().extend(slashed_stakes);
+            self.record_era_info(None, era_info)?;
+        }
+
         self.reduce_total_supply(burned_amount)?;
 
         Ok(())
@@ -425,12 +610,21 @@ pub trait Auction:
     /// added to their delegators') ordered by size from largest to smallest, then takes the top N
     /// (number of auction slots) bidders and replaces era_validators with these.
     ///
+    /// Validators in `evicted_validators` were reported as inactive by consensus, which only
+    /// happens if they didn't participate at all in the era: there is no threshold on partial
+    /// participation.  Once inactive for `inactivity_jail_threshold` consecutive eras, a validator
+    /// is jailed: its bid is deactivated for a period starting at `jail_period_eras` and doubling
+    /// with every repeat offence, up to `max_jail_period_eras`.
+    ///
     /// Accessed by: node
     fn run_auction(
         &mut self,
         era_end_timestamp_millis: u64,
         evicted_validators: Vec<PublicKey>,
         max_delegators_per_validator: Option<u32>,
+        inactivity_jail_threshold: u32,
+        jail_period_eras: u64,
+        max_jail_period_eras: u64,
     ) -> Result<(), ApiError> {
         if self.get_caller() != PublicKey::System.to_account_hash() {
             return Err(Error::InvalidCaller.into());
@@ -457,9 +651,38 @@ pub trait Auction:
                 bids_modified = true;
             }
 
-            if evicted_validators.contains(validator_public_key) {
-                bids_modified = bid.deactivate()
+            if !evicted_validators.contains(validator_public_key) {
+                if bid.record_active_era() {
+                    bids_modified = true;
+                }
+                continue;
+            }
+
+            if bid.inactive() {
+                continue;
+            }
+
+            bids_modified = true;
+            if bid.record_inactive_era() < inactivity_jail_threshold {
+                continue;
             }
+
+            let period = detail::jail_period_eras(
+                bid.jail_count().saturating_add(1),
+                jail_period_eras,
+                max_jail_period_eras,
+            );
+            let jailed_until = if period == 0 {
+                None
+            } else {
+                // The validator is jailed for `period` eras following the upcoming one.
+                let jailed_until = era_id
+                    .checked_add(1)
+                    .and_then(|next_era_id| next_era_id.checked_add(period))
+                    .ok_or(Error::ArithmeticOverflow)?;
+                Some(jailed_until)
+            };
+            bid.jail(jailed_until);
         }
 
         // Compute next auction winners
@@ -629,23 +852,23 @@ pub trait Auction:
 
             let validators_part: Ratio<U512> = total_reward - Ratio::from(total_delegator_payout);
             let validator_reward = validators_part.to_integer();
-            let validator_bonding_purse = detail::reinvest_validator_reward(
+            let validator_payout_purse = detail::reinvest_validator_reward(
                 self,
                 seigniorage_allocations,
                 public_key.clone(),
                 validator_reward,
             )?;
 
-            self.mint_into_existing_purse(validator_reward, validator_bonding_purse)
+            self.mint_into_existing_purse(validator_reward, validator_payout_purse)
                 .map_err(Error::from)?;
 
-            for (_delegator_account_hash, delegator_payout, bonding_purse) in delegator_payouts {
-                self.mint_into_existing_purse(delegator_payout, bonding_purse)
+            for (_delegator_account_hash, delegator_payout, payout_purse) in delegator_payouts {
+                self.mint_into_existing_purse(delegator_payout, payout_purse)
                     .map_err(Error::from)?;
             }
         }
 
-        self.record_era_info(EraId::new(u64::MAX), era_info)?;
+        self.record_era_info(None, era_info)?;
 
         Ok(())
     }
@@ -657,7 +880,49 @@ pub trait Auction:
 
     /// Activates a given validator's bid.  To be used when a validator has been marked as inactive
     /// by consensus (aka "evicted").
+    ///
+    /// Superseded by [`Auction::unjail`], which it calls.
     fn activate_bid(&mut self, validator_public_key: PublicKey) -> Result<(), Error> {
+        self.unjail(validator_public_key)
+    }
+
+    /// Reactivates a given validator's bid after it has been jailed for being inactive.
+    ///
+    /// Fails with [`Error::ValidatorJailed`] while the validator's jail period is not over.
+    fn unjail(&mut self, validator_public_key: PublicKey) -> Result<(), Error> {
+        let provided_account_hash =
+            AccountHash::from_public_key(&validator_public_key, |x| self.blake2b(x));
+
+        if !self.is_allowed_session_caller(&provided_account_hash) {
+            return Err(Error::InvalidContext);
+        }
+
+        let mut bid = match self.read_bid(&provided_account_hash)? {
+            Some(bid) => bid,
+            None => return Err(Error::ValidatorNotFound),
+        };
+
+        let era_id = detail::get_era_id(self)?;
+        bid.unjail(era_id)?;
+
+        self.write_bid(provided_account_hash, bid)?;
+
+        Ok(())
+    }
+
+    /// Reserves delegation slots of a given validator for `delegators`.
+    ///
+    /// Once a validator has reserved any slot, only delegators with a reservation can delegate to
+    /// it.  Reservations count toward `max_delegators_per_validator`, along with delegators
+    /// without a reservation, and can't exceed `max_reserved_delegators_per_validator` even if the
+    /// number of delegators is unlimited.
+    fn add_reservations(
+        &mut self,
+        validator_public_key: PublicKey,
+        delegators: Vec<PublicKey>,
+        max_delegators_per_validator: Option<u32>,
+        max_reserved_delegators_per_validator: u32,
+    ) -> Result<(), Error> {
         let provided_account_hash =
             AccountHash::from_public_key(&validator_public_key, |x| self.blake2b(x));
 
@@ -670,10 +935,106 @@ pub trait Auction:
             None => return Err(Error::ValidatorNotFound),
         };
 
-        bid.activate();
+        bid.reserved_delegators_mut().extend(delegators);
+
+        if bid.reserved_delegators().len() > max_reserved_delegators_per_validator as usize {
+            return Err(Error::ExceededReservationsLimit);
+        }
+
+        if let Some(max_delegators_per_validator) = max_delegators_per_validator {
+            if bid.occupied_delegator_slots() > max_delegators_per_validator as usize {
+                return Err(Error::ExceededDelegatorSizeLimit);
+            }
+        }
 
         self.write_bid(provided_account_hash, bid)?;
 
         Ok(())
     }
+
+    /// Cancels the reservations of a given validator's delegation slots for `delegators`.
+    ///
+    /// Delegators losing their reservation keep their stake.
+    fn cancel_reservations(
+        &mut self,
+        validator_public_key: PublicKey,
+        delegators: Vec<PublicKey>,
+    ) -> Result<(), Error> {
+        let provided_account_hash =
+            AccountHash::from_public_key(&validator_public_key, |x| self.blake2b(x));
+
+        if !self.is_allowed_session_caller(&provided_account_hash) {
+            return Err(Error::InvalidContext);
+        }
+
+        let mut bid = match self.read_bid(&provided_account_hash)? {
+            Some(bid) => bid,
+            None => return Err(Error::ValidatorNotFound),
+        };
+
+        for delegator in &delegators {
+            if !bid.reserved_delegators_mut().remove(delegator) {
+                return Err(Error::ReservationNotFound);
+            }
+        }
+
+        self.write_bid(provided_account_hash, bid)?;
+
+        Ok(())
+    }
+
+    /// Sets where the seigniorage of a given validator, or of one of its delegators if
+    /// `delegator_public_key` is given, is paid from the next distribution on.
+    ///
+    /// Seigniorage is added to the stake by default.  Seigniorage paid to a purse or an account
+    /// which no longer exists at distribution is added to the stake instead.
+    fn set_reward_destination(
+        &mut self,
+        validator_public_key: PublicKey,
+        delegator_public_key: Option<PublicKey>,
+        reward_destination: RewardDestination,
+    ) -> Result<(), Error> {
+        let owner_public_key = delegator_public_key
+            .as_ref()
+            .unwrap_or(&validator_public_key);
+        let provided_account_hash =
+            AccountHash::from_public_key(owner_public_key, |x| self.blake2b(x));
+
+        if !self.is_allowed_session_caller(&provided_account_hash) {
+            return Err(Error::InvalidContext);
+        }
+
+        let reward_destination = match reward_destination {
+            RewardDestination::Purse(purse) if !purse.is_addable() => {
+                return Err(Error::InvalidRewardDestination)
+            }
+            RewardDestination::Purse(purse) => RewardDestination::Purse(purse.into_add()),
+            reward_destination => reward_destination,
+        };
+        if !reward_destination.is_compound()
+            && detail::reward_payout_purse(self, reward_destination)?.is_none()
+        {
+            return Err(Error::InvalidRewardDestination);
+        }
+
+        let validator_account_hash = AccountHash::from(&validator_public_key);
+        let mut bid = match self.read_bid(&validator_account_hash)? {
+            Some(bid) => bid,
+            None => return Err(Error::ValidatorNotFound),
+        };
+
+        match delegator_public_key {
+            Some(delegator_public_key) => {
+                if !bid.delegators().contains_key(&delegator_public_key) {
+                    return Err(Error::DelegatorNotFound);
+                }
+                bid.set_delegator_reward_destination(delegator_public_key, reward_destination);
+            }
+            None => bid.set_reward_destination(reward_destination),
+        }
+
+        self.write_bid(validator_account_hash, bid)?;
+
+        Ok(())
+    }
 }
@@ -6,16 +6,18 @@ use casper_types::{
     account::AccountHash,
     bytesrepr::{FromBytes, ToBytes},
     system::auction::{
-        Bids, Delegator, Error, SeigniorageAllocation, SeigniorageRecipientsSnapshot,
-        UnbondingPurse, UnbondingPurses, AUCTION_DELAY_KEY, ERA_END_TIMESTAMP_MILLIS_KEY,
-        ERA_ID_KEY, SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY, UNBONDING_DELAY_KEY, VALIDATOR_SLOTS_KEY,
+        Bids, Delegator, Error, RewardDestination, SeigniorageAllocation,
+        SeigniorageRecipientsSnapshot, UnbondingPurse, UnbondingPurses, AUCTION_DELAY_KEY,
+        ERA_END_TIMESTAMP_MILLIS_KEY, ERA_ID_KEY, SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY,
+        UNBONDING_DELAY_KEY, VALIDATOR_SLOTS_KEY,
     },
     ApiError, CLTyped, EraId, Key, KeyTag, PublicKey, URef, U512,
 };
 use tracing::error;
 
 use super::{
-    Auction, Bid, EraValidators, MintProvider, RuntimeProvider, StorageProvider, ValidatorWeights,
+    AccountProvider, Auction, Bid, EraValidators, MintProvider, RuntimeProvider, StorageProvider,
+    ValidatorWeights,
 };
 
 fn read_from<P, T>(provider: &mut P, name: &str) -> Result<T, Error>
@@ -217,9 +219,10 @@ pub(crate) fn process_unbond_requests<P: Auction + ?Sized>(
                         match provider.read_bid(&new_validator.to_account_hash()) {
                             Ok(Some(new_validator_bid)) => {
                                 if !new_validator_bid.staked_amount().is_zero() {
-                                    if is_under_max_delegator_cap(
+                                    if accepts_redelegation(
                                         max_delegators_per_validator,
-                                        new_validator_bid.delegators().len(),
+                                        &new_validator_bid,
+                                        unbonding_purse,
                                     ) {
                                         handle_delegation(
                                             provider,
@@ -234,7 +237,7 @@ pub(crate) fn process_unbond_requests<P: Auction + ?Sized>(
                                         // Move funds from bid purse to unbonding purse
                                         provider.unbond(unbonding_purse).map_err(|err| {
                                             error!(
-                                            "Error unbonding purse {err:?} (delegator cap reached for new validator)"
+                                            "Error unbonding purse {err:?} (redelegation not allowed by new validator)"
                                         );
                                             ApiError::from(Error::TransferToUnbondingPurse)
                                         })?
@@ -308,7 +311,35 @@ pub(crate) fn create_unbonding_purse<P: Auction + ?Sized>(
     Ok(())
 }
 
-/// Reinvests delegator reward by increasing its stake.
+/// Returns the purse into which a reward paid to `reward_destination` is minted, or `None` if the
+/// reward is added to the stake instead.
+///
+/// Rewards destined to a purse or an account which no longer exists are added to the stake.
+pub(crate) fn reward_payout_purse<P>(
+    provider: &mut P,
+    reward_destination: RewardDestination,
+) -> Result<Option<URef>, Error>
+where
+    P: MintProvider + AccountProvider + ?Sized,
+{
+    let purse = match reward_destination {
+        RewardDestination::Compound => return Ok(None),
+        RewardDestination::Purse(purse) => purse,
+        RewardDestination::Account(account_hash) => {
+            match provider.read_main_purse(&account_hash)? {
+                Some(main_purse) => main_purse.into_add(),
+                None => return Ok(None),
+            }
+        }
+    };
+    if provider.get_balance(purse)?.is_none() {
+        return Ok(None);
+    }
+    Ok(Some(purse))
+}
+
+/// Reinvests delegator rewards by increasing their stakes, or pays them to their reward
+/// destinations, and returns the purses to mint them into.
 pub fn reinvest_delegator_rewards<P>(
     provider: &mut P,
     seigniorage_allocations: &mut Vec<SeigniorageAllocation>,
@@ -316,7 +347,7 @@ pub fn reinvest_delegator_rewards<P>(
     rewards: impl Iterator<Item = (PublicKey, Ratio<U512>)>,
 ) -> Result<Vec<(AccountHash, U512, URef)>, Error>
 where
-    P: StorageProvider,
+    P: StorageProvider + MintProvider + AccountProvider,
 {
     let mut delegator_payouts = Vec::new();
 
@@ -327,29 +358,43 @@ where
         None => return Err(Error::ValidatorNotFound),
     };
 
-    let delegators = bid.delegators_mut();
+    bid.prune_delegator_reward_destinations();
 
     for (delegator_key, delegator_reward) in rewards {
-        let delegator = match delegators.get_mut(&delegator_key) {
+        if !bid.delegators().contains_key(&delegator_key) {
+            continue;
+        }
+
+        let reward_destination = bid.delegator_reward_destination(&delegator_key);
+        let payout_purse = reward_payout_purse(provider, reward_destination)?;
+
+        let delegator = match bid.delegators_mut().get_mut(&delegator_key) {
             Some(delegator) => delegator,
             None => continue,
         };
 
         let delegator_reward_trunc = delegator_reward.to_integer();
 
-        delegator.increase_stake(delegator_reward_trunc)?;
+        let (payout_purse, reward_destination) = match payout_purse {
+            Some(payout_purse) => (payout_purse, reward_destination),
+            None => {
+                delegator.increase_stake(delegator_reward_trunc)?;
+                (*delegator.bonding_purse(), RewardDestination::Compound)
+            }
+        };
 
         delegator_payouts.push((
             delegator_key.to_account_hash(),
             delegator_reward_trunc,
-            *delegator.bonding_purse(),
+            payout_purse,
         ));
 
         let allocation = SeigniorageAllocation::delegator(
             delegator_key,
             validator_public_key.clone(),
             delegator_reward_trunc,
-        );
+        )
+        .with_reward_destination(reward_destination);
 
         seigniorage_allocations.push(allocation);
     }
@@ -359,7 +404,8 @@ where
     Ok(delegator_payouts)
 }
 
-/// Reinvests validator reward by increasing its stake and returns its bonding purse.
+/// Reinvests validator reward by increasing its stake, or pays it to its reward destination, and
+/// returns the purse to mint it into.
 pub fn reinvest_validator_reward<P>(
     provider: &mut P,
     seigniorage_allocations: &mut Vec<SeigniorageAllocation>,
@@ -367,7 +413,7 @@ pub fn reinvest_validator_reward<P>(
     amount: U512,
 ) -> Result<URef, Error>
 where
-    P: StorageProvider,
+    P: StorageProvider + MintProvider + AccountProvider,
 {
     let validator_account_hash = AccountHash::from(&validator_public_key);
 
@@ -378,17 +424,24 @@ where
         }
     };
 
-    bid.increase_stake(amount)?;
+    let reward_destination = bid.reward_destination();
+    let (payout_purse, reward_destination) =
+        match reward_payout_purse(provider, reward_destination)? {
+            Some(payout_purse) => (payout_purse, reward_destination),
+            None => {
+                bid.increase_stake(amount)?;
+                (*bid.bonding_purse(), RewardDestination::Compound)
+            }
+        };
 
-    let allocation = SeigniorageAllocation::validator(validator_public_key, amount);
+    let allocation = SeigniorageAllocation::validator(validator_public_key, amount)
+        .with_reward_destination(reward_destination);
 
     seigniorage_allocations.push(allocation);
 
-    let bonding_purse = *bid.bonding_purse();
-
     provider.write_bid(validator_account_hash, bid)?;
 
-    Ok(bonding_purse)
+    Ok(payout_purse)
 }
 
 pub(crate) fn handle_delegation<P>(
@@ -494,6 +547,61 @@ pub(crate) fn era_validators_from_snapshot(
         .collect()
 }
 
+/// Returns the part of `amount` burned when slashing by `fraction`, rounded down.
+///
+/// Fractions of one or more burn the whole amount.
+pub(crate) fn slashed_amount(amount: U512, fraction: Ratio<u64>) -> Result<U512, Error> {
+    if fraction.numer() >= fraction.denom() {
+        return Ok(amount);
+    }
+    amount
+        .checked_mul(U512::from(*fraction.numer()))
+        .map(|product| product / U512::from(*fraction.denom()))
+        .ok_or(Error::ArithmeticOverflow)
+}
+
+/// Returns the number of eras a validator is jailed for on its `jail_count`-th offence.
+///
+/// The period starts at `jail_period_eras` and doubles with every repeat offence, up to
+/// `max_jail_period_eras`.
+pub(crate) fn jail_period_eras(
+    jail_count: u32,
+    jail_period_eras: u64,
+    max_jail_period_eras: u64,
+) -> u64 {
+    let multiplier = 1u64
+        .checked_shl(jail_count.saturating_sub(1))
+        .unwrap_or(u64::MAX);
+    jail_period_eras
+        .saturating_mul(multiplier)
+        .min(max_jail_period_eras)
+}
+
+/// Returns `true` if the redelegation of `unbonding_purse` to the validator of
+/// `new_validator_bid` respects the validator's reservations, delegation amount limits and
+/// delegator cap.
+fn accepts_redelegation(
+    max_delegators_per_validator: Option<u32>,
+    new_validator_bid: &Bid,
+    unbonding_purse: &UnbondingPurse,
+) -> bool {
+    let delegator_public_key = unbonding_purse.unbonder_public_key();
+    if !new_validator_bid.accepts_delegator(delegator_public_key)
+        || new_validator_bid
+            .check_delegation_amount(delegator_public_key, *unbonding_purse.amount())
+            .is_err()
+    {
+        return false;
+    }
+    new_validator_bid
+        .reserved_delegators()
+        .contains(delegator_public_key)
+        || is_under_max_delegator_cap(
+            max_delegators_per_validator,
+            new_validator_bid.occupied_delegator_slots(),
+        )
+}
+
 fn is_under_max_delegator_cap(
     max_delegators_per_validator: Option<u32>,
     new_validator_delegator_len: usize,
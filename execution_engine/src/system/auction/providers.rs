@@ -32,6 +32,9 @@ pub trait RuntimeProvider {
 
     /// Check if auction bids are allowed.
     fn allow_auction_bids(&self) -> bool;
+
+    /// Check if delegations can be transferred between delegators.
+    fn allow_delegation_transfers(&self) -> bool;
 }
 
 /// Provides functionality of a contract storage.
@@ -58,8 +61,15 @@ pub trait StorageProvider {
         unbonding_purses: Vec<UnbondingPurse>,
     ) -> Result<(), Error>;
 
+    /// Reads the most recently recorded era summary.
+    fn read_era_info(&mut self) -> Result<Option<EraInfo>, Error>;
+
     /// Records era summary.
-    fn record_era_info(&mut self, _era_id: EraId, era_summary: EraInfo) -> Result<(), Error>;
+    ///
+    /// The summary replaces the most recently recorded one regardless of `era_id`, which may be
+    /// `None` when the caller doesn't track the era the summary belongs to.
+    fn record_era_info(&mut self, era_id: Option<EraId>, era_summary: EraInfo)
+        -> Result<(), Error>;
 }
 
 /// Provides an access to mint.
@@ -105,4 +115,7 @@ pub trait MintProvider {
 pub trait AccountProvider {
     /// Get currently executing account's purse.
     fn get_main_purse(&self) -> Result<URef, Error>;
+
+    /// Reads the main purse of the account `account_hash`, if it exists.
+    fn read_main_purse(&mut self, account_hash: &AccountHash) -> Result<Option<URef>, Error>;
 }
@@ -33,6 +33,12 @@ pub trait HandlePayment: MintProvider + RuntimeProvider + StorageProvider + Size
         Ok(maybe_purse.map(|p| p.remove_access_rights()))
     }
 
+    /// Pays `amount` from a sponsor's `purse` into the payment purse, refunding any unspent payment
+    /// to the same purse.
+    fn sponsor_payment(&mut self, purse: URef, amount: U512) -> Result<(), Error> {
+        internal::sponsor_payment(self, purse, amount)
+    }
+
     /// Finalize payment with `amount_spent` and a given `account`.
     fn finalize_payment(
         &mut self,
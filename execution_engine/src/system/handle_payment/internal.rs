@@ -48,6 +48,31 @@ pub(crate) fn get_refund_purse<R: RuntimeProvider>(
     }
 }
 
+/// Transfers `amount` from a sponsor's `purse` into the payment purse and sets `purse` as the refund
+/// purse, so that the sponsor rather than the deployer gets back any unspent payment.  Only callable
+/// in the payment phase by the contract the engine called to approve payment for a sponsored deploy,
+/// so that other payment code can't have a sponsor pay for a deploy it didn't approve.
+pub(crate) fn sponsor_payment<P: MintProvider + RuntimeProvider>(
+    provider: &mut P,
+    purse: URef,
+    amount: U512,
+) -> Result<(), Error> {
+    if provider.get_phase() != Phase::Payment {
+        return Err(Error::SponsorPaymentCalledOutsidePayment);
+    }
+    if !provider.is_payment_sponsor_caller() {
+        return Err(Error::SponsorPaymentCalledByNonSponsor);
+    }
+
+    let payment_purse = get_payment_purse(provider)?;
+    if purse.remove_access_rights() == payment_purse.remove_access_rights() {
+        return Err(Error::RefundPurseIsPaymentPurse);
+    }
+
+    provider.transfer_purse_to_purse(purse, payment_purse, amount)?;
+    provider.put_key(REFUND_PURSE_KEY, Key::URef(purse))
+}
+
 /// Returns tuple where 1st element is the refund, and 2nd element is the fee.
 ///
 /// # Note
@@ -24,6 +24,10 @@ pub trait RuntimeProvider {
     /// Get caller.
     fn get_caller(&self) -> AccountHash;
 
+    /// Checks if the immediate caller is the contract the engine called to approve the payment of
+    /// a sponsored deploy.
+    fn is_payment_sponsor_caller(&self) -> bool;
+
     /// Get refund handling.
     fn refund_handling(&self) -> &RefundHandling;
 
@@ -2,6 +2,8 @@ pub(crate) mod runtime_provider;
 pub(crate) mod storage_provider;
 pub(crate) mod system_provider;
 
+use std::collections::BTreeMap;
+
 use num_rational::Ratio;
 use num_traits::CheckedMul;
 use tracing::warn;
@@ -9,10 +11,17 @@ use tracing::warn;
 use casper_types::{
     account::AccountHash,
     system::{
-        mint::{Error, ROUND_SEIGNIORAGE_RATE_KEY, TOTAL_SUPPLY_KEY},
+        mint::{
+            allowance_dictionary_item_key, Allowance, Error, ScheduledTransfer,
+            ScheduledTransferResult, TransferSchedule, ALLOWANCES_KEY, MAX_SCHEDULED_TRANSFERS,
+            MAX_SCHEDULED_TRANSFERS_PER_OWNER, ROUND_SEIGNIORAGE_RATE_KEY,
+            SCHEDULED_TRANSFERS_CURSOR_KEY, SCHEDULED_TRANSFERS_DUE_KEY, SCHEDULED_TRANSFERS_KEY,
+            SCHEDULED_TRANSFERS_PENDING_KEY, SCHEDULED_TRANSFER_COUNTS_KEY,
+            SCHEDULED_TRANSFER_NEXT_ID_KEY, SCHEDULED_TRANSFER_WINDOW_MILLIS, TOTAL_SUPPLY_KEY,
+        },
         CallStackElement,
     },
-    Key, Phase, PublicKey, StoredValue, URef, U512,
+    AccessRights, EraId, Key, Phase, PublicKey, StoredValue, URef, URefAddr, U512,
 };
 
 use crate::{
@@ -240,7 +249,7 @@ pub trait Mint: RuntimeProvider + StorageProvider + SystemProvider {
         if amount > source_balance {
             return Err(Error::InsufficientFunds);
         }
-        if self.read_balance(target)?.is_none() {
+        if !self.balance_exists(target)? {
             return Err(Error::DestNotFound);
         }
         if self.get_caller() != PublicKey::System.to_account_hash()
@@ -298,7 +307,7 @@ pub trait Mint: RuntimeProvider + StorageProvider + SystemProvider {
             // treat as noop
             return Ok(());
         }
-        if self.read_balance(existing_purse)?.is_none() {
+        if !self.balance_exists(existing_purse)? {
             return Err(Error::PurseNotFound);
         }
         self.add_balance(existing_purse, amount)?;
@@ -317,4 +326,531 @@ pub trait Mint: RuntimeProvider + StorageProvider + SystemProvider {
         self.add(total_supply_uref, amount)?;
         Ok(())
     }
+
+    /// Grants `spender` an allowance to transfer up to `amount` out of `purse` until the block time
+    /// `expires_at`, replacing any allowance previously granted over that purse. A zero `amount`
+    /// revokes the allowance.
+    fn approve_allowance(
+        &mut self,
+        purse: URef,
+        spender: Key,
+        amount: U512,
+        expires_at: Option<u64>,
+    ) -> Result<(), Error> {
+        if !matches!(spender, Key::Account(_) | Key::Hash(_)) {
+            return Err(Error::InvalidAllowanceSpender);
+        }
+        if !purse.is_writeable() {
+            return Err(Error::InvalidAccessRights);
+        }
+        if self.read_balance(purse)?.is_none() {
+            return Err(Error::PurseNotFound);
+        }
+        let allowances_uref = match self.get_key(ALLOWANCES_KEY) {
+            Some(Key::URef(uref)) => uref,
+            Some(_) => return Err(Error::MissingKey),
+            None => {
+                // the allowances dictionary is created on first use
+                let uref = self.new_uref(())?;
+                self.put_key(ALLOWANCES_KEY, Key::URef(uref))?;
+                uref
+            }
+        };
+        let dictionary_item_key =
+            allowance_dictionary_item_key(purse.addr(), &spender).map_err(|_| Error::Serialize)?;
+        self.write_dictionary(
+            allowances_uref,
+            &dictionary_item_key,
+            Allowance::new(amount, expires_at),
+        )
+    }
+
+    /// Transfers `amount` of tokens from the purse at `source` to the `target` purse, spending the
+    /// allowance previously granted over `source` to the immediate caller.
+    fn transfer_from_allowance(
+        &mut self,
+        source: URefAddr,
+        target: URef,
+        amount: U512,
+        id: Option<u64>,
+    ) -> Result<(), Error> {
+        let spender = match self.get_immediate_caller() {
+            Some(CallStackElement::Session { account_hash })
+            | Some(CallStackElement::StoredSession { account_hash, .. }) => {
+                Key::Account(*account_hash)
+            }
+            Some(CallStackElement::StoredContract { contract_hash, .. }) => {
+                Key::Hash(contract_hash.value())
+            }
+            None => return Err(Error::InvalidContext),
+        };
+
+        if !self.allow_unrestricted_transfers() && !self.is_administrator(&self.get_caller()) {
+            return Err(Error::DisabledUnrestrictedTransfers);
+        }
+        if !target.is_addable() {
+            return Err(Error::InvalidAccessRights);
+        }
+        if source == target.addr() {
+            return Err(Error::EqualSourceAndTarget);
+        }
+
+        let allowances_uref = match self.get_key(ALLOWANCES_KEY) {
+            Some(Key::URef(uref)) => uref,
+            Some(_) => return Err(Error::MissingKey),
+            None => return Err(Error::AllowanceNotFound),
+        };
+        let dictionary_item_key =
+            allowance_dictionary_item_key(source, &spender).map_err(|_| Error::Serialize)?;
+        let allowance: Allowance = self
+            .read_dictionary(allowances_uref, &dictionary_item_key)?
+            .ok_or(Error::AllowanceNotFound)?;
+        if allowance.is_expired(u64::from(self.get_blocktime())) {
+            return Err(Error::AllowanceExpired);
+        }
+        if amount > allowance.amount() {
+            return Err(Error::InsufficientAllowance);
+        }
+
+        let source = URef::new(source, AccessRights::NONE);
+        let source_balance: U512 = match self.read_balance(source)? {
+            Some(source_balance) => source_balance,
+            None => return Err(Error::SourceNotFound),
+        };
+        if amount > source_balance {
+            return Err(Error::InsufficientFunds);
+        }
+        if !self.balance_exists(target)? {
+            return Err(Error::DestNotFound);
+        }
+
+        self.write_dictionary(
+            allowances_uref,
+            &dictionary_item_key,
+            Allowance::new(allowance.amount() - amount, allowance.expires_at()),
+        )?;
+        self.write_balance(source, source_balance - amount)?;
+        self.add_balance(target, amount)?;
+        self.record_transfer(None, source, target, amount, id)?;
+        Ok(())
+    }
+
+    /// Schedules `runs` transfers of `amount` to the main purse of `target` along `schedule`,
+    /// moving the funds of all runs out of `source` into an escrow purse up front.  Returns the id
+    /// of the scheduled transfer.
+    ///
+    /// Each account can own up to [`MAX_SCHEDULED_TRANSFERS_PER_OWNER`] pending scheduled
+    /// transfers, and up to [`MAX_SCHEDULED_TRANSFERS`] can be pending in total.
+    fn schedule_transfer(
+        &mut self,
+        source: URef,
+        target: AccountHash,
+        amount: U512,
+        schedule: TransferSchedule,
+        runs: u32,
+        id: Option<u64>,
+    ) -> Result<u64, Error> {
+        if amount.is_zero() || !schedule.is_valid(runs) {
+            return Err(Error::InvalidTransferSchedule);
+        }
+        let total_amount = amount
+            .checked_mul(U512::from(runs))
+            .ok_or(Error::ArithmeticOverflow)?;
+
+        let scheduled_transfers_uref = match self.get_key(SCHEDULED_TRANSFERS_KEY) {
+            Some(Key::URef(uref)) => uref,
+            Some(_) => return Err(Error::MissingKey),
+            None => {
+                // the scheduled transfers dictionary and its indices are created on first use
+                let uref = self.new_uref(())?;
+                self.put_key(SCHEDULED_TRANSFERS_KEY, Key::URef(uref))?;
+                let due_uref = self.new_uref(())?;
+                self.put_key(SCHEDULED_TRANSFERS_DUE_KEY, Key::URef(due_uref))?;
+                // the mint only learns the current era from the step, the block time window of
+                // the current block is the earliest a transfer can be due in
+                let next_window =
+                    u64::from(self.get_blocktime()) / SCHEDULED_TRANSFER_WINDOW_MILLIS;
+                let cursor_uref = self.new_uref((None::<EraId>, next_window))?;
+                self.put_key(SCHEDULED_TRANSFERS_CURSOR_KEY, Key::URef(cursor_uref))?;
+                let pending_uref = self.new_uref(0u32)?;
+                self.put_key(SCHEDULED_TRANSFERS_PENDING_KEY, Key::URef(pending_uref))?;
+                let counts_uref = self.new_uref(())?;
+                self.put_key(SCHEDULED_TRANSFER_COUNTS_KEY, Key::URef(counts_uref))?;
+                let next_id_uref = self.new_uref(0u64)?;
+                self.put_key(SCHEDULED_TRANSFER_NEXT_ID_KEY, Key::URef(next_id_uref))?;
+                uref
+            }
+        };
+        let due_uref = get_uref(self, SCHEDULED_TRANSFERS_DUE_KEY)?;
+        let cursor_uref = get_uref(self, SCHEDULED_TRANSFERS_CURSOR_KEY)?;
+        let pending_uref = get_uref(self, SCHEDULED_TRANSFERS_PENDING_KEY)?;
+        let counts_uref = get_uref(self, SCHEDULED_TRANSFER_COUNTS_KEY)?;
+        let next_id_uref = get_uref(self, SCHEDULED_TRANSFER_NEXT_ID_KEY)?;
+
+        let owner = self.get_caller();
+        let owner_item_key = owner.to_string();
+        let owned_count: u32 = self
+            .read_dictionary(counts_uref, &owner_item_key)?
+            .unwrap_or_default();
+        let pending_count: u32 = self.read(pending_uref)?.ok_or(Error::Storage)?;
+        if owned_count >= MAX_SCHEDULED_TRANSFERS_PER_OWNER
+            || pending_count >= MAX_SCHEDULED_TRANSFERS
+        {
+            return Err(Error::TooManyScheduledTransfers);
+        }
+        let schedule_id: u64 = self.read(next_id_uref)?.ok_or(Error::Storage)?;
+
+        let escrow_purse = self.mint(U512::zero())?;
+        self.transfer(None, source, escrow_purse, total_amount, id)?;
+
+        let scheduled_transfer =
+            ScheduledTransfer::new(owner, escrow_purse, target, amount, schedule, runs, id);
+        self.write_dictionary(
+            scheduled_transfers_uref,
+            &schedule_id.to_string(),
+            scheduled_transfer,
+        )?;
+        let (next_era, next_window): (Option<EraId>, u64) =
+            self.read(cursor_uref)?.ok_or(Error::Storage)?;
+        self.index_scheduled_transfers(
+            due_uref,
+            &DueBucket::of(&schedule, next_era, next_window).item_key(),
+            vec![schedule_id],
+        )?;
+        self.write(pending_uref, pending_count + 1)?;
+        self.write_dictionary(counts_uref, &owner_item_key, owned_count + 1)?;
+        self.write(
+            next_id_uref,
+            schedule_id
+                .checked_add(1)
+                .ok_or(Error::ArithmeticOverflow)?,
+        )?;
+        Ok(schedule_id)
+    }
+
+    /// Cancels the remaining runs of the scheduled transfer `schedule_id`, returning the funds left
+    /// in escrow to the main purse of its owner.
+    fn cancel_scheduled_transfer(&mut self, schedule_id: u64) -> Result<(), Error> {
+        let scheduled_transfers_uref = match self.get_key(SCHEDULED_TRANSFERS_KEY) {
+            Some(Key::URef(uref)) => uref,
+            Some(_) => return Err(Error::MissingKey),
+            None => return Err(Error::ScheduledTransferNotFound),
+        };
+        let item_key = schedule_id.to_string();
+        let mut scheduled_transfer: ScheduledTransfer = self
+            .read_dictionary(scheduled_transfers_uref, &item_key)?
+            .filter(|scheduled_transfer: &ScheduledTransfer| {
+                scheduled_transfer.remaining_runs() > 0
+            })
+            .ok_or(Error::ScheduledTransferNotFound)?;
+        if scheduled_transfer.owner() != self.get_caller() {
+            return Err(Error::InvalidContext);
+        }
+
+        let escrow_purse = scheduled_transfer.escrow_purse();
+        let escrow_balance = self.read_balance(escrow_purse)?.unwrap_or_default();
+        if !escrow_balance.is_zero() {
+            let main_purse = self.get_main_purse();
+            self.write_balance(escrow_purse, U512::zero())?;
+            self.add_balance(main_purse, escrow_balance)?;
+            self.record_transfer(
+                Some(scheduled_transfer.owner()),
+                escrow_purse,
+                main_purse,
+                escrow_balance,
+                scheduled_transfer.id(),
+            )?;
+        }
+
+        // the id is dropped from the due index once the step reaches it
+        scheduled_transfer.cancel();
+        self.write_dictionary(scheduled_transfers_uref, &item_key, scheduled_transfer)?;
+        self.release_scheduled_transfer_slot(scheduled_transfer.owner())
+    }
+
+    /// Runs every scheduled transfer due in the step starting `era_id` at
+    /// `era_end_timestamp_millis` and returns the outcome of each run.  A single run pays all the
+    /// runs of a transfer which fell due since the previous step.  The funds left in the escrow of
+    /// finished transfers are returned to their owner.
+    ///
+    /// Only the ids indexed under the eras and block time windows reached since the previous step
+    /// are read.  A transfer failing to run is reported in its outcome and doesn't affect the
+    /// others.
+    fn run_scheduled_transfers(
+        &mut self,
+        era_id: EraId,
+        era_end_timestamp_millis: u64,
+    ) -> Result<Vec<ScheduledTransferResult>, Error> {
+        if self.get_caller() != PublicKey::System.to_account_hash() {
+            return Err(Error::InvalidContext);
+        }
+        let scheduled_transfers_uref = match self.get_key(SCHEDULED_TRANSFERS_KEY) {
+            Some(Key::URef(uref)) => uref,
+            Some(_) => return Err(Error::MissingKey),
+            None => return Ok(Vec::new()),
+        };
+        let due_uref = get_uref(self, SCHEDULED_TRANSFERS_DUE_KEY)?;
+        let cursor_uref = get_uref(self, SCHEDULED_TRANSFERS_CURSOR_KEY)?;
+        let (next_era, next_window): (Option<EraId>, u64) =
+            self.read(cursor_uref)?.ok_or(Error::Storage)?;
+
+        let last_window = era_end_timestamp_millis / SCHEDULED_TRANSFER_WINDOW_MILLIS;
+        let mut buckets: Vec<DueBucket> = match next_era {
+            Some(next_era) => (next_era.value()..=era_id.value())
+                .map(|era| DueBucket::Era(EraId::new(era)))
+                .collect(),
+            None => vec![DueBucket::Unindexed],
+        };
+        buckets.extend((next_window..=last_window).map(DueBucket::Window));
+        // the last window is only partly over, so it is read again by the next step
+        let next_era = Some(era_id.successor());
+        let next_window = next_window.max(last_window);
+        self.write(cursor_uref, (next_era, next_window))?;
+
+        let mut results = Vec::new();
+        let mut still_pending: BTreeMap<DueBucket, Vec<u64>> = BTreeMap::new();
+        for bucket in buckets {
+            let item_key = bucket.item_key();
+            let schedule_ids: Vec<u64> = match self.read_dictionary(due_uref, &item_key)? {
+                Some(schedule_ids) => schedule_ids,
+                None => continue,
+            };
+            self.write_dictionary(due_uref, &item_key, Vec::<u64>::new())?;
+            for schedule_id in schedule_ids {
+                if let Some(schedule) = self.run_scheduled_transfer(
+                    scheduled_transfers_uref,
+                    schedule_id,
+                    era_id,
+                    era_end_timestamp_millis,
+                    &mut results,
+                ) {
+                    still_pending
+                        .entry(DueBucket::of(&schedule, next_era, next_window))
+                        .or_default()
+                        .push(schedule_id);
+                }
+            }
+        }
+        for (bucket, schedule_ids) in still_pending {
+            self.index_scheduled_transfers(due_uref, &bucket.item_key(), schedule_ids)?;
+        }
+        Ok(results)
+    }
+
+    /// Runs the scheduled transfer `schedule_id` if it is due, recording the outcome of the run in
+    /// `results`.  Returns the schedule of its next run if the transfer is still pending.
+    fn run_scheduled_transfer(
+        &mut self,
+        scheduled_transfers_uref: URef,
+        schedule_id: u64,
+        era_id: EraId,
+        era_end_timestamp_millis: u64,
+        results: &mut Vec<ScheduledTransferResult>,
+    ) -> Option<TransferSchedule> {
+        let item_key = schedule_id.to_string();
+        let mut scheduled_transfer: ScheduledTransfer =
+            match self.read_dictionary(scheduled_transfers_uref, &item_key) {
+                Ok(Some(scheduled_transfer)) => scheduled_transfer,
+                Ok(None) => {
+                    warn!(schedule_id, "indexed scheduled transfer not found");
+                    return None;
+                }
+                Err(error) => {
+                    warn!(schedule_id, %error, "error while reading scheduled transfer");
+                    return None;
+                }
+            };
+        // cancelled transfers are dropped from the index here
+        if scheduled_transfer.remaining_runs() == 0 {
+            return None;
+        }
+        let runs = scheduled_transfer.schedule().due_runs(
+            era_id,
+            era_end_timestamp_millis,
+            scheduled_transfer.remaining_runs(),
+        );
+        if runs == 0 {
+            return Some(scheduled_transfer.schedule());
+        }
+
+        // bounded by the total amount moved into escrow when the transfer was scheduled
+        let amount = scheduled_transfer.amount() * U512::from(runs);
+        let payment_result = self.pay_scheduled_transfer(&scheduled_transfer, amount);
+        scheduled_transfer.record_runs(runs);
+        let store_result =
+            self.store_scheduled_transfer(scheduled_transfers_uref, &item_key, &scheduled_transfer);
+        results.push(ScheduledTransferResult {
+            schedule_id,
+            target: scheduled_transfer.target(),
+            amount,
+            error: payment_result
+                .and(store_result)
+                .err()
+                .map(|error| error.to_string()),
+        });
+        if scheduled_transfer.remaining_runs() > 0 {
+            Some(scheduled_transfer.schedule())
+        } else {
+            None
+        }
+    }
+
+    /// Stores `scheduled_transfer` after a run, refunding its escrow and freeing its slots if it
+    /// finished.  If the refund fails, the transfer is left pending with its previous runs so that
+    /// its owner can still cancel it.
+    fn store_scheduled_transfer(
+        &mut self,
+        scheduled_transfers_uref: URef,
+        item_key: &str,
+        scheduled_transfer: &ScheduledTransfer,
+    ) -> Result<(), Error> {
+        if scheduled_transfer.remaining_runs() == 0 {
+            self.refund_scheduled_transfer(scheduled_transfer)?;
+            self.release_scheduled_transfer_slot(scheduled_transfer.owner())?;
+        }
+        self.write_dictionary(scheduled_transfers_uref, item_key, *scheduled_transfer)
+    }
+
+    /// Adds `schedule_ids` to the ids indexed under the bucket `item_key` of the due index.
+    fn index_scheduled_transfers(
+        &mut self,
+        due_uref: URef,
+        item_key: &str,
+        schedule_ids: Vec<u64>,
+    ) -> Result<(), Error> {
+        let mut indexed_ids: Vec<u64> = self
+            .read_dictionary(due_uref, item_key)?
+            .unwrap_or_default();
+        indexed_ids.extend(schedule_ids);
+        self.write_dictionary(due_uref, item_key, indexed_ids)
+    }
+
+    /// Pays `amount` out of the escrow purse of `scheduled_transfer` to its target.
+    fn pay_scheduled_transfer(
+        &mut self,
+        scheduled_transfer: &ScheduledTransfer,
+        amount: U512,
+    ) -> Result<(), Error> {
+        let target_purse = match self.read_account(&scheduled_transfer.target()) {
+            Ok(Some(StoredValue::Account(account))) => account.main_purse(),
+            Ok(_) => return Err(Error::DestNotFound),
+            Err(error) => {
+                warn!(%error, "error while reading account");
+                return Err(Error::Storage);
+            }
+        };
+        let escrow_purse = scheduled_transfer.escrow_purse();
+        let escrow_balance = self
+            .read_balance(escrow_purse)?
+            .ok_or(Error::SourceNotFound)?;
+        if amount > escrow_balance {
+            return Err(Error::InsufficientFunds);
+        }
+        self.write_balance(escrow_purse, escrow_balance - amount)?;
+        self.add_balance(target_purse, amount)?;
+        self.record_transfer(
+            Some(scheduled_transfer.target()),
+            escrow_purse,
+            target_purse,
+            amount,
+            scheduled_transfer.id(),
+        )
+    }
+
+    /// Returns the funds left in the escrow purse of a finished `scheduled_transfer` to the main
+    /// purse of its owner.
+    fn refund_scheduled_transfer(
+        &mut self,
+        scheduled_transfer: &ScheduledTransfer,
+    ) -> Result<(), Error> {
+        let escrow_purse = scheduled_transfer.escrow_purse();
+        let escrow_balance = self.read_balance(escrow_purse)?.unwrap_or_default();
+        if escrow_balance.is_zero() {
+            return Ok(());
+        }
+        match self.read_account(&scheduled_transfer.owner()) {
+            Ok(Some(StoredValue::Account(account))) => {
+                let main_purse = account.main_purse();
+                self.write_balance(escrow_purse, U512::zero())?;
+                self.add_balance(main_purse, escrow_balance)?;
+                self.record_transfer(
+                    Some(scheduled_transfer.owner()),
+                    escrow_purse,
+                    main_purse,
+                    escrow_balance,
+                    scheduled_transfer.id(),
+                )
+            }
+            // the funds stay in escrow if the owner's account is gone
+            Ok(_) => Ok(()),
+            Err(error) => {
+                warn!(%error, "error while reading account");
+                Err(Error::Storage)
+            }
+        }
+    }
+
+    /// Frees one of the scheduled transfer slots of `owner`, and one of the slots of all accounts,
+    /// once one of its scheduled transfers finished or was cancelled.
+    fn release_scheduled_transfer_slot(&mut self, owner: AccountHash) -> Result<(), Error> {
+        let pending_uref = get_uref(self, SCHEDULED_TRANSFERS_PENDING_KEY)?;
+        let pending_count: u32 = self.read(pending_uref)?.unwrap_or_default();
+        self.write(pending_uref, pending_count.saturating_sub(1))?;
+        let counts_uref = get_uref(self, SCHEDULED_TRANSFER_COUNTS_KEY)?;
+        let owner_item_key = owner.to_string();
+        let owned_count: u32 = self
+            .read_dictionary(counts_uref, &owner_item_key)?
+            .unwrap_or_default();
+        self.write_dictionary(counts_uref, &owner_item_key, owned_count.saturating_sub(1))
+    }
+}
+
+/// A bucket of the index of the pending scheduled transfers by when they are next due.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum DueBucket {
+    /// Transfers along an era schedule scheduled before the first step, when the mint doesn't know
+    /// the current era yet.
+    Unindexed,
+    /// Transfers next due at the start of an era.
+    Era(EraId),
+    /// Transfers next due within a block time window of [`SCHEDULED_TRANSFER_WINDOW_MILLIS`].
+    Window(u64),
+}
+
+impl DueBucket {
+    /// Returns the bucket of a transfer next due along `schedule`, given the first era and block
+    /// time window not yet run by the step.
+    fn of(schedule: &TransferSchedule, next_era: Option<EraId>, next_window: u64) -> Self {
+        match (*schedule, next_era) {
+            (TransferSchedule::Era { era_id, .. }, Some(next_era)) => {
+                DueBucket::Era(era_id.max(next_era))
+            }
+            (TransferSchedule::Era { .. }, None) => DueBucket::Unindexed,
+            (
+                TransferSchedule::BlockTime {
+                    timestamp_millis, ..
+                },
+                _,
+            ) => DueBucket::Window(
+                (timestamp_millis / SCHEDULED_TRANSFER_WINDOW_MILLIS).max(next_window),
+            ),
+        }
+    }
+
+    /// Returns the key of the bucket in the due index.
+    fn item_key(&self) -> String {
+        match self {
+            DueBucket::Unindexed => "unindexed".to_string(),
+            DueBucket::Era(era_id) => format!("era-{}", era_id.value()),
+            DueBucket::Window(window) => format!("window-{}", window),
+        }
+    }
+}
+
+/// Returns the [`URef`] stored under `name` in the named keys of the mint.
+fn get_uref<P: RuntimeProvider + ?Sized>(provider: &P, name: &str) -> Result<URef, Error> {
+    match provider.get_key(name) {
+        Some(Key::URef(uref)) => Ok(uref),
+        Some(_) | None => Err(Error::MissingKey),
+    }
 }
@@ -1,7 +1,7 @@
 use casper_types::{
     account::AccountHash,
     system::{mint::Error, CallStackElement},
-    Key, Phase, StoredValue, URef, U512,
+    BlockTime, Key, Phase, StoredValue, URef, U512,
 };
 
 use crate::core::{engine_state::SystemContractRegistry, execution};
@@ -27,6 +27,9 @@ pub trait RuntimeProvider {
     /// Gets execution phase
     fn get_phase(&self) -> Phase;
 
+    /// Returns the block time of the current execution.
+    fn get_blocktime(&self) -> BlockTime;
+
     /// This method should handle storing given [`Key`] under `name`.
     fn put_key(&mut self, name: &str, key: Key) -> Result<(), Error>;
 
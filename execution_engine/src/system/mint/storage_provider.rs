@@ -21,9 +21,27 @@ pub trait StorageProvider {
     /// Read balance.
     fn read_balance(&mut self, uref: URef) -> Result<Option<U512>, Error>;
 
+    /// Check that a balance exists, without regard to its amount.
+    fn balance_exists(&mut self, uref: URef) -> Result<bool, Error>;
+
     /// Write balance.
     fn write_balance(&mut self, uref: URef, balance: U512) -> Result<(), Error>;
 
     /// Add amount to an existing balance.
     fn add_balance(&mut self, uref: URef, value: U512) -> Result<(), Error>;
+
+    /// Read data from a dictionary item.
+    fn read_dictionary<T: CLTyped + FromBytes>(
+        &mut self,
+        seed_uref: URef,
+        dictionary_item_key: &str,
+    ) -> Result<Option<T>, Error>;
+
+    /// Write data under a dictionary item.
+    fn write_dictionary<T: CLTyped + ToBytes>(
+        &mut self,
+        seed_uref: URef,
+        dictionary_item_key: &str,
+        value: T,
+    ) -> Result<(), Error>;
 }
@@ -10,13 +10,17 @@ use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 
 use casper_execution_engine::{
-    core::engine_state::{
-        engine_config::{
-            EngineConfig, EngineConfigBuilder, FeeHandling, RefundHandling, DEFAULT_MAX_QUERY_DEPTH,
+    core::{
+        engine_state::{
+            engine_config::{
+                EngineConfig, EngineConfigBuilder, FeeHandling, RefundHandling,
+                DEFAULT_MAX_QUERY_DEPTH,
+            },
+            genesis::ExecConfigBuilder,
+            run_genesis_request::RunGenesisRequest,
+            ExecConfig, GenesisAccount,
         },
-        genesis::ExecConfigBuilder,
-        run_genesis_request::RunGenesisRequest,
-        ExecConfig, GenesisAccount,
+        wasm_engine::WasmEngineKind,
     },
     shared::{system_config::SystemConfig, wasm_config::WasmConfig},
 };
@@ -76,10 +80,26 @@ pub struct CoreConfig {
     pub(crate) strict_argument_checking: bool,
     /// The maximum amount of delegators per validator.
     pub(crate) max_delegators_per_validator: Option<u32>,
+    /// The maximum amount of delegation slots a validator can reserve.
+    pub(crate) max_reserved_delegators_per_validator: u32,
     /// Refund handling.
     pub(crate) refund_handling: RefundHandling,
     /// Fee handling.
     pub(crate) fee_handling: FeeHandling,
+    /// Fraction of stake slashed for an equivocation.
+    pub(crate) equivocation_slashing_fraction: Ratio<u64>,
+    /// Fraction of stake slashed for being inactive for a whole era.
+    pub(crate) unavailability_slashing_fraction: Ratio<u64>,
+    /// Number of consecutive inactive eras after which a validator is jailed.
+    pub(crate) inactivity_jail_threshold: u32,
+    /// Number of eras a validator is jailed for on its first offence.
+    pub(crate) jail_period_eras: u64,
+    /// Maximum number of eras a validator is jailed for.
+    pub(crate) max_jail_period_eras: u64,
+    /// Allows delegators to transfer their delegations to other delegators.
+    pub(crate) allow_delegation_transfers: bool,
+    /// The Wasm interpreter executing contracts.
+    pub(crate) wasm_engine: WasmEngineKind,
 }
 
 /// This struct can be parsed from a TOML-encoded chainspec file.  It means that as the
@@ -161,8 +181,16 @@ impl ChainspecConfig {
             minimum_delegation_amount: _,
             strict_argument_checking: _,
             max_delegators_per_validator: _,
+            max_reserved_delegators_per_validator: _,
             refund_handling: _,
             fee_handling: _,
+            equivocation_slashing_fraction: _,
+            unavailability_slashing_fraction: _,
+            inactivity_jail_threshold: _,
+            jail_period_eras: _,
+            max_jail_period_eras: _,
+            allow_delegation_transfers: _,
+            wasm_engine: _,
         } = core_config;
 
         let exec_config = ExecConfigBuilder::new()
@@ -222,6 +250,11 @@ impl From<ChainspecConfig> for EngineConfig {
             .with_max_delegators_per_validator(
                 chainspec_config.core_config.max_delegators_per_validator,
             )
+            .with_max_reserved_delegators_per_validator(
+                chainspec_config
+                    .core_config
+                    .max_reserved_delegators_per_validator,
+            )
             .with_wasm_config(chainspec_config.wasm_config)
             .with_system_config(chainspec_config.system_costs_config)
             .build()
@@ -18,6 +18,8 @@ mod step_request_builder;
 pub mod transfer;
 mod upgrade_request_builder;
 pub mod utils;
+/// Utilities for checking that every Wasm engine executes contracts identically.
+pub mod wasm_engine_conformance;
 mod wasm_test_builder;
 
 use num_rational::Ratio;
@@ -200,6 +202,13 @@ pub static SYSTEM_ADDR: Lazy<AccountHash> = Lazy::new(|| PublicKey::System.to_ac
 
 #[cfg(test)]
 mod tests {
+    use casper_execution_engine::core::engine_state::engine_config::{
+        DEFAULT_ALLOW_DELEGATION_TRANSFERS, DEFAULT_INACTIVITY_JAIL_THRESHOLD,
+        DEFAULT_JAIL_PERIOD_ERAS, DEFAULT_MAX_JAIL_PERIOD_ERAS,
+        DEFAULT_MAX_RESERVED_DELEGATORS_PER_VALIDATOR, DEFAULT_SLASHING_FRACTION,
+        DEFAULT_WASM_ENGINE,
+    };
+
     use super::*;
 
     #[test]
@@ -232,6 +241,36 @@ mod tests {
             DEFAULT_MINIMUM_DELEGATION_AMOUNT
         );
 
+        assert_eq!(
+            production.core_config.equivocation_slashing_fraction,
+            DEFAULT_SLASHING_FRACTION
+        );
+        assert_eq!(
+            production.core_config.unavailability_slashing_fraction,
+            DEFAULT_SLASHING_FRACTION
+        );
+        assert_eq!(
+            production.core_config.inactivity_jail_threshold,
+            DEFAULT_INACTIVITY_JAIL_THRESHOLD
+        );
+        assert_eq!(
+            production.core_config.jail_period_eras,
+            DEFAULT_JAIL_PERIOD_ERAS
+        );
+        assert_eq!(
+            production.core_config.max_jail_period_eras,
+            DEFAULT_MAX_JAIL_PERIOD_ERAS
+        );
+        assert_eq!(
+            production.core_config.max_reserved_delegators_per_validator,
+            DEFAULT_MAX_RESERVED_DELEGATORS_PER_VALIDATOR
+        );
+        assert_eq!(
+            production.core_config.allow_delegation_transfers,
+            DEFAULT_ALLOW_DELEGATION_TRANSFERS
+        );
+        assert_eq!(production.core_config.wasm_engine, DEFAULT_WASM_ENGINE);
+
         assert_eq!(production.wasm_config, WasmConfig::default());
         assert_eq!(production.system_costs_config, SystemConfig::default());
     }
@@ -0,0 +1,101 @@
+use std::{cell::RefCell, rc::Rc};
+
+use casper_execution_engine::core::{
+    engine_state::{deploy_item::DeployItem, ExecutionTrace, ExecutionTracer},
+    wasm_engine::WasmEngineKind,
+};
+use casper_hashing::Digest;
+use casper_types::{Gas, TransferAddr};
+
+use crate::{
+    ExecuteRequestBuilder, InMemoryWasmTestBuilder, PRODUCTION_CHAINSPEC_PATH,
+    PRODUCTION_RUN_GENESIS_REQUEST,
+};
+
+/// Everything observable about the execution of a deploy which must not depend on the Wasm engine.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DeployOutcome {
+    /// The gas charged.
+    pub cost: Gas,
+    /// The error the deploy failed with, if it did, formatted with `Debug`.
+    pub error: Option<String>,
+    /// The transfers made.
+    pub transfers: Vec<TransferAddr>,
+    /// The host calls made and the global state read and written, in execution order.
+    pub trace: ExecutionTrace,
+    /// The state root hash after committing the deploy.
+    pub post_state_hash: Digest,
+}
+
+/// Runs genesis using the production chainspec on `wasm_engine`, then executes and commits
+/// `deploy_items` in order, returning their outcomes.
+pub fn run_on_wasm_engine(
+    wasm_engine: WasmEngineKind,
+    deploy_items: &[DeployItem],
+) -> Vec<DeployOutcome> {
+    let engine_config = InMemoryWasmTestBuilder::engine_config_builder(&*PRODUCTION_CHAINSPEC_PATH)
+        .with_wasm_engine(wasm_engine)
+        .build();
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    deploy_items
+        .iter()
+        .map(|deploy_item| {
+            let tracer = Rc::new(RefCell::new(ExecutionTracer::new()));
+            let exec_request = ExecuteRequestBuilder::from_deploy_item(deploy_item.clone()).build();
+            builder
+                .with_execution_hook(Some(tracer.clone()))
+                .exec(exec_request)
+                .commit();
+
+            let exec_result = builder
+                .get_last_exec_results()
+                .and_then(|exec_results| exec_results.into_iter().next())
+                .expect("should have execution result");
+            let trace = tracer.borrow_mut().take_trace();
+            DeployOutcome {
+                cost: exec_result.cost(),
+                error: exec_result.as_error().map(|error| format!("{:?}", error)),
+                transfers: exec_result.transfers().clone(),
+                trace,
+                post_state_hash: builder.get_post_state_hash(),
+            }
+        })
+        .collect()
+}
+
+/// Runs `deploy_items` on every [`WasmEngineKind`] as in [`run_on_wasm_engine`], panicking if the
+/// outcome of any deploy differs from that on the default engine.
+///
+/// Also panics if there is no engine other than the default one to compare against, since nothing
+/// would be checked.
+///
+/// Returns the outcomes on the default engine.
+pub fn assert_wasm_engines_conform(deploy_items: &[DeployItem]) -> Vec<DeployOutcome> {
+    let other_engines: Vec<WasmEngineKind> = WasmEngineKind::ALL
+        .into_iter()
+        .filter(|wasm_engine| *wasm_engine != WasmEngineKind::default())
+        .collect();
+    assert!(
+        !other_engines.is_empty(),
+        "no Wasm engine to compare {:?} against",
+        WasmEngineKind::default()
+    );
+
+    let expected = run_on_wasm_engine(WasmEngineKind::default(), deploy_items);
+    for wasm_engine in other_engines {
+        let outcomes = run_on_wasm_engine(wasm_engine, deploy_items);
+        for (index, (outcome, expected)) in outcomes.iter().zip(&expected).enumerate() {
+            assert_eq!(
+                outcome,
+                expected,
+                "deploy {} executed differently on {:?} than on {:?}",
+                index,
+                wasm_engine,
+                WasmEngineKind::default()
+            );
+        }
+    }
+    expected
+}
@@ -1,4 +1,5 @@
 use std::{
+    cell::RefCell,
     collections::BTreeMap,
     convert::{TryFrom, TryInto},
     ffi::OsStr,
@@ -23,6 +24,8 @@ use casper_execution_engine::{
             era_validators::GetEraValidatorsRequest,
             execute_request::ExecuteRequest,
             execution_result::ExecutionResult,
+            execution_trace::ExecutionHook,
+            gas_profile::GasProfile,
             run_genesis_request::RunGenesisRequest,
             step::{EvictItem, StepRequest, StepSuccess},
             BalanceResult, EngineConfig, EngineConfigBuilder, EngineState, Error, GenesisSuccess,
@@ -119,6 +122,12 @@ pub struct WasmTestBuilder<S> {
     system_contract_registry: Option<SystemContractRegistry>,
     /// Global state dir, for implementations that define one.
     global_state_dir: Option<PathBuf>,
+    /// Whether exec calls profile the gas charged by their deploys.
+    gas_profiling: bool,
+    /// Gas profiles of the deploys of each exec call made while gas profiling was enabled.
+    gas_profiles: Vec<Vec<GasProfile>>,
+    /// Hook called at each event of the executions of subsequent exec calls.
+    execution_hook: Option<Rc<RefCell<dyn ExecutionHook>>>,
 }
 
 impl<S> WasmTestBuilder<S> {
@@ -151,6 +160,9 @@ impl<S> Clone for WasmTestBuilder<S> {
             scratch_engine_state: None,
             system_contract_registry: self.system_contract_registry.clone(),
             global_state_dir: self.global_state_dir.clone(),
+            gas_profiling: self.gas_profiling,
+            gas_profiles: self.gas_profiles.clone(),
+            execution_hook: self.execution_hook.clone(),
         }
     }
 }
@@ -179,6 +191,9 @@ impl InMemoryWasmTestBuilder {
             scratch_engine_state: None,
             system_contract_registry: None,
             global_state_dir: None,
+            gas_profiling: false,
+            gas_profiles: Vec::new(),
+            execution_hook: None,
         }
     }
 
@@ -203,6 +218,9 @@ impl InMemoryWasmTestBuilder {
             scratch_engine_state: None,
             system_contract_registry: None,
             global_state_dir: None,
+            gas_profiling: false,
+            gas_profiles: Vec::new(),
+            execution_hook: None,
         }
     }
 
@@ -211,6 +229,15 @@ impl InMemoryWasmTestBuilder {
         chainspec_path: P,
         post_state_hash: Option<Digest>,
     ) -> Self {
+        let engine_config = Self::engine_config_builder(chainspec_path).build();
+
+        let global_state = InMemoryGlobalState::empty().expect("should create global state");
+
+        Self::new(global_state, engine_config, post_state_hash)
+    }
+
+    /// Returns an [`EngineConfigBuilder`] set up using values from a given chainspec.
+    pub(crate) fn engine_config_builder<P: AsRef<Path>>(chainspec_path: P) -> EngineConfigBuilder {
         let chainspec_config = ChainspecConfig::from_chainspec_path(chainspec_path)
             .expect("must build chainspec configuration");
 
@@ -232,11 +259,19 @@ impl InMemoryWasmTestBuilder {
             minimum_delegation_amount,
             strict_argument_checking,
             max_delegators_per_validator,
+            max_reserved_delegators_per_validator,
             refund_handling,
             fee_handling,
+            equivocation_slashing_fraction,
+            unavailability_slashing_fraction,
+            inactivity_jail_threshold,
+            jail_period_eras,
+            max_jail_period_eras,
+            allow_delegation_transfers,
+            wasm_engine,
         } = core_config;
 
-        let engine_config = EngineConfigBuilder::new()
+        EngineConfigBuilder::new()
             .with_max_query_depth(DEFAULT_MAX_QUERY_DEPTH)
             .with_max_associated_keys(max_associated_keys)
             .with_max_runtime_call_stack_height(max_runtime_call_stack_height)
@@ -244,15 +279,18 @@ impl InMemoryWasmTestBuilder {
             .with_strict_argument_checking(strict_argument_checking)
             .with_vesting_schedule_period_millis(vesting_schedule_period.millis())
             .with_max_delegators_per_validator(max_delegators_per_validator)
+            .with_max_reserved_delegators_per_validator(max_reserved_delegators_per_validator)
             .with_wasm_config(wasm_config)
             .with_system_config(system_costs_config)
             .with_refund_handling(refund_handling)
             .with_fee_handling(fee_handling)
-            .build();
-
-        let global_state = InMemoryGlobalState::empty().expect("should create global state");
-
-        Self::new(global_state, engine_config, post_state_hash)
+            .with_equivocation_slashing_fraction(equivocation_slashing_fraction)
+            .with_unavailability_slashing_fraction(unavailability_slashing_fraction)
+            .with_inactivity_jail_threshold(inactivity_jail_threshold)
+            .with_jail_period_eras(jail_period_eras)
+            .with_max_jail_period_eras(max_jail_period_eras)
+            .with_allow_delegation_transfers(allow_delegation_transfers)
+            .with_wasm_engine(wasm_engine)
     }
 }
 
@@ -341,6 +379,9 @@ impl LmdbWasmTestBuilder {
             scratch_engine_state: None,
             system_contract_registry: None,
             global_state_dir: Some(global_state_dir),
+            gas_profiling: false,
+            gas_profiles: Vec::new(),
+            execution_hook: None,
         }
     }
 
@@ -424,6 +465,9 @@ impl LmdbWasmTestBuilder {
             scratch_engine_state: None,
             system_contract_registry: None,
             global_state_dir: None,
+            gas_profiling: false,
+            gas_profiles: Vec::new(),
+            execution_hook: None,
         };
 
         builder.system_contract_registry =
@@ -724,9 +768,22 @@ where
             exec_request
         };
 
-        let execution_results = self
-            .engine_state
-            .run_execute(CorrelationId::new(), exec_request)?;
+        let execution_results = if self.gas_profiling {
+            let (execution_results, gas_profiles) = self
+                .engine_state
+                .run_execute_with_gas_profiles(CorrelationId::new(), exec_request)?;
+            self.gas_profiles.push(gas_profiles);
+            execution_results
+        } else if let Some(execution_hook) = &self.execution_hook {
+            self.engine_state.run_execute_with_hook(
+                CorrelationId::new(),
+                exec_request,
+                Rc::clone(execution_hook),
+            )?
+        } else {
+            self.engine_state
+                .run_execute(CorrelationId::new(), exec_request)?
+        };
         // Cache transformations
         self.transforms.extend(
             execution_results
@@ -738,6 +795,30 @@ where
         Ok(self)
     }
 
+    /// Enables or disables profiling of the gas charged by the deploys of subsequent exec calls.
+    pub fn with_gas_profiling(&mut self, gas_profiling: bool) -> &mut Self {
+        self.gas_profiling = gas_profiling;
+        self
+    }
+
+    /// Sets or clears the hook called at each event of the executions of subsequent exec calls,
+    /// e.g. an [`ExecutionTracer`](casper_execution_engine::core::engine_state::ExecutionTracer).
+    ///
+    /// The hook is not called while gas profiling is enabled.
+    pub fn with_execution_hook(
+        &mut self,
+        execution_hook: Option<Rc<RefCell<dyn ExecutionHook>>>,
+    ) -> &mut Self {
+        self.execution_hook = execution_hook;
+        self
+    }
+
+    /// Returns the gas profiles of the deploys of the last exec call made while gas profiling was
+    /// enabled.
+    pub fn get_last_gas_profiles(&self) -> Option<&[GasProfile]> {
+        self.gas_profiles.last().map(Vec::as_slice)
+    }
+
     /// Commit effects of previous exec call on the latest post-state hash.
     pub fn commit(&mut self) -> &mut Self {
         let prestate_hash = self.post_state_hash.expect("Should have genesis hash");
@@ -1377,9 +1458,10 @@ where
             .expect("should have standard payment hash")
     }
 
-    /// Resets the `exec_results`, `upgrade_results` and `transform` fields.
+    /// Resets the `exec_results`, `gas_profiles`, `upgrade_results` and `transform` fields.
     pub fn clear_results(&mut self) -> &mut Self {
         self.exec_results = Vec::new();
+        self.gas_profiles = Vec::new();
         self.upgrade_results = Vec::new();
         self.transforms = Vec::new();
         self
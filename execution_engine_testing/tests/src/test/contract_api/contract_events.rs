@@ -0,0 +1,101 @@
+use casper_engine_test_support::{
+    ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    PRODUCTION_RUN_GENESIS_REQUEST,
+};
+use casper_types::{runtime_args, CLValue, Key, RuntimeArgs};
+
+const CONTRACT_EVENTS_WASM: &str = "contract_events.wasm";
+const ARG_TOPIC: &str = "topic";
+const ARG_VALUE: &str = "value";
+const ARG_COUNT: &str = "count";
+
+const TOPIC: &str = "counter_incremented";
+
+#[ignore]
+#[test]
+fn should_record_emitted_events_in_execution_result() {
+    const COUNT: u32 = 3;
+    const VALUE: u64 = 10;
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_EVENTS_WASM,
+        runtime_args! {
+            ARG_TOPIC => TOPIC,
+            ARG_VALUE => VALUE,
+            ARG_COUNT => COUNT,
+        },
+    )
+    .build();
+
+    builder.exec(exec_request).commit().expect_success();
+
+    let exec_results = builder
+        .get_last_exec_results()
+        .expect("should have exec results");
+    let events = exec_results[0].events();
+    assert_eq!(events.len(), COUNT as usize);
+
+    let emitter = Key::from(*DEFAULT_ACCOUNT_ADDR).to_formatted_string();
+    for (index, event) in events.iter().enumerate() {
+        assert_eq!(event.emitter, emitter);
+        assert_eq!(event.topic, TOPIC);
+        let expected_value = CLValue::from_t(VALUE + index as u64).unwrap();
+        assert_eq!(event.value, expected_value);
+    }
+}
+
+#[ignore]
+#[test]
+fn should_charge_more_for_larger_events() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let mut emit = |topic: &str| {
+        let exec_request = ExecuteRequestBuilder::standard(
+            *DEFAULT_ACCOUNT_ADDR,
+            CONTRACT_EVENTS_WASM,
+            runtime_args! {
+                ARG_TOPIC => topic,
+                ARG_VALUE => 0u64,
+                ARG_COUNT => 1u32,
+            },
+        )
+        .build();
+        builder.exec(exec_request).commit().expect_success();
+        builder.last_exec_gas_cost()
+    };
+
+    let small_cost = emit("a");
+    let large_cost = emit(&"a".repeat(1_000));
+
+    assert!(large_cost > small_cost);
+}
+
+#[ignore]
+#[test]
+fn should_not_record_events_when_nothing_emitted() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_EVENTS_WASM,
+        runtime_args! {
+            ARG_TOPIC => TOPIC,
+            ARG_VALUE => 0u64,
+            ARG_COUNT => 0u32,
+        },
+    )
+    .build();
+
+    builder.exec(exec_request).commit().expect_success();
+
+    let exec_results = builder
+        .get_last_exec_results()
+        .expect("should have exec results");
+    assert!(exec_results[0].events().is_empty());
+}
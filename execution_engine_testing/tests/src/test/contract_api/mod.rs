@@ -1,4 +1,5 @@
 mod account;
+mod contract_events;
 mod create_purse;
 mod dictionary;
 mod get_arg;
@@ -7,7 +7,11 @@ use casper_engine_test_support::{
     DEFAULT_PAYMENT, PRODUCTION_RUN_GENESIS_REQUEST,
 };
 use casper_execution_engine::core::{runtime_context::RANDOM_BYTES_COUNT, ADDRESS_LENGTH};
-use casper_types::{crypto, runtime_args, RuntimeArgs, BLAKE2B_DIGEST_LENGTH};
+use casper_types::{
+    bytesrepr::{Bytes, ToBytes},
+    crypto, runtime_args, PublicKey, RuntimeArgs, SecretKey, BLAKE2B_DIGEST_LENGTH,
+    KECCAK256_DIGEST_LENGTH, SHA256_DIGEST_LENGTH,
+};
 
 const ARG_BYTES: &str = "bytes";
 const ARG_AMOUNT: &str = "amount";
@@ -15,6 +19,15 @@ const ARG_AMOUNT: &str = "amount";
 const BLAKE2B_WASM: &str = "blake2b.wasm";
 const HASH_RESULT: &str = "hash_result";
 
+const SHA256_WASM: &str = "sha256.wasm";
+const KECCAK256_WASM: &str = "keccak256.wasm";
+
+const VERIFY_SIGNATURE_WASM: &str = "verify_signature.wasm";
+const SIGNATURE_VALID: &str = "signature_valid";
+const ARG_MESSAGE: &str = "message";
+const ARG_SIGNATURE: &str = "signature";
+const ARG_PUBLIC_KEY: &str = "public_key";
+
 const RANDOM_BYTES_WASM: &str = "random_bytes.wasm";
 const RANDOM_BYTES_RESULT: &str = "random_bytes_result";
 
@@ -38,6 +51,43 @@ fn get_value<const COUNT: usize>(builder: &InMemoryWasmTestBuilder, result: &str
         .expect("should convert")
 }
 
+fn verify_signature(
+    builder: &mut InMemoryWasmTestBuilder,
+    message: &[u8],
+    signature: &crypto::Signature,
+    public_key: &PublicKey,
+) -> bool {
+    let exec_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        VERIFY_SIGNATURE_WASM,
+        runtime_args! {
+            ARG_MESSAGE => Bytes::from(message.to_vec()),
+            ARG_SIGNATURE => Bytes::from(signature.to_bytes().unwrap()),
+            ARG_PUBLIC_KEY => public_key.clone(),
+        },
+    )
+    .build();
+
+    builder.exec(exec_request).commit().expect_success();
+
+    let account = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let uref = account
+        .named_keys()
+        .get(SIGNATURE_VALID)
+        .expect("should have value");
+
+    builder
+        .query(None, *uref, &[])
+        .expect("should query")
+        .as_cl_value()
+        .cloned()
+        .expect("should be CLValue")
+        .into_t()
+        .expect("should convert")
+}
+
 #[ignore]
 #[test]
 fn should_return_different_random_bytes_on_different_phases() {
@@ -130,3 +180,91 @@ fn should_hash() {
         assert_eq!(digest, expected_digest);
     }
 }
+
+#[ignore]
+#[test]
+fn should_hash_with_sha256_and_keccak256() {
+    const INPUT_LENGTH: usize = 32;
+    const RUNS: usize = 10;
+
+    let mut rng = rand::thread_rng();
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    for _ in 0..RUNS {
+        let input: [u8; INPUT_LENGTH] = rng.gen();
+
+        let exec_request = ExecuteRequestBuilder::standard(
+            *DEFAULT_ACCOUNT_ADDR,
+            SHA256_WASM,
+            runtime_args! {
+                ARG_BYTES => input
+            },
+        )
+        .build();
+        builder.exec(exec_request).commit().expect_success();
+        let digest = get_value::<SHA256_DIGEST_LENGTH>(&builder, HASH_RESULT);
+        assert_eq!(digest, crypto::sha256(input));
+
+        let exec_request = ExecuteRequestBuilder::standard(
+            *DEFAULT_ACCOUNT_ADDR,
+            KECCAK256_WASM,
+            runtime_args! {
+                ARG_BYTES => input
+            },
+        )
+        .build();
+        builder.exec(exec_request).commit().expect_success();
+        let digest = get_value::<KECCAK256_DIGEST_LENGTH>(&builder, HASH_RESULT);
+        assert_eq!(digest, crypto::keccak256(input));
+    }
+}
+
+#[ignore]
+#[test]
+fn should_verify_signatures() {
+    const MESSAGE: &[u8] = b"message to sign";
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let ed25519_secret_key = SecretKey::ed25519_from_bytes([1; 32]).unwrap();
+    let ed25519_public_key = PublicKey::from(&ed25519_secret_key);
+    let secp256k1_secret_key = SecretKey::secp256k1_from_bytes([2; 32]).unwrap();
+    let secp256k1_public_key = PublicKey::from(&secp256k1_secret_key);
+
+    for (secret_key, public_key, other_public_key) in [
+        (
+            &ed25519_secret_key,
+            &ed25519_public_key,
+            &secp256k1_public_key,
+        ),
+        (
+            &secp256k1_secret_key,
+            &secp256k1_public_key,
+            &ed25519_public_key,
+        ),
+    ] {
+        let signature = crypto::sign(MESSAGE, secret_key, public_key);
+
+        assert!(verify_signature(
+            &mut builder,
+            MESSAGE,
+            &signature,
+            public_key
+        ));
+        assert!(!verify_signature(
+            &mut builder,
+            b"another message",
+            &signature,
+            public_key
+        ));
+        assert!(!verify_signature(
+            &mut builder,
+            MESSAGE,
+            &signature,
+            other_public_key
+        ));
+    }
+}
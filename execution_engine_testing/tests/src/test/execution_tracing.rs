@@ -0,0 +1,266 @@
+use std::{cell::RefCell, rc::Rc};
+
+use casper_engine_test_support::{
+    DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, ARG_AMOUNT,
+    DEFAULT_ACCOUNT_ADDR, DEFAULT_PAYMENT, PRODUCTION_RUN_GENESIS_REQUEST,
+};
+use casper_execution_engine::core::{
+    engine_state::{Error, ExecuteRequest, ExecutionTracer, HookAction, TraceEvent, TraceReplayer},
+    execution,
+};
+use casper_types::{runtime_args, system::CallStackElement, ContractHash, Key, RuntimeArgs};
+
+const DO_NOTHING_STORED_CONTRACT: &str = "do_nothing_stored.wasm";
+const DO_NOTHING_HASH_KEY_NAME: &str = "do_nothing_hash";
+const DO_NOTHING_ENTRY_POINT: &str = "delegate";
+const PUT_KEY_HOST_FUNCTION: &str = "casper_put_key";
+const LOAD_NAMED_KEYS_HOST_FUNCTION: &str = "casper_load_named_keys";
+
+/// `storage::new_contract` puts the package hash and access URef, then the contract puts the
+/// contract version and hash.
+const PUT_KEY_CALL_COUNT: usize = 4;
+
+const DEPLOY_HASH: [u8; 32] = [42; 32];
+const OTHER_DEPLOY_HASH: [u8; 32] = [43; 32];
+
+/// Returns a request installing the contract under a fixed deploy hash, so that repeated
+/// executions are identical.
+fn install_request() -> ExecuteRequest {
+    install_request_with_deploy_hash(DEPLOY_HASH)
+}
+
+fn install_request_with_deploy_hash(deploy_hash: [u8; 32]) -> ExecuteRequest {
+    let deploy_item = DeployItemBuilder::new()
+        .with_address(*DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(DO_NOTHING_STORED_CONTRACT, RuntimeArgs::new())
+        .with_empty_payment_bytes(runtime_args! {
+            ARG_AMOUNT => *DEFAULT_PAYMENT
+        })
+        .with_authorization_keys(&[*DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash(deploy_hash)
+        .build();
+    ExecuteRequestBuilder::from_deploy_item(deploy_item).build()
+}
+
+#[ignore]
+#[test]
+fn should_trace_host_calls_and_named_keys() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let tracer = Rc::new(RefCell::new(ExecutionTracer::new()));
+    builder
+        .with_execution_hook(Some(tracer.clone()))
+        .exec(install_request())
+        .expect_success()
+        .commit();
+
+    let contract_hash_key = builder
+        .get_expected_account(*DEFAULT_ACCOUNT_ADDR)
+        .named_keys()
+        .get(DO_NOTHING_HASH_KEY_NAME)
+        .copied()
+        .expect("should have contract hash");
+
+    let trace = tracer.borrow_mut().take_trace();
+    let put_key_index = trace
+        .events
+        .iter()
+        .rposition(|event| event.host_call_name() == Some(PUT_KEY_HOST_FUNCTION))
+        .expect("should have called put_key");
+    assert!(
+        trace.events[put_key_index..].contains(&TraceEvent::NamedKeyWrite {
+            name: DO_NOTHING_HASH_KEY_NAME.to_string(),
+            key: contract_hash_key,
+        })
+    );
+    assert!(
+        trace.events[put_key_index..].contains(&TraceEvent::HostReturn {
+            name: PUT_KEY_HOST_FUNCTION.to_string(),
+            result: Ok(None),
+        })
+    );
+    assert_eq!(
+        trace
+            .events
+            .iter()
+            .filter(|event| event.host_call_name() == Some(PUT_KEY_HOST_FUNCTION))
+            .count(),
+        PUT_KEY_CALL_COUNT
+    );
+    assert!(trace.events.iter().any(|event| matches!(
+        event,
+        TraceEvent::Write {
+            key: Key::URef(_),
+            ..
+        }
+    )));
+}
+
+#[ignore]
+#[test]
+fn should_trace_call_contract_frames() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+    builder.exec(install_request()).expect_success().commit();
+
+    let contract_hash = builder
+        .get_expected_account(*DEFAULT_ACCOUNT_ADDR)
+        .named_keys()
+        .get(DO_NOTHING_HASH_KEY_NAME)
+        .and_then(|key| key.into_hash())
+        .map(ContractHash::new)
+        .expect("should have contract hash");
+
+    let call_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        contract_hash,
+        DO_NOTHING_ENTRY_POINT,
+        RuntimeArgs::new(),
+    )
+    .build();
+
+    let tracer = Rc::new(RefCell::new(ExecutionTracer::new()));
+    builder
+        .with_execution_hook(Some(tracer.clone()))
+        .exec(call_request)
+        .expect_success()
+        .commit();
+
+    let trace = tracer.borrow_mut().take_trace();
+    let enter_index = trace
+        .events
+        .iter()
+        .position(|event| match event {
+            TraceEvent::EnterFrame {
+                frame:
+                    CallStackElement::StoredContract {
+                        contract_hash: frame_contract_hash,
+                        ..
+                    },
+                entry_point,
+            } => *frame_contract_hash == contract_hash && entry_point == DO_NOTHING_ENTRY_POINT,
+            _ => false,
+        })
+        .expect("should have entered the contract");
+    let exit_index = trace
+        .events
+        .iter()
+        .rposition(|event| *event == TraceEvent::ExitFrame)
+        .expect("should have exited the contract");
+    assert!(trace.events[enter_index..exit_index]
+        .iter()
+        .any(|event| event.host_call_name() == Some(LOAD_NAMED_KEYS_HOST_FUNCTION)));
+}
+
+#[ignore]
+#[test]
+fn should_stop_at_breakpoint_and_feed_trace() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let tracer = Rc::new(RefCell::new(ExecutionTracer::new()));
+    builder
+        .with_execution_hook(Some(tracer.clone()))
+        .exec(install_request())
+        .expect_success();
+    let trace = tracer.borrow_mut().take_trace();
+
+    let breaking_tracer = Rc::new(RefCell::new(
+        ExecutionTracer::new().with_breakpoint(PUT_KEY_HOST_FUNCTION),
+    ));
+    builder
+        .with_execution_hook(Some(breaking_tracer.clone()))
+        .exec(install_request())
+        .expect_failure();
+
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(
+            &error,
+            Error::Exec(execution::Error::Breakpoint(name)) if name == PUT_KEY_HOST_FUNCTION
+        ),
+        "{:?}",
+        error
+    );
+    let broken_trace = breaking_tracer.borrow_mut().take_trace();
+    let breakpoint_index = broken_trace
+        .events
+        .iter()
+        .position(|event| event.host_call_name() == Some(PUT_KEY_HOST_FUNCTION))
+        .expect("should have hit breakpoint");
+    assert_eq!(
+        broken_trace.events[..breakpoint_index],
+        trace.events[..breakpoint_index]
+    );
+
+    // Feeding the full trace to a tracer with the same breakpoint stops at the same host call.
+    let mut feeding_tracer = ExecutionTracer::new().with_breakpoint(PUT_KEY_HOST_FUNCTION);
+    assert_eq!(trace.feed(&mut feeding_tracer), Some(breakpoint_index));
+    assert_eq!(
+        feeding_tracer.trace().events,
+        broken_trace.events[..=breakpoint_index]
+    );
+
+    // A breakpoint handler which carries on lets the execution succeed.
+    let hits = Rc::new(RefCell::new(0));
+    let continuing_tracer = {
+        let hits = Rc::clone(&hits);
+        ExecutionTracer::new()
+            .with_breakpoint(PUT_KEY_HOST_FUNCTION)
+            .on_breakpoint(move |_event, _trace| {
+                *hits.borrow_mut() += 1;
+                HookAction::Continue
+            })
+    };
+    builder
+        .with_execution_hook(Some(Rc::new(RefCell::new(continuing_tracer))))
+        .exec(install_request())
+        .expect_success();
+    assert_eq!(*hits.borrow(), PUT_KEY_CALL_COUNT);
+}
+
+#[ignore]
+#[test]
+fn should_replay_trace_with_identical_effects() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let tracer = Rc::new(RefCell::new(ExecutionTracer::new()));
+    builder
+        .with_execution_hook(Some(tracer.clone()))
+        .exec(install_request())
+        .expect_success();
+    let trace = tracer.borrow_mut().take_trace();
+    let effects = builder
+        .get_execution_journals()
+        .pop()
+        .expect("should have effects");
+
+    // Re-executing against the same parent state emits the recorded events, which are passed on,
+    // and has the same effects.
+    let replaying_tracer = Rc::new(RefCell::new(ExecutionTracer::new()));
+    let replayer = Rc::new(RefCell::new(
+        TraceReplayer::new(trace.clone()).with_hook(replaying_tracer.clone()),
+    ));
+    builder
+        .with_execution_hook(Some(replayer.clone()))
+        .exec(install_request())
+        .expect_success();
+    assert_eq!(replayer.borrow().divergence(), None);
+    assert_eq!(replaying_tracer.borrow().trace(), &trace);
+    assert_eq!(
+        builder.get_execution_journals().pop(),
+        Some(effects.clone())
+    );
+
+    // A deploy with another hash creates URefs at other addresses, so its execution departs from
+    // the trace at the first of them.
+    let replayer = Rc::new(RefCell::new(TraceReplayer::new(trace)));
+    builder
+        .with_execution_hook(Some(replayer.clone()))
+        .exec(install_request_with_deploy_hash(OTHER_DEPLOY_HASH))
+        .expect_success();
+    assert!(replayer.borrow().divergence().is_some());
+    assert_ne!(builder.get_execution_journals().pop(), Some(effects));
+}
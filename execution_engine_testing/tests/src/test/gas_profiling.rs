@@ -0,0 +1,162 @@
+use casper_wasm::{
+    builder,
+    elements::{Instruction, Instructions},
+};
+
+use casper_engine_test_support::{
+    DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, ARG_AMOUNT,
+    DEFAULT_ACCOUNT_ADDR, DEFAULT_PAYMENT, DEFAULT_WASM_CONFIG, PRODUCTION_RUN_GENESIS_REQUEST,
+};
+use casper_types::{contracts::DEFAULT_ENTRY_POINT_NAME, runtime_args, ContractHash, RuntimeArgs};
+
+const DO_NOTHING_STORED_CONTRACT: &str = "do_nothing_stored.wasm";
+const DO_NOTHING_HASH_KEY_NAME: &str = "do_nothing_hash";
+const DO_NOTHING_ENTRY_POINT: &str = "delegate";
+
+fn make_session_code_with(instructions: Vec<Instruction>) -> Vec<u8> {
+    let module = builder::module()
+        .function()
+        .signature()
+        .build()
+        .body()
+        .with_instructions(Instructions::new(instructions))
+        .build()
+        .build()
+        .export()
+        .field(DEFAULT_ENTRY_POINT_NAME)
+        .build()
+        .memory()
+        .build()
+        .build();
+    casper_wasm::serialize(module).expect("should serialize")
+}
+
+#[ignore]
+#[test]
+fn should_profile_gas_by_opcode_class() {
+    let opcode_costs = DEFAULT_WASM_CONFIG.opcode_costs();
+
+    let session_bytes = make_session_code_with(vec![
+        Instruction::I32Const(1),
+        Instruction::I32Const(2),
+        Instruction::I32Add,
+        Instruction::I32Const(3),
+        Instruction::I32Mul,
+        Instruction::Drop,
+        Instruction::End,
+    ]);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let exec_request = {
+        let deploy_item = DeployItemBuilder::new()
+            .with_address(*DEFAULT_ACCOUNT_ADDR)
+            .with_session_bytes(session_bytes, RuntimeArgs::default())
+            .with_empty_payment_bytes(runtime_args! {
+                ARG_AMOUNT => *DEFAULT_PAYMENT
+            })
+            .with_authorization_keys(&[*DEFAULT_ACCOUNT_ADDR])
+            .with_deploy_hash([42; 32])
+            .build();
+        ExecuteRequestBuilder::from_deploy_item(deploy_item).build()
+    };
+
+    builder
+        .with_gas_profiling(true)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let gas_profiles = builder
+        .get_last_gas_profiles()
+        .expect("should have gas profiles");
+    assert_eq!(gas_profiles.len(), 1);
+    let gas_profile = &gas_profiles[0];
+
+    assert_eq!(
+        gas_profile.opcodes.get("add").copied(),
+        Some(u64::from(opcode_costs.add))
+    );
+    assert_eq!(
+        gas_profile.opcodes.get("mul").copied(),
+        Some(u64::from(opcode_costs.mul))
+    );
+    assert_eq!(
+        gas_profile.stacks.get("session;wasm:mul").copied(),
+        Some(u64::from(opcode_costs.mul))
+    );
+    assert!(gas_profile
+        .to_folded_stacks()
+        .lines()
+        .any(|line| line == format!("session;wasm:add {}", opcode_costs.add)));
+}
+
+#[ignore]
+#[test]
+fn should_profile_gas_by_contract_entry_point_and_host_function() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let install_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        DO_NOTHING_STORED_CONTRACT,
+        RuntimeArgs::new(),
+    )
+    .build();
+    builder.exec(install_request).expect_success().commit();
+
+    let contract_hash = builder
+        .get_expected_account(*DEFAULT_ACCOUNT_ADDR)
+        .named_keys()
+        .get(DO_NOTHING_HASH_KEY_NAME)
+        .and_then(|key| key.into_hash())
+        .map(ContractHash::new)
+        .expect("should have contract hash");
+
+    let call_request = || {
+        ExecuteRequestBuilder::contract_call_by_name(
+            *DEFAULT_ACCOUNT_ADDR,
+            DO_NOTHING_HASH_KEY_NAME,
+            DO_NOTHING_ENTRY_POINT,
+            RuntimeArgs::new(),
+        )
+        .build()
+    };
+
+    builder
+        .with_gas_profiling(true)
+        .exec(call_request())
+        .expect_success()
+        .commit();
+    let profiled_cost = builder.last_exec_gas_cost();
+
+    let gas_profile = builder
+        .get_last_gas_profiles()
+        .and_then(|gas_profiles| gas_profiles.first())
+        .cloned()
+        .expect("should have gas profile");
+
+    let entry_point_frame = format!(
+        "{}::{}",
+        contract_hash.to_formatted_string(),
+        DO_NOTHING_ENTRY_POINT
+    );
+    assert!(gas_profile.entry_points.contains_key(&entry_point_frame));
+    assert!(gas_profile
+        .host_functions
+        .contains_key("casper_load_named_keys"));
+    let host_function_stack = format!("session;{};host:casper_load_named_keys", entry_point_frame);
+    assert_eq!(
+        gas_profile.stacks.get(&host_function_stack),
+        gas_profile.host_functions.get("casper_load_named_keys")
+    );
+
+    // Profiling doesn't change the cost of the execution.
+    builder
+        .with_gas_profiling(false)
+        .exec(call_request())
+        .expect_success()
+        .commit();
+    assert_eq!(builder.last_exec_gas_cost(), profiled_cost);
+}
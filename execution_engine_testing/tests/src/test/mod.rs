@@ -4,8 +4,10 @@ mod check_transfer_success;
 mod contract_api;
 mod contract_context;
 mod deploy;
+mod execution_tracing;
 mod explorer;
 mod gas_counter;
+mod gas_profiling;
 mod get_balance;
 mod groups;
 mod host_function_costs;
@@ -19,4 +21,5 @@ mod system_contracts;
 mod system_costs;
 mod tutorial;
 mod upgrade;
+mod wasm_engine_conformance;
 mod wasmless_transfer;
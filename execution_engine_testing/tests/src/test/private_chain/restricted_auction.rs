@@ -74,7 +74,7 @@ fn should_not_distribute_rewards_but_compute_next_set() {
     assert!(
         matches!(
             era_info.select(VALIDATOR_1_PUBLIC_KEY.clone()).next(),
-            Some(SeigniorageAllocation::Validator { validator_public_key, amount })
+            Some(SeigniorageAllocation::Validator { validator_public_key, amount, .. })
             if *validator_public_key == *VALIDATOR_1_PUBLIC_KEY && *amount == EXPECTED_VALIDATOR_1_PAYOUT
         ),
         "era info is {:?}",
@@ -305,6 +305,7 @@ mod fixture {
                     SeigniorageAllocation::Validator {
                         validator_public_key,
                         amount,
+                        ..
                     } if validator_public_key == &*DEFAULT_ACCOUNT_PUBLIC_KEY => amount,
                     SeigniorageAllocation::Validator { .. } => panic!("Unexpected validator"),
                     SeigniorageAllocation::Delegator { .. } => panic!("No delegators"),
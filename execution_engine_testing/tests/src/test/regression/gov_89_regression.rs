@@ -4,6 +4,7 @@ use std::{
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
+use num_rational::Ratio;
 use num_traits::Zero;
 use once_cell::sync::Lazy;
 
@@ -18,7 +19,7 @@ use casper_execution_engine::{
 };
 use casper_types::{
     system::auction::{
-        Bids, DelegationRate, SeigniorageRecipientsSnapshot, BLOCK_REWARD,
+        Bids, DelegationRate, SeigniorageRecipientsSnapshot, SlashingOffence, BLOCK_REWARD,
         SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY,
     },
     CLValue, EraId, Key, Motes, ProtocolVersion, PublicKey, SecretKey, StoredValue, U512,
@@ -84,7 +85,11 @@ fn should_not_create_any_purse() {
     let step_request_1 = StepRequestBuilder::new()
         .with_parent_state_hash(builder.get_post_state_hash())
         .with_protocol_version(ProtocolVersion::V1_0_0)
-        .with_slash_item(SlashItem::new(ACCOUNT_1_PK.clone()))
+        .with_slash_item(SlashItem::new(
+            ACCOUNT_1_PK.clone(),
+            SlashingOffence::Equivocation,
+            Ratio::from_integer(1),
+        ))
         .with_reward_item(RewardItem::new(ACCOUNT_1_PK.clone(), BLOCK_REWARD / 2))
         .with_reward_item(RewardItem::new(ACCOUNT_2_PK.clone(), BLOCK_REWARD / 2))
         .with_next_era_id(EraId::from(1))
@@ -139,7 +144,11 @@ fn should_not_create_any_purse() {
     let step_request_2 = StepRequestBuilder::new()
         .with_parent_state_hash(builder.get_post_state_hash())
         .with_protocol_version(ProtocolVersion::V1_0_0)
-        .with_slash_item(SlashItem::new(ACCOUNT_1_PK.clone()))
+        .with_slash_item(SlashItem::new(
+            ACCOUNT_1_PK.clone(),
+            SlashingOffence::Equivocation,
+            Ratio::from_integer(1),
+        ))
         .with_reward_item(RewardItem::new(ACCOUNT_1_PK.clone(), BLOCK_REWARD / 2))
         .with_reward_item(RewardItem::new(ACCOUNT_2_PK.clone(), BLOCK_REWARD / 2))
         .with_next_era_id(EraId::from(2))
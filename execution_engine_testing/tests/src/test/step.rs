@@ -1,3 +1,4 @@
+use num_rational::Ratio;
 use num_traits::Zero;
 use once_cell::sync::Lazy;
 
@@ -14,7 +15,7 @@ use casper_execution_engine::{
 use casper_types::{
     system::{
         auction::{
-            Bids, DelegationRate, SeigniorageRecipientsSnapshot, BLOCK_REWARD,
+            Bids, DelegationRate, SeigniorageRecipientsSnapshot, SlashingOffence, BLOCK_REWARD,
             SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY,
         },
         mint::TOTAL_SUPPLY_KEY,
@@ -88,7 +89,11 @@ fn should_step() {
     let step_request = StepRequestBuilder::new()
         .with_parent_state_hash(builder.get_post_state_hash())
         .with_protocol_version(ProtocolVersion::V1_0_0)
-        .with_slash_item(SlashItem::new(ACCOUNT_1_PK.clone()))
+        .with_slash_item(SlashItem::new(
+            ACCOUNT_1_PK.clone(),
+            SlashingOffence::Equivocation,
+            Ratio::from_integer(1),
+        ))
         .with_reward_item(RewardItem::new(ACCOUNT_1_PK.clone(), BLOCK_REWARD / 2))
         .with_reward_item(RewardItem::new(ACCOUNT_2_PK.clone(), BLOCK_REWARD / 2))
         .with_next_era_id(EraId::from(1))
@@ -164,8 +169,16 @@ fn should_adjust_total_supply() {
     let step_request = StepRequestBuilder::new()
         .with_parent_state_hash(builder.get_post_state_hash())
         .with_protocol_version(ProtocolVersion::V1_0_0)
-        .with_slash_item(SlashItem::new(ACCOUNT_1_PK.clone()))
-        .with_slash_item(SlashItem::new(ACCOUNT_2_PK.clone()))
+        .with_slash_item(SlashItem::new(
+            ACCOUNT_1_PK.clone(),
+            SlashingOffence::Equivocation,
+            Ratio::from_integer(1),
+        ))
+        .with_slash_item(SlashItem::new(
+            ACCOUNT_2_PK.clone(),
+            SlashingOffence::Equivocation,
+            Ratio::from_integer(1),
+        ))
         .with_reward_item(RewardItem::new(ACCOUNT_1_PK.clone(), 0))
         .with_reward_item(RewardItem::new(ACCOUNT_2_PK.clone(), BLOCK_REWARD / 2))
         .with_next_era_id(EraId::from(1))
@@ -190,3 +203,56 @@ fn should_adjust_total_supply() {
         "total supply should be reduced due to slashing"
     );
 }
+
+/// Should burn only the configured fraction of a validator's stake and record it in era info.
+#[ignore]
+#[test]
+fn should_slash_fraction_of_stake() {
+    let mut builder = initialize_builder();
+
+    let step_request = StepRequestBuilder::new()
+        .with_parent_state_hash(builder.get_post_state_hash())
+        .with_protocol_version(ProtocolVersion::V1_0_0)
+        .with_slash_item(SlashItem::new(
+            ACCOUNT_1_PK.clone(),
+            SlashingOffence::Unavailability,
+            Ratio::new(1, 4),
+        ))
+        .with_slash_item(SlashItem::new(
+            ACCOUNT_2_PK.clone(),
+            SlashingOffence::Unavailability,
+            Ratio::from_integer(0),
+        ))
+        .with_reward_item(RewardItem::new(ACCOUNT_1_PK.clone(), 0))
+        .with_reward_item(RewardItem::new(ACCOUNT_2_PK.clone(), 0))
+        .with_next_era_id(EraId::from(1))
+        .build();
+
+    builder.step(step_request).unwrap();
+
+    let bids: Bids = builder.get_bids();
+    let account_1_bid = bids.get(&ACCOUNT_1_PK).unwrap();
+    assert!(!account_1_bid.inactive());
+    assert_eq!(
+        *account_1_bid.staked_amount(),
+        U512::from(ACCOUNT_1_BOND - ACCOUNT_1_BOND / 4)
+    );
+    let account_2_bid = bids.get(&ACCOUNT_2_PK).unwrap();
+    assert_eq!(*account_2_bid.staked_amount(), U512::from(ACCOUNT_2_BOND));
+
+    let era_info = builder
+        .query(None, Key::EraSummary, &[])
+        .expect("should have value")
+        .as_era_info()
+        .cloned()
+        .expect("should be era info");
+    assert_eq!(era_info.slashes().len(), 1);
+    let slashed_stake = &era_info.slashes()[0];
+    assert_eq!(slashed_stake.validator_public_key(), &*ACCOUNT_1_PK);
+    assert_eq!(slashed_stake.offence(), SlashingOffence::Unavailability);
+    assert_eq!(
+        *slashed_stake.validator_amount(),
+        U512::from(ACCOUNT_1_BOND / 4)
+    );
+    assert!(slashed_stake.delegators_amount().is_zero());
+}
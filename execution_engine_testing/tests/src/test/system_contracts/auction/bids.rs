@@ -6,11 +6,11 @@ use once_cell::sync::Lazy;
 
 use casper_engine_test_support::{
     utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, StepRequestBuilder, DEFAULT_ACCOUNTS,
-    DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE, DEFAULT_CHAINSPEC_REGISTRY,
-    DEFAULT_EXEC_CONFIG, DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_GENESIS_TIMESTAMP_MILLIS,
-    DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS, DEFAULT_PROTOCOL_VERSION, DEFAULT_UNBONDING_DELAY,
-    MINIMUM_ACCOUNT_CREATION_BALANCE, PRODUCTION_RUN_GENESIS_REQUEST, SYSTEM_ADDR,
-    TIMESTAMP_MILLIS_INCREMENT,
+    DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE, DEFAULT_ACCOUNT_PUBLIC_KEY,
+    DEFAULT_CHAINSPEC_REGISTRY, DEFAULT_EXEC_CONFIG, DEFAULT_GENESIS_CONFIG_HASH,
+    DEFAULT_GENESIS_TIMESTAMP_MILLIS, DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS, DEFAULT_PROTOCOL_VERSION,
+    DEFAULT_UNBONDING_DELAY, MINIMUM_ACCOUNT_CREATION_BALANCE, PRODUCTION_RUN_GENESIS_REQUEST,
+    SYSTEM_ADDR, TIMESTAMP_MILLIS_INCREMENT,
 };
 use casper_execution_engine::{
     core::{
@@ -35,8 +35,9 @@ use casper_types::{
         self,
         auction::{
             self, Bids, DelegationRate, EraValidators, Error as AuctionError, UnbondingPurses,
-            ValidatorWeights, ARG_AMOUNT, ARG_DELEGATION_RATE, ARG_DELEGATOR, ARG_NEW_VALIDATOR,
-            ARG_PUBLIC_KEY, ARG_VALIDATOR, ERA_ID_KEY, INITIAL_ERA_ID,
+            ValidatorWeights, VestingCurve, VestingTranche, ARG_AMOUNT, ARG_DELEGATION_RATE,
+            ARG_DELEGATOR, ARG_NEW_VALIDATOR, ARG_PUBLIC_KEY, ARG_VALIDATOR, ERA_ID_KEY,
+            INITIAL_ERA_ID,
         },
     },
     EraId, Motes, ProtocolVersion, PublicKey, RuntimeArgs, SecretKey, U256, U512,
@@ -2069,6 +2070,130 @@ fn should_handle_evictions() {
     );
 }
 
+#[ignore]
+#[test]
+fn should_jail_inactive_validators_with_escalating_jail_periods() {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        let account_1 = GenesisAccount::account(
+            ACCOUNT_1_PK.clone(),
+            Motes::new(ACCOUNT_1_BALANCE.into()),
+            Some(GenesisValidator::new(
+                Motes::new(ACCOUNT_1_BOND.into()),
+                DelegationRate::zero(),
+            )),
+        );
+        let account_2 = GenesisAccount::account(
+            BID_ACCOUNT_1_PK.clone(),
+            Motes::new(BID_ACCOUNT_1_BALANCE.into()),
+            Some(GenesisValidator::new(
+                Motes::new(300_000.into()),
+                DelegationRate::zero(),
+            )),
+        );
+        tmp.push(account_1);
+        tmp.push(account_2);
+        tmp
+    };
+
+    let engine_config = EngineConfigBuilder::new()
+        .with_inactivity_jail_threshold(2)
+        .with_jail_period_eras(1)
+        .with_max_jail_period_eras(2)
+        .build();
+
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+
+    builder.run_genesis(&utils::create_run_genesis_request(accounts));
+
+    let system_fund_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            "target" => *SYSTEM_ADDR,
+            ARG_AMOUNT => U512::from(SYSTEM_TRANSFER_AMOUNT)
+        },
+    )
+    .build();
+    builder.exec(system_fund_request).commit().expect_success();
+
+    let auction_hash = builder.get_auction_contract_hash();
+    let unjail_request = || {
+        ExecuteRequestBuilder::contract_call_by_hash(
+            *BID_ACCOUNT_1_ADDR,
+            auction_hash,
+            auction::METHOD_UNJAIL,
+            runtime_args! {
+                auction::ARG_VALIDATOR_PUBLIC_KEY => BID_ACCOUNT_1_PK.clone(),
+            },
+        )
+        .build()
+    };
+
+    let latest_validators = |builder: &mut InMemoryWasmTestBuilder| {
+        let era_validators: EraValidators = builder.get_era_validators();
+        let validators = era_validators
+            .iter()
+            .next_back()
+            .map(|(_era_id, validators)| validators)
+            .expect("should have validators");
+        validators.keys().cloned().collect::<BTreeSet<PublicKey>>()
+    };
+
+    let mut timestamp = DEFAULT_GENESIS_TIMESTAMP_MILLIS;
+
+    // A single inactive era stays below the threshold.
+    builder.run_auction(timestamp, vec![BID_ACCOUNT_1_PK.clone()]);
+    timestamp += WEEK_MILLIS;
+
+    let bid = builder.get_bids().get(&BID_ACCOUNT_1_PK).cloned().unwrap();
+    assert!(!bid.inactive());
+    assert_eq!(bid.inactive_eras(), 1);
+    assert!(latest_validators(&mut builder).contains(&BID_ACCOUNT_1_PK));
+
+    // The second consecutive inactive era jails the validator for one era.
+    let era_id = builder.get_era();
+    builder.run_auction(timestamp, vec![BID_ACCOUNT_1_PK.clone()]);
+    timestamp += WEEK_MILLIS;
+
+    let bid = builder.get_bids().get(&BID_ACCOUNT_1_PK).cloned().unwrap();
+    assert!(bid.inactive());
+    assert_eq!(bid.inactive_eras(), 0);
+    assert_eq!(bid.jail_count(), 1);
+    assert_eq!(bid.jailed_until(), Some(era_id + 2));
+    assert!(!latest_validators(&mut builder).contains(&BID_ACCOUNT_1_PK));
+
+    builder.exec(unjail_request()).expect_failure();
+    let error = builder.get_error().expect("must get error");
+    assert!(matches!(
+        error,
+        Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if auction_error == AuctionError::ValidatorJailed as u8));
+
+    builder.run_auction(timestamp, Vec::new());
+    timestamp += WEEK_MILLIS;
+
+    builder.exec(unjail_request()).expect_success().commit();
+    builder.run_auction(timestamp, Vec::new());
+    timestamp += WEEK_MILLIS;
+
+    let bid = builder.get_bids().get(&BID_ACCOUNT_1_PK).cloned().unwrap();
+    assert!(!bid.inactive());
+    assert_eq!(bid.jailed_until(), None);
+    assert!(latest_validators(&mut builder).contains(&BID_ACCOUNT_1_PK));
+
+    // A repeat offence doubles the jail period.
+    builder.run_auction(timestamp, vec![BID_ACCOUNT_1_PK.clone()]);
+    timestamp += WEEK_MILLIS;
+    let era_id = builder.get_era();
+    builder.run_auction(timestamp, vec![BID_ACCOUNT_1_PK.clone()]);
+
+    let bid = builder.get_bids().get(&BID_ACCOUNT_1_PK).cloned().unwrap();
+    assert!(bid.inactive());
+    assert_eq!(bid.jail_count(), 2);
+    assert_eq!(bid.jailed_until(), Some(era_id + 3));
+}
+
 #[should_panic(expected = "OrphanedDelegator")]
 #[ignore]
 #[test]
@@ -2222,6 +2347,33 @@ fn should_validate_bond_amount_of_genesis_validator() {
     builder.run_genesis(&run_genesis_request);
 }
 
+#[should_panic(expected = "InvalidVestingCurve")]
+#[ignore]
+#[test]
+fn should_validate_vesting_curve_of_genesis_validator() {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        let account_1 = GenesisAccount::account(
+            ACCOUNT_1_PK.clone(),
+            Motes::new(ACCOUNT_1_BALANCE.into()),
+            Some(
+                GenesisValidator::new(Motes::new(ACCOUNT_1_BOND.into()), DelegationRate::zero())
+                    .with_vesting_curve(Some(VestingCurve::Tranches(vec![VestingTranche::new(
+                        0, 50,
+                    )]))),
+            ),
+        );
+        tmp.push(account_1);
+        tmp
+    };
+
+    let run_genesis_request = utils::create_run_genesis_request(accounts);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&run_genesis_request);
+}
+
 #[ignore]
 #[test]
 fn should_setup_genesis_delegators() {
@@ -4368,3 +4520,720 @@ fn should_increase_existing_delegation_when_limit_exceeded() {
         .expect_success()
         .commit();
 }
+
+#[ignore]
+#[test]
+fn should_enforce_validator_delegation_amount_limits() {
+    const MINIMUM_DELEGATION_AMOUNT: u64 = DEFAULT_MINIMUM_DELEGATION_AMOUNT + 1_000;
+    const MAXIMUM_DELEGATION_AMOUNT: u64 = MINIMUM_DELEGATION_AMOUNT + 5_000;
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let transfer_to_validator_1 = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => *NON_FOUNDER_VALIDATOR_1_ADDR,
+            ARG_AMOUNT => U512::from(TRANSFER_AMOUNT)
+        },
+    )
+    .build();
+
+    let transfer_to_delegator_1 = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => *BID_ACCOUNT_1_ADDR,
+            ARG_AMOUNT => U512::from(BID_ACCOUNT_1_BALANCE)
+        },
+    )
+    .build();
+
+    let post_genesis_request = vec![transfer_to_validator_1, transfer_to_delegator_1];
+
+    for request in post_genesis_request {
+        builder.exec(request).expect_success().commit();
+    }
+
+    let auction_hash = builder.get_auction_contract_hash();
+    let add_bid_request = |minimum_delegation_amount: u64, maximum_delegation_amount: u64| {
+        ExecuteRequestBuilder::contract_call_by_hash(
+            *NON_FOUNDER_VALIDATOR_1_ADDR,
+            auction_hash,
+            auction::METHOD_ADD_BID,
+            runtime_args! {
+                ARG_PUBLIC_KEY => NON_FOUNDER_VALIDATOR_1_PK.clone(),
+                ARG_AMOUNT => U512::from(ADD_BID_AMOUNT_1),
+                ARG_DELEGATION_RATE => ADD_BID_DELEGATION_RATE_1,
+                auction::ARG_MINIMUM_DELEGATION_AMOUNT => Some(minimum_delegation_amount),
+                auction::ARG_MAXIMUM_DELEGATION_AMOUNT => Some(maximum_delegation_amount),
+            },
+        )
+        .build()
+    };
+
+    let invalid_add_bid_request =
+        add_bid_request(MAXIMUM_DELEGATION_AMOUNT + 1, MAXIMUM_DELEGATION_AMOUNT);
+    builder.exec(invalid_add_bid_request).expect_failure();
+    let error = builder.get_error().expect("must get error");
+    assert!(matches!(
+        error,
+        Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if auction_error == AuctionError::InvalidDelegationAmountLimits as u8));
+
+    let add_bid_request_1 = add_bid_request(MINIMUM_DELEGATION_AMOUNT, MAXIMUM_DELEGATION_AMOUNT);
+    builder.exec(add_bid_request_1).expect_success().commit();
+
+    let bids: Bids = builder.get_bids();
+    let bid = bids
+        .get(&NON_FOUNDER_VALIDATOR_1_PK)
+        .expect("should have bid");
+    assert_eq!(
+        bid.minimum_delegation_amount(),
+        Some(MINIMUM_DELEGATION_AMOUNT)
+    );
+    assert_eq!(
+        bid.maximum_delegation_amount(),
+        Some(MAXIMUM_DELEGATION_AMOUNT)
+    );
+
+    let delegation_request = |amount: u64| {
+        ExecuteRequestBuilder::standard(
+            *BID_ACCOUNT_1_ADDR,
+            CONTRACT_DELEGATE,
+            runtime_args! {
+                ARG_AMOUNT => U512::from(amount),
+                ARG_VALIDATOR => NON_FOUNDER_VALIDATOR_1_PK.clone(),
+                ARG_DELEGATOR => BID_ACCOUNT_1_PK.clone(),
+            },
+        )
+        .build()
+    };
+
+    // Above the chain-wide minimum, but below the validator's.
+    builder
+        .exec(delegation_request(MINIMUM_DELEGATION_AMOUNT - 1))
+        .expect_failure();
+    let error = builder.get_error().expect("must get error");
+    assert!(matches!(
+        error,
+        Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if auction_error == AuctionError::DelegationAmountTooSmall as u8));
+
+    builder
+        .exec(delegation_request(MINIMUM_DELEGATION_AMOUNT))
+        .expect_success()
+        .commit();
+
+    // The total delegated by the delegator would exceed the validator's maximum.
+    builder
+        .exec(delegation_request(
+            MAXIMUM_DELEGATION_AMOUNT - MINIMUM_DELEGATION_AMOUNT + 1,
+        ))
+        .expect_failure();
+    let error = builder.get_error().expect("must get error");
+    assert!(matches!(
+        error,
+        Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if auction_error == AuctionError::DelegationAmountTooLarge as u8));
+}
+
+#[ignore]
+#[test]
+fn should_only_allow_delegations_from_reserved_delegators() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let transfer_to_validator_1 = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => *NON_FOUNDER_VALIDATOR_1_ADDR,
+            ARG_AMOUNT => U512::from(TRANSFER_AMOUNT)
+        },
+    )
+    .build();
+
+    let transfer_to_delegator_1 = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => *BID_ACCOUNT_1_ADDR,
+            ARG_AMOUNT => U512::from(BID_ACCOUNT_1_BALANCE)
+        },
+    )
+    .build();
+
+    let transfer_to_delegator_2 = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => *BID_ACCOUNT_2_ADDR,
+            ARG_AMOUNT => U512::from(BID_ACCOUNT_2_BALANCE)
+        },
+    )
+    .build();
+
+    let add_bid_request_1 = ExecuteRequestBuilder::standard(
+        *NON_FOUNDER_VALIDATOR_1_ADDR,
+        CONTRACT_ADD_BID,
+        runtime_args! {
+            ARG_PUBLIC_KEY => NON_FOUNDER_VALIDATOR_1_PK.clone(),
+            ARG_AMOUNT => U512::from(ADD_BID_AMOUNT_1),
+            ARG_DELEGATION_RATE => ADD_BID_DELEGATION_RATE_1,
+        },
+    )
+    .build();
+
+    let post_genesis_request = vec![
+        transfer_to_validator_1,
+        transfer_to_delegator_1,
+        transfer_to_delegator_2,
+        add_bid_request_1,
+    ];
+
+    for request in post_genesis_request {
+        builder.exec(request).expect_success().commit();
+    }
+
+    let auction_hash = builder.get_auction_contract_hash();
+    let reservations_request = |entry_point: &str, delegators: Vec<PublicKey>| {
+        ExecuteRequestBuilder::contract_call_by_hash(
+            *NON_FOUNDER_VALIDATOR_1_ADDR,
+            auction_hash,
+            entry_point,
+            runtime_args! {
+                ARG_VALIDATOR => NON_FOUNDER_VALIDATOR_1_PK.clone(),
+                auction::ARG_DELEGATORS => delegators,
+            },
+        )
+        .build()
+    };
+
+    builder
+        .exec(reservations_request(
+            auction::METHOD_ADD_RESERVATIONS,
+            vec![BID_ACCOUNT_1_PK.clone()],
+        ))
+        .expect_success()
+        .commit();
+
+    let bids: Bids = builder.get_bids();
+    let bid = bids
+        .get(&NON_FOUNDER_VALIDATOR_1_PK)
+        .expect("should have bid");
+    assert_eq!(
+        bid.reserved_delegators(),
+        &BTreeSet::from_iter([BID_ACCOUNT_1_PK.clone()])
+    );
+
+    let delegation_request = |delegator_addr: AccountHash, delegator_pk: PublicKey| {
+        ExecuteRequestBuilder::standard(
+            delegator_addr,
+            CONTRACT_DELEGATE,
+            runtime_args! {
+                ARG_AMOUNT => U512::from(DELEGATE_AMOUNT_1),
+                ARG_VALIDATOR => NON_FOUNDER_VALIDATOR_1_PK.clone(),
+                ARG_DELEGATOR => delegator_pk,
+            },
+        )
+        .build()
+    };
+
+    builder
+        .exec(delegation_request(
+            *BID_ACCOUNT_2_ADDR,
+            BID_ACCOUNT_2_PK.clone(),
+        ))
+        .expect_failure();
+    let error = builder.get_error().expect("must get error");
+    assert!(matches!(
+        error,
+        Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if auction_error == AuctionError::DelegatorNotReserved as u8));
+
+    builder
+        .exec(delegation_request(
+            *BID_ACCOUNT_1_ADDR,
+            BID_ACCOUNT_1_PK.clone(),
+        ))
+        .expect_success()
+        .commit();
+
+    builder
+        .exec(reservations_request(
+            auction::METHOD_CANCEL_RESERVATIONS,
+            vec![BID_ACCOUNT_2_PK.clone()],
+        ))
+        .expect_failure();
+    let error = builder.get_error().expect("must get error");
+    assert!(matches!(
+        error,
+        Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if auction_error == AuctionError::ReservationNotFound as u8));
+
+    // Once the last reservation is cancelled, anyone can delegate again.
+    builder
+        .exec(reservations_request(
+            auction::METHOD_CANCEL_RESERVATIONS,
+            vec![BID_ACCOUNT_1_PK.clone()],
+        ))
+        .expect_success()
+        .commit();
+
+    builder
+        .exec(delegation_request(
+            *BID_ACCOUNT_2_ADDR,
+            BID_ACCOUNT_2_PK.clone(),
+        ))
+        .expect_success()
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_not_allow_more_reservations_than_the_limit() {
+    let engine_config = EngineConfigBuilder::new()
+        .with_max_delegators_per_validator(None)
+        .with_max_reserved_delegators_per_validator(1)
+        .build();
+
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let transfer_to_validator_1 = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => *NON_FOUNDER_VALIDATOR_1_ADDR,
+            ARG_AMOUNT => U512::from(TRANSFER_AMOUNT)
+        },
+    )
+    .build();
+
+    let add_bid_request_1 = ExecuteRequestBuilder::standard(
+        *NON_FOUNDER_VALIDATOR_1_ADDR,
+        CONTRACT_ADD_BID,
+        runtime_args! {
+            ARG_PUBLIC_KEY => NON_FOUNDER_VALIDATOR_1_PK.clone(),
+            ARG_AMOUNT => U512::from(ADD_BID_AMOUNT_1),
+            ARG_DELEGATION_RATE => ADD_BID_DELEGATION_RATE_1,
+        },
+    )
+    .build();
+
+    for request in [transfer_to_validator_1, add_bid_request_1] {
+        builder.exec(request).expect_success().commit();
+    }
+
+    let auction_hash = builder.get_auction_contract_hash();
+    let add_reservations_request = |delegators: Vec<PublicKey>| {
+        ExecuteRequestBuilder::contract_call_by_hash(
+            *NON_FOUNDER_VALIDATOR_1_ADDR,
+            auction_hash,
+            auction::METHOD_ADD_RESERVATIONS,
+            runtime_args! {
+                ARG_VALIDATOR => NON_FOUNDER_VALIDATOR_1_PK.clone(),
+                auction::ARG_DELEGATORS => delegators,
+            },
+        )
+        .build()
+    };
+
+    // The limit applies to a single call...
+    builder
+        .exec(add_reservations_request(vec![
+            BID_ACCOUNT_1_PK.clone(),
+            BID_ACCOUNT_2_PK.clone(),
+        ]))
+        .expect_failure();
+    let error = builder.get_error().expect("must get error");
+    assert!(matches!(
+        error,
+        Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if auction_error == AuctionError::ExceededReservationsLimit as u8));
+
+    builder
+        .exec(add_reservations_request(vec![BID_ACCOUNT_1_PK.clone()]))
+        .expect_success()
+        .commit();
+
+    // ...as well as across calls.
+    builder
+        .exec(add_reservations_request(vec![BID_ACCOUNT_2_PK.clone()]))
+        .expect_failure();
+    let error = builder.get_error().expect("must get error");
+    assert!(matches!(
+        error,
+        Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if auction_error == AuctionError::ExceededReservationsLimit as u8));
+
+    // Re-adding an existing reservation doesn't count against the limit.
+    builder
+        .exec(add_reservations_request(vec![BID_ACCOUNT_1_PK.clone()]))
+        .expect_success()
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_transfer_delegation_without_unbonding() {
+    let engine_config = EngineConfigBuilder::new()
+        .with_allow_delegation_transfers(true)
+        .build();
+
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let transfer_to_validator_1 = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => *NON_FOUNDER_VALIDATOR_1_ADDR,
+            ARG_AMOUNT => U512::from(TRANSFER_AMOUNT)
+        },
+    )
+    .build();
+
+    let transfer_to_delegator_1 = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => *BID_ACCOUNT_1_ADDR,
+            ARG_AMOUNT => U512::from(BID_ACCOUNT_1_BALANCE)
+        },
+    )
+    .build();
+
+    let transfer_to_delegator_2 = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => *BID_ACCOUNT_2_ADDR,
+            ARG_AMOUNT => U512::from(BID_ACCOUNT_2_BALANCE)
+        },
+    )
+    .build();
+
+    let add_bid_request_1 = ExecuteRequestBuilder::standard(
+        *NON_FOUNDER_VALIDATOR_1_ADDR,
+        CONTRACT_ADD_BID,
+        runtime_args! {
+            ARG_PUBLIC_KEY => NON_FOUNDER_VALIDATOR_1_PK.clone(),
+            ARG_AMOUNT => U512::from(ADD_BID_AMOUNT_1),
+            ARG_DELEGATION_RATE => ADD_BID_DELEGATION_RATE_1,
+        },
+    )
+    .build();
+
+    let delegate_request_1 = ExecuteRequestBuilder::standard(
+        *BID_ACCOUNT_1_ADDR,
+        CONTRACT_DELEGATE,
+        runtime_args! {
+            ARG_AMOUNT => U512::from(DELEGATE_AMOUNT_1),
+            ARG_VALIDATOR => NON_FOUNDER_VALIDATOR_1_PK.clone(),
+            ARG_DELEGATOR => BID_ACCOUNT_1_PK.clone(),
+        },
+    )
+    .build();
+
+    let post_genesis_request = vec![
+        transfer_to_validator_1,
+        transfer_to_delegator_1,
+        transfer_to_delegator_2,
+        add_bid_request_1,
+        delegate_request_1,
+    ];
+
+    for request in post_genesis_request {
+        builder.exec(request).expect_success().commit();
+    }
+
+    let auction_hash = builder.get_auction_contract_hash();
+    let transfer_delegation_request = |amount: u64, new_delegator: PublicKey| {
+        ExecuteRequestBuilder::contract_call_by_hash(
+            *BID_ACCOUNT_1_ADDR,
+            auction_hash,
+            auction::METHOD_TRANSFER_DELEGATION,
+            runtime_args! {
+                ARG_DELEGATOR => BID_ACCOUNT_1_PK.clone(),
+                ARG_VALIDATOR => NON_FOUNDER_VALIDATOR_1_PK.clone(),
+                ARG_AMOUNT => U512::from(amount),
+                auction::ARG_NEW_DELEGATOR => new_delegator,
+            },
+        )
+        .build()
+    };
+
+    builder
+        .exec(transfer_delegation_request(
+            DEFAULT_MINIMUM_DELEGATION_AMOUNT,
+            BID_ACCOUNT_1_PK.clone(),
+        ))
+        .expect_failure();
+    let error = builder.get_error().expect("must get error");
+    assert!(matches!(
+        error,
+        Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if auction_error == AuctionError::DelegationTransferToSelf as u8));
+
+    builder
+        .exec(transfer_delegation_request(
+            DEFAULT_MINIMUM_DELEGATION_AMOUNT,
+            BID_ACCOUNT_2_PK.clone(),
+        ))
+        .expect_success()
+        .commit();
+
+    let bids: Bids = builder.get_bids();
+    let delegators = bids
+        .get(&NON_FOUNDER_VALIDATOR_1_PK)
+        .expect("should have bid")
+        .delegators();
+    assert_eq!(
+        *delegators[&BID_ACCOUNT_1_PK].staked_amount(),
+        U512::from(DELEGATE_AMOUNT_1 - DEFAULT_MINIMUM_DELEGATION_AMOUNT)
+    );
+    assert_eq!(
+        *delegators[&BID_ACCOUNT_2_PK].staked_amount(),
+        U512::from(DEFAULT_MINIMUM_DELEGATION_AMOUNT)
+    );
+    assert_eq!(
+        builder.get_purse_balance(*delegators[&BID_ACCOUNT_2_PK].bonding_purse()),
+        U512::from(DEFAULT_MINIMUM_DELEGATION_AMOUNT)
+    );
+
+    // The transferred delegation is not unbonded.
+    let unbonding_purses: UnbondingPurses = builder.get_unbonds();
+    assert!(unbonding_purses.is_empty());
+
+    // Transferring the remaining delegation removes the delegator.
+    builder
+        .exec(transfer_delegation_request(
+            DELEGATE_AMOUNT_1 - DEFAULT_MINIMUM_DELEGATION_AMOUNT,
+            BID_ACCOUNT_2_PK.clone(),
+        ))
+        .expect_success()
+        .commit();
+
+    let bids: Bids = builder.get_bids();
+    let delegators = bids
+        .get(&NON_FOUNDER_VALIDATOR_1_PK)
+        .expect("should have bid")
+        .delegators();
+    assert!(!delegators.contains_key(&BID_ACCOUNT_1_PK));
+    assert_eq!(
+        *delegators[&BID_ACCOUNT_2_PK].staked_amount(),
+        U512::from(DELEGATE_AMOUNT_1)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_transfer_delegation_if_disabled() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let auction_hash = builder.get_auction_contract_hash();
+    let transfer_delegation_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        auction_hash,
+        auction::METHOD_TRANSFER_DELEGATION,
+        runtime_args! {
+            ARG_DELEGATOR => DEFAULT_ACCOUNT_PUBLIC_KEY.clone(),
+            ARG_VALIDATOR => NON_FOUNDER_VALIDATOR_1_PK.clone(),
+            ARG_AMOUNT => U512::from(DEFAULT_MINIMUM_DELEGATION_AMOUNT),
+            auction::ARG_NEW_DELEGATOR => BID_ACCOUNT_1_PK.clone(),
+        },
+    )
+    .build();
+
+    builder.exec(transfer_delegation_request).expect_failure();
+    let error = builder.get_error().expect("must get error");
+    assert!(matches!(
+        error,
+        Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if auction_error == AuctionError::DelegationTransfersDisabled as u8));
+}
+
+#[ignore]
+#[test]
+fn should_release_genesis_stake_along_custom_vesting_curves() {
+    const VALIDATOR_1_UNLOCKED_STAKE: u64 = VALIDATOR_1_STAKE / 2;
+    const DELEGATOR_1_UNLOCKED_STAKE: u64 = DELEGATOR_1_STAKE / 4;
+
+    let validator_vesting_curve = VestingCurve::Linear {
+        cliff_millis: 4 * WEEK_MILLIS,
+        step_millis: 4 * WEEK_MILLIS,
+        steps: 2,
+    };
+    let delegator_vesting_curve = VestingCurve::Tranches(vec![
+        VestingTranche::new(0, 25),
+        VestingTranche::new(52 * WEEK_MILLIS, 75),
+    ]);
+
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        let validator_1 = GenesisAccount::account(
+            VALIDATOR_1.clone(),
+            Motes::new(DEFAULT_ACCOUNT_INITIAL_BALANCE.into()),
+            Some(
+                GenesisValidator::new(Motes::new(VALIDATOR_1_STAKE.into()), DelegationRate::zero())
+                    .with_vesting_curve(Some(validator_vesting_curve.clone())),
+            ),
+        );
+        let delegator_1 = GenesisAccount::delegator_with_vesting_curve(
+            VALIDATOR_1.clone(),
+            DELEGATOR_1.clone(),
+            Motes::new(DEFAULT_ACCOUNT_INITIAL_BALANCE.into()),
+            Motes::new(DELEGATOR_1_STAKE.into()),
+            Some(delegator_vesting_curve.clone()),
+        );
+        tmp.push(validator_1);
+        tmp.push(delegator_1);
+        tmp
+    };
+
+    let run_genesis_request = {
+        let exec_config = ExecConfigBuilder::default()
+            .with_accounts(accounts)
+            .with_locked_funds_period_millis(CASPER_LOCKED_FUNDS_PERIOD_MILLIS)
+            .build();
+
+        RunGenesisRequest::new(
+            *DEFAULT_GENESIS_CONFIG_HASH,
+            *DEFAULT_PROTOCOL_VERSION,
+            exec_config,
+            DEFAULT_CHAINSPEC_REGISTRY.clone(),
+        )
+    };
+
+    let custom_engine_config = EngineConfigBuilder::default()
+        .with_vesting_schedule_period_millis(CASPER_VESTING_SCHEDULE_PERIOD_MILLIS)
+        .build();
+
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+
+    let mut builder = InMemoryWasmTestBuilder::new(global_state, custom_engine_config, None);
+
+    builder.run_genesis(&run_genesis_request);
+
+    let fund_system_account = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => *SYSTEM_ADDR,
+            ARG_AMOUNT => U512::from(MINIMUM_ACCOUNT_CREATION_BALANCE)
+        },
+    )
+    .build();
+    builder.exec(fund_system_account).commit().expect_success();
+
+    {
+        let bids: Bids = builder.get_bids();
+        let bid = bids.get(&*VALIDATOR_1).expect("should have validator");
+        let vesting_schedule = bid
+            .vesting_schedule()
+            .expect("should have vesting schedule");
+        assert_eq!(
+            vesting_schedule.vesting_curve(),
+            Some(&validator_vesting_curve)
+        );
+        assert_eq!(vesting_schedule.vested_amount(), None);
+
+        let delegator_vesting_schedule = bid
+            .delegators()
+            .get(&*DELEGATOR_1)
+            .expect("should have delegator")
+            .vesting_schedule()
+            .expect("should have vesting schedule");
+        assert_eq!(
+            delegator_vesting_schedule.vesting_curve(),
+            Some(&delegator_vesting_curve)
+        );
+    }
+
+    builder.run_auction(WEEK_TIMESTAMPS[0], Vec::new());
+
+    {
+        let bids: Bids = builder.get_bids();
+        let bid = bids.get(&*VALIDATOR_1).expect("should have validator");
+        let vesting_schedule = bid
+            .vesting_schedule()
+            .expect("should have vesting schedule");
+        assert_eq!(
+            vesting_schedule.vested_amount(),
+            Some(U512::from(VALIDATOR_1_STAKE))
+        );
+        assert_eq!(vesting_schedule.locked_amounts(), None);
+    }
+
+    let withdraw_bid_request = |amount: u64| {
+        ExecuteRequestBuilder::standard(
+            *VALIDATOR_1_ADDR,
+            CONTRACT_WITHDRAW_BID,
+            runtime_args! {
+                ARG_PUBLIC_KEY => VALIDATOR_1.clone(),
+                ARG_AMOUNT => U512::from(amount),
+            },
+        )
+        .build()
+    };
+    let undelegate_request = |amount: u64| {
+        ExecuteRequestBuilder::standard(
+            *DELEGATOR_1_ADDR,
+            CONTRACT_UNDELEGATE,
+            runtime_args! {
+                auction::ARG_VALIDATOR => VALIDATOR_1.clone(),
+                auction::ARG_DELEGATOR => DELEGATOR_1.clone(),
+                ARG_AMOUNT => U512::from(amount),
+            },
+        )
+        .build()
+    };
+
+    // Nothing of the validator's stake is released before the cliff.
+    builder.exec(withdraw_bid_request(1)).expect_failure();
+    let error = builder.get_error().expect("must get error");
+    assert!(matches!(
+        error,
+        Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if auction_error == AuctionError::ValidatorFundsLocked as u8));
+
+    // Only the first tranche of the delegator's stake is released.
+    builder
+        .exec(undelegate_request(DELEGATOR_1_UNLOCKED_STAKE + 1))
+        .expect_failure();
+    let error = builder.get_error().expect("must get error");
+    assert!(matches!(
+        error,
+        Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if auction_error == AuctionError::DelegatorFundsLocked as u8));
+
+    builder
+        .exec(undelegate_request(DELEGATOR_1_UNLOCKED_STAKE))
+        .commit()
+        .expect_success();
+
+    builder.run_auction(WEEK_TIMESTAMPS[4], Vec::new());
+
+    builder
+        .exec(withdraw_bid_request(VALIDATOR_1_UNLOCKED_STAKE + 1))
+        .expect_failure();
+    let error = builder.get_error().expect("must get error");
+    assert!(matches!(
+        error,
+        Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if auction_error == AuctionError::ValidatorFundsLocked as u8));
+
+    builder
+        .exec(withdraw_bid_request(VALIDATOR_1_UNLOCKED_STAKE))
+        .commit()
+        .expect_success();
+}
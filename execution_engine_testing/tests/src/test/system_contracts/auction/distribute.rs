@@ -19,10 +19,11 @@ use casper_types::{
     account::AccountHash,
     runtime_args,
     system::auction::{
-        self, Bid, Bids, DelegationRate, Delegator, SeigniorageAllocation,
+        self, Bid, Bids, DelegationRate, Delegator, RewardDestination, SeigniorageAllocation,
         SeigniorageRecipientsSnapshot, ARG_AMOUNT, ARG_DELEGATION_RATE, ARG_DELEGATOR,
-        ARG_PUBLIC_KEY, ARG_REWARD_FACTORS, ARG_VALIDATOR, BLOCK_REWARD,
-        DELEGATION_RATE_DENOMINATOR, METHOD_DISTRIBUTE, SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY,
+        ARG_PUBLIC_KEY, ARG_REWARD_DESTINATION, ARG_REWARD_FACTORS, ARG_VALIDATOR, BLOCK_REWARD,
+        DELEGATION_RATE_DENOMINATOR, METHOD_DISTRIBUTE, METHOD_SET_REWARD_DESTINATION,
+        SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY,
     },
     EraId, Key, ProtocolVersion, PublicKey, RuntimeArgs, SecretKey, U512,
 };
@@ -406,7 +407,7 @@ fn should_distribute_delegation_rate_zero() {
 
     assert!(matches!(
         era_info.select(VALIDATOR_1.clone()).next(),
-        Some(SeigniorageAllocation::Validator { validator_public_key, amount })
+        Some(SeigniorageAllocation::Validator { validator_public_key, amount, .. })
         if *validator_public_key == *VALIDATOR_1 && *amount == validator_1_expected_payout
     ));
 
@@ -714,7 +715,7 @@ fn should_withdraw_bids_after_distribute() {
 
     assert!(matches!(
         era_info.select(VALIDATOR_1.clone()).next(),
-        Some(SeigniorageAllocation::Validator { validator_public_key, amount })
+        Some(SeigniorageAllocation::Validator { validator_public_key, amount, .. })
         if *validator_public_key == *VALIDATOR_1 && *amount == validator_1_expected_payout
     ));
 
@@ -955,7 +956,7 @@ fn should_distribute_rewards_after_restaking_delegated_funds() {
 
     assert!(matches!(
         era_info_1.select(VALIDATOR_1.clone()).next(),
-        Some(SeigniorageAllocation::Validator { validator_public_key, amount })
+        Some(SeigniorageAllocation::Validator { validator_public_key, amount, .. })
         if *validator_public_key == *VALIDATOR_1 && *amount == validator_1_expected_payout_1
     ));
 
@@ -1373,19 +1374,19 @@ fn should_distribute_reinvested_rewards_by_different_factor() {
 
     assert!(matches!(
         era_info_1.select(VALIDATOR_1.clone()).next(),
-        Some(SeigniorageAllocation::Validator { validator_public_key, amount })
+        Some(SeigniorageAllocation::Validator { validator_public_key, amount, .. })
         if *validator_public_key == *VALIDATOR_1 && *amount == validator_1_expected_payout_1
     ));
 
     assert!(matches!(
         era_info_1.select(VALIDATOR_2.clone()).next(),
-        Some(SeigniorageAllocation::Validator { validator_public_key, amount })
+        Some(SeigniorageAllocation::Validator { validator_public_key, amount, .. })
         if *validator_public_key == *VALIDATOR_2 && *amount == validator_2_expected_payout_1
     ));
 
     assert!(matches!(
         era_info_1.select(VALIDATOR_3.clone()).next(),
-        Some(SeigniorageAllocation::Validator { validator_public_key, amount })
+        Some(SeigniorageAllocation::Validator { validator_public_key, amount, .. })
         if *validator_public_key == *VALIDATOR_3 && *amount == validator_3_expected_payout_1
     ));
 
@@ -1506,19 +1507,19 @@ fn should_distribute_reinvested_rewards_by_different_factor() {
 
     assert!(matches!(
         era_info_2.select(VALIDATOR_1.clone()).next(),
-        Some(SeigniorageAllocation::Validator { validator_public_key, amount })
+        Some(SeigniorageAllocation::Validator { validator_public_key, amount, .. })
         if *validator_public_key == *VALIDATOR_1 && *amount == validator_1_expected_payout_2
     ));
 
     assert!(matches!(
         era_info_2.select(VALIDATOR_2.clone()).next(),
-        Some(SeigniorageAllocation::Validator { validator_public_key, amount })
+        Some(SeigniorageAllocation::Validator { validator_public_key, amount, .. })
         if *validator_public_key == *VALIDATOR_2 && *amount == validator_2_expected_payout_2
     ));
 
     assert!(matches!(
         era_info_2.select(VALIDATOR_3.clone()).next(),
-        Some(SeigniorageAllocation::Validator { validator_public_key, amount })
+        Some(SeigniorageAllocation::Validator { validator_public_key, amount, .. })
         if *validator_public_key == *VALIDATOR_3 && *amount == validator_3_expected_payout_2
     ));
 
@@ -1786,7 +1787,7 @@ fn should_distribute_delegation_rate_half() {
 
     assert!(matches!(
         era_info.select(VALIDATOR_1.clone()).next(),
-        Some(SeigniorageAllocation::Validator { validator_public_key, amount })
+        Some(SeigniorageAllocation::Validator { validator_public_key, amount, .. })
         if *validator_public_key == *VALIDATOR_1 && *amount == validator_1_expected_payout
     ));
 
@@ -1979,7 +1980,7 @@ fn should_distribute_delegation_rate_full() {
 
     assert!(matches!(
         era_info.select(VALIDATOR_1.clone()).next(),
-        Some(SeigniorageAllocation::Validator { validator_public_key, amount })
+        Some(SeigniorageAllocation::Validator { validator_public_key, amount, .. })
         if *validator_public_key == *VALIDATOR_1 && *amount == expected_validator_1_balance
     ));
 
@@ -2212,7 +2213,7 @@ fn should_distribute_uneven_delegation_rate_zero() {
 
     assert!(matches!(
         era_info.select(VALIDATOR_1.clone()).next(),
-        Some(SeigniorageAllocation::Validator { validator_public_key, amount })
+        Some(SeigniorageAllocation::Validator { validator_public_key, amount, .. })
         if *validator_public_key == *VALIDATOR_1 && *amount == validator_1_expected_payout
     ));
 
@@ -2439,19 +2440,19 @@ fn should_distribute_by_factor() {
 
     assert!(matches!(
         era_info.select(VALIDATOR_1.clone()).next(),
-        Some(SeigniorageAllocation::Validator { validator_public_key, amount })
+        Some(SeigniorageAllocation::Validator { validator_public_key, amount, .. })
         if *validator_public_key == *VALIDATOR_1 && *amount == validator_1_expected_payout
     ));
 
     assert!(matches!(
         era_info.select(VALIDATOR_2.clone()).next(),
-        Some(SeigniorageAllocation::Validator { validator_public_key, amount })
+        Some(SeigniorageAllocation::Validator { validator_public_key, amount, .. })
         if *validator_public_key == *VALIDATOR_2 && *amount == validator_2_expected_payout
     ));
 
     assert!(matches!(
         era_info.select(VALIDATOR_3.clone()).next(),
-        Some(SeigniorageAllocation::Validator { validator_public_key, amount })
+        Some(SeigniorageAllocation::Validator { validator_public_key, amount, .. })
         if *validator_public_key == *VALIDATOR_3 && *amount == validator_3_expected_payout
     ));
 }
@@ -2668,19 +2669,19 @@ fn should_distribute_by_factor_regardless_of_stake() {
 
     assert!(matches!(
         era_info.select(VALIDATOR_1.clone()).next(),
-        Some(SeigniorageAllocation::Validator { validator_public_key, amount })
+        Some(SeigniorageAllocation::Validator { validator_public_key, amount, .. })
         if *validator_public_key == *VALIDATOR_1 && *amount == validator_1_expected_payout
     ));
 
     assert!(matches!(
         era_info.select(VALIDATOR_2.clone()).next(),
-        Some(SeigniorageAllocation::Validator { validator_public_key, amount })
+        Some(SeigniorageAllocation::Validator { validator_public_key, amount, .. })
         if *validator_public_key == *VALIDATOR_2 && *amount == validator_2_expected_payout
     ));
 
     assert!(matches!(
         era_info.select(VALIDATOR_3.clone()).next(),
-        Some(SeigniorageAllocation::Validator { validator_public_key, amount })
+        Some(SeigniorageAllocation::Validator { validator_public_key, amount, .. })
         if *validator_public_key == *VALIDATOR_3 && *amount == validator_3_expected_payout
     ));
 }
@@ -2893,19 +2894,19 @@ fn should_distribute_by_factor_uneven() {
 
     assert!(matches!(
         era_info.select(VALIDATOR_1.clone()).next(),
-        Some(SeigniorageAllocation::Validator { validator_public_key, amount })
+        Some(SeigniorageAllocation::Validator { validator_public_key, amount, .. })
         if *validator_public_key == *VALIDATOR_1 && *amount == validator_1_expected_payout
     ));
 
     assert!(matches!(
         era_info.select(VALIDATOR_2.clone()).next(),
-        Some(SeigniorageAllocation::Validator { validator_public_key, amount })
+        Some(SeigniorageAllocation::Validator { validator_public_key, amount, .. })
         if *validator_public_key == *VALIDATOR_2 && *amount == validator_2_expected_payout
     ));
 
     assert!(matches!(
         era_info.select(VALIDATOR_3.clone()).next(),
-        Some(SeigniorageAllocation::Validator { validator_public_key, amount })
+        Some(SeigniorageAllocation::Validator { validator_public_key, amount, .. })
         if *validator_public_key == *VALIDATOR_3 && *amount == validator_3_expected_payout
     ));
 }
@@ -3190,19 +3191,19 @@ fn should_distribute_with_multiple_validators_and_delegators() {
 
     assert!(matches!(
         era_info.select(VALIDATOR_1.clone()).next(),
-        Some(SeigniorageAllocation::Validator { validator_public_key, amount })
+        Some(SeigniorageAllocation::Validator { validator_public_key, amount, .. })
         if *validator_public_key == *VALIDATOR_1 && *amount == validator_1_actual_payout
     ));
 
     assert!(matches!(
         era_info.select(VALIDATOR_2.clone()).next(),
-        Some(SeigniorageAllocation::Validator { validator_public_key, amount })
+        Some(SeigniorageAllocation::Validator { validator_public_key, amount, .. })
         if *validator_public_key == *VALIDATOR_2 && *amount == validator_2_actual_payout
     ));
 
     assert!(matches!(
         era_info.select(VALIDATOR_3.clone()).next(),
-        Some(SeigniorageAllocation::Validator { validator_public_key, amount })
+        Some(SeigniorageAllocation::Validator { validator_public_key, amount, .. })
         if *validator_public_key == *VALIDATOR_3 && *amount == validator_3_actual_payout
     ));
 
@@ -3636,19 +3637,19 @@ fn should_distribute_with_multiple_validators_and_shared_delegator() {
 
     assert!(matches!(
         era_info.select(VALIDATOR_1.clone()).next(),
-        Some(SeigniorageAllocation::Validator { validator_public_key, amount })
+        Some(SeigniorageAllocation::Validator { validator_public_key, amount, .. })
         if *validator_public_key == *VALIDATOR_1 && *amount == validator_1_expected_payout
     ));
 
     assert!(matches!(
         era_info.select(VALIDATOR_2.clone()).next(),
-        Some(SeigniorageAllocation::Validator { validator_public_key, amount })
+        Some(SeigniorageAllocation::Validator { validator_public_key, amount, .. })
         if *validator_public_key == *VALIDATOR_2 && *amount == validator_2_expected_payout
     ));
 
     assert!(matches!(
         era_info.select(VALIDATOR_3.clone()).next(),
-        Some(SeigniorageAllocation::Validator { validator_public_key, amount })
+        Some(SeigniorageAllocation::Validator { validator_public_key, amount, .. })
         if *validator_public_key == *VALIDATOR_3 && *amount == validator_3_expected_payout
     ));
 
@@ -4648,3 +4649,130 @@ fn delegator_full_unbond_during_first_reward_era() {
     let delegator = get_delegator_bid(&mut builder, VALIDATOR_1.clone(), DELEGATOR_1.clone());
     assert!(delegator.is_none());
 }
+
+#[ignore]
+#[test]
+fn should_pay_delegator_rewards_to_reward_destination() {
+    const DELEGATOR_1_STAKE: u64 = DEFAULT_MINIMUM_DELEGATION_AMOUNT;
+    const VALIDATOR_1_STAKE: u64 = 1_000_000;
+    const VALIDATOR_1_DELEGATION_RATE: DelegationRate = 0;
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    // advance past the initial auction delay due to special condition of post-genesis behavior.
+    builder.advance_eras_by_default_auction_delay(vec![], vec![]);
+
+    for target in [*VALIDATOR_1_ADDR, *DELEGATOR_1_ADDR] {
+        let fund_request = ExecuteRequestBuilder::standard(
+            *DEFAULT_ACCOUNT_ADDR,
+            CONTRACT_TRANSFER_TO_ACCOUNT,
+            runtime_args! {
+                ARG_TARGET => target,
+                ARG_AMOUNT => U512::from(TRANSFER_AMOUNT)
+            },
+        )
+        .build();
+        builder.exec(fund_request).expect_success().commit();
+    }
+
+    let validator_1_add_bid_request = ExecuteRequestBuilder::standard(
+        *VALIDATOR_1_ADDR,
+        CONTRACT_ADD_BID,
+        runtime_args! {
+            ARG_AMOUNT => U512::from(VALIDATOR_1_STAKE),
+            ARG_DELEGATION_RATE => VALIDATOR_1_DELEGATION_RATE,
+            ARG_PUBLIC_KEY => VALIDATOR_1.clone(),
+        },
+    )
+    .build();
+    builder
+        .exec(validator_1_add_bid_request)
+        .expect_success()
+        .commit();
+
+    let delegator_1_validator_1_delegate_request = ExecuteRequestBuilder::standard(
+        *DELEGATOR_1_ADDR,
+        CONTRACT_DELEGATE,
+        runtime_args! {
+            ARG_AMOUNT => U512::from(DELEGATOR_1_STAKE),
+            ARG_VALIDATOR => VALIDATOR_1.clone(),
+            ARG_DELEGATOR => DELEGATOR_1.clone(),
+        },
+    )
+    .build();
+    builder
+        .exec(delegator_1_validator_1_delegate_request)
+        .expect_success()
+        .commit();
+
+    let reward_destination = RewardDestination::Account(*DELEGATOR_1_ADDR);
+    let set_reward_destination_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *DELEGATOR_1_ADDR,
+        builder.get_auction_contract_hash(),
+        METHOD_SET_REWARD_DESTINATION,
+        runtime_args! {
+            ARG_VALIDATOR => VALIDATOR_1.clone(),
+            ARG_DELEGATOR => Some(DELEGATOR_1.clone()),
+            ARG_REWARD_DESTINATION => reward_destination,
+        },
+    )
+    .build();
+    builder
+        .exec(set_reward_destination_request)
+        .expect_success()
+        .commit();
+
+    let validator_1_bid = get_validator_bid(&mut builder, VALIDATOR_1.clone()).unwrap();
+    assert_eq!(
+        validator_1_bid.delegator_reward_destination(&DELEGATOR_1),
+        reward_destination
+    );
+
+    builder.advance_eras_by_default_auction_delay(vec![], vec![]);
+
+    let delegator_1_main_purse = builder.get_expected_account(*DELEGATOR_1_ADDR).main_purse();
+    let delegator_1_balance_before = builder.get_purse_balance(delegator_1_main_purse);
+
+    builder.advance_era(
+        vec![RewardItem::new(VALIDATOR_1.clone(), BLOCK_REWARD)],
+        vec![],
+    );
+
+    let era_info = {
+        let era_info_value = builder
+            .query(None, Key::EraSummary, &[])
+            .expect("should have value");
+
+        era_info_value
+            .as_era_info()
+            .cloned()
+            .expect("should be era info")
+    };
+
+    let delegator_1_payout = match era_info.select(DELEGATOR_1.clone()).next() {
+        Some(SeigniorageAllocation::Delegator {
+            amount,
+            reward_destination: allocation_destination,
+            ..
+        }) if *allocation_destination == reward_destination => *amount,
+        allocation => panic!("unexpected allocation {:?}", allocation),
+    };
+    assert!(!delegator_1_payout.is_zero());
+
+    assert!(matches!(
+        era_info.select(VALIDATOR_1.clone()).next(),
+        Some(SeigniorageAllocation::Validator { reward_destination, .. })
+        if reward_destination.is_compound()
+    ));
+
+    // The delegator's rewards are paid to its main purse, not added to its stake.
+    assert_eq!(
+        get_delegator_staked_amount(&mut builder, VALIDATOR_1.clone(), DELEGATOR_1.clone()),
+        U512::from(DELEGATOR_1_STAKE)
+    );
+    assert_eq!(
+        builder.get_purse_balance(delegator_1_main_purse),
+        delegator_1_balance_before + delegator_1_payout
+    );
+}
@@ -0,0 +1,253 @@
+use assert_matches::assert_matches;
+
+use casper_engine_test_support::{
+    utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    DEFAULT_PAYMENT, MINIMUM_ACCOUNT_CREATION_BALANCE, PRODUCTION_RUN_GENESIS_REQUEST,
+};
+use casper_execution_engine::core::{
+    engine_state::{Error, ExecuteRequest},
+    execution,
+};
+use casper_types::{
+    account::AccountHash, runtime_args, system::handle_payment, ContractHash, Key, RuntimeArgs,
+    U512,
+};
+
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const CONTRACT_SPONSOR_PAYMENT: &str = "sponsor_payment.wasm";
+const CONTRACT_SPONSOR_PAYMENT_SPOOF: &str = "sponsor_payment_spoof.wasm";
+const DO_NOTHING_WASM: &str = "do_nothing.wasm";
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1u8; 32]);
+const SPONSOR_PAYMENT_HASH_KEY: &str = "sponsor_payment_hash";
+const SPONSOR_PURSE_KEY: &str = "sponsor_purse";
+const ARG_AMOUNT: &str = "amount";
+const ARG_CONTRACT_HASH: &str = "contract_hash";
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let fund_account_1_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+        runtime_args! {
+            "target" => ACCOUNT_1_ADDR,
+            "amount" => U512::from(MINIMUM_ACCOUNT_CREATION_BALANCE),
+        },
+    )
+    .build();
+    builder
+        .exec(fund_account_1_request)
+        .expect_success()
+        .commit();
+
+    builder
+}
+
+fn sponsored_request(
+    account_hash: AccountHash,
+    sponsor: Key,
+    authorization_keys: &[AccountHash],
+) -> ExecuteRequest {
+    let deploy = DeployItemBuilder::new()
+        .with_address(account_hash)
+        .with_deploy_hash([42; 32])
+        .with_session_code(DO_NOTHING_WASM, RuntimeArgs::default())
+        .with_empty_payment_bytes(runtime_args! {
+            ARG_AMOUNT => *DEFAULT_PAYMENT,
+            handle_payment::ARG_SPONSOR => sponsor,
+        })
+        .with_authorization_keys(authorization_keys)
+        .build();
+
+    ExecuteRequestBuilder::new().push_deploy(deploy).build()
+}
+
+fn install_sponsor_payment(builder: &mut InMemoryWasmTestBuilder) -> ContractHash {
+    let install_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_SPONSOR_PAYMENT,
+        runtime_args! {
+            ARG_AMOUNT => *DEFAULT_PAYMENT * 2,
+        },
+    )
+    .build();
+    builder.exec(install_request).expect_success().commit();
+
+    builder
+        .get_expected_account(*DEFAULT_ACCOUNT_ADDR)
+        .named_keys()
+        .get(SPONSOR_PAYMENT_HASH_KEY)
+        .and_then(|key| key.into_hash())
+        .map(ContractHash::new)
+        .expect("should have contract hash")
+}
+
+#[ignore]
+#[test]
+fn should_charge_sponsoring_account_for_payment() {
+    let mut builder = setup();
+
+    let account_purse = builder
+        .get_expected_account(*DEFAULT_ACCOUNT_ADDR)
+        .main_purse();
+    let sponsor_purse = builder.get_expected_account(ACCOUNT_1_ADDR).main_purse();
+    let account_balance_before = builder.get_purse_balance(account_purse);
+    let sponsor_balance_before = builder.get_purse_balance(sponsor_purse);
+
+    let sponsor = Key::Account(ACCOUNT_1_ADDR);
+    let exec_request = sponsored_request(
+        *DEFAULT_ACCOUNT_ADDR,
+        sponsor,
+        &[*DEFAULT_ACCOUNT_ADDR, ACCOUNT_1_ADDR],
+    );
+    builder.exec(exec_request).expect_success().commit();
+
+    let exec_result = builder
+        .get_last_exec_results()
+        .expect("should have exec results")
+        .pop()
+        .expect("should have exec result");
+    assert_eq!(exec_result.sponsor(), Some(&sponsor));
+
+    assert_eq!(
+        builder.get_purse_balance(account_purse),
+        account_balance_before
+    );
+    assert!(builder.get_purse_balance(sponsor_purse) < sponsor_balance_before);
+}
+
+#[ignore]
+#[test]
+fn should_not_charge_sponsoring_account_without_its_signature() {
+    let mut builder = setup();
+
+    let exec_request = sponsored_request(
+        *DEFAULT_ACCOUNT_ADDR,
+        Key::Account(ACCOUNT_1_ADDR),
+        &[*DEFAULT_ACCOUNT_ADDR],
+    );
+    builder.exec(exec_request).commit();
+
+    let response = builder
+        .get_last_exec_results()
+        .expect("there should be a response");
+
+    let precondition_failure = utils::get_precondition_failure(&response);
+    assert_matches!(precondition_failure, Error::Authorization);
+}
+
+#[ignore]
+#[test]
+fn should_charge_sponsoring_contract_for_payment() {
+    let mut builder = setup();
+
+    let contract_hash = install_sponsor_payment(&mut builder);
+    let sponsor_purse = builder
+        .get_contract(contract_hash)
+        .expect("should have contract")
+        .named_keys()
+        .get(SPONSOR_PURSE_KEY)
+        .and_then(Key::as_uref)
+        .copied()
+        .expect("should have sponsor purse");
+
+    let account_purse = builder.get_expected_account(ACCOUNT_1_ADDR).main_purse();
+    let account_balance_before = builder.get_purse_balance(account_purse);
+    let sponsor_balance_before = builder.get_purse_balance(sponsor_purse);
+
+    let sponsor = Key::from(contract_hash);
+    let exec_request = sponsored_request(ACCOUNT_1_ADDR, sponsor, &[ACCOUNT_1_ADDR]);
+    builder.exec(exec_request).expect_success().commit();
+
+    let exec_result = builder
+        .get_last_exec_results()
+        .expect("should have exec results")
+        .pop()
+        .expect("should have exec result");
+    assert_eq!(exec_result.sponsor(), Some(&sponsor));
+
+    assert_eq!(
+        builder.get_purse_balance(account_purse),
+        account_balance_before
+    );
+    assert!(builder.get_purse_balance(sponsor_purse) < sponsor_balance_before);
+}
+
+#[ignore]
+#[test]
+fn should_not_let_deploy_name_the_account_a_sponsoring_contract_pays_for() {
+    let mut builder = setup();
+
+    let contract_hash = install_sponsor_payment(&mut builder);
+
+    let deploy = DeployItemBuilder::new()
+        .with_address(ACCOUNT_1_ADDR)
+        .with_deploy_hash([42; 32])
+        .with_session_code(DO_NOTHING_WASM, RuntimeArgs::default())
+        .with_empty_payment_bytes(runtime_args! {
+            ARG_AMOUNT => *DEFAULT_PAYMENT,
+            handle_payment::ARG_SPONSOR => Key::from(contract_hash),
+            handle_payment::ARG_ACCOUNT => *DEFAULT_ACCOUNT_ADDR,
+        })
+        .with_authorization_keys(&[ACCOUNT_1_ADDR])
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+    builder.exec(exec_request).commit();
+
+    let response = builder
+        .get_last_exec_results()
+        .expect("there should be a response");
+
+    let precondition_failure = utils::get_precondition_failure(&response);
+    assert_matches!(
+        precondition_failure,
+        Error::ReservedArgument(argument) if argument == handle_payment::ARG_ACCOUNT
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_let_custom_payment_call_a_sponsoring_contract() {
+    let mut builder = setup();
+
+    let contract_hash = install_sponsor_payment(&mut builder);
+    let sponsor_purse = builder
+        .get_contract(contract_hash)
+        .expect("should have contract")
+        .named_keys()
+        .get(SPONSOR_PURSE_KEY)
+        .and_then(Key::as_uref)
+        .copied()
+        .expect("should have sponsor purse");
+    let sponsor_balance_before = builder.get_purse_balance(sponsor_purse);
+
+    // The payment code claims the deploy is from the account the sponsor was installed by.
+    let deploy = DeployItemBuilder::new()
+        .with_address(ACCOUNT_1_ADDR)
+        .with_deploy_hash([42; 32])
+        .with_session_code(DO_NOTHING_WASM, RuntimeArgs::default())
+        .with_payment_code(
+            CONTRACT_SPONSOR_PAYMENT_SPOOF,
+            runtime_args! {
+                ARG_AMOUNT => *DEFAULT_PAYMENT,
+                ARG_CONTRACT_HASH => contract_hash,
+                handle_payment::ARG_ACCOUNT => *DEFAULT_ACCOUNT_ADDR,
+            },
+        )
+        .with_authorization_keys(&[ACCOUNT_1_ADDR])
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+    builder.exec(exec_request).commit();
+
+    let error = builder.get_error().expect("should have error");
+    assert_matches!(
+        error,
+        Error::Exec(execution::Error::Revert(api_error))
+            if api_error == handle_payment::Error::SponsorPaymentCalledByNonSponsor.into()
+    );
+    assert_eq!(
+        builder.get_purse_balance(sponsor_purse),
+        sponsor_balance_before
+    );
+}
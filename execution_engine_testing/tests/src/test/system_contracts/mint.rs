@@ -0,0 +1,426 @@
+use casper_engine_test_support::{
+    ExecuteRequestBuilder, InMemoryWasmTestBuilder, StepRequestBuilder, DEFAULT_ACCOUNT_ADDR,
+    DEFAULT_PROTOCOL_VERSION, MINIMUM_ACCOUNT_CREATION_BALANCE, PRODUCTION_RUN_GENESIS_REQUEST,
+};
+use casper_execution_engine::{
+    core::engine_state::{ExecuteRequest, StepSuccess},
+    shared::transform::Transform,
+};
+use casper_types::{
+    account::AccountHash,
+    runtime_args,
+    system::mint::{self, TransferSchedule, MAX_SCHEDULED_TRANSFERS_PER_OWNER},
+    EraId, Key, RuntimeArgs, StoredValue, Transfer, URef, U512,
+};
+
+const TRANSFER_TO_NAMED_PURSE_CONTRACT: &str = "transfer_to_named_purse.wasm";
+
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1u8; 32]);
+const UNKNOWN_ADDR: AccountHash = AccountHash::new([42u8; 32]);
+const ARG_PURSE_NAME: &str = "purse_name";
+const ARG_AMOUNT: &str = "amount";
+const SOURCE_PURSE: &str = "source_purse";
+const SOURCE_PURSE_BALANCE: u64 = 1_000_000_000;
+const ALLOWANCE_AMOUNT: u64 = 100;
+const ALLOWANCE_EXPIRES_AT: u64 = 10_000;
+const SCHEDULED_AMOUNT: u64 = 1_000;
+const SCHEDULED_RUNS: u32 = 2;
+
+fn setup() -> (InMemoryWasmTestBuilder, URef, URef) {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let fund_account_1_request = ExecuteRequestBuilder::transfer(
+        *DEFAULT_ACCOUNT_ADDR,
+        runtime_args! {
+            mint::ARG_TARGET => ACCOUNT_1_ADDR,
+            mint::ARG_AMOUNT => U512::from(MINIMUM_ACCOUNT_CREATION_BALANCE),
+            mint::ARG_ID => <Option<u64>>::None,
+        },
+    )
+    .build();
+    let fund_source_purse_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        TRANSFER_TO_NAMED_PURSE_CONTRACT,
+        runtime_args! {
+            ARG_PURSE_NAME => SOURCE_PURSE,
+            ARG_AMOUNT => U512::from(SOURCE_PURSE_BALANCE),
+        },
+    )
+    .build();
+    builder
+        .exec(fund_account_1_request)
+        .expect_success()
+        .commit();
+    builder
+        .exec(fund_source_purse_request)
+        .expect_success()
+        .commit();
+
+    let source_purse = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(SOURCE_PURSE)
+        .and_then(Key::as_uref)
+        .copied()
+        .expect("should have source purse");
+    let account_1_purse = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should have account")
+        .main_purse();
+
+    let mint_hash = builder.get_mint_contract_hash();
+    let approve_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        mint_hash,
+        mint::METHOD_APPROVE_ALLOWANCE,
+        runtime_args! {
+            mint::ARG_PURSE => source_purse,
+            mint::ARG_SPENDER => Key::Account(ACCOUNT_1_ADDR),
+            mint::ARG_AMOUNT => U512::from(ALLOWANCE_AMOUNT),
+            mint::ARG_EXPIRES_AT => Some(ALLOWANCE_EXPIRES_AT),
+        },
+    )
+    .build();
+    builder.exec(approve_request).expect_success().commit();
+
+    (builder, source_purse, account_1_purse)
+}
+
+fn transfer_from_allowance(
+    builder: &mut InMemoryWasmTestBuilder,
+    source_purse: URef,
+    target_purse: URef,
+    amount: u64,
+    block_time: u64,
+) {
+    let mint_hash = builder.get_mint_contract_hash();
+    let transfer_request = ExecuteRequestBuilder::contract_call_by_hash(
+        ACCOUNT_1_ADDR,
+        mint_hash,
+        mint::METHOD_TRANSFER_FROM_ALLOWANCE,
+        runtime_args! {
+            mint::ARG_SOURCE => source_purse.addr(),
+            mint::ARG_TARGET => target_purse,
+            mint::ARG_AMOUNT => U512::from(amount),
+            mint::ARG_ID => <Option<u64>>::None,
+        },
+    )
+    .with_block_time(block_time)
+    .build();
+    // A failed transfer only shows up in the discarded `mint::Error` result, so callers check the
+    // balance of the source purse instead.
+    builder.exec(transfer_request).expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_transfer_from_allowance_up_to_approved_amount() {
+    let (mut builder, source_purse, account_1_purse) = setup();
+
+    transfer_from_allowance(&mut builder, source_purse, account_1_purse, 60, 0);
+    assert_eq!(
+        builder.get_purse_balance(source_purse),
+        U512::from(SOURCE_PURSE_BALANCE - 60)
+    );
+
+    // only 40 motes of the allowance remain
+    transfer_from_allowance(&mut builder, source_purse, account_1_purse, 60, 0);
+    assert_eq!(
+        builder.get_purse_balance(source_purse),
+        U512::from(SOURCE_PURSE_BALANCE - 60)
+    );
+
+    transfer_from_allowance(&mut builder, source_purse, account_1_purse, 40, 0);
+    assert_eq!(
+        builder.get_purse_balance(source_purse),
+        U512::from(SOURCE_PURSE_BALANCE - ALLOWANCE_AMOUNT)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_transfer_from_expired_allowance() {
+    let (mut builder, source_purse, account_1_purse) = setup();
+
+    transfer_from_allowance(
+        &mut builder,
+        source_purse,
+        account_1_purse,
+        ALLOWANCE_AMOUNT,
+        ALLOWANCE_EXPIRES_AT,
+    );
+    assert_eq!(
+        builder.get_purse_balance(source_purse),
+        U512::from(SOURCE_PURSE_BALANCE)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_transfer_from_allowance_granted_to_another_spender() {
+    let (mut builder, source_purse, _account_1_purse) = setup();
+
+    let default_account_purse = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .main_purse();
+    let mint_hash = builder.get_mint_contract_hash();
+    let transfer_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        mint_hash,
+        mint::METHOD_TRANSFER_FROM_ALLOWANCE,
+        runtime_args! {
+            mint::ARG_SOURCE => source_purse.addr(),
+            mint::ARG_TARGET => default_account_purse,
+            mint::ARG_AMOUNT => U512::one(),
+            mint::ARG_ID => <Option<u64>>::None,
+        },
+    )
+    .build();
+    builder.exec(transfer_request).expect_success().commit();
+
+    assert_eq!(
+        builder.get_purse_balance(source_purse),
+        U512::from(SOURCE_PURSE_BALANCE)
+    );
+}
+
+fn schedule_transfer_request(
+    builder: &InMemoryWasmTestBuilder,
+    source_purse: URef,
+    target: AccountHash,
+    schedule: TransferSchedule,
+    runs: u32,
+) -> ExecuteRequest {
+    let mint_hash = builder.get_mint_contract_hash();
+    ExecuteRequestBuilder::contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        mint_hash,
+        mint::METHOD_SCHEDULE_TRANSFER,
+        runtime_args! {
+            mint::ARG_SOURCE => source_purse,
+            mint::ARG_TARGET => target,
+            mint::ARG_AMOUNT => U512::from(SCHEDULED_AMOUNT),
+            mint::ARG_SCHEDULE => schedule,
+            mint::ARG_RUNS => runs,
+            mint::ARG_ID => <Option<u64>>::None,
+        },
+    )
+    .build()
+}
+
+fn schedule_transfer(builder: &mut InMemoryWasmTestBuilder, source_purse: URef) {
+    let schedule = TransferSchedule::Era {
+        era_id: EraId::new(1),
+        interval_eras: 1,
+    };
+    schedule_transfer_along(
+        builder,
+        source_purse,
+        ACCOUNT_1_ADDR,
+        schedule,
+        SCHEDULED_RUNS,
+    );
+}
+
+fn schedule_transfer_along(
+    builder: &mut InMemoryWasmTestBuilder,
+    source_purse: URef,
+    target: AccountHash,
+    schedule: TransferSchedule,
+    runs: u32,
+) {
+    let schedule_request = schedule_transfer_request(builder, source_purse, target, schedule, runs);
+    builder.exec(schedule_request).expect_success().commit();
+}
+
+fn cancel_scheduled_transfer(builder: &mut InMemoryWasmTestBuilder, schedule_id: u64) {
+    let mint_hash = builder.get_mint_contract_hash();
+    let cancel_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        mint_hash,
+        mint::METHOD_CANCEL_SCHEDULED_TRANSFER,
+        runtime_args! {
+            mint::ARG_SCHEDULE_ID => schedule_id,
+        },
+    )
+    .build();
+    builder.exec(cancel_request).expect_success().commit();
+}
+
+fn step(builder: &mut InMemoryWasmTestBuilder) -> StepSuccess {
+    step_at(builder, 0)
+}
+
+fn step_at(builder: &mut InMemoryWasmTestBuilder, era_end_timestamp_millis: u64) -> StepSuccess {
+    let step_request = StepRequestBuilder::new()
+        .with_parent_state_hash(builder.get_post_state_hash())
+        .with_protocol_version(*DEFAULT_PROTOCOL_VERSION)
+        .with_next_era_id(builder.get_era().successor())
+        .with_era_end_timestamp_millis(era_end_timestamp_millis)
+        .with_run_auction(true)
+        .build();
+    builder.step(step_request).expect("should step")
+}
+
+fn recorded_transfers(step_success: &StepSuccess) -> Vec<Transfer> {
+    step_success
+        .execution_journal
+        .iter()
+        .filter_map(|(_, transform)| match transform {
+            Transform::Write(StoredValue::Transfer(transfer)) => Some(*transfer),
+            _ => None,
+        })
+        .collect()
+}
+
+#[ignore]
+#[test]
+fn should_run_scheduled_transfers_in_step() {
+    let (mut builder, source_purse, account_1_purse) = setup();
+    let account_1_balance = builder.get_purse_balance(account_1_purse);
+
+    schedule_transfer(&mut builder, source_purse);
+    let escrowed = SCHEDULED_AMOUNT * u64::from(SCHEDULED_RUNS);
+    assert_eq!(
+        builder.get_purse_balance(source_purse),
+        U512::from(SOURCE_PURSE_BALANCE - escrowed)
+    );
+
+    for run in 1..=u64::from(SCHEDULED_RUNS) {
+        let step_success = step(&mut builder);
+        let results = step_success.scheduled_transfers.clone();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].target, ACCOUNT_1_ADDR);
+        assert_eq!(results[0].amount, U512::from(SCHEDULED_AMOUNT));
+        assert!(results[0].error.is_none());
+        assert_eq!(
+            builder.get_purse_balance(account_1_purse),
+            account_1_balance + U512::from(SCHEDULED_AMOUNT * run)
+        );
+
+        // each payout is recorded as a transfer from the escrow purse
+        let transfers = recorded_transfers(&step_success);
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].to, Some(ACCOUNT_1_ADDR));
+        assert_eq!(transfers[0].target, account_1_purse);
+        assert_eq!(transfers[0].amount, U512::from(SCHEDULED_AMOUNT));
+    }
+
+    // all runs are done, so the scheduled transfer is gone
+    assert!(step(&mut builder).scheduled_transfers.is_empty());
+}
+
+#[ignore]
+#[test]
+fn should_pay_every_run_due_since_previous_step() {
+    let (mut builder, source_purse, account_1_purse) = setup();
+    let account_1_balance = builder.get_purse_balance(account_1_purse);
+
+    // runs every second, far more often than eras end
+    let schedule = TransferSchedule::BlockTime {
+        timestamp_millis: 0,
+        interval_millis: 1_000,
+    };
+    schedule_transfer_along(&mut builder, source_purse, ACCOUNT_1_ADDR, schedule, 5);
+
+    // the runs due at 0, 1 and 2 seconds are paid at once
+    let results = step_at(&mut builder, 2_500).scheduled_transfers;
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].amount, U512::from(SCHEDULED_AMOUNT * 3));
+    assert!(results[0].error.is_none());
+
+    // only the two runs left are paid, however many more fell due
+    let results = step_at(&mut builder, 60_000).scheduled_transfers;
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].amount, U512::from(SCHEDULED_AMOUNT * 2));
+    assert_eq!(
+        builder.get_purse_balance(account_1_purse),
+        account_1_balance + U512::from(SCHEDULED_AMOUNT * 5)
+    );
+
+    assert!(step_at(&mut builder, 120_000)
+        .scheduled_transfers
+        .is_empty());
+}
+
+#[ignore]
+#[test]
+fn should_record_failed_scheduled_transfer_and_run_others() {
+    let (mut builder, source_purse, account_1_purse) = setup();
+    let account_1_balance = builder.get_purse_balance(account_1_purse);
+
+    let schedule = TransferSchedule::Era {
+        era_id: EraId::new(1),
+        interval_eras: 0,
+    };
+    schedule_transfer_along(&mut builder, source_purse, UNKNOWN_ADDR, schedule, 1);
+    schedule_transfer_along(&mut builder, source_purse, ACCOUNT_1_ADDR, schedule, 1);
+
+    let results = step(&mut builder).scheduled_transfers;
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].target, UNKNOWN_ADDR);
+    assert!(results[0].error.is_some());
+    assert_eq!(results[1].target, ACCOUNT_1_ADDR);
+    assert!(results[1].error.is_none());
+    assert_eq!(
+        builder.get_purse_balance(account_1_purse),
+        account_1_balance + U512::from(SCHEDULED_AMOUNT)
+    );
+
+    assert!(step(&mut builder).scheduled_transfers.is_empty());
+}
+
+#[ignore]
+#[test]
+fn should_refund_escrow_when_scheduled_transfer_is_cancelled() {
+    let (mut builder, source_purse, account_1_purse) = setup();
+    let account_1_balance = builder.get_purse_balance(account_1_purse);
+
+    schedule_transfer(&mut builder, source_purse);
+    cancel_scheduled_transfer(&mut builder, 0);
+
+    // the escrow is refunded to the owner's main purse, not to the source purse
+    let escrowed = SCHEDULED_AMOUNT * u64::from(SCHEDULED_RUNS);
+    assert_eq!(
+        builder.get_purse_balance(source_purse),
+        U512::from(SOURCE_PURSE_BALANCE - escrowed)
+    );
+
+    assert!(step(&mut builder).scheduled_transfers.is_empty());
+    assert_eq!(
+        builder.get_purse_balance(account_1_purse),
+        account_1_balance
+    );
+}
+
+#[ignore]
+#[test]
+fn should_limit_pending_scheduled_transfers_per_owner() {
+    let (mut builder, source_purse, _account_1_purse) = setup();
+    let escrowed = SCHEDULED_AMOUNT * u64::from(SCHEDULED_RUNS);
+
+    for _ in 0..MAX_SCHEDULED_TRANSFERS_PER_OWNER {
+        schedule_transfer(&mut builder, source_purse);
+    }
+    let source_balance = builder.get_purse_balance(source_purse);
+    assert_eq!(
+        source_balance,
+        U512::from(SOURCE_PURSE_BALANCE - escrowed * u64::from(MAX_SCHEDULED_TRANSFERS_PER_OWNER))
+    );
+
+    // A rejected schedule only shows up in the discarded `mint::Error` result, so nothing is
+    // escrowed.
+    schedule_transfer(&mut builder, source_purse);
+    assert_eq!(builder.get_purse_balance(source_purse), source_balance);
+
+    // cancelling a scheduled transfer frees a slot
+    cancel_scheduled_transfer(&mut builder, 0);
+    schedule_transfer(&mut builder, source_purse);
+    assert_eq!(
+        builder.get_purse_balance(source_purse),
+        source_balance - U512::from(escrowed)
+    );
+}
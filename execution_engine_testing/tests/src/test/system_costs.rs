@@ -979,6 +979,11 @@ fn should_verify_wasm_add_bid_wasm_cost_is_not_recursive() {
         blake2b: HostFunction::fixed(0),
         random_bytes: HostFunction::fixed(0),
         enable_contract_version: HostFunction::fixed(0),
+        emit_event: HostFunction::fixed(0),
+        verify_signature: HostFunction::fixed(0),
+        sha256: HostFunction::fixed(0),
+        keccak256: HostFunction::fixed(0),
+        approve_allowance: HostFunction::fixed(0),
     };
 
     let new_wasm_config = WasmConfig::new(
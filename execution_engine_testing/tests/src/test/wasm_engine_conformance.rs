@@ -0,0 +1,117 @@
+use casper_engine_test_support::{
+    wasm_engine_conformance::{self, DeployOutcome},
+    DeployItemBuilder, ARG_AMOUNT, DEFAULT_ACCOUNT_ADDR, DEFAULT_PAYMENT,
+};
+use casper_execution_engine::core::{
+    engine_state::deploy_item::DeployItem, wasm_engine::WasmEngineKind,
+};
+use casper_types::{runtime_args, RuntimeArgs};
+
+const ARG_VALUE: &str = "value";
+
+/// Computes a value in a loop, stores it under a new URef and puts that under the named key
+/// "result".
+const COMPUTE_AND_STORE_WAT: &str = r#"(module
+    (import "env" "casper_new_uref" (func $new_uref (param i32 i32 i32)))
+    (import "env" "casper_put_key" (func $put_key (param i32 i32 i32 i32)))
+    (memory $memory 1)
+    (data (i32.const 0) "\06\00\00\00result")
+    (data (i32.const 16) "\04\00\00\00\00\00\00\00\04")
+    (data (i32.const 32) "\02")
+    (func $call (local $i i32) (local $acc i32)
+        (local.set $i (i32.const 1000))
+        (local.set $acc (i32.const 1))
+        (loop $continue
+            (local.set $acc (i32.add (i32.mul (local.get $acc) (i32.const 31)) (local.get $i)))
+            (local.set $i (i32.sub (local.get $i) (i32.const 1)))
+            (br_if $continue (local.get $i)))
+        (i32.store (i32.const 20) (local.get $acc))
+        (call $new_uref (i32.const 33) (i32.const 16) (i32.const 9))
+        (call $put_key (i32.const 0) (i32.const 10) (i32.const 32) (i32.const 34)))
+    (export "call" (func $call)))"#;
+
+/// Reverts with the size of the runtime argument "value".
+const REVERT_WITH_ARG_SIZE_WAT: &str = r#"(module
+    (import "env" "casper_get_named_arg_size"
+        (func $get_named_arg_size (param i32 i32 i32) (result i32)))
+    (import "env" "casper_revert" (func $revert (param i32)))
+    (memory $memory 1)
+    (data (i32.const 0) "value")
+    (func $call
+        (call $revert
+            (i32.add
+                (call $get_named_arg_size (i32.const 0) (i32.const 5) (i32.const 8))
+                (i32.load (i32.const 8)))))
+    (export "call" (func $call)))"#;
+
+const UNREACHABLE_WAT: &str = r#"(module
+    (memory $memory 1)
+    (func $call unreachable)
+    (export "call" (func $call)))"#;
+
+const LOAD_OUT_OF_BOUNDS_WAT: &str = r#"(module
+    (memory $memory 1)
+    (func $call (drop (i32.load (i32.const 65536))))
+    (export "call" (func $call)))"#;
+
+fn deploy_item(session_wat: &str, session_args: RuntimeArgs) -> DeployItem {
+    let module_bytes = wat::parse_str(session_wat).expect("should parse wat");
+    DeployItemBuilder::new()
+        .with_address(*DEFAULT_ACCOUNT_ADDR)
+        .with_session_bytes(module_bytes, session_args)
+        .with_empty_payment_bytes(runtime_args! {
+            ARG_AMOUNT => *DEFAULT_PAYMENT
+        })
+        .with_authorization_keys(&[*DEFAULT_ACCOUNT_ADDR])
+        .build()
+}
+
+/// Writes to global state after a loop, reverts after a host call returning a value, and traps.
+fn deploy_items() -> Vec<DeployItem> {
+    vec![
+        deploy_item(COMPUTE_AND_STORE_WAT, RuntimeArgs::new()),
+        deploy_item(
+            REVERT_WITH_ARG_SIZE_WAT,
+            runtime_args! {
+                ARG_VALUE => 7u64,
+            },
+        ),
+        deploy_item(UNREACHABLE_WAT, RuntimeArgs::new()),
+        deploy_item(LOAD_OUT_OF_BOUNDS_WAT, RuntimeArgs::new()),
+    ]
+}
+
+#[test]
+fn should_execute_identically_on_all_wasm_engines() {
+    let outcomes = wasm_engine_conformance::assert_wasm_engines_conform(&deploy_items());
+
+    assert!(
+        matches!(
+            outcomes.as_slice(),
+            [
+                DeployOutcome { error: None, .. },
+                DeployOutcome { error: Some(reverted), .. },
+                DeployOutcome { error: Some(unreachable), .. },
+                DeployOutcome { error: Some(out_of_bounds), .. },
+            ] if reverted.contains("Revert")
+                && unreachable.contains("Unreachable")
+                && out_of_bounds.contains("MemoryAccessOutOfBounds")
+        ),
+        "{:?}",
+        outcomes
+    );
+    assert!(!outcomes[0].trace.events.is_empty());
+}
+
+#[test]
+fn should_execute_deterministically_on_each_wasm_engine() {
+    let deploy_items = deploy_items();
+    for wasm_engine in WasmEngineKind::ALL {
+        assert_eq!(
+            wasm_engine_conformance::run_on_wasm_engine(wasm_engine, &deploy_items),
+            wasm_engine_conformance::run_on_wasm_engine(wasm_engine, &deploy_items),
+            "{:?}",
+            wasm_engine
+        );
+    }
+}
@@ -3,6 +3,14 @@
 //! The block validator checks whether all the deploys included in the block payload exist, either
 //! locally or on the network.
 //!
+//! Once all deploys are known, it also checks their dependencies: each dependency must either be
+//! executed earlier in the same block, or be included in one of the block's ancestors or in a block
+//! held in storage.
+//!
+//! Of all deploys from the same account with the same timestamp, i.e. in the same slot, only one
+//! can ever be executed: a block must not contain two deploys in the same slot, nor a deploy whose
+//! slot is taken by a deploy in one of the block's ancestors or in a block held in storage.
+//!
 //! When multiple requests are made to validate the same block payload, they will eagerly return
 //! true if valid, but only fail if all sources have been exhausted. This is only relevant when
 //! calling for validation of the same proposed block multiple times at the same time.
@@ -13,12 +21,15 @@ mod state;
 #[cfg(test)]
 mod tests;
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    sync::Arc,
+};
 
 use datasize::DataSize;
 use tracing::{debug, error, warn};
 
-use casper_types::Timestamp;
+use casper_types::{PublicKey, Timestamp};
 
 use crate::{
     components::{
@@ -31,8 +42,8 @@ use crate::{
         EffectBuilder, EffectExt, Effects, Responder,
     },
     types::{
-        ApprovalsHash, Chainspec, Deploy, DeployHashWithApprovals, DeployId, DeployOrTransferHash,
-        NodeId,
+        ApprovalsHash, Chainspec, Deploy, DeployHash, DeployHashWithApprovals, DeployId,
+        DeployOrTransferHash, NodeId,
     },
     NodeRng,
 };
@@ -54,6 +65,25 @@ impl ProposedBlock<ClContext> {
     fn transfers(&self) -> &Vec<DeployHashWithApprovals> {
         self.value().transfers()
     }
+
+    /// Returns those of the given deploys which are not included in any of the block's ancestors.
+    fn not_in_ancestors(&self, mut deploy_hashes: HashSet<DeployHash>) -> HashSet<DeployHash> {
+        for ancestor in self.context().ancestor_values() {
+            for deploy_hash in ancestor.deploy_and_transfer_hashes() {
+                deploy_hashes.remove(deploy_hash);
+            }
+        }
+        deploy_hashes
+    }
+
+    /// Returns the deploys and transfers included in any of the block's ancestors.
+    fn ancestor_deploys(&self) -> BTreeSet<DeployHash> {
+        self.context()
+            .ancestor_values()
+            .iter()
+            .flat_map(|ancestor| ancestor.deploy_and_transfer_hashes().copied())
+            .collect()
+    }
 }
 
 /// The return type of trying to handle a validation request as an already-existing request.
@@ -219,7 +249,7 @@ impl BlockValidator {
         result: FetchResult<Deploy>,
     ) -> Effects<Event>
     where
-        REv: From<Event> + From<FetcherRequest<Deploy>> + Send,
+        REv: From<Event> + From<FetcherRequest<Deploy>> + From<StorageRequest> + Send,
     {
         match &result {
             Ok(FetchedData::FromPeer { peer, .. }) => {
@@ -263,12 +293,20 @@ impl BlockValidator {
                 };
 
                 let mut effects = Effects::new();
-                for state in self.validation_states.values_mut() {
+                for (block, state) in self.validation_states.iter_mut() {
                     let responders = state.try_add_deploy_footprint(&dt_hash, &deploy_footprint);
                     if !responders.is_empty() {
                         let is_valid = matches!(state, BlockValidationState::Valid(_));
                         effects.extend(respond(is_valid, responders));
                     }
+                    if let Some((dependencies, slots)) = state.take_executed_deploys_check() {
+                        effects.extend(check_executed_deploys(
+                            effect_builder,
+                            block,
+                            dependencies,
+                            slots,
+                        ));
+                    }
                 }
                 effects
             }
@@ -355,6 +393,14 @@ where
             Event::DeployFetched { dt_hash, result } => {
                 self.handle_deploy_fetched(effect_builder, dt_hash, result)
             }
+            Event::ExecutedDeploysChecked { block, is_valid } => {
+                match self.validation_states.get_mut(&block) {
+                    Some(state) => {
+                        respond(is_valid, state.resolve_executed_deploys_check(is_valid))
+                    }
+                    None => Effects::new(),
+                }
+            }
         }
     }
 
@@ -382,6 +428,53 @@ where
         .collect()
 }
 
+/// Checks a block against the deploys included in its ancestors and in the blocks held in storage.
+///
+/// The dependencies which are not part of the block must be included in one of them, and none of
+/// them may take the slot of one of the block's deploys.  The block's own deploys found there are
+/// replays, which are checked by consensus instead.
+fn check_executed_deploys<REv>(
+    effect_builder: EffectBuilder<REv>,
+    block: &ProposedBlock<ClContext>,
+    dependencies: HashSet<DeployHash>,
+    slots: HashMap<(PublicKey, Timestamp), DeployHash>,
+) -> Effects<Event>
+where
+    REv: From<StorageRequest> + Send,
+{
+    let unresolved = block.not_in_ancestors(dependencies);
+    let ancestor_deploys = block.ancestor_deploys();
+    let block = block.clone();
+    async move {
+        if !unresolved.is_empty() {
+            debug!(
+                unresolved_len = unresolved.len(),
+                "checking storage for executed dependencies"
+            );
+            if effect_builder
+                .get_executed_deploys(unresolved.clone())
+                .await
+                != unresolved
+            {
+                warn!("deploy depends on a deploy which was not executed - block invalid");
+                return false;
+            }
+        }
+        let in_block: HashSet<DeployHash> = slots.values().copied().collect();
+        let in_slots = effect_builder
+            .get_deploys_in_slots(slots.into_keys().collect(), ancestor_deploys)
+            .await;
+        match in_slots.difference(&in_block).next() {
+            Some(earlier) => {
+                warn!(%earlier, "deploy takes the slot of an earlier deploy - block invalid");
+                false
+            }
+            None => true,
+        }
+    }
+    .event(move |is_valid| Event::ExecutedDeploysChecked { block, is_valid })
+}
+
 fn respond(
     is_valid: bool,
     responders: impl IntoIterator<Item = Responder<bool>>,
@@ -1,7 +1,10 @@
 use derive_more::{Display, From};
 
 use crate::{
-    components::fetcher::FetchResult,
+    components::{
+        consensus::{ClContext, ProposedBlock},
+        fetcher::FetchResult,
+    },
     effect::requests::BlockValidationRequest,
     types::{Deploy, DeployOrTransferHash},
 };
@@ -16,4 +19,10 @@ pub(crate) enum Event {
         dt_hash: DeployOrTransferHash,
         result: FetchResult<Deploy>,
     },
+
+    #[display(fmt = "{} checked against executed deploys: {}", block, is_valid)]
+    ExecutedDeploysChecked {
+        block: ProposedBlock<ClContext>,
+        is_valid: bool,
+    },
 }
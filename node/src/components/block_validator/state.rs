@@ -1,5 +1,5 @@
 use std::{
-    collections::{hash_map::Entry, BTreeSet, HashMap},
+    collections::{hash_map::Entry, BTreeSet, HashMap, HashSet},
     fmt::{self, Debug, Display, Formatter},
     iter, mem,
 };
@@ -7,16 +7,14 @@ use std::{
 use datasize::DataSize;
 use tracing::{debug, error, warn};
 
-use casper_types::Timestamp;
+use casper_types::{PublicKey, Timestamp};
 
-#[cfg(test)]
-use crate::types::DeployHash;
 use crate::{
     components::consensus::{ClContext, ProposedBlock},
     effect::Responder,
     types::{
         appendable_block::AppendableBlock, Approval, ApprovalsHash, Chainspec, DeployFootprint,
-        DeployHashWithApprovals, DeployOrTransferHash, NodeId,
+        DeployHash, DeployHashWithApprovals, DeployOrTransferHash, NodeId,
     },
 };
 
@@ -88,11 +86,30 @@ pub(super) enum BlockValidationState {
         /// The set of approvals contains approvals from deploys that would be finalized with the
         /// block.
         missing_deploys: HashMap<DeployOrTransferHash, ApprovalInfo>,
+        /// The deploys and transfers of the block in the order in which they are executed.
+        execution_order: Vec<DeployOrTransferHash>,
+        /// The dependencies of the deploys fetched so far.
+        dependencies: HashMap<DeployOrTransferHash, Vec<DeployHash>>,
         /// The set of peers which each claim to hold all the deploys.
         holders: HashMap<NodeId, HolderState>,
         /// A list of responders that are awaiting an answer.
         responders: Vec<Responder<bool>>,
     },
+    /// All deploys are present and valid, but have yet to be checked against the deploys included
+    /// in earlier blocks: their dependencies which are not part of the block must have been
+    /// executed, and their slots must not have been taken by another deploy.
+    CheckingExecutedDeploys {
+        /// The timestamp of the proposed block.
+        timestamp: Timestamp,
+        /// The dependencies which are not part of the block; emptied once the check has been
+        /// started.
+        dependencies: HashSet<DeployHash>,
+        /// The deploy taking each slot, i.e. each pair of account and deploy timestamp, in the
+        /// block; emptied once the check has been started.
+        slots: HashMap<(PublicKey, Timestamp), DeployHash>,
+        /// A list of responders that are awaiting an answer.
+        responders: Vec<Responder<bool>>,
+    },
     /// The proposed block with the given timestamp is valid.
     Valid(Timestamp),
     /// The proposed block with the given timestamp is invalid.
@@ -135,6 +152,7 @@ impl BlockValidationState {
         let appendable_block = AppendableBlock::new(chainspec.deploy_config, block.timestamp());
 
         let mut missing_deploys = HashMap::new();
+        let mut execution_order = Vec::with_capacity(deploy_count);
         let deploys_iter = block.deploys().iter().map(|dhwa| {
             let dt_hash = DeployOrTransferHash::Deploy(*dhwa.deploy_hash());
             (dt_hash, dhwa.approvals().clone())
@@ -158,11 +176,14 @@ impl BlockValidationState {
                 let state = BlockValidationState::Invalid(block.timestamp());
                 return (state, Some(responder));
             }
+            execution_order.push(dt_hash);
         }
 
         let state = BlockValidationState::InProgress {
             appendable_block,
             missing_deploys,
+            execution_order,
+            dependencies: HashMap::new(),
             holders: iter::once((sender, HolderState::Unasked)).collect(),
             responders: vec![responder],
         };
@@ -177,7 +198,8 @@ impl BlockValidationState {
     /// the value which should be provided to the responder.
     pub(super) fn add_responder(&mut self, responder: Responder<bool>) -> AddResponderResult {
         match self {
-            BlockValidationState::InProgress { responders, .. } => {
+            BlockValidationState::InProgress { responders, .. }
+            | BlockValidationState::CheckingExecutedDeploys { responders, .. } => {
                 responders.push(responder);
                 AddResponderResult::Added
             }
@@ -212,6 +234,12 @@ impl BlockValidationState {
                     entry.insert(HolderState::Unasked);
                 }
             },
+            BlockValidationState::CheckingExecutedDeploys { timestamp, .. } => {
+                debug!(
+                    block_timestamp = %timestamp,
+                    "all deploys already fetched - not registering holder for block validation"
+                );
+            }
             BlockValidationState::Valid(_) | BlockValidationState::Invalid(_) => {
                 error!(state = %self, "unexpected state when adding holder");
             }
@@ -234,6 +262,7 @@ impl BlockValidationState {
     ///     one `Unasked` holder, returns `Start`
     ///   * if `InProgress` and any holder `Asked`, returns `Ongoing`
     ///   * if `InProgress` and all holders `Failed`, returns `Unable`
+    ///   * if `CheckingExecutedDeploys`, returns `Ongoing`
     ///   * if `Valid` or `Invalid`, returns `ValidationSucceeded` or `ValidationFailed`
     ///     respectively
     pub(super) fn start_fetching(&mut self) -> MaybeStartFetching {
@@ -274,6 +303,7 @@ impl BlockValidationState {
                     missing_deploys,
                 }
             }
+            BlockValidationState::CheckingExecutedDeploys { .. } => MaybeStartFetching::Ongoing,
             BlockValidationState::Valid(_) => MaybeStartFetching::ValidationSucceeded,
             BlockValidationState::Invalid(_) => MaybeStartFetching::ValidationFailed,
         }
@@ -281,13 +311,20 @@ impl BlockValidationState {
 
     pub(super) fn take_responders(&mut self) -> Vec<Responder<bool>> {
         match self {
-            BlockValidationState::InProgress { responders, .. } => mem::take(responders),
+            BlockValidationState::InProgress { responders, .. }
+            | BlockValidationState::CheckingExecutedDeploys { responders, .. } => {
+                mem::take(responders)
+            }
             BlockValidationState::Valid(_) | BlockValidationState::Invalid(_) => vec![],
         }
     }
 
     /// If the current state is `InProgress` and `dt_hash` is present, tries to add the footprint to
     /// the appendable block to continue validation of the proposed block.
+    ///
+    /// Once all footprints are added, the dependencies of the deploys are checked: those within
+    /// the block must be executed before their dependents.  The state then changes to
+    /// `CheckingExecutedDeploys`, to check the block against the deploys in earlier blocks.
     pub(super) fn try_add_deploy_footprint(
         &mut self,
         dt_hash: &DeployOrTransferHash,
@@ -297,6 +334,8 @@ impl BlockValidationState {
             BlockValidationState::InProgress {
                 appendable_block,
                 missing_deploys,
+                execution_order,
+                dependencies,
                 responders,
                 ..
             } => {
@@ -319,6 +358,10 @@ impl BlockValidationState {
                 };
                 match add_result {
                     Ok(()) => {
+                        let deploy_dependencies = footprint.header.dependencies();
+                        if !deploy_dependencies.is_empty() {
+                            dependencies.insert(*dt_hash, deploy_dependencies.clone());
+                        }
                         if !missing_deploys.is_empty() {
                             // The appendable block is still valid, but we still have missing
                             // deploys - nothing further to do here.
@@ -329,12 +372,33 @@ impl BlockValidationState {
                             );
                             return vec![];
                         }
-                        debug!(
-                            block_timestamp = %appendable_block.timestamp(),
-                            "no further missing deploys - block validation complete"
-                        );
-                        let new_state = BlockValidationState::Valid(appendable_block.timestamp());
-                        (new_state, mem::take(responders))
+                        let timestamp = appendable_block.timestamp();
+                        match external_dependencies(execution_order, dependencies) {
+                            Ok(external) => {
+                                debug!(
+                                    block_timestamp = %timestamp,
+                                    external_dependencies_len = external.len(),
+                                    "no further missing deploys - checking executed deploys"
+                                );
+                                *self = BlockValidationState::CheckingExecutedDeploys {
+                                    timestamp,
+                                    dependencies: external,
+                                    slots: appendable_block.slots().clone(),
+                                    responders: mem::take(responders),
+                                };
+                                return vec![];
+                            }
+                            Err(dependent) => {
+                                warn!(
+                                    %dependent,
+                                    "deploy executed before its dependency - block invalid"
+                                );
+                                (
+                                    BlockValidationState::Invalid(timestamp),
+                                    mem::take(responders),
+                                )
+                            }
+                        }
                     }
                     Err(error) => {
                         warn!(%dt_hash, ?footprint, %error, "block invalid");
@@ -343,12 +407,58 @@ impl BlockValidationState {
                     }
                 }
             }
-            BlockValidationState::Valid(_) | BlockValidationState::Invalid(_) => return vec![],
+            BlockValidationState::CheckingExecutedDeploys { .. }
+            | BlockValidationState::Valid(_)
+            | BlockValidationState::Invalid(_) => return vec![],
         };
         *self = new_state;
         responders
     }
 
+    /// If the current state is `CheckingExecutedDeploys` and the check has not been started yet,
+    /// returns the dependencies and slots to be checked.
+    #[allow(clippy::type_complexity)]
+    pub(super) fn take_executed_deploys_check(
+        &mut self,
+    ) -> Option<(
+        HashSet<DeployHash>,
+        HashMap<(PublicKey, Timestamp), DeployHash>,
+    )> {
+        match self {
+            BlockValidationState::CheckingExecutedDeploys {
+                dependencies,
+                slots,
+                ..
+            } if !slots.is_empty() => Some((mem::take(dependencies), mem::take(slots))),
+            _ => None,
+        }
+    }
+
+    /// If the current state is `CheckingExecutedDeploys`, sets the state to `Valid` if the block
+    /// is consistent with the deploys in earlier blocks, or to `Invalid` otherwise, and returns
+    /// the responders.
+    pub(super) fn resolve_executed_deploys_check(
+        &mut self,
+        is_valid: bool,
+    ) -> Vec<Responder<bool>> {
+        let (timestamp, responders) = match self {
+            BlockValidationState::CheckingExecutedDeploys {
+                timestamp,
+                responders,
+                ..
+            } => (*timestamp, mem::take(responders)),
+            BlockValidationState::InProgress { .. }
+            | BlockValidationState::Valid(_)
+            | BlockValidationState::Invalid(_) => return vec![],
+        };
+        *self = if is_valid {
+            BlockValidationState::Valid(timestamp)
+        } else {
+            BlockValidationState::Invalid(timestamp)
+        };
+        responders
+    }
+
     /// If the current state is `InProgress` and `dt_hash` is present, sets the state to `Invalid`
     /// and returns the responders.
     pub(super) fn try_mark_invalid(
@@ -367,7 +477,9 @@ impl BlockValidationState {
                 }
                 (appendable_block.timestamp(), mem::take(responders))
             }
-            BlockValidationState::Valid(_) | BlockValidationState::Invalid(_) => return vec![],
+            BlockValidationState::CheckingExecutedDeploys { .. }
+            | BlockValidationState::Valid(_)
+            | BlockValidationState::Invalid(_) => return vec![],
         };
         *self = BlockValidationState::Valid(timestamp);
         responders
@@ -375,7 +487,8 @@ impl BlockValidationState {
 
     pub(super) fn block_timestamp_if_completed(&self) -> Option<Timestamp> {
         match self {
-            BlockValidationState::InProgress { .. } => None,
+            BlockValidationState::InProgress { .. }
+            | BlockValidationState::CheckingExecutedDeploys { .. } => None,
             BlockValidationState::Valid(timestamp) | BlockValidationState::Invalid(timestamp) => {
                 Some(*timestamp)
             }
@@ -391,7 +504,9 @@ impl BlockValidationState {
                 .keys()
                 .map(|dt_hash| *dt_hash.deploy_hash())
                 .collect(),
-            BlockValidationState::Valid(_) | BlockValidationState::Invalid(_) => vec![],
+            BlockValidationState::CheckingExecutedDeploys { .. }
+            | BlockValidationState::Valid(_)
+            | BlockValidationState::Invalid(_) => vec![],
         }
     }
 
@@ -399,21 +514,27 @@ impl BlockValidationState {
     pub(super) fn holders_mut(&mut self) -> Option<&mut HashMap<NodeId, HolderState>> {
         match self {
             BlockValidationState::InProgress { holders, .. } => Some(holders),
-            BlockValidationState::Valid(_) | BlockValidationState::Invalid(_) => None,
+            BlockValidationState::CheckingExecutedDeploys { .. }
+            | BlockValidationState::Valid(_)
+            | BlockValidationState::Invalid(_) => None,
         }
     }
 
     #[cfg(test)]
     pub(super) fn responder_count(&self) -> usize {
         match self {
-            BlockValidationState::InProgress { responders, .. } => responders.len(),
+            BlockValidationState::InProgress { responders, .. }
+            | BlockValidationState::CheckingExecutedDeploys { responders, .. } => responders.len(),
             BlockValidationState::Valid(_) | BlockValidationState::Invalid(_) => 0,
         }
     }
 
     #[cfg(test)]
     pub(super) fn completed(&self) -> bool {
-        !matches!(self, BlockValidationState::InProgress { .. })
+        matches!(
+            self,
+            BlockValidationState::Valid(_) | BlockValidationState::Invalid(_)
+        )
     }
 }
 
@@ -425,6 +546,7 @@ impl Display for BlockValidationState {
                 missing_deploys,
                 holders,
                 responders,
+                ..
             } => {
                 write!(
                     formatter,
@@ -435,6 +557,21 @@ impl Display for BlockValidationState {
                     responders.len()
                 )
             }
+            BlockValidationState::CheckingExecutedDeploys {
+                timestamp,
+                dependencies,
+                slots,
+                responders,
+            } => {
+                write!(
+                    formatter,
+                    "BlockValidationState::CheckingExecutedDeploys({timestamp}, {} dependencies, \
+                    {} slots, {} responders)",
+                    dependencies.len(),
+                    slots.len(),
+                    responders.len()
+                )
+            }
             BlockValidationState::Valid(timestamp) => {
                 write!(formatter, "BlockValidationState::Valid({timestamp})")
             }
@@ -445,12 +582,41 @@ impl Display for BlockValidationState {
     }
 }
 
+/// Checks that every deploy in the block is executed after those of its dependencies which are
+/// part of the block, and returns the dependencies which are not part of it.
+///
+/// Returns the offending deploy if a deploy is executed before (or is) one of its dependencies.
+fn external_dependencies(
+    execution_order: &[DeployOrTransferHash],
+    dependencies: &HashMap<DeployOrTransferHash, Vec<DeployHash>>,
+) -> Result<HashSet<DeployHash>, DeployHash> {
+    let in_block: HashSet<DeployHash> = execution_order
+        .iter()
+        .map(|dt_hash| *dt_hash.deploy_hash())
+        .collect();
+    let mut executed = HashSet::new();
+    let mut external = HashSet::new();
+    for dt_hash in execution_order {
+        for dependency in dependencies.get(dt_hash).into_iter().flatten() {
+            if executed.contains(dependency) {
+                continue;
+            }
+            if in_block.contains(dependency) {
+                return Err(*dt_hash.deploy_hash());
+            }
+            external.insert(*dependency);
+        }
+        executed.insert(*dt_hash.deploy_hash());
+    }
+    Ok(external)
+}
+
 #[cfg(test)]
 mod tests {
     use futures::channel::oneshot;
     use rand::Rng;
 
-    use casper_types::{testing::TestRng, TimeDiff};
+    use casper_types::{testing::TestRng, SecretKey, TimeDiff};
 
     use super::{super::tests::*, *};
     use crate::{
@@ -616,9 +782,9 @@ mod tests {
                 assert_eq!(holders.values().next().unwrap(), &HolderState::Unasked);
                 assert_eq!(responders.len(), 1);
             }
-            BlockValidationState::Valid(_) | BlockValidationState::Invalid(_) => {
-                panic!("unexpected state")
-            }
+            BlockValidationState::CheckingExecutedDeploys { .. }
+            | BlockValidationState::Valid(_)
+            | BlockValidationState::Invalid(_) => panic!("unexpected state"),
         }
         assert!(maybe_responder.is_none());
     }
@@ -856,10 +1022,111 @@ mod tests {
             ));
         }
 
-        // The final deploy should cause the state to go to `Valid` and the responders to be
-        // returned.
+        // The final deploy should cause the state to go to `CheckingExecutedDeploys`, and once
+        // that check passes, to `Valid` with the responders returned.
         let (dt_hash, footprint) = footprints.pop().unwrap();
         let responders = state.try_add_deploy_footprint(&dt_hash, &footprint);
+        assert!(responders.is_empty());
+        assert!(matches!(
+            state,
+            BlockValidationState::CheckingExecutedDeploys { .. }
+        ));
+        assert!(state.take_executed_deploys_check().is_some());
+        let responders = state.resolve_executed_deploys_check(true);
+        assert_eq!(responders.len(), 1);
+        assert!(matches!(state, BlockValidationState::Valid(_)));
+    }
+
+    #[test]
+    fn state_should_change_to_invalid_if_deploys_take_same_slot() {
+        let mut fixture = Fixture::new();
+        let ttl = TimeDiff::from_seconds(10);
+        let secret_key = SecretKey::random(&mut fixture.rng);
+        let timestamp = Timestamp::from(900);
+        fixture.deploys = vec![
+            new_signed_deploy(&secret_key, timestamp, ttl, 1, vec![]),
+            new_signed_deploy(&secret_key, timestamp, ttl, 2, vec![]),
+        ];
+        let (mut state, _maybe_responder) = fixture.new_state(0, 0);
+
+        let mut responders = vec![];
+        for (dt_hash, footprint) in fixture.footprints() {
+            responders = state.try_add_deploy_footprint(&dt_hash, &footprint);
+        }
+        assert_eq!(responders.len(), 1);
+        assert!(matches!(state, BlockValidationState::Invalid(_)));
+    }
+
+    #[test]
+    fn state_should_change_to_invalid_if_deploy_precedes_its_dependency() {
+        let mut fixture = Fixture::new();
+        let ttl = TimeDiff::from_seconds(10);
+        let dependency = new_deploy(&mut fixture.rng, Timestamp::from(900), ttl);
+        let dependent = new_dependent_deploy(
+            &mut fixture.rng,
+            Timestamp::from(901),
+            ttl,
+            vec![*dependency.hash()],
+        );
+        fixture.deploys = vec![dependent, dependency];
+        let (mut state, _maybe_responder) = fixture.new_state(0, 0);
+
+        let mut responders = vec![];
+        for (dt_hash, footprint) in fixture.footprints() {
+            responders = state.try_add_deploy_footprint(&dt_hash, &footprint);
+        }
+        assert_eq!(responders.len(), 1);
+        assert!(matches!(state, BlockValidationState::Invalid(_)));
+    }
+
+    #[test]
+    fn state_should_check_dependencies_not_in_block() {
+        let mut fixture = Fixture::new();
+        let ttl = TimeDiff::from_seconds(10);
+        let dependency = new_deploy(&mut fixture.rng, Timestamp::from(900), ttl);
+        let external_dependency = DeployHash::random(&mut fixture.rng);
+        let dependent = new_dependent_deploy(
+            &mut fixture.rng,
+            Timestamp::from(901),
+            ttl,
+            vec![*dependency.hash(), external_dependency],
+        );
+        // Transfers are executed after all deploys, so may depend on any deploy in the block.
+        let transfer = new_transfer(&mut fixture.rng, Timestamp::from(902), ttl);
+        fixture.deploys = vec![dependency, dependent.clone()];
+        fixture.transfers = vec![transfer];
+        let (mut state, _maybe_responder) = fixture.new_state(0, 0);
+
+        for (dt_hash, footprint) in fixture.footprints() {
+            let responders = state.try_add_deploy_footprint(&dt_hash, &footprint);
+            assert!(responders.is_empty());
+        }
+        assert!(matches!(
+            state,
+            BlockValidationState::CheckingExecutedDeploys { .. }
+        ));
+
+        // Only the dependency outside the block should need checking, along with the slots of all
+        // deploys in the block, and only once.
+        let (dependencies, slots) = state.take_executed_deploys_check().unwrap();
+        assert_eq!(dependencies, iter::once(external_dependency).collect());
+        let expected_slots = fixture
+            .deploys
+            .iter()
+            .chain(fixture.transfers.iter())
+            .map(|deploy| {
+                let slot = (
+                    deploy.header().account().clone(),
+                    deploy.header().timestamp(),
+                );
+                (slot, *deploy.hash())
+            })
+            .collect();
+        assert_eq!(slots, expected_slots);
+        assert!(state.take_executed_deploys_check().is_none());
+        assert_eq!(state.start_fetching(), MaybeStartFetching::Ongoing);
+
+        let responders = state.resolve_executed_deploys_check(true);
         assert_eq!(responders.len(), 1);
         assert!(matches!(state, BlockValidationState::Valid(_)));
     }
@@ -879,9 +1146,9 @@ mod tests {
                 missing_deploys.clone(),
                 holders.clone(),
             ),
-            BlockValidationState::Valid(_) | BlockValidationState::Invalid(_) => {
-                panic!("unexpected state")
-            }
+            BlockValidationState::CheckingExecutedDeploys { .. }
+            | BlockValidationState::Valid(_)
+            | BlockValidationState::Invalid(_) => panic!("unexpected state"),
         };
 
         // Create a new, random deploy.
@@ -903,9 +1170,9 @@ mod tests {
                 assert_eq!(&missing_deploys_before, missing_deploys);
                 assert_eq!(&holders_before, holders);
             }
-            BlockValidationState::Valid(_) | BlockValidationState::Invalid(_) => {
-                panic!("unexpected state")
-            }
+            BlockValidationState::CheckingExecutedDeploys { .. }
+            | BlockValidationState::Valid(_)
+            | BlockValidationState::Invalid(_) => panic!("unexpected state"),
         };
     }
 
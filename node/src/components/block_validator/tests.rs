@@ -99,6 +99,45 @@ impl MockReactor {
             }
         }
     }
+
+    /// Answers the storage requests made when checking a block against the executed deploys, as if
+    /// `executed` were the deploys included in stored blocks.
+    async fn expect_executed_deploys_check(&self, executed: &[Deploy]) {
+        loop {
+            let ((_ancestor, reactor_event), _) = self.scheduler.pop().await;
+            match reactor_event {
+                ReactorEvent::Storage(StorageRequest::GetExecutedDeploys {
+                    deploy_hashes,
+                    responder,
+                }) => {
+                    let found = executed
+                        .iter()
+                        .map(|deploy| *deploy.hash())
+                        .filter(|deploy_hash| deploy_hashes.contains(deploy_hash))
+                        .collect();
+                    responder.respond(found).await;
+                }
+                // The slots are always checked last.
+                ReactorEvent::Storage(StorageRequest::GetDeploysInSlots {
+                    slots,
+                    deploy_hashes: _,
+                    responder,
+                }) => {
+                    let found = executed
+                        .iter()
+                        .filter(|deploy| {
+                            let header = deploy.header();
+                            slots.contains(&(header.account().clone(), header.timestamp()))
+                        })
+                        .map(|deploy| *deploy.hash())
+                        .collect();
+                    responder.respond(found).await;
+                    return;
+                }
+                _ => panic!("unexpected event: {:?}", reactor_event),
+            }
+        }
+    }
 }
 
 pub(super) fn new_proposed_block(
@@ -114,7 +153,28 @@ pub(super) fn new_proposed_block(
 }
 
 pub(super) fn new_deploy(rng: &mut TestRng, timestamp: Timestamp, ttl: TimeDiff) -> Deploy {
+    new_dependent_deploy(rng, timestamp, ttl, vec![])
+}
+
+pub(super) fn new_dependent_deploy(
+    rng: &mut TestRng,
+    timestamp: Timestamp,
+    ttl: TimeDiff,
+    dependencies: Vec<DeployHash>,
+) -> Deploy {
     let secret_key = SecretKey::random(rng);
+    new_signed_deploy(&secret_key, timestamp, ttl, 1, dependencies)
+}
+
+/// Returns a deploy signed by the given key, so that deploys with the same timestamp take the same
+/// slot.
+pub(super) fn new_signed_deploy(
+    secret_key: &SecretKey,
+    timestamp: Timestamp,
+    ttl: TimeDiff,
+    gas_price: u64,
+    dependencies: Vec<DeployHash>,
+) -> Deploy {
     let chain_name = "chain".to_string();
     let payment = ExecutableDeployItem::ModuleBytes {
         module_bytes: Bytes::new(),
@@ -124,8 +184,6 @@ pub(super) fn new_deploy(rng: &mut TestRng, timestamp: Timestamp, ttl: TimeDiff)
         module_bytes: Bytes::new(),
         args: RuntimeArgs::new(),
     };
-    let dependencies = vec![];
-    let gas_price = 1;
 
     Deploy::new(
         timestamp,
@@ -135,7 +193,7 @@ pub(super) fn new_deploy(rng: &mut TestRng, timestamp: Timestamp, ttl: TimeDiff)
         chain_name,
         payment,
         session,
-        &secret_key,
+        secret_key,
         None,
     )
 }
@@ -172,6 +230,18 @@ async fn validate_block(
     timestamp: Timestamp,
     deploys: Vec<Deploy>,
     transfers: Vec<Deploy>,
+) -> bool {
+    validate_block_after(rng, timestamp, deploys, transfers, &[]).await
+}
+
+/// Validates a block using a `BlockValidator` component, with `executed` as the deploys included in
+/// stored blocks, and returns the result.
+async fn validate_block_after(
+    rng: &mut TestRng,
+    timestamp: Timestamp,
+    deploys: Vec<Deploy>,
+    transfers: Vec<Deploy>,
+    executed: &[Deploy],
 ) -> bool {
     // Assemble the block to be validated.
     let deploys_for_block = deploys
@@ -233,8 +303,28 @@ async fn validate_block(
         }));
     }
 
-    // We expect exactly one effect: the validation response. This will resolve the result.
+    // We expect exactly one effect: either the validation response if any deploy turned out to be
+    // invalid, or the check against the executed deploys, which results in the response.
     assert_eq!(1, effects.len());
+    if !block_validator
+        .validation_states
+        .values()
+        .all(BlockValidationState::completed)
+    {
+        let checks: Vec<_> = effects.into_iter().map(tokio::spawn).collect();
+        reactor.expect_executed_deploys_check(executed).await;
+        effects = Effects::new();
+        for check in checks {
+            let events = check.await.unwrap();
+            assert_eq!(1, events.len());
+            effects.extend(
+                events
+                    .into_iter()
+                    .flat_map(|event| block_validator.handle_event(effect_builder, rng, event)),
+            );
+        }
+        assert_eq!(1, effects.len());
+    }
     for effect in effects {
         tokio::spawn(effect).await.unwrap(); // Response.
     }
@@ -316,6 +406,40 @@ async fn transfer_deploy_mixup_and_replay() {
     assert!(!validate_block(&mut rng, timestamp, deploys, transfers).await);
 }
 
+/// Verifies that a block is invalid if it contains two deploys from the same account with the same
+/// timestamp, or a deploy whose slot is taken by a deploy executed earlier.
+#[tokio::test]
+async fn superseding_deploys() {
+    let mut rng = TestRng::new();
+    let ttl = TimeDiff::from_millis(200);
+    let timestamp = Timestamp::from(1000);
+    let later = Timestamp::from(1100);
+    let secret_key = SecretKey::random(&mut rng);
+    let deploy1 = new_signed_deploy(&secret_key, timestamp, ttl, 1, vec![]);
+    let deploy2 = new_signed_deploy(&secret_key, timestamp, ttl, 2, vec![]);
+    let later_deploy = new_signed_deploy(&secret_key, later, ttl, 1, vec![]);
+    let other_deploy = new_deploy(&mut rng, timestamp, ttl);
+
+    // Each deploy is valid on its own, as are deploys from the same account in different slots.
+    assert!(validate_block(&mut rng, timestamp, vec![deploy1.clone()], vec![]).await);
+    assert!(validate_block(&mut rng, timestamp, vec![deploy2.clone()], vec![]).await);
+    let deploys = vec![deploy1.clone(), later_deploy.clone()];
+    assert!(validate_block(&mut rng, later, deploys, vec![]).await);
+
+    // Two deploys in the same slot can't both be included in a block.
+    let deploys = vec![deploy1.clone(), deploy2.clone()];
+    assert!(!validate_block(&mut rng, timestamp, deploys, vec![]).await);
+
+    // Nor can a deploy whose slot is taken by an executed deploy, even with a higher gas price.
+    let deploys = vec![deploy2.clone(), other_deploy.clone()];
+    let executed = [deploy1.clone()];
+    assert!(!validate_block_after(&mut rng, timestamp, deploys, vec![], &executed).await);
+
+    // Executed deploys in other slots don't matter.
+    let executed = [deploy1, other_deploy];
+    assert!(validate_block_after(&mut rng, later, vec![later_deploy], vec![], &executed).await);
+}
+
 /// Verifies that the block validator fetches from multiple peers.
 #[tokio::test]
 async fn should_fetch_from_multiple_peers() {
@@ -469,11 +593,23 @@ async fn should_fetch_from_multiple_peers() {
             assert_eq!(1, events.len());
             // The event should be `DeployFetched`.
             let event = events.pop().unwrap();
-            // Once the block is deemed valid (i.e. when the final missing deploy is successfully
-            // fetched) the effects will be three validation responses.
+            // Once the final missing deploy is successfully fetched, the effect will be the check
+            // against the executed deploys.
             effects.extend(block_validator.handle_event(effect_builder, &mut rng, event));
-            assert!(effects.is_empty() || effects.len() == peer_count as usize);
+            assert!(effects.len() <= 1);
+        }
+
+        // Once the block is deemed valid the effects will be three validation responses.
+        assert_eq!(effects.len(), 1);
+        let checks = effects.into_iter().map(tokio::spawn).collect_vec();
+        reactor.expect_executed_deploys_check(&[]).await;
+        let mut effects = Effects::new();
+        for check in checks {
+            for event in check.await.unwrap() {
+                effects.extend(block_validator.handle_event(effect_builder, &mut rng, event));
+            }
         }
+        assert_eq!(effects.len(), peer_count as usize);
 
         for effect in effects {
             tokio::spawn(effect).await.unwrap();
@@ -21,6 +21,7 @@ use std::{
 use datasize::DataSize;
 use derive_more::From;
 use lmdb::DatabaseFlags;
+use num_rational::Ratio;
 use once_cell::sync::Lazy;
 use prometheus::Registry;
 use serde::Serialize;
@@ -28,12 +29,15 @@ use thiserror::Error;
 use tracing::{debug, error, info, trace};
 
 use casper_execution_engine::{
-    core::engine_state::{
-        self,
-        engine_config::{FeeHandling, RefundHandling},
-        genesis::GenesisError,
-        ChainspecRegistry, DeployItem, EngineConfigBuilder, EngineState, GenesisSuccess,
-        SystemContractRegistry, UpgradeConfig, UpgradeSuccess,
+    core::{
+        engine_state::{
+            self,
+            engine_config::{FeeHandling, RefundHandling},
+            genesis::GenesisError,
+            ChainspecRegistry, DeployItem, EngineConfigBuilder, EngineState, GasProfile,
+            GenesisSuccess, SystemContractRegistry, UpgradeConfig, UpgradeSuccess,
+        },
+        wasm_engine::WasmEngineKind,
     },
     shared::{newtypes::CorrelationId, system_config::SystemConfig, wasm_config::WasmConfig},
     storage::{
@@ -42,7 +46,9 @@ use casper_execution_engine::{
     },
 };
 use casper_hashing::Digest;
-use casper_types::{bytesrepr::Bytes, EraId, ProtocolVersion, PublicKey, Timestamp};
+use casper_types::{
+    bytesrepr::Bytes, EraId, ExecutionResult, ProtocolVersion, PublicKey, Timestamp,
+};
 
 use crate::{
     components::{fetcher::FetchResponse, Component, ComponentState},
@@ -128,6 +134,10 @@ pub struct SpeculativeExecutionState {
     pub protocol_version: ProtocolVersion,
 }
 
+/// Result of a speculative execution: the execution result of the deploy, if any, along with a
+/// profile of the gas it charged if one was requested.
+pub(crate) type SpeculativeExecutionResult = Option<(ExecutionResult, Option<GasProfile>)>;
+
 /// State to use to construct the next block in the blockchain. Includes the state root hash for the
 /// execution engine as well as certain values the next header will be based on.
 #[derive(DataSize, Debug, Clone, Serialize)]
@@ -212,6 +222,7 @@ pub(crate) struct ContractRuntime {
     system_contract_registry: Option<SystemContractRegistry>,
     activation_point: ActivationPoint,
     prune_batch_size: u64,
+    execution_threads: usize,
 }
 
 impl Debug for ContractRuntime {
@@ -356,6 +367,23 @@ impl ContractRuntime {
                 }
                 .ignore()
             }
+            ContractRuntimeRequest::QueryMulti {
+                query_request,
+                responder,
+            } => {
+                trace!(?query_request, "multi query");
+                let engine_state = Arc::clone(&self.engine_state);
+                let metrics = Arc::clone(&self.metrics);
+                async move {
+                    let correlation_id = CorrelationId::new();
+                    let start = Instant::now();
+                    let result = engine_state.run_query_multi(correlation_id, query_request);
+                    metrics.run_query.observe(start.elapsed().as_secs_f64());
+                    trace!(?result, "multi query result");
+                    responder.respond(result).await
+                }
+                .ignore()
+            }
             ContractRuntimeRequest::GetBalance {
                 balance_request,
                 responder,
@@ -494,6 +522,7 @@ impl ContractRuntime {
                         let shared_pre_state = Arc::clone(&self.execution_pre_state);
                         let activation_point = self.activation_point;
                         let prune_batch_size = self.prune_batch_size;
+                        let execution_threads = self.execution_threads;
                         effects.extend(
                             Self::execute_finalized_block_or_requeue(
                                 engine_state,
@@ -508,6 +537,7 @@ impl ContractRuntime {
                                 activation_point,
                                 key_block_height_for_activation_point,
                                 prune_batch_size,
+                                execution_threads,
                                 meta_block_state,
                             )
                             .ignore(),
@@ -569,6 +599,7 @@ impl ContractRuntime {
             ContractRuntimeRequest::SpeculativeDeployExecution {
                 execution_prestate,
                 deploy,
+                gas_profiling,
                 responder,
             } => {
                 let engine_state = Arc::clone(&self.engine_state);
@@ -578,6 +609,7 @@ impl ContractRuntime {
                             engine_state.as_ref(),
                             execution_prestate,
                             DeployItem::from((*deploy).clone()),
+                            gas_profiling,
                         )
                     })
                     .await;
@@ -605,12 +637,20 @@ impl ContractRuntime {
         strict_argument_checking: bool,
         vesting_schedule_period_millis: u64,
         max_delegators_per_validator: Option<u32>,
+        max_reserved_delegators_per_validator: u32,
         registry: &Registry,
         administrative_accounts: BTreeSet<PublicKey>,
         allow_auction_bids: bool,
         allow_unrestricted_transfers: bool,
         refund_handling: RefundHandling,
         fee_handling: FeeHandling,
+        equivocation_slashing_fraction: Ratio<u64>,
+        unavailability_slashing_fraction: Ratio<u64>,
+        inactivity_jail_threshold: u32,
+        jail_period_eras: u64,
+        max_jail_period_eras: u64,
+        allow_delegation_transfers: bool,
+        wasm_engine: WasmEngineKind,
     ) -> Result<Self, ConfigError> {
         // TODO: This is bogus, get rid of this
         let execution_pre_state = Arc::new(Mutex::new(ExecutionPreState {
@@ -642,6 +682,7 @@ impl ContractRuntime {
             .with_strict_argument_checking(strict_argument_checking)
             .with_vesting_schedule_period_millis(vesting_schedule_period_millis)
             .with_max_delegators_per_validator(max_delegators_per_validator)
+            .with_max_reserved_delegators_per_validator(max_reserved_delegators_per_validator)
             .with_wasm_config(wasm_config)
             .with_system_config(system_config)
             .with_administrative_accounts(administrative_accounts)
@@ -649,6 +690,13 @@ impl ContractRuntime {
             .with_allow_unrestricted_transfers(allow_unrestricted_transfers)
             .with_refund_handling(refund_handling)
             .with_fee_handling(fee_handling)
+            .with_equivocation_slashing_fraction(equivocation_slashing_fraction)
+            .with_unavailability_slashing_fraction(unavailability_slashing_fraction)
+            .with_inactivity_jail_threshold(inactivity_jail_threshold)
+            .with_jail_period_eras(jail_period_eras)
+            .with_max_jail_period_eras(max_jail_period_eras)
+            .with_allow_delegation_transfers(allow_delegation_transfers)
+            .with_wasm_engine(wasm_engine)
             .build();
 
         let engine_state = Arc::new(EngineState::new(global_state, engine_config));
@@ -665,6 +713,7 @@ impl ContractRuntime {
             system_contract_registry: None,
             activation_point,
             prune_batch_size,
+            execution_threads: contract_runtime_config.execution_threads_or_default(),
         })
     }
 
@@ -756,6 +805,7 @@ impl ContractRuntime {
         activation_point: ActivationPoint,
         key_block_height_for_activation_point: u64,
         prune_batch_size: u64,
+        execution_threads: usize,
         mut meta_block_state: MetaBlockState,
     ) where
         REv: From<ContractRuntimeRequest>
@@ -784,6 +834,7 @@ impl ContractRuntime {
                 activation_point.era_id(),
                 key_block_height_for_activation_point,
                 prune_batch_size,
+                execution_threads,
             )
         })
         .await
@@ -821,11 +872,16 @@ impl ContractRuntime {
 
         if let Some(StepEffectAndUpcomingEraValidators {
             step_execution_journal,
+            scheduled_transfers,
             mut upcoming_era_validators,
         }) = maybe_step_effect_and_upcoming_era_validators
         {
             effect_builder
-                .announce_commit_step_success(current_era_id, step_execution_journal)
+                .announce_commit_step_success(
+                    current_era_id,
+                    step_execution_journal,
+                    scheduled_transfers,
+                )
                 .await;
 
             if current_era_id.is_genesis() {
@@ -980,7 +1036,12 @@ impl ContractRuntime {
 #[cfg(test)]
 mod trie_chunking_tests {
     use casper_execution_engine::{
-        core::engine_state::engine_config::{DEFAULT_FEE_HANDLING, DEFAULT_REFUND_HANDLING},
+        core::engine_state::engine_config::{
+            DEFAULT_ALLOW_DELEGATION_TRANSFERS, DEFAULT_FEE_HANDLING,
+            DEFAULT_INACTIVITY_JAIL_THRESHOLD, DEFAULT_JAIL_PERIOD_ERAS,
+            DEFAULT_MAX_JAIL_PERIOD_ERAS, DEFAULT_MAX_RESERVED_DELEGATORS_PER_VALIDATOR,
+            DEFAULT_REFUND_HANDLING, DEFAULT_SLASHING_FRACTION,
+        },
         shared::{
             additive_map::AdditiveMap, newtypes::CorrelationId, system_config::SystemConfig,
             transform::Transform, wasm_config::WasmConfig,
@@ -1000,7 +1061,7 @@ mod trie_chunking_tests {
         types::{ActivationPoint, ChunkingError, TrieOrChunk, TrieOrChunkId, ValueOrChunk},
     };
 
-    use super::ContractRuntimeError;
+    use super::{ContractRuntimeError, WasmEngineKind};
 
     #[derive(Debug, Clone)]
     struct TestPair(Key, StoredValue);
@@ -1065,12 +1126,20 @@ mod trie_chunking_tests {
             true,
             1,
             None,
+            DEFAULT_MAX_RESERVED_DELEGATORS_PER_VALIDATOR,
             &Registry::default(),
             Default::default(),
             true,
             true,
             DEFAULT_REFUND_HANDLING,
             DEFAULT_FEE_HANDLING,
+            DEFAULT_SLASHING_FRACTION,
+            DEFAULT_SLASHING_FRACTION,
+            DEFAULT_INACTIVITY_JAIL_THRESHOLD,
+            DEFAULT_JAIL_PERIOD_ERAS,
+            DEFAULT_MAX_JAIL_PERIOD_ERAS,
+            DEFAULT_ALLOW_DELEGATION_TRANSFERS,
+            WasmEngineKind::default(),
         )
         .unwrap();
         let empty_state_root = contract_runtime
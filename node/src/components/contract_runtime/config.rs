@@ -7,6 +7,7 @@ const DEFAULT_MAX_GLOBAL_STATE_SIZE: usize = 805_306_368_000; // 750 GiB
 const DEFAULT_MAX_READERS: u32 = 512;
 const DEFAULT_MAX_QUERY_DEPTH: u64 = 5;
 const DEFAULT_MANUAL_SYNC_ENABLED: bool = true;
+const DEFAULT_EXECUTION_THREADS: usize = 1;
 
 /// Contract runtime configuration.
 #[derive(Clone, Copy, DataSize, Debug, Deserialize, Serialize)]
@@ -31,6 +32,10 @@ pub struct Config {
     ///
     /// Defaults to `true`.
     pub enable_manual_sync: Option<bool>,
+    /// The number of threads used to speculatively execute the deploys of a block in parallel.
+    ///
+    /// Defaults to 1, i.e. deploys are executed serially.
+    pub execution_threads: Option<usize>,
 }
 
 impl Config {
@@ -58,6 +63,13 @@ impl Config {
         self.enable_manual_sync
             .unwrap_or(DEFAULT_MANUAL_SYNC_ENABLED)
     }
+
+    /// Number of threads used to execute deploys.
+    pub fn execution_threads_or_default(&self) -> usize {
+        self.execution_threads
+            .unwrap_or(DEFAULT_EXECUTION_THREADS)
+            .max(1)
+    }
 }
 
 impl Default for Config {
@@ -67,6 +79,7 @@ impl Default for Config {
             max_readers: Some(DEFAULT_MAX_READERS),
             max_query_depth: Some(DEFAULT_MAX_QUERY_DEPTH),
             enable_manual_sync: Some(DEFAULT_MANUAL_SYNC_ENABLED),
+            execution_threads: Some(DEFAULT_EXECUTION_THREADS),
         }
     }
 }
@@ -1,4 +1,4 @@
-use prometheus::{self, Gauge, Histogram, IntGauge, Registry};
+use prometheus::{self, Gauge, Histogram, IntCounter, IntGauge, Registry};
 
 use crate::{unregister_metric, utils};
 
@@ -55,6 +55,19 @@ const EXEC_QUEUE_SIZE_NAME: &str = "execution_queue_size";
 const EXEC_QUEUE_SIZE_HELP: &str =
     "number of blocks that are currently enqueued and waiting for execution";
 
+const WASM_CACHE_HITS_NAME: &str = "contract_runtime_wasm_cache_hits";
+const WASM_CACHE_HITS_HELP: &str =
+    "number of stored contract calls whose wasm module was served from the module cache";
+
+const WASM_CACHE_MISSES_NAME: &str = "contract_runtime_wasm_cache_misses";
+const WASM_CACHE_MISSES_HELP: &str =
+    "number of stored contract calls whose wasm module had to be deserialized and compiled";
+
+const REEXECUTED_DEPLOYS_NAME: &str = "contract_runtime_reexecuted_deploys";
+const REEXECUTED_DEPLOYS_HELP: &str =
+    "number of speculatively executed deploys which had to be executed again as an earlier deploy \
+     in their block changed a value they read";
+
 /// Metrics for the contract runtime component.
 #[derive(Debug)]
 pub struct Metrics {
@@ -71,6 +84,9 @@ pub struct Metrics {
     pub(super) exec_block: Histogram,
     pub(super) latest_commit_step: Gauge,
     pub(super) exec_queue_size: IntGauge,
+    pub(super) wasm_cache_hits: IntCounter,
+    pub(super) wasm_cache_misses: IntCounter,
+    pub(super) reexecuted_deploys: IntCounter,
     registry: Registry,
 }
 
@@ -95,6 +111,15 @@ impl Metrics {
         let exec_queue_size = IntGauge::new(EXEC_QUEUE_SIZE_NAME, EXEC_QUEUE_SIZE_HELP)?;
         registry.register(Box::new(exec_queue_size.clone()))?;
 
+        let wasm_cache_hits = IntCounter::new(WASM_CACHE_HITS_NAME, WASM_CACHE_HITS_HELP)?;
+        registry.register(Box::new(wasm_cache_hits.clone()))?;
+
+        let wasm_cache_misses = IntCounter::new(WASM_CACHE_MISSES_NAME, WASM_CACHE_MISSES_HELP)?;
+        registry.register(Box::new(wasm_cache_misses.clone()))?;
+
+        let reexecuted_deploys = IntCounter::new(REEXECUTED_DEPLOYS_NAME, REEXECUTED_DEPLOYS_HELP)?;
+        registry.register(Box::new(reexecuted_deploys.clone()))?;
+
         Ok(Metrics {
             run_execute: utils::register_histogram_metric(
                 registry,
@@ -164,6 +189,9 @@ impl Metrics {
             )?,
             latest_commit_step,
             exec_queue_size,
+            wasm_cache_hits,
+            wasm_cache_misses,
+            reexecuted_deploys,
             registry: registry.clone(),
         })
     }
@@ -184,5 +212,8 @@ impl Drop for Metrics {
         unregister_metric!(self.registry, self.exec_block);
         unregister_metric!(self.registry, self.latest_commit_step);
         unregister_metric!(self.registry, self.exec_queue_size);
+        unregister_metric!(self.registry, self.wasm_cache_hits);
+        unregister_metric!(self.registry, self.wasm_cache_misses);
+        unregister_metric!(self.registry, self.reexecuted_deploys);
     }
 }
@@ -1,4 +1,14 @@
-use std::{cmp, collections::BTreeMap, ops::Range, sync::Arc, time::Instant};
+use std::{
+    cmp,
+    collections::{BTreeMap, BTreeSet},
+    ops::Range,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::Instant,
+};
 
 use itertools::Itertools;
 use tracing::{debug, error, info, trace, warn};
@@ -8,17 +18,20 @@ use casper_execution_engine::{
         engine_state::{
             self, execution_result::ExecutionResults, step::EvictItem, ChecksumRegistry,
             DeployItem, EngineState, ExecuteRequest, ExecutionResult as EngineExecutionResult,
-            GetEraValidatorsRequest, PruneConfig, PruneResult, RewardItem, StepError, StepRequest,
-            StepSuccess,
+            GetEraValidatorsRequest, PruneConfig, PruneResult, RewardItem, SlashItem, StepError,
+            StepRequest, StepSuccess,
         },
         execution,
     },
     shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
-    storage::global_state::{lmdb::LmdbGlobalState, CommitProvider, StateProvider},
+    storage::global_state::{
+        lmdb::LmdbGlobalState, read_recording::ReadSet, CommitProvider, StateProvider,
+    },
 };
 use casper_hashing::Digest;
 use casper_types::{
-    CLValue, DeployHash, EraId, ExecutionResult, Key, ProtocolVersion, PublicKey, U512,
+    system::auction::SlashingOffence, CLValue, DeployHash, EraId, ExecutionResult, Key,
+    ProtocolVersion, PublicKey, U512,
 };
 
 use crate::{
@@ -26,8 +39,8 @@ use crate::{
         consensus::EraReport,
         contract_runtime::{
             error::BlockExecutionError, types::StepEffectAndUpcomingEraValidators,
-            BlockAndExecutionResults, ExecutionPreState, Metrics, SpeculativeExecutionState,
-            APPROVALS_CHECKSUM_NAME, EXECUTION_RESULTS_CHECKSUM_NAME,
+            BlockAndExecutionResults, ExecutionPreState, Metrics, SpeculativeExecutionResult,
+            SpeculativeExecutionState, APPROVALS_CHECKSUM_NAME, EXECUTION_RESULTS_CHECKSUM_NAME,
         },
         fetcher::FetchItem,
     },
@@ -99,6 +112,7 @@ pub fn execute_finalized_block(
     activation_point_era_id: EraId,
     key_block_height_for_activation_point: u64,
     prune_batch_size: u64,
+    execution_threads: usize,
 ) -> Result<BlockAndExecutionResults, BlockExecutionError> {
     if finalized_block.height() != execution_pre_state.next_block_height {
         return Err(BlockExecutionError::WrongBlockHeight {
@@ -125,24 +139,71 @@ pub fn execute_finalized_block(
     // Create a new EngineState that reads from LMDB but only caches changes in memory.
     let scratch_state = engine_state.get_scratch_engine_state();
 
+    // Speculatively execute the deploys in parallel against the pre-state.  This must happen before
+    // any effects are committed to the scratch state.
+    let speculative_results = execute_speculatively(
+        &scratch_state,
+        execution_threads,
+        pre_state_root_hash,
+        block_time,
+        protocol_version,
+        &finalized_block.proposer(),
+        &deploys,
+    );
+    // The keys written by the deploys committed so far.
+    let mut written_keys = BTreeSet::new();
+    let mut reexecuted_count = 0_usize;
+
     // WARNING: Do not change the order of `deploys` as it will result in a different root hash.
-    for deploy in deploys {
+    for (deploy, speculative_result) in deploys.into_iter().zip(speculative_results) {
         let deploy_hash = *deploy.hash();
         let deploy_header = deploy.header().clone();
-        let execute_request = ExecuteRequest::new(
-            state_root_hash,
-            block_time,
-            vec![DeployItem::from(deploy)],
-            protocol_version,
-            *finalized_block.proposer(),
-        );
 
-        // TODO: this is currently working coincidentally because we are passing only one
-        // deploy_item per exec. The execution results coming back from the EE lack the
-        // mapping between deploy_hash and execution result, and this outer logic is
-        // enriching it with the deploy hash. If we were passing multiple deploys per exec
-        // the relation between the deploy and the execution results would be lost.
-        let result = execute(&scratch_state, metrics.clone(), execute_request)?;
+        // A speculative result is identical to the serial one if the values it read are unchanged
+        // by the earlier deploys in the block, other than by adding to values it only added to.
+        // Otherwise, execute the deploy again against the current state.
+        let reusable_result = match speculative_result {
+            Some((result, read_set)) => {
+                if scratch_state.is_read_set_current(
+                    CorrelationId::new(),
+                    state_root_hash,
+                    &read_set,
+                    &written_keys,
+                )? {
+                    Some(result)
+                } else {
+                    reexecuted_count += 1;
+                    None
+                }
+            }
+            None => None,
+        };
+        let result = match reusable_result {
+            Some(result) => result,
+            None => {
+                let execute_request = ExecuteRequest::new(
+                    state_root_hash,
+                    block_time,
+                    vec![DeployItem::from(deploy)],
+                    protocol_version,
+                    *finalized_block.proposer(),
+                );
+
+                // TODO: this is currently working coincidentally because we are passing only one
+                // deploy_item per exec. The execution results coming back from the EE lack the
+                // mapping between deploy_hash and execution result, and this outer logic is
+                // enriching it with the deploy hash. If we were passing multiple deploys per exec
+                // the relation between the deploy and the execution results would be lost.
+                execute(&scratch_state, metrics.clone(), execute_request)?
+            }
+        };
+        written_keys.extend(
+            result
+                .iter()
+                .flat_map(|execution_result| execution_result.execution_journal().iter())
+                .filter(|(_, transform)| !matches!(transform, Transform::Identity))
+                .map(|(key, _)| *key),
+        );
 
         trace!(?deploy_hash, ?result, "deploy execution result");
         // As for now a given state is expected to exist.
@@ -181,8 +242,31 @@ pub fn execute_finalized_block(
     );
     scratch_state.apply_effect(CorrelationId::new(), state_root_hash, effects)?;
 
+    if execution_threads > 1 {
+        debug!(
+            deploy_count = deploy_ids.len(),
+            reexecuted_count, "deploys re-executed after speculative execution"
+        );
+    }
+
     if let Some(metrics) = metrics.as_ref() {
         metrics.exec_block.observe(start.elapsed().as_secs_f64());
+        metrics
+            .reexecuted_deploys
+            .inc_by(reexecuted_count.try_into().unwrap_or(u64::MAX));
+        // The cache counts hits and misses since the engine state was created, which the counters
+        // catch up with.
+        let cache_stats = engine_state.module_cache_stats();
+        metrics.wasm_cache_hits.inc_by(
+            cache_stats
+                .hits
+                .saturating_sub(metrics.wasm_cache_hits.get()),
+        );
+        metrics.wasm_cache_misses.inc_by(
+            cache_stats
+                .misses
+                .saturating_sub(metrics.wasm_cache_misses.get()),
+        );
     }
 
     // If the finalized block has an era report, run the auction contract and get the upcoming era
@@ -192,6 +276,7 @@ pub fn execute_finalized_block(
             let StepSuccess {
                 post_state_hash: _, // ignore the post-state-hash returned from scratch
                 execution_journal: step_execution_journal,
+                scheduled_transfers,
             } = commit_step(
                 &scratch_state, // engine_state
                 metrics,
@@ -216,6 +301,7 @@ pub fn execute_finalized_block(
             )?;
             Some(StepEffectAndUpcomingEraValidators {
                 step_execution_journal,
+                scheduled_transfers,
                 upcoming_era_validators,
             })
         } else {
@@ -407,12 +493,14 @@ where
 /// Execute the transaction without commiting the effects.
 /// Intended to be used for discovery operations on read-only nodes.
 ///
-/// Returns effects of the execution.
+/// Returns effects of the execution, along with a profile of the gas it charged if `gas_profiling`
+/// is set.
 pub fn execute_only<S>(
     engine_state: &EngineState<S>,
     execution_state: SpeculativeExecutionState,
     deploy: DeployItem,
-) -> Result<Option<ExecutionResult>, engine_state::Error>
+    gas_profiling: bool,
+) -> Result<SpeculativeExecutionResult, engine_state::Error>
 where
     S: StateProvider + CommitProvider,
     S::Error: Into<execution::Error>,
@@ -430,8 +518,15 @@ where
         protocol_version,
         PublicKey::System,
     );
-    let results = execute(engine_state, None, execute_request);
-    results.map(|mut execution_results| {
+    let results = if gas_profiling {
+        engine_state
+            .run_execute_with_gas_profiles(CorrelationId::new(), execute_request)
+            .map(|(execution_results, gas_profiles)| (execution_results, Some(gas_profiles)))
+    } else {
+        execute(engine_state, None, execute_request)
+            .map(|execution_results| (execution_results, None))
+    };
+    results.map(|(mut execution_results, gas_profiles)| {
         let len = execution_results.len();
         if len != 1 {
             warn!(
@@ -444,7 +539,10 @@ where
             // with `Some(_)` but `pop_front` already returns an `Option`.
             // We need to transform the `engine_state::ExecutionResult` into
             // `casper_types::ExecutionResult` as well.
-            execution_results.pop_front().map(Into::into)
+            let gas_profile = gas_profiles.and_then(|mut gas_profiles| gas_profiles.pop());
+            execution_results
+                .pop_front()
+                .map(|execution_result| (execution_result.into(), gas_profile))
         }
     })
 }
@@ -469,6 +567,74 @@ where
     result
 }
 
+/// Executes each of `deploys` against `state_root_hash` on up to `execution_threads` threads,
+/// returning the results along with the keys each execution read from global state.
+///
+/// The returned vector holds one entry per deploy, `None` where the deploy wasn't executed or its
+/// execution failed.  If `execution_threads` is 1, no deploys are executed.
+fn execute_speculatively<S>(
+    engine_state: &EngineState<S>,
+    execution_threads: usize,
+    state_root_hash: Digest,
+    block_time: u64,
+    protocol_version: ProtocolVersion,
+    proposer: &PublicKey,
+    deploys: &[Deploy],
+) -> Vec<Option<(ExecutionResults, ReadSet)>>
+where
+    S: StateProvider + CommitProvider + Sync,
+    S::Error: Into<execution::Error>,
+{
+    let mut results: Vec<_> = deploys.iter().map(|_| None).collect();
+    if execution_threads <= 1 || deploys.len() <= 1 {
+        return results;
+    }
+
+    let next_index = AtomicUsize::new(0);
+    let execute_deploys = || {
+        let mut thread_results = vec![];
+        loop {
+            let index = next_index.fetch_add(1, Ordering::Relaxed);
+            let deploy = match deploys.get(index) {
+                Some(deploy) => deploy,
+                None => break,
+            };
+            let execute_request = ExecuteRequest::new(
+                state_root_hash,
+                block_time,
+                vec![DeployItem::from(deploy.clone())],
+                protocol_version,
+                proposer.clone(),
+            );
+            match engine_state.run_execute_recording_reads(CorrelationId::new(), execute_request) {
+                Ok(result) => thread_results.push((index, result)),
+                Err(error) => {
+                    // The deploy will be executed again serially, surfacing the error if it recurs.
+                    debug!(deploy_hash = %deploy.hash(), ?error, "speculative execution failed");
+                }
+            }
+        }
+        thread_results
+    };
+
+    thread::scope(|scope| {
+        let handles = (0..cmp::min(execution_threads, deploys.len()))
+            .map(|_| scope.spawn(execute_deploys))
+            .collect_vec();
+        for handle in handles {
+            match handle.join() {
+                Ok(thread_results) => {
+                    for (index, result) in thread_results {
+                        results[index] = Some(result);
+                    }
+                }
+                Err(_) => error!("speculative execution thread panicked"),
+            }
+        }
+    });
+    results
+}
+
 fn commit_step<S>(
     engine_state: &EngineState<S>,
     maybe_metrics: Option<Arc<Metrics>>,
@@ -502,12 +668,31 @@ where
         .map(EvictItem::new)
         .collect();
 
+    // Equivocators and inactive validators are slashed by the fraction configured for their
+    // offence; an equivocator which was also inactive is only slashed for equivocating.  No one is
+    // slashed for an offence whose configured fraction is zero, which is the default.
+    let slash_item = |validator_id: &PublicKey, offence| {
+        let fraction = engine_state.config().slashing_fraction(offence);
+        (*fraction.numer() != 0).then(|| SlashItem::new(validator_id.clone(), offence, fraction))
+    };
+    let slash_items = equivocators
+        .iter()
+        .filter_map(|validator_id| slash_item(validator_id, SlashingOffence::Equivocation))
+        .chain(
+            inactive_validators
+                .iter()
+                .filter(|validator_id| !equivocators.contains(validator_id))
+                .filter_map(|validator_id| {
+                    slash_item(validator_id, SlashingOffence::Unavailability)
+                }),
+        )
+        .collect();
+
     let step_request = StepRequest {
         pre_state_hash: pre_state_root_hash,
         protocol_version,
         reward_items,
-        // Note: The Casper Network does not slash, but another network could
-        slash_items: vec![],
+        slash_items,
         evict_items,
         next_era_id,
         era_end_timestamp_millis,
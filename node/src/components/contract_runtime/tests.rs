@@ -7,7 +7,9 @@ use serde::Serialize;
 use tempfile::TempDir;
 
 use casper_execution_engine::core::engine_state::ExecutableDeployItem;
-use casper_types::{runtime_args, EraId, PublicKey, RuntimeArgs, SecretKey, TimeDiff, U512};
+use casper_types::{
+    runtime_args, EraId, ExecutionResult, PublicKey, RuntimeArgs, SecretKey, TimeDiff, U512,
+};
 
 use super::*;
 use crate::{
@@ -119,12 +121,20 @@ impl reactor::Reactor for Reactor {
             chainspec.core_config.strict_argument_checking,
             chainspec.core_config.vesting_schedule_period.millis(),
             Some(chainspec.core_config.max_delegators_per_validator),
+            chainspec.core_config.max_reserved_delegators_per_validator,
             registry,
             chainspec.core_config.administrators.clone(),
             chainspec.core_config.allow_auction_bids,
             chainspec.core_config.allow_unrestricted_transfers,
             chainspec.core_config.refund_handling,
             chainspec.core_config.fee_handling,
+            chainspec.core_config.equivocation_slashing_fraction,
+            chainspec.core_config.unavailability_slashing_fraction,
+            chainspec.core_config.inactivity_jail_threshold,
+            chainspec.core_config.jail_period_eras,
+            chainspec.core_config.max_jail_period_eras,
+            chainspec.core_config.allow_delegation_transfers,
+            chainspec.core_config.wasm_engine,
         )?;
 
         let reactor = Reactor {
@@ -392,3 +402,192 @@ async fn should_not_set_shared_pre_state_to_lower_block_height() {
         next_block_height
     );
 }
+
+fn transfer_deploys(chainspec: &Chainspec, rng: &mut NodeRng, count: usize) -> Vec<Deploy> {
+    let timestamp = Timestamp::now();
+    let ttl = TimeDiff::from_seconds(100);
+    let payment = ExecutableDeployItem::ModuleBytes {
+        module_bytes: Bytes::new(),
+        args: runtime_args! {
+          "amount" => U512::from(chainspec.system_costs_config.wasmless_transfer_cost()),
+        },
+    };
+    let secret_keys: Vec<SecretKey> = ["node-1.pem", "node-2.pem", "node-3.pem"]
+        .iter()
+        .map(|file_name| {
+            SecretKey::from_file(
+                RESOURCES_PATH
+                    .join("local")
+                    .join("secret_keys")
+                    .join(file_name),
+            )
+            .unwrap()
+        })
+        .collect();
+    let node_1_public_key = PublicKey::from(&secret_keys[0]);
+
+    // Each account transfers to new accounts, and some of node-2's transfers go to node-1's
+    // account, so some deploys depend on the effects of earlier ones.
+    (0..count)
+        .map(|index| {
+            let target_public_key = if index % 6 == 1 {
+                node_1_public_key.clone()
+            } else {
+                PublicKey::random(rng)
+            };
+            let session = ExecutableDeployItem::Transfer {
+                args: runtime_args! {
+                  "amount" => U512::from(chainspec.deploy_config.native_transfer_minimum_motes),
+                  "target" => target_public_key,
+                  "id" => Some(index as u64),
+                },
+            };
+            Deploy::new(
+                timestamp,
+                ttl,
+                1,
+                vec![],
+                chainspec.network_config.name.clone(),
+                payment.clone(),
+                session,
+                &secret_keys[index % secret_keys.len()],
+                None,
+            )
+        })
+        .collect()
+}
+
+/// Executes a block of the given deploys using `execution_threads` threads, returning the results
+/// and the number of deploys executed again after speculative execution.
+fn execute_deploys_with_threads(
+    chainspec: &Chainspec,
+    chainspec_raw_bytes: &ChainspecRawBytes,
+    timestamp: Timestamp,
+    deploys: &[Deploy],
+    execution_threads: usize,
+) -> (BlockAndExecutionResults, u64) {
+    let block_payload = BlockPayload::new(
+        vec![],
+        deploys.iter().map(DeployHashWithApprovals::from).collect(),
+        vec![],
+        true,
+    );
+    let finalized_block = FinalizedBlock::new(
+        block_payload,
+        None,
+        timestamp,
+        EraId::new(0),
+        0,
+        PublicKey::System,
+    );
+    let config = Config {
+        max_global_state_size: Some(100 * 1024 * 1024),
+        execution_threads: Some(execution_threads),
+        ..Config::default()
+    };
+    let storage_dir = tempfile::tempdir().unwrap();
+    let contract_runtime = ContractRuntime::new(
+        chainspec.protocol_version(),
+        storage_dir.path(),
+        &config,
+        chainspec.wasm_config,
+        chainspec.system_costs_config,
+        chainspec.core_config.max_associated_keys,
+        chainspec.core_config.max_runtime_call_stack_height,
+        chainspec.core_config.minimum_delegation_amount,
+        chainspec.protocol_config.activation_point,
+        chainspec.core_config.prune_batch_size,
+        chainspec.core_config.strict_argument_checking,
+        chainspec.core_config.vesting_schedule_period.millis(),
+        Some(chainspec.core_config.max_delegators_per_validator),
+        chainspec.core_config.max_reserved_delegators_per_validator,
+        &Registry::new(),
+        chainspec.core_config.administrators.clone(),
+        chainspec.core_config.allow_auction_bids,
+        chainspec.core_config.allow_unrestricted_transfers,
+        chainspec.core_config.refund_handling,
+        chainspec.core_config.fee_handling,
+        chainspec.core_config.equivocation_slashing_fraction,
+        chainspec.core_config.unavailability_slashing_fraction,
+        chainspec.core_config.inactivity_jail_threshold,
+        chainspec.core_config.jail_period_eras,
+        chainspec.core_config.max_jail_period_eras,
+        chainspec.core_config.allow_delegation_transfers,
+        chainspec.core_config.wasm_engine,
+    )
+    .unwrap();
+    let post_genesis_state_hash = contract_runtime
+        .commit_genesis(chainspec, chainspec_raw_bytes)
+        .unwrap()
+        .post_state_hash;
+
+    let metrics = Arc::new(Metrics::new(&Registry::new()).unwrap());
+    let block_and_execution_results = execute_finalized_block(
+        contract_runtime.engine_state.as_ref(),
+        Some(Arc::clone(&metrics)),
+        chainspec.protocol_version(),
+        ExecutionPreState::new(
+            0,
+            post_genesis_state_hash,
+            BlockHash::default(),
+            Digest::default(),
+        ),
+        finalized_block,
+        deploys.to_vec(),
+        chainspec.protocol_config.activation_point.era_id(),
+        0,
+        chainspec.core_config.prune_batch_size,
+        contract_runtime.execution_threads,
+    )
+    .unwrap();
+    (
+        block_and_execution_results,
+        metrics.reexecuted_deploys.get(),
+    )
+}
+
+#[test]
+fn parallel_execution_should_match_serial_execution() {
+    testing::init_logging();
+
+    let (chainspec, chainspec_raw_bytes) =
+        <(Chainspec, ChainspecRawBytes)>::from_resources("local");
+    let mut rng = crate::new_rng();
+    let deploys = transfer_deploys(&chainspec, &mut rng, 30);
+    let timestamp = Timestamp::now();
+
+    let (serial, _) =
+        execute_deploys_with_threads(&chainspec, &chainspec_raw_bytes, timestamp, &deploys, 1);
+    assert!(serial
+        .execution_results
+        .iter()
+        .all(|(_, _, result)| matches!(result, ExecutionResult::Success { .. })));
+
+    let (parallel, reexecuted) =
+        execute_deploys_with_threads(&chainspec, &chainspec_raw_bytes, timestamp, &deploys, 4);
+    assert_eq!(parallel.block, serial.block);
+    assert_eq!(parallel.execution_results, serial.execution_results);
+    // Only deploys from an account which already has a deploy earlier in the block need to be
+    // executed again.
+    assert!(reexecuted < deploys.len() as u64);
+}
+
+#[test]
+fn parallel_execution_should_reuse_results_of_independent_deploys() {
+    testing::init_logging();
+
+    let (chainspec, chainspec_raw_bytes) =
+        <(Chainspec, ChainspecRawBytes)>::from_resources("local");
+    let mut rng = crate::new_rng();
+    // One deploy per account, all paying the same proposer and one crediting another account.
+    let deploys = transfer_deploys(&chainspec, &mut rng, 3);
+    let timestamp = Timestamp::now();
+
+    let (serial, _) =
+        execute_deploys_with_threads(&chainspec, &chainspec_raw_bytes, timestamp, &deploys, 1);
+    let (parallel, reexecuted) =
+        execute_deploys_with_threads(&chainspec, &chainspec_raw_bytes, timestamp, &deploys, 4);
+    assert_eq!(parallel.block, serial.block);
+    assert_eq!(parallel.execution_results, serial.execution_results);
+    assert_eq!(reexecuted, 0);
+}
@@ -6,7 +6,9 @@ use casper_execution_engine::{
     core::engine_state::GetEraValidatorsRequest, shared::execution_journal::ExecutionJournal,
 };
 use casper_hashing::Digest;
-use casper_types::{EraId, ExecutionResult, ProtocolVersion, PublicKey, U512};
+use casper_types::{
+    system::mint::ScheduledTransferResult, EraId, ExecutionResult, ProtocolVersion, PublicKey, U512,
+};
 
 use crate::types::{ApprovalsHashes, Block, DeployHash, DeployHeader};
 
@@ -90,6 +92,8 @@ pub(crate) struct StepEffectAndUpcomingEraValidators {
     pub(crate) upcoming_era_validators: BTreeMap<EraId, BTreeMap<PublicKey, U512>>,
     /// An [`ExecutionJournal`] created by an era ending.
     pub(crate) step_execution_journal: ExecutionJournal,
+    /// The outcomes of the scheduled transfers run by the step.
+    pub(crate) scheduled_transfers: Vec<ScheduledTransferResult>,
 }
 
 #[doc(hidden)]
@@ -22,7 +22,7 @@ use casper_execution_engine::core::engine_state::{
 use casper_hashing::Digest;
 use casper_types::{
     account::{Account, AccountHash},
-    system::auction::ARG_AMOUNT,
+    system::{auction::ARG_AMOUNT, handle_payment::ARG_SPONSOR},
     Contract, ContractHash, ContractPackage, ContractPackageHash, ContractVersion,
     ContractVersionKey, Key, ProtocolVersion, Timestamp, U512,
 };
@@ -144,6 +144,9 @@ pub(crate) enum DeployParameterFailure {
     /// Module bytes for session code cannot be empty.
     #[error("module bytes for session code cannot be empty")]
     MissingModuleBytes,
+    /// Failed to parse payment "sponsor" runtime argument.
+    #[error("failed to parse payment 'sponsor' runtime argument as an account or contract key")]
+    FailedToParsePaymentSponsor,
 }
 
 /// A helper trait constraining `DeployAcceptor` compatible reactor events.
@@ -380,6 +383,41 @@ impl DeployAcceptor {
                 )
             }
             Some(account) => {
+                let maybe_sponsor_account_hash =
+                    match sponsoring_account_hash(event_metadata.deploy.payment()) {
+                        Ok(maybe_sponsor_account_hash) => maybe_sponsor_account_hash,
+                        Err(failure) => {
+                            let error = Error::parameter_failure(&block_header, failure);
+                            debug!("failed to parse payment sponsor");
+                            return self.handle_invalid_deploy_result(
+                                effect_builder,
+                                event_metadata,
+                                error,
+                                verification_start_timestamp,
+                            );
+                        }
+                    };
+
+                // A sponsoring account co-signs the deploy and pays for it, so the approvals are
+                // checked against both accounts once the sponsoring account has been read.
+                if let Some(sponsor_account_hash) = maybe_sponsor_account_hash {
+                    return effect_builder
+                        .get_account_from_global_state(
+                            *block_header.state_root_hash(),
+                            Key::from(sponsor_account_hash),
+                        )
+                        .event(
+                            move |maybe_sponsor_account| Event::GetSponsorAccountResult {
+                                event_metadata,
+                                block_header,
+                                account: Box::new(account),
+                                sponsor_account_hash,
+                                maybe_sponsor_account,
+                                verification_start_timestamp,
+                            },
+                        );
+                }
+
                 let authorization_keys = event_metadata
                     .deploy
                     .approvals()
@@ -387,31 +425,9 @@ impl DeployAcceptor {
                     .map(|approval| approval.signer().to_account_hash())
                     .collect();
 
-                let admin_set: BTreeSet<AccountHash> = {
-                    self.core_config
-                        .administrators
-                        .iter()
-                        .map(|public_key| public_key.to_account_hash())
-                        .collect()
-                };
-                if admin_set.intersection(&authorization_keys).next().is_some() {
-                    return effect_builder
-                        .check_purse_balance(*block_header.state_root_hash(), account.main_purse())
-                        .event(move |maybe_balance_value| Event::GetBalanceResult {
-                            event_metadata,
-                            maybe_balance_value,
-                            account_hash: account.account_hash(),
-                            verification_start_timestamp,
-                            block_header,
-                        });
-                }
-
-                if !account.can_authorize(&authorization_keys) {
-                    let error = Error::parameter_failure(
-                        &block_header,
-                        DeployParameterFailure::InvalidAssociatedKeys,
-                    );
-                    debug!(?authorization_keys, "account authorization invalid");
+                if let Err(failure) = self.check_authorization(&account, &authorization_keys) {
+                    debug!(?authorization_keys, %failure, "account authorization invalid");
+                    let error = Error::parameter_failure(&block_header, failure);
                     return self.handle_invalid_deploy_result(
                         effect_builder,
                         event_metadata,
@@ -420,19 +436,6 @@ impl DeployAcceptor {
                     );
                 }
 
-                if !account.can_deploy_with(&authorization_keys) {
-                    let error = Error::parameter_failure(
-                        &block_header,
-                        DeployParameterFailure::InsufficientDeploySignatureWeight,
-                    );
-                    debug!(?authorization_keys, "insufficient deploy signature weight");
-                    return self.handle_invalid_deploy_result(
-                        effect_builder,
-                        event_metadata,
-                        error,
-                        verification_start_timestamp,
-                    );
-                }
                 effect_builder
                     .check_purse_balance(*block_header.state_root_hash(), account.main_purse())
                     .event(move |maybe_balance_value| Event::GetBalanceResult {
@@ -446,6 +449,149 @@ impl DeployAcceptor {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
+    fn handle_get_sponsor_account_result<REv: ReactorEventT>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        event_metadata: Box<EventMetadata>,
+        block_header: Box<BlockHeader>,
+        account: Box<Account>,
+        sponsor_account_hash: AccountHash,
+        maybe_sponsor_account: Option<Account>,
+        verification_start_timestamp: Timestamp,
+    ) -> Effects<Event> {
+        let sponsor_account = match maybe_sponsor_account {
+            Some(sponsor_account) => sponsor_account,
+            None => {
+                let error = Error::parameter_failure(
+                    &block_header,
+                    DeployParameterFailure::NonexistentAccount {
+                        account_hash: sponsor_account_hash,
+                    },
+                );
+                debug!(
+                    ?sponsor_account_hash,
+                    "nonexistent sponsoring account associated with the deploy"
+                );
+                return self.handle_invalid_deploy_result(
+                    effect_builder,
+                    event_metadata,
+                    error,
+                    verification_start_timestamp,
+                );
+            }
+        };
+
+        let authorization_keys = event_metadata
+            .deploy
+            .approvals()
+            .iter()
+            .map(|approval| approval.signer().to_account_hash())
+            .collect();
+
+        let authorization_result = self
+            .split_authorization_keys(&account, &sponsor_account, &authorization_keys)
+            .and_then(|(account_keys, sponsor_keys)| {
+                self.check_authorization(&account, &account_keys)?;
+                self.check_authorization(&sponsor_account, &sponsor_keys)
+            });
+        if let Err(failure) = authorization_result {
+            debug!(
+                ?authorization_keys,
+                %failure,
+                "account or sponsoring account authorization invalid"
+            );
+            let error = Error::parameter_failure(&block_header, failure);
+            return self.handle_invalid_deploy_result(
+                effect_builder,
+                event_metadata,
+                error,
+                verification_start_timestamp,
+            );
+        }
+
+        effect_builder
+            .check_purse_balance(
+                *block_header.state_root_hash(),
+                sponsor_account.main_purse(),
+            )
+            .event(move |maybe_balance_value| Event::GetBalanceResult {
+                event_metadata,
+                block_header,
+                maybe_balance_value,
+                account_hash: sponsor_account_hash,
+                verification_start_timestamp,
+            })
+    }
+
+    /// Checks that the given keys authorize a deploy on behalf of the account, as the execution
+    /// engine does.
+    fn check_authorization(
+        &self,
+        account: &Account,
+        authorization_keys: &BTreeSet<AccountHash>,
+    ) -> Result<(), DeployParameterFailure> {
+        let admin_set: BTreeSet<AccountHash> = self
+            .core_config
+            .administrators
+            .iter()
+            .map(|public_key| public_key.to_account_hash())
+            .collect();
+        if admin_set.intersection(authorization_keys).next().is_some() {
+            return Ok(());
+        }
+
+        if !account.can_authorize(authorization_keys) {
+            return Err(DeployParameterFailure::InvalidAssociatedKeys);
+        }
+
+        if !account.can_deploy_with(authorization_keys) {
+            return Err(DeployParameterFailure::InsufficientDeploySignatureWeight);
+        }
+
+        Ok(())
+    }
+
+    /// Splits the keys approving a sponsored deploy between the deploy's account and its
+    /// sponsoring account, as the execution engine does.
+    ///
+    /// Each account is authorized only by the keys associated with it, and every key must be
+    /// associated with at least one of the two accounts.  Administrator keys authorize both.
+    fn split_authorization_keys(
+        &self,
+        account: &Account,
+        sponsor_account: &Account,
+        authorization_keys: &BTreeSet<AccountHash>,
+    ) -> Result<(BTreeSet<AccountHash>, BTreeSet<AccountHash>), DeployParameterFailure> {
+        let admin_set: BTreeSet<AccountHash> = self
+            .core_config
+            .administrators
+            .iter()
+            .map(|public_key| public_key.to_account_hash())
+            .collect();
+
+        let mut account_keys = BTreeSet::new();
+        let mut sponsor_keys = BTreeSet::new();
+        for authorization_key in authorization_keys {
+            let is_admin = admin_set.contains(authorization_key);
+            let is_account_key = account.associated_keys().contains_key(authorization_key);
+            let is_sponsor_key = sponsor_account
+                .associated_keys()
+                .contains_key(authorization_key);
+            if !is_admin && !is_account_key && !is_sponsor_key {
+                return Err(DeployParameterFailure::InvalidAssociatedKeys);
+            }
+            if is_admin || is_account_key {
+                account_keys.insert(*authorization_key);
+            }
+            if is_admin || is_sponsor_key {
+                sponsor_keys.insert(*authorization_key);
+            }
+        }
+
+        Ok((account_keys, sponsor_keys))
+    }
+
     fn handle_get_balance_result<REv: ReactorEventT>(
         &self,
         effect_builder: EffectBuilder<REv>,
@@ -1032,6 +1178,28 @@ impl DeployAcceptor {
     }
 }
 
+/// Returns the account paying for a deploy in place of its account, if its standard payment names
+/// one through the `sponsor` argument.
+///
+/// A deploy sponsored by a contract is still checked against its own account, which remains liable
+/// for the penalty of a failed payment.
+fn sponsoring_account_hash(
+    payment: &ExecutableDeployItem,
+) -> Result<Option<AccountHash>, DeployParameterFailure> {
+    let maybe_sponsor = match payment {
+        ExecutableDeployItem::ModuleBytes { module_bytes, args } if module_bytes.is_empty() => {
+            args.get(ARG_SPONSOR)
+        }
+        _ => None,
+    };
+    match maybe_sponsor.map(|cl_value| cl_value.clone().into_t::<Key>()) {
+        None => Ok(None),
+        Some(Ok(Key::Account(sponsor_account_hash))) => Ok(Some(sponsor_account_hash)),
+        Some(Ok(Key::Hash(_))) => Ok(None),
+        Some(Ok(_)) | Some(Err(_)) => Err(DeployParameterFailure::FailedToParsePaymentSponsor),
+    }
+}
+
 impl<REv: ReactorEventT> Component<REv> for DeployAcceptor {
     type Event = Event;
 
@@ -1070,6 +1238,22 @@ impl<REv: ReactorEventT> Component<REv> for DeployAcceptor {
                 maybe_account,
                 verification_start_timestamp,
             ),
+            Event::GetSponsorAccountResult {
+                event_metadata,
+                block_header,
+                account,
+                sponsor_account_hash,
+                maybe_sponsor_account,
+                verification_start_timestamp,
+            } => self.handle_get_sponsor_account_result(
+                effect_builder,
+                event_metadata,
+                block_header,
+                account,
+                sponsor_account_hash,
+                maybe_sponsor_account,
+                verification_start_timestamp,
+            ),
             Event::GetBalanceResult {
                 event_metadata,
                 block_header,
@@ -74,6 +74,15 @@ pub(crate) enum Event {
         maybe_account: Option<Account>,
         verification_start_timestamp: Timestamp,
     },
+    /// The result of querying global state for the `Account` sponsoring the `Deploy`.
+    GetSponsorAccountResult {
+        event_metadata: Box<EventMetadata>,
+        block_header: Box<BlockHeader>,
+        account: Box<Account>,
+        sponsor_account_hash: AccountHash,
+        maybe_sponsor_account: Option<Account>,
+        verification_start_timestamp: Timestamp,
+    },
     /// The result of querying the balance of the `Account` associated with the `Deploy`.
     GetBalanceResult {
         event_metadata: Box<EventMetadata>,
@@ -161,6 +170,13 @@ impl Display for Event {
                     event_metadata.deploy.hash()
                 )
             }
+            Event::GetSponsorAccountResult { event_metadata, .. } => {
+                write!(
+                    formatter,
+                    "verifying sponsoring account to validate deploy with hash {}",
+                    event_metadata.deploy.hash()
+                )
+            }
             Event::GetBalanceResult { event_metadata, .. } => {
                 write!(
                     formatter,
@@ -27,7 +27,7 @@ use casper_types::{
     account::{Account, ActionThresholds, AssociatedKeys, Weight},
     contracts::NamedKeys,
     testing::TestRng,
-    CLValue, EraId, PublicKey, SecretKey, StoredValue, TimeDiff, URef, U512,
+    AccessRights, CLValue, EraId, PublicKey, SecretKey, StoredValue, TimeDiff, URef, U512,
 };
 
 use super::*;
@@ -57,6 +57,8 @@ const POLL_INTERVAL: Duration = Duration::from_millis(10);
 const TIMEOUT: Duration = Duration::from_secs(10);
 const ALICE_SECRET_KEY_BYTES: [u8; 32] = [123; 32];
 const BOB_SECRET_KEY_BYTES: [u8; 32] = [124; 32];
+const SPONSOR_SECRET_KEY_BYTES: [u8; 32] = [125; 32];
+const SPONSOR_PURSE: URef = URef::new([125; 32], AccessRights::READ_ADD_WRITE);
 
 /// Top-level event for the reactor.
 #[derive(Debug, From, Serialize)]
@@ -152,6 +154,13 @@ enum ContractPackageScenario {
     MissingContractVersion,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum SponsorScenario {
+    Valid,
+    MissingSponsorApproval,
+    InsufficientSponsorBalance,
+}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 enum TestScenario {
     FromPeerInvalidDeploy,
@@ -178,6 +187,7 @@ enum TestScenario {
     FromClientCustomPaymentContractPackage(ContractPackageScenario),
     FromClientSessionContract(ContractScenario),
     FromClientSessionContractPackage(ContractPackageScenario),
+    FromClientSponsoredDeploy(SponsorScenario),
     DeployWithNativeTransferInPayment,
     DeployWithEmptySessionModuleBytes,
     DeployWithoutPaymentAmount,
@@ -234,6 +244,7 @@ impl TestScenario {
             | TestScenario::FromClientCustomPaymentContractPackage(_)
             | TestScenario::FromClientSessionContract(_)
             | TestScenario::FromClientSessionContractPackage(_)
+            | TestScenario::FromClientSponsoredDeploy(_)
             | TestScenario::DeployWithEmptySessionModuleBytes
             | TestScenario::DeployWithNativeTransferInPayment
             | TestScenario::ShouldNotAcceptExpiredDeploySentByClient
@@ -335,6 +346,16 @@ impl TestScenario {
                     }
                 }
             }
+            TestScenario::FromClientSponsoredDeploy(sponsor_scenario) => {
+                let sponsor = sponsor_secret_key();
+                let sponsor_account_hash = PublicKey::from(&sponsor).to_account_hash();
+                let mut deploy =
+                    Deploy::random_with_payment_sponsor(rng, Key::from(sponsor_account_hash));
+                if *sponsor_scenario != SponsorScenario::MissingSponsorApproval {
+                    deploy.sign(&sponsor);
+                }
+                deploy
+            }
             TestScenario::DeployWithEmptySessionModuleBytes => {
                 Deploy::random_with_empty_session_module_bytes(rng)
             }
@@ -422,6 +443,11 @@ impl TestScenario {
                     | ContractPackageScenario::MissingContractVersion => false,
                 }
             }
+            TestScenario::FromClientSponsoredDeploy(sponsor_scenario) => match sponsor_scenario {
+                SponsorScenario::Valid => true,
+                SponsorScenario::MissingSponsorApproval
+                | SponsorScenario::InsufficientSponsorBalance => false,
+            },
             TestScenario::ShouldAcceptDeployFromAdministrator { .. } => true,
             TestScenario::ShouldRejectDeployFromNonAdministrator { .. } => false
         }
@@ -461,8 +487,17 @@ impl TestScenario {
     }
 }
 
+fn sponsor_secret_key() -> SecretKey {
+    SecretKey::ed25519_from_bytes(SPONSOR_SECRET_KEY_BYTES).expect("should create sponsor")
+}
+
 fn create_account(account_hash: AccountHash, test_scenario: &TestScenario) -> Account {
     match test_scenario {
+        TestScenario::FromClientSponsoredDeploy(_)
+            if account_hash == PublicKey::from(&sponsor_secret_key()).to_account_hash() =>
+        {
+            Account::create(account_hash, NamedKeys::default(), SPONSOR_PURSE)
+        }
         TestScenario::FromPeerAccountWithInvalidAssociatedKeys
         | TestScenario::FromClientAccountWithInvalidAssociatedKeys => Account::create(
             AccountHash::default(),
@@ -690,11 +725,19 @@ impl reactor::Reactor for Reactor {
                         StoredValue::CLValue(CLValue::from_t(()).expect("should get CLValue")),
                         VecDeque::new(),
                     );
-                    let motes = if self.test_scenario == TestScenario::FromClientInsufficientBalance
-                    {
-                        MAX_PAYMENT_AMOUNT - 1
-                    } else {
-                        MAX_PAYMENT_AMOUNT
+                    let motes = match self.test_scenario {
+                        TestScenario::FromClientInsufficientBalance => MAX_PAYMENT_AMOUNT - 1,
+                        // Only the sponsoring account can afford the payment.
+                        TestScenario::FromClientSponsoredDeploy(sponsor_scenario) => {
+                            if balance_request.purse_uref() == SPONSOR_PURSE
+                                && sponsor_scenario != SponsorScenario::InsufficientSponsorBalance
+                            {
+                                MAX_PAYMENT_AMOUNT
+                            } else {
+                                MAX_PAYMENT_AMOUNT - 1
+                            }
+                        }
+                        _ => MAX_PAYMENT_AMOUNT,
                     };
                     let balance_result =
                         if self.test_scenario == TestScenario::AccountWithUnknownBalance {
@@ -1015,6 +1058,27 @@ async fn run_deploy_acceptor_without_timeout(
                 event,
                 Event::DeployAcceptor(super::Event::StoredFinalizedApprovals { is_new: false, .. })
             ),
+            // Check that a deploy sponsored by an account is accepted only when the sponsoring
+            // account approves it and can pay for it.
+            TestScenario::FromClientSponsoredDeploy(sponsor_scenario) => match sponsor_scenario {
+                SponsorScenario::Valid => matches!(
+                    event,
+                    Event::DeployAcceptorAnnouncement(
+                        DeployAcceptorAnnouncement::AcceptedNewDeploy {
+                            source: Source::Client,
+                            ..
+                        }
+                    )
+                ),
+                SponsorScenario::MissingSponsorApproval
+                | SponsorScenario::InsufficientSponsorBalance => matches!(
+                    event,
+                    Event::DeployAcceptorAnnouncement(DeployAcceptorAnnouncement::InvalidDeploy {
+                        source: Source::Client,
+                        ..
+                    })
+                ),
+            },
             TestScenario::ShouldAcceptDeployFromAdministrator { .. } => {
                 matches!(
                     event,
@@ -1574,6 +1638,42 @@ async fn should_reject_deploy_with_missing_version_in_session_contract_package_f
     ))
 }
 
+#[tokio::test]
+async fn should_accept_deploy_co_signed_by_sponsoring_account_from_client() {
+    let test_scenario = TestScenario::FromClientSponsoredDeploy(SponsorScenario::Valid);
+    let result = run_deploy_acceptor(test_scenario).await;
+    assert!(result.is_ok())
+}
+
+#[tokio::test]
+async fn should_reject_deploy_not_signed_by_sponsoring_account_from_client() {
+    let test_scenario =
+        TestScenario::FromClientSponsoredDeploy(SponsorScenario::MissingSponsorApproval);
+    let result = run_deploy_acceptor(test_scenario).await;
+    assert!(matches!(
+        result,
+        Err(super::Error::InvalidDeployParameters {
+            failure: DeployParameterFailure::InvalidAssociatedKeys,
+            ..
+        })
+    ))
+}
+
+#[tokio::test]
+async fn should_reject_deploy_from_client_for_sponsoring_account_with_insufficient_balance() {
+    let test_scenario =
+        TestScenario::FromClientSponsoredDeploy(SponsorScenario::InsufficientSponsorBalance);
+    let result = run_deploy_acceptor(test_scenario).await;
+    let sponsor_account_hash = PublicKey::from(&sponsor_secret_key()).to_account_hash();
+    assert!(matches!(
+        result,
+        Err(super::Error::InvalidDeployParameters {
+            failure: DeployParameterFailure::InsufficientBalance { account_hash },
+            ..
+        }) if account_hash == sponsor_account_hash
+    ))
+}
+
 #[tokio::test]
 async fn should_reject_deploy_with_empty_module_bytes_in_session() {
     let test_scenario = TestScenario::DeployWithEmptySessionModuleBytes;
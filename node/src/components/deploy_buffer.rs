@@ -1,11 +1,12 @@
 mod config;
 mod event;
 mod metrics;
+mod priority;
 #[cfg(test)]
 mod tests;
 
 use std::{
-    collections::{btree_map, BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    collections::{btree_map, BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet},
     convert::TryInto,
     iter::FromIterator,
     mem,
@@ -18,12 +19,12 @@ use prometheus::Registry;
 use smallvec::smallvec;
 use tracing::{debug, error, info, warn};
 
-use casper_hashing::Digest;
-use casper_types::Timestamp;
+use casper_types::{PublicKey, Timestamp};
 
 use crate::{
     components::{
         consensus::{ClContext, ProposedBlock},
+        gossiper::GossipItem,
         Component, ComponentState, InitializedComponent,
     },
     effect::{
@@ -37,8 +38,8 @@ use crate::{
     types::{
         appendable_block::{AddError, AppendableBlock},
         chainspec::DeployConfig,
-        Approval, Block, Deploy, DeployFootprint, DeployHash, DeployHashWithApprovals, DeployId,
-        FinalizedBlock,
+        Approval, ApprovalsHash, Block, Deploy, DeployFootprint, DeployHash,
+        DeployHashWithApprovals, DeployId, FinalizedBlock,
     },
     utils::DisplayIter,
     NodeRng,
@@ -47,6 +48,7 @@ pub(crate) use config::Config;
 pub(crate) use event::Event;
 
 use metrics::Metrics;
+use priority::{Candidate, Priority};
 
 const COMPONENT_NAME: &str = "deploy_buffer";
 
@@ -73,6 +75,18 @@ pub(crate) struct DeployBuffer {
     hold: BTreeMap<Timestamp, HashSet<DeployHash>>,
     // deploy_hashes that should not be proposed, ever
     dead: HashSet<DeployHash>,
+    // the deploy occupying each account's slot at a given deploy timestamp: a deploy in the same
+    // slot with a higher gas price supersedes it, and once one of them is executed, the others
+    // are superseded
+    slots: HashMap<(PublicKey, Timestamp), DeployHash>,
+    // deploys this node will not propose as another deploy took their slot
+    //
+    // Block validation only ensures that at most one deploy per slot is executed, not which one:
+    // until one of them is, a superseded deploy may still be proposed by other nodes and executed.
+    // Unlike `dead`, this therefore doesn't mean the deploy will never be executed.
+    superseded: HashSet<DeployHash>,
+    // when each buffered deploy was first received, from which its priority ages
+    received: HashMap<DeployHash, Timestamp>,
     // deploy buffer metrics
     #[data_size(skip)]
     metrics: Metrics,
@@ -92,6 +106,9 @@ impl DeployBuffer {
             buffer: HashMap::new(),
             hold: BTreeMap::new(),
             dead: HashSet::new(),
+            slots: HashMap::new(),
+            superseded: HashSet::new(),
+            received: HashMap::new(),
             metrics: Metrics::new(registry)?,
         })
     }
@@ -173,6 +190,12 @@ impl DeployBuffer {
         self.dead
             .retain(|deploy_hash| freed.remove(deploy_hash).is_none());
         self.buffer = buffer;
+        self.slots
+            .retain(|_, deploy_hash| self.buffer.contains_key(deploy_hash));
+        self.superseded
+            .retain(|deploy_hash| self.buffer.contains_key(deploy_hash));
+        self.received
+            .retain(|deploy_hash, _| self.buffer.contains_key(deploy_hash));
 
         if !freed.is_empty() {
             info!(
@@ -212,21 +235,63 @@ impl DeployBuffer {
     }
 
     /// Update buffer considering new stored deploy.
-    fn register_deploy(&mut self, deploy: Deploy) {
+    ///
+    /// Returns the ID of the deploy superseded as a result, if any: either a pending deploy from
+    /// the same account with the same timestamp which this one outbids, or this deploy itself if
+    /// it doesn't.
+    ///
+    /// Supersession is best-effort: this node won't propose the superseded deploy, but other
+    /// nodes may still do so until a deploy in its slot is executed.
+    fn register_deploy(&mut self, deploy: Deploy) -> Option<DeployId> {
         let deploy_hash = deploy.hash();
         if self.dead.contains(deploy_hash) {
             info!(%deploy_hash, "DeployBuffer: attempt to register already dead deploy");
-            return;
+            return None;
+        }
+        if self.superseded.contains(deploy_hash) {
+            info!(%deploy_hash, "DeployBuffer: attempt to register already superseded deploy");
+            return None;
         }
         if self.hold.values().any(|dhs| dhs.contains(deploy_hash)) {
             info!(%deploy_hash, "DeployBuffer: attempt to register already held deploy");
-            return;
+            return None;
         }
         let footprint = match deploy.footprint() {
             Ok(footprint) => footprint,
             Err(err) => {
                 error!(%deploy_hash, %err, "DeployBuffer: deploy footprint exceeds tolerances");
-                return;
+                return None;
+            }
+        };
+        let slot = (
+            deploy.header().account().clone(),
+            deploy.header().timestamp(),
+        );
+        let superseded = match self.slots.get(&slot) {
+            Some(pending) if pending != deploy_hash => {
+                if self.outbids(&footprint, deploy_hash, pending) {
+                    Some(*pending)
+                } else {
+                    Some(*deploy_hash)
+                }
+            }
+            _ => None,
+        };
+        let superseded_id = match superseded {
+            Some(superseded) if superseded == *deploy_hash => {
+                info!(%deploy_hash, "DeployBuffer: deploy superseded by pending deploy");
+                self.superseded.insert(superseded);
+                Some(deploy.gossip_id())
+            }
+            Some(superseded) => {
+                info!(%superseded, %deploy_hash, "DeployBuffer: pending deploy superseded");
+                self.superseded.insert(superseded);
+                self.slots.insert(slot, *deploy_hash);
+                self.deploy_id(&superseded)
+            }
+            None => {
+                self.slots.insert(slot, *deploy_hash);
+                None
             }
         };
         let expiry_time = deploy.header().expires();
@@ -243,6 +308,53 @@ impl DeployBuffer {
                 self.metrics.total_deploys.inc();
             }
         }
+        self.received
+            .entry(*deploy_hash)
+            .or_insert_with(Timestamp::now);
+        if superseded.is_some() {
+            self.update_all_metrics();
+        }
+        superseded_id
+    }
+
+    /// Returns `true` if the deploy with the given footprint and hash supersedes the `pending`
+    /// one in its slot.
+    ///
+    /// A deploy only supersedes a pending deploy which is not dead, i.e. not executed, and only if
+    /// it has a higher gas price, with ties going to the lower deploy hash so that all nodes agree
+    /// on the winner regardless of the order in which they receive the deploys.
+    fn outbids(
+        &self,
+        footprint: &DeployFootprint,
+        deploy_hash: &DeployHash,
+        pending: &DeployHash,
+    ) -> bool {
+        if self.dead.contains(pending) {
+            return false;
+        }
+        match self.buffer.get(pending) {
+            Some((_, Some((pending_footprint, _)))) => {
+                let gas_price = footprint.header.gas_price();
+                let pending_gas_price = pending_footprint.header.gas_price();
+                gas_price > pending_gas_price
+                    || (gas_price == pending_gas_price && deploy_hash < pending)
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the ID of the given buffered deploy, if its approvals are known.
+    fn deploy_id(&self, deploy_hash: &DeployHash) -> Option<DeployId> {
+        let (_, Some((_, approvals))) = self.buffer.get(deploy_hash)? else {
+            return None;
+        };
+        match ApprovalsHash::compute(approvals) {
+            Ok(approvals_hash) => Some(DeployId::new(*deploy_hash, approvals_hash)),
+            Err(error) => {
+                error!(%deploy_hash, %error, "DeployBuffer: failed to serialize approvals");
+                None
+            }
+        }
     }
 
     /// Update holds considering new proposed block.
@@ -276,9 +388,32 @@ impl DeployBuffer {
         let expiry_timestamp = timestamp.saturating_add(self.deploy_config.max_ttl);
 
         for deploy_hash in deploy_hashes {
-            if !self.buffer.contains_key(deploy_hash) {
-                self.buffer.insert(*deploy_hash, (expiry_timestamp, None));
+            match self.buffer.get(deploy_hash) {
+                None => {
+                    self.buffer.insert(*deploy_hash, (expiry_timestamp, None));
+                }
+                Some((_, Some((footprint, _)))) => {
+                    // the executed deploy takes its slot; whichever deploy occupied it is
+                    // superseded
+                    let slot = (
+                        footprint.header.account().clone(),
+                        footprint.header.timestamp(),
+                    );
+                    if let Some(pending) = self.slots.insert(slot, *deploy_hash) {
+                        if pending != *deploy_hash {
+                            info!(
+                                %pending,
+                                %deploy_hash,
+                                "DeployBuffer: pending deploy superseded by executed deploy"
+                            );
+                            self.superseded.insert(pending);
+                        }
+                    }
+                }
+                Some((_, None)) => {}
             }
+            // a superseded deploy proposed by another node was executed after all
+            self.superseded.remove(deploy_hash);
             self.dead.insert(*deploy_hash);
         }
         // deploys held for proposed blocks which did not get finalized in time are eligible again
@@ -305,13 +440,28 @@ impl DeployBuffer {
         self.register_deploys(timestamp, finalized_block.deploy_and_transfer_hashes());
     }
 
-    /// Returns eligible deploys that are buffered and not held or dead.
+    /// Returns eligible deploys that are buffered and not held, dead or superseded.
+    ///
+    /// Deploys whose slot is taken by a held deploy are not eligible either: a block containing
+    /// one would be invalid if built on the block holding the other.
     fn proposable(&self) -> Vec<(DeployHashWithApprovals, DeployFootprint)> {
         debug!("DeployBuffer: getting proposable deploys");
+        let held_slots: HashSet<(&PublicKey, Timestamp)> = self
+            .hold
+            .values()
+            .flatten()
+            .filter_map(|dh| match self.buffer.get(dh) {
+                Some((_, Some((footprint, _)))) => {
+                    Some((footprint.header.account(), footprint.header.timestamp()))
+                }
+                _ => None,
+            })
+            .collect();
         self.buffer
             .iter()
             .filter(|(dh, _)| !self.hold.values().any(|hs| hs.contains(dh)))
             .filter(|(dh, _)| !self.dead.contains(dh))
+            .filter(|(dh, _)| !self.superseded.contains(dh))
             .filter_map(|(dh, (_, maybe_data))| {
                 maybe_data.as_ref().map(|(footprint, approvals)| {
                     (
@@ -320,30 +470,61 @@ impl DeployBuffer {
                     )
                 })
             })
+            .filter(|(_, footprint)| {
+                !held_slots.contains(&(footprint.header.account(), footprint.header.timestamp()))
+            })
+            .collect()
+    }
+
+    /// Returns the dependencies of all proposable deploys.
+    ///
+    /// Whether these have been executed is looked up in storage before a block is proposed, the
+    /// same way the block validator checks them.
+    fn proposable_dependencies(&self) -> HashSet<DeployHash> {
+        self.proposable()
+            .iter()
+            .flat_map(|(_, footprint)| footprint.header.dependencies().iter().copied())
             .collect()
     }
 
-    fn buckets(&mut self) -> HashMap<Digest, Vec<(DeployHashWithApprovals, DeployFootprint)>> {
+    /// Returns the proposable deploys grouped by account, each group sorted by ascending
+    /// priority.
+    fn buckets(&mut self, now: Timestamp) -> HashMap<PublicKey, Vec<Candidate>> {
         let proposable = self.proposable();
+        let aging_interval = self.cfg.priority_aging_interval();
 
-        let mut buckets: HashMap<Digest, Vec<(DeployHashWithApprovals, DeployFootprint)>> =
-            HashMap::new();
+        let mut buckets: HashMap<PublicKey, Vec<Candidate>> = HashMap::new();
 
         for (with_approvals, footprint) in proposable {
-            let body_hash = *footprint.header.body_hash();
-            buckets
-                .entry(body_hash)
-                .and_modify(|vec| vec.push((with_approvals.clone(), footprint.clone())))
-                .or_insert(vec![(with_approvals, footprint)]);
+            let account = footprint.header.account().clone();
+            let received = self
+                .received
+                .get(with_approvals.deploy_hash())
+                .copied()
+                .unwrap_or(now);
+            let candidate =
+                Candidate::new(with_approvals, footprint, received, aging_interval, now);
+            buckets.entry(account).or_default().push(candidate);
+        }
+        for bucket in buckets.values_mut() {
+            bucket.sort_unstable_by_key(|candidate| candidate.priority(0));
         }
         buckets
     }
 
     /// Returns a right-sized payload of deploys that can be proposed.
+    ///
+    /// Deploys are added in order of priority: the highest-scoring deploy of each account competes
+    /// with those of all other accounts, its score divided by one more than the number of deploys
+    /// the account already has in the block.
+    ///
+    /// A deploy is only added once each of its dependencies is either in `executed_dependencies`,
+    /// i.e. has been executed in a block held in storage, or has been added to the block earlier.
     fn appendable_block(
         &mut self,
         timestamp: Timestamp,
         request_expiry: Timestamp,
+        executed_dependencies: &HashSet<DeployHash>,
     ) -> AppendableBlock {
         let mut ret = AppendableBlock::new(self.deploy_config, timestamp);
         if Timestamp::now() >= request_expiry {
@@ -353,15 +534,37 @@ impl DeployBuffer {
         let mut have_hit_transfer_limit = false;
         let mut have_hit_deploy_limit = false;
 
-        let mut buckets = self.buckets();
-        let mut body_hashes_queue: VecDeque<_> = buckets.keys().cloned().collect();
+        let mut buckets = self.buckets(Timestamp::now());
+        let gas_prices: Vec<_> = buckets
+            .values()
+            .flatten()
+            .map(|candidate| {
+                (
+                    *candidate.with_approvals.deploy_hash(),
+                    candidate.footprint.header.gas_price(),
+                )
+            })
+            .collect();
+        // the best deploy of each account, keyed by its priority
+        let mut queue: BinaryHeap<(Priority, PublicKey)> = buckets
+            .iter()
+            .filter_map(|(account, bucket)| {
+                let candidate = bucket.last()?;
+                Some((candidate.priority(0), account.clone()))
+            })
+            .collect();
+        // the number of deploys from each account added to the block so far
+        let mut account_deploys: HashMap<PublicKey, u64> = HashMap::new();
+        // Deploys which can't be added yet as they depend on a deploy which has neither been
+        // executed in an earlier block nor added to this one, keyed by that dependency.
+        let mut awaiting_dependency: HashMap<DeployHash, Vec<Candidate>> = HashMap::new();
 
         #[cfg(test)]
         let mut iter_counter = 0;
         #[cfg(test)]
         let iter_limit = self.buffer.len() * 4;
 
-        while let Some(body_hash) = body_hashes_queue.pop_front() {
+        while let Some((_, account)) = queue.pop() {
             if Timestamp::now() > request_expiry {
                 break;
             }
@@ -374,92 +577,155 @@ impl DeployBuffer {
                 );
             }
 
-            let Some((with_approvals, footprint)) =
-                buckets.get_mut(&body_hash).and_then(Vec::<_>::pop)
-            else {
+            let Some(candidate) = buckets.get_mut(&account).and_then(Vec::pop) else {
                 continue;
             };
-            // bucket wasn't empty - push the hash back into the queue to be processed again on the
-            // next pass
-            body_hashes_queue.push_back(body_hash);
-            if footprint.is_transfer && have_hit_transfer_limit {
-                continue;
-            }
-            if !footprint.is_transfer && have_hit_deploy_limit {
-                continue;
-            }
-            let deploy_hash = *with_approvals.deploy_hash();
-            let has_multiple_approvals = with_approvals.approvals().len() > 1;
-            match ret.add(with_approvals, &footprint) {
-                Ok(_) => {
-                    debug!(%deploy_hash, "DeployBuffer: proposing deploy");
-                    holds.insert(deploy_hash);
-                }
-                Err(error) => {
-                    match error {
-                        AddError::Duplicate => {
-                            // it should be physically impossible for a duplicate deploy to
-                            // be in the deploy buffer, thus this should be unreachable
-                            error!(
-                                ?deploy_hash,
-                                "DeployBuffer: duplicated deploy in deploy buffer"
-                            );
-                            self.dead.insert(deploy_hash);
-                        }
-                        AddError::Expired => {
-                            info!(
-                                ?deploy_hash,
-                                "DeployBuffer: expired deploy in deploy buffer"
-                            );
-                            self.dead.insert(deploy_hash);
-                        }
-                        AddError::InvalidDeploy => {
-                            // It should not generally be possible for an invalid deploy to get
-                            // buffered in the first place, thus this should be unreachable.  There
-                            // is a small potential for a slightly future-dated deploy to be
-                            // accepted (if within `timestamp_leeway`) and still be future-dated by
-                            // the time we try and add it to a proposed block here.
-                            warn!(
-                                ?deploy_hash,
-                                "DeployBuffer: invalid deploy in deploy buffer"
-                            );
-                            self.dead.insert(deploy_hash);
+            let footprint = &candidate.footprint;
+            let deploy_hash = *candidate.with_approvals.deploy_hash();
+            let awaited_dependency = footprint.header.dependencies().iter().find(|dependency| {
+                !executed_dependencies.contains(dependency)
+                    && !ret.executes_before(dependency, footprint.is_transfer)
+            });
+            if (footprint.is_transfer && have_hit_transfer_limit)
+                || (!footprint.is_transfer && have_hit_deploy_limit)
+            {
+                // the deploy can't be added - move on to the account's next deploy
+            } else if let Some(dependency) = awaited_dependency {
+                debug!(
+                    %deploy_hash,
+                    %dependency,
+                    "DeployBuffer: deploy awaiting dependency"
+                );
+                awaiting_dependency
+                    .entry(*dependency)
+                    .or_default()
+                    .push(candidate);
+            } else {
+                let Candidate {
+                    with_approvals,
+                    footprint,
+                    ..
+                } = candidate;
+                let has_multiple_approvals = with_approvals.approvals().len() > 1;
+                match ret.add(with_approvals, &footprint) {
+                    Ok(_) => {
+                        debug!(%deploy_hash, "DeployBuffer: proposing deploy");
+                        holds.insert(deploy_hash);
+                        self.metrics
+                            .included_gas_price
+                            .observe(footprint.header.gas_price() as f64);
+                        *account_deploys.entry(account.clone()).or_default() += 1;
+                        // deploys depending on this one might be proposable now
+                        for dependent in awaiting_dependency
+                            .remove(&deploy_hash)
+                            .into_iter()
+                            .flatten()
+                        {
+                            let dependent_account = dependent.footprint.header.account().clone();
+                            let dependent_priority = dependent.priority(0);
+                            let bucket = buckets.entry(dependent_account.clone()).or_default();
+                            let position = bucket.partition_point(|candidate| {
+                                candidate.priority(0) < dependent_priority
+                            });
+                            let is_best = position == bucket.len();
+                            bucket.insert(position, dependent);
+                            // the current account's next deploy is queued below
+                            if is_best && dependent_account != account {
+                                let added = account_deploys
+                                    .get(&dependent_account)
+                                    .copied()
+                                    .unwrap_or_default();
+                                let priority = bucket[position].priority(added);
+                                queue.push((priority, dependent_account));
+                            }
                         }
-                        AddError::TransferCount => {
-                            if have_hit_deploy_limit {
+                    }
+                    Err(error) => {
+                        match error {
+                            AddError::Duplicate => {
+                                // it should be physically impossible for a duplicate deploy to
+                                // be in the deploy buffer, thus this should be unreachable
+                                error!(
+                                    ?deploy_hash,
+                                    "DeployBuffer: duplicated deploy in deploy buffer"
+                                );
+                                self.dead.insert(deploy_hash);
+                            }
+                            AddError::SlotTaken => {
+                                // only one deploy per slot is proposable, thus this should be
+                                // unreachable; the deploy is skipped for this block only
+                                warn!(
+                                    ?deploy_hash,
+                                    "DeployBuffer: deploy in a slot already taken in proposed block"
+                                );
+                            }
+                            AddError::Expired => {
                                 info!(
                                     ?deploy_hash,
-                                    "DeployBuffer: block filled with transfers and deploys"
+                                    "DeployBuffer: expired deploy in deploy buffer"
                                 );
-                                break;
+                                self.dead.insert(deploy_hash);
                             }
-                            have_hit_transfer_limit = true;
-                        }
-                        AddError::DeployCount => {
-                            if have_hit_transfer_limit {
+                            AddError::InvalidDeploy => {
+                                // It should not generally be possible for an invalid deploy to
+                                // get buffered in the first place, thus this should be
+                                // unreachable.  There is a small potential for a slightly
+                                // future-dated deploy to be accepted (if within
+                                // `timestamp_leeway`) and still be future-dated by the time we
+                                // try and add it to a proposed block here.
+                                warn!(
+                                    ?deploy_hash,
+                                    "DeployBuffer: invalid deploy in deploy buffer"
+                                );
+                                self.dead.insert(deploy_hash);
+                            }
+                            AddError::TransferCount => {
+                                if have_hit_deploy_limit {
+                                    info!(
+                                        ?deploy_hash,
+                                        "DeployBuffer: block filled with transfers and deploys"
+                                    );
+                                    break;
+                                }
+                                have_hit_transfer_limit = true;
+                            }
+                            AddError::DeployCount => {
+                                if have_hit_transfer_limit {
+                                    info!(
+                                        ?deploy_hash,
+                                        "DeployBuffer: block filled with deploys and transfers"
+                                    );
+                                    break;
+                                }
+                                have_hit_deploy_limit = true;
+                            }
+                            AddError::ApprovalCount if has_multiple_approvals => {
+                                // keep iterating, we can maybe fit in a deploy with fewer approvals
+                            }
+                            AddError::ApprovalCount | AddError::GasLimit | AddError::BlockSize => {
                                 info!(
                                     ?deploy_hash,
-                                    "DeployBuffer: block filled with deploys and transfers"
+                                    %error,
+                                    "DeployBuffer: a block limit has been reached"
                                 );
+                                // a block limit has been reached
                                 break;
                             }
-                            have_hit_deploy_limit = true;
-                        }
-                        AddError::ApprovalCount if has_multiple_approvals => {
-                            // keep iterating, we can maybe fit in a deploy with fewer approvals
-                        }
-                        AddError::ApprovalCount | AddError::GasLimit | AddError::BlockSize => {
-                            info!(
-                                ?deploy_hash,
-                                %error,
-                                "DeployBuffer: a block limit has been reached"
-                            );
-                            // a block limit has been reached
-                            break;
                         }
                     }
                 }
             }
+            // queue the account's next deploy, if any
+            if let Some(candidate) = buckets.get(&account).and_then(|bucket| bucket.last()) {
+                let added = account_deploys.get(&account).copied().unwrap_or_default();
+                queue.push((candidate.priority(added), account));
+            }
+        }
+
+        for (deploy_hash, gas_price) in gas_prices {
+            if !holds.contains(&deploy_hash) {
+                self.metrics.waiting_gas_price.observe(gas_price as f64);
+            }
         }
 
         // put a hold on all proposed deploys / transfers and update metrics
@@ -573,6 +839,7 @@ where
                             .event(move |_| Event::Expire)
                     }
                     Event::Request(_)
+                    | Event::ExecutedDependencies { .. }
                     | Event::ReceiveDeployGossiped(_)
                     | Event::StoredDeploy(_, _)
                     | Event::BlockProposed(_)
@@ -601,8 +868,37 @@ where
                     timestamp,
                     request_expiry,
                     responder,
-                }) => responder
-                    .respond(self.appendable_block(timestamp, request_expiry))
+                }) => {
+                    let dependencies = self.proposable_dependencies();
+                    if dependencies.is_empty() {
+                        return responder
+                            .respond(self.appendable_block(
+                                timestamp,
+                                request_expiry,
+                                &dependencies,
+                            ))
+                            .ignore();
+                    }
+                    effect_builder.get_executed_deploys(dependencies).event(
+                        move |executed_dependencies| Event::ExecutedDependencies {
+                            timestamp,
+                            request_expiry,
+                            executed_dependencies,
+                            responder,
+                        },
+                    )
+                }
+                Event::ExecutedDependencies {
+                    timestamp,
+                    request_expiry,
+                    executed_dependencies,
+                    responder,
+                } => responder
+                    .respond(self.appendable_block(
+                        timestamp,
+                        request_expiry,
+                        &executed_dependencies,
+                    ))
                     .ignore(),
                 Event::BlockFinalized(finalized_block) => {
                     self.register_block_finalized(&finalized_block);
@@ -619,17 +915,18 @@ where
                 Event::ReceiveDeployGossiped(deploy_id) => {
                     self.register_deploy_gossiped(deploy_id, effect_builder)
                 }
-                Event::StoredDeploy(deploy_id, maybe_deploy) => {
-                    match maybe_deploy {
-                        Some(deploy) => {
-                            self.register_deploy(*deploy);
-                        }
-                        None => {
-                            warn!("cannot register un-stored deploy({})", deploy_id);
-                        }
+                Event::StoredDeploy(deploy_id, maybe_deploy) => match maybe_deploy {
+                    Some(deploy) => match self.register_deploy(*deploy) {
+                        Some(superseded) => effect_builder
+                            .announce_superseded_deploy(superseded)
+                            .ignore(),
+                        None => Effects::new(),
+                    },
+                    None => {
+                        warn!("cannot register un-stored deploy({})", deploy_id);
+                        Effects::new()
                     }
-                    Effects::new()
-                }
+                },
                 Event::Expire => self.expire(effect_builder),
             },
         }
@@ -4,12 +4,16 @@ use serde::{Deserialize, Serialize};
 use casper_types::TimeDiff;
 
 const DEFAULT_EXPIRY_CHECK_INTERVAL: &str = "1min";
+const DEFAULT_PRIORITY_AGING_INTERVAL: &str = "1min";
 
 #[derive(Copy, Clone, DataSize, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct Config {
     /// The interval of checking for expired deploys.
     pub expiry_check_interval: TimeDiff,
+    /// The time after which the priority of a buffered deploy is raised by one gas price unit.
+    #[serde(default = "default_priority_aging_interval")]
+    pub priority_aging_interval: TimeDiff,
 }
 
 impl Config {
@@ -17,12 +21,23 @@ impl Config {
     pub fn expiry_check_interval(&self) -> TimeDiff {
         self.expiry_check_interval
     }
+
+    /// Returns the time after which the priority of a buffered deploy is raised by one gas price
+    /// unit.
+    pub fn priority_aging_interval(&self) -> TimeDiff {
+        self.priority_aging_interval
+    }
+}
+
+fn default_priority_aging_interval() -> TimeDiff {
+    DEFAULT_PRIORITY_AGING_INTERVAL.parse().unwrap()
 }
 
 impl Default for Config {
     fn default() -> Self {
         Config {
             expiry_check_interval: DEFAULT_EXPIRY_CHECK_INTERVAL.parse().unwrap(),
+            priority_aging_interval: default_priority_aging_interval(),
         }
     }
 }
@@ -1,4 +1,5 @@
 use std::{
+    collections::HashSet,
     fmt::{self, Display, Formatter},
     sync::Arc,
 };
@@ -6,10 +7,14 @@ use std::{
 use datasize::DataSize;
 use derive_more::From;
 
+use casper_types::Timestamp;
+
 use crate::{
     components::consensus::{ClContext, ProposedBlock},
-    effect::requests::DeployBufferRequest,
-    types::{Block, Deploy, DeployId, FinalizedBlock},
+    effect::{requests::DeployBufferRequest, Responder},
+    types::{
+        appendable_block::AppendableBlock, Block, Deploy, DeployHash, DeployId, FinalizedBlock,
+    },
 };
 
 #[derive(Debug, From, DataSize)]
@@ -17,6 +22,14 @@ pub(crate) enum Event {
     Initialize(Vec<Block>),
     #[from]
     Request(DeployBufferRequest),
+    /// The dependencies of the proposable deploys which were found to be executed in storage,
+    /// completing a request for an appendable block.
+    ExecutedDependencies {
+        timestamp: Timestamp,
+        request_expiry: Timestamp,
+        executed_dependencies: HashSet<DeployHash>,
+        responder: Responder<AppendableBlock>,
+    },
     ReceiveDeployGossiped(DeployId),
     StoredDeploy(DeployId, Option<Box<Deploy>>),
     BlockProposed(Box<ProposedBlock<ClContext>>),
@@ -34,6 +47,16 @@ impl Display for Event {
             Event::Request(DeployBufferRequest::GetAppendableBlock { .. }) => {
                 write!(formatter, "get appendable block request")
             }
+            Event::ExecutedDependencies {
+                executed_dependencies,
+                ..
+            } => {
+                write!(
+                    formatter,
+                    "{} executed dependencies",
+                    executed_dependencies.len()
+                )
+            }
             Event::ReceiveDeployGossiped(deploy_id) => {
                 write!(formatter, "receive deploy gossiped {}", deploy_id)
             }
@@ -1,6 +1,21 @@
-use prometheus::{IntGauge, Registry};
+use prometheus::{Histogram, IntGauge, Registry};
 
-use crate::unregister_metric;
+use crate::{unregister_metric, utils};
+
+const INCLUDED_GAS_PRICE_NAME: &str = "deploy_buffer_included_gas_price";
+const INCLUDED_GAS_PRICE_HELP: &str = "gas price of deploys included in proposed blocks.";
+const WAITING_GAS_PRICE_NAME: &str = "deploy_buffer_waiting_gas_price";
+const WAITING_GAS_PRICE_HELP: &str =
+    "gas price of proposable deploys left out of proposed blocks, observed per proposal.";
+
+/// Value of upper bound of the first bucket.
+const EXPONENTIAL_BUCKET_START: f64 = 1.0;
+
+/// Multiplier of previous upper bound for next bound.
+const EXPONENTIAL_BUCKET_FACTOR: f64 = 2.0;
+
+/// Bucket count, with the last bucket going to +Inf which will not be included in the results.
+const EXPONENTIAL_BUCKET_COUNT: usize = 12;
 
 /// Metrics for the deploy_buffer component.
 #[derive(Debug)]
@@ -11,6 +26,10 @@ pub(super) struct Metrics {
     pub(super) held_deploys: IntGauge,
     /// Number of deploys that should not be included in future proposals ever again.
     pub(super) dead_deploys: IntGauge,
+    /// Gas prices of deploys included in proposed blocks.
+    pub(super) included_gas_price: Histogram,
+    /// Gas prices of proposable deploys which were not included in proposed blocks.
+    pub(super) waiting_gas_price: Histogram,
     registry: Registry,
 }
 
@@ -29,6 +48,11 @@ impl Metrics {
             "deploy_buffer_dead_deploys".to_string(),
             "number of deploys that should not be included in future proposals.".to_string(),
         )?;
+        let gas_price_buckets = prometheus::exponential_buckets(
+            EXPONENTIAL_BUCKET_START,
+            EXPONENTIAL_BUCKET_FACTOR,
+            EXPONENTIAL_BUCKET_COUNT,
+        )?;
 
         registry.register(Box::new(total_deploys.clone()))?;
         registry.register(Box::new(held_deploys.clone()))?;
@@ -38,6 +62,18 @@ impl Metrics {
             total_deploys,
             held_deploys,
             dead_deploys,
+            included_gas_price: utils::register_histogram_metric(
+                registry,
+                INCLUDED_GAS_PRICE_NAME,
+                INCLUDED_GAS_PRICE_HELP,
+                gas_price_buckets.clone(),
+            )?,
+            waiting_gas_price: utils::register_histogram_metric(
+                registry,
+                WAITING_GAS_PRICE_NAME,
+                WAITING_GAS_PRICE_HELP,
+                gas_price_buckets,
+            )?,
             registry: registry.clone(),
         })
     }
@@ -48,5 +84,7 @@ impl Drop for Metrics {
         unregister_metric!(self.registry, self.total_deploys);
         unregister_metric!(self.registry, self.held_deploys);
         unregister_metric!(self.registry, self.dead_deploys);
+        unregister_metric!(self.registry, self.included_gas_price);
+        unregister_metric!(self.registry, self.waiting_gas_price);
     }
 }
@@ -0,0 +1,88 @@
+use std::cmp::Ordering;
+
+use casper_types::{TimeDiff, Timestamp};
+
+use crate::types::{DeployFootprint, DeployHash, DeployHashWithApprovals};
+
+/// A proposable deploy along with its score.
+#[derive(Clone, PartialEq, Debug)]
+pub(super) struct Candidate {
+    /// The deploy's gas price, raised by one for each full aging interval which has passed since
+    /// the deploy was received.
+    pub(super) score: u64,
+    /// When the deploy was received by the deploy buffer.
+    ///
+    /// The deploy's own timestamp is set by its sender, so aging from it would let a backdated
+    /// deploy jump the queue.
+    pub(super) received: Timestamp,
+    pub(super) with_approvals: DeployHashWithApprovals,
+    pub(super) footprint: DeployFootprint,
+}
+
+impl Candidate {
+    pub(super) fn new(
+        with_approvals: DeployHashWithApprovals,
+        footprint: DeployFootprint,
+        received: Timestamp,
+        aging_interval: TimeDiff,
+        now: Timestamp,
+    ) -> Self {
+        let age = now.saturating_diff(received).millis();
+        let aging_bonus = age.checked_div(aging_interval.millis()).unwrap_or_default();
+        Candidate {
+            score: footprint.header.gas_price().saturating_add(aging_bonus),
+            received,
+            with_approvals,
+            footprint,
+        }
+    }
+
+    /// Returns the priority of this candidate, given the number of deploys from the same account
+    /// which have already been added to the block.
+    pub(super) fn priority(&self, account_deploys: u64) -> Priority {
+        Priority {
+            score: self.score,
+            account_deploys,
+            received: self.received,
+            deploy_hash: *self.with_approvals.deploy_hash(),
+        }
+    }
+}
+
+/// The priority of a candidate for inclusion in a proposed block.
+///
+/// Candidates are ordered by their score divided by one more than the number of deploys from the
+/// same account already in the block, so that a single account can't crowd out all others.  Ties
+/// are broken in favor of the deploy received first.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Priority {
+    score: u64,
+    account_deploys: u64,
+    received: Timestamp,
+    deploy_hash: DeployHash,
+}
+
+impl Ord for Priority {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Compare the ratios by cross-multiplying, which can't overflow a `u128`.
+        let lhs = u128::from(self.score) * (u128::from(other.account_deploys) + 1);
+        let rhs = u128::from(other.score) * (u128::from(self.account_deploys) + 1);
+        lhs.cmp(&rhs)
+            .then_with(|| other.received.cmp(&self.received))
+            .then_with(|| other.deploy_hash.cmp(&self.deploy_hash))
+    }
+}
+
+impl PartialOrd for Priority {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Priority {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Priority {}
@@ -3,7 +3,10 @@ use std::iter;
 use prometheus::Registry;
 use rand::Rng;
 
-use casper_types::{testing::TestRng, EraId, SecretKey, TimeDiff};
+use casper_execution_engine::core::engine_state::executable_deploy_item::ExecutableDeployItem;
+use casper_types::{
+    bytesrepr::Bytes, runtime_args, testing::TestRng, EraId, RuntimeArgs, SecretKey, TimeDiff, U512,
+};
 
 use super::*;
 use crate::{
@@ -55,10 +58,11 @@ fn create_valid_deploys(
                 deploys.push(deploy);
             }
             DeployType::Random => {
-                deploys.push(Deploy::random_with_timestamp_and_ttl(
+                deploys.push(Deploy::random_with_dependencies(
                     rng,
                     deploy_timestamp,
                     deploy_ttl,
+                    vec![],
                 ));
             }
         }
@@ -132,9 +136,9 @@ fn register_deploy_and_check_size() {
     let num_valid_deploys: usize = rng.gen_range(50..500);
     let valid_deploys =
         create_valid_deploys(&mut rng, num_valid_deploys, DeployType::Random, None, None);
-    valid_deploys
-        .iter()
-        .for_each(|deploy| deploy_buffer.register_deploy(deploy.clone()));
+    valid_deploys.iter().for_each(|deploy| {
+        deploy_buffer.register_deploy(deploy.clone());
+    });
     assert_container_sizes(&deploy_buffer, valid_deploys.len(), 0, 0);
 
     // Try to register a duplicate deploy
@@ -185,9 +189,9 @@ fn get_proposable_deploys() {
 
     // populate deploy buffer with some deploys
     let deploys = create_valid_deploys(&mut rng, 50, DeployType::Random, None, None);
-    deploys
-        .iter()
-        .for_each(|deploy| deploy_buffer.register_deploy(deploy.clone()));
+    deploys.iter().for_each(|deploy| {
+        deploy_buffer.register_deploy(deploy.clone());
+    });
     assert_container_sizes(&deploy_buffer, deploys.len(), 0, 0);
 
     // Create a block with some deploys and register it with the deploy_buffer
@@ -213,7 +217,7 @@ fn get_proposable_deploys() {
 
     let timestamp = Timestamp::now();
     let expiry = timestamp.saturating_add(TimeDiff::from_seconds(1));
-    let appendable_block = deploy_buffer.appendable_block(timestamp, expiry);
+    let appendable_block = deploy_buffer.appendable_block(timestamp, expiry, &HashSet::new());
     assert_eq!(deploy_buffer.hold.len(), 1);
     assert_container_sizes(
         &deploy_buffer,
@@ -300,14 +304,14 @@ fn get_appendable_block(
 ) {
     // populate deploy buffer with more transfers than a block can fit
     let deploys = create_valid_deploys(rng, deploy_limit + 50, deploy_type, None, None);
-    deploys
-        .iter()
-        .for_each(|deploy| deploy_buffer.register_deploy(deploy.clone()));
+    deploys.iter().for_each(|deploy| {
+        deploy_buffer.register_deploy(deploy.clone());
+    });
     assert_container_sizes(deploy_buffer, deploys.len(), 0, 0);
 
     let timestamp = Timestamp::now();
     let expiry = timestamp.saturating_add(TimeDiff::from_seconds(1));
-    let appendable_block = deploy_buffer.appendable_block(timestamp, expiry);
+    let appendable_block = deploy_buffer.appendable_block(timestamp, expiry, &HashSet::new());
     assert!(appendable_block.deploy_and_transfer_set().len() <= deploy_limit,);
     assert_eq!(deploy_buffer.hold.len(), 1);
     assert_container_sizes(
@@ -328,9 +332,9 @@ fn register_deploys_and_blocks() {
     let num_valid_deploys: usize = rng.gen_range(50..500);
     let valid_deploys =
         create_valid_deploys(&mut rng, num_valid_deploys, DeployType::Random, None, None);
-    valid_deploys
-        .iter()
-        .for_each(|deploy| deploy_buffer.register_deploy(deploy.clone()));
+    valid_deploys.iter().for_each(|deploy| {
+        deploy_buffer.register_deploy(deploy.clone());
+    });
     assert_container_sizes(&deploy_buffer, valid_deploys.len(), 0, 0);
 
     // register a block with deploys
@@ -345,9 +349,9 @@ fn register_deploys_and_blocks() {
     );
 
     // try to register the deploys of the block again. Should not work since those deploys are dead.
-    block_deploys
-        .iter()
-        .for_each(|deploy| deploy_buffer.register_deploy(deploy.clone()));
+    block_deploys.iter().for_each(|deploy| {
+        deploy_buffer.register_deploy(deploy.clone());
+    });
     assert_container_sizes(
         &deploy_buffer,
         block_deploys.len() + valid_deploys.len(),
@@ -359,7 +363,7 @@ fn register_deploys_and_blocks() {
 
     let timestamp = Timestamp::now();
     let expiry = timestamp.saturating_add(TimeDiff::from_seconds(1));
-    let appendable_block = deploy_buffer.appendable_block(timestamp, expiry);
+    let appendable_block = deploy_buffer.appendable_block(timestamp, expiry, &HashSet::new());
     assert_eq!(deploy_buffer.hold.len(), 1);
     assert_container_sizes(
         &deploy_buffer,
@@ -379,7 +383,9 @@ fn register_deploys_and_blocks() {
         })
         .peekable();
     assert!(held_deploys.peek().is_some());
-    held_deploys.for_each(|deploy| deploy_buffer.register_deploy(deploy));
+    held_deploys.for_each(|deploy| {
+        deploy_buffer.register_deploy(deploy);
+    });
     assert_container_sizes(
         &deploy_buffer,
         block_deploys.len() + valid_deploys.len(),
@@ -407,19 +413,347 @@ fn register_deploys_and_blocks() {
 }
 
 #[test]
-fn should_have_one_bucket_per_distinct_body_hash() {
+fn should_propose_deploys_only_after_their_dependencies() {
+    let mut rng = TestRng::new();
+    let mut deploy_buffer =
+        DeployBuffer::new(DeployConfig::default(), Config::default(), &Registry::new()).unwrap();
+
+    let timestamp = Timestamp::now();
+    let ttl = TimeDiff::from_seconds(60);
+    let executed = Deploy::random_with_dependencies(&mut rng, timestamp, ttl, vec![]);
+    let first = Deploy::random_with_dependencies(&mut rng, timestamp, ttl, vec![*executed.hash()]);
+    let second = Deploy::random_with_dependencies(&mut rng, timestamp, ttl, vec![*first.hash()]);
+    let unknown_dependency = DeployHash::random(&mut rng);
+    let blocked =
+        Deploy::random_with_dependencies(&mut rng, timestamp, ttl, vec![unknown_dependency]);
+
+    // the first dependency has been executed in an earlier block
+    let block = Block::random_with_deploys(&mut rng, iter::once(&executed));
+    deploy_buffer.register_block(&block);
+    // register the dependent deploy before its dependency
+    for deploy in [&second, &first, &blocked] {
+        deploy_buffer.register_deploy(deploy.clone());
+    }
+    assert_container_sizes(&deploy_buffer, 4, 1, 0);
+
+    let expiry = Timestamp::now().saturating_add(TimeDiff::from_seconds(1));
+    let appendable_block = deploy_buffer.appendable_block(
+        Timestamp::now(),
+        expiry,
+        &HashSet::from([*executed.hash()]),
+    );
+    assert_container_sizes(&deploy_buffer, 4, 1, 2);
+    let proposed: Vec<_> = appendable_block
+        .into_block_payload(vec![], false)
+        .deploys()
+        .iter()
+        .map(|deploy| *deploy.deploy_hash())
+        .collect();
+    assert_eq!(proposed, vec![*first.hash(), *second.hash()]);
+}
+
+#[test]
+fn should_resolve_dependencies_against_executed_deploys_only() {
+    let mut rng = TestRng::new();
+    let mut deploy_buffer =
+        DeployBuffer::new(DeployConfig::default(), Config::default(), &Registry::new()).unwrap();
+
+    let timestamp = Timestamp::now();
+    let ttl = TimeDiff::from_seconds(60);
+    // a dependency which is dead without having been executed, e.g. as it was found invalid
+    let dead_dependency = DeployHash::random(&mut rng);
+    deploy_buffer.dead.insert(dead_dependency);
+    // a dependency executed long enough ago to have been pruned from the buffer
+    let pruned_dependency = DeployHash::random(&mut rng);
+    let blocked = Deploy::random_with_dependencies(&mut rng, timestamp, ttl, vec![dead_dependency]);
+    let unblocked =
+        Deploy::random_with_dependencies(&mut rng, timestamp, ttl, vec![pruned_dependency]);
+    for deploy in [&blocked, &unblocked] {
+        deploy_buffer.register_deploy(deploy.clone());
+    }
+    assert_eq!(
+        deploy_buffer.proposable_dependencies(),
+        HashSet::from([dead_dependency, pruned_dependency])
+    );
+
+    let expiry = Timestamp::now().saturating_add(TimeDiff::from_seconds(1));
+    let appendable_block = deploy_buffer.appendable_block(
+        Timestamp::now(),
+        expiry,
+        &HashSet::from([pruned_dependency]),
+    );
+    let proposed: Vec<_> = appendable_block
+        .into_block_payload(vec![], false)
+        .deploys()
+        .iter()
+        .map(|deploy| *deploy.deploy_hash())
+        .collect();
+    assert_eq!(proposed, vec![*unblocked.hash()]);
+}
+
+fn create_deploy_with_gas_price(
+    rng: &mut TestRng,
+    secret_key: &SecretKey,
+    timestamp: Timestamp,
+    gas_price: u64,
+) -> Deploy {
+    let payment = ExecutableDeployItem::ModuleBytes {
+        module_bytes: Bytes::new(),
+        args: runtime_args! { "amount" => U512::from(10) },
+    };
+    let session = ExecutableDeployItem::StoredContractByName {
+        name: format!("{}", rng.gen::<u64>()),
+        entry_point: "call".to_string(),
+        args: RuntimeArgs::new(),
+    };
+    Deploy::new(
+        timestamp,
+        TimeDiff::from_seconds(3600),
+        gas_price,
+        vec![],
+        "test_chain".to_string(),
+        payment,
+        session,
+        secret_key,
+        None,
+    )
+}
+
+fn proposed_deploys(deploy_buffer: &mut DeployBuffer) -> Vec<DeployHash> {
+    let timestamp = Timestamp::now();
+    let expiry = timestamp.saturating_add(TimeDiff::from_seconds(1));
+    deploy_buffer
+        .appendable_block(timestamp, expiry, &HashSet::new())
+        .into_block_payload(vec![], false)
+        .deploys()
+        .iter()
+        .map(|deploy| *deploy.deploy_hash())
+        .collect()
+}
+
+#[test]
+fn should_propose_deploys_in_order_of_gas_price() {
     let mut rng = TestRng::new();
-    let max_deploy_count = 2;
-    let max_transfer_count = 0;
     let deploy_config = DeployConfig {
-        block_max_deploy_count: max_deploy_count,
-        block_max_transfer_count: max_transfer_count,
-        block_max_approval_count: max_deploy_count + max_transfer_count,
+        block_max_deploy_count: 2,
+        block_max_transfer_count: 0,
+        block_max_approval_count: 2,
+        ..Default::default()
+    };
+    let mut deploy_buffer =
+        DeployBuffer::new(deploy_config, Config::default(), &Registry::new()).unwrap();
+
+    let timestamp = Timestamp::now();
+    let deploys: Vec<_> = [1, 5, 3]
+        .into_iter()
+        .map(|gas_price| {
+            let secret_key = SecretKey::random(&mut rng);
+            create_deploy_with_gas_price(&mut rng, &secret_key, timestamp, gas_price)
+        })
+        .collect();
+    for deploy in &deploys {
+        deploy_buffer.register_deploy(deploy.clone());
+    }
+
+    assert_eq!(
+        proposed_deploys(&mut deploy_buffer),
+        vec![*deploys[1].hash(), *deploys[2].hash()]
+    );
+}
+
+#[test]
+fn should_share_block_between_accounts() {
+    let mut rng = TestRng::new();
+    let deploy_config = DeployConfig {
+        block_max_deploy_count: 3,
+        block_max_transfer_count: 0,
+        block_max_approval_count: 3,
         ..Default::default()
     };
     let mut deploy_buffer =
         DeployBuffer::new(deploy_config, Config::default(), &Registry::new()).unwrap();
 
+    // three deploys at a gas price of 10 from one account, one at a gas price of 4 from another
+    let timestamp = Timestamp::now();
+    let secret_key = SecretKey::random(&mut rng);
+    let expensive: HashSet<_> = (0..3)
+        .map(|offset| {
+            let timestamp = timestamp.saturating_sub(TimeDiff::from_millis(offset));
+            let deploy = create_deploy_with_gas_price(&mut rng, &secret_key, timestamp, 10);
+            deploy_buffer.register_deploy(deploy.clone());
+            *deploy.hash()
+        })
+        .collect();
+    let other_secret_key = SecretKey::random(&mut rng);
+    let cheap = create_deploy_with_gas_price(&mut rng, &other_secret_key, timestamp, 4);
+    deploy_buffer.register_deploy(cheap.clone());
+
+    // the first account's third deploy is down to a priority of 10 / 3, below the other's 4
+    let proposed = proposed_deploys(&mut deploy_buffer);
+    assert_eq!(proposed.len(), 3);
+    assert!(expensive.contains(&proposed[0]));
+    assert!(expensive.contains(&proposed[1]));
+    assert_eq!(proposed[2], *cheap.hash());
+}
+
+#[test]
+fn should_raise_priority_of_waiting_deploys() {
+    let mut rng = TestRng::new();
+    let deploy_config = DeployConfig {
+        block_max_deploy_count: 1,
+        block_max_transfer_count: 0,
+        block_max_approval_count: 1,
+        ..Default::default()
+    };
+    let now = Timestamp::now();
+    let received_earlier = now.saturating_sub(TimeDiff::from_seconds(300));
+    let secret_key = SecretKey::random(&mut rng);
+    let old = create_deploy_with_gas_price(&mut rng, &secret_key, received_earlier, 1);
+    let other_secret_key = SecretKey::random(&mut rng);
+    let new = create_deploy_with_gas_price(&mut rng, &other_secret_key, now, 3);
+
+    // with a score of 1 + 5, the deploy waiting in the buffer outranks the new one
+    let config = Config {
+        priority_aging_interval: TimeDiff::from_seconds(60),
+        ..Config::default()
+    };
+    let mut deploy_buffer = DeployBuffer::new(deploy_config, config, &Registry::new()).unwrap();
+    deploy_buffer.register_deploy(old.clone());
+    deploy_buffer.register_deploy(new.clone());
+    deploy_buffer.received.insert(*old.hash(), received_earlier);
+    assert_eq!(proposed_deploys(&mut deploy_buffer), vec![*old.hash()]);
+
+    // without aging, the gas price decides
+    let config = Config {
+        priority_aging_interval: TimeDiff::from_seconds(0),
+        ..Config::default()
+    };
+    let mut deploy_buffer = DeployBuffer::new(deploy_config, config, &Registry::new()).unwrap();
+    deploy_buffer.register_deploy(old.clone());
+    deploy_buffer.register_deploy(new.clone());
+    deploy_buffer.received.insert(*old.hash(), received_earlier);
+    assert_eq!(proposed_deploys(&mut deploy_buffer), vec![*new.hash()]);
+}
+
+#[test]
+fn should_not_age_backdated_deploys() {
+    let mut rng = TestRng::new();
+    let deploy_config = DeployConfig {
+        block_max_deploy_count: 1,
+        block_max_transfer_count: 0,
+        block_max_approval_count: 1,
+        ..Default::default()
+    };
+    let now = Timestamp::now();
+    let secret_key = SecretKey::random(&mut rng);
+    let backdated = create_deploy_with_gas_price(
+        &mut rng,
+        &secret_key,
+        now.saturating_sub(TimeDiff::from_seconds(300)),
+        1,
+    );
+    let other_secret_key = SecretKey::random(&mut rng);
+    let new = create_deploy_with_gas_price(&mut rng, &other_secret_key, now, 3);
+
+    // both were just received, so an old deploy timestamp earns no aging bonus
+    let config = Config {
+        priority_aging_interval: TimeDiff::from_seconds(60),
+        ..Config::default()
+    };
+    let mut deploy_buffer = DeployBuffer::new(deploy_config, config, &Registry::new()).unwrap();
+    deploy_buffer.register_deploy(backdated);
+    deploy_buffer.register_deploy(new.clone());
+    assert_eq!(proposed_deploys(&mut deploy_buffer), vec![*new.hash()]);
+}
+
+#[test]
+fn should_supersede_deploy_with_higher_gas_price() {
+    let mut rng = TestRng::new();
+    let mut deploy_buffer =
+        DeployBuffer::new(DeployConfig::default(), Config::default(), &Registry::new()).unwrap();
+
+    let timestamp = Timestamp::now();
+    let secret_key = SecretKey::random(&mut rng);
+    let original = create_deploy_with_gas_price(&mut rng, &secret_key, timestamp, 2);
+    let replacement = create_deploy_with_gas_price(&mut rng, &secret_key, timestamp, 3);
+    let underbid = create_deploy_with_gas_price(&mut rng, &secret_key, timestamp, 1);
+
+    assert_eq!(deploy_buffer.register_deploy(original.clone()), None);
+    assert_eq!(
+        deploy_buffer.register_deploy(replacement.clone()),
+        Some(original.gossip_id())
+    );
+    assert_eq!(
+        deploy_buffer.register_deploy(underbid.clone()),
+        Some(underbid.gossip_id())
+    );
+    assert_container_sizes(&deploy_buffer, 3, 0, 0);
+    assert_eq!(
+        deploy_buffer.superseded,
+        HashSet::from([*original.hash(), *underbid.hash()])
+    );
+    assert_eq!(
+        proposed_deploys(&mut deploy_buffer),
+        vec![*replacement.hash()]
+    );
+
+    // a deploy from the same account with a different timestamp doesn't compete
+    let other = create_deploy_with_gas_price(
+        &mut rng,
+        &secret_key,
+        timestamp.saturating_sub(TimeDiff::from_millis(1)),
+        1,
+    );
+    assert_eq!(deploy_buffer.register_deploy(other), None);
+}
+
+#[test]
+fn should_not_supersede_executed_deploy() {
+    let mut rng = TestRng::new();
+    let mut deploy_buffer =
+        DeployBuffer::new(DeployConfig::default(), Config::default(), &Registry::new()).unwrap();
+
+    let timestamp = Timestamp::now();
+    let secret_key = SecretKey::random(&mut rng);
+    let original = create_deploy_with_gas_price(&mut rng, &secret_key, timestamp, 1);
+    deploy_buffer.register_deploy(original.clone());
+    assert_eq!(proposed_deploys(&mut deploy_buffer), vec![*original.hash()]);
+
+    // the original is held, but can still be replaced before it is executed
+    let replacement = create_deploy_with_gas_price(&mut rng, &secret_key, timestamp, 2);
+    assert_eq!(
+        deploy_buffer.register_deploy(replacement.clone()),
+        Some(original.gossip_id())
+    );
+    // though the replacement isn't proposed while the original is held, as a block containing it
+    // would be invalid if built on the one containing the original
+    assert!(proposed_deploys(&mut deploy_buffer).is_empty());
+
+    // once the original is executed after all, the replacement is superseded in turn
+    let block = Block::random_with_deploys(&mut rng, iter::once(&original));
+    deploy_buffer.register_block(&block);
+    assert_container_sizes(&deploy_buffer, 2, 1, 0);
+    assert_eq!(
+        deploy_buffer.superseded,
+        HashSet::from([*replacement.hash()])
+    );
+    assert!(proposed_deploys(&mut deploy_buffer).is_empty());
+
+    // and no later deploy can take the executed deploy's slot
+    let late = create_deploy_with_gas_price(&mut rng, &secret_key, timestamp, 3);
+    assert_eq!(
+        deploy_buffer.register_deploy(late.clone()),
+        Some(late.gossip_id())
+    );
+    assert!(proposed_deploys(&mut deploy_buffer).is_empty());
+}
+
+#[test]
+fn should_have_one_bucket_per_account() {
+    let mut rng = TestRng::new();
+    let mut deploy_buffer =
+        DeployBuffer::new(DeployConfig::default(), Config::default(), &Registry::new()).unwrap();
+
     let secret_key1 = SecretKey::random(&mut rng);
     let ttl = TimeDiff::from_seconds(30);
     let deploy1 = Deploy::random_contract_by_name(
@@ -430,8 +764,9 @@ fn should_have_one_bucket_per_distinct_body_hash() {
         Some(Timestamp::now()),
         Some(ttl),
     );
-    let deploy1_body_hash = *deploy1.header().body_hash();
     deploy_buffer.register_deploy(deploy1);
+    let buckets = deploy_buffer.buckets(Timestamp::now());
+    assert!(buckets.len() == 1, "should be 1 bucket");
 
     let secret_key2 = SecretKey::random(&mut rng); // different signer
     let deploy2 = Deploy::random_contract_by_name(
@@ -445,37 +780,11 @@ fn should_have_one_bucket_per_distinct_body_hash() {
         Some(Timestamp::now()), // different timestamp
         Some(ttl),
     );
-    assert_eq!(
-        &deploy1_body_hash,
-        deploy2.header().body_hash(),
-        "1 & 2 should have same body hashes"
-    );
     deploy_buffer.register_deploy(deploy2);
-
-    let buckets = deploy_buffer.buckets();
-    assert!(buckets.len() == 1, "should be 1 bucket");
+    let buckets = deploy_buffer.buckets(Timestamp::now());
+    assert!(buckets.len() == 2, "should be 2 buckets");
 
     let deploy3 = Deploy::random_contract_by_name(
-        &mut rng,
-        Some(
-            SecretKey::from_pem(secret_key2.to_pem().expect("should pemify"))
-                .expect("should un-pemify"),
-        ),
-        None,
-        None,
-        Some(Timestamp::now()), // different timestamp
-        Some(ttl),
-    );
-    assert_eq!(
-        &deploy1_body_hash,
-        deploy3.header().body_hash(),
-        "1 & 3 should have same body hashes"
-    );
-    deploy_buffer.register_deploy(deploy3);
-    let buckets = deploy_buffer.buckets();
-    assert!(buckets.len() == 1, "should still be 1 bucket");
-
-    let deploy4 = Deploy::random_contract_by_name(
         &mut rng,
         Some(
             SecretKey::from_pem(secret_key2.to_pem().expect("should pemify"))
@@ -486,27 +795,17 @@ fn should_have_one_bucket_per_distinct_body_hash() {
         Some(Timestamp::now()), // different timestamp
         Some(ttl),
     );
-    assert_ne!(
-        &deploy1_body_hash,
-        deploy4.header().body_hash(),
-        "1 & 4 should have different body hashes"
-    );
-    deploy_buffer.register_deploy(deploy4);
-    let buckets = deploy_buffer.buckets();
-    assert!(buckets.len() == 2, "should be 2 buckets");
+    deploy_buffer.register_deploy(deploy3);
+    let buckets = deploy_buffer.buckets(Timestamp::now());
+    assert!(buckets.len() == 2, "should still be 2 buckets");
 
-    let transfer5 = Deploy::random_valid_native_transfer_with_timestamp_and_ttl(
+    let transfer4 = Deploy::random_valid_native_transfer_with_timestamp_and_ttl(
         &mut rng,
         Timestamp::now(),
         ttl,
     );
-    assert_ne!(
-        &deploy1_body_hash,
-        transfer5.header().body_hash(),
-        "1 & 5 should have different body hashes"
-    );
-    deploy_buffer.register_deploy(transfer5);
-    let buckets = deploy_buffer.buckets();
+    deploy_buffer.register_deploy(transfer4);
+    let buckets = deploy_buffer.buckets(Timestamp::now());
     assert!(buckets.len() == 3, "should be 3 buckets");
 }
 
@@ -537,7 +836,7 @@ fn should_be_empty_if_no_time_until_expiry() {
     let deploy1_body_hash = *deploy1.header().body_hash();
     deploy_buffer.register_deploy(deploy1);
 
-    let buckets = deploy_buffer.buckets();
+    let buckets = deploy_buffer.buckets(Timestamp::now());
     assert!(buckets.len() == 1, "should be 1 buckets");
 
     let transfer2 = Deploy::random_valid_native_transfer_with_timestamp_and_ttl(
@@ -551,17 +850,20 @@ fn should_be_empty_if_no_time_until_expiry() {
         "1 & 2 should have different body hashes"
     );
     deploy_buffer.register_deploy(transfer2);
-    let buckets = deploy_buffer.buckets();
+    let buckets = deploy_buffer.buckets(Timestamp::now());
     assert!(buckets.len() == 2, "should be 2 buckets");
 
     let now = Timestamp::now();
-    let appendable = deploy_buffer.appendable_block(now, now);
+    let appendable = deploy_buffer.appendable_block(now, now, &HashSet::new());
     let count = appendable.deploy_and_transfer_set().len();
     assert!(count == 0, "expected 0 found {}", count);
 
     // logic should tolerate invalid expiry
-    let appendable =
-        deploy_buffer.appendable_block(now, now.saturating_sub(TimeDiff::from_millis(1)));
+    let appendable = deploy_buffer.appendable_block(
+        now,
+        now.saturating_sub(TimeDiff::from_millis(1)),
+        &HashSet::new(),
+    );
     let count = appendable.deploy_and_transfer_set().len();
     assert!(count == 0, "expected 0 found {}", count);
 }
@@ -641,12 +943,13 @@ fn should_have_diverse_proposable_blocks_with_stocked_buffer() {
     let expected_count = cap + (max_transfer_count as usize);
     assert_container_sizes(&deploy_buffer, expected_count, 0, 0);
 
-    let buckets1 = deploy_buffer.buckets();
+    let now = Timestamp::now();
+    let buckets1 = deploy_buffer.buckets(now);
     assert!(
         buckets1.len() > 1,
         "should be multiple buckets with this much state"
     );
-    let buckets2 = deploy_buffer.buckets();
+    let buckets2 = deploy_buffer.buckets(now);
     assert_eq!(
         buckets1, buckets2,
         "with same state should get same buckets every time"
@@ -659,8 +962,8 @@ fn should_have_diverse_proposable_blocks_with_stocked_buffer() {
 
     let expiry = last_timestamp.saturating_add(TimeDiff::from_seconds(1));
     for _ in 0..10 {
-        let appendable1 = deploy_buffer.appendable_block(last_timestamp, expiry);
-        let appendable2 = deploy_buffer.appendable_block(last_timestamp, expiry);
+        let appendable1 = deploy_buffer.appendable_block(last_timestamp, expiry, &HashSet::new());
+        let appendable2 = deploy_buffer.appendable_block(last_timestamp, expiry, &HashSet::new());
         if appendable1 == appendable2 {
             actual_eq_count += 1;
         }
@@ -744,9 +1047,9 @@ async fn expire_deploys_and_check_announcement() {
         Some(past_timestamp),
         Some(ttl),
     );
-    expired_deploys
-        .iter()
-        .for_each(|deploy| deploy_buffer.register_deploy(deploy.clone()));
+    expired_deploys.iter().for_each(|deploy| {
+        deploy_buffer.register_deploy(deploy.clone());
+    });
     assert_container_sizes(&deploy_buffer, expired_deploys.len(), 0, 0);
 
     // include the last expired deploy in a block and register it
@@ -756,9 +1059,9 @@ async fn expire_deploys_and_check_announcement() {
 
     // generate and register some valid deploys
     let deploys = create_valid_deploys(&mut rng, num_deploys, DeployType::Transfer, None, None);
-    deploys
-        .iter()
-        .for_each(|deploy| deploy_buffer.register_deploy(deploy.clone()));
+    deploys.iter().for_each(|deploy| {
+        deploy_buffer.register_deploy(deploy.clone());
+    });
     assert_container_sizes(&deploy_buffer, deploys.len() + expired_deploys.len(), 1, 0);
 
     // expire deploys and check that they were announced as expired
@@ -843,6 +1146,7 @@ fn test_buckets_single_hash() {
     let _block = deploy_buffer.appendable_block(
         Timestamp::now(),
         Timestamp::now() + TimeDiff::from_millis(16384 / 6),
+        &HashSet::new(),
     );
 }
 
@@ -863,6 +1167,7 @@ fn test_buckets_unique_hashes() {
     let _block = deploy_buffer.appendable_block(
         Timestamp::now(),
         Timestamp::now() + TimeDiff::from_millis(16384 / 6),
+        &HashSet::new(),
     );
 }
 
@@ -884,5 +1189,40 @@ fn test_buckets_mixed_load() {
     let _block = deploy_buffer.appendable_block(
         Timestamp::now(),
         Timestamp::now() + TimeDiff::from_millis(16384 / 6),
+        &HashSet::new(),
+    );
+}
+
+#[test]
+fn should_default_priority_aging_interval_in_older_configs() {
+    let config: Config = toml::from_str("expiry_check_interval = '1min'").unwrap();
+    assert_eq!(
+        config.priority_aging_interval(),
+        Config::default().priority_aging_interval()
+    );
+}
+
+#[test]
+fn should_keep_superseded_deploys_out_of_dead() {
+    let mut rng = TestRng::new();
+    let mut deploy_buffer =
+        DeployBuffer::new(DeployConfig::default(), Config::default(), &Registry::new()).unwrap();
+
+    let timestamp = Timestamp::now();
+    let secret_key = SecretKey::random(&mut rng);
+    let original = create_deploy_with_gas_price(&mut rng, &secret_key, timestamp, 1);
+    let replacement = create_deploy_with_gas_price(&mut rng, &secret_key, timestamp, 2);
+    deploy_buffer.register_deploy(original.clone());
+    deploy_buffer.register_deploy(replacement.clone());
+    assert!(!deploy_buffer.dead.contains(original.hash()));
+
+    // supersession is local to this node, so another node may still execute the original
+    let block = Block::random_with_deploys(&mut rng, iter::once(&original));
+    deploy_buffer.register_block(&block);
+    assert_eq!(deploy_buffer.dead, HashSet::from([*original.hash()]));
+    assert_eq!(
+        deploy_buffer.superseded,
+        HashSet::from([*replacement.hash()])
     );
+    assert!(proposed_deploys(&mut deploy_buffer).is_empty());
 }
@@ -295,15 +295,30 @@ where
                     deploy_header,
                     block_hash,
                     execution_result,
-                } => self.broadcast(SseData::DeployProcessed {
-                    deploy_hash: Box::new(deploy_hash),
-                    account: Box::new(deploy_header.account().clone()),
-                    timestamp: deploy_header.timestamp(),
-                    ttl: deploy_header.ttl(),
-                    dependencies: deploy_header.dependencies().clone(),
-                    block_hash: Box::new(block_hash),
-                    execution_result,
-                }),
+                } => {
+                    let contract_events: Vec<_> = execution_result
+                        .events()
+                        .iter()
+                        .map(|event| SseData::ContractEvent {
+                            deploy_hash: Box::new(deploy_hash),
+                            block_hash: Box::new(block_hash),
+                            event: Box::new(event.clone()),
+                        })
+                        .collect();
+                    let mut effects = self.broadcast(SseData::DeployProcessed {
+                        deploy_hash: Box::new(deploy_hash),
+                        account: Box::new(deploy_header.account().clone()),
+                        timestamp: deploy_header.timestamp(),
+                        ttl: deploy_header.ttl(),
+                        dependencies: deploy_header.dependencies().clone(),
+                        block_hash: Box::new(block_hash),
+                        execution_result,
+                    });
+                    for contract_event in contract_events {
+                        effects.extend(self.broadcast(contract_event));
+                    }
+                    effects
+                }
                 Event::DeploysExpired(deploy_hashes) => deploy_hashes
                     .into_iter()
                     .flat_map(|deploy_hash| self.broadcast(SseData::DeployExpired { deploy_hash }))
@@ -321,9 +336,11 @@ where
                 Event::Step {
                     era_id,
                     execution_effect,
+                    scheduled_transfers,
                 } => self.broadcast(SseData::Step {
                     era_id,
                     execution_effect,
+                    scheduled_transfers,
                 }),
             },
         }
@@ -3,7 +3,10 @@ use std::{
     sync::Arc,
 };
 
-use casper_types::{EraId, ExecutionEffect, ExecutionResult, PublicKey, Timestamp};
+use casper_types::{
+    system::mint::ScheduledTransferResult, EraId, ExecutionEffect, ExecutionResult, PublicKey,
+    Timestamp,
+};
 use itertools::Itertools;
 
 use crate::types::{Block, BlockHash, Deploy, DeployHash, DeployHeader, FinalitySignature};
@@ -29,6 +32,7 @@ pub enum Event {
     Step {
         era_id: EraId,
         execution_effect: ExecutionEffect,
+        scheduled_transfers: Vec<ScheduledTransferResult>,
     },
 }
 
@@ -35,7 +35,8 @@ use warp::{
 #[cfg(test)]
 use casper_types::testing::TestRng;
 use casper_types::{
-    EraId, ExecutionEffect, ExecutionResult, ProtocolVersion, PublicKey, TimeDiff, Timestamp,
+    system::mint::ScheduledTransferResult, ContractEvent, EraId, ExecutionEffect, ExecutionResult,
+    ProtocolVersion, PublicKey, TimeDiff, Timestamp,
 };
 
 use crate::types::{BlockHash, Deploy, DeployHash, FinalitySignature, JsonBlock};
@@ -55,9 +56,10 @@ pub const SSE_API_SIGNATURES_PATH: &str = "sigs";
 pub const QUERY_FIELD: &str = "start_from";
 
 /// The filter associated with `/events/main` path.
-const MAIN_FILTER: [EventFilter; 5] = [
+const MAIN_FILTER: [EventFilter; 6] = [
     EventFilter::BlockAdded,
     EventFilter::DeployProcessed,
+    EventFilter::ContractEvent,
     EventFilter::DeployExpired,
     EventFilter::Fault,
     EventFilter::Step,
@@ -98,6 +100,13 @@ pub enum SseData {
         #[data_size(skip)]
         execution_result: Box<ExecutionResult>,
     },
+    /// An event emitted by a contract while executing the given deploy, which forms part of the
+    /// given block.
+    ContractEvent {
+        deploy_hash: Box<DeployHash>,
+        block_hash: Box<BlockHash>,
+        event: Box<ContractEvent>,
+    },
     /// The given deploy has expired.
     DeployExpired { deploy_hash: DeployHash },
     /// Generic representation of validator's fault in an era.
@@ -111,6 +120,8 @@ pub enum SseData {
     /// The execution effects produced by a `StepRequest`.
     Step {
         era_id: EraId,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        scheduled_transfers: Vec<ScheduledTransferResult>,
         #[data_size(skip)]
         execution_effect: ExecutionEffect,
     },
@@ -125,6 +136,7 @@ impl SseData {
             SseData::BlockAdded { .. } => filter.contains(&EventFilter::BlockAdded),
             SseData::DeployAccepted { .. } => filter.contains(&EventFilter::DeployAccepted),
             SseData::DeployProcessed { .. } => filter.contains(&EventFilter::DeployProcessed),
+            SseData::ContractEvent { .. } => filter.contains(&EventFilter::ContractEvent),
             SseData::DeployExpired { .. } => filter.contains(&EventFilter::DeployExpired),
             SseData::Fault { .. } => filter.contains(&EventFilter::Fault),
             SseData::FinalitySignature(_) => filter.contains(&EventFilter::FinalitySignature),
@@ -177,6 +189,15 @@ impl SseData {
         }
     }
 
+    /// Returns a random `SseData::ContractEvent`.
+    pub(super) fn random_contract_event(rng: &mut TestRng) -> Self {
+        SseData::ContractEvent {
+            deploy_hash: Box::new(DeployHash::random(rng)),
+            block_hash: Box::new(BlockHash::random(rng)),
+            event: Box::new(rng.gen()),
+        }
+    }
+
     /// Returns a random `SseData::DeployExpired`
     pub(super) fn random_deploy_expired(rng: &mut TestRng) -> Self {
         let deploy = testing::create_expired_deploy(Timestamp::now(), rng);
@@ -212,6 +233,7 @@ impl SseData {
         SseData::Step {
             era_id: EraId::new(rng.gen()),
             execution_effect,
+            scheduled_transfers: Vec::new(),
         }
     }
 }
@@ -269,6 +291,7 @@ pub(super) enum EventFilter {
     BlockAdded,
     DeployAccepted,
     DeployProcessed,
+    ContractEvent,
     DeployExpired,
     Fault,
     FinalitySignature,
@@ -311,6 +334,7 @@ async fn filter_map_server_sent_event(
 
         &SseData::BlockAdded { .. }
         | &SseData::DeployProcessed { .. }
+        | &SseData::ContractEvent { .. }
         | &SseData::DeployExpired { .. }
         | &SseData::Fault { .. }
         | &SseData::Step { .. }
@@ -618,6 +642,10 @@ mod tests {
             id: Some(rng.gen()),
             data: SseData::random_deploy_processed(&mut rng),
         };
+        let contract_event = ServerSentEvent {
+            id: Some(rng.gen()),
+            data: SseData::random_contract_event(&mut rng),
+        };
         let deploy_expired = ServerSentEvent {
             id: Some(rng.gen()),
             data: SseData::random_deploy_expired(&mut rng),
@@ -643,6 +671,7 @@ mod tests {
         should_not_filter_out(&api_version, &MAIN_FILTER[..]).await;
         should_not_filter_out(&block_added, &MAIN_FILTER[..]).await;
         should_not_filter_out(&deploy_processed, &MAIN_FILTER[..]).await;
+        should_not_filter_out(&contract_event, &MAIN_FILTER[..]).await;
         should_not_filter_out(&deploy_expired, &MAIN_FILTER[..]).await;
         should_not_filter_out(&fault, &MAIN_FILTER[..]).await;
         should_not_filter_out(&step, &MAIN_FILTER[..]).await;
@@ -659,6 +688,7 @@ mod tests {
 
         should_filter_out(&block_added, &DEPLOYS_FILTER[..]).await;
         should_filter_out(&deploy_processed, &DEPLOYS_FILTER[..]).await;
+        should_filter_out(&contract_event, &DEPLOYS_FILTER[..]).await;
         should_filter_out(&deploy_expired, &DEPLOYS_FILTER[..]).await;
         should_filter_out(&fault, &DEPLOYS_FILTER[..]).await;
         should_filter_out(&finality_signature, &DEPLOYS_FILTER[..]).await;
@@ -673,6 +703,7 @@ mod tests {
         should_filter_out(&block_added, &SIGNATURES_FILTER[..]).await;
         should_filter_out(&deploy_accepted, &SIGNATURES_FILTER[..]).await;
         should_filter_out(&deploy_processed, &SIGNATURES_FILTER[..]).await;
+        should_filter_out(&contract_event, &SIGNATURES_FILTER[..]).await;
         should_filter_out(&deploy_expired, &SIGNATURES_FILTER[..]).await;
         should_filter_out(&fault, &SIGNATURES_FILTER[..]).await;
         should_filter_out(&step, &SIGNATURES_FILTER[..]).await;
@@ -704,6 +735,10 @@ mod tests {
             id: None,
             data: SseData::random_deploy_processed(&mut rng),
         };
+        let malformed_contract_event = ServerSentEvent {
+            id: None,
+            data: SseData::random_contract_event(&mut rng),
+        };
         let malformed_deploy_expired = ServerSentEvent {
             id: None,
             data: SseData::random_deploy_expired(&mut rng),
@@ -734,6 +769,7 @@ mod tests {
             should_filter_out(&malformed_block_added, filter).await;
             should_filter_out(&malformed_deploy_accepted, filter).await;
             should_filter_out(&malformed_deploy_processed, filter).await;
+            should_filter_out(&malformed_contract_event, filter).await;
             should_filter_out(&malformed_deploy_expired, filter).await;
             should_filter_out(&malformed_fault, filter).await;
             should_filter_out(&malformed_finality_signature, filter).await;
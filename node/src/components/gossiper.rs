@@ -444,6 +444,13 @@ impl<const ID_IS_COMPLETE_ITEM: bool, T: GossipItem + 'static> Gossiper<ID_IS_CO
         effect_builder.send_message(requester, message).ignore()
     }
 
+    /// Stops gossiping an item which the component responsible for it has dropped.
+    fn item_dropped(&mut self, item_id: T::Id) {
+        if self.table.force_finish(&item_id) {
+            debug!(item=%item_id, "finished gossiping since item was dropped");
+        }
+    }
+
     /// Handles the `None` case when attempting to get the item from storage.
     fn failed_to_get_from_storage<REv>(
         &mut self,
@@ -586,6 +593,10 @@ where
                 source,
                 target,
             } => self.handle_item_received(effect_builder, item_id, source, target),
+            Event::ItemDropped { item_id } => {
+                self.item_dropped(item_id);
+                Effects::new()
+            }
             Event::GossipedTo {
                 item_id,
                 requested_count,
@@ -688,6 +699,10 @@ where
                 source,
                 target,
             } => self.handle_item_received(effect_builder, item_id, source, target),
+            Event::ItemDropped { item_id } => {
+                self.item_dropped(item_id);
+                Effects::new()
+            }
             Event::GossipedTo {
                 item_id,
                 requested_count,
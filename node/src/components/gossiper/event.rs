@@ -25,6 +25,8 @@ pub(crate) enum Event<T: GossipItem> {
         source: Source,
         target: GossipTarget,
     },
+    /// The component responsible for an item has dropped it, so it should not be gossiped further.
+    ItemDropped { item_id: T::Id },
     /// The network component gossiped to the included peers.
     GossipedTo {
         item_id: T::Id,
@@ -75,6 +77,9 @@ impl<T: GossipItem> Display for Event<T> {
             } => {
                 write!(formatter, "new item {} received from {}", item_id, source)
             }
+            Event::ItemDropped { item_id } => {
+                write!(formatter, "item {} dropped", item_id)
+            }
             Event::GossipedTo { item_id, peers, .. } => write!(
                 formatter,
                 "gossiped {} to {}",
@@ -25,8 +25,8 @@ use futures::join;
 use tracing::{error, info, warn};
 
 use casper_execution_engine::core::engine_state::{
-    self, BalanceRequest, BalanceResult, GetBidsRequest, GetEraValidatorsError, QueryRequest,
-    QueryResult,
+    self, BalanceRequest, BalanceResult, GetBidsRequest, GetEraValidatorsError, MultiQueryRequest,
+    MultiQueryResult, QueryRequest, QueryResult,
 };
 use casper_hashing::Digest;
 use casper_types::{system::auction::EraValidators, Key, ProtocolVersion, URef};
@@ -152,6 +152,22 @@ impl RpcServer {
             })
     }
 
+    fn handle_query_multi<REv: ReactorEventT>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        state_root_hash: Digest,
+        keys: Vec<Key>,
+        responder: Responder<Result<MultiQueryResult, engine_state::Error>>,
+    ) -> Effects<Event> {
+        let query = MultiQueryRequest::new(state_root_hash, keys);
+        effect_builder
+            .query_global_state_multi(query)
+            .event(move |result| Event::QueryGlobalStateMultiResult {
+                result,
+                main_responder: responder,
+            })
+    }
+
     fn handle_era_validators<REv: ReactorEventT>(
         &mut self,
         effect_builder: EffectBuilder<REv>,
@@ -224,6 +240,7 @@ where
                 Event::RpcRequest(_)
                 | Event::GetBlockTransfersResult { .. }
                 | Event::QueryGlobalStateResult { .. }
+                | Event::QueryGlobalStateMultiResult { .. }
                 | Event::QueryEraValidatorsResult { .. }
                 | Event::GetBidsResult { .. }
                 | Event::GetDeployResult { .. }
@@ -262,6 +279,11 @@ where
                     path,
                     responder,
                 }) => self.handle_query(effect_builder, state_root_hash, base_key, path, responder),
+                Event::RpcRequest(RpcRequest::QueryGlobalStateMulti {
+                    state_root_hash,
+                    keys,
+                    responder,
+                }) => self.handle_query_multi(effect_builder, state_root_hash, keys, responder),
                 Event::RpcRequest(RpcRequest::QueryEraValidators {
                     state_root_hash,
                     protocol_version,
@@ -381,6 +403,10 @@ where
                     result,
                     main_responder,
                 } => main_responder.respond(result).ignore(),
+                Event::QueryGlobalStateMultiResult {
+                    result,
+                    main_responder,
+                } => main_responder.respond(result).ignore(),
                 Event::QueryEraValidatorsResult {
                     result,
                     main_responder,
@@ -468,6 +494,7 @@ where
             self.api_version,
             cfg.qps_limit,
             cfg.max_body_bytes,
+            cfg.max_query_global_state_multi_keys,
             cfg.cors_origin.clone(),
         ));
 
@@ -10,6 +10,8 @@ const DEFAULT_QPS_LIMIT: u64 = 100;
 /// Default max body bytes.  This is 2.5MB which should be able to accommodate the largest valid
 /// JSON-RPC request, which would be an "account_put_deploy".
 const DEFAULT_MAX_BODY_BYTES: u32 = 2_621_440;
+/// Default maximum number of keys in a single "query_global_state_multi" request.
+const DEFAULT_MAX_QUERY_GLOBAL_STATE_MULTI_KEYS: u32 = 100;
 /// Default CORS origin.
 const DEFAULT_CORS_ORIGIN: &str = "";
 
@@ -26,6 +28,8 @@ pub struct Config {
     pub qps_limit: u64,
     /// Maximum number of bytes to accept in a single request body.
     pub max_body_bytes: u32,
+    /// Maximum number of keys to accept in a single "query_global_state_multi" request.
+    pub max_query_global_state_multi_keys: u32,
     /// CORS origin.
     pub cors_origin: String,
 }
@@ -38,6 +42,7 @@ impl Config {
             address: DEFAULT_ADDRESS.to_string(),
             qps_limit: DEFAULT_QPS_LIMIT,
             max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            max_query_global_state_multi_keys: DEFAULT_MAX_QUERY_GLOBAL_STATE_MULTI_KEYS,
             cors_origin: DEFAULT_CORS_ORIGIN.to_string(),
         }
     }
@@ -6,7 +6,7 @@ use std::{
 use derive_more::From;
 
 use casper_execution_engine::core::engine_state::{
-    self, BalanceResult, GetBidsResult, GetEraValidatorsError, QueryResult,
+    self, BalanceResult, GetBidsResult, GetEraValidatorsError, MultiQueryResult, QueryResult,
 };
 use casper_types::{system::auction::EraValidators, Transfer};
 
@@ -29,6 +29,10 @@ pub(crate) enum Event {
         result: Result<QueryResult, engine_state::Error>,
         main_responder: Responder<Result<QueryResult, engine_state::Error>>,
     },
+    QueryGlobalStateMultiResult {
+        result: Result<MultiQueryResult, engine_state::Error>,
+        main_responder: Responder<Result<MultiQueryResult, engine_state::Error>>,
+    },
     QueryEraValidatorsResult {
         result: Result<EraValidators, GetEraValidatorsError>,
         main_responder: Responder<Result<EraValidators, GetEraValidatorsError>>,
@@ -67,6 +71,9 @@ impl Display for Event {
             Event::QueryGlobalStateResult { result, .. } => {
                 write!(formatter, "query result: {:?}", result)
             }
+            Event::QueryGlobalStateMultiResult { result, .. } => {
+                write!(formatter, "multi query result: {:?}", result)
+            }
             Event::QueryEraValidatorsResult { result, .. } => {
                 write!(formatter, "query era validators result: {:?}", result)
             }
@@ -13,7 +13,7 @@ use super::{
         info::{GetChainspec, GetDeploy, GetPeers, GetStatus, GetValidatorChanges},
         state::{
             GetAccountInfo, GetAuctionInfo, GetBalance, GetDictionaryItem, GetItem, GetTrie,
-            QueryBalance, QueryGlobalState,
+            QueryBalance, QueryGlobalState, QueryGlobalStateMulti,
         },
         RpcWithOptionalParams, RpcWithParams, RpcWithoutParams,
     },
@@ -33,6 +33,7 @@ pub(super) async fn run<REv: ReactorEventT>(
     api_version: ProtocolVersion,
     qps_limit: u64,
     max_body_bytes: u32,
+    max_query_global_state_multi_keys: u32,
     cors_origin: String,
 ) {
     let mut handlers = RequestHandlersBuilder::new();
@@ -42,6 +43,12 @@ pub(super) async fn run<REv: ReactorEventT>(
     GetStateRootHash::register_as_handler(effect_builder, api_version, &mut handlers);
     GetItem::register_as_handler(effect_builder, api_version, &mut handlers);
     QueryGlobalState::register_as_handler(effect_builder, api_version, &mut handlers);
+    QueryGlobalStateMulti::register_as_handler_with_max_keys(
+        effect_builder,
+        api_version,
+        max_query_global_state_multi_keys,
+        &mut handlers,
+    );
     GetBalance::register_as_handler(effect_builder, api_version, &mut handlers);
     GetAccountInfo::register_as_handler(effect_builder, api_version, &mut handlers);
     GetDeploy::register_as_handler(effect_builder, api_version, &mut handlers);
@@ -35,6 +35,8 @@ pub enum ErrorCode {
     FailedToGetTrie = -32011,
     /// The requested state root hash was not found.
     NoSuchStateRoot = -32012,
+    /// A query requested more keys than allowed.
+    TooManyQueryKeys = -32013,
 }
 
 impl From<ErrorCode> for (i64, &'static str) {
@@ -59,6 +61,7 @@ impl From<ErrorCode> for (i64, &'static str) {
             }
             ErrorCode::FailedToGetTrie => (error_code as i64, "Failed to get trie"),
             ErrorCode::NoSuchStateRoot => (error_code as i64, "No such state root"),
+            ErrorCode::TooManyQueryKeys => (error_code as i64, "Too many query keys"),
         }
     }
 }
@@ -10,7 +10,7 @@ use once_cell::sync::Lazy;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use casper_execution_engine::core::engine_state::Error as EngineStateError;
+use casper_execution_engine::core::engine_state::{Error as EngineStateError, GasProfile};
 use casper_json_rpc::ReservedErrorCode;
 use casper_types::{ExecutionResult, ProtocolVersion};
 
@@ -29,11 +29,13 @@ use crate::{
 static SPECULATIVE_EXEC_PARAMS: Lazy<SpeculativeExecParams> = Lazy::new(|| SpeculativeExecParams {
     block_identifier: Some(BlockIdentifier::Hash(*Block::doc_example().hash())),
     deploy: Deploy::doc_example().clone(),
+    gas_profiling: false,
 });
 static SPECULATIVE_EXEC_RESULT: Lazy<SpeculativeExecResult> = Lazy::new(|| SpeculativeExecResult {
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     block_hash: *Block::doc_example().hash(),
     execution_result: ExecutionResult::example().clone(),
+    gas_profile: None,
 });
 
 /// Params for "speculative_exec" RPC request.
@@ -44,6 +46,10 @@ pub struct SpeculativeExecParams {
     pub block_identifier: Option<BlockIdentifier>,
     /// Deploy to execute.
     pub deploy: Deploy,
+    /// Whether to also return a profile of the gas charged by the deploy. If `false` or omitted,
+    /// no profile is returned.
+    #[serde(default)]
+    pub gas_profiling: bool,
 }
 
 impl DocExample for SpeculativeExecParams {
@@ -63,6 +69,9 @@ pub struct SpeculativeExecResult {
     pub block_hash: BlockHash,
     /// Result of the execution.
     pub execution_result: ExecutionResult,
+    /// Profile of the gas charged by the execution, if requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_profile: Option<GasProfile>,
 }
 
 impl DocExample for SpeculativeExecResult {
@@ -88,6 +97,7 @@ impl RpcWithParams for SpeculativeExec {
         let SpeculativeExecParams {
             block_identifier: maybe_block_id,
             deploy,
+            gas_profiling,
         } = params;
         let deploy = Arc::new(deploy);
         let only_from_available_block_range = true;
@@ -114,15 +124,16 @@ impl RpcWithParams for SpeculativeExec {
         }
 
         let result = effect_builder
-            .speculative_execute_deploy(execution_prestate, Arc::clone(&deploy))
+            .speculative_execute_deploy(execution_prestate, Arc::clone(&deploy), gas_profiling)
             .await;
 
         match result {
-            Ok(Some(execution_result)) => {
+            Ok(Some((execution_result, gas_profile))) => {
                 let result = Self::ResponseResult {
                     api_version,
                     block_hash,
                     execution_result,
+                    gas_profile,
                 };
                 Ok(result)
             }
@@ -3,7 +3,7 @@
 // TODO - remove once schemars stops causing warning.
 #![allow(clippy::field_reassign_with_default)]
 
-use std::str;
+use std::{str, sync::Arc};
 
 use async_trait::async_trait;
 use once_cell::sync::Lazy;
@@ -12,14 +12,18 @@ use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info, warn};
 
 use casper_execution_engine::{
-    core::engine_state::{self, BalanceResult, GetBidsResult, QueryResult},
-    storage::trie::merkle_proof::TrieMerkleProof,
+    core::engine_state::{self, BalanceResult, GetBidsResult, MultiQueryResult, QueryResult},
+    storage::trie::{
+        merkle_proof::{TrieMerkleMultiProof, TrieMerkleProof},
+        Pointer, Trie,
+    },
 };
 use casper_hashing::Digest;
-use casper_json_rpc::ReservedErrorCode;
+use casper_json_rpc::{RequestHandlersBuilder, ReservedErrorCode};
 use casper_types::{
-    account::AccountHash,
+    account::{Account as DomainAccount, AccountHash},
     bytesrepr::{Bytes, ToBytes},
+    contracts::NamedKeys,
     CLValue, Key, ProtocolVersion, PublicKey, SecretKey, StoredValue as DomainStoredValue, URef,
     U512,
 };
@@ -112,6 +116,69 @@ static QUERY_GLOBAL_STATE_RESULT: Lazy<QueryGlobalStateResult> =
         stored_value: StoredValue::Account(JsonAccount::doc_example().clone()),
         merkle_proof: MERKLE_PROOF.clone(),
     });
+/// An account and the balance of its main purse, which are queried together in the examples of
+/// "query_global_state_multi".
+static QUERY_GLOBAL_STATE_MULTI_ENTRIES: Lazy<Vec<(Key, DomainStoredValue)>> = Lazy::new(|| {
+    let secret_key = SecretKey::ed25519_from_bytes([0; 32]).unwrap();
+    let account_hash = PublicKey::from(&secret_key).to_account_hash();
+    let main_purse = URef::from_formatted_str(
+        "uref-09480c3248ef76b603d386f3f4f8a5f87f597d4eaffd475433f861af187ab5db-007",
+    )
+    .unwrap();
+    let account = DomainAccount::create(account_hash, NamedKeys::new(), main_purse);
+    let balance = CLValue::from_t(U512::from(123_456_000_000_000u64)).unwrap();
+    vec![
+        (
+            Key::Account(account_hash),
+            DomainStoredValue::Account(account),
+        ),
+        (
+            Key::Balance(main_purse.addr()),
+            DomainStoredValue::CLValue(balance),
+        ),
+    ]
+});
+static QUERY_GLOBAL_STATE_MULTI_PARAMS: Lazy<QueryGlobalStateMultiParams> =
+    Lazy::new(|| QueryGlobalStateMultiParams {
+        state_identifier: Some(GlobalStateIdentifier::BlockHash(
+            *Block::doc_example().hash(),
+        )),
+        keys: QUERY_GLOBAL_STATE_MULTI_ENTRIES
+            .iter()
+            .map(|(key, _)| key.to_formatted_string())
+            .collect(),
+    });
+static QUERY_GLOBAL_STATE_MULTI_RESULT: Lazy<QueryGlobalStateMultiResult> = Lazy::new(|| {
+    // The entries are the only leaves of a trie whose root node points to both of them, as their
+    // keys differ in their first byte.
+    let leaves: Vec<Trie<Key, DomainStoredValue>> = QUERY_GLOBAL_STATE_MULTI_ENTRIES
+        .iter()
+        .map(|(key, value)| Trie::leaf(*key, value.clone()))
+        .collect();
+    let pointers: Vec<(u8, Pointer)> = QUERY_GLOBAL_STATE_MULTI_ENTRIES
+        .iter()
+        .zip(&leaves)
+        .map(|((key, _), leaf)| {
+            (
+                key.to_bytes().unwrap()[0],
+                Pointer::LeafPointer(leaf.trie_hash().unwrap()),
+            )
+        })
+        .collect();
+    let mut tries = vec![Trie::node(&pointers)];
+    tries.extend(leaves);
+    let merkle_proof = TrieMerkleMultiProof::new(tries);
+
+    QueryGlobalStateMultiResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        block_header: Some(JsonBlockHeader::doc_example().clone()),
+        stored_values: QUERY_GLOBAL_STATE_MULTI_ENTRIES
+            .iter()
+            .map(|(_, value)| StoredValue::try_from(value.clone()).unwrap())
+            .collect(),
+        merkle_proof: base16::encode_lower(&merkle_proof.to_bytes().unwrap()),
+    }
+});
 static GET_TRIE_PARAMS: Lazy<GetTrieParams> = Lazy::new(|| GetTrieParams {
     trie_key: *Block::doc_example().header().state_root_hash(),
 });
@@ -888,6 +955,194 @@ impl RpcWithParams for QueryGlobalState {
     }
 }
 
+/// Params for "query_global_state_multi" RPC
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct QueryGlobalStateMultiParams {
+    /// The identifier used for the query. If none is passed
+    /// the tip of the chain will be used.
+    pub state_identifier: Option<GlobalStateIdentifier>,
+    /// `casper_types::Key`s as formatted strings, at most as many as the node's
+    /// `rpc_server.max_query_global_state_multi_keys` config option allows.
+    pub keys: Vec<String>,
+}
+
+impl DocExample for QueryGlobalStateMultiParams {
+    fn doc_example() -> &'static Self {
+        &QUERY_GLOBAL_STATE_MULTI_PARAMS
+    }
+}
+
+/// Result for "query_global_state_multi" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct QueryGlobalStateMultiResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The block header if a Block hash was provided.
+    pub block_header: Option<JsonBlockHeader>,
+    /// The stored values, in the same order as the requested keys.
+    pub stored_values: Vec<StoredValue>,
+    /// The single Merkle proof covering all of the stored values.
+    pub merkle_proof: String,
+}
+
+impl DocExample for QueryGlobalStateMultiResult {
+    fn doc_example() -> &'static Self {
+        &QUERY_GLOBAL_STATE_MULTI_RESULT
+    }
+}
+
+/// "query_global_state_multi" RPC
+pub struct QueryGlobalStateMulti {}
+
+impl QueryGlobalStateMulti {
+    /// Registers this RPC as the handler for JSON-RPC requests whose "method" field is the same as
+    /// `Self::METHOD`, rejecting requests for more than `max_keys` keys.
+    pub(in crate::components::rpc_server) fn register_as_handler_with_max_keys<
+        REv: ReactorEventT,
+    >(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        max_keys: u32,
+        handlers_builder: &mut RequestHandlersBuilder,
+    ) {
+        let handler = move |maybe_params| async move {
+            let params = Self::try_parse_params(maybe_params)?;
+            if params.keys.len() > max_keys as usize {
+                let error_msg = format!(
+                    "too many keys: {} requested, at most {} allowed",
+                    params.keys.len(),
+                    max_keys
+                );
+                info!("{}", error_msg);
+                return Err(Error::new(ErrorCode::TooManyQueryKeys, error_msg));
+            }
+            Self::do_handle_request(effect_builder, api_version, params).await
+        };
+        handlers_builder.register_handler(Self::METHOD, Arc::new(handler))
+    }
+}
+
+#[async_trait]
+impl RpcWithParams for QueryGlobalStateMulti {
+    const METHOD: &'static str = "query_global_state_multi";
+    type RequestParams = QueryGlobalStateMultiParams;
+    type ResponseResult = QueryGlobalStateMultiResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        let (state_root_hash, maybe_block_header) = match params.state_identifier {
+            None => match effect_builder
+                .get_highest_complete_block_header_from_storage()
+                .await
+            {
+                None => {
+                    return Err(Error::new(
+                        ErrorCode::NoSuchBlock,
+                        "query-global-state-multi failed to retrieve highest block header",
+                    ))
+                }
+                Some(block_header) => (
+                    *block_header.state_root_hash(),
+                    Some(JsonBlockHeader::from(block_header.clone())),
+                ),
+            },
+            Some(state_identifier) => {
+                get_state_root_hash_and_optional_header(effect_builder, state_identifier).await?
+            }
+        };
+
+        let mut keys = Vec::with_capacity(params.keys.len());
+        for key in &params.keys {
+            match Key::from_formatted_str(key) {
+                Ok(key) => keys.push(key),
+                Err(error) => {
+                    let error_msg = format!("failed to parse key {}: {}", key, error);
+                    info!("{}", error_msg);
+                    return Err(Error::new(ErrorCode::FailedToParseQueryKey, error_msg));
+                }
+            }
+        }
+
+        let query_result = effect_builder
+            .make_request(
+                |responder| RpcRequest::QueryGlobalStateMulti {
+                    state_root_hash,
+                    keys,
+                    responder,
+                },
+                QueueKind::Api,
+            )
+            .await;
+
+        let (values, proof) = match query_result {
+            Ok(MultiQueryResult::Success { values, proof }) => (values, proof),
+            Ok(MultiQueryResult::RootNotFound) => {
+                info!("multi query failed: root not found");
+                let error = common::missing_block_or_state_root_error(
+                    effect_builder,
+                    ErrorCode::NoSuchStateRoot,
+                    format!("failed to get state root at {:?}", state_root_hash),
+                )
+                .await;
+                return Err(error);
+            }
+            Ok(query_result) => {
+                debug!(?query_result, "multi query failed");
+                return Err(Error::new(
+                    ErrorCode::QueryFailed,
+                    format!("{:?}", query_result),
+                ));
+            }
+            Err(error) => {
+                info!(?error, "multi query failed to execute");
+                return Err(Error::new(
+                    ErrorCode::QueryFailedToExecute,
+                    format!("{:?}", error),
+                ));
+            }
+        };
+
+        let mut stored_values = Vec::with_capacity(values.len());
+        for value in values {
+            match StoredValue::try_from(value) {
+                Ok(value_compat) => stored_values.push(value_compat),
+                Err(error) => {
+                    warn!(?error, "failed to encode stored value");
+                    return Err(Error::new(
+                        ReservedErrorCode::InternalError,
+                        format!("failed to encode stored value: {}", error),
+                    ));
+                }
+            }
+        }
+
+        let merkle_proof = match proof.to_bytes() {
+            Ok(bytes) => base16::encode_lower(&bytes),
+            Err(error) => {
+                warn!(?error, "failed to encode proof");
+                return Err(Error::new(
+                    ReservedErrorCode::InternalError,
+                    format!("failed to encode proof: {}", error),
+                ));
+            }
+        };
+
+        let result = Self::ResponseResult {
+            api_version,
+            block_header: maybe_block_header,
+            stored_values,
+            merkle_proof,
+        };
+        Ok(result)
+    }
+}
+
 /// Identifier of a purse.
 #[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
@@ -35,14 +35,13 @@ mod error;
 mod lmdb_ext;
 mod metrics;
 mod object_pool;
+mod reader;
 #[cfg(test)]
 mod tests;
 
-#[cfg(test)]
-use std::collections::BTreeSet;
 use std::{
     borrow::Cow,
-    collections::{btree_map, hash_map, BTreeMap, HashMap, HashSet},
+    collections::{btree_map, hash_map, BTreeMap, BTreeSet, HashMap, HashSet},
     convert::{TryFrom, TryInto},
     fmt::{self, Display, Formatter},
     fs::{self, OpenOptions},
@@ -51,6 +50,7 @@ use std::{
     path::{Path, PathBuf},
     rc::Rc,
     sync::Arc,
+    time::Duration,
 };
 
 use datasize::DataSize;
@@ -68,6 +68,7 @@ use static_assertions::const_assert;
 use tempfile::TempDir;
 use tracing::{debug, error, info, trace, warn};
 
+use casper_execution_engine::core::engine_state::executable_deploy_item::ExecutableDeployItem;
 use casper_hashing::Digest;
 use casper_types::{
     bytesrepr::{FromBytes, ToBytes},
@@ -104,9 +105,12 @@ use crate::{
 use disjoint_sequences::{DisjointSequences, Sequence};
 pub use error::FatalStorageError;
 use error::GetRequestError;
-use lmdb_ext::{BytesreprError, LmdbExtError, TransactionExt, WriteTransactionExt};
+use lmdb_ext::{
+    BytesreprError, LmdbExtError, RecordCompressor, TransactionExt, WriteTransactionExt,
+};
 use metrics::Metrics;
 use object_pool::ObjectPool;
+pub use reader::StorageReader;
 
 const COMPONENT_NAME: &str = "storage";
 
@@ -117,6 +121,10 @@ const STORAGE_DB_FILENAME: &str = "storage.lmdb";
 /// one time.
 const MAX_TRANSACTIONS: u32 = 1;
 
+/// Number of additional reader slots, for read-only access by other processes via a
+/// [`StorageReader`].
+const MAX_SECONDARY_READERS: u32 = 16;
+
 /// One Gibibyte.
 const GIB: usize = 1024 * 1024 * 1024;
 
@@ -134,6 +142,14 @@ const MAX_DB_COUNT: u32 = 9;
 const COMPLETED_BLOCKS_STORAGE_KEY: &[u8] = b"completed_blocks_disjoint_sequences";
 /// Name of the file created when initializing a force resync.
 const FORCE_RESYNC_FILE_NAME: &str = "force_resync";
+/// Key under which the zstd dictionary trained on deploy Wasm payloads is stored.
+const ZSTD_DEPLOY_DICTIONARY_STORAGE_KEY: &[u8] = b"zstd_deploy_dictionary";
+/// Minimum number of Wasm payloads required to train a zstd dictionary.
+const MIN_DICTIONARY_TRAINING_SAMPLES: usize = 32;
+/// Maximum number of Wasm payloads used to train a zstd dictionary.
+const MAX_DICTIONARY_TRAINING_SAMPLES: usize = 10_000;
+/// Delay between two steps of the background recompression of uncompressed records.
+const RECOMPRESSION_INTERVAL: Duration = Duration::from_millis(100);
 
 /// OS-specific lmdb flags.
 #[cfg(not(target_os = "macos"))]
@@ -217,6 +233,25 @@ pub struct Storage {
     metrics: Option<Metrics>,
     /// The maximum TTL of a deploy.
     max_ttl: MaxTtl,
+    /// Compression applied to newly written block bodies and deploy metadata, if enabled.
+    compressor: Option<RecordCompressor>,
+    /// Compression applied to newly written deploys, if enabled.
+    ///
+    /// Uses the dictionary trained on deploy Wasm payloads, if there is one.
+    deploy_compressor: Option<RecordCompressor>,
+    /// The number of records examined per background recompression step.
+    recompression_batch_size: usize,
+    /// How far the background recompression of uncompressed records has progressed.
+    recompression_progress: RecompressionProgress,
+}
+
+/// Position of the background recompression of records written uncompressed.
+#[derive(Clone, DataSize, Debug, Default)]
+struct RecompressionProgress {
+    /// Index of the database currently being recompressed, see [`Storage::recompressed_dbs`].
+    db_index: usize,
+    /// The last key examined in the current database.
+    last_key: Option<Vec<u8>>,
 }
 
 /// A storage component event.
@@ -234,6 +269,8 @@ pub(crate) enum Event {
     /// Make block executable request.
     #[from]
     MakeBlockExecutableRequest(Box<MakeBlockExecutableRequest>),
+    /// Recompress the next batch of records written uncompressed.
+    RecompressRecords,
 }
 
 impl Display for Event {
@@ -243,6 +280,7 @@ impl Display for Event {
             Event::NetRequestIncoming(incoming) => incoming.fmt(f),
             Event::MarkBlockCompletedRequest(req) => req.fmt(f),
             Event::MakeBlockExecutableRequest(req) => req.fmt(f),
+            Event::RecompressRecords => write!(f, "recompress records"),
         }
     }
 }
@@ -336,6 +374,16 @@ where
                     Err(err) => Err(err),
                 }
             }
+            Event::RecompressRecords => match self.recompress_records() {
+                Ok(true) => {
+                    info!("background recompression of storage records complete");
+                    Ok(Effects::new())
+                }
+                Ok(false) => Ok(effect_builder
+                    .set_timeout(RECOMPRESSION_INTERVAL)
+                    .event(|_| Event::RecompressRecords)),
+                Err(err) => Err(err),
+            },
         };
 
         // Any error is turned into a fatal effect, the component itself does not panic. Note that
@@ -401,7 +449,7 @@ impl Storage {
                 // Disable read-ahead. Our data is not stored/read in sequence that would benefit from the read-ahead.
                 | EnvironmentFlags::NO_READAHEAD,
             )
-            .set_max_readers(MAX_TRANSACTIONS)
+            .set_max_readers(MAX_TRANSACTIONS + MAX_SECONDARY_READERS)
             .set_max_dbs(MAX_DB_COUNT)
             .set_map_size(total_size)
             .open(&root.join(STORAGE_DB_FILENAME))?;
@@ -519,8 +567,14 @@ impl Storage {
             recent_era_count,
             max_ttl,
             metrics,
+            compressor: None,
+            deploy_compressor: None,
+            recompression_batch_size: config.compression.recompression_batch_size,
+            recompression_progress: RecompressionProgress::default(),
         };
 
+        component.initialize_compression(&config.compression)?;
+
         if force_resync {
             let force_resync_file_path = component.root_path().join(FORCE_RESYNC_FILE_NAME);
             // Check if resync is already in progress. Force resync will kick
@@ -636,6 +690,163 @@ impl Storage {
         Ok(())
     }
 
+    /// Sets up record compression according to the config.
+    ///
+    /// A previously trained deploy dictionary is registered even if compression is disabled, since
+    /// records compressed with it may still be in storage.
+    fn initialize_compression(
+        &mut self,
+        config: &CompressionConfig,
+    ) -> Result<(), FatalStorageError> {
+        let mut maybe_dictionary =
+            self.read_state_store(&Cow::Borrowed(ZSTD_DEPLOY_DICTIONARY_STORAGE_KEY))?;
+        if let Some(dictionary) = &maybe_dictionary {
+            lmdb_ext::register_zstd_dictionary(dictionary)?;
+        }
+
+        if !config.enabled {
+            return Ok(());
+        }
+
+        if maybe_dictionary.is_none() && config.train_wasm_dictionary {
+            maybe_dictionary = self.train_wasm_dictionary(config.max_dictionary_size)?;
+            if let Some(dictionary) = &maybe_dictionary {
+                self.write_state_store(
+                    Cow::Borrowed(ZSTD_DEPLOY_DICTIONARY_STORAGE_KEY),
+                    dictionary,
+                )?;
+            }
+        }
+
+        let compressor = RecordCompressor::new(config.level);
+        self.deploy_compressor = match &maybe_dictionary {
+            Some(dictionary) => Some(compressor.with_dictionary(dictionary)?),
+            None => Some(compressor.clone()),
+        };
+        self.compressor = Some(compressor);
+        Ok(())
+    }
+
+    /// Trains a zstd dictionary on the Wasm payloads of the stored deploys.
+    ///
+    /// Returns `None` if there are too few payloads or training fails.
+    fn train_wasm_dictionary(&self, max_size: usize) -> Result<Option<Vec<u8>>, FatalStorageError> {
+        info!("collecting deploy wasm payloads for zstd dictionary training");
+        let mut samples = Vec::new();
+        let txn = self.env.begin_ro_txn()?;
+        let mut cursor = txn.open_ro_cursor(self.deploy_db)?;
+        for row in cursor.iter() {
+            let (_, raw_val) = row?;
+            let deploy: Deploy = match lmdb_ext::deserialize_internal(raw_val)? {
+                Some(deploy) => deploy,
+                None => continue,
+            };
+            for item in [deploy.payment(), deploy.session()] {
+                if let ExecutableDeployItem::ModuleBytes { module_bytes, .. } = item {
+                    if !module_bytes.is_empty() {
+                        samples.push(module_bytes.to_vec());
+                    }
+                }
+            }
+            if samples.len() >= MAX_DICTIONARY_TRAINING_SAMPLES {
+                break;
+            }
+        }
+        drop(cursor);
+        txn.commit()?;
+
+        if samples.len() < MIN_DICTIONARY_TRAINING_SAMPLES {
+            info!(
+                sample_count = samples.len(),
+                "too few deploy wasm payloads to train a zstd dictionary"
+            );
+            return Ok(None);
+        }
+        match lmdb_ext::train_zstd_dictionary(&samples, max_size) {
+            Ok(dictionary) => {
+                info!(
+                    sample_count = samples.len(),
+                    dictionary_size = dictionary.len(),
+                    "trained zstd dictionary on deploy wasm payloads"
+                );
+                Ok(Some(dictionary))
+            }
+            Err(err) => {
+                warn!(%err, "failed to train zstd dictionary on deploy wasm payloads");
+                Ok(None)
+            }
+        }
+    }
+
+    /// Returns `true` if uncompressed records should be recompressed in the background.
+    pub(crate) fn should_recompress_records(&self) -> bool {
+        self.compressor.is_some() && self.recompression_batch_size > 0
+    }
+
+    /// Returns the databases subject to background recompression, along with the compressor to
+    /// use for each of them.
+    fn recompressed_dbs(&self) -> Vec<(Database, RecordCompressor)> {
+        match (&self.deploy_compressor, &self.compressor) {
+            (Some(deploy_compressor), Some(compressor)) => vec![
+                (self.deploy_db, deploy_compressor.clone()),
+                (self.block_body_db, compressor.clone()),
+                (self.deploy_metadata_db, compressor.clone()),
+            ],
+            _ => vec![],
+        }
+    }
+
+    /// Compresses the next batch of records which were written uncompressed.
+    ///
+    /// Returns `true` once all databases have been fully processed.
+    fn recompress_records(&mut self) -> Result<bool, FatalStorageError> {
+        let dbs = self.recompressed_dbs();
+        let mut remaining = self.recompression_batch_size;
+        let mut txn = self.env.begin_rw_txn()?;
+        while let Some((db, compressor)) = dbs.get(self.recompression_progress.db_index) {
+            let mut recompressed = vec![];
+            let mut exhausted = true;
+            {
+                let mut cursor = txn.open_ro_cursor(*db)?;
+                let last_key = self.recompression_progress.last_key.clone();
+                let iter = match &last_key {
+                    Some(last_key) => cursor.iter_from(last_key),
+                    None => cursor.iter(),
+                };
+                for row in iter {
+                    let (key, raw_val) = row?;
+                    if last_key.as_deref() == Some(key) {
+                        continue;
+                    }
+                    if remaining == 0 {
+                        exhausted = false;
+                        break;
+                    }
+                    remaining -= 1;
+                    self.recompression_progress.last_key = Some(key.to_vec());
+                    if !lmdb_ext::is_compressed(raw_val) {
+                        let compressed = compressor.compress(raw_val)?;
+                        if compressed.len() < raw_val.len() {
+                            recompressed.push((key.to_vec(), compressed));
+                        }
+                    }
+                }
+            }
+            for (key, value) in recompressed {
+                txn.put(*db, &key, &value, WriteFlags::empty())?;
+            }
+            if !exhausted {
+                break;
+            }
+            self.recompression_progress = RecompressionProgress {
+                db_index: self.recompression_progress.db_index + 1,
+                last_key: None,
+            };
+        }
+        txn.commit()?;
+        Ok(self.recompression_progress.db_index >= dbs.len())
+    }
+
     /// Returns the path to the storage folder.
     pub(crate) fn root_path(&self) -> &Path {
         &self.root
@@ -834,6 +1045,19 @@ impl Storage {
             } => responder
                 .respond(self.get_deploys_era_ids(deploy_hashes))
                 .ignore(),
+            StorageRequest::GetExecutedDeploys {
+                deploy_hashes,
+                responder,
+            } => responder
+                .respond(self.get_executed_deploys(deploy_hashes))
+                .ignore(),
+            StorageRequest::GetDeploysInSlots {
+                slots,
+                deploy_hashes,
+                responder,
+            } => responder
+                .respond(self.get_deploys_in_slots(&slots, deploy_hashes)?)
+                .ignore(),
             StorageRequest::GetBlockHeader {
                 block_hash,
                 only_from_available_block_range,
@@ -1252,7 +1476,13 @@ impl Storage {
     pub fn put_deploy(&self, deploy: &Deploy) -> Result<bool, FatalStorageError> {
         let mut txn = self.env.begin_rw_txn()?;
         let deploy_hash = deploy.hash();
-        let outcome = txn.put_value(self.deploy_db, deploy_hash, deploy, false)?;
+        let outcome = txn.put_value_compressed(
+            self.deploy_db,
+            deploy_hash,
+            deploy,
+            false,
+            self.deploy_compressor.as_ref(),
+        )?;
         if outcome {
             debug!(%deploy_hash, "Storage: new deploy stored");
         } else {
@@ -1491,8 +1721,13 @@ impl Storage {
                 }
             }
 
-            let was_written =
-                txn.put_value(self.deploy_metadata_db, &deploy_hash, &metadata, true)?;
+            let was_written = txn.put_value_compressed(
+                self.deploy_metadata_db,
+                &deploy_hash,
+                &metadata,
+                true,
+                self.compressor.as_ref(),
+            )?;
             if !was_written {
                 error!(?block_hash, ?deploy_hash, "failed to write deploy metadata");
                 debug_assert!(was_written);
@@ -1797,6 +2032,54 @@ impl Storage {
             .collect()
     }
 
+    /// Returns those of the given deploys which were included in a stored block.
+    fn get_executed_deploys(&self, deploy_hashes: HashSet<DeployHash>) -> HashSet<DeployHash> {
+        deploy_hashes
+            .into_iter()
+            .filter(|deploy_hash| self.deploy_hash_index.contains_key(deploy_hash))
+            .collect()
+    }
+
+    /// Returns those of the given deploys, and of the deploys included in a stored block, which
+    /// take one of the given slots.
+    ///
+    /// A deploy can only be included in a block no earlier than its own timestamp, so only blocks
+    /// from the earliest slot's timestamp onwards are read.  The given deploys which are included
+    /// in a stored block are covered by these, and those not held in storage are skipped.
+    fn get_deploys_in_slots(
+        &self,
+        slots: &BTreeSet<(PublicKey, Timestamp)>,
+        deploy_hashes: BTreeSet<DeployHash>,
+    ) -> Result<HashSet<DeployHash>, FatalStorageError> {
+        let earliest = match slots.iter().map(|(_, timestamp)| *timestamp).min() {
+            Some(earliest) => earliest,
+            None => return Ok(HashSet::new()),
+        };
+        let mut txn = self.env.begin_ro_txn()?;
+        let blocks = self.get_blocks_while(&mut txn, |block| block.timestamp() >= earliest)?;
+        let candidates: HashSet<DeployHash> = blocks
+            .iter()
+            .flat_map(|block| block.deploy_and_transfer_hashes().copied())
+            .chain(
+                deploy_hashes
+                    .into_iter()
+                    .filter(|deploy_hash| !self.deploy_hash_index.contains_key(deploy_hash)),
+            )
+            .collect();
+        let mut in_slots = HashSet::new();
+        for deploy_hash in candidates {
+            let deploy: Deploy = match txn.get_value(self.deploy_db, &deploy_hash)? {
+                Some(deploy) => deploy,
+                None => continue,
+            };
+            let slot = (deploy.header().account().clone(), deploy.header().timestamp());
+            if slots.contains(&slot) {
+                in_slots.insert(deploy_hash);
+            }
+        }
+        Ok(in_slots)
+    }
+
     /// Retrieves the block hash and height for a deploy hash by looking it up in the index
     /// and returning it.
     fn get_block_hash_and_height_by_deploy_hash(
@@ -2110,8 +2393,14 @@ impl Storage {
         block_body_hash: &Digest,
         block_body: &BlockBody,
     ) -> Result<bool, LmdbExtError> {
-        txn.put_value(self.block_body_db, block_body_hash, block_body, true)
-            .map_err(Into::into)
+        txn.put_value_compressed(
+            self.block_body_db,
+            block_body_hash,
+            block_body,
+            true,
+            self.compressor.as_ref(),
+        )
+        .map_err(Into::into)
     }
 
     /// Retrieves a block header by hash.
@@ -2813,6 +3102,9 @@ pub struct Config {
     pub enable_mem_deduplication: bool,
     /// How many loads before memory duplication checks for dead references.
     pub mem_pool_prune_interval: u16,
+    /// Compression of block bodies, deploys and deploy metadata.
+    #[serde(default)]
+    pub compression: CompressionConfig,
 }
 
 impl Default for Config {
@@ -2826,6 +3118,39 @@ impl Default for Config {
             max_state_store_size: DEFAULT_MAX_STATE_STORE_SIZE,
             enable_mem_deduplication: true,
             mem_pool_prune_interval: 4096,
+            compression: CompressionConfig::default(),
+        }
+    }
+}
+
+/// Storage compression configuration.
+#[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct CompressionConfig {
+    /// Whether newly written block bodies, deploys and deploy metadata are zstd-compressed.
+    ///
+    /// Records are readable regardless of this setting.
+    pub enabled: bool,
+    /// The zstd compression level.
+    pub level: i32,
+    /// Whether to train a zstd dictionary on the Wasm payloads of stored deploys, which is then
+    /// used when compressing deploys.
+    pub train_wasm_dictionary: bool,
+    /// The maximum size in bytes of the trained dictionary.
+    pub max_dictionary_size: usize,
+    /// The number of records examined per step of the background recompression of records written
+    /// uncompressed.  Setting this to 0 disables background recompression.
+    pub recompression_batch_size: usize,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        CompressionConfig {
+            enabled: false,
+            level: 3,
+            train_wasm_dictionary: false,
+            max_dictionary_size: 112_640,
+            recompression_batch_size: 1_000,
         }
     }
 }
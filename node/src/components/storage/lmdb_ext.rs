@@ -9,10 +9,23 @@
 //!
 //! Serialization errors are unified into a generic, type erased `std` error to allow for easy
 //! interchange of the serialization format if desired.
+//!
+//! ## Compression
+//!
+//! Records can optionally be written zstd-compressed, in which case they are prefixed with a short
+//! header. Reading detects the header and decompresses transparently, so records written
+//! uncompressed (e.g. by earlier versions) remain readable.
+
+use std::{
+    any::TypeId,
+    borrow::Cow,
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
 
-use std::any::TypeId;
-
+use datasize::DataSize;
 use lmdb::{Database, RwTransaction, Transaction, WriteFlags};
+use once_cell::sync::Lazy;
 use serde::{de::DeserializeOwned, Serialize};
 use thiserror::Error;
 
@@ -23,6 +36,18 @@ use casper_types::{
 
 const UNBONDING_PURSE_V2_MAGIC_BYTES: &[u8] = &[121, 17, 133, 179, 91, 63, 69, 222];
 
+/// Header prefixed to zstd-compressed records: the zstd frame magic number followed by `cspr`.
+///
+/// None of the uncompressed record types can begin with these bytes, as they either start with a
+/// hash, an enum tag or a small length prefix.
+const ZSTD_RECORD_MAGIC_BYTES: &[u8] = &[40, 181, 47, 253, 99, 115, 112, 114];
+
+/// Zstd dictionaries known to this process, keyed by their dictionary ID.
+///
+/// Dictionary-compressed records only carry the ID of their dictionary in the zstd frame header, so
+/// the dictionary has to be registered here before such records can be read.
+static ZSTD_DICTIONARIES: Lazy<RwLock<HashMap<u32, Arc<[u8]>>>> = Lazy::new(Default::default);
+
 /// Error wrapper for lower-level storage errors.
 ///
 /// Used to classify storage errors, allowing more accurate reporting on potential issues and
@@ -51,6 +76,120 @@ pub enum LmdbExtError {
 #[error("{0}")]
 pub struct BytesreprError(pub bytesrepr::Error);
 
+/// A compressed record refers to a zstd dictionary which has not been registered.
+#[derive(Debug, Error)]
+#[error("unknown zstd dictionary {0}")]
+pub struct UnknownDictionaryError(pub u32);
+
+/// Zstd compression applied to records written via
+/// [`WriteTransactionExt::put_value_compressed`].
+#[derive(Clone, DataSize, Debug)]
+pub(crate) struct RecordCompressor {
+    /// The zstd compression level.
+    level: i32,
+    /// The dictionary to compress with, if any.
+    #[data_size(skip)]
+    dictionary: Option<Arc<[u8]>>,
+}
+
+impl RecordCompressor {
+    /// Creates a new compressor using the given zstd compression level and no dictionary.
+    pub(crate) fn new(level: i32) -> Self {
+        RecordCompressor {
+            level,
+            dictionary: None,
+        }
+    }
+
+    /// Returns a compressor with the same level using the given zstd dictionary.
+    ///
+    /// The dictionary is registered so that records compressed with it can be read back.
+    pub(crate) fn with_dictionary(&self, dictionary: &[u8]) -> Result<Self, LmdbExtError> {
+        Ok(RecordCompressor {
+            level: self.level,
+            dictionary: Some(register_zstd_dictionary(dictionary)?),
+        })
+    }
+
+    /// Compresses an already serialized record, prefixing it with the compressed record header.
+    ///
+    /// If compression does not make the record smaller, it is returned unchanged.
+    pub(crate) fn compress(&self, raw: &[u8]) -> Result<Vec<u8>, LmdbExtError> {
+        let compressed = match self.dictionary {
+            Some(ref dictionary) => zstd::bulk::Compressor::with_dictionary(self.level, dictionary)
+                .and_then(|mut compressor| compressor.compress(raw)),
+            None => zstd::bulk::compress(raw, self.level),
+        }
+        .map_err(|err| LmdbExtError::Other(Box::new(err)))?;
+
+        if ZSTD_RECORD_MAGIC_BYTES.len() + compressed.len() >= raw.len() {
+            return Ok(raw.to_vec());
+        }
+        let mut buffer = Vec::with_capacity(ZSTD_RECORD_MAGIC_BYTES.len() + compressed.len());
+        buffer.extend_from_slice(ZSTD_RECORD_MAGIC_BYTES);
+        buffer.extend(compressed);
+        Ok(buffer)
+    }
+}
+
+/// Registers a zstd dictionary for decompressing records, returning a shared copy of it.
+///
+/// Fails if the dictionary has no ID, as records compressed with it could not be attributed.
+pub(crate) fn register_zstd_dictionary(dictionary: &[u8]) -> Result<Arc<[u8]>, LmdbExtError> {
+    let id = zstd::zstd_safe::get_dict_id_from_dict(dictionary)
+        .ok_or_else(|| LmdbExtError::Other("zstd dictionary has no dictionary ID".into()))?;
+    let mut dictionaries = ZSTD_DICTIONARIES
+        .write()
+        .map_err(|_| LmdbExtError::Other("zstd dictionary registry poisoned".into()))?;
+    let dictionary = dictionaries
+        .entry(id.get())
+        .or_insert_with(|| Arc::from(dictionary));
+    Ok(Arc::clone(dictionary))
+}
+
+/// Trains a zstd dictionary of at most `max_size` bytes on the given samples.
+pub(crate) fn train_zstd_dictionary<S: AsRef<[u8]>>(
+    samples: &[S],
+    max_size: usize,
+) -> Result<Vec<u8>, LmdbExtError> {
+    zstd::dict::from_samples(samples, max_size).map_err(|err| LmdbExtError::Other(Box::new(err)))
+}
+
+/// Returns `true` if the raw record is compressed.
+pub(crate) fn is_compressed(raw: &[u8]) -> bool {
+    raw.starts_with(ZSTD_RECORD_MAGIC_BYTES)
+}
+
+/// Decompresses a raw record if it carries the compressed record header, otherwise returns it as
+/// is.
+pub(crate) fn decompress(raw: &[u8]) -> Result<Cow<[u8]>, LmdbExtError> {
+    if !is_compressed(raw) {
+        return Ok(Cow::Borrowed(raw));
+    }
+    let frame = &raw[ZSTD_RECORD_MAGIC_BYTES.len()..];
+    let decompressed = match zstd::zstd_safe::get_dict_id_from_frame(frame) {
+        Some(id) => {
+            let dictionary = ZSTD_DICTIONARIES
+                .read()
+                .map_err(|_| LmdbExtError::Other("zstd dictionary registry poisoned".into()))?
+                .get(&id.get())
+                .cloned()
+                .ok_or_else(|| {
+                    LmdbExtError::DataCorrupted(Box::new(UnknownDictionaryError(id.get())))
+                })?;
+            zstd::stream::read::Decoder::with_dictionary(frame, &dictionary).and_then(
+                |mut decoder| {
+                    let mut buffer = Vec::new();
+                    std::io::Read::read_to_end(&mut decoder, &mut buffer).map(|_| buffer)
+                },
+            )
+        }
+        None => zstd::stream::decode_all(frame),
+    }
+    .map_err(|err| LmdbExtError::DataCorrupted(Box::new(err)))?;
+    Ok(Cow::Owned(decompressed))
+}
+
 // Classifies an `lmdb::Error` according to our scheme. This one of the rare cases where we accept a
 // blanked `From<>` implementation for error type conversion.
 impl From<lmdb::Error> for LmdbExtError {
@@ -120,6 +259,20 @@ pub(super) trait WriteTransactionExt {
         overwrite: bool,
     ) -> Result<bool, LmdbExtError>;
 
+    /// Helper function to write a value to a database, compressing it if a `compressor` is given.
+    ///
+    /// Returns `true` if the value has actually been written, `false` if the key already existed.
+    ///
+    /// Setting `overwrite` to true will cause the value to always be written instead.
+    fn put_value_compressed<K: AsRef<[u8]>, V: 'static + Serialize>(
+        &mut self,
+        db: Database,
+        key: &K,
+        value: &V,
+        overwrite: bool,
+        compressor: Option<&RecordCompressor>,
+    ) -> Result<bool, LmdbExtError>;
+
     /// Helper function to write a value to a database using the `bytesrepr` `ToBytes`/`FromBytes`
     /// serialization.
     ///
@@ -174,7 +327,7 @@ where
     ) -> Result<Option<V>, LmdbExtError> {
         match self.get(db, key) {
             // Deserialization failures are likely due to storage corruption.
-            Ok(raw) => deserialize_bytesrepr(raw).map(Some),
+            Ok(raw) => deserialize_bytesrepr(&decompress(raw)?).map(Some),
             Err(lmdb::Error::NotFound) => Ok(None),
             Err(err) => Err(err.into()),
         }
@@ -197,13 +350,14 @@ pub(crate) fn serialize_internal<V: 'static + Serialize>(
     Ok(buffer)
 }
 
-/// Deserializes an object from the raw bytes.
+/// Deserializes an object from the raw bytes, decompressing them first if needed.
 /// In case the expected object is of the `UnbondingPurse` type it uses the specialized
 /// function to provide compatibility with the legacy version of the `UnbondingPurse` struct.
 /// See [`deserialize_unbonding_purse`] for more details.
 pub(crate) fn deserialize_internal<V: 'static + DeserializeOwned>(
     raw: &[u8],
 ) -> Result<Option<V>, LmdbExtError> {
+    let raw = &decompress(raw)?;
     if TypeId::of::<UnbondingPurse>() == TypeId::of::<V>() {
         deserialize_unbonding_purse(raw).map(Some)
     } else {
@@ -219,7 +373,21 @@ impl WriteTransactionExt for RwTransaction<'_> {
         value: &V,
         overwrite: bool,
     ) -> Result<bool, LmdbExtError> {
-        let buffer = serialize_internal(value)?;
+        self.put_value_compressed(db, key, value, overwrite, None)
+    }
+
+    fn put_value_compressed<K: AsRef<[u8]>, V: 'static + Serialize>(
+        &mut self,
+        db: Database,
+        key: &K,
+        value: &V,
+        overwrite: bool,
+        compressor: Option<&RecordCompressor>,
+    ) -> Result<bool, LmdbExtError> {
+        let mut buffer = serialize_internal(value)?;
+        if let Some(compressor) = compressor {
+            buffer = compressor.compress(&buffer)?;
+        }
 
         let flags = if overwrite {
             WriteFlags::empty()
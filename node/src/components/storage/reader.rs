@@ -0,0 +1,261 @@
+//! Read-only access to the storage of a node.
+//!
+//! The [`StorageReader`] opens the LMDB files of a [`Storage`](super::Storage) component in
+//! read-only mode, so that external tools can inspect the data directory of a running node.
+//! Unlike the storage component, it does not rebuild its indices on startup, but only on first
+//! use.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
+
+use lmdb::{Cursor, Database, Environment, EnvironmentFlags, Transaction};
+use once_cell::unsync::OnceCell;
+
+use casper_types::{EraId, ExecutionResult};
+
+use super::{
+    get_body_for_block_header, insert_to_block_header_indices, insert_to_deploy_index,
+    lmdb_ext::{self, TransactionExt},
+    FatalStorageError, MAX_DB_COUNT, MAX_SECONDARY_READERS, MAX_TRANSACTIONS, STORAGE_DB_FILENAME,
+    ZSTD_DEPLOY_DICTIONARY_STORAGE_KEY,
+};
+use crate::types::{
+    Block, BlockHash, BlockHashHeightAndEra, BlockHeader, BlockSignatures, Deploy, DeployHash,
+    DeployMetadata,
+};
+
+/// Indices over the block and deploy stores, built on first use.
+#[derive(Debug, Default)]
+struct ReaderIndices {
+    /// A map of block height to block ID.
+    block_height_index: BTreeMap<u64, BlockHash>,
+    /// A map of era ID to switch block ID.
+    switch_block_era_id_index: BTreeMap<EraId, BlockHash>,
+    /// A map of deploy hashes to hashes, heights and era IDs of blocks containing them.
+    deploy_hash_index: BTreeMap<DeployHash, BlockHashHeightAndEra>,
+}
+
+/// Read-only handle to the storage of a node.
+///
+/// Can be opened while the node itself is running. Lookups by hash always reflect the current
+/// contents of the storage, while lookups going through the indices (by height, era or deploy
+/// inclusion) reflect the contents at the time the indices were built; see
+/// [`StorageReader::refresh_indices`].
+#[derive(Debug)]
+pub struct StorageReader {
+    /// Environment holding LMDB databases.
+    env: Environment,
+    /// The block header database.
+    block_header_db: Database,
+    /// The block body database.
+    block_body_db: Database,
+    /// The block metadata db.
+    block_metadata_db: Database,
+    /// The deploy database.
+    deploy_db: Database,
+    /// The deploy metadata database.
+    deploy_metadata_db: Database,
+    /// Lazily built indices.
+    indices: OnceCell<ReaderIndices>,
+}
+
+impl StorageReader {
+    /// Opens the storage files in the given directory read-only.
+    ///
+    /// `root` is the directory holding the storage files, i.e. the configured storage path joined
+    /// with the network name.
+    pub fn open<P: AsRef<Path>>(root: P) -> Result<Self, FatalStorageError> {
+        let env = Environment::new()
+            .set_flags(
+                EnvironmentFlags::READ_ONLY
+                    | EnvironmentFlags::NO_SUB_DIR
+                    | EnvironmentFlags::NO_TLS
+                    | EnvironmentFlags::NO_READAHEAD,
+            )
+            .set_max_readers(MAX_TRANSACTIONS + MAX_SECONDARY_READERS)
+            .set_max_dbs(MAX_DB_COUNT)
+            .open(&root.as_ref().join(STORAGE_DB_FILENAME))?;
+
+        let block_header_db = env.open_db(Some("block_header"))?;
+        let block_body_db = env.open_db(Some("block_body"))?;
+        let block_metadata_db = env.open_db(Some("block_metadata"))?;
+        let deploy_db = env.open_db(Some("deploys"))?;
+        let deploy_metadata_db = env.open_db(Some("deploy_metadata"))?;
+        let state_store_db = env.open_db(Some("state_store"))?;
+
+        // Deploys compressed with a trained dictionary can only be read once it is registered.
+        let txn = env.begin_ro_txn()?;
+        match txn.get(state_store_db, &ZSTD_DEPLOY_DICTIONARY_STORAGE_KEY) {
+            Ok(dictionary) => {
+                lmdb_ext::register_zstd_dictionary(dictionary)?;
+            }
+            Err(lmdb::Error::NotFound) => {}
+            Err(err) => return Err(err.into()),
+        }
+        txn.commit()?;
+
+        Ok(StorageReader {
+            env,
+            block_header_db,
+            block_body_db,
+            block_metadata_db,
+            deploy_db,
+            deploy_metadata_db,
+            indices: OnceCell::new(),
+        })
+    }
+
+    /// Discards the indices, so that they are rebuilt from the current contents of the storage on
+    /// next use.
+    pub fn refresh_indices(&mut self) {
+        self.indices = OnceCell::new();
+    }
+
+    /// Retrieves a block header by hash.
+    pub fn read_block_header(
+        &self,
+        block_hash: &BlockHash,
+    ) -> Result<Option<BlockHeader>, FatalStorageError> {
+        let mut txn = self.env.begin_ro_txn()?;
+        let maybe_block_header: Option<BlockHeader> =
+            txn.get_value(self.block_header_db, block_hash)?;
+        if let Some(block_header) = &maybe_block_header {
+            block_header.set_block_hash(*block_hash);
+        }
+        Ok(maybe_block_header)
+    }
+
+    /// Retrieves a block by hash.
+    pub fn read_block(&self, block_hash: &BlockHash) -> Result<Option<Block>, FatalStorageError> {
+        let mut txn = self.env.begin_ro_txn()?;
+        let block_header: BlockHeader = match txn.get_value(self.block_header_db, block_hash)? {
+            Some(block_header) => block_header,
+            None => return Ok(None),
+        };
+        block_header.set_block_hash(*block_hash);
+        let block_body = match get_body_for_block_header(
+            &mut txn,
+            block_header.body_hash(),
+            self.block_body_db,
+        )? {
+            Some(block_body) => block_body,
+            None => return Ok(None),
+        };
+        Ok(Some(Block::new_from_header_and_body(
+            block_header,
+            block_body,
+        )?))
+    }
+
+    /// Retrieves the block at the given height.
+    pub fn read_block_by_height(&self, height: u64) -> Result<Option<Block>, FatalStorageError> {
+        match self.indices()?.block_height_index.get(&height) {
+            Some(block_hash) => self.read_block(block_hash),
+            None => Ok(None),
+        }
+    }
+
+    /// Retrieves the block with the highest height.
+    pub fn read_highest_block(&self) -> Result<Option<Block>, FatalStorageError> {
+        match self.indices()?.block_height_index.values().next_back() {
+            Some(block_hash) => self.read_block(block_hash),
+            None => Ok(None),
+        }
+    }
+
+    /// Retrieves the switch block of the given era.
+    pub fn read_switch_block_by_era_id(
+        &self,
+        era_id: EraId,
+    ) -> Result<Option<Block>, FatalStorageError> {
+        match self.indices()?.switch_block_era_id_index.get(&era_id) {
+            Some(block_hash) => self.read_block(block_hash),
+            None => Ok(None),
+        }
+    }
+
+    /// Retrieves the finality signatures of a block.
+    pub fn read_block_signatures(
+        &self,
+        block_hash: &BlockHash,
+    ) -> Result<Option<BlockSignatures>, FatalStorageError> {
+        let mut txn = self.env.begin_ro_txn()?;
+        Ok(txn.get_value(self.block_metadata_db, block_hash)?)
+    }
+
+    /// Retrieves a deploy by hash.
+    pub fn read_deploy(
+        &self,
+        deploy_hash: &DeployHash,
+    ) -> Result<Option<Deploy>, FatalStorageError> {
+        let mut txn = self.env.begin_ro_txn()?;
+        Ok(txn.get_value(self.deploy_db, deploy_hash)?)
+    }
+
+    /// Retrieves the hash of the block containing the given deploy.
+    pub fn read_block_hash_for_deploy(
+        &self,
+        deploy_hash: &DeployHash,
+    ) -> Result<Option<BlockHash>, FatalStorageError> {
+        Ok(self
+            .indices()?
+            .deploy_hash_index
+            .get(deploy_hash)
+            .map(|block_hash_height_and_era| block_hash_height_and_era.block_hash))
+    }
+
+    /// Retrieves the results of executing the given deploy, keyed by the hash of the block in
+    /// which it was executed.
+    pub fn read_execution_results(
+        &self,
+        deploy_hash: &DeployHash,
+    ) -> Result<HashMap<BlockHash, ExecutionResult>, FatalStorageError> {
+        let mut txn = self.env.begin_ro_txn()?;
+        let maybe_metadata: Option<DeployMetadata> =
+            txn.get_value(self.deploy_metadata_db, deploy_hash)?;
+        Ok(maybe_metadata
+            .map(|metadata| metadata.execution_results)
+            .unwrap_or_default())
+    }
+
+    /// Returns the indices, building them first if necessary.
+    fn indices(&self) -> Result<&ReaderIndices, FatalStorageError> {
+        self.indices.get_or_try_init(|| self.build_indices())
+    }
+
+    /// Builds the indices from the current contents of the block stores.
+    fn build_indices(&self) -> Result<ReaderIndices, FatalStorageError> {
+        let mut indices = ReaderIndices::default();
+        let txn = self.env.begin_ro_txn()?;
+        let mut body_txn = self.env.begin_ro_txn()?;
+        let mut cursor = txn.open_ro_cursor(self.block_header_db)?;
+        for row in cursor.iter() {
+            let (_, raw_val) = row?;
+            let block_header: BlockHeader = lmdb_ext::deserialize(raw_val)?;
+            insert_to_block_header_indices(
+                &mut indices.block_height_index,
+                &mut indices.switch_block_era_id_index,
+                &block_header,
+            )?;
+            if let Some(block_body) = get_body_for_block_header(
+                &mut body_txn,
+                block_header.body_hash(),
+                self.block_body_db,
+            )? {
+                insert_to_deploy_index(
+                    &mut indices.deploy_hash_index,
+                    block_header.block_hash(),
+                    &block_body,
+                    block_header.height(),
+                    block_header.era_id(),
+                )?;
+            }
+        }
+        drop(cursor);
+        txn.commit()?;
+        body_txn.commit()?;
+        Ok(indices)
+    }
+}
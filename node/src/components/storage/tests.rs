@@ -21,9 +21,12 @@ use casper_types::{
 
 use super::{
     initialize_block_metadata_db,
-    lmdb_ext::{deserialize_internal, serialize_internal, TransactionExt, WriteTransactionExt},
-    move_storage_files_to_network_subdir, should_move_storage_files_to_network_subdir, Config,
-    Storage, FORCE_RESYNC_FILE_NAME,
+    lmdb_ext::{
+        self, deserialize_internal, serialize_internal, RecordCompressor, TransactionExt,
+        WriteTransactionExt,
+    },
+    move_storage_files_to_network_subdir, should_move_storage_files_to_network_subdir,
+    CompressionConfig, Config, Storage, StorageReader, FORCE_RESYNC_FILE_NAME,
 };
 use crate::{
     components::fetcher::{FetchItem, FetchResponse},
@@ -57,6 +60,7 @@ fn new_config(harness: &ComponentHarness<UnitTestEvent>) -> Config {
         max_state_store_size: 50 * MIB,
         enable_mem_deduplication: true,
         mem_pool_prune_interval: 4,
+        compression: CompressionConfig::default(),
     }
 }
 
@@ -1199,6 +1203,8 @@ fn prepare_exec_result_with_transfer(
         effect,
         transfers: vec![],
         cost: rng.gen(),
+        events: vec![],
+        sponsor: None,
     };
     (exec_result, transfer)
 }
@@ -2117,6 +2123,232 @@ fn unbonding_purse_serialization_roundtrip() {
     assert!(deserialized.new_validator().is_some())
 }
 
+#[test]
+fn compressed_records_should_be_read_transparently() {
+    let original: Vec<u64> = (0..1_000).map(|i| i % 10).collect();
+
+    let serialized = serialize_internal(&original).expect("serialization");
+    let compressed = RecordCompressor::new(3)
+        .compress(&serialized)
+        .expect("compression");
+    assert!(lmdb_ext::is_compressed(&compressed));
+    assert!(compressed.len() < serialized.len());
+
+    for raw in [&serialized, &compressed] {
+        let deserialized: Vec<u64> = deserialize_internal(raw)
+            .expect("should deserialize w/o error")
+            .expect("should be Some");
+        assert_eq!(original, deserialized);
+    }
+}
+
+#[test]
+fn incompressible_records_should_be_stored_uncompressed() {
+    let mut rng = TestRng::new();
+    let original: Vec<u8> = iter::repeat_with(|| rng.gen()).take(64).collect();
+
+    let serialized = serialize_internal(&original).expect("serialization");
+    let compressed = RecordCompressor::new(3)
+        .compress(&serialized)
+        .expect("compression");
+    assert!(!lmdb_ext::is_compressed(&compressed));
+    assert_eq!(compressed, serialized);
+}
+
+#[test]
+fn dictionary_compressed_records_should_be_read_transparently() {
+    let samples: Vec<Vec<u8>> = (0..500)
+        .map(|i| {
+            format!(
+                "(module (func $add_{} (param i32) (result i32) local.get 0 i32.const {} i32.add) \
+                 (export \"call\" (func $add_{})))",
+                i,
+                i * 7,
+                i
+            )
+            .into_bytes()
+        })
+        .collect();
+    let dictionary = lmdb_ext::train_zstd_dictionary(&samples, 4_096).expect("should train");
+    let compressor = RecordCompressor::new(3)
+        .with_dictionary(&dictionary)
+        .expect("should register dictionary");
+
+    let original = samples[42].repeat(3);
+    let serialized = serialize_internal(&original).expect("serialization");
+    let compressed = compressor.compress(&serialized).expect("compression");
+    assert!(lmdb_ext::is_compressed(&compressed));
+
+    let deserialized: Vec<u8> = deserialize_internal(&compressed)
+        .expect("should deserialize w/o error")
+        .expect("should be Some");
+    assert_eq!(original, deserialized);
+}
+
+#[test]
+fn should_recompress_uncompressed_records() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let deploys: Vec<_> = iter::repeat_with(|| Arc::new(Deploy::random(&mut harness.rng)))
+        .take(5)
+        .collect();
+    for deploy in &deploys {
+        assert!(put_deploy(&mut harness, &mut storage, Arc::clone(deploy)));
+    }
+    drop(storage);
+
+    // Reopen the storage with compression enabled, and recompress two records per step.
+    let mut cfg = new_config(&harness);
+    cfg.compression = CompressionConfig {
+        enabled: true,
+        recompression_batch_size: 2,
+        ..CompressionConfig::default()
+    };
+    let mut storage = Storage::new(
+        &WithDir::new(harness.tmp.path(), cfg),
+        None,
+        ProtocolVersion::from_parts(1, 0, 0),
+        EraId::default(),
+        "test",
+        MAX_TTL.into(),
+        RECENT_ERA_COUNT,
+        None,
+        false,
+    )
+    .expect("could not create storage component fixture");
+    assert!(storage.should_recompress_records());
+
+    let mut steps = 1;
+    while !storage.recompress_records().expect("should recompress") {
+        steps += 1;
+    }
+    assert_eq!(steps, 3);
+
+    // A deploy written after enabling compression is readable as well.
+    let new_deploy = Arc::new(Deploy::random(&mut harness.rng));
+    assert!(put_deploy(
+        &mut harness,
+        &mut storage,
+        Arc::clone(&new_deploy)
+    ));
+
+    let deploy_hashes = deploys
+        .iter()
+        .chain(iter::once(&new_deploy))
+        .map(|deploy| *deploy.hash())
+        .collect();
+    let response = get_naive_deploys(&mut harness, &mut storage, deploy_hashes);
+    let expected: Vec<_> = deploys
+        .iter()
+        .chain(iter::once(&new_deploy))
+        .map(|deploy| Some((**deploy).clone()))
+        .collect();
+    assert_eq!(response, expected);
+}
+
+#[test]
+fn storage_reader_should_read_from_live_storage() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let deploy = Deploy::random(&mut harness.rng);
+    let block = Arc::new(
+        TestBlockBuilder::new()
+            .era(1)
+            .height(1)
+            .switch_block(true)
+            .deploys(Some(&deploy))
+            .build(&mut harness.rng),
+    );
+    let block_hash = *block.hash();
+    put_deploy(&mut harness, &mut storage, Arc::new(deploy.clone()));
+    assert!(put_complete_block(
+        &mut harness,
+        &mut storage,
+        Arc::clone(&block)
+    ));
+    let signatures = random_signatures(&mut harness.rng, &block);
+    assert!(put_block_signatures(
+        &mut harness,
+        &mut storage,
+        signatures.clone()
+    ));
+    let execution_result: ExecutionResult = harness.rng.gen();
+    let mut execution_results = HashMap::new();
+    execution_results.insert(*deploy.hash(), execution_result.clone());
+    put_execution_results(&mut harness, &mut storage, block_hash, execution_results);
+
+    // Open the reader while the storage component is still running.
+    let mut reader = StorageReader::open(storage.root_path()).expect("should open reader");
+
+    assert_eq!(
+        reader.read_block_header(&block_hash).unwrap().as_ref(),
+        Some(block.header())
+    );
+    assert_eq!(
+        reader.read_block(&block_hash).unwrap().as_ref(),
+        Some(&*block)
+    );
+    assert_eq!(
+        reader.read_block_by_height(1).unwrap().as_ref(),
+        Some(&*block)
+    );
+    assert_eq!(reader.read_highest_block().unwrap().as_ref(), Some(&*block));
+    assert_eq!(
+        reader
+            .read_switch_block_by_era_id(EraId::from(1))
+            .unwrap()
+            .as_ref(),
+        Some(&*block)
+    );
+    assert_eq!(
+        reader.read_block_signatures(&block_hash).unwrap(),
+        Some(signatures)
+    );
+    assert_eq!(
+        reader.read_deploy(deploy.hash()).unwrap(),
+        Some(deploy.clone())
+    );
+    assert_eq!(
+        reader.read_block_hash_for_deploy(deploy.hash()).unwrap(),
+        Some(block_hash)
+    );
+    assert_eq!(
+        reader.read_execution_results(deploy.hash()).unwrap(),
+        HashMap::from_iter([(block_hash, execution_result)])
+    );
+
+    // A block written afterwards is visible by hash right away, but only by height once the
+    // indices have been refreshed.
+    let next_block = Arc::new(
+        TestBlockBuilder::new()
+            .era(2)
+            .height(2)
+            .switch_block(false)
+            .build(&mut harness.rng),
+    );
+    assert!(put_complete_block(
+        &mut harness,
+        &mut storage,
+        Arc::clone(&next_block)
+    ));
+    assert_eq!(
+        reader.read_block(next_block.hash()).unwrap().as_ref(),
+        Some(&*next_block)
+    );
+    assert_eq!(reader.read_block_by_height(2).unwrap(), None);
+    reader.refresh_indices();
+    assert_eq!(
+        reader.read_block_by_height(2).unwrap().as_ref(),
+        Some(&*next_block)
+    );
+    assert_eq!(
+        reader.read_highest_block().unwrap().as_ref(),
+        Some(&*next_block)
+    );
+}
+
 // Clippy complains because there's a `OnceCell` in `FinalitySignature`, hence it should not be used
 // as a key in `BTreeSet`. However, we don't change the content of the cell during the course of the
 // test so there's no risk the hash or order of keys will change.
@@ -98,7 +98,7 @@ pub(crate) mod requests;
 use std::{
     any::type_name,
     borrow::Cow,
-    collections::{BTreeMap, HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     fmt::{self, Debug, Display, Formatter},
     future::Future,
     mem,
@@ -117,16 +117,18 @@ use tracing::{debug, error, warn};
 use casper_execution_engine::{
     core::engine_state::{
         self, era_validators::GetEraValidatorsError, BalanceRequest, BalanceResult, GetBidsRequest,
-        GetBidsResult, QueryRequest, QueryResult,
+        GetBidsResult, MultiQueryRequest, MultiQueryResult, QueryRequest, QueryResult,
     },
     shared::execution_journal::ExecutionJournal,
     storage::trie::TrieRaw,
 };
 use casper_hashing::Digest;
 use casper_types::{
-    account::Account, bytesrepr::Bytes, system::auction::EraValidators, Contract, ContractPackage,
-    EraId, ExecutionEffect, ExecutionResult, Key, PublicKey, TimeDiff, Timestamp, Transfer, URef,
-    U512,
+    account::Account,
+    bytesrepr::Bytes,
+    system::{auction::EraValidators, mint::ScheduledTransferResult},
+    Contract, ContractPackage, EraId, ExecutionEffect, ExecutionResult, Key, PublicKey, TimeDiff,
+    Timestamp, Transfer, URef, U512,
 };
 
 use crate::{
@@ -144,7 +146,7 @@ use crate::{
         network::{blocklist::BlocklistJustification, FromIncoming, NetworkInsights},
         upgrade_watcher::NextUpgrade,
     },
-    contract_runtime::SpeculativeExecutionState,
+    contract_runtime::{SpeculativeExecutionResult, SpeculativeExecutionState},
     failpoints::FailpointActivation,
     reactor::{main_reactor::ReactorState, EventQueueHandle, QueueKind},
     types::{
@@ -809,6 +811,20 @@ impl<REv> EffectBuilder<REv> {
             .await;
     }
 
+    /// Announces that a deploy has been superseded by another and will not be proposed by this
+    /// node.
+    pub(crate) async fn announce_superseded_deploy(self, deploy_id: DeployId)
+    where
+        REv: From<DeployBufferAnnouncement>,
+    {
+        self.event_queue
+            .schedule(
+                DeployBufferAnnouncement::DeploySuperseded(deploy_id),
+                QueueKind::Validation,
+            )
+            .await;
+    }
+
     /// Announces an incoming network message.
     pub(crate) async fn announce_incoming<P>(self, sender: NodeId, payload: P)
     where
@@ -1010,6 +1026,7 @@ impl<REv> EffectBuilder<REv> {
         self,
         era_id: EraId,
         execution_journal: ExecutionJournal,
+        scheduled_transfers: Vec<ScheduledTransferResult>,
     ) where
         REv: From<ContractRuntimeAnnouncement>,
     {
@@ -1018,6 +1035,7 @@ impl<REv> EffectBuilder<REv> {
                 ContractRuntimeAnnouncement::CommitStepSuccess {
                     era_id,
                     execution_effect: ExecutionEffect::from(&execution_journal),
+                    scheduled_transfers,
                 },
                 QueueKind::ContractRuntime,
             )
@@ -1323,6 +1341,46 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Returns those of the given deploys which were executed in a block held in storage.
+    pub(crate) async fn get_executed_deploys(
+        self,
+        deploy_hashes: HashSet<DeployHash>,
+    ) -> HashSet<DeployHash>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetExecutedDeploys {
+                deploy_hashes,
+                responder,
+            },
+            QueueKind::FromStorage,
+        )
+        .await
+    }
+
+    /// Returns those of the given deploys, and of the deploys executed in a block held in storage,
+    /// which take one of the given slots, i.e. are from the given account with the given
+    /// timestamp.
+    pub(crate) async fn get_deploys_in_slots(
+        self,
+        slots: BTreeSet<(PublicKey, Timestamp)>,
+        deploy_hashes: BTreeSet<DeployHash>,
+    ) -> HashSet<DeployHash>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetDeploysInSlots {
+                slots,
+                deploy_hashes,
+                responder,
+            },
+            QueueKind::FromStorage,
+        )
+        .await
+    }
+
     /// Requests the highest complete block.
     pub(crate) async fn get_highest_complete_block_from_storage(self) -> Option<Block>
     where
@@ -1900,6 +1958,24 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Requests a query for several keys be executed on the Contract Runtime component.
+    pub(crate) async fn query_global_state_multi(
+        self,
+        query_request: MultiQueryRequest,
+    ) -> Result<MultiQueryResult, engine_state::Error>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::QueryMulti {
+                query_request,
+                responder,
+            },
+            QueueKind::ContractRuntime,
+        )
+        .await
+    }
+
     /// Retrieves an `Account` from global state if present.
     pub(crate) async fn get_account_from_global_state(
         self,
@@ -2199,13 +2275,15 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
-    /// Requests execution of a single deploy, without commiting its effects.
+    /// Requests execution of a single deploy, without commiting its effects, optionally profiling
+    /// the gas it charges.
     /// Inteded to be used for debugging & discovery purposes.
     pub(crate) async fn speculative_execute_deploy(
         self,
         execution_prestate: SpeculativeExecutionState,
         deploy: Arc<Deploy>,
-    ) -> Result<Option<ExecutionResult>, engine_state::Error>
+        gas_profiling: bool,
+    ) -> Result<SpeculativeExecutionResult, engine_state::Error>
     where
         REv: From<ContractRuntimeRequest>,
     {
@@ -2213,6 +2291,7 @@ impl<REv> EffectBuilder<REv> {
             |responder| ContractRuntimeRequest::SpeculativeDeployExecution {
                 execution_prestate,
                 deploy,
+                gas_profiling,
                 responder,
             },
             QueueKind::ContractRuntime,
@@ -14,7 +14,9 @@ use datasize::DataSize;
 use itertools::Itertools;
 use serde::Serialize;
 
-use casper_types::{EraId, ExecutionEffect, PublicKey, Timestamp, U512};
+use casper_types::{
+    system::mint::ScheduledTransferResult, EraId, ExecutionEffect, PublicKey, Timestamp, U512,
+};
 
 use crate::{
     components::{
@@ -27,7 +29,9 @@ use crate::{
     },
     effect::Responder,
     failpoints::FailpointActivation,
-    types::{Block, Deploy, DeployHash, FinalitySignature, FinalizedBlock, MetaBlock, NodeId},
+    types::{
+        Block, Deploy, DeployHash, DeployId, FinalitySignature, FinalizedBlock, MetaBlock, NodeId,
+    },
     utils::Source,
 };
 
@@ -223,6 +227,11 @@ impl Display for DeployAcceptorAnnouncement {
 pub(crate) enum DeployBufferAnnouncement {
     /// Hashes of the deploys that expired.
     DeploysExpired(Vec<DeployHash>),
+    /// The ID of a deploy which was superseded by another deploy from the same account with the
+    /// same timestamp and will not be proposed by this node.
+    ///
+    /// This is best-effort only: other nodes may still propose and execute the deploy.
+    DeploySuperseded(DeployId),
 }
 
 impl Display for DeployBufferAnnouncement {
@@ -231,6 +240,9 @@ impl Display for DeployBufferAnnouncement {
             DeployBufferAnnouncement::DeploysExpired(hashes) => {
                 write!(f, "pruned hashes: {}", hashes.iter().join(", "))
             }
+            DeployBufferAnnouncement::DeploySuperseded(deploy_id) => {
+                write!(f, "superseded deploy: {}", deploy_id)
+            }
         }
     }
 }
@@ -354,6 +366,8 @@ pub(crate) enum ContractRuntimeAnnouncement {
         era_id: EraId,
         /// The operations and transforms committed to global state.
         execution_effect: ExecutionEffect,
+        /// The outcomes of the scheduled transfers run by the step.
+        scheduled_transfers: Vec<ScheduledTransferResult>,
     },
     /// New era validators.
     UpcomingEraValidators {
@@ -4,7 +4,7 @@
 //! top-level module documentation for details.
 
 use std::{
-    collections::{BTreeMap, HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     fmt::{self, Debug, Display, Formatter},
     mem,
     sync::Arc,
@@ -21,7 +21,7 @@ use casper_execution_engine::{
         balance::{BalanceRequest, BalanceResult},
         era_validators::GetEraValidatorsError,
         get_bids::{GetBidsRequest, GetBidsResult},
-        query::{QueryRequest, QueryResult},
+        query::{MultiQueryRequest, MultiQueryResult, QueryRequest, QueryResult},
     },
     storage::trie::TrieRaw,
 };
@@ -46,7 +46,9 @@ use crate::{
         network::NetworkInsights,
         upgrade_watcher::NextUpgrade,
     },
-    contract_runtime::{ContractRuntimeError, SpeculativeExecutionState},
+    contract_runtime::{
+        ContractRuntimeError, SpeculativeExecutionResult, SpeculativeExecutionState,
+    },
     effect::{AutoClosingResponder, Responder},
     reactor::main_reactor::ReactorState,
     rpcs::docs::OpenRpcSchema,
@@ -323,6 +325,18 @@ pub(crate) enum StorageRequest {
         deploy_hashes: HashSet<DeployHash>,
         responder: Responder<HashSet<EraId>>,
     },
+    /// Retrieve those of the given deploys which were executed in a stored block.
+    GetExecutedDeploys {
+        deploy_hashes: HashSet<DeployHash>,
+        responder: Responder<HashSet<DeployHash>>,
+    },
+    /// Retrieve those of the given deploys, and of the deploys executed in a stored block, which
+    /// take one of the given slots, i.e. are from the given account with the given timestamp.
+    GetDeploysInSlots {
+        slots: BTreeSet<(PublicKey, Timestamp)>,
+        deploy_hashes: BTreeSet<DeployHash>,
+        responder: Responder<HashSet<DeployHash>>,
+    },
     /// Retrieve block header with given hash.
     GetBlockHeader {
         /// Hash of block to get header of.
@@ -527,6 +541,25 @@ impl Display for StorageRequest {
             StorageRequest::GetDeploysEraIds { deploy_hashes, .. } => {
                 write!(formatter, "get era ids for {} deploys", deploy_hashes.len())
             }
+            StorageRequest::GetExecutedDeploys { deploy_hashes, .. } => {
+                write!(
+                    formatter,
+                    "get executed among {} deploys",
+                    deploy_hashes.len()
+                )
+            }
+            StorageRequest::GetDeploysInSlots {
+                slots,
+                deploy_hashes,
+                ..
+            } => {
+                write!(
+                    formatter,
+                    "get deploys in {} slots among executed and {} deploys",
+                    slots.len(),
+                    deploy_hashes.len()
+                )
+            }
             StorageRequest::GetBlockHeader { block_hash, .. } => {
                 write!(formatter, "get {}", block_hash)
             }
@@ -710,6 +743,15 @@ pub(crate) enum RpcRequest {
         /// Responder to call with the result.
         responder: Responder<Result<QueryResult, engine_state::Error>>,
     },
+    /// Query the global state for several keys at the given root hash.
+    QueryGlobalStateMulti {
+        /// The state root hash.
+        state_root_hash: Digest,
+        /// The keys to query.
+        keys: Vec<Key>,
+        /// Responder to call with the result.
+        responder: Responder<Result<MultiQueryResult, engine_state::Error>>,
+    },
     /// Query the global state at the given root hash.
     QueryEraValidators {
         /// The global state hash.
@@ -779,6 +821,15 @@ impl Display for RpcRequest {
                 "query {}, base_key: {}, path: {:?}",
                 state_root_hash, base_key, path
             ),
+            RpcRequest::QueryGlobalStateMulti {
+                state_root_hash,
+                keys,
+                ..
+            } => write!(
+                formatter,
+                "query multi {}, keys: {:?}",
+                state_root_hash, keys
+            ),
             RpcRequest::QueryEraValidators {
                 state_root_hash, ..
             } => write!(formatter, "auction {}", state_root_hash),
@@ -870,6 +921,14 @@ pub(crate) enum ContractRuntimeRequest {
         /// Responder to call with the query result.
         responder: Responder<Result<QueryResult, engine_state::Error>>,
     },
+    /// A query request for several keys.
+    QueryMulti {
+        /// Multi-key query request.
+        #[serde(skip_serializing)]
+        query_request: MultiQueryRequest,
+        /// Responder to call with the query result.
+        responder: Responder<Result<MultiQueryResult, engine_state::Error>>,
+    },
     /// A balance request.
     GetBalance {
         /// Balance request.
@@ -927,8 +986,10 @@ pub(crate) enum ContractRuntimeRequest {
         execution_prestate: SpeculativeExecutionState,
         /// Deploy to execute.
         deploy: Arc<Deploy>,
+        /// Whether to also profile the gas charged by the deploy.
+        gas_profiling: bool,
         /// Results
-        responder: Responder<Result<Option<ExecutionResult>, engine_state::Error>>,
+        responder: Responder<Result<SpeculativeExecutionResult, engine_state::Error>>,
     },
 }
 
@@ -943,6 +1004,9 @@ impl Display for ContractRuntimeRequest {
             ContractRuntimeRequest::Query { query_request, .. } => {
                 write!(formatter, "query request: {:?}", query_request)
             }
+            ContractRuntimeRequest::QueryMulti { query_request, .. } => {
+                write!(formatter, "multi query request: {:?}", query_request)
+            }
             ContractRuntimeRequest::GetBalance {
                 balance_request, ..
             } => write!(formatter, "balance request: {:?}", balance_request),
@@ -46,7 +46,7 @@ use crate::{
         rest_server::RestServer,
         rpc_server::RpcServer,
         shutdown_trigger::{self, ShutdownTrigger},
-        storage::Storage,
+        storage::{self, Storage},
         sync_leaper::SyncLeaper,
         upgrade_watcher::{self, UpgradeWatcher},
         Component, ValidatorBoundComponent,
@@ -811,6 +811,13 @@ impl reactor::Reactor for MainReactor {
                 );
                 self.dispatch_event(effect_builder, rng, reactor_event)
             }
+            MainEvent::DeployBufferAnnouncement(DeployBufferAnnouncement::DeploySuperseded(
+                deploy_id,
+            )) => {
+                let reactor_event =
+                    MainEvent::DeployGossiper(gossiper::Event::ItemDropped { item_id: deploy_id });
+                self.dispatch_event(effect_builder, rng, reactor_event)
+            }
 
             // CONTRACT RUNTIME & GLOBAL STATE
             MainEvent::ContractRuntime(event) => reactor::wrap_effects(
@@ -827,12 +834,14 @@ impl reactor::Reactor for MainReactor {
                 ContractRuntimeAnnouncement::CommitStepSuccess {
                     era_id,
                     execution_effect,
+                    scheduled_transfers,
                 },
             ) => {
                 let reactor_event =
                     MainEvent::EventStreamServer(event_stream_server::Event::Step {
                         era_id,
                         execution_effect,
+                        scheduled_transfers,
                     });
                 self.dispatch_event(effect_builder, rng, reactor_event)
             }
@@ -1059,12 +1068,20 @@ impl reactor::Reactor for MainReactor {
             chainspec.core_config.strict_argument_checking,
             chainspec.core_config.vesting_schedule_period.millis(),
             max_delegators_per_validator,
+            chainspec.core_config.max_reserved_delegators_per_validator,
             registry,
             chainspec.core_config.administrators.clone(),
             chainspec.core_config.allow_auction_bids,
             chainspec.core_config.allow_unrestricted_transfers,
             chainspec.core_config.refund_handling,
             chainspec.core_config.fee_handling,
+            chainspec.core_config.equivocation_slashing_fraction,
+            chainspec.core_config.unavailability_slashing_fraction,
+            chainspec.core_config.inactivity_jail_threshold,
+            chainspec.core_config.jail_period_eras,
+            chainspec.core_config.max_jail_period_eras,
+            chainspec.core_config.allow_delegation_transfers,
+            chainspec.core_config.wasm_engine,
         )?;
 
         let network = Network::new(
@@ -1213,7 +1230,7 @@ impl reactor::Reactor for MainReactor {
         // shut down immediately for upgrade.
         let should_upgrade_immediately = reactor.upgrade_watcher.next_upgrade_activation_point()
             == Some(reactor.chainspec.protocol_config.activation_point.era_id());
-        let effects = if should_upgrade_immediately {
+        let mut effects = if should_upgrade_immediately {
             info!("MainReactor: immediate shutdown for upgrade");
             effect_builder
                 .immediately()
@@ -1223,6 +1240,13 @@ impl reactor::Reactor for MainReactor {
                 .immediately()
                 .event(|()| MainEvent::ReactorCrank)
         };
+        if reactor.storage.should_recompress_records() {
+            effects.extend(
+                effect_builder
+                    .immediately()
+                    .event(|()| MainEvent::Storage(storage::Event::RecompressRecords)),
+            );
+        }
         Ok((reactor, effects))
     }
 
@@ -1,5 +1,5 @@
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     fmt::{self, Display, Formatter},
 };
 
@@ -29,6 +29,10 @@ pub(crate) enum AddError {
     BlockSize,
     #[error("duplicate deploy")]
     Duplicate,
+    #[error(
+        "another deploy from the same account with the same timestamp is already in the block"
+    )]
+    SlotTaken,
     #[error("deploy has expired")]
     Expired,
     #[error("deploy is not valid in this context")]
@@ -42,6 +46,9 @@ pub(crate) struct AppendableBlock {
     deploys: Vec<DeployHashWithApprovals>,
     transfers: Vec<DeployHashWithApprovals>,
     deploy_and_transfer_set: HashSet<DeployHash>,
+    // the deploy taking each account's slot at a given deploy timestamp: only one deploy per slot
+    // can be included in a block
+    slots: HashMap<(PublicKey, Timestamp), DeployHash>,
     timestamp: Timestamp,
     #[data_size(skip)]
     total_gas: Gas,
@@ -58,6 +65,7 @@ impl AppendableBlock {
             transfers: Vec::new(),
             timestamp,
             deploy_and_transfer_set: HashSet::new(),
+            slots: HashMap::new(),
             total_gas: Gas::zero(),
             total_size: 0,
             total_approvals: 0,
@@ -93,6 +101,9 @@ impl AppendableBlock {
         {
             return Err(AddError::Duplicate);
         }
+        if self.slots.contains_key(&slot(footprint)) {
+            return Err(AddError::SlotTaken);
+        }
         if footprint.header.expired(self.timestamp) {
             return Err(AddError::Expired);
         }
@@ -115,6 +126,7 @@ impl AppendableBlock {
             return Err(AddError::ApprovalCount);
         }
         self.deploy_and_transfer_set.insert(*transfer.deploy_hash());
+        self.slots.insert(slot(footprint), *transfer.deploy_hash());
         self.total_approvals += transfer.approvals().len();
         self.transfers.push(transfer);
         Ok(())
@@ -133,6 +145,9 @@ impl AppendableBlock {
         if self.deploy_and_transfer_set.contains(deploy.deploy_hash()) {
             return Err(AddError::Duplicate);
         }
+        if self.slots.contains_key(&slot(footprint)) {
+            return Err(AddError::SlotTaken);
+        }
         if footprint.header.expired(self.timestamp) {
             return Err(AddError::Expired);
         }
@@ -172,6 +187,7 @@ impl AppendableBlock {
         self.total_size = new_total_size;
         self.total_approvals += deploy.approvals().len();
         self.deploy_and_transfer_set.insert(*deploy.deploy_hash());
+        self.slots.insert(slot(footprint), *deploy.deploy_hash());
         self.deploys.push(deploy);
         Ok(())
     }
@@ -193,6 +209,27 @@ impl AppendableBlock {
         self.timestamp
     }
 
+    /// Returns the deploy taking each slot, i.e. each pair of account and deploy timestamp, of the
+    /// deploys and transfers added so far.
+    pub(crate) fn slots(&self) -> &HashMap<(PublicKey, Timestamp), DeployHash> {
+        &self.slots
+    }
+
+    /// Returns `true` if `deploy_hash` has already been added to this block such that it is
+    /// executed before a transfer (if `is_transfer` is `true`) or a deploy added now.
+    ///
+    /// All deploys in a block are executed before all of its transfers, so a deploy can only rely
+    /// on deploys earlier in the block, while a transfer can rely on either.
+    pub(crate) fn executes_before(&self, deploy_hash: &DeployHash, is_transfer: bool) -> bool {
+        if is_transfer {
+            self.deploy_and_transfer_set.contains(deploy_hash)
+        } else {
+            self.deploys
+                .iter()
+                .any(|deploy| deploy.deploy_hash() == deploy_hash)
+        }
+    }
+
     /// Returns `true` if the number of transfers is already the maximum allowed count, i.e. no
     /// more transfers can be added to this block.
     fn has_max_transfer_count(&self) -> bool {
@@ -221,6 +258,16 @@ impl AppendableBlock {
     }
 }
 
+/// Returns the slot the deploy with the given footprint takes: its account and timestamp.
+///
+/// Of all deploys from the same account with the same timestamp, only one can be executed.
+fn slot(footprint: &DeployFootprint) -> (PublicKey, Timestamp) {
+    (
+        footprint.header.account().clone(),
+        footprint.header.timestamp(),
+    )
+}
+
 impl Display for AppendableBlock {
     fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
         let deploy_approvals_count = self
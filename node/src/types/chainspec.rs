@@ -363,6 +363,11 @@ mod tests {
             blake2b: HostFunction::new(133, [0, 1, 2, 3]),
             random_bytes: HostFunction::new(123, [0, 1]),
             enable_contract_version: HostFunction::new(142, [0, 1, 2, 3]),
+            emit_event: HostFunction::new(143, [0, 1, 2, 3]),
+            verify_signature: HostFunction::new(144, [0, 1, 2, 3, 4, 5]),
+            sha256: HostFunction::new(145, [0, 1, 2, 3]),
+            keccak256: HostFunction::new(146, [0, 1, 2, 3]),
+            approve_allowance: HostFunction::new(147, [0, 1, 2, 3, 4, 5, 6, 7]),
         });
     static EXPECTED_GENESIS_WASM_COSTS: Lazy<WasmConfig> = Lazy::new(|| {
         WasmConfig::new(
@@ -191,6 +191,8 @@ impl From<AccountsConfig> for Vec<GenesisAccount> {
 
 #[cfg(test)]
 mod tests {
+    use casper_types::system::auction::{VestingCurve, VestingTranche};
+
     use super::*;
 
     #[test]
@@ -199,4 +201,59 @@ mod tests {
         let accounts_config = AccountsConfig::random(&mut rng);
         bytesrepr::test_serialization_roundtrip(&accounts_config);
     }
+
+    #[test]
+    fn should_parse_vesting_curves() {
+        const ACCOUNTS_TOML: &str = r#"
+            [[accounts]]
+            public_key = "01f60bce2bb1059c41910eac1e7ee6c3ef4c8fcc63a901eb9603c1524cadfb0c18"
+            balance = "1000000000000000000"
+
+            [accounts.validator]
+            bonded_amount = "500000000000000"
+            vesting_curve = { Linear = { cliff_millis = 2592000000, step_millis = 604800000, steps = 12 } }
+
+            [[delegators]]
+            validator_public_key = "01f60bce2bb1059c41910eac1e7ee6c3ef4c8fcc63a901eb9603c1524cadfb0c18"
+            delegator_public_key = "018f5a3ee4c1221686fcdbe6c0b6168acb24025c5485f59f7c4039ffc444fb7509"
+            balance = "1000000000000000000"
+            delegated_amount = "400000000000000"
+            vesting_curve = { Tranches = [
+                { release_offset_millis = 0, release_percentage = 25 },
+                { release_offset_millis = 31536000000, release_percentage = 75 },
+            ] }
+        "#;
+
+        let accounts_config: AccountsConfig = toml::from_str(ACCOUNTS_TOML).unwrap();
+        bytesrepr::test_serialization_roundtrip(&accounts_config);
+
+        let validator_config = accounts_config.accounts()[0].validator.as_ref().unwrap();
+        assert_eq!(
+            validator_config.vesting_curve(),
+            Some(&VestingCurve::Linear {
+                cliff_millis: 2_592_000_000,
+                step_millis: 604_800_000,
+                steps: 12,
+            })
+        );
+        assert_eq!(
+            accounts_config.delegators()[0].vesting_curve,
+            Some(VestingCurve::Tranches(vec![
+                VestingTranche::new(0, 25),
+                VestingTranche::new(31_536_000_000, 75),
+            ]))
+        );
+
+        let genesis_accounts: Vec<GenesisAccount> = accounts_config.into();
+        let (.., delegator_vesting_curve) = genesis_accounts
+            .iter()
+            .find_map(GenesisAccount::as_delegator)
+            .unwrap();
+        assert!(delegator_vesting_curve.unwrap().is_valid());
+        let validator_vesting_curve = genesis_accounts
+            .iter()
+            .find_map(GenesisAccount::validator)
+            .and_then(|genesis_validator| genesis_validator.vesting_curve());
+        assert!(validator_vesting_curve.unwrap().is_valid());
+    }
 }
@@ -47,7 +47,7 @@ impl AccountConfig {
 
     /// Bonded amount.
     pub fn bonded_amount(&self) -> Motes {
-        match self.validator {
+        match &self.validator {
             Some(validator_config) => validator_config.bonded_amount(),
             None => Motes::zero(),
         }
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use casper_execution_engine::core::engine_state::GenesisAccount;
 use casper_types::{
     bytesrepr::{self, FromBytes, ToBytes},
+    system::auction::VestingCurve,
     Motes, PublicKey,
 };
 #[cfg(test)]
@@ -22,6 +23,9 @@ pub struct DelegatorConfig {
     pub balance: Motes,
     /// Delegated amount in Motes.
     pub delegated_amount: Motes,
+    /// Custom curve along which the delegated amount is released after the locked funds period.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vesting_curve: Option<VestingCurve>,
 }
 
 impl DelegatorConfig {
@@ -37,6 +41,7 @@ impl DelegatorConfig {
             delegator_public_key,
             balance,
             delegated_amount,
+            vesting_curve: None,
         }
     }
 
@@ -55,6 +60,7 @@ impl DelegatorConfig {
             delegator_public_key,
             balance,
             delegated_amount,
+            vesting_curve: None,
         }
     }
 }
@@ -91,6 +97,7 @@ impl ToBytes for DelegatorConfig {
         buffer.extend(self.delegator_public_key.to_bytes()?);
         buffer.extend(self.balance.to_bytes()?);
         buffer.extend(self.delegated_amount.to_bytes()?);
+        buffer.extend(self.vesting_curve.to_bytes()?);
         Ok(buffer)
     }
 
@@ -99,6 +106,7 @@ impl ToBytes for DelegatorConfig {
             + self.delegator_public_key.serialized_length()
             + self.balance.serialized_length()
             + self.delegated_amount.serialized_length()
+            + self.vesting_curve.serialized_length()
     }
 }
 
@@ -108,11 +116,13 @@ impl FromBytes for DelegatorConfig {
         let (delegator_public_key, remainder) = FromBytes::from_bytes(remainder)?;
         let (balance, remainder) = FromBytes::from_bytes(remainder)?;
         let (delegated_amount, remainder) = FromBytes::from_bytes(remainder)?;
+        let (vesting_curve, remainder) = FromBytes::from_bytes(remainder)?;
         let delegator_config = DelegatorConfig {
             validator_public_key,
             delegator_public_key,
             balance,
             delegated_amount,
+            vesting_curve,
         };
         Ok((delegator_config, remainder))
     }
@@ -120,11 +130,12 @@ impl FromBytes for DelegatorConfig {
 
 impl From<DelegatorConfig> for GenesisAccount {
     fn from(delegator_config: DelegatorConfig) -> Self {
-        GenesisAccount::delegator(
+        GenesisAccount::delegator_with_vesting_curve(
             delegator_config.validator_public_key,
             delegator_config.delegator_public_key,
             delegator_config.balance,
             delegator_config.delegated_amount,
+            delegator_config.vesting_curve,
         )
     }
 }
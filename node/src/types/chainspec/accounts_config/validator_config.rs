@@ -7,18 +7,20 @@ use serde::{Deserialize, Serialize};
 use casper_execution_engine::core::engine_state::genesis::GenesisValidator;
 use casper_types::{
     bytesrepr::{self, FromBytes, ToBytes},
-    system::auction::DelegationRate,
+    system::auction::{DelegationRate, VestingCurve},
     Motes,
 };
 #[cfg(test)]
 use casper_types::{testing::TestRng, U512};
 
 /// Validator account configuration.
-#[derive(PartialEq, Eq, Ord, PartialOrd, Serialize, Deserialize, DataSize, Debug, Copy, Clone)]
+#[derive(PartialEq, Eq, Ord, PartialOrd, Serialize, Deserialize, DataSize, Debug, Clone)]
 pub struct ValidatorConfig {
     bonded_amount: Motes,
     #[serde(default = "DelegationRate::zero")]
     delegation_rate: DelegationRate,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    vesting_curve: Option<VestingCurve>,
 }
 
 impl ValidatorConfig {
@@ -27,9 +29,22 @@ impl ValidatorConfig {
         Self {
             bonded_amount,
             delegation_rate,
+            vesting_curve: None,
         }
     }
 
+    /// Sets the custom curve along which the bonded amount is released after the locked funds
+    /// period.
+    pub fn with_vesting_curve(mut self, vesting_curve: Option<VestingCurve>) -> Self {
+        self.vesting_curve = vesting_curve;
+        self
+    }
+
+    /// Custom vesting curve, if any.
+    pub fn vesting_curve(&self) -> Option<&VestingCurve> {
+        self.vesting_curve.as_ref()
+    }
+
     /// Delegation rate.
     pub fn delegation_rate(&self) -> DelegationRate {
         self.delegation_rate
@@ -49,6 +64,7 @@ impl ValidatorConfig {
         ValidatorConfig {
             bonded_amount,
             delegation_rate,
+            vesting_curve: None,
         }
     }
 }
@@ -71,11 +87,14 @@ impl ToBytes for ValidatorConfig {
         let mut buffer = bytesrepr::allocate_buffer(self)?;
         buffer.extend(self.bonded_amount.to_bytes()?);
         buffer.extend(self.delegation_rate.to_bytes()?);
+        buffer.extend(self.vesting_curve.to_bytes()?);
         Ok(buffer)
     }
 
     fn serialized_length(&self) -> usize {
-        self.bonded_amount.serialized_length() + self.delegation_rate.serialized_length()
+        self.bonded_amount.serialized_length()
+            + self.delegation_rate.serialized_length()
+            + self.vesting_curve.serialized_length()
     }
 }
 
@@ -83,9 +102,11 @@ impl FromBytes for ValidatorConfig {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
         let (bonded_amount, remainder) = FromBytes::from_bytes(bytes)?;
         let (delegation_rate, remainder) = FromBytes::from_bytes(remainder)?;
+        let (vesting_curve, remainder) = FromBytes::from_bytes(remainder)?;
         let account_config = ValidatorConfig {
             bonded_amount,
             delegation_rate,
+            vesting_curve,
         };
         Ok((account_config, remainder))
     }
@@ -97,5 +118,6 @@ impl From<ValidatorConfig> for GenesisValidator {
             account_config.bonded_amount(),
             account_config.delegation_rate,
         )
+        .with_vesting_curve(account_config.vesting_curve)
     }
 }
@@ -2,7 +2,10 @@ use std::collections::BTreeSet;
 
 use tracing::{error, warn};
 
-use casper_execution_engine::core::engine_state::engine_config::{FeeHandling, RefundHandling};
+use casper_execution_engine::core::{
+    engine_state::engine_config::{FeeHandling, RefundHandling},
+    wasm_engine::WasmEngineKind,
+};
 #[cfg(test)]
 use casper_types::testing::TestRng;
 use casper_types::{
@@ -96,6 +99,8 @@ pub struct CoreConfig {
     /// The maximum amount of delegators per validator.
     /// if the value is 0, there is no maximum capacity.
     pub max_delegators_per_validator: u32,
+    /// The maximum amount of delegation slots a validator can reserve.
+    pub max_reserved_delegators_per_validator: u32,
     /// Auction entrypoints such as "add_bid" or "delegate" are disabled if this flag is set to
     /// `false`. Setting up this option makes sense only for private chains where validator set
     /// rotation is unnecessary.
@@ -104,6 +109,27 @@ pub struct CoreConfig {
     pub(crate) allow_unrestricted_transfers: bool,
     /// If set to false then consensus doesn't compute rewards and always uses 0.
     pub(crate) compute_rewards: bool,
+    /// Fraction of stake slashed from an equivocating validator and its delegators.
+    #[data_size(skip)]
+    pub(crate) equivocation_slashing_fraction: Ratio<u64>,
+    /// Fraction of stake slashed from a validator and its delegators that were inactive for a
+    /// whole era.
+    #[data_size(skip)]
+    pub(crate) unavailability_slashing_fraction: Ratio<u64>,
+    /// Number of consecutive eras a validator has to be inactive in before it is jailed.
+    ///
+    /// Only eras in which the validator didn't participate at all count, as reported by consensus
+    /// in the era report.
+    pub(crate) inactivity_jail_threshold: u32,
+    /// Number of eras a validator is jailed for on its first offence, doubling with every repeat
+    /// offence.
+    pub(crate) jail_period_eras: u64,
+    /// Maximum number of eras a validator is jailed for.
+    pub(crate) max_jail_period_eras: u64,
+    /// Allows delegators to transfer their delegations to other delegators without unbonding.
+    pub(crate) allow_delegation_transfers: bool,
+    /// The Wasm interpreter executing contracts.
+    pub(crate) wasm_engine: WasmEngineKind,
     /// Administrative accounts are valid option for a private chain only.
     #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
     pub(crate) administrators: BTreeSet<PublicKey>,
@@ -161,6 +187,36 @@ impl CoreConfig {
             return false;
         }
 
+        for (name, fraction) in [
+            (
+                "equivocation_slashing_fraction",
+                self.equivocation_slashing_fraction,
+            ),
+            (
+                "unavailability_slashing_fraction",
+                self.unavailability_slashing_fraction,
+            ),
+        ] {
+            if fraction > Ratio::new(1, 1) {
+                error!(%fraction, "{} is not in the range [0, 1]", name);
+                return false;
+            }
+        }
+
+        if self.inactivity_jail_threshold == 0 {
+            error!("inactivity_jail_threshold must be greater than 0");
+            return false;
+        }
+
+        if self.max_jail_period_eras < self.jail_period_eras {
+            error!(
+                jail_period_eras = self.jail_period_eras,
+                max_jail_period_eras = self.max_jail_period_eras,
+                "max_jail_period_eras must not be less than jail_period_eras"
+            );
+            return false;
+        }
+
         true
     }
 }
@@ -209,6 +265,17 @@ impl CoreConfig {
         } else {
             FeeHandling::Accumulate
         };
+        let equivocation_slashing_fraction = Ratio::new(rng.gen_range(0..=100), 100);
+        let unavailability_slashing_fraction = Ratio::new(rng.gen_range(0..=100), 100);
+        let inactivity_jail_threshold = rng.gen_range(1..10);
+        let jail_period_eras = rng.gen_range(0..10);
+        let max_jail_period_eras = rng.gen_range(jail_period_eras..100);
+        let allow_delegation_transfers = rng.gen();
+        let wasm_engine = if rng.gen() {
+            WasmEngineKind::Wasmi
+        } else {
+            WasmEngineKind::UpstreamWasmi
+        };
 
         CoreConfig {
             era_duration,
@@ -231,12 +298,20 @@ impl CoreConfig {
             simultaneous_peer_requests,
             consensus_protocol,
             max_delegators_per_validator: 0,
+            max_reserved_delegators_per_validator: rng.gen(),
             allow_auction_bids,
             administrators,
             allow_unrestricted_transfers,
             compute_rewards,
             refund_handling,
             fee_handling,
+            equivocation_slashing_fraction,
+            unavailability_slashing_fraction,
+            inactivity_jail_threshold,
+            jail_period_eras,
+            max_jail_period_eras,
+            allow_delegation_transfers,
+            wasm_engine,
         }
     }
 }
@@ -267,12 +342,20 @@ impl ToBytes for CoreConfig {
         buffer.extend(self.simultaneous_peer_requests.to_bytes()?);
         buffer.extend(self.consensus_protocol.to_bytes()?);
         buffer.extend(self.max_delegators_per_validator.to_bytes()?);
+        buffer.extend(self.max_reserved_delegators_per_validator.to_bytes()?);
         buffer.extend(self.allow_auction_bids.to_bytes()?);
         buffer.extend(self.allow_unrestricted_transfers.to_bytes()?);
         buffer.extend(self.compute_rewards.to_bytes()?);
         buffer.extend(self.administrators.to_bytes()?);
         buffer.extend(self.refund_handling.to_bytes()?);
         buffer.extend(self.fee_handling.to_bytes()?);
+        buffer.extend(self.equivocation_slashing_fraction.to_bytes()?);
+        buffer.extend(self.unavailability_slashing_fraction.to_bytes()?);
+        buffer.extend(self.inactivity_jail_threshold.to_bytes()?);
+        buffer.extend(self.jail_period_eras.to_bytes()?);
+        buffer.extend(self.max_jail_period_eras.to_bytes()?);
+        buffer.extend(self.allow_delegation_transfers.to_bytes()?);
+        buffer.extend(self.wasm_engine.to_bytes()?);
         Ok(buffer)
     }
 
@@ -299,12 +382,22 @@ impl ToBytes for CoreConfig {
             + self.simultaneous_peer_requests.serialized_length()
             + self.consensus_protocol.serialized_length()
             + self.max_delegators_per_validator.serialized_length()
+            + self
+                .max_reserved_delegators_per_validator
+                .serialized_length()
             + self.allow_auction_bids.serialized_length()
             + self.allow_unrestricted_transfers.serialized_length()
             + self.compute_rewards.serialized_length()
             + self.administrators.serialized_length()
             + self.refund_handling.serialized_length()
             + self.fee_handling.serialized_length()
+            + self.equivocation_slashing_fraction.serialized_length()
+            + self.unavailability_slashing_fraction.serialized_length()
+            + self.inactivity_jail_threshold.serialized_length()
+            + self.jail_period_eras.serialized_length()
+            + self.max_jail_period_eras.serialized_length()
+            + self.allow_delegation_transfers.serialized_length()
+            + self.wasm_engine.serialized_length()
     }
 }
 
@@ -331,12 +424,20 @@ impl FromBytes for CoreConfig {
         let (simultaneous_peer_requests, remainder) = u8::from_bytes(remainder)?;
         let (consensus_protocol, remainder) = ConsensusProtocolName::from_bytes(remainder)?;
         let (max_delegators_per_validator, remainder) = FromBytes::from_bytes(remainder)?;
+        let (max_reserved_delegators_per_validator, remainder) = u32::from_bytes(remainder)?;
         let (allow_auction_bids, remainder) = FromBytes::from_bytes(remainder)?;
         let (allow_unrestricted_transfers, remainder) = FromBytes::from_bytes(remainder)?;
         let (compute_rewards, remainder) = bool::from_bytes(remainder)?;
         let (administrative_accounts, remainder) = FromBytes::from_bytes(remainder)?;
         let (refund_handling, remainder) = FromBytes::from_bytes(remainder)?;
         let (fee_handling, remainder) = FromBytes::from_bytes(remainder)?;
+        let (equivocation_slashing_fraction, remainder) = Ratio::<u64>::from_bytes(remainder)?;
+        let (unavailability_slashing_fraction, remainder) = Ratio::<u64>::from_bytes(remainder)?;
+        let (inactivity_jail_threshold, remainder) = u32::from_bytes(remainder)?;
+        let (jail_period_eras, remainder) = u64::from_bytes(remainder)?;
+        let (max_jail_period_eras, remainder) = u64::from_bytes(remainder)?;
+        let (allow_delegation_transfers, remainder) = bool::from_bytes(remainder)?;
+        let (wasm_engine, remainder) = WasmEngineKind::from_bytes(remainder)?;
         let config = CoreConfig {
             era_duration,
             minimum_era_height,
@@ -358,12 +459,20 @@ impl FromBytes for CoreConfig {
             simultaneous_peer_requests,
             consensus_protocol,
             max_delegators_per_validator,
+            max_reserved_delegators_per_validator,
             allow_auction_bids,
             allow_unrestricted_transfers,
             compute_rewards,
             administrators: administrative_accounts,
             refund_handling,
             fee_handling,
+            equivocation_slashing_fraction,
+            unavailability_slashing_fraction,
+            inactivity_jail_threshold,
+            jail_period_eras,
+            max_jail_period_eras,
+            allow_delegation_transfers,
+            wasm_engine,
         };
         Ok((config, remainder))
     }
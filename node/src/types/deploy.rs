@@ -37,10 +37,10 @@ use casper_execution_engine::core::engine_state::{
     executable_deploy_item::ExecutableDeployItem, DeployItem,
 };
 use casper_hashing::Digest;
-#[cfg(test)]
-use casper_types::bytesrepr::Bytes;
 #[cfg(any(feature = "testing", test))]
 use casper_types::testing::TestRng;
+#[cfg(test)]
+use casper_types::{bytesrepr::Bytes, Key};
 use casper_types::{
     bytesrepr::{self, FromBytes, ToBytes},
     crypto, runtime_args,
@@ -666,13 +666,23 @@ impl Deploy {
         timestamp: Timestamp,
         ttl: TimeDiff,
     ) -> Self {
-        let gas_price = rng.gen_range(1..100);
-
         let dependencies = vec![
             DeployHash::new(Digest::hash(rng.next_u64().to_le_bytes())),
             DeployHash::new(Digest::hash(rng.next_u64().to_le_bytes())),
             DeployHash::new(Digest::hash(rng.next_u64().to_le_bytes())),
         ];
+        Deploy::random_with_dependencies(rng, timestamp, ttl, dependencies)
+    }
+
+    /// Returns a random deploy but using the specified `timestamp`, `ttl` and `dependencies`.
+    pub fn random_with_dependencies(
+        rng: &mut TestRng,
+        timestamp: Timestamp,
+        ttl: TimeDiff,
+        dependencies: Vec<DeployHash>,
+    ) -> Self {
+        let gas_price = rng.gen_range(1..100);
+
         let chain_name = String::from("casper-example");
 
         // We need "amount" in order to be able to get correct info via `deploy_info()`.
@@ -718,13 +728,13 @@ impl Deploy {
         Self::random_valid_native_transfer_with_timestamp_and_ttl(rng, timestamp, ttl)
     }
 
-    /// Returns a random deploy for a native transfer with timestamp and ttl.
+    /// Returns a random deploy for a native transfer with timestamp and ttl and no dependencies.
     pub(crate) fn random_valid_native_transfer_with_timestamp_and_ttl(
         rng: &mut TestRng,
         timestamp: Timestamp,
         ttl: TimeDiff,
     ) -> Self {
-        let deploy = Self::random_with_timestamp_and_ttl(rng, timestamp, ttl);
+        let deploy = Self::random_with_dependencies(rng, timestamp, ttl, vec![]);
         let transfer_args = runtime_args! {
             "amount" => *MAX_PAYMENT,
             "source" => PublicKey::random(rng).to_account_hash(),
@@ -807,6 +817,19 @@ impl Deploy {
         Self::random_transfer_with_payment(rng, payment)
     }
 
+    /// Returns a random deploy whose standard payment names the given sponsor.
+    pub(crate) fn random_with_payment_sponsor(rng: &mut TestRng, sponsor: Key) -> Self {
+        let payment_args = runtime_args! {
+            "amount" => U512::from(10),
+            "sponsor" => sponsor,
+        };
+        let payment = ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::new(),
+            args: payment_args,
+        };
+        Self::random_transfer_with_payment(rng, payment)
+    }
+
     /// Returns a random deploy with custom payment specified as a stored contract by name.
     pub(crate) fn random_with_valid_custom_payment_contract_by_name(rng: &mut TestRng) -> Self {
         let payment = ExecutableDeployItem::StoredContractByName {
@@ -10,7 +10,9 @@ use serde::{Deserialize, Serialize};
 
 use casper_hashing::Digest;
 use casper_types::{
-    system::auction::{Bid, Bids, DelegationRate, Delegator, EraValidators},
+    system::auction::{
+        Bid, Bids, DelegationRate, Delegator, EraValidators, RewardDestination, VestingSchedule,
+    },
     AccessRights, EraId, PublicKey, SecretKey, URef, U512,
 };
 
@@ -93,6 +95,12 @@ pub struct JsonDelegator {
     staked_amount: U512,
     bonding_purse: URef,
     delegatee: PublicKey,
+    /// Where the delegator's seigniorage is paid, if not added to its stake.
+    #[serde(default, skip_serializing_if = "RewardDestination::is_compound")]
+    reward_destination: RewardDestination,
+    /// The vesting schedule of a genesis delegator.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    vesting_schedule: Option<VestingSchedule>,
 }
 
 /// An entry in a founding validator map representing a bid.
@@ -109,6 +117,31 @@ pub struct JsonBid {
     delegators: Vec<JsonDelegator>,
     /// Is this an inactive validator.
     inactive: bool,
+    /// The minimum amount of a single delegation, if limited by the validator.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    minimum_delegation_amount: Option<u64>,
+    /// The maximum total amount a single delegator can delegate, if limited by the validator.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    maximum_delegation_amount: Option<u64>,
+    /// The delegators with a reserved delegation slot.  If not empty, only these delegators can
+    /// delegate.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    reserved_delegators: Vec<PublicKey>,
+    /// The number of consecutive eras in which consensus reported the validator as inactive.
+    #[serde(default, skip_serializing_if = "Zero::is_zero")]
+    inactive_eras: u32,
+    /// The number of times the validator has been jailed.
+    #[serde(default, skip_serializing_if = "Zero::is_zero")]
+    jail_count: u32,
+    /// The era from which the jailed validator can unjail, if jailed for a period.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    jailed_until: Option<EraId>,
+    /// Where the validator's seigniorage is paid, if not added to its stake.
+    #[serde(default, skip_serializing_if = "RewardDestination::is_compound")]
+    reward_destination: RewardDestination,
+    /// The vesting schedule of a genesis validator.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    vesting_schedule: Option<VestingSchedule>,
 }
 
 impl From<Bid> for JsonBid {
@@ -120,6 +153,8 @@ impl From<Bid> for JsonBid {
                 staked_amount: *delegator.staked_amount(),
                 bonding_purse: *delegator.bonding_purse(),
                 delegatee: delegator.validator_public_key().clone(),
+                reward_destination: bid.delegator_reward_destination(public_key),
+                vesting_schedule: delegator.vesting_schedule().cloned(),
             });
         }
         JsonBid {
@@ -128,6 +163,14 @@ impl From<Bid> for JsonBid {
             delegation_rate: *bid.delegation_rate(),
             delegators: json_delegators,
             inactive: bid.inactive(),
+            minimum_delegation_amount: bid.minimum_delegation_amount(),
+            maximum_delegation_amount: bid.maximum_delegation_amount(),
+            reserved_delegators: bid.reserved_delegators().iter().cloned().collect(),
+            inactive_eras: bid.inactive_eras(),
+            jail_count: bid.jail_count(),
+            jailed_until: bid.jailed_until(),
+            reward_destination: bid.reward_destination(),
+            vesting_schedule: bid.vesting_schedule().cloned(),
         }
     }
 }
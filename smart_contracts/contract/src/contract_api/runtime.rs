@@ -6,11 +6,12 @@ use core::mem::MaybeUninit;
 use casper_types::{
     account::AccountHash,
     api_error,
-    bytesrepr::{self, FromBytes},
+    bytesrepr::{self, FromBytes, ToBytes},
     contracts::{ContractVersion, NamedKeys},
     system::CallStackElement,
     ApiError, BlockTime, CLTyped, CLValue, ContractHash, ContractPackageHash, Key, Phase,
-    RuntimeArgs, URef, BLAKE2B_DIGEST_LENGTH, BLOCKTIME_SERIALIZED_LENGTH, PHASE_SERIALIZED_LENGTH,
+    PublicKey, RuntimeArgs, Signature, URef, BLAKE2B_DIGEST_LENGTH, BLOCKTIME_SERIALIZED_LENGTH,
+    KECCAK256_DIGEST_LENGTH, PHASE_SERIALIZED_LENGTH, SHA256_DIGEST_LENGTH,
 };
 
 use crate::{contract_api, ext_ffi, unwrap_or_revert::UnwrapOrRevert};
@@ -351,6 +352,64 @@ pub fn blake2b<T: AsRef<[u8]>>(input: T) -> [u8; BLAKE2B_DIGEST_LENGTH] {
     ret
 }
 
+/// Returns a 32-byte SHA-256 digest
+pub fn sha256<T: AsRef<[u8]>>(input: T) -> [u8; SHA256_DIGEST_LENGTH] {
+    let mut ret = [0; SHA256_DIGEST_LENGTH];
+    let result = unsafe {
+        ext_ffi::casper_sha256(
+            input.as_ref().as_ptr(),
+            input.as_ref().len(),
+            ret.as_mut_ptr(),
+            SHA256_DIGEST_LENGTH,
+        )
+    };
+    api_error::result_from(result).unwrap_or_revert();
+    ret
+}
+
+/// Returns a 32-byte Keccak-256 digest, as used by Ethereum
+pub fn keccak256<T: AsRef<[u8]>>(input: T) -> [u8; KECCAK256_DIGEST_LENGTH] {
+    let mut ret = [0; KECCAK256_DIGEST_LENGTH];
+    let result = unsafe {
+        ext_ffi::casper_keccak256(
+            input.as_ref().as_ptr(),
+            input.as_ref().len(),
+            ret.as_mut_ptr(),
+            KECCAK256_DIGEST_LENGTH,
+        )
+    };
+    api_error::result_from(result).unwrap_or_revert();
+    ret
+}
+
+/// Returns `true` if `signature` is a valid signature of `message` by `public_key`.
+///
+/// Both Ed25519 and secp256k1 keys are supported.  A signature of a different algorithm than the
+/// public key is never valid.
+pub fn verify_signature<T: AsRef<[u8]>>(
+    message: T,
+    signature: &Signature,
+    public_key: &PublicKey,
+) -> bool {
+    let signature_bytes = signature.to_bytes().unwrap_or_revert();
+    let public_key_bytes = public_key.to_bytes().unwrap_or_revert();
+    let result = unsafe {
+        ext_ffi::casper_verify_signature(
+            message.as_ref().as_ptr(),
+            message.as_ref().len(),
+            signature_bytes.as_ptr(),
+            signature_bytes.len(),
+            public_key_bytes.as_ptr(),
+            public_key_bytes.len(),
+        )
+    };
+    match api_error::result_from(result) {
+        Ok(()) => true,
+        Err(ApiError::InvalidSignature) => false,
+        Err(error) => revert(error),
+    }
+}
+
 /// Returns 32 pseudo random bytes.
 pub fn random_bytes() -> [u8; RANDOM_BYTES_COUNT] {
     let mut ret = [0; RANDOM_BYTES_COUNT];
@@ -359,6 +418,19 @@ pub fn random_bytes() -> [u8; RANDOM_BYTES_COUNT] {
     ret
 }
 
+/// Emits an event with the given topic and value.
+///
+/// Events are recorded in the execution result of the deploy, along with the key of the account or
+/// contract which emitted them, and are streamed to off-chain consumers by the node.
+pub fn emit_event<T: CLTyped + ToBytes>(topic: &str, value: T) {
+    let value = CLValue::from_t(value).unwrap_or_revert();
+    let (topic_ptr, topic_size, _topic_bytes) = contract_api::to_ptr(topic);
+    let (value_ptr, value_size, _value_bytes) = contract_api::to_ptr(value);
+    let result =
+        unsafe { ext_ffi::casper_emit_event(topic_ptr, topic_size, value_ptr, value_size) };
+    api_error::result_from(result).unwrap_or_revert();
+}
+
 fn read_host_buffer_into(dest: &mut [u8]) -> Result<usize, ApiError> {
     let mut bytes_written = MaybeUninit::uninit();
     let ret = unsafe {
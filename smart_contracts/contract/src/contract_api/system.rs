@@ -5,13 +5,15 @@ use core::mem::MaybeUninit;
 
 use casper_types::{
     account::AccountHash,
-    api_error, bytesrepr,
+    api_error, bytesrepr, runtime_args,
     system::{
         auction::{self, EraInfo},
+        handle_payment,
+        mint::{self, TransferSchedule},
         SystemContractType,
     },
-    ApiError, ContractHash, EraId, HashAddr, PublicKey, TransferResult, TransferredTo, URef, U512,
-    UREF_SERIALIZED_LENGTH,
+    ApiError, ContractHash, EraId, HashAddr, Key, PublicKey, RuntimeArgs, TransferResult,
+    TransferredTo, URef, URefAddr, U512, UREF_SERIALIZED_LENGTH,
 };
 
 use crate::{
@@ -222,6 +224,115 @@ pub fn transfer_from_purse_to_purse(
     api_error::result_from(result)
 }
 
+/// Grants `spender` an allowance to transfer up to `amount` motes out of `purse` until the block
+/// time `expires_at`, replacing any allowance previously granted to `spender` over `purse`.  A zero
+/// `amount` revokes the allowance.
+///
+/// `spender` must be a [`Key::Account`] or a [`Key::Hash`] of a contract.
+pub fn approve_allowance(
+    purse: URef,
+    spender: Key,
+    amount: U512,
+    expires_at: Option<u64>,
+) -> Result<(), ApiError> {
+    let (purse_ptr, purse_size, _bytes1) = contract_api::to_ptr(purse);
+    let (spender_ptr, spender_size, _bytes2) = contract_api::to_ptr(spender);
+    let (amount_ptr, amount_size, _bytes3) = contract_api::to_ptr(amount);
+    let (expires_at_ptr, expires_at_size, _bytes4) = contract_api::to_ptr(expires_at);
+    let result = unsafe {
+        ext_ffi::casper_approve_allowance(
+            purse_ptr,
+            purse_size,
+            spender_ptr,
+            spender_size,
+            amount_ptr,
+            amount_size,
+            expires_at_ptr,
+            expires_at_size,
+        )
+    };
+    api_error::result_from(result)
+}
+
+/// Transfers `amount` of motes from the purse at `source` to `target` purse, spending the
+/// allowance granted over `source` to the caller of this function.
+pub fn transfer_from_allowance(
+    source: URefAddr,
+    target: URef,
+    amount: U512,
+    id: Option<u64>,
+) -> Result<(), ApiError> {
+    let result: Result<(), mint::Error> = runtime::call_contract(
+        get_mint(),
+        mint::METHOD_TRANSFER_FROM_ALLOWANCE,
+        runtime_args! {
+            mint::ARG_SOURCE => source,
+            mint::ARG_TARGET => target,
+            mint::ARG_AMOUNT => amount,
+            mint::ARG_ID => id,
+        },
+    );
+    result.map_err(ApiError::from)
+}
+
+/// Schedules `runs` transfers of `amount` motes each from `source` purse to the main purse of
+/// `target`, to be run by the system at the end of the eras or block times given by `schedule`.
+///
+/// The total amount is moved into an escrow purse held by the mint up front.  Returns the id of
+/// the new scheduled transfer.
+pub fn schedule_transfer(
+    source: URef,
+    target: AccountHash,
+    amount: U512,
+    schedule: TransferSchedule,
+    runs: u32,
+    id: Option<u64>,
+) -> Result<u64, ApiError> {
+    let result: Result<u64, mint::Error> = runtime::call_contract(
+        get_mint(),
+        mint::METHOD_SCHEDULE_TRANSFER,
+        runtime_args! {
+            mint::ARG_SOURCE => source,
+            mint::ARG_TARGET => target,
+            mint::ARG_AMOUNT => amount,
+            mint::ARG_SCHEDULE => schedule,
+            mint::ARG_RUNS => runs,
+            mint::ARG_ID => id,
+        },
+    );
+    result.map_err(ApiError::from)
+}
+
+/// Cancels the scheduled transfer `schedule_id` created by the caller of this function, refunding
+/// the remaining escrowed motes to the caller's main purse.
+pub fn cancel_scheduled_transfer(schedule_id: u64) -> Result<(), ApiError> {
+    let result: Result<(), mint::Error> = runtime::call_contract(
+        get_mint(),
+        mint::METHOD_CANCEL_SCHEDULED_TRANSFER,
+        runtime_args! {
+            mint::ARG_SCHEDULE_ID => schedule_id,
+        },
+    );
+    result.map_err(ApiError::from)
+}
+
+/// Pays `amount` of motes from `purse` for the deploy being executed, refunding any unspent payment
+/// to `purse`.
+///
+/// Can only be called in the payment phase, typically from the `approve_payment` entry point of a
+/// contract sponsoring the deploy.  Any failure will trigger [`revert`](runtime::revert) with an
+/// appropriate [`ApiError`].
+pub fn sponsor_payment(purse: URef, amount: U512) {
+    runtime::call_contract::<()>(
+        get_handle_payment(),
+        handle_payment::METHOD_SPONSOR_PAYMENT,
+        runtime_args! {
+            handle_payment::ARG_PURSE => purse,
+            handle_payment::ARG_AMOUNT => amount,
+        },
+    )
+}
+
 /// Records a transfer.  Can only be called from within the mint contract.
 /// Needed to support system contract-based execution.
 #[doc(hidden)]
@@ -805,4 +805,89 @@ extern "C" {
         contract_hash_ptr: *const u8,
         contract_hash_size: usize,
     ) -> i32;
+    /// Emits an event with the given topic and value, recorded in the execution result of the
+    /// deploy. Returns non-zero standard error for a failure, otherwise a zero indicates success.
+    ///
+    /// # Arguments
+    ///
+    /// * `topic_ptr` - pointer to serialized topic.
+    /// * `topic_size` - size of topic in serialized form.
+    /// * `value_ptr` - pointer to serialized `CLValue`.
+    /// * `value_size` - size of `CLValue` in serialized form.
+    pub fn casper_emit_event(
+        topic_ptr: *const u8,
+        topic_size: usize,
+        value_ptr: *const u8,
+        value_size: usize,
+    ) -> i32;
+    /// Verifies the signature of the given message against the given public key. Returns zero if
+    /// the signature is valid, otherwise a non-zero standard error.
+    ///
+    /// # Arguments
+    ///
+    /// * `message_ptr` - pointer to the message bytes.
+    /// * `message_size` - length of the message bytes.
+    /// * `signature_ptr` - pointer to serialized `Signature`.
+    /// * `signature_size` - size of `Signature` in serialized form.
+    /// * `public_key_ptr` - pointer to serialized `PublicKey`.
+    /// * `public_key_size` - size of `PublicKey` in serialized form.
+    pub fn casper_verify_signature(
+        message_ptr: *const u8,
+        message_size: usize,
+        signature_ptr: *const u8,
+        signature_size: usize,
+        public_key_ptr: *const u8,
+        public_key_size: usize,
+    ) -> i32;
+    /// Returns a 32-byte SHA-256 hash digest from the given input bytes
+    ///
+    /// # Arguments
+    /// * `in_ptr` - pointer to bytes
+    /// * `in_size` - length of bytes
+    /// * `out_ptr` - pointer to the location where argument bytes will be copied from the host side
+    /// * `out_size` - size of output pointer
+    pub fn casper_sha256(
+        in_ptr: *const u8,
+        in_size: usize,
+        out_ptr: *mut u8,
+        out_size: usize,
+    ) -> i32;
+    /// Returns a 32-byte Keccak-256 hash digest from the given input bytes
+    ///
+    /// # Arguments
+    /// * `in_ptr` - pointer to bytes
+    /// * `in_size` - length of bytes
+    /// * `out_ptr` - pointer to the location where argument bytes will be copied from the host side
+    /// * `out_size` - size of output pointer
+    pub fn casper_keccak256(
+        in_ptr: *const u8,
+        in_size: usize,
+        out_ptr: *mut u8,
+        out_size: usize,
+    ) -> i32;
+    /// Grants a spender an allowance to transfer motes out of the given purse through the mint's
+    /// `transfer_from_allowance` entry point. Any allowance previously granted to the same spender
+    /// over the same purse is replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `purse_ptr` - pointer in wasm memory to bytes representing the `URef` of the purse
+    /// * `purse_size` - size of the `URef` (in bytes)
+    /// * `spender_ptr` - pointer in wasm memory to bytes representing the spender's `Key`
+    /// * `spender_size` - size of the `Key` (in bytes)
+    /// * `amount_ptr` - pointer in wasm memory to bytes representing the allowed amount
+    /// * `amount_size` - size of the amount (in bytes)
+    /// * `expires_at_ptr` - pointer in wasm memory to bytes representing the optional block time at
+    ///   which the allowance expires
+    /// * `expires_at_size` - size of the expiry (in bytes)
+    pub fn casper_approve_allowance(
+        purse_ptr: *const u8,
+        purse_size: usize,
+        spender_ptr: *const u8,
+        spender_size: usize,
+        amount_ptr: *const u8,
+        amount_size: usize,
+        expires_at_ptr: *const u8,
+        expires_at_size: usize,
+    ) -> i32;
 }
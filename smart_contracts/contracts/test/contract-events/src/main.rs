@@ -0,0 +1,23 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use casper_contract::contract_api::runtime;
+
+const ARG_TOPIC: &str = "topic";
+const ARG_VALUE: &str = "value";
+const ARG_COUNT: &str = "count";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let topic: String = runtime::get_named_arg(ARG_TOPIC);
+    let value: u64 = runtime::get_named_arg(ARG_VALUE);
+    let count: u32 = runtime::get_named_arg(ARG_COUNT);
+
+    for index in 0..count {
+        runtime::emit_event(&topic, value + u64::from(index));
+    }
+}
@@ -0,0 +1,27 @@
+#![no_std]
+#![no_main]
+
+use casper_contract::contract_api::runtime;
+use casper_types::{
+    account::AccountHash, runtime_args, system::handle_payment, ContractHash, RuntimeArgs, U512,
+};
+
+const ARG_CONTRACT_HASH: &str = "contract_hash";
+
+/// Payment code calling a sponsoring contract's `approve_payment` entry point itself, claiming the
+/// deploy is from an account the sponsor pays for.
+#[no_mangle]
+pub extern "C" fn call() {
+    let contract_hash: ContractHash = runtime::get_named_arg(ARG_CONTRACT_HASH);
+    let amount: U512 = runtime::get_named_arg(handle_payment::ARG_AMOUNT);
+    let account: AccountHash = runtime::get_named_arg(handle_payment::ARG_ACCOUNT);
+
+    runtime::call_contract::<()>(
+        contract_hash,
+        handle_payment::ENTRY_POINT_APPROVE_PAYMENT,
+        runtime_args! {
+            handle_payment::ARG_AMOUNT => amount,
+            handle_payment::ARG_ACCOUNT => account,
+        },
+    );
+}
@@ -0,0 +1,80 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{string::ToString, vec};
+
+use casper_contract::{
+    contract_api::{account, runtime, storage, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    account::AccountHash,
+    contracts::{EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, NamedKeys, Parameter},
+    system::handle_payment,
+    CLType, CLTyped, Key, U512,
+};
+
+const SPONSOR_PURSE_KEY: &str = "sponsor_purse";
+const PACKAGE_HASH_KEY_NAME: &str = "sponsor_payment_package";
+const ACCESS_KEY_NAME: &str = "sponsor_payment_access";
+const HASH_KEY_NAME: &str = "sponsor_payment_hash";
+const ARG_AMOUNT: &str = "amount";
+
+#[no_mangle]
+pub extern "C" fn approve_payment() {
+    let amount: U512 = runtime::get_named_arg(handle_payment::ARG_AMOUNT);
+
+    let sponsor_purse = runtime::get_key(SPONSOR_PURSE_KEY)
+        .and_then(Key::into_uref)
+        .unwrap_or_revert();
+
+    system::sponsor_payment(sponsor_purse, amount);
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+
+    let entry_points = {
+        let mut entry_points = EntryPoints::new();
+
+        let approve_payment = EntryPoint::new(
+            handle_payment::ENTRY_POINT_APPROVE_PAYMENT.to_string(),
+            vec![
+                Parameter::new(handle_payment::ARG_AMOUNT, U512::cl_type()),
+                Parameter::new(handle_payment::ARG_ACCOUNT, AccountHash::cl_type()),
+            ],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        );
+
+        entry_points.add_entry_point(approve_payment);
+        entry_points
+    };
+
+    let named_keys = {
+        let sponsor_purse = system::create_purse();
+        system::transfer_from_purse_to_purse(
+            account::get_main_purse(),
+            sponsor_purse,
+            amount,
+            None,
+        )
+        .unwrap_or_revert();
+
+        let mut named_keys = NamedKeys::new();
+        named_keys.insert(SPONSOR_PURSE_KEY.to_string(), sponsor_purse.into());
+        named_keys
+    };
+
+    let (contract_hash, _contract_version) = storage::new_contract(
+        entry_points,
+        Some(named_keys),
+        Some(PACKAGE_HASH_KEY_NAME.to_string()),
+        Some(ACCESS_KEY_NAME.to_string()),
+    );
+    runtime::put_key(HASH_KEY_NAME, contract_hash.into());
+}
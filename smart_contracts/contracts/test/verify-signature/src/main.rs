@@ -0,0 +1,29 @@
+#![no_std]
+#![no_main]
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    bytesrepr::{self, Bytes},
+    PublicKey, Signature,
+};
+
+const SIGNATURE_VALID: &str = "signature_valid";
+
+const ARG_MESSAGE: &str = "message";
+const ARG_SIGNATURE: &str = "signature";
+const ARG_PUBLIC_KEY: &str = "public_key";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let message: Bytes = runtime::get_named_arg(ARG_MESSAGE);
+    let signature_bytes: Bytes = runtime::get_named_arg(ARG_SIGNATURE);
+    let public_key: PublicKey = runtime::get_named_arg(ARG_PUBLIC_KEY);
+
+    let signature: Signature = bytesrepr::deserialize(signature_bytes.into()).unwrap_or_revert();
+    let is_valid = runtime::verify_signature(message, &signature, &public_key);
+    let uref = storage::new_uref(is_valid);
+    runtime::put_key(SIGNATURE_VALID, uref.into())
+}
@@ -7,6 +7,8 @@ use blake2::{
     digest::{Update, VariableOutput},
     VarBlake2b,
 };
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
 
 use crate::key::BLAKE2B_DIGEST_LENGTH;
 #[cfg(any(feature = "std", test))]
@@ -21,6 +23,11 @@ pub use error::Error;
 #[cfg(any(feature = "std", test))]
 pub use error::ErrorExt;
 
+/// The number of bytes in a SHA-256 digest.
+pub const SHA256_DIGEST_LENGTH: usize = 32;
+/// The number of bytes in a Keccak-256 digest.
+pub const KECCAK256_DIGEST_LENGTH: usize = 32;
+
 #[doc(hidden)]
 pub fn blake2b<T: AsRef<[u8]>>(data: T) -> [u8; BLAKE2B_DIGEST_LENGTH] {
     let mut result = [0; BLAKE2B_DIGEST_LENGTH];
@@ -33,3 +40,40 @@ pub fn blake2b<T: AsRef<[u8]>>(data: T) -> [u8; BLAKE2B_DIGEST_LENGTH] {
     });
     result
 }
+
+/// Returns the SHA-256 digest of `data`.
+pub fn sha256<T: AsRef<[u8]>>(data: T) -> [u8; SHA256_DIGEST_LENGTH] {
+    Sha256::digest(data).into()
+}
+
+/// Returns the Keccak-256 digest of `data`, as used by Ethereum.
+///
+/// Note that this differs from the standardized SHA3-256 in its padding.
+pub fn keccak256<T: AsRef<[u8]>>(data: T) -> [u8; KECCAK256_DIGEST_LENGTH] {
+    Keccak256::digest(data).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_compute_sha256_digest() {
+        assert_eq!(
+            hex::encode(sha256(b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn should_compute_keccak256_digest() {
+        assert_eq!(
+            hex::encode(keccak256(b"")),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+        assert_eq!(
+            hex::encode(keccak256(b"abc")),
+            "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"
+        );
+    }
+}
@@ -30,7 +30,7 @@ use rand::{
 };
 #[cfg(feature = "json-schema")]
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 #[cfg(feature = "json-schema")]
 use crate::KEY_HASH_LENGTH;
@@ -38,7 +38,7 @@ use crate::{
     account::AccountHash,
     bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
     system::auction::{Bid, EraInfo, UnbondingPurse, WithdrawPurse},
-    CLValue, DeployInfo, NamedKey, Transfer, TransferAddr, U128, U256, U512,
+    CLValue, DeployInfo, Key, NamedKey, Transfer, TransferAddr, U128, U256, U512,
 };
 
 #[derive(FromPrimitive, ToPrimitive, Debug)]
@@ -46,6 +46,9 @@ use crate::{
 enum ExecutionResultTag {
     Failure = 0,
     Success = 1,
+    SuccessWithEvents = 2,
+    FailureWithSponsor = 3,
+    SuccessWithSponsor = 4,
 }
 
 impl TryFrom<u8> for ExecutionResultTag {
@@ -143,10 +146,19 @@ static EXECUTION_RESULT: Lazy<ExecutionResult> = Lazy::new(|| {
         TransferAddr::new([130; KEY_HASH_LENGTH]),
     ];
 
+    let events = vec![ContractEvent {
+        emitter: "hash-2c4a11c062a8a337bfc97e27fd66291caeb2c65865dcb5d3ef3759c4c97efecb"
+            .to_string(),
+        topic: "transfer".to_string(),
+        value: CLValue::from_t(U512::from(1_000)).unwrap(),
+    }];
+
     ExecutionResult::Success {
         effect,
         transfers,
         cost: U512::from(123_456),
+        events,
+        sponsor: None,
     }
 });
 
@@ -154,7 +166,7 @@ static EXECUTION_RESULT: Lazy<ExecutionResult> = Lazy::new(|| {
 #[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
 #[cfg_attr(feature = "datasize", derive(DataSize))]
 #[cfg_attr(feature = "json-schema", derive(JsonSchema))]
-#[serde(deny_unknown_fields)]
+#[serde(deny_unknown_fields, remote = "Self")]
 pub enum ExecutionResult {
     /// The result of a failed execution.
     Failure {
@@ -166,6 +178,14 @@ pub enum ExecutionResult {
         cost: U512,
         /// The error message associated with executing the deploy.
         error_message: String,
+        /// The account or contract which paid for the deploy in place of its account, if any.
+        #[serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "serde_option_formatted_key"
+        )]
+        #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]
+        sponsor: Option<Key>,
     },
     /// The result of a successful execution.
     Success {
@@ -175,6 +195,17 @@ pub enum ExecutionResult {
         transfers: Vec<TransferAddr>,
         /// The cost of executing the deploy.
         cost: U512,
+        /// The events emitted by contracts while executing the deploy.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        events: Vec<ContractEvent>,
+        /// The account or contract which paid for the deploy in place of its account, if any.
+        #[serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "serde_option_formatted_key"
+        )]
+        #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]
+        sponsor: Option<Key>,
     },
 }
 
@@ -186,23 +217,288 @@ impl ExecutionResult {
         &EXECUTION_RESULT
     }
 
+    /// Returns the events emitted by contracts while executing the deploy.
+    ///
+    /// Events are only recorded for successful executions.
+    pub fn events(&self) -> &[ContractEvent] {
+        match self {
+            ExecutionResult::Failure { .. } => &[],
+            ExecutionResult::Success { events, .. } => events,
+        }
+    }
+
+    /// Returns the account or contract which paid for the deploy in place of its account, if any.
+    pub fn sponsor(&self) -> Option<Key> {
+        match self {
+            ExecutionResult::Failure { sponsor, .. } | ExecutionResult::Success { sponsor, .. } => {
+                *sponsor
+            }
+        }
+    }
+
     fn tag(&self) -> ExecutionResultTag {
         match self {
+            ExecutionResult::Failure { sponsor: None, .. } => ExecutionResultTag::Failure,
+            ExecutionResult::Failure { .. } => ExecutionResultTag::FailureWithSponsor,
+            ExecutionResult::Success {
+                sponsor: Some(_), ..
+            } => ExecutionResultTag::SuccessWithSponsor,
+            ExecutionResult::Success { events, .. } if events.is_empty() => {
+                ExecutionResultTag::Success
+            }
+            ExecutionResult::Success { .. } => ExecutionResultTag::SuccessWithEvents,
+        }
+    }
+}
+
+// Binary formats such as bincode can't skip a field, so in those the events of a successful result
+// and the sponsor of a result are carried by separate variants.  A result without events or a
+// sponsor keeps the representation it had before these were introduced, so previously stored
+// results remain readable.
+impl Serialize for ExecutionResult {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return ExecutionResult::serialize(self, serializer);
+        }
+        let helper = match self {
             ExecutionResult::Failure {
-                effect: _,
-                transfers: _,
-                cost: _,
-                error_message: _,
-            } => ExecutionResultTag::Failure,
+                effect,
+                transfers,
+                cost,
+                error_message,
+                sponsor: None,
+            } => BinarySerHelper::Failure {
+                effect,
+                transfers,
+                cost,
+                error_message,
+            },
+            ExecutionResult::Failure {
+                effect,
+                transfers,
+                cost,
+                error_message,
+                sponsor: Some(sponsor),
+            } => BinarySerHelper::FailureWithSponsor {
+                effect,
+                transfers,
+                cost,
+                error_message,
+                sponsor,
+            },
             ExecutionResult::Success {
-                effect: _,
-                transfers: _,
-                cost: _,
-            } => ExecutionResultTag::Success,
+                effect,
+                transfers,
+                cost,
+                events,
+                sponsor: Some(sponsor),
+            } => BinarySerHelper::SuccessWithSponsor {
+                effect,
+                transfers,
+                cost,
+                events,
+                sponsor,
+            },
+            ExecutionResult::Success {
+                effect,
+                transfers,
+                cost,
+                events,
+                sponsor: None,
+            } if events.is_empty() => BinarySerHelper::Success {
+                effect,
+                transfers,
+                cost,
+            },
+            ExecutionResult::Success {
+                effect,
+                transfers,
+                cost,
+                events,
+                sponsor: None,
+            } => BinarySerHelper::SuccessWithEvents {
+                effect,
+                transfers,
+                cost,
+                events,
+            },
+        };
+        helper.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ExecutionResult {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            return ExecutionResult::deserialize(deserializer);
         }
+        let execution_result = match BinaryDeserHelper::deserialize(deserializer)? {
+            BinaryDeserHelper::Failure {
+                effect,
+                transfers,
+                cost,
+                error_message,
+            } => ExecutionResult::Failure {
+                effect,
+                transfers,
+                cost,
+                error_message,
+                sponsor: None,
+            },
+            BinaryDeserHelper::Success {
+                effect,
+                transfers,
+                cost,
+            } => ExecutionResult::Success {
+                effect,
+                transfers,
+                cost,
+                events: Vec::new(),
+                sponsor: None,
+            },
+            BinaryDeserHelper::SuccessWithEvents {
+                effect,
+                transfers,
+                cost,
+                events,
+            } => ExecutionResult::Success {
+                effect,
+                transfers,
+                cost,
+                events,
+                sponsor: None,
+            },
+            BinaryDeserHelper::FailureWithSponsor {
+                effect,
+                transfers,
+                cost,
+                error_message,
+                sponsor,
+            } => ExecutionResult::Failure {
+                effect,
+                transfers,
+                cost,
+                error_message,
+                sponsor: Some(sponsor),
+            },
+            BinaryDeserHelper::SuccessWithSponsor {
+                effect,
+                transfers,
+                cost,
+                events,
+                sponsor,
+            } => ExecutionResult::Success {
+                effect,
+                transfers,
+                cost,
+                events,
+                sponsor: Some(sponsor),
+            },
+        };
+        Ok(execution_result)
     }
 }
 
+/// Serializes an `Option<Key>` as the formatted string of the key, like the other keys of the
+/// human-readable representation of an execution result.
+mod serde_option_formatted_key {
+    use alloc::string::String;
+
+    use serde::{de::Error as SerdeError, Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::Key;
+
+    pub(super) fn serialize<S: Serializer>(
+        maybe_key: &Option<Key>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        maybe_key
+            .map(|key| key.to_formatted_string())
+            .serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Key>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|formatted_key| {
+                Key::from_formatted_str(&formatted_key)
+                    .map_err(|error| SerdeError::custom(format!("{}", error)))
+            })
+            .transpose()
+    }
+}
+
+#[derive(Serialize)]
+enum BinarySerHelper<'a> {
+    Failure {
+        effect: &'a ExecutionEffect,
+        transfers: &'a Vec<TransferAddr>,
+        cost: &'a U512,
+        error_message: &'a String,
+    },
+    Success {
+        effect: &'a ExecutionEffect,
+        transfers: &'a Vec<TransferAddr>,
+        cost: &'a U512,
+    },
+    SuccessWithEvents {
+        effect: &'a ExecutionEffect,
+        transfers: &'a Vec<TransferAddr>,
+        cost: &'a U512,
+        events: &'a Vec<ContractEvent>,
+    },
+    FailureWithSponsor {
+        effect: &'a ExecutionEffect,
+        transfers: &'a Vec<TransferAddr>,
+        cost: &'a U512,
+        error_message: &'a String,
+        sponsor: &'a Key,
+    },
+    SuccessWithSponsor {
+        effect: &'a ExecutionEffect,
+        transfers: &'a Vec<TransferAddr>,
+        cost: &'a U512,
+        events: &'a Vec<ContractEvent>,
+        sponsor: &'a Key,
+    },
+}
+
+#[derive(Deserialize)]
+enum BinaryDeserHelper {
+    Failure {
+        effect: ExecutionEffect,
+        transfers: Vec<TransferAddr>,
+        cost: U512,
+        error_message: String,
+    },
+    Success {
+        effect: ExecutionEffect,
+        transfers: Vec<TransferAddr>,
+        cost: U512,
+    },
+    SuccessWithEvents {
+        effect: ExecutionEffect,
+        transfers: Vec<TransferAddr>,
+        cost: U512,
+        events: Vec<ContractEvent>,
+    },
+    FailureWithSponsor {
+        effect: ExecutionEffect,
+        transfers: Vec<TransferAddr>,
+        cost: U512,
+        error_message: String,
+        sponsor: Key,
+    },
+    SuccessWithSponsor {
+        effect: ExecutionEffect,
+        transfers: Vec<TransferAddr>,
+        cost: U512,
+        events: Vec<ContractEvent>,
+        sponsor: Key,
+    },
+}
+
 impl Distribution<ExecutionResult> for Standard {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> ExecutionResult {
         let op_count = rng.gen_range(0..6);
@@ -234,18 +530,32 @@ impl Distribution<ExecutionResult> for Standard {
             transfers.push(TransferAddr::new(rng.gen()))
         }
 
+        let sponsor = if rng.gen() {
+            Some(Key::Hash(rng.gen()))
+        } else {
+            None
+        };
+
         if rng.gen() {
             ExecutionResult::Failure {
                 effect: execution_effect,
                 transfers,
                 cost: rng.gen::<u64>().into(),
                 error_message: format!("Error message {}", rng.gen::<u64>()),
+                sponsor,
             }
         } else {
+            let event_count = rng.gen_range(0..3);
+            let mut events = vec![];
+            for _ in 0..event_count {
+                events.push(rng.gen());
+            }
             ExecutionResult::Success {
                 effect: execution_effect,
                 transfers,
                 cost: rng.gen::<u64>().into(),
+                events,
+                sponsor,
             }
         }
     }
@@ -263,20 +573,32 @@ impl ToBytes for ExecutionResult {
                 transfers,
                 cost,
                 error_message,
+                sponsor,
             } => {
                 buffer.extend(effect.to_bytes()?);
                 buffer.extend(transfers.to_bytes()?);
                 buffer.extend(cost.to_bytes()?);
                 buffer.extend(error_message.to_bytes()?);
+                if let Some(sponsor) = sponsor {
+                    buffer.extend(sponsor.to_bytes()?);
+                }
             }
             ExecutionResult::Success {
                 effect,
                 transfers,
                 cost,
+                events,
+                sponsor,
             } => {
                 buffer.extend(effect.to_bytes()?);
                 buffer.extend(transfers.to_bytes()?);
                 buffer.extend(cost.to_bytes()?);
+                if !events.is_empty() || sponsor.is_some() {
+                    buffer.extend(events.to_bytes()?);
+                }
+                if let Some(sponsor) = sponsor {
+                    buffer.extend(sponsor.to_bytes()?);
+                }
             }
         }
         Ok(buffer)
@@ -290,20 +612,33 @@ impl ToBytes for ExecutionResult {
                     transfers,
                     cost,
                     error_message,
+                    sponsor,
                 } => {
+                    let sponsor_length = sponsor.as_ref().map_or(0, Key::serialized_length);
                     execution_effect.serialized_length()
                         + transfers.serialized_length()
                         + cost.serialized_length()
                         + error_message.serialized_length()
+                        + sponsor_length
                 }
                 ExecutionResult::Success {
                     effect: execution_effect,
                     transfers,
                     cost,
+                    events,
+                    sponsor,
                 } => {
+                    let events_length = if events.is_empty() && sponsor.is_none() {
+                        0
+                    } else {
+                        events.serialized_length()
+                    };
+                    let sponsor_length = sponsor.as_ref().map_or(0, Key::serialized_length);
                     execution_effect.serialized_length()
                         + transfers.serialized_length()
                         + cost.serialized_length()
+                        + events_length
+                        + sponsor_length
                 }
             }
     }
@@ -323,6 +658,7 @@ impl FromBytes for ExecutionResult {
                     transfers,
                     cost,
                     error_message,
+                    sponsor: None,
                 };
                 Ok((execution_result, remainder))
             }
@@ -334,6 +670,52 @@ impl FromBytes for ExecutionResult {
                     effect: execution_effect,
                     transfers,
                     cost,
+                    events: Vec::new(),
+                    sponsor: None,
+                };
+                Ok((execution_result, remainder))
+            }
+            ExecutionResultTag::SuccessWithEvents => {
+                let (execution_effect, remainder) = ExecutionEffect::from_bytes(remainder)?;
+                let (transfers, remainder) = Vec::<TransferAddr>::from_bytes(remainder)?;
+                let (cost, remainder) = U512::from_bytes(remainder)?;
+                let (events, remainder) = Vec::<ContractEvent>::from_bytes(remainder)?;
+                let execution_result = ExecutionResult::Success {
+                    effect: execution_effect,
+                    transfers,
+                    cost,
+                    events,
+                    sponsor: None,
+                };
+                Ok((execution_result, remainder))
+            }
+            ExecutionResultTag::FailureWithSponsor => {
+                let (effect, remainder) = ExecutionEffect::from_bytes(remainder)?;
+                let (transfers, remainder) = Vec::<TransferAddr>::from_bytes(remainder)?;
+                let (cost, remainder) = U512::from_bytes(remainder)?;
+                let (error_message, remainder) = String::from_bytes(remainder)?;
+                let (sponsor, remainder) = Key::from_bytes(remainder)?;
+                let execution_result = ExecutionResult::Failure {
+                    effect,
+                    transfers,
+                    cost,
+                    error_message,
+                    sponsor: Some(sponsor),
+                };
+                Ok((execution_result, remainder))
+            }
+            ExecutionResultTag::SuccessWithSponsor => {
+                let (execution_effect, remainder) = ExecutionEffect::from_bytes(remainder)?;
+                let (transfers, remainder) = Vec::<TransferAddr>::from_bytes(remainder)?;
+                let (cost, remainder) = U512::from_bytes(remainder)?;
+                let (events, remainder) = Vec::<ContractEvent>::from_bytes(remainder)?;
+                let (sponsor, remainder) = Key::from_bytes(remainder)?;
+                let execution_result = ExecutionResult::Success {
+                    effect: execution_effect,
+                    transfers,
+                    cost,
+                    events,
+                    sponsor: Some(sponsor),
                 };
                 Ok((execution_result, remainder))
             }
@@ -389,6 +771,60 @@ impl FromBytes for ExecutionEffect {
     }
 }
 
+/// An event emitted by a contract while executing a deploy.
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "datasize", derive(DataSize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct ContractEvent {
+    /// The formatted string of the `Key` of the account or contract which emitted the event.
+    pub emitter: String,
+    /// The topic under which the event was emitted.
+    pub topic: String,
+    /// The event's payload.
+    pub value: CLValue,
+}
+
+impl ToBytes for ContractEvent {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        buffer.extend(self.emitter.to_bytes()?);
+        buffer.extend(self.topic.to_bytes()?);
+        buffer.extend(self.value.to_bytes()?);
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.emitter.serialized_length()
+            + self.topic.serialized_length()
+            + self.value.serialized_length()
+    }
+}
+
+impl FromBytes for ContractEvent {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (emitter, remainder) = String::from_bytes(bytes)?;
+        let (topic, remainder) = String::from_bytes(remainder)?;
+        let (value, remainder) = CLValue::from_bytes(remainder)?;
+        let contract_event = ContractEvent {
+            emitter,
+            topic,
+            value,
+        };
+        Ok((contract_event, remainder))
+    }
+}
+
+impl Distribution<ContractEvent> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> ContractEvent {
+        ContractEvent {
+            emitter: rng.gen::<u64>().to_string(),
+            topic: rng.gen::<u64>().to_string(),
+            value: CLValue::from_t(rng.gen::<u64>()).unwrap(),
+        }
+    }
+}
+
 /// An operation performed while executing a deploy.
 #[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
 #[cfg_attr(feature = "datasize", derive(DataSize))]
@@ -778,6 +1214,13 @@ mod tests {
     use rand::{rngs::SmallRng, Rng, SeedableRng};
 
     use super::*;
+    use crate::{
+        system::auction::{
+            Bid, RewardDestination, SeigniorageAllocation, SlashedStake, SlashingOffence,
+        },
+        testing::TestRng,
+        AccessRights, EraId, PublicKey, URef,
+    };
 
     fn get_rng() -> SmallRng {
         let mut seed = [0u8; 32];
@@ -798,4 +1241,131 @@ mod tests {
         let execution_result: ExecutionResult = rng.gen();
         bytesrepr::test_serialization_roundtrip(&execution_result);
     }
+
+    #[test]
+    fn should_encode_success_without_events_as_before() {
+        let mut rng = get_rng();
+        let effect = ExecutionEffect::new(vec![TransformEntry {
+            key: rng.gen::<u64>().to_string(),
+            transform: rng.gen(),
+        }]);
+        let transfers = vec![TransferAddr::new(rng.gen())];
+        let cost = U512::from(rng.gen::<u64>());
+        let execution_result = ExecutionResult::Success {
+            effect: effect.clone(),
+            transfers: transfers.clone(),
+            cost,
+            events: vec![],
+            sponsor: None,
+        };
+
+        let mut expected_bytes = vec![ExecutionResultTag::Success as u8];
+        expected_bytes.extend(effect.to_bytes().unwrap());
+        expected_bytes.extend(transfers.to_bytes().unwrap());
+        expected_bytes.extend(cost.to_bytes().unwrap());
+        assert_eq!(execution_result.to_bytes().unwrap(), expected_bytes);
+
+        let json = serde_json::to_value(&execution_result).unwrap();
+        assert!(json["Success"].get("events").is_none());
+        let decoded: ExecutionResult = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, execution_result);
+    }
+
+    #[test]
+    fn should_encode_sponsor_only_when_present() {
+        let mut rng = get_rng();
+        let sponsor = Key::Account(AccountHash::new(rng.gen()));
+        let execution_result = ExecutionResult::Failure {
+            effect: ExecutionEffect::default(),
+            transfers: vec![],
+            cost: U512::from(rng.gen::<u64>()),
+            error_message: "Out of gas error".to_string(),
+            sponsor: Some(sponsor),
+        };
+        assert_eq!(execution_result.sponsor(), Some(sponsor));
+        bytesrepr::test_serialization_roundtrip(&execution_result);
+
+        let json = serde_json::to_value(&execution_result).unwrap();
+        assert_eq!(json["Failure"]["sponsor"], sponsor.to_formatted_string());
+        let decoded: ExecutionResult = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, execution_result);
+
+        let unsponsored = ExecutionResult::Failure {
+            effect: ExecutionEffect::default(),
+            transfers: vec![],
+            cost: U512::zero(),
+            error_message: String::new(),
+            sponsor: None,
+        };
+        let json = serde_json::to_value(&unsponsored).unwrap();
+        assert!(json["Failure"].get("sponsor").is_none());
+    }
+
+    #[test]
+    fn bincode_test_execution_result() {
+        let mut rng = get_rng();
+        let execution_result: ExecutionResult = rng.gen();
+        let serialized = bincode::serialize(&execution_result).unwrap();
+        let deserialized: ExecutionResult = bincode::deserialize(&serialized).unwrap();
+        assert_eq!(deserialized, execution_result);
+    }
+
+    #[test]
+    fn bincode_test_write_era_info_transform() {
+        let mut rng = TestRng::new();
+        let validator = PublicKey::random(&mut rng);
+        let mut era_info = EraInfo::new();
+        era_info
+            .seigniorage_allocations_mut()
+            .push(SeigniorageAllocation::validator(
+                validator.clone(),
+                U512::one(),
+            ));
+
+        let without_slashes = Transform::WriteEraInfo(era_info.clone());
+        let serialized = bincode::serialize(&without_slashes).unwrap();
+        let deserialized: Transform = bincode::deserialize(&serialized).unwrap();
+        assert_eq!(deserialized, without_slashes);
+
+        era_info.slashes_mut().push(SlashedStake::new(
+            validator,
+            SlashingOffence::Equivocation,
+            U512::from(10),
+            U512::from(5),
+        ));
+        let with_slashes = Transform::WriteEraInfo(era_info);
+        let serialized = bincode::serialize(&with_slashes).unwrap();
+        let deserialized: Transform = bincode::deserialize(&serialized).unwrap();
+        assert_eq!(deserialized, with_slashes);
+    }
+
+    #[test]
+    fn bincode_test_write_bid_transform() {
+        let mut rng = TestRng::new();
+        let mut bid = Bid::unlocked(
+            PublicKey::random(&mut rng),
+            URef::new(rng.gen(), AccessRights::READ_ADD_WRITE),
+            U512::from(rng.gen::<u64>()),
+            rng.gen(),
+        );
+
+        let plain = Transform::WriteBid(Box::new(bid.clone()));
+        let serialized = bincode::serialize(&plain).unwrap();
+        let deserialized: Transform = bincode::deserialize(&serialized).unwrap();
+        assert_eq!(deserialized, plain);
+
+        let delegator = PublicKey::random(&mut rng);
+        bid.with_delegation_amount_limits(Some(1), Some(2)).unwrap();
+        bid.reserved_delegators_mut().insert(delegator.clone());
+        bid.jail(Some(EraId::new(rng.gen())));
+        bid.set_reward_destination(RewardDestination::Account(AccountHash::new(rng.gen())));
+        bid.set_delegator_reward_destination(
+            delegator,
+            RewardDestination::Account(AccountHash::new(rng.gen())),
+        );
+        let with_options = Transform::WriteBid(Box::new(bid));
+        let serialized = bincode::serialize(&with_options).unwrap();
+        let deserialized: Transform = bincode::deserialize(&serialized).unwrap();
+        assert_eq!(deserialized, with_options);
+    }
 }
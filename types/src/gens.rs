@@ -2,7 +2,7 @@
 //! [`Proptest`](https://crates.io/crates/proptest).
 #![allow(missing_docs)]
 
-use alloc::{boxed::Box, string::String, vec};
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
 
 use proptest::{
     array, bits, bool,
@@ -19,8 +19,8 @@ use crate::{
     },
     crypto::gens::public_key_arb_no_system,
     system::auction::{
-        gens::era_info_arb, Bid, DelegationRate, Delegator, UnbondingPurse, WithdrawPurse,
-        DELEGATION_RATE_DENOMINATOR,
+        gens::era_info_arb, Bid, DelegationRate, Delegator, RewardDestination, UnbondingPurse,
+        WithdrawPurse, DELEGATION_RATE_DENOMINATOR,
     },
     transfer::TransferAddr,
     AccessRights, CLType, CLValue, Contract, ContractHash, ContractPackage, ContractVersionKey,
@@ -398,6 +398,14 @@ fn delegator_arb() -> impl Strategy<Value = Delegator> {
         )
 }
 
+pub fn reward_destination_arb() -> impl Strategy<Value = RewardDestination> {
+    prop_oneof![
+        Just(RewardDestination::Compound),
+        uref_arb().prop_map(RewardDestination::Purse),
+        account_hash_arb().prop_map(RewardDestination::Account),
+    ]
+}
+
 fn delegation_rate_arb() -> impl Strategy<Value = DelegationRate> {
     0..=DELEGATION_RATE_DENOMINATOR // Maximum, allowed value for delegation rate.
 }
@@ -410,6 +418,12 @@ pub(crate) fn bid_arb(delegations_len: impl Into<SizeRange>) -> impl Strategy<Va
         delegation_rate_arb(),
         bool::ANY,
         collection::vec(delegator_arb(), delegations_len),
+        option::of(any::<u64>()),
+        option::of(any::<u64>()),
+        collection::btree_set(public_key_arb_no_system(), 0..3),
+        option::of(option::of(era_id_arb())),
+        0..3u32,
+        (reward_destination_arb(), reward_destination_arb()),
     )
         .prop_map(
             |(
@@ -419,6 +433,12 @@ pub(crate) fn bid_arb(delegations_len: impl Into<SizeRange>) -> impl Strategy<Va
                 delegation_rate,
                 is_locked,
                 new_delegators,
+                limit_a,
+                limit_b,
+                reserved_delegators,
+                jail,
+                inactive_eras,
+                (reward_destination, delegator_reward_destination),
             )| {
                 let mut bid = if is_locked {
                     Bid::locked(
@@ -442,6 +462,31 @@ pub(crate) fn bid_arb(delegations_len: impl Into<SizeRange>) -> impl Strategy<Va
                         .insert(delegator.delegator_public_key().clone(), delegator)
                         .is_none());
                 });
+                let (minimum_delegation_amount, maximum_delegation_amount) =
+                    match (limit_a, limit_b) {
+                        (Some(a), Some(b)) => (Some(a.min(b)), Some(a.max(b))),
+                        limits => limits,
+                    };
+                bid.with_delegation_amount_limits(
+                    minimum_delegation_amount,
+                    maximum_delegation_amount,
+                )
+                .expect("should set valid limits");
+                *bid.reserved_delegators_mut() = reserved_delegators;
+                if let Some(jailed_until) = jail {
+                    bid.jail(jailed_until);
+                }
+                for _ in 0..inactive_eras {
+                    bid.record_inactive_era();
+                }
+                bid.set_reward_destination(reward_destination);
+                let delegator_public_keys: Vec<_> = bid.delegators().keys().cloned().collect();
+                for delegator_public_key in delegator_public_keys {
+                    bid.set_delegator_reward_destination(
+                        delegator_public_key,
+                        delegator_reward_destination,
+                    );
+                }
                 bid
             },
         )
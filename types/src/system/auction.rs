@@ -1,23 +1,31 @@
 //! Contains implementation of a Auction contract functionality.
 mod bid;
+mod binary_fields;
 mod constants;
 mod delegator;
 mod entry_points;
 mod era_info;
 mod error;
+mod reward_destination;
 mod seigniorage_recipient;
+mod slashing;
 mod unbonding_purse;
 mod withdraw_purse;
 
 use alloc::{collections::BTreeMap, vec::Vec};
 
-pub use bid::{Bid, VESTING_SCHEDULE_LENGTH_MILLIS};
+pub use bid::{
+    Bid, VestingCurve, VestingSchedule, VestingTranche, MAX_VESTING_TRANCHES,
+    VESTING_SCHEDULE_LENGTH_MILLIS,
+};
 pub use constants::*;
 pub use delegator::Delegator;
 pub use entry_points::auction_entry_points;
-pub use era_info::{EraInfo, SeigniorageAllocation};
+pub use era_info::{EraInfo, SeigniorageAllocation, SlashedStake};
 pub use error::Error;
+pub use reward_destination::RewardDestination;
 pub use seigniorage_recipient::SeigniorageRecipient;
+pub use slashing::SlashingOffence;
 pub use unbonding_purse::UnbondingPurse;
 pub use withdraw_purse::WithdrawPurse;
 
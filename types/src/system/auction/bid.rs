@@ -2,28 +2,58 @@
 #![allow(clippy::field_reassign_with_default)]
 
 mod vesting;
+mod vesting_curve;
 
-use alloc::{collections::BTreeMap, vec::Vec};
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+use core::fmt::{self, Formatter};
 
 #[cfg(feature = "datasize")]
 use datasize::DataSize;
 #[cfg(feature = "json-schema")]
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use serde::{
+    de::{Error as SerdeError, SeqAccess, Visitor},
+    ser::SerializeTuple,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
 
 use crate::{
-    bytesrepr::{self, FromBytes, ToBytes},
-    system::auction::{DelegationRate, Delegator, Error},
-    CLType, CLTyped, PublicKey, URef, U512,
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    system::auction::{
+        binary_fields::BinaryFields, DelegationRate, Delegator, Error, RewardDestination,
+    },
+    CLType, CLTyped, EraId, PublicKey, URef, U512,
 };
 
 pub use vesting::{VestingSchedule, VESTING_SCHEDULE_LENGTH_MILLIS};
+pub use vesting_curve::{VestingCurve, VestingTranche, MAX_VESTING_TRANCHES};
+
+/// Set in the serialized `inactive` flag of a bid which is followed by its delegation amount
+/// limits.
+///
+/// A bid without limits or reservations is serialized exactly as before they were introduced, so
+/// existing bid records remain valid and unchanged.
+const DELEGATION_AMOUNT_LIMITS_FLAG: u8 = 0b10;
+/// Set in the serialized `inactive` flag of a bid which is followed by its reserved delegators,
+/// after its delegation amount limits if any.
+const RESERVED_DELEGATORS_FLAG: u8 = 0b100;
+/// Set in the serialized `inactive` flag of a bid which is followed by its jail state, after its
+/// reserved delegators if any.
+const JAIL_STATE_FLAG: u8 = 0b1000;
+/// Set in the serialized `inactive` flag of a bid which is followed by the reward destinations of
+/// its validator and delegators, after its jail state if any.
+const REWARD_DESTINATIONS_FLAG: u8 = 0b1_0000;
+/// The number of fields of a bid, serialized one after the other by binary formats.
+const BID_FIELD_COUNT: usize = 15;
 
 /// An entry in the validator map.
 #[derive(PartialEq, Eq, Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "datasize", derive(DataSize))]
 #[cfg_attr(feature = "json-schema", derive(JsonSchema))]
-#[serde(deny_unknown_fields)]
+#[serde(deny_unknown_fields, remote = "Self")]
 pub struct Bid {
     /// Validator public key
     validator_public_key: PublicKey,
@@ -39,6 +69,32 @@ pub struct Bid {
     delegators: BTreeMap<PublicKey, Delegator>,
     /// `true` if validator has been "evicted"
     inactive: bool,
+    /// Minimum amount of a single delegation to this validator, if limited by the validator.
+    #[serde(default)]
+    minimum_delegation_amount: Option<u64>,
+    /// Maximum total amount a single delegator can delegate to this validator, if limited by the
+    /// validator.
+    #[serde(default)]
+    maximum_delegation_amount: Option<u64>,
+    /// Delegators with a reserved delegation slot.  If not empty, only these delegators can
+    /// delegate to this validator.
+    #[serde(default)]
+    reserved_delegators: BTreeSet<PublicKey>,
+    /// Number of consecutive eras in which consensus reported this validator as inactive.
+    #[serde(default)]
+    inactive_eras: u32,
+    /// Number of times this validator has been jailed.
+    #[serde(default)]
+    jail_count: u32,
+    /// The era from which this jailed validator can reactivate its bid.
+    #[serde(default)]
+    jailed_until: Option<EraId>,
+    /// Where the seigniorage of this validator is paid.
+    #[serde(default)]
+    reward_destination: RewardDestination,
+    /// Where the seigniorage of delegators is paid, for delegators not adding it to their stake.
+    #[serde(default)]
+    delegator_reward_destinations: BTreeMap<PublicKey, RewardDestination>,
 }
 
 impl Bid {
@@ -61,6 +117,14 @@ impl Bid {
             vesting_schedule,
             delegators,
             inactive,
+            minimum_delegation_amount: None,
+            maximum_delegation_amount: None,
+            reserved_delegators: BTreeSet::new(),
+            inactive_eras: 0,
+            jail_count: 0,
+            jailed_until: None,
+            reward_destination: RewardDestination::Compound,
+            delegator_reward_destinations: BTreeMap::new(),
         }
     }
 
@@ -82,6 +146,14 @@ impl Bid {
             vesting_schedule,
             delegators,
             inactive,
+            minimum_delegation_amount: None,
+            maximum_delegation_amount: None,
+            reserved_delegators: BTreeSet::new(),
+            inactive_eras: 0,
+            jail_count: 0,
+            jailed_until: None,
+            reward_destination: RewardDestination::Compound,
+            delegator_reward_destinations: BTreeMap::new(),
         }
     }
 
@@ -100,6 +172,14 @@ impl Bid {
             vesting_schedule,
             delegators,
             inactive,
+            minimum_delegation_amount: None,
+            maximum_delegation_amount: None,
+            reserved_delegators: BTreeSet::new(),
+            inactive_eras: 0,
+            jail_count: 0,
+            jailed_until: None,
+            reward_destination: RewardDestination::Compound,
+            delegator_reward_destinations: BTreeMap::new(),
         }
     }
 
@@ -180,6 +260,217 @@ impl Bid {
         self.inactive
     }
 
+    /// Returns the minimum amount of a single delegation to this validator, if limited.
+    pub fn minimum_delegation_amount(&self) -> Option<u64> {
+        self.minimum_delegation_amount
+    }
+
+    /// Returns the maximum total amount a single delegator can delegate to this validator, if
+    /// limited.
+    pub fn maximum_delegation_amount(&self) -> Option<u64> {
+        self.maximum_delegation_amount
+    }
+
+    /// Updates the delegation amount limits of the provided bid, leaving a limit unchanged if
+    /// `None`.
+    ///
+    /// Fails with [`Error::InvalidDelegationAmountLimits`], leaving the bid unchanged, if the
+    /// resulting minimum is above the resulting maximum.
+    pub fn with_delegation_amount_limits(
+        &mut self,
+        minimum_delegation_amount: Option<u64>,
+        maximum_delegation_amount: Option<u64>,
+    ) -> Result<&mut Self, Error> {
+        let minimum_delegation_amount =
+            minimum_delegation_amount.or(self.minimum_delegation_amount);
+        let maximum_delegation_amount =
+            maximum_delegation_amount.or(self.maximum_delegation_amount);
+        if let (Some(minimum), Some(maximum)) =
+            (minimum_delegation_amount, maximum_delegation_amount)
+        {
+            if minimum > maximum {
+                return Err(Error::InvalidDelegationAmountLimits);
+            }
+        }
+        self.minimum_delegation_amount = minimum_delegation_amount;
+        self.maximum_delegation_amount = maximum_delegation_amount;
+        Ok(self)
+    }
+
+    /// Checks that `delegator_public_key` delegating a further `amount` to this validator is
+    /// within the bid's delegation amount limits.
+    pub fn check_delegation_amount(
+        &self,
+        delegator_public_key: &PublicKey,
+        amount: U512,
+    ) -> Result<(), Error> {
+        if let Some(minimum_delegation_amount) = self.minimum_delegation_amount {
+            if amount < U512::from(minimum_delegation_amount) {
+                return Err(Error::DelegationAmountTooSmall);
+            }
+        }
+        if let Some(maximum_delegation_amount) = self.maximum_delegation_amount {
+            let delegated_amount = self
+                .delegators
+                .get(delegator_public_key)
+                .map(|delegator| *delegator.staked_amount())
+                .unwrap_or_default()
+                .checked_add(amount)
+                .ok_or(Error::InvalidAmount)?;
+            if delegated_amount > U512::from(maximum_delegation_amount) {
+                return Err(Error::DelegationAmountTooLarge);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the delegators with a reserved delegation slot.
+    pub fn reserved_delegators(&self) -> &BTreeSet<PublicKey> {
+        &self.reserved_delegators
+    }
+
+    /// Returns a mutable reference to the delegators with a reserved delegation slot.
+    pub fn reserved_delegators_mut(&mut self) -> &mut BTreeSet<PublicKey> {
+        &mut self.reserved_delegators
+    }
+
+    /// Returns `true` if `delegator_public_key` can delegate to this validator, i.e. if the
+    /// validator has not reserved its delegation slots or has reserved one for the delegator.
+    pub fn accepts_delegator(&self, delegator_public_key: &PublicKey) -> bool {
+        self.reserved_delegators.is_empty()
+            || self.reserved_delegators.contains(delegator_public_key)
+    }
+
+    /// Returns the number of delegation slots taken, by either a delegator or a reservation.
+    pub fn occupied_delegator_slots(&self) -> usize {
+        self.delegators.len()
+            + self
+                .reserved_delegators
+                .iter()
+                .filter(|public_key| !self.delegators.contains_key(public_key))
+                .count()
+    }
+
+    /// Returns the number of consecutive eras in which consensus reported this validator as
+    /// inactive.
+    pub fn inactive_eras(&self) -> u32 {
+        self.inactive_eras
+    }
+
+    /// Returns the number of times this validator has been jailed.
+    pub fn jail_count(&self) -> u32 {
+        self.jail_count
+    }
+
+    /// Returns the era from which this validator can reactivate its bid, if jailed for a period.
+    pub fn jailed_until(&self) -> Option<EraId> {
+        self.jailed_until
+    }
+
+    /// Returns `true` if this validator is jailed in `era_id`, i.e. cannot reactivate its bid yet.
+    pub fn is_jailed(&self, era_id: EraId) -> bool {
+        self.jailed_until
+            .map_or(false, |jailed_until| era_id < jailed_until)
+    }
+
+    /// Records an era in which consensus reported this validator as inactive.
+    ///
+    /// Returns the number of consecutive eras in which the validator was inactive.
+    pub fn record_inactive_era(&mut self) -> u32 {
+        self.inactive_eras = self.inactive_eras.saturating_add(1);
+        self.inactive_eras
+    }
+
+    /// Records an era in which this validator was not reported as inactive.
+    ///
+    /// Returns `true` if this reset the count of consecutive inactive eras.
+    pub fn record_active_era(&mut self) -> bool {
+        let was_inactive = self.inactive_eras != 0;
+        self.inactive_eras = 0;
+        was_inactive
+    }
+
+    /// Jails this validator: deactivates its bid, counts the offence and resets the count of
+    /// consecutive inactive eras.  `jailed_until` is the era from which the validator can
+    /// reactivate its bid, if it is jailed for a period.
+    pub fn jail(&mut self, jailed_until: Option<EraId>) {
+        self.inactive = true;
+        self.inactive_eras = 0;
+        self.jail_count = self.jail_count.saturating_add(1);
+        self.jailed_until = jailed_until;
+    }
+
+    /// Reactivates the bid of this validator if it is no longer jailed in `era_id`.
+    pub fn unjail(&mut self, era_id: EraId) -> Result<(), Error> {
+        if self.is_jailed(era_id) {
+            return Err(Error::ValidatorJailed);
+        }
+        self.jailed_until = None;
+        self.activate();
+        Ok(())
+    }
+
+    /// Returns where the seigniorage of this validator is paid.
+    pub fn reward_destination(&self) -> RewardDestination {
+        self.reward_destination
+    }
+
+    /// Sets where the seigniorage of this validator is paid.
+    pub fn set_reward_destination(&mut self, reward_destination: RewardDestination) {
+        self.reward_destination = reward_destination;
+    }
+
+    /// Returns where the seigniorage of the delegator `delegator_public_key` is paid.
+    pub fn delegator_reward_destination(
+        &self,
+        delegator_public_key: &PublicKey,
+    ) -> RewardDestination {
+        self.delegator_reward_destinations
+            .get(delegator_public_key)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns where the seigniorage of delegators is paid, for delegators not adding it to their
+    /// stake.
+    pub fn delegator_reward_destinations(&self) -> &BTreeMap<PublicKey, RewardDestination> {
+        &self.delegator_reward_destinations
+    }
+
+    /// Sets where the seigniorage of the delegator `delegator_public_key` is paid.
+    pub fn set_delegator_reward_destination(
+        &mut self,
+        delegator_public_key: PublicKey,
+        reward_destination: RewardDestination,
+    ) {
+        if reward_destination.is_compound() {
+            self.delegator_reward_destinations
+                .remove(&delegator_public_key);
+        } else {
+            self.delegator_reward_destinations
+                .insert(delegator_public_key, reward_destination);
+        }
+    }
+
+    /// Forgets the reward destinations of delegators which no longer delegate to this validator.
+    pub fn prune_delegator_reward_destinations(&mut self) {
+        let delegators = &self.delegators;
+        self.delegator_reward_destinations
+            .retain(|delegator_public_key, _| delegators.contains_key(delegator_public_key));
+    }
+
+    fn has_reward_destinations(&self) -> bool {
+        !self.reward_destination.is_compound() || !self.delegator_reward_destinations.is_empty()
+    }
+
+    fn has_jail_state(&self) -> bool {
+        self.inactive_eras != 0 || self.jail_count != 0 || self.jailed_until.is_some()
+    }
+
+    fn has_delegation_amount_limits(&self) -> bool {
+        self.minimum_delegation_amount.is_some() || self.maximum_delegation_amount.is_some()
+    }
+
     /// Decreases the stake of the provided bid
     pub fn decrease_stake(
         &mut self,
@@ -321,7 +612,7 @@ impl ToBytes for Bid {
         self.delegation_rate.write_bytes(&mut result)?;
         self.vesting_schedule.write_bytes(&mut result)?;
         self.delegators().write_bytes(&mut result)?;
-        self.inactive.write_bytes(&mut result)?;
+        self.write_flags_and_extensions(&mut result)?;
         Ok(result)
     }
 
@@ -332,7 +623,31 @@ impl ToBytes for Bid {
             + self.delegation_rate.serialized_length()
             + self.vesting_schedule.serialized_length()
             + self.delegators.serialized_length()
-            + self.inactive.serialized_length()
+            + U8_SERIALIZED_LENGTH
+            + if self.has_delegation_amount_limits() {
+                self.minimum_delegation_amount.serialized_length()
+                    + self.maximum_delegation_amount.serialized_length()
+            } else {
+                0
+            }
+            + if self.reserved_delegators.is_empty() {
+                0
+            } else {
+                self.reserved_delegators.serialized_length()
+            }
+            + if self.has_jail_state() {
+                self.inactive_eras.serialized_length()
+                    + self.jail_count.serialized_length()
+                    + self.jailed_until.serialized_length()
+            } else {
+                0
+            }
+            + if self.has_reward_destinations() {
+                self.reward_destination.serialized_length()
+                    + self.delegator_reward_destinations.serialized_length()
+            } else {
+                0
+            }
     }
 
     fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
@@ -342,7 +657,48 @@ impl ToBytes for Bid {
         self.delegation_rate.write_bytes(writer)?;
         self.vesting_schedule.write_bytes(writer)?;
         self.delegators().write_bytes(writer)?;
-        self.inactive.write_bytes(writer)?;
+        self.write_flags_and_extensions(writer)?;
+        Ok(())
+    }
+}
+
+impl Bid {
+    /// Returns the `inactive` flag of the bid along with the flags of the fields following it.
+    fn flags(&self) -> u8 {
+        let mut flags = u8::from(self.inactive);
+        if self.has_delegation_amount_limits() {
+            flags |= DELEGATION_AMOUNT_LIMITS_FLAG;
+        }
+        if !self.reserved_delegators.is_empty() {
+            flags |= RESERVED_DELEGATORS_FLAG;
+        }
+        if self.has_jail_state() {
+            flags |= JAIL_STATE_FLAG;
+        }
+        if self.has_reward_destinations() {
+            flags |= REWARD_DESTINATIONS_FLAG;
+        }
+        flags
+    }
+
+    fn write_flags_and_extensions(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        writer.push(self.flags());
+        if self.has_delegation_amount_limits() {
+            self.minimum_delegation_amount.write_bytes(writer)?;
+            self.maximum_delegation_amount.write_bytes(writer)?;
+        }
+        if !self.reserved_delegators.is_empty() {
+            self.reserved_delegators.write_bytes(writer)?;
+        }
+        if self.has_jail_state() {
+            self.inactive_eras.write_bytes(writer)?;
+            self.jail_count.write_bytes(writer)?;
+            self.jailed_until.write_bytes(writer)?;
+        }
+        if self.has_reward_destinations() {
+            self.reward_destination.write_bytes(writer)?;
+            self.delegator_reward_destinations.write_bytes(writer)?;
+        }
         Ok(())
     }
 }
@@ -355,7 +711,69 @@ impl FromBytes for Bid {
         let (delegation_rate, bytes) = FromBytes::from_bytes(bytes)?;
         let (vesting_schedule, bytes) = FromBytes::from_bytes(bytes)?;
         let (delegators, bytes) = FromBytes::from_bytes(bytes)?;
-        let (inactive, bytes) = FromBytes::from_bytes(bytes)?;
+        let (flags, bytes) = u8::from_bytes(bytes)?;
+        let inactive = match flags
+            & !(DELEGATION_AMOUNT_LIMITS_FLAG
+                | RESERVED_DELEGATORS_FLAG
+                | JAIL_STATE_FLAG
+                | REWARD_DESTINATIONS_FLAG)
+        {
+            0 => false,
+            1 => true,
+            _ => return Err(bytesrepr::Error::Formatting),
+        };
+        let (minimum_delegation_amount, maximum_delegation_amount, bytes) =
+            if flags & DELEGATION_AMOUNT_LIMITS_FLAG == 0 {
+                (None, None, bytes)
+            } else {
+                let (minimum_delegation_amount, bytes) = Option::<u64>::from_bytes(bytes)?;
+                let (maximum_delegation_amount, bytes) = Option::<u64>::from_bytes(bytes)?;
+                if minimum_delegation_amount.is_none() && maximum_delegation_amount.is_none() {
+                    // Bids without limits are always serialized without the flag.
+                    return Err(bytesrepr::Error::Formatting);
+                }
+                (minimum_delegation_amount, maximum_delegation_amount, bytes)
+            };
+        let (reserved_delegators, bytes) = if flags & RESERVED_DELEGATORS_FLAG == 0 {
+            (BTreeSet::new(), bytes)
+        } else {
+            let (reserved_delegators, bytes) = BTreeSet::<PublicKey>::from_bytes(bytes)?;
+            if reserved_delegators.is_empty() {
+                // Bids without reservations are always serialized without the flag.
+                return Err(bytesrepr::Error::Formatting);
+            }
+            (reserved_delegators, bytes)
+        };
+        let (inactive_eras, jail_count, jailed_until, bytes) = if flags & JAIL_STATE_FLAG == 0 {
+            (0, 0, None, bytes)
+        } else {
+            let (inactive_eras, bytes) = u32::from_bytes(bytes)?;
+            let (jail_count, bytes) = u32::from_bytes(bytes)?;
+            let (jailed_until, bytes) = Option::<EraId>::from_bytes(bytes)?;
+            if inactive_eras == 0 && jail_count == 0 && jailed_until.is_none() {
+                // Bids without jail state are always serialized without the flag.
+                return Err(bytesrepr::Error::Formatting);
+            }
+            (inactive_eras, jail_count, jailed_until, bytes)
+        };
+        let (reward_destination, delegator_reward_destinations, bytes) =
+            if flags & REWARD_DESTINATIONS_FLAG == 0 {
+                (RewardDestination::Compound, BTreeMap::new(), bytes)
+            } else {
+                let (reward_destination, bytes) = RewardDestination::from_bytes(bytes)?;
+                let (delegator_reward_destinations, bytes) =
+                    BTreeMap::<PublicKey, RewardDestination>::from_bytes(bytes)?;
+                if (reward_destination.is_compound() && delegator_reward_destinations.is_empty())
+                    || delegator_reward_destinations
+                        .values()
+                        .any(RewardDestination::is_compound)
+                {
+                    // Compounding rewards is the default and is always serialized without the
+                    // flag or entry.
+                    return Err(bytesrepr::Error::Formatting);
+                }
+                (reward_destination, delegator_reward_destinations, bytes)
+            };
         Ok((
             Bid {
                 validator_public_key,
@@ -365,25 +783,213 @@ impl FromBytes for Bid {
                 vesting_schedule,
                 delegators,
                 inactive,
+                minimum_delegation_amount,
+                maximum_delegation_amount,
+                reserved_delegators,
+                inactive_eras,
+                jail_count,
+                jailed_until,
+                reward_destination,
+                delegator_reward_destinations,
             },
             bytes,
         ))
     }
 }
 
+// Binary formats such as bincode can't skip a field, so in those the fields added to a bid follow
+// its `inactive` flag only when set, as in its `bytesrepr` encoding.  A bid without any of these
+// fields keeps the representation it had before they were introduced, so previously stored bids,
+// such as those written by the `WriteBid` transforms of execution results, remain readable.
+impl Serialize for Bid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return Bid::serialize(self, serializer);
+        }
+        let flags = self.flags();
+        let mut field_count = 7;
+        if flags & DELEGATION_AMOUNT_LIMITS_FLAG != 0 {
+            field_count += 2;
+        }
+        if flags & RESERVED_DELEGATORS_FLAG != 0 {
+            field_count += 1;
+        }
+        if flags & JAIL_STATE_FLAG != 0 {
+            field_count += 3;
+        }
+        if flags & REWARD_DESTINATIONS_FLAG != 0 {
+            field_count += 2;
+        }
+        let mut tuple = serializer.serialize_tuple(field_count)?;
+        tuple.serialize_element(&self.validator_public_key)?;
+        tuple.serialize_element(&self.bonding_purse)?;
+        tuple.serialize_element(&self.staked_amount)?;
+        tuple.serialize_element(&self.delegation_rate)?;
+        tuple.serialize_element(&self.vesting_schedule)?;
+        tuple.serialize_element(&self.delegators)?;
+        tuple.serialize_element(&flags)?;
+        if flags & DELEGATION_AMOUNT_LIMITS_FLAG != 0 {
+            tuple.serialize_element(&self.minimum_delegation_amount)?;
+            tuple.serialize_element(&self.maximum_delegation_amount)?;
+        }
+        if flags & RESERVED_DELEGATORS_FLAG != 0 {
+            tuple.serialize_element(&self.reserved_delegators)?;
+        }
+        if flags & JAIL_STATE_FLAG != 0 {
+            tuple.serialize_element(&self.inactive_eras)?;
+            tuple.serialize_element(&self.jail_count)?;
+            tuple.serialize_element(&self.jailed_until)?;
+        }
+        if flags & REWARD_DESTINATIONS_FLAG != 0 {
+            tuple.serialize_element(&self.reward_destination)?;
+            tuple.serialize_element(&self.delegator_reward_destinations)?;
+        }
+        tuple.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Bid {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            return Bid::deserialize(deserializer);
+        }
+        deserializer.deserialize_tuple(BID_FIELD_COUNT, BinaryBidVisitor)
+    }
+}
+
+struct BinaryBidVisitor;
+
+impl<'de> Visitor<'de> for BinaryBidVisitor {
+    type Value = Bid;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("a bid")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Bid, A::Error> {
+        let mut fields = BinaryFields::new(seq, &self);
+        let validator_public_key = fields.next()?;
+        let bonding_purse = fields.next()?;
+        let staked_amount = fields.next()?;
+        let delegation_rate = fields.next()?;
+        let vesting_schedule = fields.next()?;
+        let delegators = fields.next()?;
+        let flags: u8 = fields.next()?;
+        let mut bid = Bid {
+            validator_public_key,
+            bonding_purse,
+            staked_amount,
+            delegation_rate,
+            vesting_schedule,
+            delegators,
+            inactive: flags & 1 != 0,
+            minimum_delegation_amount: None,
+            maximum_delegation_amount: None,
+            reserved_delegators: BTreeSet::new(),
+            inactive_eras: 0,
+            jail_count: 0,
+            jailed_until: None,
+            reward_destination: RewardDestination::Compound,
+            delegator_reward_destinations: BTreeMap::new(),
+        };
+        if flags & DELEGATION_AMOUNT_LIMITS_FLAG != 0 {
+            bid.minimum_delegation_amount = fields.next()?;
+            bid.maximum_delegation_amount = fields.next()?;
+        }
+        if flags & RESERVED_DELEGATORS_FLAG != 0 {
+            bid.reserved_delegators = fields.next()?;
+        }
+        if flags & JAIL_STATE_FLAG != 0 {
+            bid.inactive_eras = fields.next()?;
+            bid.jail_count = fields.next()?;
+            bid.jailed_until = fields.next()?;
+        }
+        if flags & REWARD_DESTINATIONS_FLAG != 0 {
+            bid.reward_destination = fields.next()?;
+            bid.delegator_reward_destinations = fields.next()?;
+        }
+        // Fields holding their default value are always serialized without their flag.
+        if bid.flags() != flags {
+            return Err(SerdeError::custom(format_args!(
+                "invalid bid flags {:#b}",
+                flags
+            )));
+        }
+        Ok(bid)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use alloc::collections::BTreeMap;
+    use alloc::{
+        collections::{BTreeMap, BTreeSet},
+        vec::Vec,
+    };
 
+    use serde::Serialize;
+
+    use super::DELEGATION_AMOUNT_LIMITS_FLAG;
     use crate::{
-        bytesrepr,
-        system::auction::{bid::VestingSchedule, Bid, DelegationRate, Delegator},
-        AccessRights, PublicKey, SecretKey, URef, U512,
+        account::AccountHash,
+        bytesrepr::{self, ToBytes},
+        system::auction::{
+            bid::VestingSchedule, Bid, DelegationRate, Delegator, Error, RewardDestination,
+        },
+        AccessRights, EraId, PublicKey, SecretKey, URef, U512,
     };
 
     const WEEK_MILLIS: u64 = 7 * 24 * 60 * 60 * 1000;
     const TEST_VESTING_SCHEDULE_LENGTH_MILLIS: u64 = 7 * WEEK_MILLIS;
 
+    /// A bid as serialized before any field was added to it.
+    #[derive(Serialize)]
+    struct LegacyBid {
+        validator_public_key: PublicKey,
+        bonding_purse: URef,
+        staked_amount: U512,
+        delegation_rate: DelegationRate,
+        vesting_schedule: Option<VestingSchedule>,
+        delegators: BTreeMap<PublicKey, Delegator>,
+        inactive: bool,
+    }
+
+    /// The serde representations of a [`VestingSchedule`] and a [`Delegator`] before vesting
+    /// curves were introduced.
+    #[derive(Serialize)]
+    struct LegacyVestingSchedule {
+        initial_release_timestamp_millis: u64,
+        locked_amounts: Option<[U512; 14]>,
+    }
+
+    #[derive(Serialize)]
+    struct LegacyDelegator {
+        delegator_public_key: PublicKey,
+        staked_amount: U512,
+        bonding_purse: URef,
+        validator_public_key: PublicKey,
+        vesting_schedule: Option<LegacyVestingSchedule>,
+    }
+
+    #[derive(Serialize)]
+    struct LegacyLockedBid {
+        validator_public_key: PublicKey,
+        bonding_purse: URef,
+        staked_amount: U512,
+        delegation_rate: DelegationRate,
+        vesting_schedule: Option<LegacyVestingSchedule>,
+        delegators: BTreeMap<PublicKey, LegacyDelegator>,
+        inactive: bool,
+    }
+
+    /// Asserts that a bid followed by other data, as in a stored execution result, survives a
+    /// bincode roundtrip.
+    fn assert_bincode_roundtrip(bid: &Bid) {
+        let serialized = bincode::serialize(&(bid, u64::MAX)).unwrap();
+        let deserialized: (Bid, u64) = bincode::deserialize(&serialized).unwrap();
+        assert_eq!(&deserialized.0, bid);
+        assert_eq!(deserialized.1, u64::MAX);
+    }
+
     #[test]
     fn serialization_roundtrip() {
         let founding_validator = Bid {
@@ -396,10 +1002,375 @@ mod tests {
             vesting_schedule: Some(VestingSchedule::default()),
             delegators: BTreeMap::default(),
             inactive: true,
+            minimum_delegation_amount: None,
+            maximum_delegation_amount: None,
+            reserved_delegators: BTreeSet::default(),
+            inactive_eras: 0,
+            jail_count: 0,
+            jailed_until: None,
+            reward_destination: RewardDestination::Compound,
+            delegator_reward_destinations: BTreeMap::new(),
         };
         bytesrepr::test_serialization_roundtrip(&founding_validator);
     }
 
+    fn unlocked_bid() -> Bid {
+        Bid::unlocked(
+            PublicKey::from(&SecretKey::ed25519_from_bytes([42; 32]).unwrap()),
+            URef::new([42; 32], AccessRights::READ_ADD_WRITE),
+            U512::from(1000),
+            0,
+        )
+    }
+
+    #[test]
+    fn serialization_roundtrip_with_delegation_amount_limits() {
+        for (minimum, maximum) in [(Some(1), Some(2)), (Some(1), None), (None, Some(2))] {
+            for inactive in [false, true] {
+                let mut bid = unlocked_bid();
+                bid.inactive = inactive;
+                bid.with_delegation_amount_limits(minimum, maximum).unwrap();
+                bytesrepr::test_serialization_roundtrip(&bid);
+                assert_bincode_roundtrip(&bid);
+            }
+        }
+    }
+
+    #[test]
+    fn should_deserialize_bincode_bid_in_its_original_layout() {
+        let validator_public_key: PublicKey =
+            (&SecretKey::ed25519_from_bytes([42; 32]).unwrap()).into();
+        let delegator_public_key: PublicKey =
+            (&SecretKey::ed25519_from_bytes([43; 32]).unwrap()).into();
+        let delegator = Delegator::unlocked(
+            delegator_public_key.clone(),
+            U512::from(10),
+            URef::new([3; 32], AccessRights::READ_ADD_WRITE),
+            validator_public_key.clone(),
+        );
+        let legacy_bid = LegacyBid {
+            validator_public_key,
+            bonding_purse: URef::new([42; 32], AccessRights::READ_ADD_WRITE),
+            staked_amount: U512::from(1000),
+            delegation_rate: 10,
+            vesting_schedule: None,
+            delegators: BTreeMap::from([(delegator_public_key, delegator)]),
+            inactive: true,
+        };
+        let legacy_bytes = bincode::serialize(&(&legacy_bid, u64::MAX)).unwrap();
+
+        let (bid, trailing): (Bid, u64) = bincode::deserialize(&legacy_bytes).unwrap();
+        assert_eq!(trailing, u64::MAX);
+        assert_eq!(bid.validator_public_key(), &legacy_bid.validator_public_key);
+        assert_eq!(bid.staked_amount(), &legacy_bid.staked_amount);
+        assert_eq!(bid.delegators(), &legacy_bid.delegators);
+        assert!(bid.inactive());
+        assert_eq!(bid.minimum_delegation_amount(), None);
+        assert_eq!(bid.maximum_delegation_amount(), None);
+        assert!(bid.reserved_delegators().is_empty());
+        assert_eq!(bid.inactive_eras(), 0);
+        assert_eq!(bid.jail_count(), 0);
+        assert_eq!(bid.jailed_until(), None);
+        assert!(bid.reward_destination().is_compound());
+        assert!(bid.delegator_reward_destinations().is_empty());
+
+        // A bid without any of the fields added since keeps its original layout.
+        assert_eq!(bincode::serialize(&(&bid, u64::MAX)).unwrap(), legacy_bytes);
+    }
+
+    #[test]
+    fn should_deserialize_bincode_locked_bid_in_its_original_layout() {
+        let validator_public_key: PublicKey =
+            (&SecretKey::ed25519_from_bytes([42; 32]).unwrap()).into();
+        let delegator_public_key: PublicKey =
+            (&SecretKey::ed25519_from_bytes([43; 32]).unwrap()).into();
+        let mut locked_amounts = [U512::zero(); 14];
+        locked_amounts[0] = U512::from(500);
+        let legacy_bid = LegacyLockedBid {
+            validator_public_key: validator_public_key.clone(),
+            bonding_purse: URef::new([42; 32], AccessRights::READ_ADD_WRITE),
+            staked_amount: U512::from(1000),
+            delegation_rate: 10,
+            vesting_schedule: Some(LegacyVestingSchedule {
+                initial_release_timestamp_millis: 1,
+                locked_amounts: Some(locked_amounts),
+            }),
+            delegators: BTreeMap::from([(
+                delegator_public_key.clone(),
+                LegacyDelegator {
+                    delegator_public_key: delegator_public_key.clone(),
+                    staked_amount: U512::from(10),
+                    bonding_purse: URef::new([3; 32], AccessRights::READ_ADD_WRITE),
+                    validator_public_key,
+                    vesting_schedule: Some(LegacyVestingSchedule {
+                        initial_release_timestamp_millis: 2,
+                        locked_amounts: None,
+                    }),
+                },
+            )]),
+            inactive: false,
+        };
+        let legacy_bytes = bincode::serialize(&(&legacy_bid, u64::MAX)).unwrap();
+
+        let (bid, trailing): (Bid, u64) = bincode::deserialize(&legacy_bytes).unwrap();
+        assert_eq!(trailing, u64::MAX);
+        let vesting_schedule = bid.vesting_schedule().unwrap();
+        assert_eq!(vesting_schedule.initial_release_timestamp_millis(), 1);
+        assert_eq!(
+            vesting_schedule.locked_amounts(),
+            Some(locked_amounts.as_slice())
+        );
+        assert!(vesting_schedule.vesting_curve().is_none());
+        let delegator_vesting_schedule = bid.delegators()[&delegator_public_key]
+            .vesting_schedule()
+            .unwrap();
+        assert_eq!(delegator_vesting_schedule, &VestingSchedule::new(2));
+
+        // Schedules without a curve keep their original layout.
+        assert_eq!(bincode::serialize(&(&bid, u64::MAX)).unwrap(), legacy_bytes);
+    }
+
+    #[test]
+    fn should_reject_bincode_bid_with_invalid_flags() {
+        let mut bytes = bincode::serialize(&unlocked_bid()).unwrap();
+        // A set flag must be followed by a field which is not at its default value.
+        *bytes.last_mut().unwrap() = DELEGATION_AMOUNT_LIMITS_FLAG;
+        bytes.extend(bincode::serialize(&(None::<u64>, None::<u64>)).unwrap());
+        assert!(bincode::deserialize::<Bid>(&bytes).is_err());
+
+        // Unknown flags are rejected.
+        let mut bytes = bincode::serialize(&unlocked_bid()).unwrap();
+        *bytes.last_mut().unwrap() = 0b1000_0000;
+        assert!(bincode::deserialize::<Bid>(&bytes).is_err());
+    }
+
+    #[test]
+    fn serialization_roundtrip_with_reserved_delegators() {
+        let delegator_pk: PublicKey = (&SecretKey::ed25519_from_bytes([43; 32]).unwrap()).into();
+        for limits in [None, Some(1)] {
+            let mut bid = unlocked_bid();
+            bid.with_delegation_amount_limits(limits, limits).unwrap();
+            bid.reserved_delegators_mut().insert(delegator_pk.clone());
+            bytesrepr::test_serialization_roundtrip(&bid);
+            assert_bincode_roundtrip(&bid);
+        }
+    }
+
+    #[test]
+    fn serialization_roundtrip_with_jail_state() {
+        let delegator_pk: PublicKey = (&SecretKey::ed25519_from_bytes([43; 32]).unwrap()).into();
+        for jailed_until in [None, Some(EraId::new(5))] {
+            let mut bid = unlocked_bid();
+            bid.reserved_delegators_mut().insert(delegator_pk.clone());
+            bid.jail(jailed_until);
+            bytesrepr::test_serialization_roundtrip(&bid);
+            assert_bincode_roundtrip(&bid);
+
+            bid.record_inactive_era();
+            bytesrepr::test_serialization_roundtrip(&bid);
+            assert_bincode_roundtrip(&bid);
+        }
+    }
+
+    #[test]
+    fn serialization_roundtrip_with_reward_destinations() {
+        let delegator_pk: PublicKey = (&SecretKey::ed25519_from_bytes([43; 32]).unwrap()).into();
+        let purse = RewardDestination::Purse(URef::new([1; 32], AccessRights::ADD));
+        let account = RewardDestination::Account(AccountHash::new([2; 32]));
+        for (validator_destination, delegator_destination) in [
+            (purse, RewardDestination::Compound),
+            (RewardDestination::Compound, account),
+            (account, purse),
+        ] {
+            let mut bid = unlocked_bid();
+            bid.jail(None);
+            bid.set_reward_destination(validator_destination);
+            bid.set_delegator_reward_destination(delegator_pk.clone(), delegator_destination);
+            bytesrepr::test_serialization_roundtrip(&bid);
+            assert_bincode_roundtrip(&bid);
+        }
+    }
+
+    #[test]
+    fn should_prune_reward_destinations_of_former_delegators() {
+        let delegator_pk: PublicKey = (&SecretKey::ed25519_from_bytes([43; 32]).unwrap()).into();
+        let destination = RewardDestination::Account(AccountHash::new([2; 32]));
+        let mut bid = unlocked_bid();
+        let delegator = Delegator::unlocked(
+            delegator_pk.clone(),
+            U512::one(),
+            URef::new([3; 32], AccessRights::READ_ADD_WRITE),
+            bid.validator_public_key().clone(),
+        );
+        bid.delegators_mut().insert(delegator_pk.clone(), delegator);
+        bid.set_delegator_reward_destination(delegator_pk.clone(), destination);
+
+        bid.prune_delegator_reward_destinations();
+        assert_eq!(bid.delegator_reward_destination(&delegator_pk), destination);
+
+        bid.delegators_mut().remove(&delegator_pk);
+        bid.prune_delegator_reward_destinations();
+        assert_eq!(
+            bid.delegator_reward_destination(&delegator_pk),
+            RewardDestination::Compound
+        );
+        assert_eq!(bid, unlocked_bid());
+    }
+
+    #[test]
+    fn should_serialize_bid_without_limits_as_before_limits() {
+        let mut bid = unlocked_bid();
+        bid.deactivate();
+        let mut legacy_bytes = Vec::new();
+        legacy_bytes.extend(bid.validator_public_key.to_bytes().unwrap());
+        legacy_bytes.extend(bid.bonding_purse.to_bytes().unwrap());
+        legacy_bytes.extend(bid.staked_amount.to_bytes().unwrap());
+        legacy_bytes.extend(bid.delegation_rate.to_bytes().unwrap());
+        legacy_bytes.extend(bid.vesting_schedule.to_bytes().unwrap());
+        legacy_bytes.extend(bid.delegators.to_bytes().unwrap());
+        legacy_bytes.extend(bid.inactive.to_bytes().unwrap());
+
+        assert_eq!(bid.to_bytes().unwrap(), legacy_bytes);
+        assert_eq!(bytesrepr::deserialize::<Bid>(legacy_bytes).unwrap(), bid);
+    }
+
+    #[test]
+    fn should_reject_invalid_flags() {
+        let mut bytes = unlocked_bid().to_bytes().unwrap();
+        *bytes.last_mut().unwrap() = 4;
+        assert!(bytesrepr::deserialize::<Bid>(bytes.clone()).is_err());
+
+        // The reserved delegators flag without any reservation.
+        let mut reserved_bytes = bytes.clone();
+        *reserved_bytes.last_mut().unwrap() = 4;
+        reserved_bytes.extend(BTreeSet::<PublicKey>::new().to_bytes().unwrap());
+        assert!(bytesrepr::deserialize::<Bid>(reserved_bytes).is_err());
+
+        // The jail state flag without any jail state.
+        let mut jail_bytes = bytes.clone();
+        *jail_bytes.last_mut().unwrap() = 8;
+        jail_bytes.extend(0u32.to_bytes().unwrap());
+        jail_bytes.extend(0u32.to_bytes().unwrap());
+        jail_bytes.extend(Option::<EraId>::None.to_bytes().unwrap());
+        assert!(bytesrepr::deserialize::<Bid>(jail_bytes).is_err());
+
+        // The reward destinations flag with rewards added to all stakes.
+        let mut destination_bytes = bytes.clone();
+        *destination_bytes.last_mut().unwrap() = 16;
+        destination_bytes.extend(RewardDestination::Compound.to_bytes().unwrap());
+        destination_bytes.extend(
+            BTreeMap::<PublicKey, RewardDestination>::new()
+                .to_bytes()
+                .unwrap(),
+        );
+        assert!(bytesrepr::deserialize::<Bid>(destination_bytes).is_err());
+
+        // The limits flag without any limit.
+        *bytes.last_mut().unwrap() = 2;
+        bytes.extend(Option::<u64>::None.to_bytes().unwrap());
+        bytes.extend(Option::<u64>::None.to_bytes().unwrap());
+        assert!(bytesrepr::deserialize::<Bid>(bytes).is_err());
+    }
+
+    #[test]
+    fn should_accept_only_reserved_delegators() {
+        let delegator_pk: PublicKey = (&SecretKey::ed25519_from_bytes([43; 32]).unwrap()).into();
+        let other_pk: PublicKey = (&SecretKey::ed25519_from_bytes([44; 32]).unwrap()).into();
+        let mut bid = unlocked_bid();
+        assert!(bid.accepts_delegator(&other_pk));
+
+        bid.reserved_delegators_mut().insert(delegator_pk.clone());
+        assert!(bid.accepts_delegator(&delegator_pk));
+        assert!(!bid.accepts_delegator(&other_pk));
+        assert_eq!(bid.occupied_delegator_slots(), 1);
+
+        // A reserved delegator which delegated takes a single slot.
+        let delegator = Delegator::unlocked(
+            delegator_pk.clone(),
+            U512::from(20),
+            URef::new([52; 32], AccessRights::ADD),
+            bid.validator_public_key().clone(),
+        );
+        bid.delegators_mut().insert(delegator_pk, delegator);
+        assert_eq!(bid.occupied_delegator_slots(), 1);
+
+        bid.reserved_delegators_mut().insert(other_pk);
+        assert_eq!(bid.occupied_delegator_slots(), 2);
+    }
+
+    #[test]
+    fn should_jail_until_era() {
+        let mut bid = unlocked_bid();
+        assert_eq!(bid.record_inactive_era(), 1);
+        assert_eq!(bid.record_inactive_era(), 2);
+        assert!(bid.record_active_era());
+        assert!(!bid.record_active_era());
+
+        bid.record_inactive_era();
+        bid.jail(Some(EraId::new(5)));
+        assert!(bid.inactive());
+        assert_eq!(bid.inactive_eras(), 0);
+        assert_eq!(bid.jail_count(), 1);
+        assert!(bid.is_jailed(EraId::new(4)));
+        assert_eq!(bid.unjail(EraId::new(4)), Err(Error::ValidatorJailed));
+        assert!(bid.inactive());
+
+        assert_eq!(bid.unjail(EraId::new(5)), Ok(()));
+        assert!(!bid.inactive());
+        assert_eq!(bid.jailed_until(), None);
+        assert_eq!(bid.jail_count(), 1);
+    }
+
+    #[test]
+    fn should_update_delegation_amount_limits() {
+        let mut bid = unlocked_bid();
+        bid.with_delegation_amount_limits(Some(10), None).unwrap();
+        bid.with_delegation_amount_limits(None, Some(20)).unwrap();
+        assert_eq!(bid.minimum_delegation_amount(), Some(10));
+        assert_eq!(bid.maximum_delegation_amount(), Some(20));
+
+        assert_eq!(
+            bid.with_delegation_amount_limits(Some(21), None)
+                .unwrap_err(),
+            Error::InvalidDelegationAmountLimits
+        );
+        assert_eq!(bid.minimum_delegation_amount(), Some(10));
+    }
+
+    #[test]
+    fn should_check_delegation_amount_against_limits() {
+        let delegator_pk: PublicKey = (&SecretKey::ed25519_from_bytes([43; 32]).unwrap()).into();
+        let mut bid = unlocked_bid();
+        assert!(bid
+            .check_delegation_amount(&delegator_pk, U512::one())
+            .is_ok());
+
+        bid.with_delegation_amount_limits(Some(10), Some(25))
+            .unwrap();
+        assert_eq!(
+            bid.check_delegation_amount(&delegator_pk, U512::from(9)),
+            Err(Error::DelegationAmountTooSmall)
+        );
+        assert!(bid
+            .check_delegation_amount(&delegator_pk, U512::from(25))
+            .is_ok());
+
+        let delegator = Delegator::unlocked(
+            delegator_pk.clone(),
+            U512::from(20),
+            URef::new([52; 32], AccessRights::ADD),
+            bid.validator_public_key().clone(),
+        );
+        bid.delegators_mut().insert(delegator_pk.clone(), delegator);
+        assert_eq!(
+            bid.check_delegation_amount(&delegator_pk, U512::from(10)),
+            Err(Error::DelegationAmountTooLarge)
+        );
+        assert!(bid
+            .check_delegation_amount(&delegator_pk, U512::from(5))
+            .is_err());
+    }
+
     #[test]
     fn should_immediately_initialize_unlock_amounts() {
         const TIMESTAMP_MILLIS: u64 = 0;
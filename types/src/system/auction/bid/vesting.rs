@@ -2,15 +2,22 @@
 #![allow(clippy::field_reassign_with_default)]
 
 use alloc::vec::Vec;
+use core::fmt::{self, Formatter};
 
 #[cfg(feature = "datasize")]
 use datasize::DataSize;
 #[cfg(feature = "json-schema")]
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use serde::{
+    de::{Error as SerdeError, SeqAccess, Unexpected, Visitor},
+    ser::SerializeTuple,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
 
+use super::VestingCurve;
 use crate::{
-    bytesrepr::{self, Error, FromBytes, ToBytes},
+    bytesrepr::{self, Error, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    system::auction::binary_fields::BinaryFields,
     U512,
 };
 
@@ -26,13 +33,30 @@ pub const VESTING_SCHEDULE_LENGTH_MILLIS: u64 =
 /// 91 days / 7 days in a week = 13 weeks
 const LOCKED_AMOUNTS_MAX_LENGTH: usize = (VESTING_SCHEDULE_LENGTH_DAYS / DAYS_IN_WEEK) + 1;
 
+/// Serialized in place of the `Option` tag of the locked amounts of a schedule which follows a
+/// custom vesting curve.  Schedules without a curve are serialized exactly as before curves were
+/// introduced.
+const VESTING_CURVE_TAG: u8 = 2;
+/// The `Option` tags of the locked amounts of a schedule without a custom vesting curve.
+const LOCKED_AMOUNTS_NONE_TAG: u8 = 0;
+const LOCKED_AMOUNTS_SOME_TAG: u8 = 1;
+/// The largest number of fields of a schedule, serialized one after the other by binary formats.
+const VESTING_SCHEDULE_FIELD_COUNT: usize = 4;
+
+/// The schedule along which the stake of a genesis validator or delegator is released.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "datasize", derive(DataSize))]
 #[cfg_attr(feature = "json-schema", derive(JsonSchema))]
-#[serde(deny_unknown_fields)]
+#[serde(deny_unknown_fields, remote = "Self")]
 pub struct VestingSchedule {
     initial_release_timestamp_millis: u64,
     locked_amounts: Option<[U512; LOCKED_AMOUNTS_MAX_LENGTH]>,
+    /// Custom release curve replacing the weekly release of `locked_amounts`.
+    #[serde(default)]
+    vesting_curve: Option<VestingCurve>,
+    /// Amount released along `vesting_curve`, set once the schedule is initialized.
+    #[serde(default)]
+    vested_amount: Option<U512>,
 }
 
 fn vesting_schedule_period_to_weeks(vesting_schedule_period_millis: u64) -> usize {
@@ -42,16 +66,39 @@ fn vesting_schedule_period_to_weeks(vesting_schedule_period_millis: u64) -> usiz
 }
 
 impl VestingSchedule {
+    /// Creates a new, uninitialized vesting schedule releasing from the given timestamp.
     pub fn new(initial_release_timestamp_millis: u64) -> Self {
         let locked_amounts = None;
         VestingSchedule {
             initial_release_timestamp_millis,
             locked_amounts,
+            vesting_curve: None,
+            vested_amount: None,
         }
     }
 
+    /// Sets a custom curve along which the staked amount is released instead of the configured
+    /// weekly release.
+    pub fn set_vesting_curve(&mut self, vesting_curve: VestingCurve) {
+        self.vesting_curve = Some(vesting_curve);
+    }
+
+    /// Returns the custom curve along which the staked amount is released, if any.
+    pub fn vesting_curve(&self) -> Option<&VestingCurve> {
+        self.vesting_curve.as_ref()
+    }
+
+    /// Returns the amount released along the custom vesting curve.  `None` if there is no custom
+    /// curve or the schedule is not yet initialized.
+    pub fn vested_amount(&self) -> Option<U512> {
+        self.vested_amount
+    }
+
     /// Initializes vesting schedule with a configured amount of weekly releases.
     ///
+    /// A schedule with a custom vesting curve releases the staked amount along that curve instead,
+    /// ignoring `vesting_schedule_period_millis`.
+    ///
     /// Returns `false` if already initialized.
     ///
     /// # Panics
@@ -62,6 +109,14 @@ impl VestingSchedule {
         staked_amount: U512,
         vesting_schedule_period_millis: u64,
     ) -> bool {
+        if self.vesting_curve.is_some() {
+            if self.vested_amount.is_some() {
+                return false;
+            }
+            self.vested_amount = Some(staked_amount);
+            return true;
+        }
+
         if self.locked_amounts.is_some() {
             return false;
         }
@@ -109,16 +164,28 @@ impl VestingSchedule {
         self.initialize_with_schedule(staked_amount, VESTING_SCHEDULE_LENGTH_MILLIS)
     }
 
+    /// Returns the timestamp from which the stake starts being released.
     pub fn initial_release_timestamp_millis(&self) -> u64 {
         self.initial_release_timestamp_millis
     }
 
+    /// Returns the weekly locked amounts.  `None` if the schedule is not yet initialized or
+    /// follows a custom vesting curve.
     pub fn locked_amounts(&self) -> Option<&[U512]> {
         let locked_amounts = self.locked_amounts.as_ref()?;
         Some(locked_amounts.as_slice())
     }
 
+    /// Returns the amount still locked at the given timestamp.  `None` if the schedule is not yet
+    /// initialized or the timestamp precedes the initial release.
     pub fn locked_amount(&self, timestamp_millis: u64) -> Option<U512> {
+        if let Some(vesting_curve) = self.vesting_curve.as_ref() {
+            let vested_amount = self.vested_amount?;
+            let elapsed_millis =
+                timestamp_millis.checked_sub(self.initial_release_timestamp_millis)?;
+            return Some(vesting_curve.locked_amount(vested_amount, elapsed_millis));
+        }
+
         let locked_amounts = self.locked_amounts()?;
 
         let index = {
@@ -138,6 +205,13 @@ impl VestingSchedule {
         timestamp_millis: u64,
         vesting_schedule_period_millis: u64,
     ) -> bool {
+        if let Some(vesting_curve) = self.vesting_curve.as_ref() {
+            let vested_period = self
+                .initial_release_timestamp_millis()
+                .saturating_add(vesting_curve.duration_millis());
+            return timestamp_millis < vested_period;
+        }
+
         let vested_period = match self.locked_amounts() {
             Some(locked_amounts) => {
                 let vesting_weeks = locked_amounts
@@ -198,48 +272,164 @@ impl ToBytes for VestingSchedule {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         let mut result = bytesrepr::allocate_buffer(self)?;
         result.append(&mut self.initial_release_timestamp_millis.to_bytes()?);
-        result.append(&mut self.locked_amounts.to_bytes()?);
+        match self.vesting_curve.as_ref() {
+            None => result.append(&mut self.locked_amounts.to_bytes()?),
+            Some(vesting_curve) => {
+                result.push(VESTING_CURVE_TAG);
+                result.append(&mut vesting_curve.to_bytes()?);
+                result.append(&mut self.vested_amount.to_bytes()?);
+            }
+        }
         Ok(result)
     }
 
     fn serialized_length(&self) -> usize {
         self.initial_release_timestamp_millis.serialized_length()
-            + self.locked_amounts.serialized_length()
+            + match self.vesting_curve.as_ref() {
+                None => self.locked_amounts.serialized_length(),
+                Some(vesting_curve) => {
+                    U8_SERIALIZED_LENGTH
+                        + vesting_curve.serialized_length()
+                        + self.vested_amount.serialized_length()
+                }
+            }
     }
 }
 
 impl FromBytes for VestingSchedule {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
         let (initial_release_timestamp_millis, bytes) = FromBytes::from_bytes(bytes)?;
+        if bytes.first() == Some(&VESTING_CURVE_TAG) {
+            let (vesting_curve, bytes) = FromBytes::from_bytes(&bytes[1..])?;
+            let (vested_amount, bytes) = FromBytes::from_bytes(bytes)?;
+            return Ok((
+                VestingSchedule {
+                    initial_release_timestamp_millis,
+                    locked_amounts: None,
+                    vesting_curve: Some(vesting_curve),
+                    vested_amount,
+                },
+                bytes,
+            ));
+        }
         let (locked_amounts, bytes) = FromBytes::from_bytes(bytes)?;
         Ok((
             VestingSchedule {
                 initial_release_timestamp_millis,
                 locked_amounts,
+                vesting_curve: None,
+                vested_amount: None,
             },
             bytes,
         ))
     }
 }
 
+// Binary formats such as bincode can't skip a field, so in those a schedule following a custom
+// vesting curve is marked by a tag in place of the `Option` tag of its locked amounts, as in its
+// `bytesrepr` encoding.  A schedule without a curve keeps the representation it had before curves
+// were introduced, so previously stored bids and delegators remain readable.
+impl Serialize for VestingSchedule {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return VestingSchedule::serialize(self, serializer);
+        }
+        match self.vesting_curve.as_ref() {
+            None => {
+                let mut tuple = serializer.serialize_tuple(2)?;
+                tuple.serialize_element(&self.initial_release_timestamp_millis)?;
+                tuple.serialize_element(&self.locked_amounts)?;
+                tuple.end()
+            }
+            Some(vesting_curve) => {
+                let mut tuple = serializer.serialize_tuple(VESTING_SCHEDULE_FIELD_COUNT)?;
+                tuple.serialize_element(&self.initial_release_timestamp_millis)?;
+                tuple.serialize_element(&VESTING_CURVE_TAG)?;
+                tuple.serialize_element(vesting_curve)?;
+                tuple.serialize_element(&self.vested_amount)?;
+                tuple.end()
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for VestingSchedule {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            return VestingSchedule::deserialize(deserializer);
+        }
+        deserializer.deserialize_tuple(VESTING_SCHEDULE_FIELD_COUNT, BinaryVestingScheduleVisitor)
+    }
+}
+
+struct BinaryVestingScheduleVisitor;
+
+impl<'de> Visitor<'de> for BinaryVestingScheduleVisitor {
+    type Value = VestingSchedule;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("a vesting schedule")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<VestingSchedule, A::Error> {
+        let mut fields = BinaryFields::new(seq, &self);
+        let mut vesting_schedule = VestingSchedule::new(fields.next()?);
+        match fields.next()? {
+            LOCKED_AMOUNTS_NONE_TAG => {}
+            LOCKED_AMOUNTS_SOME_TAG => vesting_schedule.locked_amounts = Some(fields.next()?),
+            VESTING_CURVE_TAG => {
+                vesting_schedule.vesting_curve = Some(fields.next()?);
+                vesting_schedule.vested_amount = fields.next()?;
+            }
+            tag => {
+                return Err(SerdeError::invalid_value(
+                    Unexpected::Unsigned(u64::from(tag)),
+                    &"a locked amounts or vesting curve tag",
+                ))
+            }
+        }
+        Ok(vesting_schedule)
+    }
+}
+
 /// Generators for [`VestingSchedule`]
 #[cfg(test)]
 mod gens {
     use proptest::{
         array, option,
         prelude::{Arbitrary, Strategy},
+        prop_oneof,
     };
 
     use super::VestingSchedule;
-    use crate::gens::u512_arb;
+    use crate::{gens::u512_arb, system::auction::bid::vesting_curve::gens::vesting_curve_arb};
 
     pub fn vesting_schedule_arb() -> impl Strategy<Value = VestingSchedule> {
-        (<u64>::arbitrary(), option::of(array::uniform14(u512_arb()))).prop_map(
-            |(initial_release_timestamp_millis, locked_amounts)| VestingSchedule {
-                initial_release_timestamp_millis,
-                locked_amounts,
-            },
-        )
+        prop_oneof![
+            (<u64>::arbitrary(), option::of(array::uniform14(u512_arb()))).prop_map(
+                |(initial_release_timestamp_millis, locked_amounts)| VestingSchedule {
+                    initial_release_timestamp_millis,
+                    locked_amounts,
+                    vesting_curve: None,
+                    vested_amount: None,
+                },
+            ),
+            (
+                <u64>::arbitrary(),
+                vesting_curve_arb(),
+                option::of(u512_arb())
+            )
+                .prop_map(
+                    |(initial_release_timestamp_millis, vesting_curve, vested_amount)| {
+                        VestingSchedule {
+                            initial_release_timestamp_millis,
+                            locked_amounts: None,
+                            vesting_curve: Some(vesting_curve),
+                            vested_amount,
+                        }
+                    },
+                ),
+        ]
     }
 }
 
@@ -252,7 +442,7 @@ mod tests {
         gens::u512_arb,
         system::auction::bid::{
             vesting::{gens::vesting_schedule_arb, vesting_schedule_period_to_weeks, WEEK_MILLIS},
-            VestingSchedule,
+            VestingCurve, VestingSchedule,
         },
         U512,
     };
@@ -493,6 +683,109 @@ mod tests {
         );
     }
 
+    #[test]
+    fn should_follow_vesting_curve() {
+        let mut vesting_schedule = VestingSchedule::new(RELEASE_TIMESTAMP);
+        vesting_schedule.set_vesting_curve(VestingCurve::Linear {
+            cliff_millis: 2 * WEEK_MILLIS as u64,
+            step_millis: 4 * WEEK_MILLIS as u64,
+            steps: 2,
+        });
+
+        assert!(vesting_schedule.is_vesting(RELEASE_TIMESTAMP, 0));
+        assert_eq!(vesting_schedule.locked_amount(RELEASE_TIMESTAMP), None);
+
+        assert!(vesting_schedule
+            .initialize_with_schedule(U512::from(STAKE), DEFAULT_VESTING_SCHEDULE_PERIOD_MILLIS));
+        assert!(!vesting_schedule.initialize(U512::from(STAKE)));
+        assert_eq!(vesting_schedule.locked_amounts(), None);
+        assert_eq!(vesting_schedule.vested_amount(), Some(U512::from(STAKE)));
+
+        let timestamp = RELEASE_TIMESTAMP + 2 * WEEK_MILLIS as u64;
+        assert_eq!(
+            vesting_schedule.locked_amount(timestamp - 1),
+            Some(U512::from(STAKE))
+        );
+        assert_eq!(
+            vesting_schedule.locked_amount(timestamp),
+            Some(U512::from(STAKE / 2))
+        );
+
+        let timestamp = RELEASE_TIMESTAMP + 6 * WEEK_MILLIS as u64;
+        assert!(vesting_schedule.is_vesting(timestamp - 1, DEFAULT_VESTING_SCHEDULE_PERIOD_MILLIS));
+        assert!(!vesting_schedule.is_vesting(timestamp, DEFAULT_VESTING_SCHEDULE_PERIOD_MILLIS));
+        assert_eq!(
+            vesting_schedule.locked_amount(timestamp),
+            Some(U512::zero())
+        );
+    }
+
+    #[test]
+    fn should_serialize_schedule_without_curve_as_before() {
+        let mut vesting_schedule = VestingSchedule::new(RELEASE_TIMESTAMP);
+        vesting_schedule.initialize(U512::from(STAKE));
+
+        let mut expected_bytes = RELEASE_TIMESTAMP.to_bytes().unwrap();
+        expected_bytes.extend(vesting_schedule.locked_amounts.to_bytes().unwrap());
+        assert_eq!(vesting_schedule.to_bytes().unwrap(), expected_bytes);
+    }
+
+    #[test]
+    fn should_roundtrip_through_bincode_with_and_without_curve() {
+        let mut vesting_schedule = VestingSchedule::new(RELEASE_TIMESTAMP);
+        vesting_schedule.initialize(U512::from(STAKE));
+        let serialized = bincode::serialize(&vesting_schedule).unwrap();
+        let deserialized: VestingSchedule = bincode::deserialize(&serialized).unwrap();
+        assert_eq!(deserialized, vesting_schedule);
+
+        let mut vesting_schedule = VestingSchedule::new(RELEASE_TIMESTAMP);
+        vesting_schedule.set_vesting_curve(VestingCurve::Linear {
+            cliff_millis: 2 * WEEK_MILLIS as u64,
+            step_millis: 4 * WEEK_MILLIS as u64,
+            steps: 2,
+        });
+        vesting_schedule.initialize(U512::from(STAKE));
+        let serialized = bincode::serialize(&vesting_schedule).unwrap();
+        let deserialized: VestingSchedule = bincode::deserialize(&serialized).unwrap();
+        assert_eq!(deserialized, vesting_schedule);
+    }
+
+    /// The serde representation of a [`VestingSchedule`] before vesting curves were introduced.
+    #[derive(Serialize)]
+    struct LegacyVestingSchedule {
+        initial_release_timestamp_millis: u64,
+        locked_amounts: Option<[U512; LOCKED_AMOUNTS_MAX_LENGTH]>,
+    }
+
+    #[test]
+    fn should_deserialize_bincode_schedule_in_its_original_layout() {
+        let mut initialized = VestingSchedule::new(RELEASE_TIMESTAMP);
+        initialized.initialize(U512::from(STAKE));
+        for vesting_schedule in [VestingSchedule::new(RELEASE_TIMESTAMP), initialized] {
+            let legacy_vesting_schedule = LegacyVestingSchedule {
+                initial_release_timestamp_millis: RELEASE_TIMESTAMP,
+                locked_amounts: vesting_schedule.locked_amounts,
+            };
+            let legacy_bytes = bincode::serialize(&(&legacy_vesting_schedule, u64::MAX)).unwrap();
+
+            let deserialized: (VestingSchedule, u64) = bincode::deserialize(&legacy_bytes).unwrap();
+            assert_eq!(deserialized, (vesting_schedule.clone(), u64::MAX));
+
+            // A schedule without a curve keeps its original layout.
+            assert_eq!(
+                bincode::serialize(&(&vesting_schedule, u64::MAX)).unwrap(),
+                legacy_bytes
+            );
+        }
+    }
+
+    #[test]
+    fn should_reject_bincode_schedule_with_invalid_tag() {
+        let mut bytes = bincode::serialize(&VestingSchedule::new(RELEASE_TIMESTAMP)).unwrap();
+        *bytes.last_mut().unwrap() = VESTING_CURVE_TAG + 1;
+        assert!(bincode::deserialize::<VestingSchedule>(&bytes).is_err());
+    }
+
     #[test]
     fn should_calculate_vesting_schedule_period_to_weeks() {
         let thirteen_weeks_millis = 13 * 7 * DAY_MILLIS as u64;
@@ -519,5 +812,13 @@ mod tests {
         fn prop_serialization_roundtrip(vesting_schedule in vesting_schedule_arb()) {
             bytesrepr::test_serialization_roundtrip(&vesting_schedule)
         }
+
+        #[test]
+        fn prop_bincode_roundtrip(vesting_schedule in vesting_schedule_arb()) {
+            // Followed by other data, as when embedded in a bid.
+            let serialized = bincode::serialize(&(&vesting_schedule, u64::MAX)).unwrap();
+            let deserialized: (VestingSchedule, u64) = bincode::deserialize(&serialized).unwrap();
+            prop_assert!(deserialized == (vesting_schedule, u64::MAX));
+        }
     }
 }
@@ -0,0 +1,418 @@
+use alloc::vec::Vec;
+
+#[cfg(feature = "datasize")]
+use datasize::DataSize;
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    U512,
+};
+
+const LINEAR_TAG: u8 = 0;
+const TRANCHES_TAG: u8 = 1;
+
+/// The maximum number of tranches of a [`VestingCurve`].
+pub const MAX_VESTING_TRANCHES: usize = 100;
+
+const FULL_RELEASE_PERCENTAGE: u64 = 100;
+
+/// A part of a vesting curve, released at a fixed time after the initial release timestamp.
+#[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "datasize", derive(DataSize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct VestingTranche {
+    /// Time after the initial release timestamp at which the tranche is released.
+    release_offset_millis: u64,
+    /// Percentage of the vested amount released by the tranche.
+    release_percentage: u8,
+}
+
+impl VestingTranche {
+    /// Creates a new [`VestingTranche`].
+    pub fn new(release_offset_millis: u64, release_percentage: u8) -> Self {
+        VestingTranche {
+            release_offset_millis,
+            release_percentage,
+        }
+    }
+
+    /// Returns the time after the initial release timestamp at which the tranche is released.
+    pub fn release_offset_millis(&self) -> u64 {
+        self.release_offset_millis
+    }
+
+    /// Returns the percentage of the vested amount released by the tranche.
+    pub fn release_percentage(&self) -> u8 {
+        self.release_percentage
+    }
+}
+
+impl ToBytes for VestingTranche {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.release_offset_millis.serialized_length() + self.release_percentage.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.release_offset_millis.write_bytes(writer)?;
+        self.release_percentage.write_bytes(writer)
+    }
+}
+
+impl FromBytes for VestingTranche {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (release_offset_millis, bytes) = FromBytes::from_bytes(bytes)?;
+        let (release_percentage, bytes) = FromBytes::from_bytes(bytes)?;
+        Ok((
+            VestingTranche {
+                release_offset_millis,
+                release_percentage,
+            },
+            bytes,
+        ))
+    }
+}
+
+/// How the vested amount of a genesis validator or delegator is released once its locked funds
+/// period ends, replacing the default weekly release over the vesting schedule period.
+#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "datasize", derive(DataSize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub enum VestingCurve {
+    /// The vested amount is released in `steps` equal parts, the first `cliff_millis` after the
+    /// initial release timestamp and each following one `step_millis` after the previous.  A
+    /// single step releases everything at the cliff.
+    Linear {
+        /// Time after the initial release timestamp at which the first part is released.
+        cliff_millis: u64,
+        /// Time between the releases of two consecutive parts.
+        step_millis: u64,
+        /// Number of parts the vested amount is released in.
+        steps: u32,
+    },
+    /// The vested amount is released in tranches, ordered by release time, whose percentages add
+    /// up to 100.
+    Tranches(Vec<VestingTranche>),
+}
+
+impl VestingCurve {
+    /// Returns `true` if the curve releases the whole vested amount in a finite, well-ordered
+    /// sequence of parts.
+    pub fn is_valid(&self) -> bool {
+        match self {
+            VestingCurve::Linear {
+                step_millis, steps, ..
+            } => *steps == 1 || (*steps > 1 && *step_millis > 0),
+            VestingCurve::Tranches(tranches) => {
+                if tranches.is_empty() || tranches.len() > MAX_VESTING_TRANCHES {
+                    return false;
+                }
+                let ordered = tranches
+                    .windows(2)
+                    .all(|pair| pair[0].release_offset_millis < pair[1].release_offset_millis);
+                let total_percentage: u64 = tranches
+                    .iter()
+                    .map(|tranche| u64::from(tranche.release_percentage))
+                    .sum();
+                ordered
+                    && tranches
+                        .iter()
+                        .all(|tranche| tranche.release_percentage > 0)
+                    && total_percentage == FULL_RELEASE_PERCENTAGE
+            }
+        }
+    }
+
+    /// Returns the time after the initial release timestamp at which the whole vested amount is
+    /// released.
+    pub fn duration_millis(&self) -> u64 {
+        match self {
+            VestingCurve::Linear {
+                cliff_millis,
+                step_millis,
+                steps,
+            } => {
+                let remaining_steps = u64::from(steps.saturating_sub(1));
+                cliff_millis.saturating_add(step_millis.saturating_mul(remaining_steps))
+            }
+            VestingCurve::Tranches(tranches) => tranches
+                .last()
+                .map(VestingTranche::release_offset_millis)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Returns the part of `vested_amount` which is still locked `elapsed_millis` after the
+    /// initial release timestamp.
+    pub fn locked_amount(&self, vested_amount: U512, elapsed_millis: u64) -> U512 {
+        let (released_parts, total_parts) = match self {
+            VestingCurve::Linear {
+                cliff_millis,
+                step_millis,
+                steps,
+            } => {
+                let total_steps = u64::from(*steps);
+                let released_steps = match elapsed_millis.checked_sub(*cliff_millis) {
+                    None => 0,
+                    Some(_) if *step_millis == 0 => total_steps,
+                    Some(since_cliff) => (since_cliff / step_millis)
+                        .saturating_add(1)
+                        .min(total_steps),
+                };
+                (released_steps, total_steps)
+            }
+            VestingCurve::Tranches(tranches) => {
+                let released_percentage: u64 = tranches
+                    .iter()
+                    .take_while(|tranche| tranche.release_offset_millis <= elapsed_millis)
+                    .map(|tranche| u64::from(tranche.release_percentage))
+                    .sum();
+                (released_percentage, FULL_RELEASE_PERCENTAGE)
+            }
+        };
+
+        if total_parts == 0 || released_parts >= total_parts {
+            return U512::zero();
+        }
+
+        let released_amount =
+            vested_amount.saturating_mul(U512::from(released_parts)) / U512::from(total_parts);
+        vested_amount.saturating_sub(released_amount)
+    }
+
+    fn tag(&self) -> u8 {
+        match self {
+            VestingCurve::Linear { .. } => LINEAR_TAG,
+            VestingCurve::Tranches(_) => TRANCHES_TAG,
+        }
+    }
+}
+
+impl ToBytes for VestingCurve {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + match self {
+                VestingCurve::Linear {
+                    cliff_millis,
+                    step_millis,
+                    steps,
+                } => {
+                    cliff_millis.serialized_length()
+                        + step_millis.serialized_length()
+                        + steps.serialized_length()
+                }
+                VestingCurve::Tranches(tranches) => tranches.serialized_length(),
+            }
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        writer.push(self.tag());
+        match self {
+            VestingCurve::Linear {
+                cliff_millis,
+                step_millis,
+                steps,
+            } => {
+                cliff_millis.write_bytes(writer)?;
+                step_millis.write_bytes(writer)?;
+                steps.write_bytes(writer)
+            }
+            VestingCurve::Tranches(tranches) => tranches.write_bytes(writer),
+        }
+    }
+}
+
+impl FromBytes for VestingCurve {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        match tag {
+            LINEAR_TAG => {
+                let (cliff_millis, remainder) = FromBytes::from_bytes(remainder)?;
+                let (step_millis, remainder) = FromBytes::from_bytes(remainder)?;
+                let (steps, remainder) = FromBytes::from_bytes(remainder)?;
+                Ok((
+                    VestingCurve::Linear {
+                        cliff_millis,
+                        step_millis,
+                        steps,
+                    },
+                    remainder,
+                ))
+            }
+            TRANCHES_TAG => {
+                let (tranches, remainder) = FromBytes::from_bytes(remainder)?;
+                Ok((VestingCurve::Tranches(tranches), remainder))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+/// Generators for [`VestingCurve`]
+#[cfg(test)]
+pub(super) mod gens {
+    use proptest::{
+        collection::vec,
+        prelude::{any, Strategy},
+        prop_oneof,
+    };
+
+    use super::{VestingCurve, VestingTranche};
+
+    pub fn vesting_curve_arb() -> impl Strategy<Value = VestingCurve> {
+        prop_oneof![
+            (any::<u64>(), any::<u64>(), any::<u32>()).prop_map(
+                |(cliff_millis, step_millis, steps)| VestingCurve::Linear {
+                    cliff_millis,
+                    step_millis,
+                    steps,
+                }
+            ),
+            vec((any::<u64>(), any::<u8>()), 0..5).prop_map(|tranches| {
+                VestingCurve::Tranches(
+                    tranches
+                        .into_iter()
+                        .map(|(offset, percentage)| VestingTranche::new(offset, percentage))
+                        .collect(),
+                )
+            }),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use super::{gens::vesting_curve_arb, *};
+
+    const DAY_MILLIS: u64 = 24 * 60 * 60 * 1000;
+    const STAKE: u64 = 1200;
+
+    #[test]
+    fn should_release_linear_curve_after_cliff() {
+        let curve = VestingCurve::Linear {
+            cliff_millis: 30 * DAY_MILLIS,
+            step_millis: 10 * DAY_MILLIS,
+            steps: 4,
+        };
+        assert!(curve.is_valid());
+        assert_eq!(curve.duration_millis(), 60 * DAY_MILLIS);
+
+        let stake = U512::from(STAKE);
+        assert_eq!(curve.locked_amount(stake, 0), stake);
+        assert_eq!(curve.locked_amount(stake, 30 * DAY_MILLIS - 1), stake);
+        assert_eq!(curve.locked_amount(stake, 30 * DAY_MILLIS), U512::from(900));
+        assert_eq!(
+            curve.locked_amount(stake, 40 * DAY_MILLIS - 1),
+            U512::from(900)
+        );
+        assert_eq!(curve.locked_amount(stake, 40 * DAY_MILLIS), U512::from(600));
+        assert_eq!(curve.locked_amount(stake, 50 * DAY_MILLIS), U512::from(300));
+        assert_eq!(curve.locked_amount(stake, 60 * DAY_MILLIS), U512::zero());
+        assert_eq!(curve.locked_amount(stake, u64::MAX), U512::zero());
+    }
+
+    #[test]
+    fn should_release_single_step_curve_at_cliff() {
+        let curve = VestingCurve::Linear {
+            cliff_millis: 365 * DAY_MILLIS,
+            step_millis: 0,
+            steps: 1,
+        };
+        assert!(curve.is_valid());
+        assert_eq!(curve.duration_millis(), 365 * DAY_MILLIS);
+
+        let stake = U512::from(STAKE);
+        assert_eq!(curve.locked_amount(stake, 365 * DAY_MILLIS - 1), stake);
+        assert_eq!(curve.locked_amount(stake, 365 * DAY_MILLIS), U512::zero());
+    }
+
+    #[test]
+    fn should_release_tranches() {
+        let curve = VestingCurve::Tranches(vec![
+            VestingTranche::new(0, 10),
+            VestingTranche::new(30 * DAY_MILLIS, 40),
+            VestingTranche::new(90 * DAY_MILLIS, 50),
+        ]);
+        assert!(curve.is_valid());
+        assert_eq!(curve.duration_millis(), 90 * DAY_MILLIS);
+
+        let stake = U512::from(STAKE);
+        assert_eq!(curve.locked_amount(stake, 0), U512::from(1080));
+        assert_eq!(
+            curve.locked_amount(stake, 30 * DAY_MILLIS - 1),
+            U512::from(1080)
+        );
+        assert_eq!(curve.locked_amount(stake, 30 * DAY_MILLIS), U512::from(600));
+        assert_eq!(
+            curve.locked_amount(stake, 90 * DAY_MILLIS - 1),
+            U512::from(600)
+        );
+        assert_eq!(curve.locked_amount(stake, 90 * DAY_MILLIS), U512::zero());
+    }
+
+    #[test]
+    fn should_reject_invalid_curves() {
+        let no_steps = VestingCurve::Linear {
+            cliff_millis: 0,
+            step_millis: DAY_MILLIS,
+            steps: 0,
+        };
+        assert!(!no_steps.is_valid());
+
+        let instant_steps = VestingCurve::Linear {
+            cliff_millis: 0,
+            step_millis: 0,
+            steps: 2,
+        };
+        assert!(!instant_steps.is_valid());
+
+        assert!(!VestingCurve::Tranches(vec![]).is_valid());
+
+        let incomplete = VestingCurve::Tranches(vec![VestingTranche::new(0, 99)]);
+        assert!(!incomplete.is_valid());
+
+        let unordered = VestingCurve::Tranches(vec![
+            VestingTranche::new(DAY_MILLIS, 50),
+            VestingTranche::new(DAY_MILLIS, 50),
+        ]);
+        assert!(!unordered.is_valid());
+
+        let empty_tranche = VestingCurve::Tranches(vec![
+            VestingTranche::new(0, 100),
+            VestingTranche::new(DAY_MILLIS, 0),
+        ]);
+        assert!(!empty_tranche.is_valid());
+
+        let too_many = VestingCurve::Tranches(
+            (0..=MAX_VESTING_TRANCHES as u64)
+                .map(|offset| VestingTranche::new(offset, 1))
+                .collect(),
+        );
+        assert!(!too_many.is_valid());
+    }
+
+    proptest! {
+        #[test]
+        fn prop_serialization_roundtrip(vesting_curve in vesting_curve_arb()) {
+            bytesrepr::test_serialization_roundtrip(&vesting_curve)
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use core::marker::PhantomData;
+
+use serde::de::{Deserialize, DeserializeSeed, Error, Expected, SeqAccess};
+
+/// The fields of an auction record serialized one after the other by a binary format such as
+/// bincode, where the presence of a field can depend on the value of an earlier one.
+pub(super) struct BinaryFields<'a, A> {
+    seq: A,
+    index: usize,
+    expected: &'a dyn Expected,
+}
+
+impl<'de, 'a, A: SeqAccess<'de>> BinaryFields<'a, A> {
+    pub(super) fn new(seq: A, expected: &'a dyn Expected) -> Self {
+        BinaryFields {
+            seq,
+            index: 0,
+            expected,
+        }
+    }
+
+    /// Reads the next field.
+    pub(super) fn next<T: Deserialize<'de>>(&mut self) -> Result<T, A::Error> {
+        self.next_seed(PhantomData)
+    }
+
+    /// Reads the next field using the given seed.
+    pub(super) fn next_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<T::Value, A::Error> {
+        let field = self
+            .seq
+            .next_element_seed(seed)?
+            .ok_or_else(|| A::Error::invalid_length(self.index, self.expected))?;
+        self.index += 1;
+        Ok(field)
+    }
+}
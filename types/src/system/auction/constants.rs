@@ -32,6 +32,8 @@ pub const ARG_VALIDATOR_PURSE: &str = "validator_purse";
 pub const ARG_VALIDATOR_KEYS: &str = "validator_keys";
 /// Named constant for `validator_public_keys`.
 pub const ARG_VALIDATOR_PUBLIC_KEYS: &str = "validator_public_keys";
+/// Named constant for `slashes`.
+pub const ARG_SLASHES: &str = "slashes";
 /// Named constant for `new_validator`.
 pub const ARG_NEW_VALIDATOR: &str = "new_validator";
 /// Named constant for `era_id`.
@@ -58,6 +60,16 @@ pub const ARG_UNBONDING_DELAY: &str = "unbonding_delay";
 pub const ARG_ERA_END_TIMESTAMP_MILLIS: &str = "era_end_timestamp_millis";
 /// Named constant for `evicted_validators`;
 pub const ARG_EVICTED_VALIDATORS: &str = "evicted_validators";
+/// Named constant for `minimum_delegation_amount`.
+pub const ARG_MINIMUM_DELEGATION_AMOUNT: &str = "minimum_delegation_amount";
+/// Named constant for `maximum_delegation_amount`.
+pub const ARG_MAXIMUM_DELEGATION_AMOUNT: &str = "maximum_delegation_amount";
+/// Named constant for `delegators`.
+pub const ARG_DELEGATORS: &str = "delegators";
+/// Named constant for `new_delegator`.
+pub const ARG_NEW_DELEGATOR: &str = "new_delegator";
+/// Named constant for `reward_destination`.
+pub const ARG_REWARD_DESTINATION: &str = "reward_destination";
 
 /// Named constant for method `get_era_validators`.
 pub const METHOD_GET_ERA_VALIDATORS: &str = "get_era_validators";
@@ -81,6 +93,16 @@ pub const METHOD_DISTRIBUTE: &str = "distribute";
 pub const METHOD_READ_ERA_ID: &str = "read_era_id";
 /// Named constant for method `activate_bid`.
 pub const METHOD_ACTIVATE_BID: &str = "activate_bid";
+/// Named constant for method `add_reservations`.
+pub const METHOD_ADD_RESERVATIONS: &str = "add_reservations";
+/// Named constant for method `cancel_reservations`.
+pub const METHOD_CANCEL_RESERVATIONS: &str = "cancel_reservations";
+/// Named constant for method `unjail`.
+pub const METHOD_UNJAIL: &str = "unjail";
+/// Named constant for method `transfer_delegation`.
+pub const METHOD_TRANSFER_DELEGATION: &str = "transfer_delegation";
+/// Named constant for method `set_reward_destination`.
+pub const METHOD_SET_REWARD_DESTINATION: &str = "set_reward_destination";
 
 /// Storage for `EraId`.
 pub const ERA_ID_KEY: &str = "era_id";
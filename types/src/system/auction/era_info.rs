@@ -2,26 +2,45 @@
 #![allow(clippy::field_reassign_with_default)]
 
 use alloc::{boxed::Box, vec::Vec};
+use core::fmt::{self, Formatter};
 
 #[cfg(feature = "datasize")]
 use datasize::DataSize;
 #[cfg(feature = "json-schema")]
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use serde::{
+    de::{DeserializeSeed, Error as SerdeError, SeqAccess, Visitor},
+    ser::SerializeTuple,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
 
 use crate::{
     bytesrepr::{self, FromBytes, ToBytes},
+    system::auction::{binary_fields::BinaryFields, RewardDestination, SlashingOffence},
     CLType, CLTyped, PublicKey, U512,
 };
 
 const SEIGNIORAGE_ALLOCATION_VALIDATOR_TAG: u8 = 0;
 const SEIGNIORAGE_ALLOCATION_DELEGATOR_TAG: u8 = 1;
+/// Tags of allocations followed by the reward destination they were paid to.  Allocations added
+/// to the stake keep their original encoding.
+const SEIGNIORAGE_ALLOCATION_VALIDATOR_WITH_DESTINATION_TAG: u8 = 2;
+const SEIGNIORAGE_ALLOCATION_DELEGATOR_WITH_DESTINATION_TAG: u8 = 3;
+
+/// Written in place of the seigniorage allocations length when an [`EraInfo`] also carries
+/// slashes.  Era infos without slashes keep their original encoding.
+const ERA_INFO_WITH_SLASHES_MARKER: u32 = u32::MAX;
+/// Written in place of the seigniorage allocations length by binary formats when an [`EraInfo`]
+/// also carries slashes.
+const BINARY_ERA_INFO_WITH_SLASHES_MARKER: u64 = u64::MAX;
+/// The largest number of fields of an era info, serialized one after the other by binary formats.
+const ERA_INFO_FIELD_COUNT: usize = 3;
 
 /// Information about a seigniorage allocation
 #[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "datasize", derive(DataSize))]
 #[cfg_attr(feature = "json-schema", derive(JsonSchema))]
-#[serde(deny_unknown_fields)]
+#[serde(deny_unknown_fields, remote = "Self")]
 pub enum SeigniorageAllocation {
     /// Info about a seigniorage allocation for a validator
     Validator {
@@ -29,6 +48,9 @@ pub enum SeigniorageAllocation {
         validator_public_key: PublicKey,
         /// Allocated amount
         amount: U512,
+        /// Where the allocated amount was paid
+        #[serde(default)]
+        reward_destination: RewardDestination,
     },
     /// Info about a seigniorage allocation for a delegator
     Delegator {
@@ -38,6 +60,9 @@ pub enum SeigniorageAllocation {
         validator_public_key: PublicKey,
         /// Allocated amount
         amount: U512,
+        /// Where the allocated amount was paid
+        #[serde(default)]
+        reward_destination: RewardDestination,
     },
 }
 
@@ -47,6 +72,7 @@ impl SeigniorageAllocation {
         SeigniorageAllocation::Validator {
             validator_public_key,
             amount,
+            reward_destination: RewardDestination::Compound,
         }
     }
 
@@ -60,7 +86,21 @@ impl SeigniorageAllocation {
             delegator_public_key,
             validator_public_key,
             amount,
+            reward_destination: RewardDestination::Compound,
+        }
+    }
+
+    /// Sets where the allocated amount was paid.
+    pub fn with_reward_destination(mut self, new_reward_destination: RewardDestination) -> Self {
+        match &mut self {
+            SeigniorageAllocation::Validator {
+                reward_destination, ..
+            }
+            | SeigniorageAllocation::Delegator {
+                reward_destination, ..
+            } => *reward_destination = new_reward_destination,
         }
+        self
     }
 
     /// Returns the amount for a given seigniorage allocation
@@ -71,14 +111,180 @@ impl SeigniorageAllocation {
         }
     }
 
-    fn tag(&self) -> u8 {
+    /// Returns where the allocated amount was paid
+    pub fn reward_destination(&self) -> &RewardDestination {
         match self {
-            SeigniorageAllocation::Validator { .. } => SEIGNIORAGE_ALLOCATION_VALIDATOR_TAG,
-            SeigniorageAllocation::Delegator { .. } => SEIGNIORAGE_ALLOCATION_DELEGATOR_TAG,
+            SeigniorageAllocation::Validator {
+                reward_destination, ..
+            } => reward_destination,
+            SeigniorageAllocation::Delegator {
+                reward_destination, ..
+            } => reward_destination,
+        }
+    }
+
+    fn tag(&self) -> u8 {
+        match (self, self.reward_destination().is_compound()) {
+            (SeigniorageAllocation::Validator { .. }, true) => SEIGNIORAGE_ALLOCATION_VALIDATOR_TAG,
+            (SeigniorageAllocation::Delegator { .. }, true) => SEIGNIORAGE_ALLOCATION_DELEGATOR_TAG,
+            (SeigniorageAllocation::Validator { .. }, false) => {
+                SEIGNIORAGE_ALLOCATION_VALIDATOR_WITH_DESTINATION_TAG
+            }
+            (SeigniorageAllocation::Delegator { .. }, false) => {
+                SEIGNIORAGE_ALLOCATION_DELEGATOR_WITH_DESTINATION_TAG
+            }
+        }
+    }
+}
+
+// Binary formats such as bincode can't skip a field, so in those an allocation paid to a reward
+// destination other than the stake is carried by a separate variant.  An allocation added to the
+// stake keeps the representation it had before reward destinations were introduced, so previously
+// stored era infos remain readable.
+impl Serialize for SeigniorageAllocation {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return SeigniorageAllocation::serialize(self, serializer);
+        }
+        let helper = match self {
+            SeigniorageAllocation::Validator {
+                validator_public_key,
+                amount,
+                reward_destination,
+            } if reward_destination.is_compound() => BinarySerHelper::Validator {
+                validator_public_key,
+                amount,
+            },
+            SeigniorageAllocation::Delegator {
+                delegator_public_key,
+                validator_public_key,
+                amount,
+                reward_destination,
+            } if reward_destination.is_compound() => BinarySerHelper::Delegator {
+                delegator_public_key,
+                validator_public_key,
+                amount,
+            },
+            SeigniorageAllocation::Validator {
+                validator_public_key,
+                amount,
+                reward_destination,
+            } => BinarySerHelper::ValidatorWithDestination {
+                validator_public_key,
+                amount,
+                reward_destination,
+            },
+            SeigniorageAllocation::Delegator {
+                delegator_public_key,
+                validator_public_key,
+                amount,
+                reward_destination,
+            } => BinarySerHelper::DelegatorWithDestination {
+                delegator_public_key,
+                validator_public_key,
+                amount,
+                reward_destination,
+            },
+        };
+        helper.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SeigniorageAllocation {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            return SeigniorageAllocation::deserialize(deserializer);
         }
+        let allocation = match BinaryDeserHelper::deserialize(deserializer)? {
+            BinaryDeserHelper::Validator {
+                validator_public_key,
+                amount,
+            } => SeigniorageAllocation::validator(validator_public_key, amount),
+            BinaryDeserHelper::Delegator {
+                delegator_public_key,
+                validator_public_key,
+                amount,
+            } => {
+                SeigniorageAllocation::delegator(delegator_public_key, validator_public_key, amount)
+            }
+            BinaryDeserHelper::ValidatorWithDestination {
+                reward_destination, ..
+            }
+            | BinaryDeserHelper::DelegatorWithDestination {
+                reward_destination, ..
+            } if reward_destination.is_compound() => {
+                return Err(SerdeError::custom(
+                    "allocation added to the stake encoded with its reward destination",
+                ));
+            }
+            BinaryDeserHelper::ValidatorWithDestination {
+                validator_public_key,
+                amount,
+                reward_destination,
+            } => SeigniorageAllocation::validator(validator_public_key, amount)
+                .with_reward_destination(reward_destination),
+            BinaryDeserHelper::DelegatorWithDestination {
+                delegator_public_key,
+                validator_public_key,
+                amount,
+                reward_destination,
+            } => {
+                SeigniorageAllocation::delegator(delegator_public_key, validator_public_key, amount)
+                    .with_reward_destination(reward_destination)
+            }
+        };
+        Ok(allocation)
     }
 }
 
+#[derive(Serialize)]
+enum BinarySerHelper<'a> {
+    Validator {
+        validator_public_key: &'a PublicKey,
+        amount: &'a U512,
+    },
+    Delegator {
+        delegator_public_key: &'a PublicKey,
+        validator_public_key: &'a PublicKey,
+        amount: &'a U512,
+    },
+    ValidatorWithDestination {
+        validator_public_key: &'a PublicKey,
+        amount: &'a U512,
+        reward_destination: &'a RewardDestination,
+    },
+    DelegatorWithDestination {
+        delegator_public_key: &'a PublicKey,
+        validator_public_key: &'a PublicKey,
+        amount: &'a U512,
+        reward_destination: &'a RewardDestination,
+    },
+}
+
+#[derive(Deserialize)]
+enum BinaryDeserHelper {
+    Validator {
+        validator_public_key: PublicKey,
+        amount: U512,
+    },
+    Delegator {
+        delegator_public_key: PublicKey,
+        validator_public_key: PublicKey,
+        amount: U512,
+    },
+    ValidatorWithDestination {
+        validator_public_key: PublicKey,
+        amount: U512,
+        reward_destination: RewardDestination,
+    },
+    DelegatorWithDestination {
+        delegator_public_key: PublicKey,
+        validator_public_key: PublicKey,
+        amount: U512,
+        reward_destination: RewardDestination,
+    },
+}
+
 impl ToBytes for SeigniorageAllocation {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         let mut buffer = bytesrepr::allocate_buffer(self)?;
@@ -92,17 +298,24 @@ impl ToBytes for SeigniorageAllocation {
                 SeigniorageAllocation::Validator {
                     validator_public_key,
                     amount,
+                    ..
                 } => validator_public_key.serialized_length() + amount.serialized_length(),
                 SeigniorageAllocation::Delegator {
                     delegator_public_key,
                     validator_public_key,
                     amount,
+                    ..
                 } => {
                     delegator_public_key.serialized_length()
                         + validator_public_key.serialized_length()
                         + amount.serialized_length()
                 }
             }
+            + if self.reward_destination().is_compound() {
+                0
+            } else {
+                self.reward_destination().serialized_length()
+            }
     }
 
     fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
@@ -111,6 +324,7 @@ impl ToBytes for SeigniorageAllocation {
             SeigniorageAllocation::Validator {
                 validator_public_key,
                 amount,
+                ..
             } => {
                 validator_public_key.write_bytes(writer)?;
                 amount.write_bytes(writer)?;
@@ -119,12 +333,16 @@ impl ToBytes for SeigniorageAllocation {
                 delegator_public_key,
                 validator_public_key,
                 amount,
+                ..
             } => {
                 delegator_public_key.write_bytes(writer)?;
                 validator_public_key.write_bytes(writer)?;
                 amount.write_bytes(writer)?;
             }
         }
+        if !self.reward_destination().is_compound() {
+            self.reward_destination().write_bytes(writer)?;
+        }
         Ok(())
     }
 }
@@ -132,29 +350,43 @@ impl ToBytes for SeigniorageAllocation {
 impl FromBytes for SeigniorageAllocation {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
         let (tag, rem) = <u8>::from_bytes(bytes)?;
-        match tag {
-            SEIGNIORAGE_ALLOCATION_VALIDATOR_TAG => {
+        let (allocation, rem) = match tag {
+            SEIGNIORAGE_ALLOCATION_VALIDATOR_TAG
+            | SEIGNIORAGE_ALLOCATION_VALIDATOR_WITH_DESTINATION_TAG => {
                 let (validator_public_key, rem) = PublicKey::from_bytes(rem)?;
                 let (amount, rem) = U512::from_bytes(rem)?;
-                Ok((
+                (
                     SeigniorageAllocation::validator(validator_public_key, amount),
                     rem,
-                ))
+                )
             }
-            SEIGNIORAGE_ALLOCATION_DELEGATOR_TAG => {
+            SEIGNIORAGE_ALLOCATION_DELEGATOR_TAG
+            | SEIGNIORAGE_ALLOCATION_DELEGATOR_WITH_DESTINATION_TAG => {
                 let (delegator_public_key, rem) = PublicKey::from_bytes(rem)?;
                 let (validator_public_key, rem) = PublicKey::from_bytes(rem)?;
                 let (amount, rem) = U512::from_bytes(rem)?;
-                Ok((
+                (
                     SeigniorageAllocation::delegator(
                         delegator_public_key,
                         validator_public_key,
                         amount,
                     ),
                     rem,
-                ))
+                )
+            }
+            _ => return Err(bytesrepr::Error::Formatting),
+        };
+        match tag {
+            SEIGNIORAGE_ALLOCATION_VALIDATOR_WITH_DESTINATION_TAG
+            | SEIGNIORAGE_ALLOCATION_DELEGATOR_WITH_DESTINATION_TAG => {
+                let (reward_destination, rem) = RewardDestination::from_bytes(rem)?;
+                if reward_destination.is_compound() {
+                    // Allocations added to the stake are always serialized without a destination.
+                    return Err(bytesrepr::Error::Formatting);
+                }
+                Ok((allocation.with_reward_destination(reward_destination), rem))
             }
-            _ => Err(bytesrepr::Error::Formatting),
+            _ => Ok((allocation, rem)),
         }
     }
 }
@@ -165,21 +397,127 @@ impl CLTyped for SeigniorageAllocation {
     }
 }
 
+/// Information about the stake burned when slashing a validator
+#[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "datasize", derive(DataSize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct SlashedStake {
+    validator_public_key: PublicKey,
+    offence: SlashingOffence,
+    validator_amount: U512,
+    delegators_amount: U512,
+}
+
+impl SlashedStake {
+    /// Constructs a [`SlashedStake`]
+    pub const fn new(
+        validator_public_key: PublicKey,
+        offence: SlashingOffence,
+        validator_amount: U512,
+        delegators_amount: U512,
+    ) -> Self {
+        SlashedStake {
+            validator_public_key,
+            offence,
+            validator_amount,
+            delegators_amount,
+        }
+    }
+
+    /// Returns the public key of the slashed validator
+    pub fn validator_public_key(&self) -> &PublicKey {
+        &self.validator_public_key
+    }
+
+    /// Returns the offence the validator was slashed for
+    pub fn offence(&self) -> SlashingOffence {
+        self.offence
+    }
+
+    /// Returns the amount burned from the validator's own stake and unbonding purses
+    pub fn validator_amount(&self) -> &U512 {
+        &self.validator_amount
+    }
+
+    /// Returns the amount burned from the stakes and unbonding purses of the validator's
+    /// delegators
+    pub fn delegators_amount(&self) -> &U512 {
+        &self.delegators_amount
+    }
+
+    /// Returns the total amount burned
+    pub fn total_amount(&self) -> U512 {
+        self.validator_amount.saturating_add(self.delegators_amount)
+    }
+}
+
+impl ToBytes for SlashedStake {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.validator_public_key.serialized_length()
+            + self.offence.serialized_length()
+            + self.validator_amount.serialized_length()
+            + self.delegators_amount.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.validator_public_key.write_bytes(writer)?;
+        self.offence.write_bytes(writer)?;
+        self.validator_amount.write_bytes(writer)?;
+        self.delegators_amount.write_bytes(writer)?;
+        Ok(())
+    }
+}
+
+impl FromBytes for SlashedStake {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (validator_public_key, rem) = PublicKey::from_bytes(bytes)?;
+        let (offence, rem) = SlashingOffence::from_bytes(rem)?;
+        let (validator_amount, rem) = U512::from_bytes(rem)?;
+        let (delegators_amount, rem) = U512::from_bytes(rem)?;
+        Ok((
+            SlashedStake::new(
+                validator_public_key,
+                offence,
+                validator_amount,
+                delegators_amount,
+            ),
+            rem,
+        ))
+    }
+}
+
+impl CLTyped for SlashedStake {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
 /// Auction metadata.  Intended to be recorded at each era.
 #[derive(Debug, Default, Clone, Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "datasize", derive(DataSize))]
 #[cfg_attr(feature = "json-schema", derive(JsonSchema))]
-#[serde(deny_unknown_fields)]
+#[serde(deny_unknown_fields, remote = "Self")]
 pub struct EraInfo {
     seigniorage_allocations: Vec<SeigniorageAllocation>,
+    #[serde(default)]
+    slashes: Vec<SlashedStake>,
 }
 
 impl EraInfo {
     /// Constructs a [`EraInfo`].
     pub fn new() -> Self {
         let seigniorage_allocations = Vec::new();
+        let slashes = Vec::new();
         EraInfo {
             seigniorage_allocations,
+            slashes,
         }
     }
 
@@ -193,6 +531,16 @@ impl EraInfo {
         &mut self.seigniorage_allocations
     }
 
+    /// Returns a reference to the slashes collection
+    pub fn slashes(&self) -> &Vec<SlashedStake> {
+        &self.slashes
+    }
+
+    /// Returns a mutable reference to the slashes collection
+    pub fn slashes_mut(&mut self) -> &mut Vec<SlashedStake> {
+        &mut self.slashes
+    }
+
     /// Returns all seigniorage allocations that match the provided public key
     /// using the following criteria:
     /// * If the match candidate is a validator allocation, the provided public key is matched
@@ -215,29 +563,151 @@ impl EraInfo {
     }
 }
 
+// Binary formats such as bincode can't skip a field, so in those an era info carrying slashes is
+// marked in place of the length of its seigniorage allocations, as in its `bytesrepr` encoding.  An
+// era info without slashes keeps the representation it had before slashes were introduced, so
+// previously stored era infos, such as those written by the `WriteEraInfo` transforms of execution
+// results, remain readable.
+impl Serialize for EraInfo {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return EraInfo::serialize(self, serializer);
+        }
+        if self.slashes.is_empty() {
+            let mut tuple = serializer.serialize_tuple(1)?;
+            tuple.serialize_element(&self.seigniorage_allocations)?;
+            return tuple.end();
+        }
+        let mut tuple = serializer.serialize_tuple(ERA_INFO_FIELD_COUNT)?;
+        tuple.serialize_element(&BINARY_ERA_INFO_WITH_SLASHES_MARKER)?;
+        tuple.serialize_element(&self.seigniorage_allocations)?;
+        tuple.serialize_element(&self.slashes)?;
+        tuple.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for EraInfo {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            return EraInfo::deserialize(deserializer);
+        }
+        deserializer.deserialize_tuple(ERA_INFO_FIELD_COUNT, BinaryEraInfoVisitor)
+    }
+}
+
+struct BinaryEraInfoVisitor;
+
+impl<'de> Visitor<'de> for BinaryEraInfoVisitor {
+    type Value = EraInfo;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("an era info")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<EraInfo, A::Error> {
+        let mut fields = BinaryFields::new(seq, &self);
+        let marker: u64 = fields.next()?;
+        if marker != BINARY_ERA_INFO_WITH_SLASHES_MARKER {
+            // The marker is the length of the seigniorage allocations which follow it.
+            let seigniorage_allocations = fields.next_seed(BinaryAllocations(marker))?;
+            return Ok(EraInfo {
+                seigniorage_allocations,
+                slashes: Vec::new(),
+            });
+        }
+        let seigniorage_allocations = fields.next()?;
+        let slashes: Vec<SlashedStake> = fields.next()?;
+        if slashes.is_empty() {
+            return Err(SerdeError::custom("empty slashes following their marker"));
+        }
+        Ok(EraInfo {
+            seigniorage_allocations,
+            slashes,
+        })
+    }
+}
+
+/// The given number of seigniorage allocations serialized one after the other, without a length.
+struct BinaryAllocations(u64);
+
+impl<'de> DeserializeSeed<'de> for BinaryAllocations {
+    type Value = Vec<SeigniorageAllocation>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let length = usize::try_from(self.0)
+            .map_err(|_| SerdeError::custom("too many seigniorage allocations"))?;
+        deserializer.deserialize_tuple(length, self)
+    }
+}
+
+impl<'de> Visitor<'de> for BinaryAllocations {
+    type Value = Vec<SeigniorageAllocation>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "{} seigniorage allocations", self.0)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        let mut fields = BinaryFields::new(seq, &self);
+        let mut seigniorage_allocations = Vec::new();
+        for _ in 0..self.0 {
+            seigniorage_allocations.push(fields.next()?);
+        }
+        Ok(seigniorage_allocations)
+    }
+}
+
 impl ToBytes for EraInfo {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         let mut result = bytesrepr::allocate_buffer(self)?;
-        self.seigniorage_allocations().write_bytes(&mut result)?;
+        self.write_bytes(&mut result)?;
         Ok(result)
     }
 
     fn serialized_length(&self) -> usize {
-        self.seigniorage_allocations.serialized_length()
+        if self.slashes.is_empty() {
+            self.seigniorage_allocations.serialized_length()
+        } else {
+            ERA_INFO_WITH_SLASHES_MARKER.serialized_length()
+                + self.seigniorage_allocations.serialized_length()
+                + self.slashes.serialized_length()
+        }
     }
 
     fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        if !self.slashes.is_empty() {
+            ERA_INFO_WITH_SLASHES_MARKER.write_bytes(writer)?;
+        }
         self.seigniorage_allocations().write_bytes(writer)?;
+        if !self.slashes.is_empty() {
+            self.slashes().write_bytes(writer)?;
+        }
         Ok(())
     }
 }
 
 impl FromBytes for EraInfo {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
-        let (seigniorage_allocations, rem) = Vec::<SeigniorageAllocation>::from_bytes(bytes)?;
+        let (marker, rem) = u32::from_bytes(bytes)?;
+        if marker != ERA_INFO_WITH_SLASHES_MARKER {
+            let (seigniorage_allocations, rem) = Vec::<SeigniorageAllocation>::from_bytes(bytes)?;
+            return Ok((
+                EraInfo {
+                    seigniorage_allocations,
+                    slashes: Vec::new(),
+                },
+                rem,
+            ));
+        }
+        let (seigniorage_allocations, rem) = Vec::<SeigniorageAllocation>::from_bytes(rem)?;
+        let (slashes, rem) = Vec::<SlashedStake>::from_bytes(rem)?;
+        if slashes.is_empty() {
+            return Err(bytesrepr::Error::Formatting);
+        }
         Ok((
             EraInfo {
                 seigniorage_allocations,
+                slashes,
             },
             rem,
         ))
@@ -255,28 +725,42 @@ impl CLTyped for EraInfo {
 pub mod gens {
     use proptest::{
         collection::{self, SizeRange},
-        prelude::Strategy,
+        prelude::{Just, Strategy},
         prop_oneof,
     };
 
     use crate::{
         crypto::gens::public_key_arb,
-        gens::u512_arb,
-        system::auction::{EraInfo, SeigniorageAllocation},
+        gens::{reward_destination_arb, u512_arb},
+        system::auction::{EraInfo, SeigniorageAllocation, SlashedStake, SlashingOffence},
     };
 
     fn seigniorage_allocation_validator_arb() -> impl Strategy<Value = SeigniorageAllocation> {
-        (public_key_arb(), u512_arb()).prop_map(|(validator_public_key, amount)| {
-            SeigniorageAllocation::validator(validator_public_key, amount)
-        })
+        (public_key_arb(), u512_arb(), reward_destination_arb()).prop_map(
+            |(validator_public_key, amount, reward_destination)| {
+                SeigniorageAllocation::validator(validator_public_key, amount)
+                    .with_reward_destination(reward_destination)
+            },
+        )
     }
 
     fn seigniorage_allocation_delegator_arb() -> impl Strategy<Value = SeigniorageAllocation> {
-        (public_key_arb(), public_key_arb(), u512_arb()).prop_map(
-            |(delegator_public_key, validator_public_key, amount)| {
-                SeigniorageAllocation::delegator(delegator_public_key, validator_public_key, amount)
-            },
+        (
+            public_key_arb(),
+            public_key_arb(),
+            u512_arb(),
+            reward_destination_arb(),
         )
+            .prop_map(
+                |(delegator_public_key, validator_public_key, amount, reward_destination)| {
+                    SeigniorageAllocation::delegator(
+                        delegator_public_key,
+                        validator_public_key,
+                        amount,
+                    )
+                    .with_reward_destination(reward_destination)
+                },
+            )
     }
 
     /// Creates an arbitrary [`SeignorageAllocation`](crate::system::auction::SeigniorageAllocation)
@@ -287,28 +771,258 @@ pub mod gens {
         ]
     }
 
+    /// Creates an arbitrary [`SlashedStake`](crate::system::auction::SlashedStake)
+    pub fn slashed_stake_arb() -> impl Strategy<Value = SlashedStake> {
+        (
+            public_key_arb(),
+            prop_oneof![
+                Just(SlashingOffence::Equivocation),
+                Just(SlashingOffence::Unavailability)
+            ],
+            u512_arb(),
+            u512_arb(),
+        )
+            .prop_map(
+                |(validator_public_key, offence, validator_amount, delegators_amount)| {
+                    SlashedStake::new(
+                        validator_public_key,
+                        offence,
+                        validator_amount,
+                        delegators_amount,
+                    )
+                },
+            )
+    }
+
     /// Creates an arbitrary [`EraInfo`]
     pub fn era_info_arb(size: impl Into<SizeRange>) -> impl Strategy<Value = EraInfo> {
-        collection::vec(seigniorage_allocation_arb(), size).prop_map(|allocations| {
-            let mut era_info = EraInfo::new();
-            *era_info.seigniorage_allocations_mut() = allocations;
-            era_info
-        })
+        (
+            collection::vec(seigniorage_allocation_arb(), size),
+            collection::vec(slashed_stake_arb(), 0..3),
+        )
+            .prop_map(|(allocations, slashes)| {
+                let mut era_info = EraInfo::new();
+                *era_info.seigniorage_allocations_mut() = allocations;
+                *era_info.slashes_mut() = slashes;
+                era_info
+            })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use proptest::prelude::*;
+    use serde::Serialize;
+
+    use crate::{
+        account::AccountHash,
+        bytesrepr::{self, FromBytes, ToBytes},
+        system::auction::{
+            RewardDestination, SeigniorageAllocation, SlashedStake, SlashingOffence,
+        },
+        PublicKey, SecretKey, U512,
+    };
+
+    use super::{gens, EraInfo};
+
+    /// The serde representation of a [`SeigniorageAllocation`] before reward destinations were
+    /// introduced.
+    #[derive(Serialize)]
+    enum LegacySeigniorageAllocation {
+        Validator {
+            validator_public_key: PublicKey,
+            amount: U512,
+        },
+        Delegator {
+            delegator_public_key: PublicKey,
+            validator_public_key: PublicKey,
+            amount: U512,
+        },
+    }
 
-    use crate::bytesrepr;
+    /// The serde representation of an [`EraInfo`] before slashes were introduced.
+    #[derive(Serialize)]
+    struct LegacyEraInfo {
+        seigniorage_allocations: Vec<LegacySeigniorageAllocation>,
+    }
 
-    use super::gens;
+    fn public_key(seed: u8) -> PublicKey {
+        PublicKey::from(&SecretKey::ed25519_from_bytes([seed; SecretKey::ED25519_LENGTH]).unwrap())
+    }
 
     proptest! {
         #[test]
         fn test_serialization_roundtrip(era_info in gens::era_info_arb(0..32)) {
             bytesrepr::test_serialization_roundtrip(&era_info)
         }
+
+        #[test]
+        fn test_bincode_roundtrip(era_info in gens::era_info_arb(0..32)) {
+            // Followed by other data, as in a stored execution result.
+            let serialized = bincode::serialize(&(&era_info, u64::MAX)).unwrap();
+            let deserialized: (EraInfo, u64) = bincode::deserialize(&serialized).unwrap();
+            prop_assert_eq!(deserialized, (era_info, u64::MAX));
+        }
+    }
+
+    #[test]
+    fn should_keep_legacy_encoding_without_slashes() {
+        let mut era_info = EraInfo::new();
+        era_info
+            .seigniorage_allocations_mut()
+            .push(SeigniorageAllocation::validator(public_key(1), U512::one()));
+
+        let legacy_bytes = era_info.seigniorage_allocations().to_bytes().unwrap();
+        assert_eq!(era_info.to_bytes().unwrap(), legacy_bytes);
+
+        let (decoded, rem) = EraInfo::from_bytes(&legacy_bytes).unwrap();
+        assert!(rem.is_empty());
+        assert_eq!(decoded, era_info);
+    }
+
+    #[test]
+    fn should_roundtrip_slashes() {
+        let mut era_info = EraInfo::new();
+        era_info.slashes_mut().push(SlashedStake::new(
+            public_key(1),
+            SlashingOffence::Unavailability,
+            U512::from(10),
+            U512::from(5),
+        ));
+        bytesrepr::test_serialization_roundtrip(&era_info);
+        assert_eq!(era_info.slashes()[0].total_amount(), U512::from(15));
+    }
+
+    #[test]
+    fn should_roundtrip_allocations_through_bincode() {
+        let allocations = vec![
+            SeigniorageAllocation::validator(public_key(1), U512::one()),
+            SeigniorageAllocation::delegator(public_key(2), public_key(1), U512::from(2)),
+            SeigniorageAllocation::validator(public_key(3), U512::from(3))
+                .with_reward_destination(RewardDestination::Account(AccountHash::new([4; 32]))),
+            SeigniorageAllocation::delegator(public_key(5), public_key(3), U512::from(5))
+                .with_reward_destination(RewardDestination::Compound),
+        ];
+        let serialized = bincode::serialize(&allocations).unwrap();
+        let deserialized: Vec<SeigniorageAllocation> = bincode::deserialize(&serialized).unwrap();
+        assert_eq!(deserialized, allocations);
+    }
+
+    #[test]
+    fn should_deserialize_bincode_allocations_in_their_original_layout() {
+        let legacy_allocations = vec![
+            LegacySeigniorageAllocation::Validator {
+                validator_public_key: public_key(1),
+                amount: U512::one(),
+            },
+            LegacySeigniorageAllocation::Delegator {
+                delegator_public_key: public_key(2),
+                validator_public_key: public_key(1),
+                amount: U512::from(2),
+            },
+        ];
+        let legacy_bytes = bincode::serialize(&legacy_allocations).unwrap();
+
+        let allocations: Vec<SeigniorageAllocation> = bincode::deserialize(&legacy_bytes).unwrap();
+        assert_eq!(
+            allocations,
+            vec![
+                SeigniorageAllocation::validator(public_key(1), U512::one()),
+                SeigniorageAllocation::delegator(public_key(2), public_key(1), U512::from(2)),
+            ]
+        );
+        assert!(allocations
+            .iter()
+            .all(|allocation| allocation.reward_destination().is_compound()));
+
+        // Allocations added to the stake keep their original layout.
+        assert_eq!(bincode::serialize(&allocations).unwrap(), legacy_bytes);
+    }
+
+    #[test]
+    fn should_reject_bincode_allocation_with_compound_destination_variant() {
+        let allocation =
+            SeigniorageAllocation::delegator(public_key(2), public_key(1), U512::one())
+                .with_reward_destination(RewardDestination::Account(AccountHash::new([4; 32])));
+        let mut bytes = bincode::serialize(&allocation).unwrap();
+        let destination_bytes =
+            bincode::serialize(&RewardDestination::Account(AccountHash::new([4; 32]))).unwrap();
+        bytes.truncate(bytes.len() - destination_bytes.len());
+        bytes.extend(bincode::serialize(&RewardDestination::Compound).unwrap());
+        assert!(bincode::deserialize::<SeigniorageAllocation>(&bytes).is_err());
+    }
+
+    #[test]
+    fn should_deserialize_bincode_era_info_in_its_original_layout() {
+        let legacy_era_info = LegacyEraInfo {
+            seigniorage_allocations: vec![
+                LegacySeigniorageAllocation::Validator {
+                    validator_public_key: public_key(1),
+                    amount: U512::one(),
+                },
+                LegacySeigniorageAllocation::Delegator {
+                    delegator_public_key: public_key(2),
+                    validator_public_key: public_key(1),
+                    amount: U512::from(2),
+                },
+            ],
+        };
+        let legacy_bytes = bincode::serialize(&(&legacy_era_info, u64::MAX)).unwrap();
+
+        let (era_info, trailing): (EraInfo, u64) = bincode::deserialize(&legacy_bytes).unwrap();
+        assert_eq!(trailing, u64::MAX);
+        let mut expected_era_info = EraInfo::new();
+        expected_era_info.seigniorage_allocations_mut().extend([
+            SeigniorageAllocation::validator(public_key(1), U512::one()),
+            SeigniorageAllocation::delegator(public_key(2), public_key(1), U512::from(2)),
+        ]);
+        assert_eq!(era_info, expected_era_info);
+
+        // An era info without slashes keeps its original layout.
+        assert_eq!(
+            bincode::serialize(&(&era_info, u64::MAX)).unwrap(),
+            legacy_bytes
+        );
+    }
+
+    #[test]
+    fn should_roundtrip_slashes_through_bincode() {
+        let mut era_info = EraInfo::new();
+        era_info
+            .seigniorage_allocations_mut()
+            .push(SeigniorageAllocation::validator(public_key(2), U512::one()));
+        era_info.slashes_mut().push(SlashedStake::new(
+            public_key(1),
+            SlashingOffence::Equivocation,
+            U512::from(10),
+            U512::from(5),
+        ));
+        let serialized = bincode::serialize(&era_info).unwrap();
+        assert_eq!(
+            bincode::deserialize::<EraInfo>(&serialized).unwrap(),
+            era_info
+        );
+    }
+
+    #[test]
+    fn should_reject_empty_bincode_slashes_after_marker() {
+        let bytes = bincode::serialize(&(
+            u64::MAX,
+            Vec::<SeigniorageAllocation>::new(),
+            Vec::<SlashedStake>::new(),
+        ))
+        .unwrap();
+        assert!(bincode::deserialize::<EraInfo>(&bytes).is_err());
+    }
+
+    #[test]
+    fn should_reject_empty_slashes_after_marker() {
+        let mut bytes = u32::MAX.to_bytes().unwrap();
+        bytes.extend(Vec::<SeigniorageAllocation>::new().to_bytes().unwrap());
+        bytes.extend(Vec::<SlashedStake>::new().to_bytes().unwrap());
+        assert_eq!(
+            EraInfo::from_bytes(&bytes),
+            Err(bytesrepr::Error::Formatting)
+        );
     }
 }
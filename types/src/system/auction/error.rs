@@ -327,6 +327,61 @@ pub enum Error {
     /// assert_eq!(49, Error::TransferToAdministrator as u8);
     /// ```
     TransferToAdministrator = 49,
+    /// The delegated amount is above the maximum allowed by the validator.
+    /// ```
+    /// # use casper_types::system::auction::Error;
+    /// assert_eq!(50, Error::DelegationAmountTooLarge as u8);
+    /// ```
+    DelegationAmountTooLarge = 50,
+    /// The minimum delegation amount of a bid is above its maximum delegation amount.
+    /// ```
+    /// # use casper_types::system::auction::Error;
+    /// assert_eq!(51, Error::InvalidDelegationAmountLimits as u8);
+    /// ```
+    InvalidDelegationAmountLimits = 51,
+    /// The delegator is not on the reservation list of a validator which has reserved its
+    /// delegation slots.
+    /// ```
+    /// # use casper_types::system::auction::Error;
+    /// assert_eq!(52, Error::DelegatorNotReserved as u8);
+    /// ```
+    DelegatorNotReserved = 52,
+    /// The delegator is not on the reservation list of the validator.
+    /// ```
+    /// # use casper_types::system::auction::Error;
+    /// assert_eq!(53, Error::ReservationNotFound as u8);
+    /// ```
+    ReservationNotFound = 53,
+    /// The validator is still jailed and cannot reactivate its bid yet.
+    /// ```
+    /// # use casper_types::system::auction::Error;
+    /// assert_eq!(54, Error::ValidatorJailed as u8);
+    /// ```
+    ValidatorJailed = 54,
+    /// Transfers of delegations are disabled.
+    /// ```
+    /// # use casper_types::system::auction::Error;
+    /// assert_eq!(55, Error::DelegationTransfersDisabled as u8);
+    /// ```
+    DelegationTransfersDisabled = 55,
+    /// A delegation cannot be transferred to the delegator holding it.
+    /// ```
+    /// # use casper_types::system::auction::Error;
+    /// assert_eq!(56, Error::DelegationTransferToSelf as u8);
+    /// ```
+    DelegationTransferToSelf = 56,
+    /// The reward destination is neither an existing purse nor an existing account.
+    /// ```
+    /// # use casper_types::system::auction::Error;
+    /// assert_eq!(57, Error::InvalidRewardDestination as u8);
+    /// ```
+    InvalidRewardDestination = 57,
+    /// The validator has reserved the maximum number of delegation slots.
+    /// ```
+    /// # use casper_types::system::auction::Error;
+    /// assert_eq!(58, Error::ExceededReservationsLimit as u8);
+    /// ```
+    ExceededReservationsLimit = 58,
 }
 
 impl Display for Error {
@@ -382,6 +437,15 @@ impl Display for Error {
             Error::AuctionBidsDisabled => formatter.write_str("Auction bids are disabled"),
             Error::GetAccumulationPurse => formatter.write_str("Get accumulation purse error"),
             Error::TransferToAdministrator => formatter.write_str("Transfer to administrator error"),
+            Error::DelegationAmountTooLarge => formatter.write_str("The delegated amount is above the maximum allowed"),
+            Error::InvalidDelegationAmountLimits => formatter.write_str("The minimum delegation amount is above the maximum delegation amount"),
+            Error::DelegatorNotReserved => formatter.write_str("The delegator has no reserved delegation slot"),
+            Error::ReservationNotFound => formatter.write_str("Reservation not found"),
+            Error::ValidatorJailed => formatter.write_str("Validator is jailed"),
+            Error::DelegationTransfersDisabled => formatter.write_str("Delegation transfers are disabled"),
+            Error::DelegationTransferToSelf => formatter.write_str("Cannot transfer a delegation to its own delegator"),
+            Error::InvalidRewardDestination => formatter.write_str("Invalid reward destination"),
+            Error::ExceededReservationsLimit => formatter.write_str("The amount of reserved delegation slots per validator has been exceeded"),
         }
     }
 }
@@ -463,6 +527,21 @@ impl TryFrom<u8> for Error {
             d if d == Error::AuctionBidsDisabled as u8 => Ok(Error::AuctionBidsDisabled),
             d if d == Error::GetAccumulationPurse as u8 => Ok(Error::GetAccumulationPurse),
             d if d == Error::TransferToAdministrator as u8 => Ok(Error::TransferToAdministrator),
+            d if d == Error::DelegationAmountTooLarge as u8 => Ok(Error::DelegationAmountTooLarge),
+            d if d == Error::InvalidDelegationAmountLimits as u8 => {
+                Ok(Error::InvalidDelegationAmountLimits)
+            }
+            d if d == Error::DelegatorNotReserved as u8 => Ok(Error::DelegatorNotReserved),
+            d if d == Error::ReservationNotFound as u8 => Ok(Error::ReservationNotFound),
+            d if d == Error::ValidatorJailed as u8 => Ok(Error::ValidatorJailed),
+            d if d == Error::DelegationTransfersDisabled as u8 => {
+                Ok(Error::DelegationTransfersDisabled)
+            }
+            d if d == Error::DelegationTransferToSelf as u8 => Ok(Error::DelegationTransferToSelf),
+            d if d == Error::InvalidRewardDestination as u8 => Ok(Error::InvalidRewardDestination),
+            d if d == Error::ExceededReservationsLimit as u8 => {
+                Ok(Error::ExceededReservationsLimit)
+            }
             _ => Err(TryFromU8ForError(())),
         }
     }
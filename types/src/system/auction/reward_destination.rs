@@ -0,0 +1,125 @@
+use alloc::vec::Vec;
+
+#[cfg(feature = "datasize")]
+use datasize::DataSize;
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    CLType, CLTyped, URef,
+};
+
+const COMPOUND_TAG: u8 = 0;
+const PURSE_TAG: u8 = 1;
+const ACCOUNT_TAG: u8 = 2;
+
+/// Where the seigniorage of a validator or of a delegator is paid.
+#[derive(
+    Debug, Default, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+#[cfg_attr(feature = "datasize", derive(DataSize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub enum RewardDestination {
+    /// The seigniorage is added to the stake.
+    #[default]
+    Compound,
+    /// The seigniorage is paid into the given purse.
+    Purse(URef),
+    /// The seigniorage is paid into the main purse of the given account.
+    Account(AccountHash),
+}
+
+impl RewardDestination {
+    /// Returns `true` if the seigniorage is added to the stake.
+    pub fn is_compound(&self) -> bool {
+        matches!(self, RewardDestination::Compound)
+    }
+
+    fn tag(&self) -> u8 {
+        match self {
+            RewardDestination::Compound => COMPOUND_TAG,
+            RewardDestination::Purse(_) => PURSE_TAG,
+            RewardDestination::Account(_) => ACCOUNT_TAG,
+        }
+    }
+}
+
+impl ToBytes for RewardDestination {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + match self {
+                RewardDestination::Compound => 0,
+                RewardDestination::Purse(purse) => purse.serialized_length(),
+                RewardDestination::Account(account_hash) => account_hash.serialized_length(),
+            }
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        writer.push(self.tag());
+        match self {
+            RewardDestination::Compound => Ok(()),
+            RewardDestination::Purse(purse) => purse.write_bytes(writer),
+            RewardDestination::Account(account_hash) => account_hash.write_bytes(writer),
+        }
+    }
+}
+
+impl FromBytes for RewardDestination {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        match tag {
+            COMPOUND_TAG => Ok((RewardDestination::Compound, remainder)),
+            PURSE_TAG => {
+                let (purse, remainder) = URef::from_bytes(remainder)?;
+                Ok((RewardDestination::Purse(purse), remainder))
+            }
+            ACCOUNT_TAG => {
+                let (account_hash, remainder) = AccountHash::from_bytes(remainder)?;
+                Ok((RewardDestination::Account(account_hash), remainder))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+impl CLTyped for RewardDestination {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AccessRights;
+
+    #[test]
+    fn serialization_roundtrip() {
+        bytesrepr::test_serialization_roundtrip(&RewardDestination::Compound);
+        bytesrepr::test_serialization_roundtrip(&RewardDestination::Purse(URef::new(
+            [1; 32],
+            AccessRights::ADD,
+        )));
+        bytesrepr::test_serialization_roundtrip(&RewardDestination::Account(AccountHash::new(
+            [2; 32],
+        )));
+    }
+
+    #[test]
+    fn should_reject_unknown_destination() {
+        assert_eq!(
+            RewardDestination::from_bytes(&[3]),
+            Err(bytesrepr::Error::Formatting)
+        );
+    }
+}
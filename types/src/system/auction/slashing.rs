@@ -0,0 +1,75 @@
+use alloc::vec::Vec;
+
+#[cfg(feature = "datasize")]
+use datasize::DataSize;
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped,
+};
+
+/// The kind of fault for which a validator is slashed.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "datasize", derive(DataSize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub enum SlashingOffence {
+    /// The validator signed conflicting consensus messages.
+    Equivocation = 0,
+    /// The validator did not participate in consensus for an entire era.
+    Unavailability = 1,
+}
+
+impl ToBytes for SlashingOffence {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        (*self as u8).to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        1
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        writer.push(*self as u8);
+        Ok(())
+    }
+}
+
+impl FromBytes for SlashingOffence {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (value, bytes) = u8::from_bytes(bytes)?;
+        match value {
+            0 => Ok((SlashingOffence::Equivocation, bytes)),
+            1 => Ok((SlashingOffence::Unavailability, bytes)),
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+impl CLTyped for SlashingOffence {
+    fn cl_type() -> CLType {
+        CLType::U8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialization_roundtrip() {
+        bytesrepr::test_serialization_roundtrip(&SlashingOffence::Equivocation);
+        bytesrepr::test_serialization_roundtrip(&SlashingOffence::Unavailability);
+    }
+
+    #[test]
+    fn should_reject_unknown_offence() {
+        assert_eq!(
+            SlashingOffence::from_bytes(&[2]),
+            Err(bytesrepr::Error::Formatting)
+        );
+    }
+}
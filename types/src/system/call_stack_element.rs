@@ -2,6 +2,7 @@ use alloc::vec::Vec;
 
 use num_derive::{FromPrimitive, ToPrimitive};
 use num_traits::FromPrimitive;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     account::AccountHash,
@@ -22,7 +23,7 @@ pub enum CallStackElementTag {
 }
 
 /// Represents the origin of a sub-call.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CallStackElement {
     /// Session
     Session {
@@ -6,6 +6,10 @@ pub const ARG_AMOUNT: &str = "amount";
 pub const ARG_ACCOUNT: &str = "account";
 /// Named constant for `target`.
 pub const ARG_TARGET: &str = "target";
+/// Named constant for `sponsor`.
+///
+/// When passed to standard payment, names the account or contract paying for the deploy.
+pub const ARG_SPONSOR: &str = "sponsor";
 
 /// Named constant for method `get_payment_purse`.
 pub const METHOD_GET_PAYMENT_PURSE: &str = "get_payment_purse";
@@ -17,6 +21,14 @@ pub const METHOD_GET_REFUND_PURSE: &str = "get_refund_purse";
 pub const METHOD_FINALIZE_PAYMENT: &str = "finalize_payment";
 /// Named constant for method `distribute_accumulated_fees`.
 pub const METHOD_DISTRIBUTE_ACCUMULATED_FEES: &str = "distribute_accumulated_fees";
+/// Named constant for method `sponsor_payment`.
+pub const METHOD_SPONSOR_PAYMENT: &str = "sponsor_payment";
+
+/// Named constant for the entry point a sponsor contract exposes to approve paying for a deploy.
+///
+/// It is called in the payment phase with the deploy's `account` and the requested `amount`, and
+/// pays by calling the handle payment contract's `sponsor_payment` entry point.
+pub const ENTRY_POINT_APPROVE_PAYMENT: &str = "approve_payment";
 
 /// Storage for handle payment contract hash.
 pub const CONTRACT_HASH_KEY: &str = "contract_hash";
@@ -3,7 +3,7 @@ use alloc::boxed::Box;
 use crate::{
     system::handle_payment::{
         ARG_ACCOUNT, ARG_AMOUNT, ARG_PURSE, METHOD_FINALIZE_PAYMENT, METHOD_GET_PAYMENT_PURSE,
-        METHOD_GET_REFUND_PURSE, METHOD_SET_REFUND_PURSE,
+        METHOD_GET_REFUND_PURSE, METHOD_SET_REFUND_PURSE, METHOD_SPONSOR_PAYMENT,
     },
     CLType, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Parameter,
 };
@@ -62,5 +62,17 @@ pub fn handle_payment_entry_points() -> EntryPoints {
     );
     entry_points.add_entry_point(distribute_accumulated_fees);
 
+    let sponsor_payment = EntryPoint::new(
+        METHOD_SPONSOR_PAYMENT,
+        vec![
+            Parameter::new(ARG_PURSE, CLType::URef),
+            Parameter::new(ARG_AMOUNT, CLType::U512),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(sponsor_payment);
+
     entry_points
 }
@@ -255,6 +255,18 @@ pub enum Error {
     /// assert_eq!(37, Error::AccumulationPurseKeyUnexpectedType as u8);
     /// ```
     AccumulationPurseKeyUnexpectedType = 37,
+    /// Sponsor payment function called from outside the payment phase.
+    /// ```
+    /// # use casper_types::system::handle_payment::Error;
+    /// assert_eq!(38, Error::SponsorPaymentCalledOutsidePayment as u8);
+    /// ```
+    SponsorPaymentCalledOutsidePayment = 38,
+    /// Sponsor payment function called by other than the contract sponsoring the deploy.
+    /// ```
+    /// # use casper_types::system::handle_payment::Error;
+    /// assert_eq!(39, Error::SponsorPaymentCalledByNonSponsor as u8);
+    /// ```
+    SponsorPaymentCalledByNonSponsor = 39,
 }
 
 impl Display for Error {
@@ -326,6 +338,12 @@ impl Display for Error {
             Error::AccumulationPurseKeyUnexpectedType => {
                 formatter.write_str("Accumulation purse has unexpected type")
             }
+            Error::SponsorPaymentCalledOutsidePayment => {
+                formatter.write_str("Sponsor payment was called outside payment")
+            }
+            Error::SponsorPaymentCalledByNonSponsor => {
+                formatter.write_str("Sponsor payment was called by other than the deploy's sponsor")
+            }
         }
     }
 }
@@ -400,6 +418,12 @@ impl TryFrom<u8> for Error {
             v if v == Error::AccumulationPurseKeyUnexpectedType as u8 => {
                 Error::AccumulationPurseKeyUnexpectedType
             }
+            v if v == Error::SponsorPaymentCalledOutsidePayment as u8 => {
+                Error::SponsorPaymentCalledOutsidePayment
+            }
+            v if v == Error::SponsorPaymentCalledByNonSponsor as u8 => {
+                Error::SponsorPaymentCalledByNonSponsor
+            }
             _ => return Err(()),
         };
         Ok(error)
@@ -1,8 +1,15 @@
 //! Contains implementation of a Mint contract functionality.
+mod allowance;
 mod constants;
 mod entry_points;
 mod error;
+mod scheduled_transfer;
 
+pub use allowance::{allowance_dictionary_item_key, Allowance};
 pub use constants::*;
 pub use entry_points::mint_entry_points;
 pub use error::Error;
+pub use scheduled_transfer::{
+    ScheduledTransfer, ScheduledTransferResult, TransferSchedule, MAX_SCHEDULED_TRANSFERS,
+    MAX_SCHEDULED_TRANSFERS_PER_OWNER, SCHEDULED_TRANSFER_WINDOW_MILLIS,
+};
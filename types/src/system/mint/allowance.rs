@@ -0,0 +1,129 @@
+//! Allowances granted over purses to third-party spenders.
+// TODO - remove once schemars stops causing warning.
+#![allow(clippy::field_reassign_with_default)]
+
+use alloc::{string::String, vec::Vec};
+
+#[cfg(feature = "datasize")]
+use datasize::DataSize;
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    crypto, CLType, CLTyped, Key, URefAddr, U512,
+};
+
+/// An allowance granted by the owner of a purse to a spender.
+///
+/// The spender can transfer up to `amount` motes out of the purse through the mint's
+/// `transfer_from_allowance` entry point until the allowance expires.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "datasize", derive(DataSize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct Allowance {
+    amount: U512,
+    expires_at: Option<u64>,
+}
+
+impl Allowance {
+    /// Creates a new allowance of `amount` motes, expiring at the block time `expires_at` (in
+    /// milliseconds since the Unix epoch), if any.
+    pub const fn new(amount: U512, expires_at: Option<u64>) -> Self {
+        Allowance { amount, expires_at }
+    }
+
+    /// Returns the amount of motes that can still be spent.
+    pub fn amount(&self) -> U512 {
+        self.amount
+    }
+
+    /// Returns the block time at which this allowance expires, if any.
+    pub fn expires_at(&self) -> Option<u64> {
+        self.expires_at
+    }
+
+    /// Returns `true` if this allowance cannot be used at the given block time.
+    pub fn is_expired(&self, blocktime: u64) -> bool {
+        matches!(self.expires_at, Some(expires_at) if blocktime >= expires_at)
+    }
+}
+
+impl CLTyped for Allowance {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for Allowance {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.amount.to_bytes()?);
+        result.append(&mut self.expires_at.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.amount.serialized_length() + self.expires_at.serialized_length()
+    }
+}
+
+impl FromBytes for Allowance {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (amount, bytes) = FromBytes::from_bytes(bytes)?;
+        let (expires_at, bytes) = FromBytes::from_bytes(bytes)?;
+        Ok((Allowance { amount, expires_at }, bytes))
+    }
+}
+
+/// Returns the key of the mint's allowances dictionary item holding the allowance granted to
+/// `spender` over the purse `purse_addr`.
+pub fn allowance_dictionary_item_key(
+    purse_addr: URefAddr,
+    spender: &Key,
+) -> Result<String, bytesrepr::Error> {
+    let mut preimage = purse_addr.to_vec();
+    preimage.append(&mut spender.to_bytes()?);
+    Ok(base16::encode_lower(&crypto::blake2b(preimage)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{account::AccountHash, bytesrepr, Key, U512};
+
+    use super::{allowance_dictionary_item_key, Allowance};
+
+    #[test]
+    fn serialization_roundtrip() {
+        let allowance = Allowance::new(U512::from(1_000), Some(42));
+        bytesrepr::test_serialization_roundtrip(&allowance);
+        let allowance = Allowance::new(U512::zero(), None);
+        bytesrepr::test_serialization_roundtrip(&allowance);
+    }
+
+    #[test]
+    fn should_expire_at_given_blocktime() {
+        let allowance = Allowance::new(U512::one(), Some(100));
+        assert!(!allowance.is_expired(99));
+        assert!(allowance.is_expired(100));
+        assert!(!Allowance::new(U512::one(), None).is_expired(u64::MAX));
+    }
+
+    #[test]
+    fn item_key_should_depend_on_purse_and_spender() {
+        let spender = Key::Account(AccountHash::new([1; 32]));
+        let other_spender = Key::Hash([1; 32]);
+        let item_key = allowance_dictionary_item_key([2; 32], &spender).unwrap();
+        assert_eq!(item_key.len(), 64);
+        assert_ne!(
+            item_key,
+            allowance_dictionary_item_key([3; 32], &spender).unwrap()
+        );
+        assert_ne!(
+            item_key,
+            allowance_dictionary_item_key([2; 32], &other_spender).unwrap()
+        );
+    }
+}
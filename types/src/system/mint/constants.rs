@@ -12,6 +12,20 @@ pub const ARG_SOURCE: &str = "source";
 pub const ARG_TARGET: &str = "target";
 /// Named constant for `round_seigniorage_rate` used in installer.
 pub const ARG_ROUND_SEIGNIORAGE_RATE: &str = "round_seigniorage_rate";
+/// Named constant for `spender`.
+pub const ARG_SPENDER: &str = "spender";
+/// Named constant for `expires_at`.
+pub const ARG_EXPIRES_AT: &str = "expires_at";
+/// Named constant for `schedule`.
+pub const ARG_SCHEDULE: &str = "schedule";
+/// Named constant for `runs`.
+pub const ARG_RUNS: &str = "runs";
+/// Named constant for `schedule_id`.
+pub const ARG_SCHEDULE_ID: &str = "schedule_id";
+/// Named constant for `era_id`.
+pub const ARG_ERA_ID: &str = "era_id";
+/// Named constant for `era_end_timestamp_millis`.
+pub const ARG_ERA_END_TIMESTAMP_MILLIS: &str = "era_end_timestamp_millis";
 
 /// Named constant for method `mint`.
 pub const METHOD_MINT: &str = "mint";
@@ -27,6 +41,16 @@ pub const METHOD_TRANSFER: &str = "transfer";
 pub const METHOD_READ_BASE_ROUND_REWARD: &str = "read_base_round_reward";
 /// Named constant for method `mint_into_existing_purse`.
 pub const METHOD_MINT_INTO_EXISTING_PURSE: &str = "mint_into_existing_purse";
+/// Named constant for method `approve_allowance`.
+pub const METHOD_APPROVE_ALLOWANCE: &str = "approve_allowance";
+/// Named constant for method `transfer_from_allowance`.
+pub const METHOD_TRANSFER_FROM_ALLOWANCE: &str = "transfer_from_allowance";
+/// Named constant for method `schedule_transfer`.
+pub const METHOD_SCHEDULE_TRANSFER: &str = "schedule_transfer";
+/// Named constant for method `cancel_scheduled_transfer`.
+pub const METHOD_CANCEL_SCHEDULED_TRANSFER: &str = "cancel_scheduled_transfer";
+/// Named constant for method `run_scheduled_transfers`.
+pub const METHOD_RUN_SCHEDULED_TRANSFERS: &str = "run_scheduled_transfers";
 
 /// Storage for mint contract hash.
 pub const HASH_KEY: &str = "mint_hash";
@@ -38,3 +62,18 @@ pub const BASE_ROUND_REWARD_KEY: &str = "mint_base_round_reward";
 pub const TOTAL_SUPPLY_KEY: &str = "total_supply";
 /// Storage for mint round seigniorage rate.
 pub const ROUND_SEIGNIORAGE_RATE_KEY: &str = "round_seigniorage_rate";
+/// Storage for the seed of the mint's allowances dictionary.
+pub const ALLOWANCES_KEY: &str = "allowances";
+/// Storage for the seed of the mint's scheduled transfers dictionary, keyed by schedule id.
+pub const SCHEDULED_TRANSFERS_KEY: &str = "scheduled_transfers";
+/// Storage for the seed of the dictionary indexing the ids of the pending scheduled transfers by
+/// the era or block time window in which they are next due.
+pub const SCHEDULED_TRANSFERS_DUE_KEY: &str = "scheduled_transfers_due";
+/// Storage for the first era and block time window not yet run by the step.
+pub const SCHEDULED_TRANSFERS_CURSOR_KEY: &str = "scheduled_transfers_cursor";
+/// Storage for the number of pending scheduled transfers.
+pub const SCHEDULED_TRANSFERS_PENDING_KEY: &str = "scheduled_transfers_pending";
+/// Storage for the seed of the dictionary counting the pending scheduled transfers of each owner.
+pub const SCHEDULED_TRANSFER_COUNTS_KEY: &str = "scheduled_transfer_counts";
+/// Storage for the id of the next scheduled transfer.
+pub const SCHEDULED_TRANSFER_NEXT_ID_KEY: &str = "scheduled_transfer_next_id";
@@ -3,9 +3,12 @@ use alloc::boxed::Box;
 use crate::{
     contracts::Parameters,
     system::mint::{
-        ARG_AMOUNT, ARG_ID, ARG_PURSE, ARG_SOURCE, ARG_TARGET, ARG_TO, METHOD_BALANCE,
-        METHOD_CREATE, METHOD_MINT, METHOD_MINT_INTO_EXISTING_PURSE, METHOD_READ_BASE_ROUND_REWARD,
-        METHOD_REDUCE_TOTAL_SUPPLY, METHOD_TRANSFER,
+        ARG_AMOUNT, ARG_ERA_END_TIMESTAMP_MILLIS, ARG_ERA_ID, ARG_EXPIRES_AT, ARG_ID, ARG_PURSE,
+        ARG_RUNS, ARG_SCHEDULE, ARG_SCHEDULE_ID, ARG_SOURCE, ARG_SPENDER, ARG_TARGET, ARG_TO,
+        METHOD_APPROVE_ALLOWANCE, METHOD_BALANCE, METHOD_CANCEL_SCHEDULED_TRANSFER, METHOD_CREATE,
+        METHOD_MINT, METHOD_MINT_INTO_EXISTING_PURSE, METHOD_READ_BASE_ROUND_REWARD,
+        METHOD_REDUCE_TOTAL_SUPPLY, METHOD_RUN_SCHEDULED_TRANSFERS, METHOD_SCHEDULE_TRANSFER,
+        METHOD_TRANSFER, METHOD_TRANSFER_FROM_ALLOWANCE,
     },
     CLType, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Parameter,
 };
@@ -98,5 +101,85 @@ pub fn mint_entry_points() -> EntryPoints {
     );
     entry_points.add_entry_point(entry_point);
 
+    let entry_point = EntryPoint::new(
+        METHOD_APPROVE_ALLOWANCE,
+        vec![
+            Parameter::new(ARG_PURSE, CLType::URef),
+            Parameter::new(ARG_SPENDER, CLType::Key),
+            Parameter::new(ARG_AMOUNT, CLType::U512),
+            Parameter::new(ARG_EXPIRES_AT, CLType::Option(Box::new(CLType::U64))),
+        ],
+        CLType::Result {
+            ok: Box::new(CLType::Unit),
+            err: Box::new(CLType::U8),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_TRANSFER_FROM_ALLOWANCE,
+        vec![
+            Parameter::new(ARG_SOURCE, CLType::ByteArray(32)),
+            Parameter::new(ARG_TARGET, CLType::URef),
+            Parameter::new(ARG_AMOUNT, CLType::U512),
+            Parameter::new(ARG_ID, CLType::Option(Box::new(CLType::U64))),
+        ],
+        CLType::Result {
+            ok: Box::new(CLType::Unit),
+            err: Box::new(CLType::U8),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_SCHEDULE_TRANSFER,
+        vec![
+            Parameter::new(ARG_SOURCE, CLType::URef),
+            Parameter::new(ARG_TARGET, CLType::ByteArray(32)),
+            Parameter::new(ARG_AMOUNT, CLType::U512),
+            Parameter::new(ARG_SCHEDULE, CLType::Any),
+            Parameter::new(ARG_RUNS, CLType::U32),
+            Parameter::new(ARG_ID, CLType::Option(Box::new(CLType::U64))),
+        ],
+        CLType::Result {
+            ok: Box::new(CLType::U64),
+            err: Box::new(CLType::U8),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_CANCEL_SCHEDULED_TRANSFER,
+        vec![Parameter::new(ARG_SCHEDULE_ID, CLType::U64)],
+        CLType::Result {
+            ok: Box::new(CLType::Unit),
+            err: Box::new(CLType::U8),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_RUN_SCHEDULED_TRANSFERS,
+        vec![
+            Parameter::new(ARG_ERA_ID, CLType::U64),
+            Parameter::new(ARG_ERA_END_TIMESTAMP_MILLIS, CLType::U64),
+        ],
+        CLType::Result {
+            ok: Box::new(CLType::List(Box::new(CLType::Any))),
+            err: Box::new(CLType::U8),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
     entry_points
 }
@@ -154,6 +154,55 @@ pub enum Error {
     /// assert_eq!(22, Error::DisabledUnrestrictedTransfers as u8);
     DisabledUnrestrictedTransfers = 22,
 
+    /// The spender holds no allowance over the source purse.
+    /// ```
+    /// # use casper_types::system::mint::Error;
+    /// assert_eq!(23, Error::AllowanceNotFound as u8);
+    /// ```
+    AllowanceNotFound = 23,
+
+    /// The spender's allowance over the source purse has expired.
+    /// ```
+    /// # use casper_types::system::mint::Error;
+    /// assert_eq!(24, Error::AllowanceExpired as u8);
+    /// ```
+    AllowanceExpired = 24,
+
+    /// The amount exceeds the spender's remaining allowance over the source purse.
+    /// ```
+    /// # use casper_types::system::mint::Error;
+    /// assert_eq!(25, Error::InsufficientAllowance as u8);
+    /// ```
+    InsufficientAllowance = 25,
+
+    /// Allowances can only be granted to accounts and contracts.
+    /// ```
+    /// # use casper_types::system::mint::Error;
+    /// assert_eq!(26, Error::InvalidAllowanceSpender as u8);
+    /// ```
+    InvalidAllowanceSpender = 26,
+
+    /// A scheduled transfer needs at least one run, and an interval between runs if it has more.
+    /// ```
+    /// # use casper_types::system::mint::Error;
+    /// assert_eq!(27, Error::InvalidTransferSchedule as u8);
+    /// ```
+    InvalidTransferSchedule = 27,
+
+    /// No scheduled transfer exists under the given id.
+    /// ```
+    /// # use casper_types::system::mint::Error;
+    /// assert_eq!(28, Error::ScheduledTransferNotFound as u8);
+    /// ```
+    ScheduledTransferNotFound = 28,
+
+    /// The caller already owns the maximum number of pending scheduled transfers.
+    /// ```
+    /// # use casper_types::system::mint::Error;
+    /// assert_eq!(29, Error::TooManyScheduledTransfers as u8);
+    /// ```
+    TooManyScheduledTransfers = 29,
+
     #[cfg(test)]
     #[doc(hidden)]
     Sentinel,
@@ -209,6 +258,17 @@ impl TryFrom<u8> for Error {
             d if d == Error::DisabledUnrestrictedTransfers as u8 => {
                 Ok(Error::DisabledUnrestrictedTransfers)
             }
+            d if d == Error::AllowanceNotFound as u8 => Ok(Error::AllowanceNotFound),
+            d if d == Error::AllowanceExpired as u8 => Ok(Error::AllowanceExpired),
+            d if d == Error::InsufficientAllowance as u8 => Ok(Error::InsufficientAllowance),
+            d if d == Error::InvalidAllowanceSpender as u8 => Ok(Error::InvalidAllowanceSpender),
+            d if d == Error::InvalidTransferSchedule as u8 => Ok(Error::InvalidTransferSchedule),
+            d if d == Error::ScheduledTransferNotFound as u8 => {
+                Ok(Error::ScheduledTransferNotFound)
+            }
+            d if d == Error::TooManyScheduledTransfers as u8 => {
+                Ok(Error::TooManyScheduledTransfers)
+            }
             _ => Err(TryFromU8ForError(())),
         }
     }
@@ -269,6 +329,13 @@ impl Display for Error {
             Error::DisabledUnrestrictedTransfers => {
                 formatter.write_str("Disabled unrestricted transfers")
             }
+            Error::AllowanceNotFound => formatter.write_str("Allowance not found"),
+            Error::AllowanceExpired => formatter.write_str("Allowance expired"),
+            Error::InsufficientAllowance => formatter.write_str("Insufficient allowance"),
+            Error::InvalidAllowanceSpender => formatter.write_str("Invalid allowance spender"),
+            Error::InvalidTransferSchedule => formatter.write_str("Invalid transfer schedule"),
+            Error::ScheduledTransferNotFound => formatter.write_str("Scheduled transfer not found"),
+            Error::TooManyScheduledTransfers => formatter.write_str("Too many scheduled transfers"),
             #[cfg(test)]
             Error::Sentinel => formatter.write_str("Sentinel error"),
         }
@@ -0,0 +1,610 @@
+//! Transfers scheduled to run at future eras or block times.
+// TODO - remove once schemars stops causing warning.
+#![allow(clippy::field_reassign_with_default)]
+
+use alloc::{string::String, vec::Vec};
+
+#[cfg(feature = "datasize")]
+use datasize::DataSize;
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    CLType, CLTyped, EraId, URef, U512,
+};
+
+const ERA_TAG: u8 = 0;
+const BLOCK_TIME_TAG: u8 = 1;
+
+/// The maximum number of pending scheduled transfers a single account can own.
+pub const MAX_SCHEDULED_TRANSFERS_PER_OWNER: u32 = 100;
+
+/// The maximum number of pending scheduled transfers across all accounts.
+pub const MAX_SCHEDULED_TRANSFERS: u32 = 10_000;
+
+/// The length of the block time windows by which the mint indexes the pending scheduled transfers
+/// along a [`TransferSchedule::BlockTime`].
+pub const SCHEDULED_TRANSFER_WINDOW_MILLIS: u64 = 60 * 60 * 1000;
+
+/// When a scheduled transfer runs.
+///
+/// Scheduled transfers are run by the step at the end of each era.  All the runs which fell due
+/// since the previous step are paid at once, so an interval shorter than an era pays several runs
+/// per step.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "datasize", derive(DataSize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub enum TransferSchedule {
+    /// The transfer runs when `era_id` starts, then every `interval_eras` eras.
+    Era {
+        /// The era at the start of which the next run happens.
+        era_id: EraId,
+        /// The number of eras between two runs.
+        interval_eras: u64,
+    },
+    /// The transfer runs at the end of the first era ending at or after `timestamp_millis`, then
+    /// every `interval_millis` milliseconds.
+    BlockTime {
+        /// The block time from which the next run is due.
+        timestamp_millis: u64,
+        /// The time between two runs.
+        interval_millis: u64,
+    },
+}
+
+impl TransferSchedule {
+    /// Returns `true` if a transfer can run `runs` times along this schedule.
+    pub fn is_valid(&self, runs: u32) -> bool {
+        let interval = match self {
+            TransferSchedule::Era { interval_eras, .. } => *interval_eras,
+            TransferSchedule::BlockTime {
+                interval_millis, ..
+            } => *interval_millis,
+        };
+        runs == 1 || (runs > 1 && interval > 0)
+    }
+
+    /// Returns `true` if the next run is due in the step starting `next_era_id` at
+    /// `era_end_timestamp_millis`.
+    pub fn is_due(&self, next_era_id: EraId, era_end_timestamp_millis: u64) -> bool {
+        match self {
+            TransferSchedule::Era { era_id, .. } => next_era_id >= *era_id,
+            TransferSchedule::BlockTime {
+                timestamp_millis, ..
+            } => era_end_timestamp_millis >= *timestamp_millis,
+        }
+    }
+
+    /// Returns the number of runs due in the step starting `next_era_id` at
+    /// `era_end_timestamp_millis`, counting from the next run and capped at `max_runs`.
+    pub fn due_runs(
+        &self,
+        next_era_id: EraId,
+        era_end_timestamp_millis: u64,
+        max_runs: u32,
+    ) -> u32 {
+        let (start, now, interval) = match *self {
+            TransferSchedule::Era {
+                era_id,
+                interval_eras,
+            } => (era_id.value(), next_era_id.value(), interval_eras),
+            TransferSchedule::BlockTime {
+                timestamp_millis,
+                interval_millis,
+            } => (timestamp_millis, era_end_timestamp_millis, interval_millis),
+        };
+        if now < start || max_runs == 0 {
+            return 0;
+        }
+        let due_runs = match (now - start).checked_div(interval) {
+            Some(elapsed_intervals) => elapsed_intervals.saturating_add(1),
+            None => 1,
+        };
+        u32::try_from(due_runs).unwrap_or(u32::MAX).min(max_runs)
+    }
+
+    /// Returns the schedule of the run following the next one, or `None` on overflow.
+    pub fn next(&self) -> Option<Self> {
+        self.skip(1)
+    }
+
+    /// Returns the schedule of the run following the next `runs` ones, or `None` on overflow.
+    pub fn skip(&self, runs: u32) -> Option<Self> {
+        match *self {
+            TransferSchedule::Era {
+                era_id,
+                interval_eras,
+            } => Some(TransferSchedule::Era {
+                era_id: era_id.checked_add(interval_eras.checked_mul(u64::from(runs))?)?,
+                interval_eras,
+            }),
+            TransferSchedule::BlockTime {
+                timestamp_millis,
+                interval_millis,
+            } => Some(TransferSchedule::BlockTime {
+                timestamp_millis: timestamp_millis
+                    .checked_add(interval_millis.checked_mul(u64::from(runs))?)?,
+                interval_millis,
+            }),
+        }
+    }
+}
+
+impl CLTyped for TransferSchedule {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for TransferSchedule {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + match self {
+                TransferSchedule::Era {
+                    era_id,
+                    interval_eras,
+                } => era_id.serialized_length() + interval_eras.serialized_length(),
+                TransferSchedule::BlockTime {
+                    timestamp_millis,
+                    interval_millis,
+                } => timestamp_millis.serialized_length() + interval_millis.serialized_length(),
+            }
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        match self {
+            TransferSchedule::Era {
+                era_id,
+                interval_eras,
+            } => {
+                writer.push(ERA_TAG);
+                era_id.write_bytes(writer)?;
+                interval_eras.write_bytes(writer)
+            }
+            TransferSchedule::BlockTime {
+                timestamp_millis,
+                interval_millis,
+            } => {
+                writer.push(BLOCK_TIME_TAG);
+                timestamp_millis.write_bytes(writer)?;
+                interval_millis.write_bytes(writer)
+            }
+        }
+    }
+}
+
+impl FromBytes for TransferSchedule {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, bytes): (u8, _) = FromBytes::from_bytes(bytes)?;
+        match tag {
+            ERA_TAG => {
+                let (era_id, bytes) = FromBytes::from_bytes(bytes)?;
+                let (interval_eras, bytes) = FromBytes::from_bytes(bytes)?;
+                Ok((
+                    TransferSchedule::Era {
+                        era_id,
+                        interval_eras,
+                    },
+                    bytes,
+                ))
+            }
+            BLOCK_TIME_TAG => {
+                let (timestamp_millis, bytes) = FromBytes::from_bytes(bytes)?;
+                let (interval_millis, bytes) = FromBytes::from_bytes(bytes)?;
+                Ok((
+                    TransferSchedule::BlockTime {
+                        timestamp_millis,
+                        interval_millis,
+                    },
+                    bytes,
+                ))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+/// A transfer held by the mint, paying `amount` to the main purse of `target` on each run out of
+/// an escrow purse funded up front.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ScheduledTransfer {
+    owner: AccountHash,
+    escrow_purse: URef,
+    target: AccountHash,
+    amount: U512,
+    schedule: TransferSchedule,
+    remaining_runs: u32,
+    id: Option<u64>,
+}
+
+impl ScheduledTransfer {
+    /// Creates a new [`ScheduledTransfer`].
+    pub fn new(
+        owner: AccountHash,
+        escrow_purse: URef,
+        target: AccountHash,
+        amount: U512,
+        schedule: TransferSchedule,
+        remaining_runs: u32,
+        id: Option<u64>,
+    ) -> Self {
+        ScheduledTransfer {
+            owner,
+            escrow_purse,
+            target,
+            amount,
+            schedule,
+            remaining_runs,
+            id,
+        }
+    }
+
+    /// Returns the account which scheduled the transfer and can cancel it.
+    pub fn owner(&self) -> AccountHash {
+        self.owner
+    }
+
+    /// Returns the purse holding the funds of the remaining runs.
+    pub fn escrow_purse(&self) -> URef {
+        self.escrow_purse
+    }
+
+    /// Returns the account paid on each run.
+    pub fn target(&self) -> AccountHash {
+        self.target
+    }
+
+    /// Returns the amount paid on each run.
+    pub fn amount(&self) -> U512 {
+        self.amount
+    }
+
+    /// Returns the schedule of the next run.
+    pub fn schedule(&self) -> TransferSchedule {
+        self.schedule
+    }
+
+    /// Returns the number of runs left.
+    pub fn remaining_runs(&self) -> u32 {
+        self.remaining_runs
+    }
+
+    /// Returns the user-defined id attached to the transfer.
+    pub fn id(&self) -> Option<u64> {
+        self.id
+    }
+
+    /// Cancels the remaining runs of the transfer.
+    pub fn cancel(&mut self) {
+        self.remaining_runs = 0;
+    }
+
+    /// Records a run of the transfer, moving its schedule on to the following run.
+    pub fn record_run(&mut self) {
+        self.record_runs(1)
+    }
+
+    /// Records `runs` runs of the transfer, moving its schedule on to the run following them.
+    pub fn record_runs(&mut self, runs: u32) {
+        self.remaining_runs = self.remaining_runs.saturating_sub(runs);
+        match self.schedule.skip(runs) {
+            Some(schedule) => self.schedule = schedule,
+            None => self.remaining_runs = 0,
+        }
+    }
+}
+
+impl CLTyped for ScheduledTransfer {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for ScheduledTransfer {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.owner.serialized_length()
+            + self.escrow_purse.serialized_length()
+            + self.target.serialized_length()
+            + self.amount.serialized_length()
+            + self.schedule.serialized_length()
+            + self.remaining_runs.serialized_length()
+            + self.id.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.owner.write_bytes(writer)?;
+        self.escrow_purse.write_bytes(writer)?;
+        self.target.write_bytes(writer)?;
+        self.amount.write_bytes(writer)?;
+        self.schedule.write_bytes(writer)?;
+        self.remaining_runs.write_bytes(writer)?;
+        self.id.write_bytes(writer)
+    }
+}
+
+impl FromBytes for ScheduledTransfer {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (owner, bytes) = FromBytes::from_bytes(bytes)?;
+        let (escrow_purse, bytes) = FromBytes::from_bytes(bytes)?;
+        let (target, bytes) = FromBytes::from_bytes(bytes)?;
+        let (amount, bytes) = FromBytes::from_bytes(bytes)?;
+        let (schedule, bytes) = FromBytes::from_bytes(bytes)?;
+        let (remaining_runs, bytes) = FromBytes::from_bytes(bytes)?;
+        let (id, bytes) = FromBytes::from_bytes(bytes)?;
+        Ok((
+            ScheduledTransfer {
+                owner,
+                escrow_purse,
+                target,
+                amount,
+                schedule,
+                remaining_runs,
+                id,
+            },
+            bytes,
+        ))
+    }
+}
+
+/// The outcome of a run of a scheduled transfer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "datasize", derive(DataSize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct ScheduledTransferResult {
+    /// The id under which the mint holds the scheduled transfer.
+    pub schedule_id: u64,
+    /// The account paid by the run.
+    pub target: AccountHash,
+    /// The amount paid by the run, covering every run which fell due since the previous step.
+    pub amount: U512,
+    /// The reason the run failed, if it did.  The funds of a failed run remain in escrow until
+    /// the scheduled transfer is cancelled or completes, or are kept there if refunding them once
+    /// the scheduled transfer completed failed.
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl CLTyped for ScheduledTransferResult {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for ScheduledTransferResult {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.schedule_id.serialized_length()
+            + self.target.serialized_length()
+            + self.amount.serialized_length()
+            + self.error.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.schedule_id.write_bytes(writer)?;
+        self.target.write_bytes(writer)?;
+        self.amount.write_bytes(writer)?;
+        self.error.write_bytes(writer)
+    }
+}
+
+impl FromBytes for ScheduledTransferResult {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (schedule_id, bytes) = FromBytes::from_bytes(bytes)?;
+        let (target, bytes) = FromBytes::from_bytes(bytes)?;
+        let (amount, bytes) = FromBytes::from_bytes(bytes)?;
+        let (error, bytes) = FromBytes::from_bytes(bytes)?;
+        Ok((
+            ScheduledTransferResult {
+                schedule_id,
+                target,
+                amount,
+                error,
+            },
+            bytes,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use crate::{account::AccountHash, bytesrepr, AccessRights, EraId, URef, U512};
+
+    use super::{ScheduledTransfer, ScheduledTransferResult, TransferSchedule};
+
+    #[test]
+    fn serialization_roundtrip() {
+        let era_schedule = TransferSchedule::Era {
+            era_id: EraId::new(10),
+            interval_eras: 2,
+        };
+        let block_time_schedule = TransferSchedule::BlockTime {
+            timestamp_millis: 1_000,
+            interval_millis: 0,
+        };
+        bytesrepr::test_serialization_roundtrip(&era_schedule);
+        bytesrepr::test_serialization_roundtrip(&block_time_schedule);
+
+        let scheduled_transfer = ScheduledTransfer::new(
+            AccountHash::new([1; 32]),
+            URef::new([2; 32], AccessRights::READ_ADD_WRITE),
+            AccountHash::new([3; 32]),
+            U512::from(100),
+            era_schedule,
+            12,
+            Some(7),
+        );
+        bytesrepr::test_serialization_roundtrip(&scheduled_transfer);
+
+        let result = ScheduledTransferResult {
+            schedule_id: 1,
+            target: AccountHash::new([3; 32]),
+            amount: U512::from(100),
+            error: Some("Destination not found".to_string()),
+        };
+        bytesrepr::test_serialization_roundtrip(&result);
+    }
+
+    #[test]
+    fn should_roundtrip_results_through_bincode() {
+        let mut result = ScheduledTransferResult {
+            schedule_id: 1,
+            target: AccountHash::new([3; 32]),
+            amount: U512::from(100),
+            error: None,
+        };
+        let serialized = bincode::serialize(&result).unwrap();
+        let deserialized: ScheduledTransferResult = bincode::deserialize(&serialized).unwrap();
+        assert_eq!(deserialized, result);
+
+        result.error = Some("Destination not found".to_string());
+        let serialized = bincode::serialize(&result).unwrap();
+        let deserialized: ScheduledTransferResult = bincode::deserialize(&serialized).unwrap();
+        assert_eq!(deserialized, result);
+    }
+
+    #[test]
+    fn should_validate_schedule_interval() {
+        let schedule = TransferSchedule::Era {
+            era_id: EraId::new(1),
+            interval_eras: 0,
+        };
+        assert!(schedule.is_valid(1));
+        assert!(!schedule.is_valid(0));
+        assert!(!schedule.is_valid(2));
+        let schedule = TransferSchedule::BlockTime {
+            timestamp_millis: 0,
+            interval_millis: 1,
+        };
+        assert!(schedule.is_valid(2));
+    }
+
+    #[test]
+    fn should_be_due_from_scheduled_era_or_block_time() {
+        let schedule = TransferSchedule::Era {
+            era_id: EraId::new(5),
+            interval_eras: 1,
+        };
+        assert!(!schedule.is_due(EraId::new(4), u64::MAX));
+        assert!(schedule.is_due(EraId::new(5), 0));
+        let schedule = TransferSchedule::BlockTime {
+            timestamp_millis: 1_000,
+            interval_millis: 1,
+        };
+        assert!(!schedule.is_due(EraId::new(u64::MAX), 999));
+        assert!(schedule.is_due(EraId::new(0), 1_000));
+    }
+
+    #[test]
+    fn should_count_every_due_run() {
+        let schedule = TransferSchedule::Era {
+            era_id: EraId::new(5),
+            interval_eras: 2,
+        };
+        assert_eq!(schedule.due_runs(EraId::new(4), u64::MAX, 10), 0);
+        assert_eq!(schedule.due_runs(EraId::new(6), 0, 10), 1);
+        assert_eq!(schedule.due_runs(EraId::new(9), 0, 10), 3);
+        assert_eq!(schedule.due_runs(EraId::new(9), 0, 2), 2);
+        let schedule = TransferSchedule::BlockTime {
+            timestamp_millis: 1_000,
+            interval_millis: 10,
+        };
+        assert_eq!(schedule.due_runs(EraId::new(0), 1_095, 100), 10);
+        assert_eq!(
+            schedule.due_runs(EraId::new(0), u64::MAX, u32::MAX),
+            u32::MAX
+        );
+        let schedule = TransferSchedule::BlockTime {
+            timestamp_millis: 0,
+            interval_millis: 0,
+        };
+        assert_eq!(schedule.due_runs(EraId::new(0), 1_000, 1), 1);
+    }
+
+    #[test]
+    fn should_record_runs_until_finished() {
+        let mut scheduled_transfer = ScheduledTransfer::new(
+            AccountHash::new([1; 32]),
+            URef::new([2; 32], AccessRights::READ_ADD_WRITE),
+            AccountHash::new([3; 32]),
+            U512::one(),
+            TransferSchedule::Era {
+                era_id: EraId::new(1),
+                interval_eras: 3,
+            },
+            2,
+            None,
+        );
+        scheduled_transfer.record_run();
+        assert_eq!(scheduled_transfer.remaining_runs(), 1);
+        assert_eq!(
+            scheduled_transfer.schedule(),
+            TransferSchedule::Era {
+                era_id: EraId::new(4),
+                interval_eras: 3,
+            }
+        );
+        scheduled_transfer.record_run();
+        assert_eq!(scheduled_transfer.remaining_runs(), 0);
+
+        let mut recurring_transfer = ScheduledTransfer::new(
+            AccountHash::new([1; 32]),
+            URef::new([2; 32], AccessRights::READ_ADD_WRITE),
+            AccountHash::new([3; 32]),
+            U512::one(),
+            TransferSchedule::BlockTime {
+                timestamp_millis: 1_000,
+                interval_millis: 10,
+            },
+            5,
+            None,
+        );
+        recurring_transfer.record_runs(3);
+        assert_eq!(recurring_transfer.remaining_runs(), 2);
+        assert_eq!(
+            recurring_transfer.schedule(),
+            TransferSchedule::BlockTime {
+                timestamp_millis: 1_030,
+                interval_millis: 10,
+            }
+        );
+
+        let mut overflowing_transfer = ScheduledTransfer::new(
+            AccountHash::new([1; 32]),
+            URef::new([2; 32], AccessRights::READ_ADD_WRITE),
+            AccountHash::new([3; 32]),
+            U512::one(),
+            TransferSchedule::BlockTime {
+                timestamp_millis: u64::MAX,
+                interval_millis: 1,
+            },
+            5,
+            None,
+        );
+        overflowing_transfer.record_run();
+        assert_eq!(overflowing_transfer.remaining_runs(), 0);
+    }
+}
@@ -0,0 +1,53 @@
+use clap::ArgMatches;
+
+use casper_engine_test_support::LmdbWasmTestBuilder;
+use casper_execution_engine::{
+    core::engine_state::{StateDiffRequest, StateDiffResult},
+    shared::newtypes::CorrelationId,
+};
+use casper_types::bytesrepr::ToBytes;
+
+use crate::utils::hash_from_str;
+
+/// Prints the differences between two global state roots in a format ready for inclusion in a
+/// TOML file.
+pub(crate) fn print_state_diff(matches: &ArgMatches<'_>) {
+    let data_dir = matches.value_of("data_dir").unwrap_or(".");
+    let old_state_hash = hash_from_str(matches.value_of("old_hash").unwrap());
+    let new_state_hash = hash_from_str(matches.value_of("new_hash").unwrap());
+
+    let builder = LmdbWasmTestBuilder::open_raw(data_dir, Default::default(), new_state_hash);
+    let result = builder
+        .get_engine_state()
+        .diff_state_roots(
+            CorrelationId::new(),
+            StateDiffRequest::new(old_state_hash, new_state_hash),
+            |trie_diff| {
+                println!("[[entries]]");
+                println!("key = \"{}\"", trie_diff.key().to_formatted_string());
+                if let Some(old_value) = trie_diff.old_value() {
+                    println!(
+                        "old_value = \"{}\"",
+                        base64::encode(old_value.to_bytes().unwrap())
+                    );
+                }
+                if let Some(new_value) = trie_diff.new_value() {
+                    println!(
+                        "new_value = \"{}\"",
+                        base64::encode(new_value.to_bytes().unwrap())
+                    );
+                }
+                println!();
+            },
+        )
+        .unwrap_or_else(|error| panic!("failed to diff state roots: {}", error));
+
+    match result {
+        StateDiffResult::RootNotFound(state_hash) => {
+            panic!("state root hash not found: {}", state_hash)
+        }
+        StateDiffResult::Success { changed_keys } => {
+            eprintln!("{} keys changed", changed_keys)
+        }
+    }
+}